@@ -8,27 +8,72 @@ use std::time::Instant;
 use web_time::Instant;
 
 use eframe::egui;
-use grapho_core::{MeshEvalState, Project};
+use grapho_core::{MeshEvalState, Project, UvLayout};
 use render::{RenderScene, ViewportRenderer};
 use tracing_subscriber::filter::LevelFilter;
 
 use crate::node_graph;
 
+mod accessibility;
+mod actions;
+mod annotations;
+#[cfg(not(target_arch = "wasm32"))]
+mod background_eval;
+mod bookmarks;
+mod collab;
+mod command_palette;
+#[cfg(not(target_arch = "wasm32"))]
+mod crash;
+mod dependencies;
+mod dope_sheet;
 mod eval;
+mod examples;
+mod flipbook;
+mod graph_stats;
+mod inspect_eval;
 mod io;
 mod logging;
+mod node_audit;
+mod node_help;
 mod node_info;
+mod node_tests;
+mod onboarding;
+#[cfg(not(target_arch = "wasm32"))]
+mod paths;
+mod project_params;
+mod seed_preview;
+mod selection_outline;
+#[cfg(feature = "space-mouse")]
+mod space_mouse;
 mod spreadsheet;
+#[cfg(not(target_arch = "wasm32"))]
+mod stats_recorder;
+mod status_bar;
 mod ui;
 mod undo;
+mod uv_view;
+mod viewer_mode;
 mod viewport;
 mod wrangle_help;
 
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use crash::install_crash_handler;
 pub(crate) use logging::setup_tracing;
 pub(crate) use logging::ConsoleBuffer;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) use paths::migrate_legacy_files;
 
+use accessibility::FocusPanel;
+use annotations::AnnotationTool;
+use collab::{CollabSession, DEFAULT_SYNC_ADDRESS};
+use command_palette::CommandPalette;
+use flipbook::Flipbook;
 use logging::level_filter_to_u8;
+use node_help::NodeHelpPanel;
 use node_info::NodeInfoPanel;
+use node_tests::NodeTestsPanel;
+use onboarding::OnboardingTour;
+use project_params::ProjectParamDraft;
 use undo::{UndoSnapshot, UndoStack};
 use wrangle_help::WrangleHelpPanel;
 
@@ -40,7 +85,19 @@ pub(crate) struct GraphoApp {
     log_level_state: Arc<AtomicU8>,
     viewport_renderer: Option<ViewportRenderer>,
     pending_scene: Option<RenderScene>,
-    eval_state: MeshEvalState,
+    eval_state: Arc<MeshEvalState>,
+    inspect_eval: inspect_eval::InspectEval,
+    seed_preview: seed_preview::SeedPreview,
+    node_audit: node_audit::NodeAudit,
+    selection_outline: selection_outline::SelectionOutline,
+    #[cfg(not(target_arch = "wasm32"))]
+    background_eval: Option<background_eval::BackgroundEval>,
+    #[cfg(not(target_arch = "wasm32"))]
+    stats_recorder: Option<stats_recorder::StatsRecorder>,
+    #[cfg(not(target_arch = "wasm32"))]
+    crash_snapshot_at: Option<Instant>,
+    #[cfg(not(target_arch = "wasm32"))]
+    crash_restore: Option<crash::CrashRestore>,
     last_eval_report: Option<grapho_core::EvalReport>,
     last_eval_ms: Option<f32>,
     eval_dirty: bool,
@@ -48,13 +105,39 @@ pub(crate) struct GraphoApp {
     node_graph: node_graph::NodeGraphState,
     last_display_state: DisplayState,
     last_node_graph_rect: Option<egui::Rect>,
+    last_viewport_rect: Option<egui::Rect>,
     last_selected_node: Option<grapho_core::NodeId>,
     info_panel: Option<NodeInfoPanel>,
     held_info_panel: Option<NodeInfoPanel>,
     wrangle_help_panel: Option<WrangleHelpPanel>,
+    node_help_panel: Option<NodeHelpPanel>,
+    node_tests_panel: Option<NodeTestsPanel>,
+    onboarding: Option<OnboardingTour>,
     undo_stack: UndoStack,
     pending_undo: Option<UndoSnapshot>,
     spreadsheet_domain: grapho_core::AttributeDomain,
+    project_param_draft: ProjectParamDraft,
+    last_display_counts: Option<(usize, usize)>,
+    debug_channel_names: Vec<String>,
+    annotation_tool: AnnotationTool,
+    last_saved: Option<Instant>,
+    surface_shader_draft: Option<String>,
+    false_color_range: Option<[f32; 2]>,
+    uv_layout_cache: Option<(u64, Option<UvLayout>)>,
+    playing: bool,
+    flipbook: Flipbook,
+    command_palette: Option<CommandPalette>,
+    script_shortcuts: Vec<(egui::KeyboardShortcut, actions::AppAction)>,
+    collab: Option<CollabSession>,
+    collab_address: String,
+    viewer_mode: bool,
+    safe_mode: bool,
+    focus_panel: Option<FocusPanel>,
+    focus_request: bool,
+    applied_theme: Option<grapho_core::UiTheme>,
+    touch_input: bool,
+    #[cfg(feature = "space-mouse")]
+    space_mouse: Option<space_mouse::SpaceMouse>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
@@ -73,7 +156,19 @@ impl GraphoApp {
             log_level_state,
             viewport_renderer: None,
             pending_scene: None,
-            eval_state: MeshEvalState::new(),
+            eval_state: Arc::new(MeshEvalState::new()),
+            inspect_eval: inspect_eval::InspectEval::default(),
+            seed_preview: seed_preview::SeedPreview::default(),
+            node_audit: node_audit::NodeAudit::default(),
+            selection_outline: selection_outline::SelectionOutline::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            background_eval: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            stats_recorder: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_snapshot_at: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            crash_restore: None,
             last_eval_report: None,
             last_eval_ms: None,
             eval_dirty: false,
@@ -81,13 +176,39 @@ impl GraphoApp {
             node_graph: node_graph::NodeGraphState::default(),
             last_display_state: DisplayState::Ok,
             last_node_graph_rect: None,
+            last_viewport_rect: None,
             last_selected_node: None,
             info_panel: None,
             held_info_panel: None,
             wrangle_help_panel: None,
+            node_help_panel: None,
+            node_tests_panel: None,
+            onboarding: None,
             undo_stack: UndoStack::new(),
             pending_undo: None,
             spreadsheet_domain: grapho_core::AttributeDomain::Point,
+            project_param_draft: ProjectParamDraft::default(),
+            last_display_counts: None,
+            debug_channel_names: Vec::new(),
+            annotation_tool: AnnotationTool::default(),
+            last_saved: None,
+            surface_shader_draft: None,
+            false_color_range: None,
+            uv_layout_cache: None,
+            playing: false,
+            flipbook: Flipbook::default(),
+            command_palette: None,
+            script_shortcuts: Vec::new(),
+            collab: None,
+            collab_address: DEFAULT_SYNC_ADDRESS.to_string(),
+            viewer_mode: false,
+            safe_mode: false,
+            focus_panel: None,
+            focus_request: false,
+            applied_theme: None,
+            touch_input: false,
+            #[cfg(feature = "space-mouse")]
+            space_mouse: space_mouse::SpaceMouse::open(),
         }
     }
 
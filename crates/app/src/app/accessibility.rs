@@ -0,0 +1,222 @@
+use eframe::egui::{self, Color32, Key, Modifiers, Stroke};
+use grapho_core::UiTheme;
+
+use super::GraphoApp;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum FocusPanel {
+    MenuBar,
+    Viewport,
+    Spreadsheet,
+    NodeGraph,
+    Parameters,
+}
+
+const FOCUS_ORDER: [FocusPanel; 5] = [
+    FocusPanel::MenuBar,
+    FocusPanel::Viewport,
+    FocusPanel::Spreadsheet,
+    FocusPanel::NodeGraph,
+    FocusPanel::Parameters,
+];
+
+// Alt+letter opens the top-level menu of the same initial.
+const MENU_MNEMONICS: [Key; 3] = [Key::F, Key::S, Key::H];
+
+impl FocusPanel {
+    fn label(self) -> &'static str {
+        match self {
+            FocusPanel::MenuBar => "Menu bar",
+            FocusPanel::Viewport => "3D viewport",
+            FocusPanel::Spreadsheet => "Spreadsheet",
+            FocusPanel::NodeGraph => "Node graph",
+            FocusPanel::Parameters => "Parameters",
+        }
+    }
+
+    fn focus_id(self) -> egui::Id {
+        egui::Id::new(("panel_focus", self.label()))
+    }
+}
+
+pub(super) fn take_menu_mnemonic(ctx: &egui::Context) -> Option<Key> {
+    ctx.input_mut(|i| {
+        MENU_MNEMONICS
+            .into_iter()
+            .find(|key| i.consume_key(Modifiers::ALT, *key))
+    })
+}
+
+pub(super) fn open_menu_from_keyboard(response: &egui::Response) {
+    response.request_focus();
+    egui::Popup::open_id(&response.ctx, egui::Popup::default_response_id(response));
+}
+
+// Panel anchors take focus so shortcuts keep working, unlike text fields.
+pub(super) fn panel_has_focus(ctx: &egui::Context) -> bool {
+    ctx.memory(|mem| mem.focused())
+        .is_some_and(|id| FOCUS_ORDER.iter().any(|panel| panel.focus_id() == id))
+}
+
+pub(super) fn toolbar_toggle(ui: &mut egui::Ui, short: &str, label: &str, value: &mut bool) {
+    let response = ui
+        .add(egui::Button::new(short).selected(*value))
+        .on_hover_text(label);
+    if response.clicked() {
+        *value = !*value;
+    }
+    response.widget_info(|| {
+        egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, *value, label)
+    });
+}
+
+pub(super) fn theme_visuals(theme: UiTheme) -> egui::Visuals {
+    match theme {
+        UiTheme::Dark => egui::Visuals::dark(),
+        UiTheme::HighContrast => high_contrast_visuals(),
+    }
+}
+
+pub(super) fn panel_fill(theme: UiTheme, fill: Color32) -> Color32 {
+    match theme {
+        UiTheme::Dark => fill,
+        UiTheme::HighContrast => Color32::BLACK,
+    }
+}
+
+fn high_contrast_visuals() -> egui::Visuals {
+    let accent = Color32::from_rgb(255, 210, 0);
+    let mut visuals = egui::Visuals::dark();
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(24);
+    visuals.window_stroke = Stroke::new(1.5, Color32::WHITE);
+    visuals.hyperlink_color = accent;
+    visuals.selection.bg_fill = Color32::from_rgb(0, 90, 200);
+    visuals.selection.stroke = Stroke::new(2.0, accent);
+    for widget in [
+        &mut visuals.widgets.noninteractive,
+        &mut visuals.widgets.inactive,
+        &mut visuals.widgets.hovered,
+        &mut visuals.widgets.active,
+        &mut visuals.widgets.open,
+    ] {
+        widget.fg_stroke = Stroke::new(1.5, Color32::WHITE);
+        widget.bg_stroke = Stroke::new(1.0, Color32::WHITE);
+        widget.bg_fill = Color32::BLACK;
+        widget.weak_bg_fill = Color32::BLACK;
+    }
+    visuals.widgets.noninteractive.bg_stroke = Stroke::new(1.0, Color32::from_gray(160));
+    visuals.widgets.hovered.bg_stroke = Stroke::new(2.0, accent);
+    visuals.widgets.active.bg_stroke = Stroke::new(2.0, accent);
+    visuals.widgets.active.bg_fill = Color32::from_gray(48);
+    visuals.widgets.open.bg_fill = Color32::from_gray(32);
+    visuals
+}
+
+impl GraphoApp {
+    pub(super) fn apply_theme(&mut self, ctx: &egui::Context) {
+        let theme = self.project.settings.theme;
+        if self.applied_theme == Some(theme) {
+            return;
+        }
+        ctx.set_visuals(theme_visuals(theme));
+        self.node_graph
+            .set_high_contrast(theme == UiTheme::HighContrast);
+        self.applied_theme = Some(theme);
+    }
+
+    pub(super) fn toggle_high_contrast(&mut self) {
+        let theme = &mut self.project.settings.theme;
+        *theme = match *theme {
+            UiTheme::Dark => UiTheme::HighContrast,
+            UiTheme::HighContrast => UiTheme::Dark,
+        };
+    }
+
+    pub(super) fn cycle_panel_focus(&mut self, forward: bool) {
+        let panels: Vec<FocusPanel> = FOCUS_ORDER
+            .into_iter()
+            .filter(|panel| self.panel_visible(*panel))
+            .collect();
+        let current = self
+            .focus_panel
+            .and_then(|panel| panels.iter().position(|candidate| *candidate == panel));
+        let next = match (current, forward) {
+            (Some(index), true) => (index + 1) % panels.len(),
+            (Some(index), false) => (index + panels.len() - 1) % panels.len(),
+            (None, true) => 0,
+            (None, false) => panels.len() - 1,
+        };
+        self.focus_panel = Some(panels[next]);
+        self.focus_request = true;
+    }
+
+    fn panel_visible(&self, panel: FocusPanel) -> bool {
+        let panels = &self.project.settings.panels;
+        match panel {
+            FocusPanel::Spreadsheet => panels.show_spreadsheet,
+            FocusPanel::Parameters => panels.show_inspector,
+            FocusPanel::MenuBar | FocusPanel::Viewport | FocusPanel::NodeGraph => true,
+        }
+    }
+
+    pub(super) fn take_focus_request(&mut self, panel: FocusPanel) -> bool {
+        if self.focus_request && self.focus_panel == Some(panel) {
+            self.focus_request = false;
+            return true;
+        }
+        false
+    }
+
+    pub(super) fn panel_focus_anchor(
+        &mut self,
+        ui: &mut egui::Ui,
+        panel: FocusPanel,
+        rect: egui::Rect,
+    ) -> egui::Response {
+        let response = ui.interact(
+            rect,
+            panel.focus_id(),
+            egui::Sense::focusable_noninteractive(),
+        );
+        response.widget_info(|| {
+            egui::WidgetInfo::labeled(egui::WidgetType::Other, true, panel.label())
+        });
+        if self.take_focus_request(panel) {
+            response.request_focus();
+        }
+        if response.has_focus() {
+            self.focus_panel = Some(panel);
+            if matches!(panel, FocusPanel::Viewport | FocusPanel::NodeGraph) {
+                ui.memory_mut(|mem| {
+                    mem.set_focus_lock_filter(
+                        response.id,
+                        egui::EventFilter {
+                            tab: panel == FocusPanel::NodeGraph,
+                            horizontal_arrows: true,
+                            vertical_arrows: true,
+                            escape: false,
+                        },
+                    )
+                });
+            }
+        }
+        response
+    }
+}
+
+pub(super) fn paint_focus_ring(ui: &egui::Ui, response: &egui::Response) {
+    if !response.has_focus() {
+        return;
+    }
+    let stroke = ui.visuals().selection.stroke;
+    ui.painter().rect_stroke(
+        response.rect.shrink(1.0),
+        0.0,
+        Stroke::new(stroke.width.max(2.0), stroke.color),
+        egui::StrokeKind::Inside,
+    );
+}
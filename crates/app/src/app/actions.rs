@@ -0,0 +1,460 @@
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+
+use grapho_core::{ScriptShortcut, StandardView};
+
+use super::accessibility::panel_has_focus;
+use super::command_palette::CommandPalette;
+use super::node_tests::NodeTestsPanel;
+use super::onboarding::OnboardingTour;
+use super::GraphoApp;
+use crate::node_graph::NodeAlign;
+
+const COMMAND_SHIFT: Modifiers = Modifiers::COMMAND.plus(Modifiers::SHIFT);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum AppAction {
+    NewProject,
+    OpenProject,
+    SaveProject,
+    SaveProjectAs,
+    ExportGraphImage,
+    Undo,
+    Redo,
+    RecomputeNow,
+    CreateDemoGraph,
+    TogglePlayback,
+    GoToStart,
+    ClearFlipbook,
+    ToggleGraphSnap,
+    AlignNodesLeft,
+    AlignNodesTop,
+    DistributeNodesHorizontally,
+    DistributeNodesVertically,
+    StraightenWires,
+    FrameAllNodes,
+    FrameSelectedNodes,
+    ToggleParameters,
+    ToggleSpreadsheet,
+    ToggleProjectParams,
+    ToggleUvView,
+    ToggleDopeSheet,
+    ToggleGraphStats,
+    ToggleDependencies,
+    ToggleNodeAudit,
+    ToggleDebug,
+    ToggleConsole,
+    ResetLayout,
+    FocusNextPanel,
+    FocusPreviousPanel,
+    ToggleHighContrast,
+    ToggleFlyCamera,
+    StandardView(StandardView),
+    FrameViewportAll,
+    FrameViewportSelected,
+    AddCameraBookmark,
+    CommandPalette,
+    ShowTour,
+    RunNodeTests,
+}
+
+pub(super) struct ActionDef {
+    pub(super) action: AppAction,
+    pub(super) category: &'static str,
+    pub(super) name: &'static str,
+    pub(super) shortcuts: &'static [KeyboardShortcut],
+    pub(super) desktop_only: bool,
+}
+
+const fn action(
+    action: AppAction,
+    category: &'static str,
+    name: &'static str,
+    shortcuts: &'static [KeyboardShortcut],
+) -> ActionDef {
+    ActionDef {
+        action,
+        category,
+        name,
+        shortcuts,
+        desktop_only: false,
+    }
+}
+
+const fn desktop_action(
+    action: AppAction,
+    category: &'static str,
+    name: &'static str,
+    shortcuts: &'static [KeyboardShortcut],
+) -> ActionDef {
+    ActionDef {
+        desktop_only: true,
+        ..self::action(action, category, name, shortcuts)
+    }
+}
+
+const ACTIONS: &[ActionDef] = &[
+    action(AppAction::NewProject, "File", "New project", &[]),
+    desktop_action(
+        AppAction::OpenProject,
+        "File",
+        "Open project...",
+        &[KeyboardShortcut::new(Modifiers::COMMAND, Key::O)],
+    ),
+    desktop_action(
+        AppAction::SaveProject,
+        "File",
+        "Save project",
+        &[KeyboardShortcut::new(Modifiers::COMMAND, Key::S)],
+    ),
+    desktop_action(
+        AppAction::SaveProjectAs,
+        "File",
+        "Save project as...",
+        &[KeyboardShortcut::new(COMMAND_SHIFT, Key::S)],
+    ),
+    desktop_action(
+        AppAction::ExportGraphImage,
+        "File",
+        "Export graph image...",
+        &[],
+    ),
+    action(
+        AppAction::Undo,
+        "Edit",
+        "Undo",
+        &[KeyboardShortcut::new(Modifiers::COMMAND, Key::Z)],
+    ),
+    action(
+        AppAction::Redo,
+        "Edit",
+        "Redo",
+        &[
+            KeyboardShortcut::new(COMMAND_SHIFT, Key::Z),
+            KeyboardShortcut::new(Modifiers::COMMAND, Key::Y),
+        ],
+    ),
+    action(AppAction::RecomputeNow, "Eval", "Recompute now", &[]),
+    action(AppAction::CreateDemoGraph, "Eval", "Create demo graph", &[]),
+    action(AppAction::TogglePlayback, "Timeline", "Play / pause", &[]),
+    action(AppAction::GoToStart, "Timeline", "Go to start frame", &[]),
+    action(
+        AppAction::ClearFlipbook,
+        "Timeline",
+        "Clear cached frames",
+        &[],
+    ),
+    action(
+        AppAction::ToggleGraphSnap,
+        "Graph",
+        "Toggle snap to grid",
+        &[],
+    ),
+    action(
+        AppAction::AlignNodesLeft,
+        "Graph",
+        "Align selected left",
+        &[],
+    ),
+    action(AppAction::AlignNodesTop, "Graph", "Align selected top", &[]),
+    action(
+        AppAction::DistributeNodesHorizontally,
+        "Graph",
+        "Distribute selected horizontally",
+        &[],
+    ),
+    action(
+        AppAction::DistributeNodesVertically,
+        "Graph",
+        "Distribute selected vertically",
+        &[],
+    ),
+    action(
+        AppAction::StraightenWires,
+        "Graph",
+        "Straighten input wires",
+        &[],
+    ),
+    action(AppAction::FrameAllNodes, "Graph", "Frame all nodes", &[]),
+    action(
+        AppAction::FrameSelectedNodes,
+        "Graph",
+        "Frame selected nodes",
+        &[],
+    ),
+    action(
+        AppAction::ToggleParameters,
+        "View",
+        "Toggle parameters",
+        &[],
+    ),
+    action(
+        AppAction::ToggleSpreadsheet,
+        "View",
+        "Toggle spreadsheet",
+        &[],
+    ),
+    action(
+        AppAction::ToggleProjectParams,
+        "View",
+        "Toggle project params",
+        &[],
+    ),
+    action(AppAction::ToggleUvView, "View", "Toggle UV view", &[]),
+    action(AppAction::ToggleDopeSheet, "View", "Toggle dope sheet", &[]),
+    action(
+        AppAction::ToggleGraphStats,
+        "View",
+        "Toggle graph statistics",
+        &[],
+    ),
+    action(
+        AppAction::ToggleDependencies,
+        "View",
+        "Toggle project dependencies",
+        &[],
+    ),
+    action(
+        AppAction::ToggleNodeAudit,
+        "View",
+        "Toggle node usage and replace",
+        &[],
+    ),
+    action(AppAction::ToggleDebug, "View", "Toggle debug", &[]),
+    action(AppAction::ToggleConsole, "View", "Toggle console", &[]),
+    action(AppAction::ResetLayout, "Layout", "Reset panel layout", &[]),
+    action(
+        AppAction::FocusNextPanel,
+        "Layout",
+        "Focus next panel",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::F6)],
+    ),
+    action(
+        AppAction::FocusPreviousPanel,
+        "Layout",
+        "Focus previous panel",
+        &[KeyboardShortcut::new(Modifiers::SHIFT, Key::F6)],
+    ),
+    action(
+        AppAction::ToggleHighContrast,
+        "View",
+        "Toggle high contrast theme",
+        &[],
+    ),
+    action(
+        AppAction::ToggleFlyCamera,
+        "View",
+        "Toggle fly camera",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::Backtick)],
+    ),
+    action(
+        AppAction::StandardView(StandardView::Front),
+        "Camera",
+        "Front view",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::Num1)],
+    ),
+    action(
+        AppAction::StandardView(StandardView::Side),
+        "Camera",
+        "Side view",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::Num3)],
+    ),
+    action(
+        AppAction::StandardView(StandardView::Perspective),
+        "Camera",
+        "Perspective view",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::Num5)],
+    ),
+    action(
+        AppAction::StandardView(StandardView::Top),
+        "Camera",
+        "Top view",
+        &[KeyboardShortcut::new(Modifiers::NONE, Key::Num7)],
+    ),
+    action(AppAction::FrameViewportAll, "Camera", "Frame all", &[]),
+    action(
+        AppAction::FrameViewportSelected,
+        "Camera",
+        "Frame selected",
+        &[],
+    ),
+    action(
+        AppAction::AddCameraBookmark,
+        "Camera",
+        "Add camera bookmark",
+        &[],
+    ),
+    action(
+        AppAction::CommandPalette,
+        "View",
+        "Command palette",
+        &[KeyboardShortcut::new(COMMAND_SHIFT, Key::P)],
+    ),
+    action(AppAction::ShowTour, "Help", "Show onboarding tour", &[]),
+    action(AppAction::RunNodeTests, "Help", "Run node self-tests", &[]),
+];
+
+pub(super) fn app_actions() -> impl Iterator<Item = &'static ActionDef> {
+    ACTIONS
+        .iter()
+        .filter(|def| !def.desktop_only || cfg!(not(target_arch = "wasm32")))
+}
+
+pub(super) fn script_shortcut(shortcut: &ScriptShortcut) -> Option<(KeyboardShortcut, AppAction)> {
+    let mut modifiers = Modifiers::NONE;
+    let mut parts: Vec<&str> = shortcut.chord.split('+').map(str::trim).collect();
+    let key = Key::from_name(parts.pop()?)?;
+    for part in parts {
+        let modifier = match part.to_lowercase().as_str() {
+            "ctrl" | "cmd" | "command" => Modifiers::COMMAND,
+            "shift" => Modifiers::SHIFT,
+            "alt" | "option" => Modifiers::ALT,
+            _ => return None,
+        };
+        modifiers = modifiers.plus(modifier);
+    }
+    let action = app_actions().find(|def| {
+        let label = format!("{}: {}", def.category, def.name);
+        label.eq_ignore_ascii_case(&shortcut.action)
+            || def.name.eq_ignore_ascii_case(&shortcut.action)
+    })?;
+    Some((KeyboardShortcut::new(modifiers, key), action.action))
+}
+
+impl GraphoApp {
+    pub(super) fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        // Text fields keep their keys, but F6 still has to move focus out of them.
+        let editing_text = ctx.wants_keyboard_input() && !panel_has_focus(ctx);
+        let mut shortcuts: Vec<(AppAction, KeyboardShortcut)> = app_actions()
+            .flat_map(|def| def.shortcuts.iter().map(|shortcut| (def.action, *shortcut)))
+            .chain(
+                self.script_shortcuts
+                    .iter()
+                    .map(|(shortcut, action)| (*action, *shortcut)),
+            )
+            .filter(|(action, _)| {
+                !editing_text
+                    || matches!(
+                        action,
+                        AppAction::FocusNextPanel | AppAction::FocusPreviousPanel
+                    )
+            })
+            .collect();
+        // Consume the most specific chords first so Ctrl+Z doesn't swallow Ctrl+Shift+Z.
+        shortcuts.sort_by_key(|(_, shortcut)| {
+            std::cmp::Reverse(shortcut.modifiers.shift as u8 + shortcut.modifiers.alt as u8)
+        });
+        let triggered = ctx.input_mut(|i| {
+            shortcuts
+                .into_iter()
+                .find(|(_, shortcut)| i.consume_shortcut(shortcut))
+                .map(|(action, _)| action)
+        });
+        if let Some(action) = triggered {
+            self.run_action(action);
+        }
+    }
+
+    pub(super) fn run_action(&mut self, action: AppAction) {
+        let panels = &mut self.project.settings.panels;
+        match action {
+            AppAction::NewProject => self.new_project(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppAction::OpenProject => self.open_project_dialog(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppAction::SaveProject => self.save_project(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppAction::SaveProjectAs => self.save_project_dialog(),
+            #[cfg(not(target_arch = "wasm32"))]
+            AppAction::ExportGraphImage => self.export_graph_image_dialog(),
+            #[cfg(target_arch = "wasm32")]
+            AppAction::OpenProject
+            | AppAction::SaveProject
+            | AppAction::SaveProjectAs
+            | AppAction::ExportGraphImage => {
+                tracing::warn!("file I/O is not available in web builds");
+            }
+            AppAction::Undo => self.try_undo(),
+            AppAction::Redo => self.try_redo(),
+            AppAction::RecomputeNow => {
+                self.eval_dirty = false;
+                self.last_param_change = None;
+                self.flipbook.clear();
+                self.evaluate_graph();
+            }
+            AppAction::CreateDemoGraph => {
+                let snapshot = self.snapshot_undo();
+                self.node_graph.add_demo_graph(&mut self.project.graph);
+                self.mark_eval_dirty();
+                self.queue_undo_snapshot(snapshot, false);
+            }
+            AppAction::TogglePlayback => self.playing = !self.playing,
+            AppAction::GoToStart => {
+                let timeline = &mut self.project.settings.timeline;
+                timeline.frame = timeline.start;
+            }
+            AppAction::ClearFlipbook => self.clear_cached_frames(),
+            AppAction::ToggleGraphSnap => {
+                let snap = !self.node_graph.snap_to_grid();
+                self.node_graph.set_snap_to_grid(snap);
+            }
+            AppAction::AlignNodesLeft => self.arrange_nodes(Some(NodeAlign::Left)),
+            AppAction::AlignNodesTop => self.arrange_nodes(Some(NodeAlign::Top)),
+            AppAction::DistributeNodesHorizontally => {
+                self.arrange_nodes(Some(NodeAlign::DistributeHorizontal));
+            }
+            AppAction::DistributeNodesVertically => {
+                self.arrange_nodes(Some(NodeAlign::DistributeVertical));
+            }
+            AppAction::StraightenWires => self.arrange_nodes(None),
+            AppAction::FrameAllNodes => self.node_graph.request_frame(false),
+            AppAction::FrameSelectedNodes => self.node_graph.request_frame(true),
+            AppAction::ToggleParameters => panels.show_inspector = !panels.show_inspector,
+            AppAction::ToggleSpreadsheet => panels.show_spreadsheet = !panels.show_spreadsheet,
+            AppAction::ToggleProjectParams => {
+                panels.show_project_params = !panels.show_project_params;
+            }
+            AppAction::ToggleUvView => panels.show_uv_view = !panels.show_uv_view,
+            AppAction::ToggleDopeSheet => panels.show_dope_sheet = !panels.show_dope_sheet,
+            AppAction::ToggleGraphStats => panels.show_graph_stats = !panels.show_graph_stats,
+            AppAction::ToggleDependencies => panels.show_dependencies = !panels.show_dependencies,
+            AppAction::ToggleNodeAudit => panels.show_node_audit = !panels.show_node_audit,
+            AppAction::ToggleDebug => panels.show_debug = !panels.show_debug,
+            AppAction::ToggleConsole => panels.show_console = !panels.show_console,
+            AppAction::ResetLayout => {
+                let defaults = grapho_core::ProjectSettings::default();
+                let settings = &mut self.project.settings;
+                settings.viewport_split = defaults.viewport_split;
+                settings.viewport_sheet_split = defaults.viewport_sheet_split;
+                settings.node_params_split = defaults.node_params_split;
+                settings.panels = defaults.panels;
+            }
+            AppAction::FocusNextPanel => self.cycle_panel_focus(true),
+            AppAction::FocusPreviousPanel => self.cycle_panel_focus(false),
+            AppAction::ToggleHighContrast => self.toggle_high_contrast(),
+            AppAction::ToggleFlyCamera => self.toggle_fly_camera(),
+            AppAction::StandardView(view) => self.project.settings.camera.set_standard_view(view),
+            AppAction::FrameViewportAll => self.frame_viewport(false),
+            AppAction::FrameViewportSelected => self.frame_viewport(true),
+            AppAction::AddCameraBookmark => self.add_camera_bookmark(),
+            AppAction::CommandPalette => {
+                self.command_palette = match self.command_palette.take() {
+                    Some(_) => None,
+                    None => Some(CommandPalette::default()),
+                };
+            }
+            AppAction::ShowTour => self.onboarding = Some(OnboardingTour::default()),
+            AppAction::RunNodeTests => self.node_tests_panel = Some(NodeTestsPanel::run()),
+        }
+    }
+
+    fn arrange_nodes(&mut self, align: Option<NodeAlign>) {
+        let snapshot = self.snapshot_undo();
+        let moved = match align {
+            Some(align) => self.node_graph.align_selection(align),
+            None => self.node_graph.straighten_selection(&self.project.graph),
+        };
+        if moved {
+            self.queue_undo_snapshot(snapshot, false);
+        }
+    }
+}
@@ -0,0 +1,154 @@
+use eframe::egui;
+use grapho_core::{Annotation, Measurement};
+use render::{paint_annotation, paint_measurement, screen_ray};
+
+use super::GraphoApp;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+pub(super) enum ViewportTool {
+    #[default]
+    Camera,
+    Measure,
+    Annotate,
+}
+
+pub(super) struct AnnotationTool {
+    tool: ViewportTool,
+    measure_start: Option<[f32; 3]>,
+    note_text: String,
+}
+
+impl Default for AnnotationTool {
+    fn default() -> Self {
+        Self {
+            tool: ViewportTool::Camera,
+            measure_start: None,
+            note_text: "Note".to_string(),
+        }
+    }
+}
+
+impl GraphoApp {
+    // Clicks pick the displayed mesh; drags still orbit, so the tools can stay active
+    // while reviewing.
+    pub(super) fn handle_annotation_click(&mut self, rect: egui::Rect, response: &egui::Response) {
+        if self.annotation_tool.tool == ViewportTool::Camera || !response.clicked() {
+            return;
+        }
+        let Some(pointer) = response.interact_pointer_pos() else {
+            return;
+        };
+        let Some(hit) = self.pick_display_mesh(rect, pointer) else {
+            return;
+        };
+        match self.annotation_tool.tool {
+            ViewportTool::Camera => {}
+            ViewportTool::Measure => match self.annotation_tool.measure_start.take() {
+                Some(start) => {
+                    let measurement = Measurement { start, end: hit };
+                    let units = self.project.graph.units();
+                    tracing::info!("measured {}", units.format_length(measurement.length()));
+                    self.project.settings.measurements.push(measurement);
+                }
+                None => self.annotation_tool.measure_start = Some(hit),
+            },
+            ViewportTool::Annotate => {
+                let text = self.annotation_tool.note_text.trim();
+                if !text.is_empty() {
+                    self.project.settings.annotations.push(Annotation {
+                        position: hit,
+                        text: text.to_string(),
+                    });
+                }
+            }
+        }
+    }
+
+    fn pick_display_mesh(&self, rect: egui::Rect, pointer: egui::Pos2) -> Option<[f32; 3]> {
+        let node = self.project.graph.display_node()?;
+        let mesh = self.eval_state.mesh_for_node(node)?;
+        let (origin, dir) = screen_ray(self.camera_state(), rect, pointer);
+        mesh.raycast(origin, dir)
+    }
+
+    pub(super) fn paint_annotations(&self, ui: &egui::Ui, rect: egui::Rect) {
+        let painter = ui.painter_at(rect);
+        let camera = self.camera_state();
+        let units = self.project.graph.units();
+        for measurement in &self.project.settings.measurements {
+            let label = units.format_length(measurement.length());
+            paint_measurement(
+                &painter,
+                rect,
+                camera,
+                measurement.start,
+                measurement.end,
+                &label,
+            );
+        }
+        if let Some(start) = self.annotation_tool.measure_start {
+            paint_measurement(&painter, rect, camera, start, start, "");
+        }
+        for annotation in &self.project.settings.annotations {
+            paint_annotation(
+                &painter,
+                rect,
+                camera,
+                annotation.position,
+                &annotation.text,
+            );
+        }
+    }
+
+    pub(super) fn show_annotation_toolbar(&mut self, ui: &mut egui::Ui, rect: egui::Rect) {
+        let bar = egui::Rect::from_min_size(
+            rect.right_top() + egui::vec2(-8.0, 8.0),
+            egui::vec2(0.0, 24.0),
+        );
+        let mut child = ui.new_child(
+            egui::UiBuilder::new()
+                .max_rect(bar.expand2(egui::vec2(rect.width() * 0.5, 0.0)))
+                .layout(egui::Layout::right_to_left(egui::Align::Min)),
+        );
+        let tool = &mut self.annotation_tool;
+        let has_marks = !self.project.settings.measurements.is_empty()
+            || !self.project.settings.annotations.is_empty();
+        if child
+            .add_enabled(has_marks, egui::Button::new("Clear"))
+            .on_hover_text("Remove all measurements and notes")
+            .clicked()
+        {
+            self.project.settings.measurements.clear();
+            self.project.settings.annotations.clear();
+        }
+        if tool.tool == ViewportTool::Annotate {
+            child.add(
+                egui::TextEdit::singleline(&mut tool.note_text)
+                    .hint_text("note")
+                    .desired_width(100.0),
+            );
+        }
+        for (label, mode, hint) in [
+            (
+                "Note",
+                ViewportTool::Annotate,
+                "Click geometry to drop a note",
+            ),
+            (
+                "Measure",
+                ViewportTool::Measure,
+                "Click two points on geometry",
+            ),
+        ] {
+            let selected = tool.tool == mode;
+            if child
+                .selectable_label(selected, label)
+                .on_hover_text(hint)
+                .clicked()
+            {
+                tool.tool = if selected { ViewportTool::Camera } else { mode };
+                tool.measure_start = None;
+            }
+        }
+    }
+}
@@ -0,0 +1,167 @@
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use grapho_core::{
+    evaluate_mesh_graph_cancellable, GraphError, Mesh, MeshEvalResult, MeshEvalState, NodeId,
+    SceneSnapshot,
+};
+
+use super::eval::{collect_template_meshes, scene_to_render_with_template, TemplateMeshes};
+use super::GraphoApp;
+
+const PARTIAL_INTERVAL: Duration = Duration::from_millis(100);
+const PARTIAL_COLOR: [f32; 3] = [0.42, 0.43, 0.45];
+
+enum EvalMessage {
+    Partial(NodeId, Mesh),
+    Done(
+        MeshEvalState,
+        Result<MeshEvalResult, GraphError>,
+        TemplateMeshes,
+    ),
+}
+
+pub(super) struct BackgroundEval {
+    receiver: Receiver<EvalMessage>,
+    started: Instant,
+    partial_node: Option<NodeId>,
+    cooked: Arc<AtomicUsize>,
+    cancel: Arc<AtomicBool>,
+    discard_frames: bool,
+}
+
+impl BackgroundEval {
+    pub(super) fn partial_node(&self) -> Option<NodeId> {
+        self.partial_node
+    }
+
+    pub(super) fn cooked_nodes(&self) -> usize {
+        self.cooked.load(Ordering::Relaxed)
+    }
+
+    pub(super) fn elapsed(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub(super) fn cancelled(&self) -> bool {
+        self.cancel.load(Ordering::Relaxed)
+    }
+
+    // The worker's state replaces the shown one, so frames cleared meanwhile go from it too.
+    pub(super) fn discard_frame_caches(&mut self) {
+        self.discard_frames = true;
+    }
+}
+
+impl GraphoApp {
+    pub(super) fn start_background_eval(&mut self, display_node: NodeId) {
+        let graph = self.project.graph.clone();
+        let template_nodes = graph.template_nodes();
+        // The inspector and spreadsheet keep reading the shown state until the result lands;
+        // the worker's copy shares its cooked meshes rather than duplicating them.
+        let shown = Arc::clone(&self.eval_state);
+        let (sender, receiver) = mpsc::channel();
+        let cooked = Arc::new(AtomicUsize::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (worker_cooked, worker_cancel) = (cooked.clone(), cancel.clone());
+        thread::spawn(move || {
+            let mut state = Arc::unwrap_or_clone(shown);
+            let mut last_partial: Option<Instant> = None;
+            let result = evaluate_mesh_graph_cancellable(
+                &graph,
+                display_node,
+                &mut state,
+                &worker_cancel,
+                |node, mesh| {
+                    worker_cooked.fetch_add(1, Ordering::Relaxed);
+                    if node == display_node
+                        || last_partial.is_some_and(|at| at.elapsed() < PARTIAL_INTERVAL)
+                    {
+                        return;
+                    }
+                    last_partial = Some(Instant::now());
+                    let _ = sender.send(EvalMessage::Partial(node, mesh.clone()));
+                },
+            );
+            let templates = match &result {
+                Ok(result) if result.report.output_valid && result.output.is_some() => {
+                    collect_template_meshes(
+                        &graph,
+                        display_node,
+                        &template_nodes,
+                        &mut state,
+                        &worker_cancel,
+                    )
+                }
+                _ => TemplateMeshes::default(),
+            };
+            let _ = sender.send(EvalMessage::Done(state, result, templates));
+        });
+        self.background_eval = Some(BackgroundEval {
+            receiver,
+            started: Instant::now(),
+            partial_node: None,
+            cooked,
+            cancel,
+            discard_frames: false,
+        });
+    }
+
+    // A newer edit makes the running cook pointless; the worker stops after its current node
+    // and hands the cache back so the next cook picks up from there.
+    pub(super) fn cancel_background_eval(&mut self) {
+        if let Some(eval) = &self.background_eval {
+            eval.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    pub(super) fn poll_background_eval(&mut self, ctx: &egui::Context) {
+        let Some(mut eval) = self.background_eval.take() else {
+            return;
+        };
+        let mut partial = None;
+        loop {
+            match eval.receiver.try_recv() {
+                Ok(EvalMessage::Partial(node, mesh)) => partial = Some((node, mesh)),
+                Ok(EvalMessage::Done(mut state, result, templates)) => {
+                    if eval.discard_frames {
+                        state.clear_frame_caches();
+                    }
+                    self.eval_state = Arc::new(state);
+                    if eval.cancelled() {
+                        tracing::debug!("background eval cancelled after {:?}", eval.elapsed());
+                        self.eval_dirty = true;
+                    } else {
+                        self.apply_eval_result(result, templates, eval.started);
+                    }
+                    return;
+                }
+                Err(TryRecvError::Empty) => break,
+                Err(TryRecvError::Disconnected) => {
+                    tracing::error!("background eval stopped without a result");
+                    self.eval_dirty = true;
+                    return;
+                }
+            }
+        }
+        if let Some((node, mesh)) = partial {
+            eval.partial_node = Some(node);
+            let mut snapshot = SceneSnapshot::from_mesh(&mesh, PARTIAL_COLOR);
+            snapshot.mesh.colors = None;
+            snapshot.mesh.corner_colors = None;
+            let scene =
+                scene_to_render_with_template(&snapshot, None, &self.project.settings.render_debug);
+            if let Some(renderer) = &self.viewport_renderer {
+                renderer.set_scene(scene);
+            } else {
+                self.pending_scene = Some(scene);
+            }
+        }
+        self.background_eval = Some(eval);
+        ctx.request_repaint_after(PARTIAL_INTERVAL);
+    }
+}
@@ -0,0 +1,165 @@
+use eframe::egui;
+use grapho_core::{GraphBookmark, GraphFrame, StandardView};
+
+use super::GraphoApp;
+
+const FRAME_COLORS: [[u8; 3]; 4] = [
+    [90, 140, 220],
+    [120, 190, 110],
+    [220, 160, 80],
+    [190, 110, 200],
+];
+const FRAME_PADDING: f32 = 16.0;
+const FRAME_TITLE_HEIGHT: f32 = 22.0;
+
+impl GraphoApp {
+    pub(super) fn show_bookmarks_menu(&mut self, ui: &mut egui::Ui) {
+        let view = self.node_graph.view_transform();
+        let selection = self.node_graph.selection();
+        let settings = &mut self.project.settings;
+
+        if ui
+            .add_enabled(view.is_some(), egui::Button::new("Add View Bookmark"))
+            .on_hover_text("Saves the graph pan/zoom and the current selection")
+            .clicked()
+        {
+            if let Some(view) = view {
+                settings.graph_bookmarks.push(GraphBookmark {
+                    name: format!("View {}", settings.graph_bookmarks.len() + 1),
+                    translation: [view.translation.x, view.translation.y],
+                    zoom: view.scaling,
+                    nodes: selection.clone(),
+                });
+            }
+        }
+        let mut removed = None;
+        for (index, bookmark) in settings.graph_bookmarks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+                ui.add(egui::TextEdit::singleline(&mut bookmark.name).desired_width(120.0));
+                if ui.button("Go").clicked() {
+                    let view = egui::emath::TSTransform::new(
+                        egui::vec2(bookmark.translation[0], bookmark.translation[1]),
+                        bookmark.zoom,
+                    );
+                    let now = ui.input(|i| i.time);
+                    self.node_graph.go_to_view(view, &bookmark.nodes, now);
+                    ui.close();
+                }
+            });
+        }
+        if let Some(index) = removed {
+            settings.graph_bookmarks.remove(index);
+        }
+
+        ui.separator();
+        if ui
+            .add_enabled(
+                !selection.is_empty(),
+                egui::Button::new("Frame Selected Nodes"),
+            )
+            .on_hover_text("Draws a titled backdrop around the selected nodes")
+            .clicked()
+        {
+            let count = settings.graph_frames.len();
+            settings.graph_frames.push(GraphFrame {
+                title: format!("Frame {}", count + 1),
+                nodes: selection,
+                color: FRAME_COLORS[count % FRAME_COLORS.len()],
+            });
+        }
+        let mut removed = None;
+        for (index, frame) in settings.graph_frames.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+                ui.add(egui::TextEdit::singleline(&mut frame.title).desired_width(160.0));
+            });
+        }
+        if let Some(index) = removed {
+            settings.graph_frames.remove(index);
+        }
+
+        ui.separator();
+        self.show_camera_bookmarks(ui);
+    }
+
+    fn show_camera_bookmarks(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            for view in StandardView::ALL {
+                if ui.button(view.label()).clicked() {
+                    self.project.settings.camera.set_standard_view(view);
+                }
+            }
+        });
+        if ui
+            .button("Add Camera Bookmark")
+            .on_hover_text("Saves the viewport camera target, distance and angles")
+            .clicked()
+        {
+            self.add_camera_bookmark();
+        }
+        let camera = &mut self.project.settings.camera;
+        let mut recalled = None;
+        let mut removed = None;
+        for (index, bookmark) in camera.bookmarks.iter_mut().enumerate() {
+            ui.horizontal(|ui| {
+                if ui.small_button("x").on_hover_text("Remove").clicked() {
+                    removed = Some(index);
+                }
+                ui.add(egui::TextEdit::singleline(&mut bookmark.name).desired_width(120.0));
+                if ui.button("Go").clicked() {
+                    recalled = Some(index);
+                    ui.close();
+                }
+            });
+        }
+        if let Some(index) = recalled {
+            let bookmark = camera.bookmarks[index].clone();
+            camera.recall(&bookmark);
+        }
+        if let Some(index) = removed {
+            camera.bookmarks.remove(index);
+        }
+    }
+
+    pub(super) fn paint_graph_frames(&self, ui: &egui::Ui) {
+        // Frames group top-level nodes; inside a subnet the ids would match unrelated nodes.
+        if !self.node_graph.subnet_path().is_empty() {
+            return;
+        }
+        let painter = ui.painter();
+        for frame in &self.project.settings.graph_frames {
+            let Some(bounds) = frame
+                .nodes
+                .iter()
+                .filter_map(|node| self.node_graph.node_screen_rect(*node))
+                .reduce(|a, b| a.union(b))
+            else {
+                continue;
+            };
+            let rect = bounds
+                .expand(FRAME_PADDING)
+                .with_min_y(bounds.min.y - FRAME_PADDING - FRAME_TITLE_HEIGHT);
+            let [r, g, b] = frame.color;
+            let color = egui::Color32::from_rgb(r, g, b);
+            painter.rect(
+                rect,
+                6.0,
+                egui::Color32::from_rgba_unmultiplied(r, g, b, 20),
+                egui::Stroke::new(1.5, color),
+                egui::StrokeKind::Inside,
+            );
+            painter.text(
+                rect.left_top() + egui::vec2(8.0, 4.0),
+                egui::Align2::LEFT_TOP,
+                &frame.title,
+                egui::FontId::proportional(14.0),
+                color,
+            );
+        }
+    }
+}
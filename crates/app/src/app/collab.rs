@@ -0,0 +1,504 @@
+use std::collections::HashMap;
+use std::io::{self, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, TryRecvError};
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use eframe::egui;
+use grapho_core::{Graph, GraphCommand, GraphDelta, NodeId};
+use serde::{Deserialize, Serialize};
+
+use super::GraphoApp;
+
+const SEND_INTERVAL: Duration = Duration::from_millis(250);
+const CONNECT_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_MESSAGE_BYTES: usize = 64 * 1024 * 1024;
+pub(super) const DEFAULT_SYNC_ADDRESS: &str = "127.0.0.1:7878";
+
+type Positions = HashMap<NodeId, [f32; 2]>;
+
+// Edits go out as deltas; snapshots only on join, after a conflict, or for unsupported edits.
+#[derive(Serialize, Deserialize)]
+enum Message {
+    Snapshot {
+        graph: Graph,
+        positions: Vec<(NodeId, [f32; 2])>,
+    },
+    Edit {
+        delta: GraphDelta,
+        positions: Vec<(NodeId, [f32; 2])>,
+    },
+    // Asks the host for a snapshot after a delta could not be merged.
+    Resync,
+    // Scripts and remote controls send edits as commands; they come back as deltas.
+    Commands(Vec<GraphCommand>),
+}
+
+// What the peer changed in the last poll.
+#[derive(Default)]
+pub(super) struct RemoteChange {
+    pub graph: bool,
+    pub positions: Vec<(NodeId, [f32; 2])>,
+}
+
+pub(super) struct CollabSession {
+    listener: Option<TcpListener>,
+    connecting: Option<Receiver<io::Result<TcpStream>>>,
+    stream: Option<TcpStream>,
+    peer: Option<String>,
+    incoming: Vec<u8>,
+    outgoing: Vec<u8>,
+    // Everything sent to or received from the peer; unset until the host's snapshot arrives.
+    synced: Option<Graph>,
+    synced_positions: Positions,
+    last_send: Instant,
+}
+
+impl CollabSession {
+    pub(super) fn host(address: &str) -> io::Result<Self> {
+        let listener = TcpListener::bind(address)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(Some(listener)))
+    }
+
+    // Connects in the background; nothing is sent until the host's snapshot arrives.
+    pub(super) fn connect(address: &str) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let address = address.to_string();
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread::spawn(move || {
+            let _ = sender.send(open_stream(&address));
+        });
+        #[cfg(target_arch = "wasm32")]
+        let _ = sender.send(open_stream(&address));
+        let mut session = Self::new(None);
+        session.connecting = Some(receiver);
+        session
+    }
+
+    fn new(listener: Option<TcpListener>) -> Self {
+        Self {
+            listener,
+            connecting: None,
+            stream: None,
+            peer: None,
+            incoming: Vec::new(),
+            outgoing: Vec::new(),
+            synced: None,
+            synced_positions: Positions::new(),
+            last_send: Instant::now(),
+        }
+    }
+
+    fn is_host(&self) -> bool {
+        self.listener.is_some()
+    }
+
+    pub(super) fn status(&self) -> String {
+        match (&self.listener, &self.peer) {
+            (_, Some(peer)) => format!("Synced with {}", peer),
+            (Some(listener), None) => match listener.local_addr() {
+                Ok(addr) => format!("Hosting on {}, waiting for peer", addr),
+                Err(_) => "Hosting, waiting for peer".to_string(),
+            },
+            (None, None) if self.connecting.is_some() => "Connecting".to_string(),
+            (None, None) => "Disconnected".to_string(),
+        }
+    }
+
+    fn poll(&mut self, graph: &mut Graph, positions: &Positions) -> io::Result<RemoteChange> {
+        self.finish_connect()?;
+        self.accept(graph, positions)?;
+        if self.stream.is_none() {
+            return Ok(RemoteChange::default());
+        }
+        self.read()?;
+
+        let mut change = RemoteChange::default();
+        while let Some(end) = self.incoming.iter().position(|byte| *byte == b'\n') {
+            let line: Vec<u8> = self.incoming.drain(..=end).collect();
+            match serde_json::from_slice::<Message>(&line) {
+                Ok(message) => self.receive(message, graph, positions, &mut change)?,
+                Err(err) => tracing::warn!("sync: ignoring malformed message: {}", err),
+            }
+        }
+
+        if self.outgoing.is_empty() && self.last_send.elapsed() >= SEND_INTERVAL {
+            self.last_send = Instant::now();
+            self.send_changes(graph, positions)?;
+        }
+        self.flush()?;
+        Ok(change)
+    }
+
+    fn receive(
+        &mut self,
+        message: Message,
+        graph: &mut Graph,
+        positions: &Positions,
+        change: &mut RemoteChange,
+    ) -> io::Result<()> {
+        match message {
+            Message::Snapshot {
+                graph: remote,
+                positions: placed,
+            } => {
+                *graph = remote.clone();
+                self.synced = Some(remote);
+                self.synced_positions = placed.iter().copied().collect();
+                change.graph = true;
+                change.positions = placed;
+            }
+            Message::Edit {
+                delta,
+                positions: placed,
+            } => {
+                let Some(synced) = &self.synced else {
+                    return Ok(());
+                };
+                // On top of unsent local edits, and to the synced copy so it is not echoed.
+                let (mut merged, mut merged_synced) = (graph.clone(), synced.clone());
+                let result = merged
+                    .apply_delta(&delta)
+                    .and_then(|_| merged_synced.apply_delta(&delta));
+                if let Err(err) = result {
+                    tracing::warn!("sync: could not merge a remote edit: {:?}", err);
+                    return self.resync(graph, positions);
+                }
+                *graph = merged;
+                self.synced = Some(merged_synced);
+                change.graph |= !delta.is_empty();
+                self.synced_positions.extend(placed.iter().copied());
+                change.positions.extend(placed);
+            }
+            Message::Resync if self.is_host() => self.send_snapshot(graph, positions)?,
+            Message::Resync => {}
+            Message::Commands(commands) => match apply_commands(graph, &commands) {
+                Ok(()) => change.graph = true,
+                Err(err) => tracing::warn!("sync: rejected commands: {}", err),
+            },
+        }
+        Ok(())
+    }
+
+    // The host's graph wins; a peer drops its copy until the host's snapshot arrives.
+    fn resync(&mut self, graph: &Graph, positions: &Positions) -> io::Result<()> {
+        if self.is_host() {
+            return self.send_snapshot(graph, positions);
+        }
+        self.synced = None;
+        self.queue(&Message::Resync)
+    }
+
+    fn send_changes(&mut self, graph: &Graph, positions: &Positions) -> io::Result<()> {
+        let Some(synced) = &self.synced else {
+            return Ok(());
+        };
+        let moved: Vec<(NodeId, [f32; 2])> = positions
+            .iter()
+            .filter(|(node, pos)| self.synced_positions.get(node) != Some(pos))
+            .map(|(node, pos)| (*node, *pos))
+            .collect();
+        let Some(delta) = GraphDelta::between(synced, graph) else {
+            return self.send_snapshot(graph, positions);
+        };
+        if delta.is_empty() && moved.is_empty() {
+            return Ok(());
+        }
+        self.synced = Some(graph.clone());
+        self.synced_positions.extend(moved.iter().copied());
+        self.queue(&Message::Edit {
+            delta,
+            positions: moved,
+        })
+    }
+
+    fn send_snapshot(&mut self, graph: &Graph, positions: &Positions) -> io::Result<()> {
+        self.synced = Some(graph.clone());
+        self.synced_positions = positions.clone();
+        self.queue(&Message::Snapshot {
+            graph: graph.clone(),
+            positions: positions.iter().map(|(node, pos)| (*node, *pos)).collect(),
+        })
+    }
+
+    fn queue(&mut self, message: &Message) -> io::Result<()> {
+        serde_json::to_writer(&mut self.outgoing, message).map_err(io::Error::other)?;
+        self.outgoing.push(b'\n');
+        Ok(())
+    }
+
+    fn finish_connect(&mut self) -> io::Result<()> {
+        let Some(receiver) = &self.connecting else {
+            return Ok(());
+        };
+        let stream = match receiver.try_recv() {
+            Ok(stream) => stream,
+            Err(TryRecvError::Empty) => return Ok(()),
+            Err(TryRecvError::Disconnected) => Err(io::Error::other("connect thread stopped")),
+        };
+        self.connecting = None;
+        let stream = stream?;
+        stream.set_nonblocking(true)?;
+        stream.set_nodelay(true)?;
+        self.peer = Some(stream.peer_addr()?.to_string());
+        self.stream = Some(stream);
+        Ok(())
+    }
+
+    fn accept(&mut self, graph: &Graph, positions: &Positions) -> io::Result<()> {
+        if self.stream.is_some() {
+            return Ok(());
+        }
+        let Some(listener) = &self.listener else {
+            return Ok(());
+        };
+        match listener.accept() {
+            Ok((stream, addr)) => {
+                stream.set_nonblocking(true)?;
+                stream.set_nodelay(true)?;
+                tracing::info!("sync: peer connected from {}", addr);
+                self.stream = Some(stream);
+                self.peer = Some(addr.to_string());
+                self.send_snapshot(graph, positions)
+            }
+            Err(err) if err.kind() == io::ErrorKind::WouldBlock => Ok(()),
+            Err(err) => Err(err),
+        }
+    }
+
+    fn read(&mut self) -> io::Result<()> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(());
+        };
+        let mut buf = [0u8; 16 * 1024];
+        // Complete lines were handled in the last poll, so all of `incoming` is one line.
+        let mut line = self.incoming.len();
+        while self.incoming.len() <= MAX_MESSAGE_BYTES {
+            match stream.read(&mut buf) {
+                Ok(0) => return Err(self.drop_peer("peer closed the connection")),
+                Ok(read) => {
+                    self.incoming.extend_from_slice(&buf[..read]);
+                    line = match buf[..read].iter().rposition(|byte| *byte == b'\n') {
+                        Some(end) => read - end - 1,
+                        None => line + read,
+                    };
+                    if line > MAX_MESSAGE_BYTES {
+                        return Err(self.drop_peer("message too long"));
+                    }
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(self.drop_peer(&err.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        let Some(stream) = self.stream.as_mut() else {
+            return Ok(());
+        };
+        while !self.outgoing.is_empty() {
+            match stream.write(&self.outgoing) {
+                Ok(0) => return Err(self.drop_peer("peer closed the connection")),
+                Ok(written) => {
+                    self.outgoing.drain(..written);
+                }
+                Err(err) if err.kind() == io::ErrorKind::WouldBlock => break,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(self.drop_peer(&err.to_string())),
+            }
+        }
+        Ok(())
+    }
+
+    fn drop_peer(&mut self, reason: &str) -> io::Error {
+        self.stream = None;
+        self.peer = None;
+        self.incoming.clear();
+        self.outgoing.clear();
+        self.synced = None;
+        io::Error::new(io::ErrorKind::ConnectionAborted, reason.to_string())
+    }
+}
+
+fn open_stream(address: &str) -> io::Result<TcpStream> {
+    let mut last_err = io::Error::new(io::ErrorKind::InvalidInput, "address did not resolve");
+    for addr in address.to_socket_addrs()? {
+        match TcpStream::connect_timeout(&addr, CONNECT_TIMEOUT) {
+            Ok(stream) => return Ok(stream),
+            Err(err) => last_err = err,
+        }
+    }
+    Err(last_err)
+}
+
+// All or nothing, so a batch that fails halfway does not leave a half-made edit behind.
+fn apply_commands(graph: &mut Graph, commands: &[GraphCommand]) -> Result<(), String> {
+    let mut edited = graph.clone();
+    for (index, command) in commands.iter().enumerate() {
+        edited
+            .apply(command)
+            .map_err(|err| format!("command {}: {:?}", index + 1, err))?;
+    }
+    *graph = edited;
+    Ok(())
+}
+
+impl GraphoApp {
+    pub(super) fn poll_collab(&mut self, ctx: &egui::Context) {
+        let Some(session) = self.collab.as_mut() else {
+            return;
+        };
+        let positions: Positions = self
+            .node_graph
+            .layout_snapshot()
+            .positions
+            .into_iter()
+            .map(|(node, pos)| (node, [pos.x, pos.y]))
+            .collect();
+        match session.poll(&mut self.project.graph, &positions) {
+            Ok(change) => {
+                if !change.positions.is_empty() {
+                    let placed: Vec<(NodeId, egui::Pos2)> = change
+                        .positions
+                        .iter()
+                        .map(|(node, [x, y])| (*node, egui::pos2(*x, *y)))
+                        .collect();
+                    self.node_graph.place_nodes(&placed);
+                }
+                if change.graph {
+                    self.node_graph.mark_wires_dirty();
+                    self.pending_undo = None;
+                    self.eval_dirty = true;
+                }
+            }
+            Err(err) => {
+                tracing::warn!("sync: {}", err);
+                if !session.is_host() {
+                    self.collab = None;
+                }
+            }
+        }
+        ctx.request_repaint_after(SEND_INTERVAL);
+    }
+
+    pub(super) fn show_collab_menu(&mut self, ui: &mut egui::Ui) {
+        if let Some(session) = &self.collab {
+            ui.label(session.status());
+            if ui.button("Disconnect").clicked() {
+                self.collab = None;
+                tracing::info!("sync: session closed");
+                ui.close();
+            }
+            return;
+        }
+
+        ui.label("Mirror graph edits with another grapho instance (experimental).");
+        ui.horizontal(|ui| {
+            ui.label("Address");
+            ui.text_edit_singleline(&mut self.collab_address);
+        });
+        ui.horizontal(|ui| {
+            if ui.button("Host").clicked() {
+                match CollabSession::host(&self.collab_address) {
+                    Ok(session) => {
+                        tracing::info!("sync: {}", session.status());
+                        self.collab = Some(session);
+                    }
+                    Err(err) => tracing::error!("sync: failed to host: {}", err),
+                }
+                ui.close();
+            }
+            if ui.button("Connect").clicked() {
+                tracing::info!("sync: connecting to {}", self.collab_address);
+                self.collab = Some(CollabSession::connect(&self.collab_address));
+                ui.close();
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grapho_core::{CommandOutput, ParamValue};
+
+    struct Side {
+        session: CollabSession,
+        graph: Graph,
+        positions: Positions,
+    }
+
+    impl Side {
+        // Stands in for the app, which moves nodes where the peer put them.
+        fn poll(&mut self) {
+            let change = self.session.poll(&mut self.graph, &self.positions).unwrap();
+            self.positions.extend(change.positions);
+        }
+
+        fn add(&mut self, kind: &str, pos: [f32; 2]) -> NodeId {
+            let command = GraphCommand::AddNode {
+                kind: kind.to_string(),
+            };
+            let Ok(CommandOutput::Node(node)) = self.graph.apply(&command) else {
+                panic!("could not add a {}", kind);
+            };
+            self.positions.insert(node, pos);
+            node
+        }
+    }
+
+    fn poll_until(host: &mut Side, peer: &mut Side, done: impl Fn(&Side, &Side) -> bool) {
+        let deadline = Instant::now() + Duration::from_secs(10);
+        while !done(host, peer) {
+            assert!(Instant::now() < deadline, "sessions did not sync");
+            host.poll();
+            peer.poll();
+            std::thread::sleep(Duration::from_millis(10));
+        }
+    }
+
+    #[test]
+    fn edits_from_both_sides_merge_over_loopback() {
+        let session = CollabSession::host("127.0.0.1:0").unwrap();
+        let address = session.listener.as_ref().unwrap().local_addr().unwrap();
+        let mut host = Side {
+            session,
+            graph: Graph::default(),
+            positions: Positions::new(),
+        };
+        let source = host.add("Box", [10.0, 20.0]);
+        let mut peer = Side {
+            session: CollabSession::connect(&address.to_string()),
+            graph: Graph::default(),
+            positions: Positions::new(),
+        };
+        poll_until(&mut host, &mut peer, |_, peer| {
+            peer.positions.get(&source) == Some(&[10.0, 20.0])
+        });
+
+        // One edit on each side in the same window: both survive on both sides.
+        let size = ParamValue::Vec3([2.0, 3.0, 4.0]);
+        peer.graph.set_param(source, "size", size.clone()).unwrap();
+        peer.positions.insert(source, [50.0, 60.0]);
+        let sphere = host.add("Sphere", [0.0, 100.0]);
+        poll_until(&mut host, &mut peer, |host, peer| {
+            host.positions.get(&source) == Some(&[50.0, 60.0])
+                && peer.positions.get(&sphere) == Some(&[0.0, 100.0])
+        });
+
+        for side in [&host, &peer] {
+            let params = &side.graph.node(source).unwrap().params.values;
+            assert_eq!(params.get("size"), Some(&size));
+            assert!(side.graph.node(sphere).is_some());
+        }
+        assert!(GraphDelta::between(&host.graph, &peer.graph).is_some_and(|d| d.is_empty()));
+    }
+}
@@ -0,0 +1,158 @@
+use eframe::egui;
+
+use super::actions::{app_actions, ActionDef, AppAction};
+use super::GraphoApp;
+
+const MAX_RESULTS: usize = 12;
+
+pub(super) struct CommandPalette {
+    query: String,
+    selected: usize,
+    focus: bool,
+}
+
+impl Default for CommandPalette {
+    fn default() -> Self {
+        Self {
+            query: String::new(),
+            selected: 0,
+            focus: true,
+        }
+    }
+}
+
+impl GraphoApp {
+    pub(super) fn show_command_palette(&mut self, ctx: &egui::Context) {
+        let Some(mut palette) = self.command_palette.take() else {
+            return;
+        };
+
+        let mut matches: Vec<(i32, &'static ActionDef)> = app_actions()
+            .filter_map(|def| {
+                let label = format!("{}: {}", def.category, def.name);
+                fuzzy_score(&palette.query, &label).map(|score| (score, def))
+            })
+            .collect();
+        matches.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+        matches.truncate(MAX_RESULTS);
+
+        let (up, down, enter, escape) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowUp),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::ArrowDown),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+            )
+        });
+        if down && palette.selected + 1 < matches.len() {
+            palette.selected += 1;
+        }
+        if up {
+            palette.selected = palette.selected.saturating_sub(1);
+        }
+        palette.selected = palette.selected.min(matches.len().saturating_sub(1));
+
+        let mut run = None;
+        let mut keep_open = !escape;
+        let response = egui::Window::new("command_palette")
+            .title_bar(false)
+            .resizable(false)
+            .collapsible(false)
+            .fixed_size([420.0, 0.0])
+            .anchor(egui::Align2::CENTER_TOP, [0.0, 60.0])
+            .frame(egui::Frame::popup(&ctx.style()))
+            .show(ctx, |ui| {
+                let search_id = ui.make_persistent_id("command_palette_search");
+                let search_response = ui.add(
+                    egui::TextEdit::singleline(&mut palette.query)
+                        .id(search_id)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Type a command..."),
+                );
+                if palette.focus {
+                    ui.memory_mut(|mem| mem.request_focus(search_id));
+                    palette.focus = false;
+                }
+                if search_response.changed() {
+                    palette.selected = 0;
+                }
+                ui.separator();
+                if matches.is_empty() {
+                    ui.label("No matching commands.");
+                }
+                for (index, (_, def)) in matches.iter().enumerate() {
+                    let shortcut = def
+                        .shortcuts
+                        .first()
+                        .map(|shortcut| ctx.format_shortcut(shortcut))
+                        .unwrap_or_default();
+                    let row = ui.add(
+                        egui::Button::selectable(
+                            index == palette.selected,
+                            format!("{}: {}", def.category, def.name),
+                        )
+                        .right_text(egui::RichText::new(shortcut).weak())
+                        .min_size(egui::vec2(ui.available_width(), 0.0)),
+                    );
+                    if row.clicked() {
+                        run = Some(def.action);
+                    }
+                }
+            });
+
+        if enter {
+            run = run.or_else(|| matches.get(palette.selected).map(|(_, def)| def.action));
+        }
+        let clicked_outside = response.is_some_and(|response| {
+            ctx.input(|i| i.pointer.any_pressed())
+                && ctx
+                    .pointer_interact_pos()
+                    .is_some_and(|pos| !response.response.rect.contains(pos))
+        });
+        if clicked_outside {
+            keep_open = false;
+        }
+        if keep_open && run.is_none() {
+            self.command_palette = Some(palette);
+        }
+        if let Some(action) = run.filter(|action| *action != AppAction::CommandPalette) {
+            self.run_action(action);
+        }
+    }
+}
+
+fn fuzzy_score(query: &str, text: &str) -> Option<i32> {
+    if query.trim().is_empty() {
+        return Some(0);
+    }
+    let mut needles = query
+        .chars()
+        .filter(|c| !c.is_whitespace())
+        .flat_map(char::to_lowercase)
+        .peekable();
+    let mut score = 0;
+    let mut prev: Option<char> = None;
+    let mut prev_matched = false;
+    for c in text.chars().flat_map(char::to_lowercase) {
+        let Some(&needle) = needles.peek() else {
+            break;
+        };
+        let matched = c == needle;
+        if matched {
+            needles.next();
+            score += 1;
+            if prev_matched {
+                score += 4;
+            }
+            if !prev.is_some_and(char::is_alphanumeric) {
+                score += 3;
+            }
+        }
+        prev_matched = matched;
+        prev = Some(c);
+    }
+    if needles.peek().is_some() {
+        return None;
+    }
+    Some(score - text.len() as i32 / 8)
+}
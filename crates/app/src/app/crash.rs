@@ -0,0 +1,247 @@
+use std::backtrace::Backtrace;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard, OnceLock, TryLockError};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use eframe::egui;
+use grapho_core::Project;
+
+use super::paths::crash_dir;
+use super::{ConsoleBuffer, GraphoApp};
+
+// A crash loses at most this much work.
+const SNAPSHOT_INTERVAL: Duration = Duration::from_secs(5);
+const REPORT_CONSOLE_LINES: usize = 200;
+const REPORT_FILE: &str = "report.txt";
+const AUTOSAVE_FILE: &str = "autosave.json";
+// Names the crash folder to offer on the next launch; removed on a clean exit.
+const PENDING_FILE: &str = "pending_restore";
+
+// What the panic hook can reach once the app is unwinding.
+struct CrashContext {
+    console: ConsoleBuffer,
+    project: Option<Project>,
+    project_path: Option<PathBuf>,
+}
+
+static CRASH_CONTEXT: OnceLock<Mutex<CrashContext>> = OnceLock::new();
+
+pub(super) struct CrashRestore {
+    dir: PathBuf,
+    summary: String,
+    project_path: Option<PathBuf>,
+    has_autosave: bool,
+}
+
+// Keeps the default hook's stderr output and adds a report folder with the panic, a
+// backtrace, the recent console and an emergency autosave of the project.
+pub(crate) fn install_crash_handler(console: ConsoleBuffer) {
+    let context = CrashContext {
+        console,
+        project: None,
+        project_path: None,
+    };
+    if CRASH_CONTEXT.set(Mutex::new(context)).is_err() {
+        return;
+    }
+    let default_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        default_hook(info);
+        // Caught by the evaluator and shown as a node error; the app keeps running.
+        if grapho_core::cook_is_isolated() {
+            return;
+        }
+        let payload = info
+            .payload()
+            .downcast_ref::<&str>()
+            .map(|text| text.to_string())
+            .or_else(|| info.payload().downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        let location = info
+            .location()
+            .map(|location| location.to_string())
+            .unwrap_or_else(|| "unknown".to_string());
+        match write_crash_report(&payload, &location) {
+            Ok(dir) => eprintln!("crash report written to {}", dir.display()),
+            Err(err) => eprintln!("failed to write crash report: {}", err),
+        }
+    }));
+}
+
+// The hook may run on the thread that holds the lock, or after a panic poisoned it.
+fn lock_context() -> Option<MutexGuard<'static, CrashContext>> {
+    match CRASH_CONTEXT.get()?.try_lock() {
+        Ok(guard) => Some(guard),
+        Err(TryLockError::Poisoned(poisoned)) => Some(poisoned.into_inner()),
+        Err(TryLockError::WouldBlock) => None,
+    }
+}
+
+fn write_crash_report(payload: &str, location: &str) -> Result<PathBuf, String> {
+    let root = crash_dir().ok_or_else(|| "no crash directory".to_string())?;
+    let time = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |time| time.as_secs());
+    let dir = root.join(format!("crash-{}", time));
+    std::fs::create_dir_all(&dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+
+    let context = lock_context();
+    let thread = std::thread::current();
+    let mut report = format!(
+        "grapho {} crashed\ntime: {}\nthread: {}\npanic: {}\nlocation: {}\n",
+        env!("CARGO_PKG_VERSION"),
+        time,
+        thread.name().unwrap_or("unnamed"),
+        payload,
+        location
+    );
+    let project_path = context
+        .as_ref()
+        .and_then(|context| context.project_path.clone());
+    report.push_str(&format!(
+        "project: {}\n",
+        project_path
+            .as_ref()
+            .map_or("unsaved".to_string(), |path| path.display().to_string())
+    ));
+
+    let project = context
+        .as_ref()
+        .and_then(|context| context.project.as_ref());
+    match project.map(serde_json::to_vec_pretty) {
+        Some(Ok(data)) => {
+            let path = dir.join(AUTOSAVE_FILE);
+            match std::fs::write(&path, data) {
+                Ok(()) => report.push_str(&format!("autosave: {}\n", path.display())),
+                Err(err) => report.push_str(&format!("autosave failed: {}\n", err)),
+            }
+        }
+        Some(Err(err)) => report.push_str(&format!("autosave failed: {}\n", err)),
+        None => report.push_str("autosave: none\n"),
+    }
+
+    report.push_str(&format!("\nbacktrace:\n{}\n", Backtrace::force_capture()));
+    if let Some(context) = &context {
+        let lines = context.console.snapshot();
+        let start = lines.len().saturating_sub(REPORT_CONSOLE_LINES);
+        report.push_str("\nrecent console output:\n");
+        for line in &lines[start..] {
+            report.push_str(line);
+            report.push('\n');
+        }
+    }
+
+    let report_path = dir.join(REPORT_FILE);
+    std::fs::write(&report_path, report)
+        .map_err(|err| format!("{}: {}", report_path.display(), err))?;
+    std::fs::write(root.join(PENDING_FILE), dir.to_string_lossy().as_bytes())
+        .map_err(|err| err.to_string())?;
+    Ok(dir)
+}
+
+// A clean exit means any panic since launch was survived, e.g. one on a worker thread.
+pub(super) fn clear_pending_restore() {
+    if let Some(path) = crash_dir().map(|dir| dir.join(PENDING_FILE)) {
+        if path.exists() {
+            if let Err(err) = std::fs::remove_file(&path) {
+                tracing::warn!("failed to remove {}: {}", path.display(), err);
+            }
+        }
+    }
+}
+
+fn read_pending_restore() -> Option<CrashRestore> {
+    let pending = crash_dir()?.join(PENDING_FILE);
+    let dir = PathBuf::from(std::fs::read_to_string(pending).ok()?.trim());
+    let report = std::fs::read_to_string(dir.join(REPORT_FILE)).ok()?;
+    let field = |name: &str| {
+        report
+            .lines()
+            .find_map(|line| line.strip_prefix(name))
+            .map(str::to_string)
+    };
+    let summary = field("panic: ").unwrap_or_default();
+    let project_path = field("project: ")
+        .filter(|path| path != "unsaved")
+        .map(PathBuf::from);
+    Some(CrashRestore {
+        has_autosave: dir.join(AUTOSAVE_FILE).exists(),
+        dir,
+        summary,
+        project_path,
+    })
+}
+
+impl GraphoApp {
+    pub(crate) fn check_crash_restore(&mut self) {
+        self.crash_restore = read_pending_restore();
+        if let Some(restore) = &self.crash_restore {
+            tracing::warn!("previous session crashed: {}", restore.dir.display());
+        }
+    }
+
+    // Hands the panic hook a recent copy of the project; cheap next to a frame at this rate.
+    pub(super) fn update_crash_snapshot(&mut self) {
+        if self
+            .crash_snapshot_at
+            .is_some_and(|at| at.elapsed() < SNAPSHOT_INTERVAL)
+        {
+            return;
+        }
+        self.crash_snapshot_at = Some(Instant::now());
+        if let Some(mut context) = lock_context() {
+            context.project = Some(self.project.clone());
+            context.project_path = self.project_path.clone();
+        }
+    }
+
+    pub(super) fn show_crash_restore(&mut self, ctx: &egui::Context) {
+        let Some(restore) = &self.crash_restore else {
+            return;
+        };
+        let mut restore_now = false;
+        let mut dismiss = false;
+        egui::Window::new("Restore after crash")
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label("grapho closed unexpectedly last time.");
+                if !restore.summary.is_empty() {
+                    ui.weak(&restore.summary);
+                }
+                ui.label(format!("Report: {}", restore.dir.display()));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(restore.has_autosave, egui::Button::new("Restore project"))
+                        .clicked()
+                    {
+                        restore_now = true;
+                    }
+                    if ui.button("Discard").clicked() {
+                        dismiss = true;
+                    }
+                });
+            });
+        if restore_now {
+            if let Some(restore) = self.crash_restore.take() {
+                self.restore_crash_autosave(&restore.dir.join(AUTOSAVE_FILE), restore.project_path);
+            }
+            clear_pending_restore();
+        } else if dismiss {
+            self.crash_restore = None;
+            clear_pending_restore();
+        }
+    }
+
+    fn restore_crash_autosave(&mut self, autosave: &Path, project_path: Option<PathBuf>) {
+        match self.load_project_from(autosave) {
+            Ok(()) => {
+                // Saving goes back to the file the crashed session was editing.
+                self.project_path = project_path;
+                tracing::info!("restored project from {}", autosave.display());
+            }
+            Err(err) => tracing::error!("failed to restore {}: {}", autosave.display(), err),
+        }
+    }
+}
@@ -0,0 +1,82 @@
+use eframe::egui;
+use grapho_core::{DependencyIssue, ProjectInfo};
+
+use crate::app::GraphoApp;
+
+impl GraphoApp {
+    pub(super) fn show_dependencies(&mut self, ctx: &egui::Context) {
+        if !self.project.settings.panels.show_dependencies {
+            return;
+        }
+
+        // Rebuilt every frame while open: a graph walk plus one stat per referenced file.
+        let info = ProjectInfo::from_project(&self.project);
+        let issues = info.dependency_issues();
+        let mut open = true;
+        egui::Window::new("Dependencies")
+            .collapsible(true)
+            .resizable(true)
+            .default_size([460.0, 320.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                if issues.is_empty() {
+                    ui.colored_label(
+                        egui::Color32::from_rgb(140, 200, 140),
+                        "Ready for a headless cook",
+                    );
+                } else {
+                    for issue in &issues {
+                        ui.colored_label(egui::Color32::from_rgb(230, 100, 90), issue.describe());
+                    }
+                }
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    ui.strong(format!("External files ({})", info.assets.len()));
+                    egui::Grid::new("dependency_files")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for asset in &info.assets {
+                                ui.label(if asset.output { "out" } else { "in" });
+                                ui.label(format!("{} #{}", asset.kind, asset.node));
+                                ui.label(&asset.path);
+                                let missing = issues.iter().any(|issue| {
+                                    matches!(
+                                        issue,
+                                        DependencyIssue::MissingInput { node, .. }
+                                        | DependencyIssue::MissingOutputDir { node, .. }
+                                            if *node == asset.node
+                                    )
+                                });
+                                if missing {
+                                    ui.colored_label(
+                                        egui::Color32::from_rgb(230, 100, 90),
+                                        "missing",
+                                    );
+                                } else {
+                                    ui.weak("ok");
+                                }
+                                ui.end_row();
+                            }
+                        });
+                    ui.separator();
+                    ui.strong(format!("Node types ({})", info.nodes_by_kind.len()));
+                    for (kind, count) in &info.nodes_by_kind {
+                        let unknown = info.unknown_nodes.iter().any(|(_, name)| name == kind);
+                        let text = format!("{} x{}", kind, count);
+                        if unknown {
+                            ui.colored_label(
+                                egui::Color32::from_rgb(230, 100, 90),
+                                format!("{} (not available)", text),
+                            );
+                        } else {
+                            ui.label(text);
+                        }
+                    }
+                });
+            });
+        if !open {
+            self.project.settings.panels.show_dependencies = false;
+        }
+    }
+}
@@ -0,0 +1,362 @@
+use std::collections::BTreeSet;
+
+use eframe::egui;
+use grapho_core::{NodeId, PlaybackTime};
+
+use super::flipbook::Flipbook;
+use crate::app::GraphoApp;
+
+const LABEL_WIDTH: f32 = 160.0;
+const ROW_HEIGHT: f32 = 20.0;
+const KEY_RADIUS: f32 = 5.0;
+const CACHE_BAR_HEIGHT: f32 = 3.0;
+
+struct DopeTrack {
+    node: NodeId,
+    label: String,
+    key: String,
+    frames: Vec<f32>,
+}
+
+impl GraphoApp {
+    pub(super) fn advance_timeline(&mut self, ctx: &egui::Context) {
+        if self.eval_dirty && !self.flipbook.is_pending() {
+            self.flipbook.clear();
+        }
+        let timeline = &mut self.project.settings.timeline;
+        if self.playing {
+            let dt = ctx.input(|i| i.stable_dt).min(0.1);
+            timeline.frame += dt * timeline.fps.max(1.0);
+            if timeline.frame > timeline.end {
+                if timeline.loop_playback {
+                    timeline.frame = timeline.start;
+                } else {
+                    timeline.frame = timeline.end;
+                    self.playing = false;
+                }
+            }
+            ctx.request_repaint();
+        }
+        if !self.playing {
+            if self
+                .project
+                .graph
+                .apply_keyframes(timeline.frame, timeline.fps)
+            {
+                self.eval_dirty = true;
+            }
+            return;
+        }
+
+        let frame = timeline.frame.floor();
+        if !self.project.graph.apply_keyframes(frame, timeline.fps) {
+            return;
+        }
+        match self.flipbook.get(frame as i32) {
+            Some(scene) if !self.eval_dirty => {
+                let scene = scene.clone();
+                if let Some(renderer) = &self.viewport_renderer {
+                    renderer.set_scene(scene);
+                } else {
+                    self.pending_scene = Some(scene);
+                }
+            }
+            _ => {
+                self.flipbook.request(frame as i32);
+                self.eval_dirty = true;
+            }
+        }
+    }
+
+    pub(super) fn show_dope_sheet(&mut self, ctx: &egui::Context) {
+        if !self.project.settings.panels.show_dope_sheet {
+            return;
+        }
+
+        let tracks: Vec<DopeTrack> = self
+            .project
+            .graph
+            .nodes()
+            .flat_map(|node| {
+                node.param_keyframes.iter().map(|(key, track)| DopeTrack {
+                    node: node.id,
+                    label: format!("{}.{}", node.name, key),
+                    key: key.clone(),
+                    frames: track.keys.iter().map(|key| key.frame).collect(),
+                })
+            })
+            .collect();
+
+        let mut open = true;
+        let mut removed = None;
+        egui::Window::new("Dope Sheet")
+            .collapsible(true)
+            .resizable(true)
+            .default_size([640.0, 220.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                self.show_transport(ui);
+                ui.separator();
+                if tracks.is_empty() {
+                    ui.label("No keyframes. Right-click a parameter to set a key.");
+                    return;
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    removed = self.draw_tracks(ui, &tracks);
+                });
+            });
+        if !open {
+            self.project.settings.panels.show_dope_sheet = false;
+        }
+
+        if let Some((node, key, frame)) = removed {
+            let snapshot = self.snapshot_undo();
+            if self
+                .project
+                .graph
+                .remove_param_keyframe(node, &key, frame)
+                .unwrap_or(false)
+            {
+                self.queue_undo_snapshot(snapshot, false);
+                self.mark_eval_dirty();
+            }
+        }
+    }
+
+    // Drops the playback frames and every node's cooked frames.
+    pub(super) fn clear_cached_frames(&mut self) {
+        self.flipbook.clear();
+        std::sync::Arc::make_mut(&mut self.eval_state).clear_frame_caches();
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(eval) = &mut self.background_eval {
+            eval.discard_frame_caches();
+        }
+    }
+
+    // The always-visible part of the transport, for the top bar.
+    pub(super) fn show_toolbar_transport(&mut self, ui: &mut egui::Ui) {
+        let timeline = &mut self.project.settings.timeline;
+        if ui.small_button("⏮").on_hover_text("Go to start").clicked() {
+            timeline.frame = timeline.start;
+        }
+        let play_label = if self.playing { "⏸" } else { "▶" };
+        if ui
+            .small_button(play_label)
+            .on_hover_text("Play / pause")
+            .clicked()
+        {
+            self.playing = !self.playing;
+        }
+        ui.add(
+            egui::DragValue::new(&mut timeline.frame)
+                .range(timeline.start..=timeline.end)
+                .speed(1.0)
+                .fixed_decimals(0)
+                .prefix("F "),
+        )
+        .on_hover_text("Drag to scrub");
+        let time = PlaybackTime {
+            frame: timeline.frame,
+            fps: timeline.fps,
+        };
+        ui.weak(format!("{:.2}s", time.seconds()));
+    }
+
+    fn show_transport(&mut self, ui: &mut egui::Ui) {
+        let node_frames = self.eval_state.cached_frames();
+        let mut clear = false;
+        let timeline = &mut self.project.settings.timeline;
+        ui.horizontal(|ui| {
+            if ui.button("⏮").on_hover_text("Go to start").clicked() {
+                timeline.frame = timeline.start;
+            }
+            let play_label = if self.playing { "⏸" } else { "▶" };
+            if ui.button(play_label).clicked() {
+                self.playing = !self.playing;
+            }
+            ui.label("Frame");
+            ui.add(
+                egui::DragValue::new(&mut timeline.frame)
+                    .range(timeline.start..=timeline.end)
+                    .speed(1.0)
+                    .fixed_decimals(0),
+            );
+            ui.separator();
+            ui.label("Start");
+            ui.add(egui::DragValue::new(&mut timeline.start).speed(1.0));
+            ui.label("End");
+            ui.add(egui::DragValue::new(&mut timeline.end).speed(1.0));
+            ui.label("FPS");
+            ui.add(egui::DragValue::new(&mut timeline.fps).range(1.0..=240.0));
+            ui.checkbox(&mut timeline.loop_playback, "Loop");
+            ui.separator();
+            ui.label(format!("Cached: {}", self.flipbook.len()));
+            if ui
+                .button("Clear")
+                .on_hover_text("Discard cached playback and simulation frames")
+                .clicked()
+            {
+                clear = true;
+            }
+        });
+        timeline.end = timeline.end.max(timeline.start + 1.0);
+        let slider = ui.add(
+            egui::Slider::new(&mut timeline.frame, timeline.start..=timeline.end).show_value(false),
+        );
+        let (start, end) = (timeline.start, timeline.end);
+        draw_cache_bar(ui, slider.rect, start, end, &node_frames, &self.flipbook);
+        if clear {
+            self.clear_cached_frames();
+        }
+    }
+
+    fn draw_tracks(
+        &mut self,
+        ui: &mut egui::Ui,
+        tracks: &[DopeTrack],
+    ) -> Option<(NodeId, String, f32)> {
+        let timeline = &mut self.project.settings.timeline;
+        let width = ui.available_width().max(LABEL_WIDTH + 120.0);
+        let height = ROW_HEIGHT * tracks.len() as f32;
+        let (rect, response) =
+            ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::click_and_drag());
+        let painter = ui.painter_at(rect);
+        let lane_left = rect.left() + LABEL_WIDTH;
+        let lane_width = (rect.right() - lane_left - KEY_RADIUS).max(1.0);
+        let span = (timeline.end - timeline.start).max(1.0);
+        let frame_x = |frame: f32| lane_left + (frame - timeline.start) / span * lane_width;
+
+        let mut hit = None;
+        let pointer = response.interact_pointer_pos().or(response.hover_pos());
+        for (row, track) in tracks.iter().enumerate() {
+            let top = rect.top() + row as f32 * ROW_HEIGHT;
+            let center_y = top + ROW_HEIGHT * 0.5;
+            if row % 2 == 1 {
+                painter.rect_filled(
+                    egui::Rect::from_min_size(
+                        egui::pos2(rect.left(), top),
+                        egui::vec2(rect.width(), ROW_HEIGHT),
+                    ),
+                    0.0,
+                    egui::Color32::from_white_alpha(6),
+                );
+            }
+            painter.text(
+                egui::pos2(rect.left() + 4.0, center_y),
+                egui::Align2::LEFT_CENTER,
+                &track.label,
+                egui::FontId::proportional(12.0),
+                ui.visuals().text_color(),
+            );
+            for frame in &track.frames {
+                let center = egui::pos2(frame_x(*frame), center_y);
+                let hovered = pointer.is_some_and(|pos| pos.distance(center) <= KEY_RADIUS + 2.0);
+                if hovered {
+                    hit = Some((track.node, track.key.clone(), *frame));
+                }
+                let color = if hovered {
+                    egui::Color32::from_rgb(255, 220, 120)
+                } else {
+                    egui::Color32::from_rgb(230, 180, 60)
+                };
+                painter.add(egui::Shape::convex_polygon(
+                    vec![
+                        center + egui::vec2(0.0, -KEY_RADIUS),
+                        center + egui::vec2(KEY_RADIUS, 0.0),
+                        center + egui::vec2(0.0, KEY_RADIUS),
+                        center + egui::vec2(-KEY_RADIUS, 0.0),
+                    ],
+                    color,
+                    egui::Stroke::new(1.0, egui::Color32::BLACK),
+                ));
+            }
+        }
+
+        let current_x = frame_x(timeline.frame);
+        painter.line_segment(
+            [
+                egui::pos2(current_x, rect.top()),
+                egui::pos2(current_x, rect.bottom()),
+            ],
+            egui::Stroke::new(1.5, egui::Color32::from_rgb(90, 170, 255)),
+        );
+
+        if response.secondary_clicked() {
+            return hit;
+        }
+        if response.clicked() || response.dragged() {
+            if let Some((_, _, frame)) = hit.filter(|_| response.clicked()) {
+                timeline.frame = frame;
+            } else if let Some(pos) = pointer.filter(|pos| pos.x >= lane_left) {
+                let frame = timeline.start + (pos.x - lane_left) / lane_width * span;
+                timeline.frame = frame.round().clamp(timeline.start, timeline.end);
+            }
+        }
+        None
+    }
+}
+
+// Playback frames on top, frames cached by animated nodes below, one rect per run.
+fn draw_cache_bar(
+    ui: &mut egui::Ui,
+    slider: egui::Rect,
+    start: f32,
+    end: f32,
+    node_frames: &BTreeSet<i32>,
+    flipbook: &Flipbook,
+) {
+    let (rect, response) = ui.allocate_exact_size(
+        egui::vec2(slider.width(), CACHE_BAR_HEIGHT * 2.0 + 1.0),
+        egui::Sense::hover(),
+    );
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::from_white_alpha(12));
+    let (first, last) = (start.ceil() as i32, end.floor() as i32);
+    let span = (end - start).max(1.0);
+    let frame_x = |frame: i32| rect.left() + (frame as f32 - start) / span * rect.width();
+    let frame_width = (rect.width() / span).max(1.0);
+    let strips = [
+        (0.0, egui::Color32::from_rgb(90, 170, 255)),
+        (
+            CACHE_BAR_HEIGHT + 1.0,
+            egui::Color32::from_rgb(110, 200, 120),
+        ),
+    ];
+    for (row, (offset, color)) in strips.into_iter().enumerate() {
+        let cached = |frame: i32| {
+            if row == 0 {
+                flipbook.contains(frame)
+            } else {
+                node_frames.contains(&frame)
+            }
+        };
+        let mut frame = first;
+        while frame <= last {
+            if !cached(frame) {
+                frame += 1;
+                continue;
+            }
+            let run_start = frame;
+            while frame <= last && cached(frame) {
+                frame += 1;
+            }
+            let left = frame_x(run_start);
+            let right = (frame_x(frame - 1) + frame_width).min(rect.right());
+            painter.rect_filled(
+                egui::Rect::from_min_max(
+                    egui::pos2(left, rect.top() + offset),
+                    egui::pos2(right, rect.top() + offset + CACHE_BAR_HEIGHT),
+                ),
+                0.0,
+                color,
+            );
+        }
+    }
+    response.on_hover_text(format!(
+        "Playback frames (top): {}\nSimulation and animation frames (bottom): {}",
+        (first..=last)
+            .filter(|frame| flipbook.contains(*frame))
+            .count(),
+        node_frames.range(first..=last).count()
+    ));
+}
@@ -1,4 +1,7 @@
 use std::collections::{HashMap, HashSet};
+use std::sync::atomic::AtomicBool;
+#[cfg(target_arch = "wasm32")]
+use std::sync::Arc;
 use std::time::Duration;
 
 #[cfg(not(target_arch = "wasm32"))]
@@ -6,8 +9,14 @@ use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
-use grapho_core::{evaluate_mesh_graph, Mesh, SceneSnapshot, ShadingMode};
-use render::{RenderMesh, RenderScene, ViewportDebug, ViewportShadingMode};
+use grapho_core::{
+    evaluate_mesh_graph_cancellable, BackgroundMode, CullMode, EvalReport, GraphError, Mesh,
+    MeshEvalResult, RenderDebugSettings, SceneSnapshot, ShadingMode,
+};
+use render::{
+    RenderDebugChannel, RenderMesh, RenderScene, ViewportBackground, ViewportCullMode,
+    ViewportDebug, ViewportShadingMode,
+};
 
 use super::{DisplayState, GraphoApp};
 
@@ -15,10 +24,12 @@ impl GraphoApp {
     pub(super) fn mark_eval_dirty(&mut self) {
         self.eval_dirty = true;
         self.last_param_change = Some(Instant::now());
+        #[cfg(not(target_arch = "wasm32"))]
+        self.cancel_background_eval();
     }
 
     pub(super) fn evaluate_if_needed(&mut self) {
-        if !self.eval_dirty {
+        if !self.eval_dirty || self.background_eval_running() || self.seed_preview_settling() {
             return;
         }
 
@@ -32,9 +43,26 @@ impl GraphoApp {
         self.eval_dirty = false;
         self.last_param_change = None;
         self.evaluate_graph();
+        self.recook_inspection();
+        self.flipbook.cancel();
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn background_eval_running(&self) -> bool {
+        self.background_eval.is_some()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    pub(super) fn background_eval_running(&self) -> bool {
+        false
     }
 
     pub(super) fn evaluate_graph(&mut self) {
+        if self.background_eval_running() {
+            // The worker owns the eval cache; cook again once it hands it back.
+            self.eval_dirty = true;
+            return;
+        }
         let display_node = self.project.graph.display_node();
         let display_node = match display_node {
             None => {
@@ -46,44 +74,81 @@ impl GraphoApp {
                     renderer.clear_scene();
                 }
                 self.pending_scene = None;
+                self.last_display_counts = None;
+                self.debug_channel_names.clear();
                 self.node_graph
                     .set_error_state(HashSet::new(), HashMap::new());
+                self.node_graph.set_eval_report(None);
                 return;
             }
             Some(node) => node,
         };
         self.last_display_state = DisplayState::Ok;
-        let template_nodes = self.project.graph.template_nodes();
 
-        let start = Instant::now();
-        match evaluate_mesh_graph(&self.project.graph, display_node, &mut self.eval_state) {
+        #[cfg(not(target_arch = "wasm32"))]
+        self.start_background_eval(display_node);
+        // Web builds have no worker thread and cook in place.
+        #[cfg(target_arch = "wasm32")]
+        {
+            let start = Instant::now();
+            let cancel = AtomicBool::new(false);
+            let graph = &self.project.graph;
+            let state = Arc::make_mut(&mut self.eval_state);
+            let result =
+                evaluate_mesh_graph_cancellable(graph, display_node, state, &cancel, |_, _| {});
+            let templates = match &result {
+                Ok(result) if result.report.output_valid && result.output.is_some() => {
+                    let template_nodes = graph.template_nodes();
+                    collect_template_meshes(graph, display_node, &template_nodes, state, &cancel)
+                }
+                _ => TemplateMeshes::default(),
+            };
+            self.apply_eval_result(result, templates, start);
+        }
+    }
+
+    pub(super) fn apply_eval_result(
+        &mut self,
+        result: Result<MeshEvalResult, GraphError>,
+        templates: TemplateMeshes,
+        start: Instant,
+    ) {
+        match result {
             Ok(result) => {
                 self.last_eval_ms = Some(start.elapsed().as_secs_f32() * 1000.0);
                 let output_valid = result.report.output_valid;
                 let mut error_nodes = HashSet::new();
                 let mut error_messages = HashMap::new();
                 merge_error_state(&result.report, &mut error_nodes, &mut error_messages);
+                for report in &templates.reports {
+                    merge_error_state(report, &mut error_nodes, &mut error_messages);
+                }
+                self.node_graph.set_eval_report(Some(&result.report));
                 self.last_eval_report = Some(result.report);
+                #[cfg(not(target_arch = "wasm32"))]
+                self.record_eval_stats();
+                self.last_display_counts = result
+                    .output
+                    .as_ref()
+                    .map(|mesh| (mesh.positions.len(), mesh.triangulation().1.len()));
+                self.debug_channel_names = debug_channel_names(&result.debug_channels);
                 if let Some(mesh) = result.output {
-                    let snapshot = SceneSnapshot::from_mesh(&mesh, [0.7, 0.72, 0.75]);
-                    let template_mesh = if output_valid {
-                        collect_template_meshes(
-                            &self.project.graph,
-                            display_node,
-                            &template_nodes,
-                            &mut self.eval_state,
-                            &mut error_nodes,
-                            &mut error_messages,
-                        )
-                    } else {
-                        None
-                    };
-                    let scene = scene_to_render_with_template(&snapshot, template_mesh.as_ref());
+                    let mut snapshot = self.scene_snapshot(&mesh);
+                    snapshot.debug_channels = result.debug_channels;
+                    let scene = scene_to_render_with_template(
+                        &snapshot,
+                        templates.mesh.as_ref(),
+                        &self.project.settings.render_debug,
+                    );
+                    if output_valid {
+                        self.flipbook.store(&scene);
+                    }
                     if let Some(renderer) = &self.viewport_renderer {
                         renderer.set_scene(scene);
                     } else {
                         self.pending_scene = Some(scene);
                     }
+                    self.invalidate_selection_outline();
                 } else {
                     if let Some(renderer) = &self.viewport_renderer {
                         renderer.clear_scene();
@@ -102,8 +167,29 @@ impl GraphoApp {
                 tracing::error!("eval failed: {:?}", err);
                 self.node_graph
                     .set_error_state(HashSet::new(), HashMap::new());
+                self.node_graph.set_eval_report(None);
+            }
+        }
+    }
+
+    pub(super) fn scene_snapshot(&mut self, mesh: &Mesh) -> SceneSnapshot {
+        self.false_color_range = None;
+        let debug = &self.project.settings.render_debug;
+        if debug.shading_mode == ShadingMode::Attribute && !debug.color_attribute.is_empty() {
+            match SceneSnapshot::from_mesh_false_color(mesh, &debug.color_attribute) {
+                Some((snapshot, range)) => {
+                    self.false_color_range = Some(range);
+                    return snapshot;
+                }
+                None => {
+                    tracing::warn!(
+                        "attribute '{}' not found on displayed mesh",
+                        debug.color_attribute
+                    );
+                }
             }
         }
+        SceneSnapshot::from_mesh(mesh, [0.7, 0.72, 0.75])
     }
 
     pub(super) fn viewport_debug(&self) -> ViewportDebug {
@@ -111,6 +197,14 @@ impl GraphoApp {
             ShadingMode::Lit => ViewportShadingMode::Lit,
             ShadingMode::Normals => ViewportShadingMode::Normals,
             ShadingMode::Depth => ViewportShadingMode::Depth,
+            ShadingMode::Attribute => ViewportShadingMode::Attribute,
+            ShadingMode::FlatShaded => ViewportShadingMode::FlatShaded,
+            ShadingMode::Wireframe => ViewportShadingMode::Wireframe,
+        };
+        let cull_mode = match self.project.settings.render_debug.cull_mode {
+            CullMode::None => ViewportCullMode::None,
+            CullMode::Back => ViewportCullMode::Back,
+            CullMode::Front => ViewportCullMode::Front,
         };
         ViewportDebug {
             show_grid: self.project.settings.render_debug.show_grid,
@@ -118,19 +212,80 @@ impl GraphoApp {
             show_normals: self.project.settings.render_debug.show_normals,
             show_bounds: self.project.settings.render_debug.show_bounds,
             normal_length: self.project.settings.render_debug.normal_length,
+            normal_density: self.project.settings.render_debug.normal_density,
             shading_mode,
             depth_near: self.project.settings.render_debug.depth_near,
             depth_far: self.project.settings.render_debug.depth_far,
             show_points: self.project.settings.render_debug.show_points,
             point_size: self.project.settings.render_debug.point_size,
             key_shadows: self.project.settings.render_debug.key_shadows,
+            contact_shadow: self.project.settings.render_debug.contact_shadow,
+            cull_mode,
+            double_sided: self.project.settings.render_debug.double_sided,
+            flat_shading: self.project.settings.render_debug.flat_shading,
+            section_plane: section_plane(&self.project.settings.render_debug),
+            section_outline: self.project.settings.render_debug.section_outline,
+            near_clip: self.project.settings.render_debug.near_clip,
+            symmetry_plane: self.project.graph.symmetry().map(|plane| plane.equation()),
+            hidden_debug_channels: self.hidden_debug_channel_mask(),
+            scale_reference: self
+                .project
+                .settings
+                .render_debug
+                .show_scale_reference
+                .then(|| self.project.graph.units().unit.units_per_meter()),
+            pick_point: None,
+            background: viewport_background(&self.project.settings.render_debug),
         }
     }
+
+    // Channel names persist with the project; the renderer only sees positions in the
+    // current scene's channel list.
+    fn hidden_debug_channel_mask(&self) -> u64 {
+        let hidden = &self.project.settings.render_debug.hidden_debug_channels;
+        self.debug_channel_names
+            .iter()
+            .take(64)
+            .enumerate()
+            .filter(|(_, name)| hidden.contains(name))
+            .fold(0, |mask, (index, _)| mask | (1 << index))
+    }
+}
+
+fn debug_channel_names(channels: &[grapho_core::DebugChannel]) -> Vec<String> {
+    channels
+        .iter()
+        .map(|channel| channel.name.clone())
+        .collect()
+}
+
+fn viewport_background(settings: &RenderDebugSettings) -> ViewportBackground {
+    let rgb = |color: [u8; 3]| color.map(|channel| channel as f32 / 255.0);
+    match settings.background_mode {
+        BackgroundMode::Solid => ViewportBackground::Solid(rgb(settings.background_color)),
+        BackgroundMode::Gradient => ViewportBackground::Gradient {
+            top: rgb(settings.background_top),
+            bottom: rgb(settings.background_bottom),
+        },
+    }
+}
+
+fn section_plane(settings: &RenderDebugSettings) -> Option<[f32; 4]> {
+    if !settings.section_enabled {
+        return None;
+    }
+    let [x, y, z] = settings.section_normal;
+    let length = (x * x + y * y + z * z).sqrt();
+    if length <= f32::EPSILON {
+        return None;
+    }
+    Some([x / length, y / length, z / length, settings.section_offset])
 }
 
 pub(super) fn scene_to_render_with_template(
     scene: &SceneSnapshot,
     template: Option<&Mesh>,
+    settings: &RenderDebugSettings,
 ) -> RenderScene {
     let has_colors = scene.mesh.colors.is_some() || scene.mesh.corner_colors.is_some();
     let base_color = if has_colors {
@@ -139,13 +294,23 @@ pub(super) fn scene_to_render_with_template(
         scene.base_color
     };
     RenderScene {
-        mesh: render_mesh_from_scene(&scene.mesh),
+        mesh: render_mesh_from_scene(&scene.mesh, settings.mesh_opacity),
         base_color,
-        template_mesh: template.map(render_mesh_from_mesh),
+        template_mesh: template.map(|mesh| render_mesh_from_mesh(mesh, settings.template_opacity)),
+        debug_channels: scene
+            .debug_channels
+            .iter()
+            .map(|channel| RenderDebugChannel {
+                name: channel.name.clone(),
+                color: channel.color,
+                points: channel.points.clone(),
+                lines: channel.lines.clone(),
+            })
+            .collect(),
     }
 }
 
-fn render_mesh_from_scene(mesh: &grapho_core::SceneMesh) -> RenderMesh {
+fn render_mesh_from_scene(mesh: &grapho_core::SceneMesh, opacity: f32) -> RenderMesh {
     RenderMesh {
         positions: mesh.positions.clone(),
         normals: mesh.normals.clone(),
@@ -153,45 +318,53 @@ fn render_mesh_from_scene(mesh: &grapho_core::SceneMesh) -> RenderMesh {
         corner_normals: mesh.corner_normals.clone(),
         colors: mesh.colors.clone(),
         corner_colors: mesh.corner_colors.clone(),
+        content_hash: mesh.content_hash,
+        opacity,
     }
 }
 
-fn render_mesh_from_mesh(mesh: &Mesh) -> RenderMesh {
+pub(super) fn render_mesh_from_mesh(mesh: &Mesh, opacity: f32) -> RenderMesh {
     let snapshot = SceneSnapshot::from_mesh(mesh, [0.7, 0.72, 0.75]);
-    render_mesh_from_scene(&snapshot.mesh)
+    render_mesh_from_scene(&snapshot.mesh, opacity)
 }
 
-fn collect_template_meshes(
+// The merged template-flagged outputs shown behind the display node, with their reports.
+#[derive(Default)]
+pub(super) struct TemplateMeshes {
+    pub mesh: Option<Mesh>,
+    pub reports: Vec<EvalReport>,
+}
+
+pub(super) fn collect_template_meshes(
     graph: &grapho_core::Graph,
     display_node: grapho_core::NodeId,
     template_nodes: &[grapho_core::NodeId],
     state: &mut grapho_core::MeshEvalState,
-    error_nodes: &mut HashSet<grapho_core::NodeId>,
-    error_messages: &mut HashMap<grapho_core::NodeId, String>,
-) -> Option<Mesh> {
+    cancel: &AtomicBool,
+) -> TemplateMeshes {
     let mut meshes = Vec::new();
+    let mut reports = Vec::new();
     for node_id in template_nodes {
         if *node_id == display_node {
             continue;
         }
-        match evaluate_mesh_graph(graph, *node_id, state) {
+        match evaluate_mesh_graph_cancellable(graph, *node_id, state, cancel, |_, _| {}) {
             Ok(result) => {
-                merge_error_state(&result.report, error_nodes, error_messages);
                 if result.report.output_valid {
                     if let Some(mesh) = result.output {
                         meshes.push(mesh);
                     }
                 }
+                reports.push(result.report);
             }
             Err(err) => {
                 tracing::error!("template eval failed: {:?}", err);
             }
         }
     }
-    if meshes.is_empty() {
-        None
-    } else {
-        Some(Mesh::merge(&meshes))
+    TemplateMeshes {
+        mesh: (!meshes.is_empty()).then(|| Mesh::merge(&meshes)),
+        reports,
     }
 }
 
@@ -217,4 +390,10 @@ fn merge_error_state(
             }
         }
     }
+    for warning in &report.warnings {
+        nodes.insert(warning.node);
+        messages
+            .entry(warning.node)
+            .or_insert_with(|| format!("Passed input through: {}", warning.message));
+    }
 }
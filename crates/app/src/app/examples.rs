@@ -0,0 +1,99 @@
+use std::collections::HashMap;
+
+use eframe::egui;
+use grapho_core::{run_startup_script, Graph, NodeId};
+
+use crate::app::GraphoApp;
+use crate::node_graph::NodeGraphLayout;
+
+const COLUMN_SPACING: f32 = 240.0;
+const ROW_SPACING: f32 = 160.0;
+
+pub(super) struct Example {
+    pub(super) name: &'static str,
+    pub(super) description: &'static str,
+    source: &'static str,
+}
+
+pub(super) const EXAMPLES: &[Example] = &[
+    Example {
+        name: "Scattered Pebbles",
+        description: "Scatter points on a noisy ground and copy spheres onto them.",
+        source: include_str!("examples/scatter.txt"),
+    },
+    Example {
+        name: "Terrain",
+        description: "Noise terrain colored by slope with a Wrangle.",
+        source: include_str!("examples/terrain.txt"),
+    },
+    Example {
+        name: "Spikes on a Sphere",
+        description: "Copy to Points with copies aligned to the template normals.",
+        source: include_str!("examples/copy_spikes.txt"),
+    },
+    Example {
+        name: "Spiral Staircase",
+        description: "Copy/Transform stacking rotated copies of a single step.",
+        source: include_str!("examples/copy_spiral.txt"),
+    },
+    Example {
+        name: "Building",
+        description: "Floor Split, Facade Repeat and Window Scatter instancing windows.",
+        source: include_str!("examples/building.txt"),
+    },
+    Example {
+        name: "Wobbling Sphere",
+        description: "A Wobble node animated by the timeline; press play to see it move.",
+        source: include_str!("examples/wobble.txt"),
+    },
+];
+
+impl GraphoApp {
+    pub(super) fn show_examples_menu(&mut self, ui: &mut egui::Ui) {
+        for example in EXAMPLES {
+            if ui
+                .button(example.name)
+                .on_hover_text(example.description)
+                .clicked()
+            {
+                self.load_example(example);
+                ui.close();
+            }
+        }
+    }
+
+    pub(super) fn load_example(&mut self, example: &Example) {
+        self.reset_project();
+        let nodes = match run_startup_script(&mut self.project.graph, example.source) {
+            Ok(output) => output.nodes,
+            Err(err) => {
+                tracing::error!("example '{}' failed to load: {}", example.name, err);
+                return;
+            }
+        };
+        let layout = NodeGraphLayout {
+            positions: layered_positions(&self.project.graph, &nodes),
+            selected: self.project.graph.display_node(),
+        };
+        self.node_graph.restore_layout(&self.project.graph, &layout);
+        self.node_graph.request_frame(false);
+        self.eval_dirty = true;
+        tracing::info!("example '{}' loaded", example.name);
+    }
+}
+
+// Columns follow the longest upstream chain so wires read left to right.
+fn layered_positions(graph: &Graph, nodes: &[NodeId]) -> HashMap<NodeId, egui::Pos2> {
+    let depth = graph.node_depths().unwrap_or_default();
+    let mut rows: HashMap<usize, usize> = HashMap::new();
+    nodes
+        .iter()
+        .map(|node| {
+            let column = depth.get(node).copied().unwrap_or(0);
+            let row = rows.entry(column).or_insert(0);
+            let pos = egui::pos2(column as f32 * COLUMN_SPACING, *row as f32 * ROW_SPACING);
+            *row += 1;
+            (*node, pos)
+        })
+        .collect()
+}
@@ -0,0 +1,51 @@
+use std::collections::HashMap;
+
+use render::RenderScene;
+
+const MAX_CACHED_FRAMES: usize = 1000;
+
+#[derive(Default)]
+pub(super) struct Flipbook {
+    frames: HashMap<i32, RenderScene>,
+    pending_frame: Option<i32>,
+}
+
+impl Flipbook {
+    pub(super) fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.frames.clear();
+        self.pending_frame = None;
+    }
+
+    pub(super) fn contains(&self, frame: i32) -> bool {
+        self.frames.contains_key(&frame)
+    }
+
+    pub(super) fn get(&self, frame: i32) -> Option<&RenderScene> {
+        self.frames.get(&frame)
+    }
+
+    pub(super) fn is_pending(&self) -> bool {
+        self.pending_frame.is_some()
+    }
+
+    pub(super) fn request(&mut self, frame: i32) {
+        self.pending_frame = Some(frame);
+    }
+
+    pub(super) fn store(&mut self, scene: &RenderScene) {
+        let Some(frame) = self.pending_frame.take() else {
+            return;
+        };
+        if self.frames.len() < MAX_CACHED_FRAMES {
+            self.frames.insert(frame, scene.clone());
+        }
+    }
+
+    pub(super) fn cancel(&mut self) {
+        self.pending_frame = None;
+    }
+}
@@ -0,0 +1,74 @@
+use eframe::egui;
+
+use crate::app::GraphoApp;
+
+impl GraphoApp {
+    pub(super) fn show_graph_stats(&mut self, ctx: &egui::Context) {
+        if !self.project.settings.panels.show_graph_stats {
+            return;
+        }
+
+        let graph = &self.project.graph;
+        let selected = self.node_graph.selected_node_id();
+        let mut open = true;
+        egui::Window::new("Graph Statistics")
+            .collapsible(true)
+            .resizable(true)
+            .default_size([420.0, 320.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let stats = match graph.stats() {
+                    Ok(stats) => stats,
+                    Err(err) => {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 100, 90),
+                            format!("Graph cannot be ordered: {:?}", err),
+                        );
+                        return;
+                    }
+                };
+                ui.label(format!(
+                    "{} nodes, {} links, max depth {}, {} sources, {} sinks",
+                    stats.nodes.len(),
+                    graph.links().count(),
+                    stats.max_depth,
+                    stats.sources.len(),
+                    stats.sinks.len()
+                ));
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("graph_stats")
+                        .num_columns(6)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in ["Node", "Depth", "In", "Out", "Upstream", "Downstream"] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+                            for (node_id, node_stats) in &stats.nodes {
+                                let name = graph.node(*node_id).map_or("?", |node| &node.name);
+                                let label = egui::RichText::new(name);
+                                if selected == Some(*node_id) {
+                                    ui.label(label.strong());
+                                } else {
+                                    ui.label(label);
+                                }
+                                for value in [
+                                    node_stats.depth,
+                                    node_stats.fan_in,
+                                    node_stats.fan_out,
+                                    node_stats.upstream,
+                                    node_stats.downstream,
+                                ] {
+                                    ui.label(value.to_string());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        if !open {
+            self.project.settings.panels.show_graph_stats = false;
+        }
+    }
+}
@@ -0,0 +1,52 @@
+use grapho_core::{evaluate_mesh_graph, Mesh, MeshEvalState, NodeId};
+
+use super::GraphoApp;
+
+// "Cook this node" results. The chain cooks into its own cache so the display node, the
+// viewport and the main eval state stay exactly as they were.
+#[derive(Default)]
+pub(super) struct InspectEval {
+    state: MeshEvalState,
+    node: Option<NodeId>,
+}
+
+impl GraphoApp {
+    pub(super) fn cook_for_inspection(&mut self, node_id: NodeId) {
+        self.inspect_eval.node = Some(node_id);
+        self.recook_inspection();
+        self.invalidate_selection_outline();
+    }
+
+    // Keeps the inspected chain current after edits; only changed nodes cook again.
+    pub(super) fn recook_inspection(&mut self) {
+        let Some(node_id) = self.inspect_eval.node else {
+            return;
+        };
+        if self.project.graph.node(node_id).is_none() {
+            self.inspect_eval = InspectEval::default();
+            return;
+        }
+        match evaluate_mesh_graph(&self.project.graph, node_id, &mut self.inspect_eval.state) {
+            Ok(result) => {
+                for error in &result.report.errors {
+                    tracing::warn!("inspection cook: {:?}", error);
+                }
+            }
+            Err(err) => tracing::warn!("inspection cook failed: {:?}", err),
+        }
+    }
+
+    // The inspected node's own cook wins; other nodes fall back to it only when the display
+    // chain never produced them.
+    pub(super) fn node_output_mesh(&self, node_id: NodeId) -> Option<&Mesh> {
+        let inspected = self
+            .inspect_eval
+            .node
+            .and_then(|_| self.inspect_eval.state.mesh_for_node(node_id));
+        if self.inspect_eval.node == Some(node_id) {
+            inspected.or_else(|| self.eval_state.mesh_for_node(node_id))
+        } else {
+            self.eval_state.mesh_for_node(node_id).or(inspected)
+        }
+    }
+}
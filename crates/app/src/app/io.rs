@@ -1,37 +1,68 @@
 use std::io;
 use std::path::Path;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
 
 #[cfg(not(target_arch = "wasm32"))]
 use rfd::FileDialog;
 
-use grapho_core::Project;
+use grapho_core::{run_startup_script, Project};
 
+use super::actions::script_shortcut;
+#[cfg(not(target_arch = "wasm32"))]
+use super::paths::default_graph_path;
 use super::GraphoApp;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::node_graph::{graph_to_png, graph_to_svg};
 
-const DEFAULT_GRAPH_PATH: &str = "graphs/default.json";
+const STARTUP_SCRIPT_ENV: &str = "GRAPHO_STARTUP_SCRIPT";
 
 impl GraphoApp {
     pub(super) fn new_project(&mut self) {
+        self.reset_project();
+        tracing::info!("new project created");
+        self.run_startup_hook();
+    }
+
+    pub(super) fn reset_project(&mut self) {
         self.project = Project::default();
         self.project_path = None;
+        self.last_saved = None;
+        self.surface_shader_draft = None;
+        self.playing = false;
+        self.flipbook.clear();
         self.node_graph.reset();
+        self.inspect_eval = Default::default();
         self.undo_stack.clear();
         self.pending_undo = None;
         self.eval_dirty = true;
         self.pending_scene = None;
-        tracing::info!("new project created");
     }
 
     #[cfg(not(target_arch = "wasm32"))]
-    pub(super) fn save_project_to(&self, path: &Path) -> io::Result<()> {
+    pub(super) fn save_project_to(&mut self, path: &Path) -> io::Result<()> {
         let data = serde_json::to_vec_pretty(&self.project).map_err(io::Error::other)?;
         std::fs::write(path, data)?;
+        self.last_saved = Some(Instant::now());
         Ok(())
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn save_project(&mut self) {
+        if let Some(path) = self.project_path.clone() {
+            if let Err(err) = self.save_project_to(&path) {
+                tracing::error!("failed to save project: {}", err);
+            } else {
+                tracing::info!("project saved");
+            }
+        } else {
+            tracing::warn!("no project path set; use Save As");
+        }
+    }
+
     #[cfg(target_arch = "wasm32")]
     #[allow(dead_code)]
-    pub(super) fn save_project_to(&self, _path: &Path) -> io::Result<()> {
+    pub(super) fn save_project_to(&mut self, _path: &Path) -> io::Result<()> {
         Err(io::Error::new(
             io::ErrorKind::Unsupported,
             "File save is not available in web builds",
@@ -41,11 +72,19 @@ impl GraphoApp {
     #[cfg(not(target_arch = "wasm32"))]
     pub(super) fn load_project_from(&mut self, path: &Path) -> io::Result<()> {
         let data = std::fs::read(path)?;
-        let project: Project = serde_json::from_slice(&data)
+        let mut project: Project = serde_json::from_slice(&data)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+        project
+            .migrate()
             .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
         self.project = project;
         self.project_path = Some(path.to_path_buf());
+        self.last_saved = None;
+        self.surface_shader_draft = None;
+        self.playing = false;
+        self.flipbook.clear();
         self.node_graph.reset();
+        self.inspect_eval = Default::default();
         self.undo_stack.clear();
         self.pending_undo = None;
         self.eval_dirty = true;
@@ -62,16 +101,29 @@ impl GraphoApp {
         ))
     }
 
+    // Recovery start: no default graph and no startup script, so a corrupt file or a
+    // crashing scripted node can be fixed from inside the app. Layout comes from the fresh
+    // project's defaults.
+    pub(crate) fn enable_safe_mode(&mut self) {
+        self.safe_mode = true;
+        self.reset_project();
+        self.script_shortcuts.clear();
+        tracing::warn!(
+            "safe mode: skipping the default graph and {}",
+            STARTUP_SCRIPT_ENV
+        );
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
     pub(crate) fn try_load_default_graph(&mut self) {
-        if cfg!(target_arch = "wasm32") {
+        if self.safe_mode {
             return;
         }
-        let path = Path::new(DEFAULT_GRAPH_PATH);
-        if !path.exists() {
+        let Some(path) = default_graph_path().filter(|path| path.exists()) else {
             return;
-        }
+        };
 
-        match self.load_project_from(path) {
+        match self.load_project_from(&path) {
             Ok(()) => {
                 tracing::info!("default graph loaded");
             }
@@ -81,6 +133,50 @@ impl GraphoApp {
         }
     }
 
+    #[cfg(target_arch = "wasm32")]
+    pub(crate) fn try_load_default_graph(&mut self) {}
+
+    pub(crate) fn run_startup_hook(&mut self) {
+        if cfg!(target_arch = "wasm32") || self.safe_mode {
+            return;
+        }
+        let Some(path) = std::env::var_os(STARTUP_SCRIPT_ENV) else {
+            return;
+        };
+        let path = Path::new(&path);
+        let source = match std::fs::read_to_string(path) {
+            Ok(source) => source,
+            Err(err) => {
+                tracing::error!("failed to read startup script {}: {}", path.display(), err);
+                return;
+            }
+        };
+        match run_startup_script(&mut self.project.graph, &source) {
+            Ok(output) => {
+                self.script_shortcuts.clear();
+                for shortcut in &output.shortcuts {
+                    match script_shortcut(shortcut) {
+                        Some(binding) => self.script_shortcuts.push(binding),
+                        None => tracing::warn!(
+                            "startup script: cannot bind '{}' to '{}'",
+                            shortcut.chord,
+                            shortcut.action
+                        ),
+                    }
+                }
+                self.eval_dirty = true;
+                tracing::info!(
+                    "startup script added {} node(s) and {} shortcut(s)",
+                    output.nodes.len(),
+                    self.script_shortcuts.len()
+                );
+            }
+            Err(err) => {
+                tracing::error!("startup script {} failed: {}", path.display(), err);
+            }
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub(super) fn open_project_dialog(&mut self) {
         if let Some(path) = FileDialog::new()
@@ -99,6 +195,31 @@ impl GraphoApp {
         }
     }
 
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(super) fn export_graph_image_dialog(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter("PNG Image", &["png"])
+            .add_filter("SVG Image", &["svg"])
+            .set_file_name("graph.png")
+            .save_file()
+        else {
+            return;
+        };
+        let layout = self.node_graph.layout_snapshot();
+        let svg = path
+            .extension()
+            .is_some_and(|ext| ext.eq_ignore_ascii_case("svg"));
+        let data = if svg {
+            graph_to_svg(&self.project.graph, &layout).map(String::into_bytes)
+        } else {
+            graph_to_png(&self.project.graph, &layout)
+        };
+        match data.and_then(|data| std::fs::write(&path, data).map_err(|err| err.to_string())) {
+            Ok(()) => tracing::info!("graph image exported to {}", path.display()),
+            Err(err) => tracing::error!("failed to export graph image: {}", err),
+        }
+    }
+
     #[cfg(not(target_arch = "wasm32"))]
     pub(super) fn save_project_dialog(&mut self) {
         if let Some(path) = FileDialog::new()
@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+
+use eframe::egui;
+use grapho_core::{
+    builtin_definitions, builtin_kind_from_name, default_params, node_kind_usage,
+    param_value_expression, replace_node_kind, ParamValue,
+};
+
+use crate::app::GraphoApp;
+
+// Source key for target parameters set to "default" in the mapping; it matches nothing.
+const USE_DEFAULT: &str = "";
+
+#[derive(Default)]
+pub(super) struct NodeAudit {
+    kind: String,
+    target: Option<String>,
+    // Target parameter -> source parameter, for rows changed away from the same name.
+    mapping: BTreeMap<String, String>,
+}
+
+impl GraphoApp {
+    pub(super) fn show_node_audit(&mut self, ctx: &egui::Context) {
+        if !self.project.settings.panels.show_node_audit {
+            return;
+        }
+
+        let mut kinds = BTreeMap::<String, usize>::new();
+        for node in self.project.graph.nodes() {
+            *kinds.entry(node.name.clone()).or_default() += 1;
+        }
+        if !kinds.contains_key(&self.node_audit.kind) {
+            self.node_audit.kind = kinds.keys().next().cloned().unwrap_or_default();
+        }
+        let usage = node_kind_usage(&self.project.graph, &self.node_audit.kind);
+        let mut replace = false;
+        let mut open = true;
+        egui::Window::new("Node Usage")
+            .collapsible(true)
+            .resizable(true)
+            .default_size([460.0, 360.0])
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let audit = &mut self.node_audit;
+                egui::ComboBox::from_label("Kind")
+                    .selected_text(format!("{} ({})", audit.kind, usage.len()))
+                    .show_ui(ui, |ui| {
+                        for (kind, count) in &kinds {
+                            ui.selectable_value(
+                                &mut audit.kind,
+                                kind.clone(),
+                                format!("{} ({})", kind, count),
+                            );
+                        }
+                    });
+                egui::ScrollArea::vertical()
+                    .max_height(180.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("node_audit_usage")
+                            .num_columns(2)
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (node_id, params) in &usage {
+                                    ui.label(format!("#{}", node_id.as_u64()));
+                                    let values: Vec<String> = params
+                                        .values
+                                        .iter()
+                                        .map(|(key, value)| {
+                                            format!("{}={}", key, value_text(value))
+                                        })
+                                        .collect();
+                                    ui.label(values.join("  "));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                ui.separator();
+                egui::ComboBox::from_label("Replace with")
+                    .selected_text(audit.target.as_deref().unwrap_or("-"))
+                    .show_ui(ui, |ui| {
+                        for def in builtin_definitions() {
+                            if def.name != audit.kind {
+                                let selected = audit.target.as_deref() == Some(&def.name);
+                                if ui.selectable_label(selected, &def.name).clicked() {
+                                    audit.target = Some(def.name);
+                                    audit.mapping.clear();
+                                }
+                            }
+                        }
+                    });
+                let Some(target) = audit.target.as_deref().and_then(builtin_kind_from_name) else {
+                    return;
+                };
+                let source_keys: Vec<String> = usage
+                    .first()
+                    .map(|(_, params)| params.values.keys().cloned().collect())
+                    .unwrap_or_default();
+                egui::Grid::new("node_audit_mapping")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        for key in default_params(target).values.keys() {
+                            ui.label(key);
+                            let source = audit.mapping.get(key).unwrap_or(key).clone();
+                            let text = if source == USE_DEFAULT {
+                                "(default)".to_string()
+                            } else {
+                                format!("<- {}", source)
+                            };
+                            egui::ComboBox::from_id_salt(("node_audit_map", key))
+                                .selected_text(text)
+                                .show_ui(ui, |ui| {
+                                    let mut choice = source.clone();
+                                    ui.selectable_value(
+                                        &mut choice,
+                                        USE_DEFAULT.to_string(),
+                                        "(default)",
+                                    );
+                                    for source_key in &source_keys {
+                                        ui.selectable_value(
+                                            &mut choice,
+                                            source_key.clone(),
+                                            source_key,
+                                        );
+                                    }
+                                    if choice != source {
+                                        if &choice == key {
+                                            audit.mapping.remove(key);
+                                        } else {
+                                            audit.mapping.insert(key.clone(), choice);
+                                        }
+                                    }
+                                });
+                            ui.end_row();
+                        }
+                    });
+                replace = ui
+                    .add_enabled(
+                        !usage.is_empty(),
+                        egui::Button::new(format!("Replace {} nodes", usage.len())),
+                    )
+                    .clicked();
+            });
+        if !open {
+            self.project.settings.panels.show_node_audit = false;
+        }
+        if replace {
+            self.replace_audited_nodes();
+        }
+    }
+
+    fn replace_audited_nodes(&mut self) {
+        let Some(target) = self
+            .node_audit
+            .target
+            .as_deref()
+            .and_then(builtin_kind_from_name)
+        else {
+            return;
+        };
+        let snapshot = self.snapshot_undo();
+        match replace_node_kind(
+            &mut self.project.graph,
+            &self.node_audit.kind,
+            target,
+            &self.node_audit.mapping,
+        ) {
+            Ok(replaced) => {
+                tracing::info!(
+                    "replaced {} {} node(s) with {}",
+                    replaced.len(),
+                    self.node_audit.kind,
+                    target.name()
+                );
+                self.node_graph.mark_wires_dirty();
+                self.mark_eval_dirty();
+                self.queue_undo_snapshot(snapshot, false);
+            }
+            Err(err) => tracing::error!("node replace failed: {:?}", err),
+        }
+    }
+}
+
+fn value_text(value: &ParamValue) -> String {
+    match value {
+        ParamValue::String(text) => format!("\"{}\"", text),
+        ParamValue::StringList(items) => format!("[{}]", items.join(", ")),
+        _ => param_value_expression(value).unwrap_or_else(|| value.type_name().to_string()),
+    }
+}
@@ -0,0 +1,87 @@
+use eframe::egui;
+
+use grapho_core::{builtin_kind_from_name, node_definition, NodeHelp};
+
+use crate::app::GraphoApp;
+
+pub(super) struct NodeHelpPanel {
+    pub(super) node_id: grapho_core::NodeId,
+    pub(super) screen_pos: egui::Pos2,
+    pub(super) open: bool,
+}
+
+impl GraphoApp {
+    pub(super) fn show_node_help_panel(
+        &mut self,
+        ctx: &egui::Context,
+        panel_slot: &mut Option<NodeHelpPanel>,
+    ) {
+        let Some(mut panel) = panel_slot.take() else {
+            return;
+        };
+        if !panel.open {
+            return;
+        }
+        let Some(node) = self.project.graph.node(panel.node_id) else {
+            return;
+        };
+
+        let help = builtin_kind_from_name(&node.name).and_then(|kind| node_definition(kind).help);
+        let mut open = panel.open;
+        let window = egui::Window::new(format!("Help - {}", node.name))
+            .id(egui::Id::new(("node_help", panel.node_id)))
+            .collapsible(true)
+            .resizable(true)
+            .default_width(320.0)
+            .default_pos(panel.screen_pos)
+            .open(&mut open);
+
+        window.show(ctx, |ui| match &help {
+            Some(help) => show_help_page(ui, help),
+            None => {
+                ui.label("No documentation is available for this node.");
+            }
+        });
+
+        panel.open = open;
+        if panel.open {
+            *panel_slot = Some(panel);
+        }
+    }
+}
+
+fn show_help_page(ui: &mut egui::Ui, help: &NodeHelp) {
+    ui.label(egui::RichText::new(&help.summary).strong());
+    let Some(page) = &help.page else {
+        return;
+    };
+    ui.separator();
+    // Help pages use a small markdown subset: headings, bullets and paragraphs.
+    for line in page.lines() {
+        let line = line.trim_end();
+        if let Some(heading) = line.strip_prefix("## ").or_else(|| line.strip_prefix("# ")) {
+            ui.add_space(4.0);
+            ui.label(egui::RichText::new(heading).heading());
+        } else if let Some(item) = line.strip_prefix("- ") {
+            match item.split_once(": ") {
+                Some((name, text)) => {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("•");
+                        ui.monospace(name);
+                        ui.label(text);
+                    });
+                }
+                None => {
+                    ui.horizontal_wrapped(|ui| {
+                        ui.label("•");
+                        ui.label(item);
+                    });
+                }
+            }
+        } else if line.is_empty() {
+            ui.add_space(4.0);
+        } else {
+            ui.label(line);
+        }
+    }
+}
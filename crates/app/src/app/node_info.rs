@@ -1,6 +1,6 @@
 use eframe::egui;
 
-use grapho_core::{AttributeDomain, AttributeInfo, AttributeType, Mesh};
+use grapho_core::{AttributeDomain, AttributeInfo, AttributePrecision, AttributeType, Mesh};
 
 use crate::app::GraphoApp;
 
@@ -37,6 +37,15 @@ impl GraphoApp {
             .open(&mut open);
 
         window.show(ctx, |ui| {
+            let graph = &self.project.graph;
+            let references: Vec<String> = grapho_core::param_dependencies(graph, node_id)
+                .into_iter()
+                .filter_map(|id| grapho_core::node_reference_name(graph, id))
+                .collect();
+            if !references.is_empty() {
+                ui.label(format!("Expressions reference: {}", references.join(", ")));
+                ui.separator();
+            }
             if let Some(mesh) = self.eval_state.mesh_for_node(node_id) {
                 self.show_mesh_info(ui, mesh);
             } else {
@@ -53,7 +62,7 @@ impl GraphoApp {
     fn show_mesh_info(&self, ui: &mut egui::Ui, mesh: &Mesh) {
         let point_count = mesh.positions.len();
         let vertex_count = mesh.indices.len();
-        let prim_count = mesh.indices.len() / 3;
+        let prim_count = mesh.face_count();
         let detail_count = if point_count == 0 && vertex_count == 0 {
             0
         } else {
@@ -165,6 +174,9 @@ impl GraphoApp {
                         if attr.implicit {
                             name.push_str(" (implicit)");
                         }
+                        if attr.precision != AttributePrecision::Full {
+                            name.push_str(&format!(" ({})", attr.precision.label()));
+                        }
                         ui.label(name);
                         ui.label(attribute_type_label(attr.data_type));
                         ui.label(attr.len.to_string());
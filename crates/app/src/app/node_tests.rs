@@ -0,0 +1,93 @@
+use eframe::egui;
+use grapho_core::{builtin_definitions, run_node_self_tests, NodeTestResult};
+
+use crate::app::GraphoApp;
+
+pub(super) struct NodeTestsPanel {
+    results: Vec<NodeTestResult>,
+    open: bool,
+}
+
+impl NodeTestsPanel {
+    pub(super) fn run() -> Self {
+        let results = builtin_definitions()
+            .iter()
+            .flat_map(run_node_self_tests)
+            .collect::<Vec<_>>();
+        let failed = results.iter().filter(|result| !result.passed()).count();
+        tracing::info!(
+            "node self-tests: {} passed, {} failed",
+            results.len() - failed,
+            failed
+        );
+        Self {
+            results,
+            open: true,
+        }
+    }
+}
+
+impl GraphoApp {
+    pub(super) fn show_node_tests_panel(
+        &mut self,
+        ctx: &egui::Context,
+        panel_slot: &mut Option<NodeTestsPanel>,
+    ) {
+        let Some(mut panel) = panel_slot.take() else {
+            return;
+        };
+        if !panel.open {
+            return;
+        }
+
+        let mut open = panel.open;
+        let mut rerun = false;
+        egui::Window::new("Node Self-Tests")
+            .collapsible(true)
+            .resizable(true)
+            .default_width(360.0)
+            .open(&mut open)
+            .show(ctx, |ui| {
+                let failed = panel.results.iter().filter(|r| !r.passed()).count();
+                ui.horizontal(|ui| {
+                    ui.label(format!(
+                        "{} passed, {} failed",
+                        panel.results.len() - failed,
+                        failed
+                    ));
+                    rerun = ui.button("Run again").clicked();
+                });
+                ui.separator();
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("node_self_tests")
+                        .num_columns(3)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for result in &panel.results {
+                                match &result.result {
+                                    Ok(()) => ui.colored_label(
+                                        egui::Color32::from_rgb(140, 200, 140),
+                                        "pass",
+                                    ),
+                                    Err(_) => ui.colored_label(
+                                        egui::Color32::from_rgb(230, 100, 90),
+                                        "FAIL",
+                                    ),
+                                };
+                                ui.label(format!("{}: {}", result.node, result.case));
+                                ui.label(result.result.as_ref().err().map_or("", String::as_str));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if rerun {
+            panel = NodeTestsPanel::run();
+        }
+        panel.open = open;
+        if panel.open {
+            *panel_slot = Some(panel);
+        }
+    }
+}
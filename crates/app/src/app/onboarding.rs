@@ -0,0 +1,162 @@
+use eframe::egui;
+
+#[cfg(not(target_arch = "wasm32"))]
+use super::paths::onboarding_marker_path;
+use crate::app::GraphoApp;
+
+#[derive(Clone, Copy)]
+enum TourTarget {
+    Screen,
+    Viewport,
+    NodeGraph,
+}
+
+struct TourStep {
+    title: &'static str,
+    text: &'static str,
+    target: TourTarget,
+}
+
+const TOUR_STEPS: &[TourStep] = &[
+    TourStep {
+        title: "Welcome to grapho",
+        text: "grapho builds geometry from a graph of nodes. This short tour points out \
+               where things live.",
+        target: TourTarget::Screen,
+    },
+    TourStep {
+        title: "Viewport",
+        text: "The viewport shows the result of the displayed node. Drag to orbit, \
+               middle-drag to pan and scroll to zoom. \
+               On a touch screen, drag two fingers to pan and pinch to zoom.",
+        target: TourTarget::Viewport,
+    },
+    TourStep {
+        title: "Node graph",
+        text: "Nodes and wires live here. Drag from an output pin to an input pin to \
+               connect nodes, and click a node to edit its parameters.",
+        target: TourTarget::NodeGraph,
+    },
+    TourStep {
+        title: "Adding nodes",
+        text: "Hover the node graph and press Tab to open the add menu. Type to filter \
+               and press Enter to place the node under the cursor.",
+        target: TourTarget::NodeGraph,
+    },
+    TourStep {
+        title: "Examples",
+        text: "File > Examples loads finished graphs to take apart. You can replay this \
+               tour from Help > Show Tour.",
+        target: TourTarget::Screen,
+    },
+];
+
+#[derive(Default)]
+pub(super) struct OnboardingTour {
+    step: usize,
+}
+
+impl GraphoApp {
+    pub(crate) fn start_onboarding_if_first_run(&mut self) {
+        if onboarding_done() {
+            return;
+        }
+        self.onboarding = Some(OnboardingTour::default());
+    }
+
+    pub(super) fn show_onboarding(&mut self, ctx: &egui::Context) {
+        let Some(tour) = &mut self.onboarding else {
+            return;
+        };
+        let Some(step) = TOUR_STEPS.get(tour.step) else {
+            self.finish_onboarding();
+            return;
+        };
+        let target = match step.target {
+            TourTarget::Screen => None,
+            TourTarget::Viewport => self.last_viewport_rect,
+            TourTarget::NodeGraph => self.last_node_graph_rect,
+        };
+        let screen = ctx.content_rect();
+        if let Some(rect) = target {
+            let painter = ctx.layer_painter(egui::LayerId::new(
+                egui::Order::Foreground,
+                egui::Id::new("onboarding_highlight"),
+            ));
+            let stroke = egui::Stroke::new(3.0, ctx.style().visuals.selection.stroke.color);
+            painter.rect_stroke(rect.shrink(2.0), 6.0, stroke, egui::StrokeKind::Inside);
+        }
+        let anchor = target.unwrap_or(screen).center();
+
+        let mut next = false;
+        let mut back = false;
+        let mut skip = false;
+        egui::Window::new(step.title)
+            .id(egui::Id::new("onboarding_tour"))
+            .collapsible(false)
+            .resizable(false)
+            .default_width(300.0)
+            .pivot(egui::Align2::CENTER_CENTER)
+            .fixed_pos(anchor)
+            .show(ctx, |ui| {
+                ui.label(step.text);
+                ui.add_space(6.0);
+                ui.horizontal(|ui| {
+                    ui.label(format!("{} / {}", tour.step + 1, TOUR_STEPS.len()));
+                    if ui.button("Skip").clicked() {
+                        skip = true;
+                    }
+                    let last = tour.step + 1 == TOUR_STEPS.len();
+                    if ui.button(if last { "Done" } else { "Next" }).clicked() {
+                        next = true;
+                    }
+                    if tour.step > 0 && ui.button("Back").clicked() {
+                        back = true;
+                    }
+                });
+            });
+
+        if next {
+            tour.step += 1;
+        } else if back {
+            tour.step -= 1;
+        }
+        if skip || tour.step >= TOUR_STEPS.len() {
+            self.finish_onboarding();
+        }
+    }
+
+    fn finish_onboarding(&mut self) {
+        self.onboarding = None;
+        mark_onboarding_done();
+    }
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn onboarding_done() -> bool {
+    onboarding_marker_path().is_some_and(|path| path.exists())
+}
+
+#[cfg(target_arch = "wasm32")]
+fn onboarding_done() -> bool {
+    false
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+fn mark_onboarding_done() {
+    let Some(path) = onboarding_marker_path() else {
+        return;
+    };
+    if let Some(parent) = path.parent() {
+        if let Err(err) = std::fs::create_dir_all(parent) {
+            tracing::warn!("failed to create {}: {}", parent.display(), err);
+            return;
+        }
+    }
+    if let Err(err) = std::fs::write(&path, b"") {
+        tracing::warn!("failed to write {}: {}", path.display(), err);
+    }
+}
+
+#[cfg(target_arch = "wasm32")]
+fn mark_onboarding_done() {}
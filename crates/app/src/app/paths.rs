@@ -0,0 +1,85 @@
+use std::path::{Path, PathBuf};
+
+use directories::ProjectDirs;
+
+// Where the default graph lived before it moved to the config directory, relative to the
+// working directory.
+const LEGACY_DEFAULT_GRAPH: &str = "graphs/default.json";
+const DEFAULT_GRAPH_FILE: &str = "default.json";
+const ONBOARDING_MARKER_FILE: &str = "onboarding_done";
+
+fn project_dirs() -> Option<ProjectDirs> {
+    ProjectDirs::from("", "", "grapho")
+}
+
+// Settings and small state files: the default graph, the onboarding marker.
+fn config_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.config_dir().to_path_buf())
+}
+
+pub(super) fn default_graph_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(DEFAULT_GRAPH_FILE))
+}
+
+// Crash reports and the emergency autosaves written next to them.
+pub(super) fn crash_dir() -> Option<PathBuf> {
+    project_dirs().map(|dirs| dirs.data_local_dir().join("crashes"))
+}
+
+pub(super) fn onboarding_marker_path() -> Option<PathBuf> {
+    config_dir().map(|dir| dir.join(ONBOARDING_MARKER_FILE))
+}
+
+// The config directory older builds computed by hand. It matches `config_dir` on Linux but
+// not on macOS or Windows.
+fn legacy_config_dir() -> Option<PathBuf> {
+    let config_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))
+    };
+    config_dir.map(|dir| dir.join("grapho"))
+}
+
+// Brings files from their old locations into the platform directories. Runs at startup and
+// never overwrites a file already at its new location. The old default graph is copied
+// rather than moved, since it usually sits in a checkout next to the examples.
+pub(crate) fn migrate_legacy_files() {
+    if let Some(path) = default_graph_path() {
+        migrate(Path::new(LEGACY_DEFAULT_GRAPH), &path, false);
+    }
+    if let (Some(legacy), Some(path)) = (legacy_config_dir(), onboarding_marker_path()) {
+        migrate(&legacy.join(ONBOARDING_MARKER_FILE), &path, true);
+    }
+}
+
+fn migrate(from: &Path, to: &Path, remove_old: bool) {
+    if from == to || !from.exists() || to.exists() {
+        return;
+    }
+    if let Some(parent) = to.parent() {
+        if let Err(err) = std::fs::create_dir_all(parent) {
+            tracing::warn!("failed to create {}: {}", parent.display(), err);
+            return;
+        }
+    }
+    let result = if remove_old {
+        std::fs::rename(from, to).or_else(|_| {
+            // Rename fails across file systems; fall back to copy and delete.
+            std::fs::copy(from, to).and_then(|_| std::fs::remove_file(from))
+        })
+    } else {
+        std::fs::copy(from, to).map(|_| ())
+    };
+    match result {
+        Ok(()) => tracing::info!("moved {} to {}", from.display(), to.display()),
+        Err(err) => tracing::warn!(
+            "failed to migrate {} to {}: {}",
+            from.display(),
+            to.display(),
+            err
+        ),
+    }
+}
@@ -0,0 +1,216 @@
+use eframe::egui;
+use grapho_core::{ErrorPolicy, LengthUnit, ParamValue, SymmetryAxis, SymmetryPlane};
+
+use crate::node_graph::edit_param;
+
+use super::GraphoApp;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq)]
+enum ProjectParamKind {
+    #[default]
+    Float,
+    Int,
+    Vec3,
+}
+
+impl ProjectParamKind {
+    fn label(self) -> &'static str {
+        match self {
+            ProjectParamKind::Float => "Float",
+            ProjectParamKind::Int => "Int",
+            ProjectParamKind::Vec3 => "Vec3",
+        }
+    }
+
+    fn default_value(self) -> ParamValue {
+        match self {
+            ProjectParamKind::Float => ParamValue::Float(1.0),
+            ProjectParamKind::Int => ParamValue::Int(1),
+            ProjectParamKind::Vec3 => ParamValue::Vec3([0.0, 0.0, 0.0]),
+        }
+    }
+}
+
+#[derive(Default)]
+pub(super) struct ProjectParamDraft {
+    name: String,
+    kind: ProjectParamKind,
+}
+
+impl GraphoApp {
+    pub(super) fn show_project_params(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut changed = self.show_project_param_values(ui, true);
+
+        ui.separator();
+        changed |= self.show_unit_settings(ui);
+        changed |= self.show_symmetry_settings(ui);
+        changed |= self.show_seed_settings(ui);
+        changed |= self.show_error_policy(ui);
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            let draft = &mut self.project_param_draft;
+            ui.add(
+                egui::TextEdit::singleline(&mut draft.name)
+                    .hint_text("name")
+                    .desired_width(120.0),
+            );
+            egui::ComboBox::from_id_salt("project_param_kind")
+                .selected_text(draft.kind.label())
+                .show_ui(ui, |ui| {
+                    for kind in [
+                        ProjectParamKind::Float,
+                        ProjectParamKind::Int,
+                        ProjectParamKind::Vec3,
+                    ] {
+                        ui.selectable_value(&mut draft.kind, kind, kind.label());
+                    }
+                });
+            let name = draft.name.trim().to_string();
+            let exists = self.project.graph.project_param(&name).is_some();
+            if ui
+                .add_enabled(!name.is_empty() && !exists, egui::Button::new("Add"))
+                .clicked()
+            {
+                if self
+                    .project
+                    .graph
+                    .set_project_param(name.clone(), draft.kind.default_value())
+                {
+                    draft.name.clear();
+                    changed = true;
+                } else {
+                    tracing::warn!("invalid project parameter name '{}'", name);
+                }
+            }
+        });
+
+        changed
+    }
+
+    fn show_unit_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut units = self.project.graph.units();
+        ui.horizontal(|ui| {
+            ui.label("Units");
+            egui::ComboBox::from_id_salt("project_units")
+                .selected_text(units.unit.label())
+                .show_ui(ui, |ui| {
+                    for unit in [LengthUnit::Meters, LengthUnit::Centimeters] {
+                        ui.selectable_value(&mut units.unit, unit, unit.label());
+                    }
+                });
+            ui.add(
+                egui::DragValue::new(&mut units.scale)
+                    .speed(0.01)
+                    .range(0.0001..=10000.0)
+                    .prefix("scale "),
+            )
+            .on_hover_text("File units to project units; imports multiply by this, exports divide");
+        });
+        self.project.graph.set_units(units)
+    }
+
+    fn show_symmetry_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let current = self.project.graph.symmetry();
+        let mut enabled = current.is_some();
+        let mut plane = current.unwrap_or_default();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut enabled, "Symmetry")
+                .on_hover_text("Nodes with mirror enabled reflect their edits across this plane");
+            ui.add_enabled_ui(enabled, |ui| {
+                egui::ComboBox::from_id_salt("project_symmetry_axis")
+                    .selected_text(plane.axis.label())
+                    .show_ui(ui, |ui| {
+                        for axis in [SymmetryAxis::X, SymmetryAxis::Y, SymmetryAxis::Z] {
+                            ui.selectable_value(&mut plane.axis, axis, axis.label());
+                        }
+                    });
+                ui.add(
+                    egui::DragValue::new(&mut plane.offset)
+                        .speed(0.01)
+                        .prefix("offset "),
+                );
+            });
+        });
+        let next: Option<SymmetryPlane> = enabled.then_some(plane);
+        self.project.graph.set_symmetry(next)
+    }
+
+    fn show_seed_settings(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut seeds = self.project.graph.seeds();
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut seeds.per_node, "Per-node seeds")
+                .on_hover_text("Offset every Scatter/Noise seed by the project seed and node id");
+            ui.add_enabled_ui(seeds.per_node, |ui| {
+                ui.add(egui::DragValue::new(&mut seeds.seed).prefix("seed "));
+            });
+            if ui
+                .button("Reseed all")
+                .on_hover_text("Pick the next project seed; every stochastic node changes")
+                .clicked()
+            {
+                seeds = seeds.reseeded();
+            }
+        });
+        self.project.graph.set_seeds(seeds)
+    }
+
+    fn show_error_policy(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut policy = self.project.graph.error_policy();
+        ui.horizontal(|ui| {
+            ui.label("On node error");
+            egui::ComboBox::from_id_salt("project_error_policy")
+                .selected_text(policy.label())
+                .show_ui(ui, |ui| {
+                    for option in [ErrorPolicy::Fail, ErrorPolicy::PassThrough] {
+                        ui.selectable_value(&mut policy, option, option.label());
+                    }
+                })
+                .response
+                .on_hover_text("Pass through forwards a failed node's first input with a warning");
+        });
+        self.project.graph.set_error_policy(policy)
+    }
+
+    pub(super) fn show_project_param_values(&mut self, ui: &mut egui::Ui, removable: bool) -> bool {
+        let mut changed = false;
+        let params: Vec<(String, ParamValue)> = self
+            .project
+            .graph
+            .project_params()
+            .iter()
+            .map(|(name, value)| (name.clone(), value.clone()))
+            .collect();
+        if params.is_empty() {
+            ui.label("No project parameters.");
+        }
+
+        let unit = self.project.graph.units().unit;
+        let mut removed = None;
+        for (name, value) in params {
+            ui.horizontal(|ui| {
+                if removable && ui.small_button("x").on_hover_text("Remove").clicked() {
+                    removed = Some(name.clone());
+                }
+                let (next_value, did_change, label_response) =
+                    edit_param(ui, "Project", &name, value, unit);
+                label_response.on_hover_text(format!("Reference in expressions as ${}", name));
+                if did_change
+                    && self
+                        .project
+                        .graph
+                        .set_project_param(name.clone(), next_value)
+                {
+                    changed = true;
+                }
+            });
+        }
+        if let Some(name) = removed {
+            if self.project.graph.remove_project_param(&name) {
+                changed = true;
+            }
+        }
+
+        changed
+    }
+}
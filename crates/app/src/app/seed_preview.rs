@@ -0,0 +1,104 @@
+use std::time::Duration;
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+use eframe::egui;
+use grapho_core::{
+    builtin_kind_from_name, evaluate_mesh_graph, BuiltinNodeKind, Graph, MeshEvalState, ParamValue,
+};
+
+use super::eval::scene_to_render_with_template;
+use super::GraphoApp;
+
+// Dice rolls cook a thinned copy of the graph right away, skipping the edit debounce; the
+// full-quality cook waits until the rolls have stopped for this long.
+const SETTLE: Duration = Duration::from_millis(400);
+const PREVIEW_DENSITY: f32 = 0.25;
+
+// Has its own cache so thinned results never end up in the main eval state.
+#[derive(Default)]
+pub(super) struct SeedPreview {
+    state: MeshEvalState,
+    settle_at: Option<Instant>,
+}
+
+impl GraphoApp {
+    pub(super) fn preview_seed_roll(&mut self, ctx: &egui::Context) {
+        self.seed_preview.settle_at = Some(Instant::now() + SETTLE);
+        self.eval_dirty = true;
+        ctx.request_repaint_after(SETTLE);
+        let Some(display_node) = self.project.graph.display_node() else {
+            return;
+        };
+        let graph = preview_graph(&self.project.graph);
+        match evaluate_mesh_graph(&graph, display_node, &mut self.seed_preview.state) {
+            Ok(result) => {
+                let Some(mesh) = result.output.filter(|_| result.report.output_valid) else {
+                    return;
+                };
+                let snapshot = self.scene_snapshot(&mesh);
+                let scene = scene_to_render_with_template(
+                    &snapshot,
+                    None,
+                    &self.project.settings.render_debug,
+                );
+                if let Some(renderer) = &self.viewport_renderer {
+                    renderer.set_scene(scene);
+                } else {
+                    self.pending_scene = Some(scene);
+                }
+            }
+            Err(err) => tracing::warn!("seed preview failed: {:?}", err),
+        }
+    }
+
+    pub(super) fn seed_preview_settling(&mut self) -> bool {
+        match self.seed_preview.settle_at {
+            Some(at) if Instant::now() < at => true,
+            Some(_) => {
+                self.seed_preview.settle_at = None;
+                false
+            }
+            None => false,
+        }
+    }
+}
+
+// Scales down the parameters that dominate cook time for stochastic setups.
+fn preview_graph(graph: &Graph) -> Graph {
+    let mut preview = graph.clone();
+    let updates: Vec<_> = graph
+        .nodes()
+        .filter_map(|node| {
+            let (key, value) = match builtin_kind_from_name(&node.name)? {
+                BuiltinNodeKind::Scatter => {
+                    let count = node.params.get_int("count", 100) as f32;
+                    (
+                        "count",
+                        ParamValue::Int((count * PREVIEW_DENSITY).ceil() as i32),
+                    )
+                }
+                BuiltinNodeKind::WindowScatter => {
+                    let density = node.params.get_float("density", 0.8);
+                    ("density", ParamValue::Float(density * PREVIEW_DENSITY))
+                }
+                BuiltinNodeKind::BakeAo => {
+                    let samples = node.params.get_int("samples", 32) as f32;
+                    (
+                        "samples",
+                        ParamValue::Int((samples * PREVIEW_DENSITY).ceil() as i32),
+                    )
+                }
+                _ => return None,
+            };
+            Some((node.id, key, value))
+        })
+        .collect();
+    for (node_id, key, value) in updates {
+        let _ = preview.set_param(node_id, key, value);
+    }
+    preview
+}
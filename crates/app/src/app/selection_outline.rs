@@ -0,0 +1,41 @@
+use grapho_core::NodeId;
+use render::RenderHighlight;
+
+use super::eval::render_mesh_from_mesh;
+use super::GraphoApp;
+
+// The outlined node, and whether a cook since then replaced the mesh it was built from.
+#[derive(Default)]
+pub(super) struct SelectionOutline {
+    node: Option<NodeId>,
+    stale: bool,
+}
+
+impl GraphoApp {
+    pub(super) fn invalidate_selection_outline(&mut self) {
+        self.selection_outline.stale = true;
+    }
+
+    // Runs every frame; the renderer only hears about it when the selection or its mesh moved.
+    pub(super) fn sync_selection_outline(&mut self) {
+        let Some(renderer) = &self.viewport_renderer else {
+            return;
+        };
+        let node = self
+            .node_graph
+            .selected_node_id()
+            .filter(|_| self.project.settings.render_debug.outline_selection);
+        if node == self.selection_outline.node && !self.selection_outline.stale {
+            return;
+        }
+        let highlight = node.and_then(|node_id| {
+            if self.project.graph.display_node() == Some(node_id) {
+                return Some(RenderHighlight::Display);
+            }
+            self.node_output_mesh(node_id)
+                .map(|mesh| RenderHighlight::Mesh(render_mesh_from_mesh(mesh, 1.0)))
+        });
+        renderer.set_highlight(highlight);
+        self.selection_outline = SelectionOutline { node, stale: false };
+    }
+}
@@ -0,0 +1,148 @@
+use std::time::Duration;
+
+use eframe::egui;
+use hidapi::{HidApi, HidDevice};
+use render::apply_camera_motion;
+
+use super::GraphoApp;
+
+const VENDOR_IDS: [u16; 2] = [0x046d, 0x256f];
+const MULTI_AXIS_USAGE_PAGE: u16 = 0x01;
+const MULTI_AXIS_USAGE: u16 = 0x08;
+const AXIS_RANGE: f32 = 350.0;
+const TRANSLATION_SCALE: f32 = 1.0;
+const ROTATION_SCALE: f32 = 2.0;
+const POLL_INTERVAL: Duration = Duration::from_millis(16);
+
+pub(super) struct SpaceMouse {
+    device: HidDevice,
+    name: String,
+    translation: [f32; 3],
+    rotation: [f32; 3],
+}
+
+impl SpaceMouse {
+    pub(super) fn open() -> Option<Self> {
+        let api = match HidApi::new() {
+            Ok(api) => api,
+            Err(err) => {
+                tracing::warn!("space mouse: HID is unavailable: {}", err);
+                return None;
+            }
+        };
+        let info = api.device_list().find(|info| {
+            VENDOR_IDS.contains(&info.vendor_id())
+                && info.usage_page() == MULTI_AXIS_USAGE_PAGE
+                && info.usage() == MULTI_AXIS_USAGE
+        })?;
+        let device = match info.open_device(&api) {
+            Ok(device) => device,
+            Err(err) => {
+                tracing::warn!("space mouse: failed to open device: {}", err);
+                return None;
+            }
+        };
+        if let Err(err) = device.set_blocking_mode(false) {
+            tracing::warn!("space mouse: failed to set non-blocking mode: {}", err);
+            return None;
+        }
+        let name = info.product_string().unwrap_or("Space mouse").to_string();
+        tracing::info!("space mouse connected: {}", name);
+        Some(Self {
+            device,
+            name,
+            translation: [0.0; 3],
+            rotation: [0.0; 3],
+        })
+    }
+
+    // Devices only report axes when they change, so the last values are held between reads.
+    fn poll(&mut self) -> Result<(), String> {
+        let mut buf = [0u8; 64];
+        loop {
+            let len = self.device.read(&mut buf).map_err(|err| err.to_string())?;
+            if len == 0 {
+                return Ok(());
+            }
+            match (buf[0], len) {
+                (1, 13..) => {
+                    self.translation = read_axes(&buf[1..7]);
+                    self.rotation = read_axes(&buf[7..13]);
+                }
+                (1, 7..) => self.translation = read_axes(&buf[1..7]),
+                (2, 7..) => self.rotation = read_axes(&buf[1..7]),
+                _ => {}
+            }
+        }
+    }
+}
+
+fn read_axes(bytes: &[u8]) -> [f32; 3] {
+    std::array::from_fn(|axis| {
+        i16::from_le_bytes([bytes[axis * 2], bytes[axis * 2 + 1]]) as f32 / AXIS_RANGE
+    })
+}
+
+fn apply_deadzone(value: f32, deadzone: f32) -> f32 {
+    let magnitude = value.abs().min(1.0);
+    if magnitude <= deadzone {
+        return 0.0;
+    }
+    value.signum() * (magnitude - deadzone) / (1.0 - deadzone).max(f32::EPSILON)
+}
+
+impl GraphoApp {
+    pub(super) fn poll_space_mouse(&mut self, ctx: &egui::Context) {
+        let settings = self.project.settings.camera.space_mouse.clone();
+        let Some(space_mouse) = &mut self.space_mouse else {
+            return;
+        };
+        if let Err(err) = space_mouse.poll() {
+            tracing::warn!("space mouse disconnected: {}", err);
+            self.space_mouse = None;
+            return;
+        }
+        ctx.request_repaint_after(POLL_INTERVAL);
+        if !settings.enabled {
+            return;
+        }
+        let [tx, ty, tz] = space_mouse
+            .translation
+            .map(|value| apply_deadzone(value, settings.deadzone));
+        let [rx, _, rz] = space_mouse
+            .rotation
+            .map(|value| apply_deadzone(value, settings.deadzone));
+        if [tx, ty, tz, rx, rz].iter().all(|value| *value == 0.0) {
+            return;
+        }
+
+        // Device axes: x right, y toward the user, z down; twisting the cap orbits.
+        let dt = ctx.input(|i| i.stable_dt).min(0.1);
+        let move_step = settings.translation_speed * TRANSLATION_SCALE * dt;
+        let turn_step = settings.rotation_speed * ROTATION_SCALE * dt;
+        let mut camera = self.camera_state();
+        apply_camera_motion(
+            &mut camera,
+            [tx * move_step, -tz * move_step, -ty * move_step],
+            [-rz * turn_step, rx * turn_step],
+        );
+        self.set_camera_state(camera);
+    }
+
+    pub(super) fn show_space_mouse_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.project.settings.camera.space_mouse;
+        ui.horizontal(|ui| {
+            match &self.space_mouse {
+                Some(space_mouse) => ui.label(format!("Connected: {}", space_mouse.name)),
+                None => ui.weak("No device"),
+            };
+            if ui.button("Reconnect").clicked() {
+                self.space_mouse = SpaceMouse::open();
+            }
+        });
+        ui.checkbox(&mut settings.enabled, "Enabled");
+        ui.add(egui::Slider::new(&mut settings.translation_speed, 0.1..=5.0).text("Pan speed"));
+        ui.add(egui::Slider::new(&mut settings.rotation_speed, 0.1..=5.0).text("Orbit speed"));
+        ui.add(egui::Slider::new(&mut settings.deadzone, 0.0..=0.5).text("Deadzone"));
+    }
+}
@@ -1,7 +1,36 @@
 use egui::{Align2, Color32, FontId, RichText, Ui};
-use grapho_core::{AttributeDomain, AttributeRef, AttributeType, Mesh};
+use grapho_core::{
+    set_cell_edit, AttributeDomain, AttributeRef, AttributeType, CellEdit, Mesh, NodeId,
+    NodeParams, ParamValue, CELL_EDITS_PARAM,
+};
 
-pub(super) fn show_spreadsheet(ui: &mut Ui, mesh: Option<&Mesh>, domain: &mut AttributeDomain) {
+use super::GraphoApp;
+
+// Column and row of the cell being typed into, with the text so far.
+type ActiveCell = (usize, usize, String);
+
+// String list parameters of the inspected node, listed above the attributes so index
+// attributes such as Merge Files' `file` can be read back to the row they came from.
+pub(super) fn string_lists(params: &NodeParams) -> Vec<(&str, &[String])> {
+    params
+        .values
+        .iter()
+        .filter_map(|(key, value)| match value {
+            ParamValue::StringList(items) => Some((key.as_str(), items.as_slice())),
+            _ => None,
+        })
+        .collect()
+}
+
+// With `editable`, double-clicking a cell edits it in place; the committed value comes
+// back as a cell edit for the inspected node to store.
+pub(super) fn show_spreadsheet(
+    ui: &mut Ui,
+    mesh: Option<&Mesh>,
+    lists: &[(&str, &[String])],
+    domain: &mut AttributeDomain,
+    editable: bool,
+) -> Option<CellEdit> {
     ui.horizontal(|ui| {
         ui.label(
             RichText::new("Spreadsheet")
@@ -21,15 +50,26 @@ pub(super) fn show_spreadsheet(ui: &mut Ui, mesh: Option<&Mesh>, domain: &mut At
     });
     ui.separator();
 
+    for (name, items) in lists {
+        ui.collapsing(format!("{} ({})", name, items.len()), |ui| {
+            for (idx, item) in items.iter().enumerate() {
+                ui.monospace(format!("{:>3}  {}", idx, item));
+            }
+        });
+    }
+    if !lists.is_empty() {
+        ui.separator();
+    }
+
     let Some(mesh) = mesh else {
         ui.label("No mesh selected.");
-        return;
+        return None;
     };
 
     let count = mesh.attribute_domain_len(*domain);
     if count == 0 {
         ui.label("No elements in this domain.");
-        return;
+        return None;
     }
 
     let mut attrs: Vec<_> = mesh
@@ -41,7 +81,7 @@ pub(super) fn show_spreadsheet(ui: &mut Ui, mesh: Option<&Mesh>, domain: &mut At
 
     if attrs.is_empty() {
         ui.label("No attributes in this domain.");
-        return;
+        return None;
     }
 
     let max_rows = count.min(128);
@@ -58,6 +98,9 @@ pub(super) fn show_spreadsheet(ui: &mut Ui, mesh: Option<&Mesh>, domain: &mut At
     let idx_width = (idx_width as f32 * char_width + 12.0).max(36.0);
     let row_height = 24.0;
 
+    let edit_id = ui.id().with("spreadsheet_cell_edit");
+    let mut active: Option<ActiveCell> = ui.data(|data| data.get_temp(edit_id));
+    let mut committed = None;
     egui::ScrollArea::both()
         .auto_shrink([false, false])
         .show(ui, |ui| {
@@ -98,23 +141,61 @@ pub(super) fn show_spreadsheet(ui: &mut Ui, mesh: Option<&Mesh>, domain: &mut At
                             false,
                             &font_id,
                         );
-                        for column in &columns {
-                            let value =
-                                column.formatted.get(row).map(String::as_str).unwrap_or("-");
-                            draw_cell(
+                        for (index, column) in columns.iter().enumerate() {
+                            let width = column.pixel_width(char_width);
+                            if let Some((_, _, text)) = active
+                                .as_mut()
+                                .filter(|(col, cell_row, _)| *col == index && *cell_row == row)
+                            {
+                                let response = ui.add_sized(
+                                    [width, row_height],
+                                    egui::TextEdit::singleline(text).font(font_id.clone()),
+                                );
+                                if response.lost_focus() {
+                                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                                        committed =
+                                            text.trim().parse::<f32>().ok().map(|value| CellEdit {
+                                                domain: *domain,
+                                                attribute: column.attribute.clone(),
+                                                component: column.component,
+                                                element: row,
+                                                value,
+                                            });
+                                    }
+                                    active = None;
+                                } else if !response.has_focus() {
+                                    response.request_focus();
+                                }
+                                continue;
+                            }
+                            let value = column.formatted.get(row).map(String::as_str);
+                            let response = draw_cell(
                                 ui,
-                                value,
-                                column.pixel_width(char_width),
+                                value.unwrap_or("-"),
+                                width,
                                 row_height,
                                 Align2::RIGHT_CENTER,
                                 false,
                                 &font_id,
                             );
+                            if let Some(value) = value.filter(|_| editable) {
+                                if response
+                                    .on_hover_text("Double-click to edit")
+                                    .double_clicked()
+                                {
+                                    active = Some((index, row, value.trim().to_string()));
+                                }
+                            }
                         }
                         ui.end_row();
                     }
                 });
         });
+    ui.data_mut(|data| match active {
+        Some(cell) => data.insert_temp(edit_id, cell),
+        None => data.remove::<ActiveCell>(edit_id),
+    });
+    committed
 }
 
 fn attr_type_label(attr_type: AttributeType) -> &'static str {
@@ -129,6 +210,8 @@ fn attr_type_label(attr_type: AttributeType) -> &'static str {
 
 struct Column {
     header: String,
+    attribute: String,
+    component: Option<usize>,
     kind: ColumnKind,
     formatted: Vec<String>,
     width_chars: usize,
@@ -214,6 +297,8 @@ fn build_columns(
             AttributeRef::Float(data) => {
                 columns.push(Column {
                     header: format!("{} {}", attr.name, attr_type_label(attr.data_type)),
+                    attribute: attr.name.clone(),
+                    component: None,
                     kind: ColumnKind::Float(
                         (0..max_rows).map(|idx| data.get(idx).copied()).collect(),
                     ),
@@ -224,6 +309,8 @@ fn build_columns(
             AttributeRef::Int(data) => {
                 columns.push(Column {
                     header: format!("{} {}", attr.name, attr_type_label(attr.data_type)),
+                    attribute: attr.name.clone(),
+                    component: None,
                     kind: ColumnKind::Int(
                         (0..max_rows).map(|idx| data.get(idx).copied()).collect(),
                     ),
@@ -235,6 +322,8 @@ fn build_columns(
                 for (axis, idx) in [('x', 0usize), ('y', 1)] {
                     columns.push(Column {
                         header: format!("{}{}", attr.name, axis),
+                        attribute: attr.name.clone(),
+                        component: Some(idx),
                         kind: ColumnKind::Float(
                             (0..max_rows)
                                 .map(|row| data.get(row).map(|v| v[idx]))
@@ -249,6 +338,8 @@ fn build_columns(
                 for (axis, idx) in [('x', 0usize), ('y', 1), ('z', 2)] {
                     columns.push(Column {
                         header: format!("{}{}", attr.name, axis),
+                        attribute: attr.name.clone(),
+                        component: Some(idx),
                         kind: ColumnKind::Float(
                             (0..max_rows)
                                 .map(|row| data.get(row).map(|v| v[idx]))
@@ -263,6 +354,8 @@ fn build_columns(
                 for (axis, idx) in [('x', 0usize), ('y', 1), ('z', 2), ('w', 3)] {
                     columns.push(Column {
                         header: format!("{}{}", attr.name, axis),
+                        attribute: attr.name.clone(),
+                        component: Some(idx),
                         kind: ColumnKind::Float(
                             (0..max_rows)
                                 .map(|row| data.get(row).map(|v| v[idx]))
@@ -313,8 +406,13 @@ fn draw_cell(
     align: Align2,
     header: bool,
     font: &FontId,
-) {
-    let (rect, _) = ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
+) -> egui::Response {
+    let sense = if header {
+        egui::Sense::hover()
+    } else {
+        egui::Sense::click()
+    };
+    let (rect, response) = ui.allocate_exact_size(egui::vec2(width, height), sense);
     let bg = if header {
         Color32::from_rgb(48, 48, 48)
     } else {
@@ -342,4 +440,28 @@ fn draw_cell(
         font.clone(),
         Color32::from_rgb(230, 230, 230),
     );
+    response
+}
+
+impl GraphoApp {
+    // Edits are plain parameter changes, so they undo and save like any other.
+    pub(super) fn apply_spreadsheet_edit(&mut self, node_id: NodeId, edit: &CellEdit) {
+        let Some(node) = self.project.graph.node(node_id) else {
+            return;
+        };
+        let mut edits = node.params.get_string_list(CELL_EDITS_PARAM).to_vec();
+        set_cell_edit(&mut edits, edit);
+        let snapshot = self.snapshot_undo();
+        match self
+            .project
+            .graph
+            .set_param(node_id, CELL_EDITS_PARAM, ParamValue::StringList(edits))
+        {
+            Ok(()) => {
+                self.mark_eval_dirty();
+                self.queue_undo_snapshot(snapshot, false);
+            }
+            Err(err) => tracing::warn!("spreadsheet edit: {:?}", err),
+        }
+    }
 }
@@ -0,0 +1,136 @@
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use grapho_core::EvalReport;
+use render::ViewportStats;
+use rfd::FileDialog;
+
+use super::GraphoApp;
+
+// Frame and eval rows share one file; columns that don't apply to a row stay empty.
+const HEADER: &str = "time_s,kind,fps,frame_ms,vertices,triangles,meshes_drawn,meshes_culled,\
+cache_hits,cache_misses,cache_uploads,eval_ms,nodes_computed,eval_cache_hits,eval_cache_misses";
+
+pub(super) struct StatsRecorder {
+    writer: BufWriter<File>,
+    path: PathBuf,
+    started: Instant,
+    rows: u64,
+}
+
+impl StatsRecorder {
+    fn create(path: PathBuf) -> io::Result<Self> {
+        let mut writer = BufWriter::new(File::create(&path)?);
+        writeln!(writer, "{}", HEADER)?;
+        Ok(Self {
+            writer,
+            path,
+            started: Instant::now(),
+            rows: 0,
+        })
+    }
+
+    pub(super) fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub(super) fn rows(&self) -> u64 {
+        self.rows
+    }
+
+    fn record_frame(&mut self, stats: &ViewportStats) -> io::Result<()> {
+        self.rows += 1;
+        writeln!(
+            self.writer,
+            "{:.4},frame,{:.2},{:.3},{},{},{},{},{},{},{},,,,",
+            self.started.elapsed().as_secs_f64(),
+            stats.fps,
+            stats.frame_time_ms,
+            stats.vertex_count,
+            stats.triangle_count,
+            stats.meshes_drawn,
+            stats.meshes_culled,
+            stats.cache_hits,
+            stats.cache_misses,
+            stats.cache_uploads
+        )
+    }
+
+    fn record_eval(&mut self, eval_ms: f32, report: &EvalReport) -> io::Result<()> {
+        self.rows += 1;
+        writeln!(
+            self.writer,
+            "{:.4},eval,,,,,,,,,,{:.3},{},{},{}",
+            self.started.elapsed().as_secs_f64(),
+            eval_ms,
+            report.computed.len(),
+            report.cache_hits,
+            report.cache_misses
+        )
+    }
+}
+
+impl GraphoApp {
+    pub(super) fn start_stats_recording_dialog(&mut self) {
+        let Some(path) = FileDialog::new()
+            .add_filter("CSV", &["csv"])
+            .set_file_name("grapho_stats.csv")
+            .save_file()
+        else {
+            return;
+        };
+        match StatsRecorder::create(path) {
+            Ok(recorder) => {
+                tracing::info!("recording stats to {}", recorder.path().display());
+                self.stats_recorder = Some(recorder);
+            }
+            Err(err) => tracing::error!("failed to start stats recording: {}", err),
+        }
+    }
+
+    pub(super) fn stop_stats_recording(&mut self) {
+        let Some(mut recorder) = self.stats_recorder.take() else {
+            return;
+        };
+        match recorder.writer.flush() {
+            Ok(()) => tracing::info!(
+                "stats recording saved: {} rows in {}",
+                recorder.rows,
+                recorder.path.display()
+            ),
+            Err(err) => tracing::error!("failed to save stats recording: {}", err),
+        }
+    }
+
+    pub(super) fn record_frame_stats(&mut self) {
+        let (Some(recorder), Some(renderer)) = (&mut self.stats_recorder, &self.viewport_renderer)
+        else {
+            return;
+        };
+        let result = recorder.record_frame(&renderer.stats_snapshot());
+        self.finish_stats_write(result);
+    }
+
+    pub(super) fn record_eval_stats(&mut self) {
+        let (Some(recorder), Some(ms), Some(report)) = (
+            &mut self.stats_recorder,
+            self.last_eval_ms,
+            &self.last_eval_report,
+        ) else {
+            return;
+        };
+        let result = recorder.record_eval(ms, report);
+        self.finish_stats_write(result);
+    }
+
+    // A failed write (disk full, file removed) ends the recording instead of retrying every
+    // frame.
+    fn finish_stats_write(&mut self, result: io::Result<()>) {
+        if let Err(err) = result {
+            tracing::error!("stats recording stopped: {}", err);
+            self.stats_recorder = None;
+        }
+    }
+}
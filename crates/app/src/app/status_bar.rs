@@ -0,0 +1,90 @@
+use eframe::egui;
+
+use super::GraphoApp;
+
+impl GraphoApp {
+    pub(super) fn show_status_bar(&self, ctx: &egui::Context) {
+        egui::TopBottomPanel::bottom("status_bar")
+            .exact_height(22.0)
+            .show(ctx, |ui| {
+                ui.horizontal_centered(|ui| {
+                    let has_errors = self
+                        .last_eval_report
+                        .as_ref()
+                        .is_some_and(|report| !report.errors.is_empty());
+                    let (state, color) = if self.eval_dirty || self.background_eval_running() {
+                        ("Cooking", egui::Color32::from_rgb(230, 190, 90))
+                    } else if has_errors {
+                        ("Error", egui::Color32::from_rgb(230, 100, 90))
+                    } else {
+                        ("Idle", egui::Color32::from_rgb(140, 200, 140))
+                    };
+                    ui.colored_label(color, state);
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if let Some(node) = self
+                        .background_eval
+                        .as_ref()
+                        .and_then(|eval| eval.partial_node())
+                        .and_then(|id| self.project.graph.node(id))
+                    {
+                        ui.weak(format!("showing {}", node.name));
+                    }
+                    if self.safe_mode {
+                        ui.colored_label(egui::Color32::from_rgb(230, 190, 90), "Safe mode")
+                            .on_hover_text("Default graph and startup script were skipped");
+                    }
+                    let recovered = self
+                        .last_eval_report
+                        .as_ref()
+                        .map_or(0, |report| report.warnings.len());
+                    if recovered > 0 {
+                        ui.colored_label(
+                            egui::Color32::from_rgb(230, 190, 90),
+                            format!("{} passed through", recovered),
+                        )
+                        .on_hover_text(
+                            "Failed nodes forwarding their input under the error policy",
+                        );
+                    }
+                    if let Some(ms) = self.last_eval_ms {
+                        ui.label(format!("{:.1} ms", ms));
+                    }
+                    ui.separator();
+
+                    match self.last_display_counts {
+                        Some((points, tris)) => {
+                            ui.label(format!("{} pts  {} tris", points, tris));
+                        }
+                        None => {
+                            ui.label("No mesh");
+                        }
+                    }
+                    ui.separator();
+
+                    let selected = self
+                        .node_graph
+                        .selected_node_id()
+                        .and_then(|id| self.project.graph.node(id))
+                        .map(|node| node.name.as_str())
+                        .unwrap_or("None");
+                    ui.label(format!("Selected: {}", selected));
+                    ui.separator();
+
+                    ui.label(format!(
+                        "Camera: {:.2}",
+                        self.project.settings.camera.distance
+                    ));
+                    ui.separator();
+
+                    match self.last_saved {
+                        Some(saved) => {
+                            ui.label(format!("Saved {}s ago", saved.elapsed().as_secs()));
+                        }
+                        None => {
+                            ui.label("Not saved");
+                        }
+                    }
+                });
+            });
+    }
+}
@@ -1,38 +1,42 @@
 use eframe::egui;
-use grapho_core::ShadingMode;
+use grapho_core::{
+    builtin_kind_from_name, BackgroundMode, BuiltinNodeKind, CameraMode, CullMode, ShadingMode,
+    UiTheme,
+};
 
+use super::accessibility::{
+    open_menu_from_keyboard, paint_focus_ring, panel_fill, panel_has_focus, take_menu_mnemonic,
+    theme_visuals, toolbar_toggle, FocusPanel,
+};
+use super::actions::AppAction;
+use super::node_help::NodeHelpPanel;
 use super::node_info::NodeInfoPanel;
-use super::spreadsheet::show_spreadsheet;
+use super::spreadsheet::{show_spreadsheet, string_lists};
 use super::wrangle_help::WrangleHelpPanel;
 use super::GraphoApp;
 
 impl eframe::App for GraphoApp {
     fn update(&mut self, ctx: &egui::Context, frame: &mut eframe::Frame) {
         self.sync_wgpu_renderer(frame);
+        self.apply_theme(ctx);
+        self.configure_touch_input(ctx);
+        #[cfg(feature = "space-mouse")]
+        self.poll_space_mouse(ctx);
+        if self.viewer_mode {
+            self.show_viewer_mode(ctx);
+            return;
+        }
         let pointer_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Primary));
         if ctx.input(|i| i.pointer.any_released()) {
             self.flush_pending_undo();
         }
-        if !ctx.wants_keyboard_input() {
-            let undo_pressed = ctx.input(|i| {
-                i.key_pressed(egui::Key::Z) && i.modifiers.command && !i.modifiers.shift
-            });
-            let redo_pressed = ctx.input(|i| {
-                (i.key_pressed(egui::Key::Z) && i.modifiers.command && i.modifiers.shift)
-                    || (i.key_pressed(egui::Key::Y) && i.modifiers.command)
-            });
-            if undo_pressed {
-                self.try_undo();
-            } else if redo_pressed {
-                self.try_redo();
-            }
-        }
+        self.handle_shortcuts(ctx);
         let mut undo_pushed = false;
         let tab_pressed = ctx.input(|i| i.key_pressed(egui::Key::Tab));
         if tab_pressed {
             let hover_pos = ctx.input(|i| i.pointer.hover_pos());
             if let (Some(rect), Some(pos)) = (self.last_node_graph_rect, hover_pos) {
-                if rect.contains(pos) && !ctx.wants_keyboard_input() {
+                if rect.contains(pos) && (!ctx.wants_keyboard_input() || panel_has_focus(ctx)) {
                     ctx.input_mut(|i| {
                         i.consume_key(egui::Modifiers::NONE, egui::Key::Tab);
                     });
@@ -40,13 +44,25 @@ impl eframe::App for GraphoApp {
                 }
             }
         }
+        self.poll_collab(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.poll_background_eval(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.record_frame_stats();
+        #[cfg(not(target_arch = "wasm32"))]
+        self.update_crash_snapshot();
+        self.show_status_bar(ctx);
+        let mnemonic = take_menu_mnemonic(ctx);
         egui::TopBottomPanel::top("top_bar").show(ctx, |ui| {
             egui::MenuBar::new().ui(ui, |ui| {
-                ui.menu_button("File", |ui| {
+                let file_menu = ui.menu_button("File", |ui| {
                     if ui.button("New").clicked() {
                         self.new_project();
                         ui.close();
                     }
+                    ui.menu_button("Examples", |ui| {
+                        self.show_examples_menu(ui);
+                    });
 
                     #[cfg(not(target_arch = "wasm32"))]
                     {
@@ -56,15 +72,7 @@ impl eframe::App for GraphoApp {
                         }
 
                         if ui.button("Save").clicked() {
-                            if let Some(path) = self.project_path.clone() {
-                                if let Err(err) = self.save_project_to(&path) {
-                                    tracing::error!("failed to save project: {}", err);
-                                } else {
-                                    tracing::info!("project saved");
-                                }
-                            } else {
-                                tracing::warn!("no project path set; use Save As");
-                            }
+                            self.save_project();
                             ui.close();
                         }
 
@@ -72,6 +80,11 @@ impl eframe::App for GraphoApp {
                             self.save_project_dialog();
                             ui.close();
                         }
+
+                        if ui.button("Export Graph Image...").clicked() {
+                            self.export_graph_image_dialog();
+                            ui.close();
+                        }
                     }
 
                     #[cfg(target_arch = "wasm32")]
@@ -79,10 +92,51 @@ impl eframe::App for GraphoApp {
                         ui.add_enabled(false, egui::Button::new("Open..."));
                         ui.add_enabled(false, egui::Button::new("Save"));
                         ui.add_enabled(false, egui::Button::new("Save As..."));
+                        ui.add_enabled(false, egui::Button::new("Export Graph Image..."));
                         ui.label("File I/O is not available in web builds.");
                     }
+
+                    ui.separator();
+                    if ui.button("Command Palette...").clicked() {
+                        self.run_action(AppAction::CommandPalette);
+                        ui.close();
+                    }
+                });
+                if mnemonic == Some(egui::Key::F) {
+                    open_menu_from_keyboard(&file_menu.response);
+                }
+                if self.take_focus_request(FocusPanel::MenuBar) {
+                    file_menu.response.request_focus();
+                }
+
+                #[cfg(not(target_arch = "wasm32"))]
+                {
+                    let sync_menu = ui.menu_button("Sync", |ui| {
+                        self.show_collab_menu(ui);
+                    });
+                    if mnemonic == Some(egui::Key::S) {
+                        open_menu_from_keyboard(&sync_menu.response);
+                    }
+                }
+
+                ui.menu_button("Bookmarks", |ui| {
+                    self.show_bookmarks_menu(ui);
                 });
 
+                let help_menu = ui.menu_button("Help", |ui| {
+                    if ui.button("Show Tour").clicked() {
+                        self.run_action(AppAction::ShowTour);
+                        ui.close();
+                    }
+                    if ui.button("Node Self-Tests").clicked() {
+                        self.run_action(AppAction::RunNodeTests);
+                        ui.close();
+                    }
+                });
+                if mnemonic == Some(egui::Key::H) {
+                    open_menu_from_keyboard(&help_menu.response);
+                }
+
                 ui.separator();
                 ui.label("grapho");
                 ui.separator();
@@ -94,16 +148,62 @@ impl eframe::App for GraphoApp {
                     &mut self.project.settings.panels.show_spreadsheet,
                     "Spreadsheet",
                 );
+                ui.checkbox(
+                    &mut self.project.settings.panels.show_project_params,
+                    "Project Params",
+                );
+                ui.checkbox(&mut self.project.settings.panels.show_uv_view, "UV View");
+                ui.checkbox(
+                    &mut self.project.settings.panels.show_dope_sheet,
+                    "Dope Sheet",
+                );
+                ui.checkbox(
+                    &mut self.project.settings.panels.show_graph_stats,
+                    "Graph Stats",
+                );
+                ui.checkbox(
+                    &mut self.project.settings.panels.show_dependencies,
+                    "Dependencies",
+                );
+                ui.checkbox(
+                    &mut self.project.settings.panels.show_node_audit,
+                    "Node Usage",
+                );
                 ui.checkbox(&mut self.project.settings.panels.show_debug, "Debug");
                 ui.checkbox(&mut self.project.settings.panels.show_console, "Console");
+                ui.separator();
+                let mut high_contrast = self.project.settings.theme == UiTheme::HighContrast;
+                if ui.checkbox(&mut high_contrast, "High Contrast").changed() {
+                    self.toggle_high_contrast();
+                }
+                ui.separator();
+                self.show_toolbar_transport(ui);
             });
         });
 
-        if self.project.settings.panels.show_debug || self.project.settings.panels.show_console {
+        if self.project.settings.panels.show_debug
+            || self.project.settings.panels.show_console
+            || self.project.settings.panels.show_project_params
+        {
             egui::SidePanel::right("side_panels")
                 .resizable(true)
                 .default_width(280.0)
                 .show(ctx, |ui| {
+                    if self.project.settings.panels.show_project_params {
+                        egui::CollapsingHeader::new("Project Parameters")
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                let snapshot = self.snapshot_undo();
+                                if self.show_project_params(ui) {
+                                    self.mark_eval_dirty();
+                                    if !undo_pushed {
+                                        self.queue_undo_snapshot(snapshot, pointer_down);
+                                        undo_pushed = true;
+                                    }
+                                }
+                            });
+                    }
+
                     if self.project.settings.panels.show_debug {
                         egui::CollapsingHeader::new("Debug")
                             .default_open(true)
@@ -118,6 +218,13 @@ impl eframe::App for GraphoApp {
                                     .custom_formatter(|value, _| format!("{:.0}%", value * 100.0)),
                                 );
 
+                                #[cfg(feature = "space-mouse")]
+                                {
+                                    ui.separator();
+                                    ui.label("Space mouse");
+                                    self.show_space_mouse_settings(ui);
+                                }
+
                                 ui.separator();
                                 ui.label("Viewport overlays");
                                 ui.checkbox(
@@ -147,11 +254,41 @@ impl eframe::App for GraphoApp {
                                             .range(0.01..=10.0),
                                         );
                                     });
+                                    ui.horizontal(|ui| {
+                                        ui.label("Normal density");
+                                        ui.add(
+                                            egui::Slider::new(
+                                                &mut self
+                                                    .project
+                                                    .settings
+                                                    .render_debug
+                                                    .normal_density,
+                                                0.01..=1.0,
+                                            )
+                                            .logarithmic(true),
+                                        )
+                                        .on_hover_text(
+                                            "Fraction of vertices drawn; very dense meshes \
+                                             are thinned further",
+                                        );
+                                    });
                                 }
                                 ui.checkbox(
                                     &mut self.project.settings.render_debug.show_bounds,
                                     "Bounds",
                                 );
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.outline_selection,
+                                    "Selection outline",
+                                );
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.show_prim_under_cursor,
+                                    "Prim under cursor",
+                                );
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.show_scale_reference,
+                                    "1 m reference cube",
+                                );
                                 ui.checkbox(
                                     &mut self.project.settings.render_debug.show_points,
                                     "Points",
@@ -172,25 +309,99 @@ impl eframe::App for GraphoApp {
                                     &mut self.project.settings.render_debug.key_shadows,
                                     "Key shadows",
                                 );
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.contact_shadow,
+                                    "Contact shadow",
+                                );
                                 ui.checkbox(
                                     &mut self.project.settings.render_debug.show_stats,
                                     "Stats overlay",
                                 );
+                                // Opacity lives in the scene, so a change rebuilds it.
+                                let render_debug = &mut self.project.settings.render_debug;
+                                let opacity_changed = ui
+                                    .add(
+                                        egui::Slider::new(
+                                            &mut render_debug.mesh_opacity,
+                                            0.05..=1.0,
+                                        )
+                                        .text("Opacity"),
+                                    )
+                                    .changed()
+                                    | ui
+                                        .add(
+                                            egui::Slider::new(
+                                                &mut render_debug.template_opacity,
+                                                0.0..=1.0,
+                                            )
+                                            .text("Template ghost"),
+                                        )
+                                        .changed();
+                                if opacity_changed {
+                                    self.mark_eval_dirty();
+                                }
+
+                                ui.separator();
+                                ui.label("Background");
+                                let render_debug = &mut self.project.settings.render_debug;
+                                ui.horizontal(|ui| {
+                                    for (mode, label) in [
+                                        (BackgroundMode::Solid, "Solid"),
+                                        (BackgroundMode::Gradient, "Gradient"),
+                                    ] {
+                                        ui.radio_value(
+                                            &mut render_debug.background_mode,
+                                            mode,
+                                            label,
+                                        );
+                                    }
+                                });
+                                match render_debug.background_mode {
+                                    BackgroundMode::Solid => {
+                                        ui.horizontal(|ui| {
+                                            ui.color_edit_button_srgb(
+                                                &mut render_debug.background_color,
+                                            );
+                                            ui.label("Color");
+                                        });
+                                    }
+                                    BackgroundMode::Gradient => {
+                                        ui.horizontal(|ui| {
+                                            ui.color_edit_button_srgb(
+                                                &mut render_debug.background_top,
+                                            );
+                                            ui.label("Top");
+                                        });
+                                        ui.horizontal(|ui| {
+                                            ui.color_edit_button_srgb(
+                                                &mut render_debug.background_bottom,
+                                            );
+                                            ui.label("Bottom");
+                                        });
+                                    }
+                                }
 
                                 ui.separator();
                                 ui.label("Shading");
                                 let shading = &mut self.project.settings.render_debug.shading_mode;
+                                let previous_shading = *shading;
                                 egui::ComboBox::from_label("Mode")
                                     .selected_text(match shading {
                                         ShadingMode::Lit => "Lit",
                                         ShadingMode::Normals => "Normals",
                                         ShadingMode::Depth => "Depth",
+                                        ShadingMode::Attribute => "Attribute",
+                                        ShadingMode::FlatShaded => "Flat shaded",
+                                        ShadingMode::Wireframe => "Wireframe",
                                     })
                                     .show_ui(ui, |ui| {
                                         for (mode, label) in [
                                             (ShadingMode::Lit, "Lit"),
                                             (ShadingMode::Normals, "Normals"),
                                             (ShadingMode::Depth, "Depth"),
+                                            (ShadingMode::Attribute, "Attribute"),
+                                            (ShadingMode::FlatShaded, "Flat shaded"),
+                                            (ShadingMode::Wireframe, "Wireframe"),
                                         ] {
                                             if ui
                                                 .selectable_label(*shading == mode, label)
@@ -229,6 +440,60 @@ impl eframe::App for GraphoApp {
                                             near + 0.01;
                                     }
                                 }
+                                let attribute_mode = self.project.settings.render_debug.shading_mode
+                                    == ShadingMode::Attribute;
+                                let mut rebuild_scene =
+                                    attribute_mode != (previous_shading == ShadingMode::Attribute);
+                                if attribute_mode {
+                                    rebuild_scene |= self.show_color_attribute_picker(ui);
+                                }
+                                if rebuild_scene {
+                                    self.eval_dirty = true;
+                                }
+
+                                let cull_mode = &mut self.project.settings.render_debug.cull_mode;
+                                egui::ComboBox::from_label("Culling")
+                                    .selected_text(match cull_mode {
+                                        CullMode::None => "None",
+                                        CullMode::Back => "Back faces",
+                                        CullMode::Front => "Front faces",
+                                    })
+                                    .show_ui(ui, |ui| {
+                                        for (mode, label) in [
+                                            (CullMode::None, "None"),
+                                            (CullMode::Back, "Back faces"),
+                                            (CullMode::Front, "Front faces"),
+                                        ] {
+                                            if ui
+                                                .selectable_label(*cull_mode == mode, label)
+                                                .clicked()
+                                            {
+                                                *cull_mode = mode;
+                                            }
+                                        }
+                                    });
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.double_sided,
+                                    "Double-sided lighting",
+                                );
+                                ui.checkbox(
+                                    &mut self.project.settings.render_debug.flat_shading,
+                                    "Flat shading",
+                                );
+
+                                ui.separator();
+                                ui.label("Clipping");
+                                self.show_clipping_settings(ui);
+
+                                if !self.debug_channel_names.is_empty() {
+                                    ui.separator();
+                                    ui.label("Debug channels");
+                                    self.show_debug_channel_settings(ui);
+                                }
+
+                                ui.separator();
+                                ui.label("Surface shader");
+                                self.show_surface_shader_editor(ui);
 
                                 ui.separator();
                                 ui.label("Evaluation");
@@ -242,9 +507,49 @@ impl eframe::App for GraphoApp {
                                     }
                                 }
                                 if ui.button("Recompute now").clicked() {
-                                    self.eval_dirty = false;
-                                    self.last_param_change = None;
-                                    self.evaluate_graph();
+                                    self.run_action(AppAction::RecomputeNow);
+                                }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                {
+                                    let recording = self.stats_recorder.as_ref().map(|recorder| {
+                                        (recorder.rows(), recorder.path().display().to_string())
+                                    });
+                                    match recording {
+                                        Some((rows, path)) => {
+                                            if ui
+                                                .button(format!("Stop stats CSV ({} rows)", rows))
+                                                .on_hover_text(path)
+                                                .clicked()
+                                            {
+                                                self.stop_stats_recording();
+                                            }
+                                        }
+                                        None => {
+                                            if ui
+                                                .button("Record stats CSV...")
+                                                .on_hover_text(
+                                                    "Log viewport stats per frame and eval timings",
+                                                )
+                                                .clicked()
+                                            {
+                                                self.start_stats_recording_dialog();
+                                            }
+                                        }
+                                    }
+                                }
+                                #[cfg(not(target_arch = "wasm32"))]
+                                if let Some(eval) = &self.background_eval {
+                                    ui.horizontal(|ui| {
+                                        ui.spinner();
+                                        let state =
+                                            if eval.cancelled() { "Cancelling" } else { "Cooking" };
+                                        ui.label(format!(
+                                            "{}: {} nodes, {:.1} s",
+                                            state,
+                                            eval.cooked_nodes(),
+                                            eval.elapsed().as_secs_f32()
+                                        ));
+                                    });
                                 }
 
                                 if let Some(report) = &self.last_eval_report {
@@ -284,6 +589,9 @@ impl eframe::App for GraphoApp {
                                             let reason = match entry.reason {
                                                 grapho_core::DirtyReason::NewNode => "new",
                                                 grapho_core::DirtyReason::ParamChanged => "param",
+                                                grapho_core::DirtyReason::ReferenceChanged => {
+                                                    "reference"
+                                                }
                                                 grapho_core::DirtyReason::UpstreamChanged => {
                                                     "upstream"
                                                 }
@@ -337,14 +645,9 @@ impl eframe::App for GraphoApp {
                 let full = ui.available_rect_before_wrap();
                 let ratio = self.project.settings.viewport_split.clamp(0.2, 0.8);
                 let left_width = full.width() * ratio;
-                let left = egui::Rect::from_min_size(
-                    full.min,
-                    egui::vec2(left_width, full.height()),
-                );
-                let right = egui::Rect::from_min_max(
-                    egui::pos2(left.max.x, full.min.y),
-                    full.max,
-                );
+                let left =
+                    egui::Rect::from_min_size(full.min, egui::vec2(left_width, full.height()));
+                let right = egui::Rect::from_min_max(egui::pos2(left.max.x, full.min.y), full.max);
                 (left, right)
             };
 
@@ -362,16 +665,13 @@ impl eframe::App for GraphoApp {
                     if total_height <= min_viewport + min_sheet + separator_height {
                         viewport_height = total_height.max(min_viewport);
                     }
-                    let sheet_height =
-                        (total_height - viewport_height - separator_height).max(0.0);
+                    let sheet_height = (total_height - viewport_height - separator_height).max(0.0);
                     (viewport_height, sheet_height)
                 } else {
                     (total_height.max(min_viewport), 0.0)
                 };
-                let viewport_rect = egui::Rect::from_min_size(
-                    full.min,
-                    egui::vec2(full.width(), viewport_height),
-                );
+                let viewport_rect =
+                    egui::Rect::from_min_size(full.min, egui::vec2(full.width(), viewport_height));
                 let separator_rect = egui::Rect::from_min_size(
                     egui::pos2(full.min.x, viewport_rect.max.y),
                     egui::vec2(full.width(), separator_height),
@@ -406,61 +706,12 @@ impl eframe::App for GraphoApp {
                 }
 
                 ui.scope_builder(egui::UiBuilder::new().max_rect(viewport_rect), |ui| {
-                    let available = ui.available_size();
-                    let (rect, response) =
-                        ui.allocate_exact_size(available, egui::Sense::click_and_drag());
-                    self.handle_viewport_input(&response);
-                    ui.painter()
-                        .rect_filled(rect, 0.0, egui::Color32::from_rgb(28, 28, 28));
-                    if let Some(renderer) = &self.viewport_renderer {
-                        let camera = self.camera_state();
-                        let debug = self.viewport_debug();
-                        let callback = renderer.paint_callback(rect, camera, debug);
-                        ui.painter().add(egui::Shape::Callback(callback));
-
-                        if self.project.settings.render_debug.show_stats {
-                            let stats = renderer.stats_snapshot();
-                            let text = format!(
-                                "FPS: {:.1}\nFrame: {:.2} ms\nVerts: {}\nTris: {}\nMeshes: {}\nCache: {} hits / {} misses / {} uploads",
-                                stats.fps,
-                                stats.frame_time_ms,
-                                stats.vertex_count,
-                                stats.triangle_count,
-                                stats.mesh_count,
-                                stats.cache_hits,
-                                stats.cache_misses,
-                                stats.cache_uploads
-                            );
-                            let font_id = egui::FontId::monospace(12.0);
-                            let galley = ui.fonts_mut(|f| {
-                                f.layout_no_wrap(text.clone(), font_id.clone(), egui::Color32::WHITE)
-                            });
-                            let padding = egui::vec2(6.0, 4.0);
-                            let bg_rect = egui::Rect::from_min_size(
-                                rect.min + egui::vec2(8.0, 8.0),
-                                galley.size() + padding * 2.0,
-                            );
-                            let painter = ui.painter();
-                            painter.rect_filled(
-                                bg_rect,
-                                4.0,
-                                egui::Color32::from_black_alpha(160),
-                            );
-                            painter.galley(bg_rect.min + padding, galley, egui::Color32::WHITE);
-                        }
-                    } else {
-                        ui.painter().text(
-                            rect.center(),
-                            egui::Align2::CENTER_CENTER,
-                            "WGPU not ready",
-                            egui::FontId::proportional(14.0),
-                            egui::Color32::GRAY,
-                        );
-                    }
+                    let rect = self.show_viewport_canvas(ui);
+                    self.last_viewport_rect = Some(rect);
 
                     let toolbar_rect = egui::Rect::from_min_size(
                         egui::pos2(rect.max.x - 36.0, rect.min.y + 8.0),
-                        egui::vec2(28.0, 160.0),
+                        egui::vec2(28.0, 200.0),
                     );
                     ui.scope_builder(egui::UiBuilder::new().max_rect(toolbar_rect), |ui| {
                         ui.set_min_width(toolbar_rect.width());
@@ -473,57 +724,65 @@ impl eframe::App for GraphoApp {
                         ui.spacing_mut().item_spacing = egui::vec2(6.0, 6.0);
 
                         let debug = &mut self.project.settings.render_debug;
-                        if ui
-                            .add(egui::Button::new("G").selected(debug.show_grid))
-                            .on_hover_text("Grid")
-                            .clicked()
-                        {
-                            debug.show_grid = !debug.show_grid;
-                        }
-                        if ui
-                            .add(egui::Button::new("A").selected(debug.show_axes))
-                            .on_hover_text("Axes")
-                            .clicked()
-                        {
-                            debug.show_axes = !debug.show_axes;
-                        }
-                        if ui
-                            .add(egui::Button::new("P").selected(debug.show_points))
-                            .on_hover_text("Points")
-                            .clicked()
-                        {
-                            debug.show_points = !debug.show_points;
-                        }
-                        if ui
-                            .add(egui::Button::new("S").selected(debug.key_shadows))
-                            .on_hover_text("Key shadows")
-                            .clicked()
-                        {
-                            debug.key_shadows = !debug.key_shadows;
-                        }
+                        toolbar_toggle(ui, "G", "Grid", &mut debug.show_grid);
+                        toolbar_toggle(ui, "A", "Axes", &mut debug.show_axes);
+                        toolbar_toggle(ui, "P", "Points", &mut debug.show_points);
+                        toolbar_toggle(ui, "S", "Key shadows", &mut debug.key_shadows);
+                        let camera = &mut self.project.settings.camera;
+                        let mut fly = camera.mode == CameraMode::Fly;
+                        toolbar_toggle(ui, "F", "Fly camera (`)", &mut fly);
+                        camera.mode = if fly {
+                            CameraMode::Fly
+                        } else {
+                            CameraMode::Orbit
+                        };
                     });
                 });
 
                 if sheet_height > 0.0 {
                     ui.scope_builder(egui::UiBuilder::new().max_rect(sheet_rect), |ui| {
-                        ui.painter().rect_filled(
-                            sheet_rect,
-                            0.0,
-                            egui::Color32::from_rgb(38, 38, 38),
-                        );
+                        let theme = self.project.settings.theme;
+                        let fill = panel_fill(theme, egui::Color32::from_rgb(38, 38, 38));
+                        ui.painter().rect_filled(sheet_rect, 0.0, fill);
+                        let focus =
+                            self.panel_focus_anchor(ui, FocusPanel::Spreadsheet, sheet_rect);
                         let frame = egui::Frame::NONE
-                            .fill(egui::Color32::from_rgb(38, 38, 38))
+                            .fill(fill)
                             .inner_margin(egui::Margin::symmetric(12, 10));
                         frame.show(ui, |ui| {
                             let style = ui.style_mut();
-                            style.visuals = egui::Visuals::dark();
-                            style.visuals.override_text_color =
-                                Some(egui::Color32::from_rgb(220, 220, 220));
+                            style.visuals = theme_visuals(theme);
+                            if theme == UiTheme::Dark {
+                                style.visuals.override_text_color =
+                                    Some(egui::Color32::from_rgb(220, 220, 220));
+                            }
                             style.spacing.item_spacing = egui::vec2(10.0, 6.0);
-                            let selected = self.node_graph.selected_node_id();
-                            let mesh = selected.and_then(|id| self.eval_state.mesh_for_node(id));
-                            show_spreadsheet(ui, mesh, &mut self.spreadsheet_domain);
+                            let mut domain = self.spreadsheet_domain;
+                            let lists = self
+                                .node_graph
+                                .selected_node_id()
+                                .and_then(|id| self.project.graph.node(id))
+                                .map(|node| string_lists(&node.params))
+                                .unwrap_or_default();
+                            let editable = self.node_graph.selected_node_id().filter(|id| {
+                                self.project.graph.node(*id).is_some_and(|node| {
+                                    builtin_kind_from_name(&node.name)
+                                        == Some(BuiltinNodeKind::AttributeEdit)
+                                })
+                            });
+                            let edit = show_spreadsheet(
+                                ui,
+                                self.inspected_mesh(),
+                                &lists,
+                                &mut domain,
+                                editable.is_some(),
+                            );
+                            self.spreadsheet_domain = domain;
+                            if let (Some(node_id), Some(edit)) = (editable, edit) {
+                                self.apply_spreadsheet_edit(node_id, &edit);
+                            }
                         });
+                        paint_focus_ring(ui, &focus);
                     });
                 }
             });
@@ -603,49 +862,47 @@ impl eframe::App for GraphoApp {
             }
 
             if params_ratio > 0.0 {
-                ui.painter().rect_filled(
-                    params_rect,
-                    0.0,
-                    egui::Color32::from_rgb(55, 55, 55),
-                );
+                let theme = self.project.settings.theme;
+                let fill = panel_fill(theme, egui::Color32::from_rgb(55, 55, 55));
+                ui.painter().rect_filled(params_rect, 0.0, fill);
                 ui.scope_builder(egui::UiBuilder::new().max_rect(params_rect), |ui| {
+                    let focus = self.panel_focus_anchor(ui, FocusPanel::Parameters, params_rect);
                     let frame = egui::Frame::NONE
-                        .fill(egui::Color32::from_rgb(55, 55, 55))
+                        .fill(fill)
                         .inner_margin(egui::Margin::symmetric(16, 12));
                     frame.show(ui, |ui| {
                         let style = ui.style_mut();
-                        style.visuals = egui::Visuals::dark();
-                        let text_color = egui::Color32::from_rgb(230, 230, 230);
-                        style.visuals.override_text_color = Some(text_color);
-                        style.visuals.widgets.inactive.fg_stroke.color = text_color;
-                        style.visuals.widgets.hovered.fg_stroke.color = text_color;
-                        style.visuals.widgets.active.fg_stroke.color = text_color;
-                        style.visuals.widgets.inactive.bg_fill =
-                            egui::Color32::from_rgb(60, 60, 60);
-                        style.visuals.widgets.hovered.bg_fill =
-                            egui::Color32::from_rgb(75, 75, 75);
-                        style.visuals.widgets.active.bg_fill =
-                            egui::Color32::from_rgb(90, 90, 90);
-                        style.visuals.widgets.inactive.bg_stroke.color =
-                            egui::Color32::from_rgb(85, 85, 85);
-                        style.visuals.widgets.hovered.bg_stroke.color =
-                            egui::Color32::from_rgb(105, 105, 105);
-                        style.visuals.widgets.active.bg_stroke.color =
-                            egui::Color32::from_rgb(125, 125, 125);
-                        style.visuals.extreme_bg_color = egui::Color32::from_rgb(45, 45, 45);
-                        style.visuals.faint_bg_color = egui::Color32::from_rgb(55, 55, 55);
-                        style.text_styles.insert(
-                            egui::TextStyle::Body,
-                            egui::FontId::proportional(16.0),
-                        );
-                        style.text_styles.insert(
-                            egui::TextStyle::Button,
-                            egui::FontId::proportional(16.0),
-                        );
-                        style.text_styles.insert(
-                            egui::TextStyle::Heading,
-                            egui::FontId::proportional(18.0),
-                        );
+                        style.visuals = theme_visuals(theme);
+                        if theme == UiTheme::Dark {
+                            let text_color = egui::Color32::from_rgb(230, 230, 230);
+                            style.visuals.override_text_color = Some(text_color);
+                            style.visuals.widgets.inactive.fg_stroke.color = text_color;
+                            style.visuals.widgets.hovered.fg_stroke.color = text_color;
+                            style.visuals.widgets.active.fg_stroke.color = text_color;
+                            style.visuals.widgets.inactive.bg_fill =
+                                egui::Color32::from_rgb(60, 60, 60);
+                            style.visuals.widgets.hovered.bg_fill =
+                                egui::Color32::from_rgb(75, 75, 75);
+                            style.visuals.widgets.active.bg_fill =
+                                egui::Color32::from_rgb(90, 90, 90);
+                            style.visuals.widgets.inactive.bg_stroke.color =
+                                egui::Color32::from_rgb(85, 85, 85);
+                            style.visuals.widgets.hovered.bg_stroke.color =
+                                egui::Color32::from_rgb(105, 105, 105);
+                            style.visuals.widgets.active.bg_stroke.color =
+                                egui::Color32::from_rgb(125, 125, 125);
+                            style.visuals.extreme_bg_color = egui::Color32::from_rgb(45, 45, 45);
+                            style.visuals.faint_bg_color = egui::Color32::from_rgb(55, 55, 55);
+                        }
+                        style
+                            .text_styles
+                            .insert(egui::TextStyle::Body, egui::FontId::proportional(16.0));
+                        style
+                            .text_styles
+                            .insert(egui::TextStyle::Button, egui::FontId::proportional(16.0));
+                        style
+                            .text_styles
+                            .insert(egui::TextStyle::Heading, egui::FontId::proportional(18.0));
                         style.spacing.item_spacing = egui::vec2(10.0, 8.0);
                         style.spacing.interact_size = egui::vec2(44.0, 26.0);
 
@@ -654,11 +911,21 @@ impl eframe::App for GraphoApp {
                             .max_height(max_height)
                             .show(ui, |ui| {
                                 let snapshot = self.snapshot_undo();
-                                if self
+                                let output = self
                                     .node_graph
-                                    .show_inspector(ui, &mut self.project.graph)
-                                {
-                                    self.mark_eval_dirty();
+                                    .inspected_node_id()
+                                    .and_then(|node_id| self.node_output_mesh(node_id));
+                                if self.node_graph.show_inspector(
+                                    ui,
+                                    &mut self.project.graph,
+                                    output,
+                                    self.project.settings.timeline.frame,
+                                ) {
+                                    if self.node_graph.take_seed_rolled() {
+                                        self.preview_seed_roll(ui.ctx());
+                                    } else {
+                                        self.mark_eval_dirty();
+                                    }
                                     if !undo_pushed {
                                         self.queue_undo_snapshot(snapshot, pointer_down);
                                         undo_pushed = true;
@@ -666,18 +933,27 @@ impl eframe::App for GraphoApp {
                                 }
                             });
                     });
+                    paint_focus_ring(ui, &focus);
                 });
             }
 
             ui.scope_builder(egui::UiBuilder::new().max_rect(graph_rect), |ui| {
                 let snapshot = self.snapshot_undo();
-                self.node_graph
-                    .show(ui, &mut self.project.graph, &mut self.eval_dirty);
+                let focus = self.panel_focus_anchor(ui, FocusPanel::NodeGraph, graph_rect);
+                self.node_graph.set_keyboard_focus(focus.has_focus());
+                self.node_graph.show(
+                    ui,
+                    &mut self.project.graph,
+                    &mut self.project.settings.graph_backdrops,
+                    &mut self.eval_dirty,
+                );
+                self.paint_graph_frames(ui);
                 let layout_moved = self.node_graph.take_layout_changed();
                 if (self.node_graph.take_changed() || layout_moved) && !undo_pushed {
                     self.queue_undo_snapshot(snapshot, pointer_down);
                     undo_pushed = true;
                 }
+                paint_focus_ring(ui, &focus);
             });
             self.last_node_graph_rect = Some(right_rect);
         });
@@ -689,6 +965,14 @@ impl eframe::App for GraphoApp {
             });
         }
 
+        if let Some(request) = self.node_graph.take_node_help_request() {
+            self.node_help_panel = Some(NodeHelpPanel {
+                node_id: request.node_id,
+                screen_pos: request.screen_pos,
+                open: true,
+            });
+        }
+
         let middle_down = ctx.input(|i| i.pointer.button_down(egui::PointerButton::Middle));
         if middle_down {
             let hover_pos = ctx.input(|i| i.pointer.hover_pos().or_else(|| i.pointer.latest_pos()));
@@ -707,6 +991,10 @@ impl eframe::App for GraphoApp {
             self.held_info_panel = None;
         }
 
+        if let Some(node_id) = self.node_graph.take_cook_request() {
+            self.cook_for_inspection(node_id);
+        }
+
         if let Some(request) = self.node_graph.take_info_request() {
             self.info_panel = Some(NodeInfoPanel {
                 node_id: request.node_id,
@@ -727,6 +1015,31 @@ impl eframe::App for GraphoApp {
         self.show_wrangle_help_panel(ctx, &mut wrangle_help_panel);
         self.wrangle_help_panel = wrangle_help_panel;
 
+        let mut node_help_panel = self.node_help_panel.take();
+        self.show_node_help_panel(ctx, &mut node_help_panel);
+        self.node_help_panel = node_help_panel;
+
+        let mut node_tests_panel = self.node_tests_panel.take();
+        self.show_node_tests_panel(ctx, &mut node_tests_panel);
+        self.node_tests_panel = node_tests_panel;
+
+        self.show_uv_view(ctx);
+        self.show_dope_sheet(ctx);
+        self.show_graph_stats(ctx);
+        self.show_dependencies(ctx);
+        self.show_node_audit(ctx);
+        self.show_command_palette(ctx);
+        self.show_onboarding(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.show_crash_restore(ctx);
+
+        self.advance_timeline(ctx);
         self.evaluate_if_needed();
+        self.sync_selection_outline();
+    }
+
+    fn on_exit(&mut self) {
+        #[cfg(not(target_arch = "wasm32"))]
+        super::crash::clear_pending_restore();
     }
 }
@@ -0,0 +1,127 @@
+use eframe::egui;
+use grapho_core::{mesh_content_hash, uv_layout, Mesh, UvLayout};
+
+use crate::app::GraphoApp;
+
+const MAX_EDGE_TRIANGLES: usize = 20_000;
+
+impl GraphoApp {
+    pub(super) fn inspected_mesh(&self) -> Option<&Mesh> {
+        let selected = self.node_graph.selected_node_id();
+        selected.and_then(|id| self.node_output_mesh(id))
+    }
+
+    pub(super) fn show_uv_view(&mut self, ctx: &egui::Context) {
+        if !self.project.settings.panels.show_uv_view {
+            return;
+        }
+
+        let key = self.inspected_mesh().map(mesh_content_hash);
+        if key != self.uv_layout_cache.as_ref().map(|(key, _)| *key) {
+            self.uv_layout_cache = self
+                .inspected_mesh()
+                .zip(key)
+                .map(|(mesh, key)| (key, uv_layout(mesh)));
+        }
+
+        let mut open = true;
+        egui::Window::new("UV View")
+            .collapsible(true)
+            .resizable(true)
+            .default_size([420.0, 460.0])
+            .open(&mut open)
+            .show(ctx, |ui| match &self.uv_layout_cache {
+                None => {
+                    ui.label("No mesh selected.");
+                }
+                Some((_, None)) => {
+                    ui.label("Selected mesh has no UVs.");
+                }
+                Some((_, Some(layout))) => {
+                    ui.label(format!(
+                        "{} islands, {} triangles, {} overlapping, {} out of bounds",
+                        layout.island_count,
+                        layout.triangles.len(),
+                        layout.overlap_count(),
+                        layout.out_of_bounds_count()
+                    ));
+                    draw_uv_layout(ui, layout);
+                }
+            });
+        if !open {
+            self.project.settings.panels.show_uv_view = false;
+        }
+    }
+}
+
+fn draw_uv_layout(ui: &mut egui::Ui, layout: &UvLayout) {
+    let available = ui.available_size();
+    let side = available.x.min(available.y).max(120.0);
+    let (rect, _) = ui.allocate_exact_size(egui::vec2(side, side), egui::Sense::hover());
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, egui::Color32::from_rgb(30, 30, 30));
+
+    let mut min = [0.0f32, 0.0f32];
+    let mut max = [1.0f32, 1.0f32];
+    for uv in layout.triangles.iter().flatten() {
+        if uv[0].is_finite() && uv[1].is_finite() {
+            min = [min[0].min(uv[0]), min[1].min(uv[1])];
+            max = [max[0].max(uv[0]), max[1].max(uv[1])];
+        }
+    }
+    let extent = (max[0] - min[0]).max(max[1] - min[1]).max(f32::EPSILON);
+    let margin = 8.0;
+    let scale = (side - margin * 2.0) / extent;
+    let to_screen = |uv: [f32; 2]| {
+        egui::pos2(
+            rect.left() + margin + (uv[0] - min[0]) * scale,
+            rect.bottom() - margin - (uv[1] - min[1]) * scale,
+        )
+    };
+
+    let grid_stroke = egui::Stroke::new(1.0, egui::Color32::from_rgb(50, 50, 50));
+    for step in 1..10 {
+        let t = step as f32 / 10.0;
+        painter.line_segment([to_screen([t, 0.0]), to_screen([t, 1.0])], grid_stroke);
+        painter.line_segment([to_screen([0.0, t]), to_screen([1.0, t])], grid_stroke);
+    }
+    painter.rect_stroke(
+        egui::Rect::from_two_pos(to_screen([0.0, 0.0]), to_screen([1.0, 1.0])),
+        0.0,
+        egui::Stroke::new(1.5, egui::Color32::from_rgb(140, 140, 140)),
+        egui::StrokeKind::Middle,
+    );
+
+    let mut mesh = egui::epaint::Mesh::default();
+    for (index, tri) in layout.triangles.iter().enumerate() {
+        let color = if layout.overlapping[index] {
+            egui::Color32::from_rgba_unmultiplied(230, 60, 50, 170)
+        } else if layout.out_of_bounds[index] {
+            egui::Color32::from_rgba_unmultiplied(240, 160, 40, 150)
+        } else {
+            island_color(layout.islands[index])
+        };
+        let base = mesh.vertices.len() as u32;
+        for uv in tri {
+            mesh.colored_vertex(to_screen(*uv), color);
+        }
+        mesh.add_triangle(base, base + 1, base + 2);
+    }
+    painter.add(egui::Shape::mesh(mesh));
+
+    if layout.triangles.len() <= MAX_EDGE_TRIANGLES {
+        let edge_stroke = egui::Stroke::new(
+            1.0,
+            egui::Color32::from_rgba_unmultiplied(220, 220, 220, 90),
+        );
+        for tri in &layout.triangles {
+            let points = tri.map(to_screen);
+            painter.add(egui::Shape::closed_line(points.to_vec(), edge_stroke));
+        }
+    }
+}
+
+fn island_color(island: usize) -> egui::Color32 {
+    let hue = (island as f32 * 0.618_034).fract();
+    egui::ecolor::Hsva::new(hue, 0.45, 0.7, 0.55).into()
+}
@@ -0,0 +1,69 @@
+use std::path::Path;
+
+use eframe::egui;
+
+use super::GraphoApp;
+
+impl GraphoApp {
+    #[cfg(not(target_arch = "wasm32"))]
+    pub(crate) fn open_viewer(&mut self, path: &Path) {
+        self.viewer_mode = true;
+        match self.load_project_from(path) {
+            Ok(()) => tracing::info!("viewer: opened {}", path.display()),
+            Err(err) => tracing::error!("viewer: failed to open {}: {}", path.display(), err),
+        }
+    }
+
+    pub(super) fn show_viewer_mode(&mut self, ctx: &egui::Context) {
+        self.show_status_bar(ctx);
+        egui::TopBottomPanel::top("viewer_bar").show(ctx, |ui| {
+            ui.horizontal(|ui| {
+                let title = self
+                    .project_path
+                    .as_deref()
+                    .and_then(Path::file_stem)
+                    .map(|name| name.to_string_lossy().into_owned())
+                    .unwrap_or_else(|| "Untitled".to_string());
+                ui.strong(title);
+                ui.separator();
+                ui.label("Viewer");
+
+                if self.project.graph.is_time_dependent() {
+                    ui.separator();
+                    let play_label = if self.playing { "⏸" } else { "▶" };
+                    if ui.button(play_label).clicked() {
+                        self.playing = !self.playing;
+                    }
+                    let timeline = &mut self.project.settings.timeline;
+                    ui.add(
+                        egui::Slider::new(&mut timeline.frame, timeline.start..=timeline.end)
+                            .text("Frame")
+                            .fixed_decimals(0),
+                    );
+                }
+            });
+        });
+
+        if !self.project.graph.project_params().is_empty() {
+            egui::SidePanel::right("viewer_params")
+                .resizable(true)
+                .default_width(260.0)
+                .show(ctx, |ui| {
+                    ui.heading("Parameters");
+                    ui.separator();
+                    if self.show_project_param_values(ui, false) {
+                        self.mark_eval_dirty();
+                    }
+                });
+        }
+
+        egui::CentralPanel::default()
+            .frame(egui::Frame::NONE)
+            .show(ctx, |ui| {
+                self.show_viewport_canvas(ui);
+            });
+
+        self.advance_timeline(ctx);
+        self.evaluate_if_needed();
+    }
+}
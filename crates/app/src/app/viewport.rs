@@ -1,8 +1,21 @@
 use eframe::egui;
-use render::{CameraState, ViewportRenderer};
+use grapho_core::{BackgroundMode, CameraMode};
+use render::{fly_camera, CameraState, ViewportRenderer};
 
+use super::accessibility::{paint_focus_ring, panel_has_focus, FocusPanel};
 use super::GraphoApp;
 
+const TOUCH_CLICK_DIST: f32 = 16.0;
+const FLY_LOOK_SPEED: f32 = 0.005;
+const FLY_BOOST: f32 = 4.0;
+
+const SURFACE_SHADER_TEMPLATE: &str =
+    "// world_pos is relative to the camera target; add uniforms.render_origin for world space.
+fn surface_color(normal: vec3<f32>, world_pos: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
+    return shade_surface(normal, world_pos, color);
+}
+";
+
 impl GraphoApp {
     pub(super) fn sync_wgpu_renderer(&mut self, frame: &eframe::Frame) {
         let Some(render_state) = frame.wgpu_render_state() else {
@@ -17,18 +30,35 @@ impl GraphoApp {
         {
             renderer.set_scene(scene);
         }
+        if let Some(renderer) = &self.viewport_renderer {
+            renderer.set_surface_shader(Some(&self.project.settings.render_debug.surface_shader));
+        }
     }
 
     pub(super) fn handle_viewport_input(&mut self, response: &egui::Response) {
         if !response.hovered() {
             return;
         }
+        if self.project.settings.camera.mode == CameraMode::Fly {
+            self.handle_fly_input(response);
+            return;
+        }
 
         let camera = &mut self.project.settings.camera;
         let orbit_speed = 0.01;
         let pan_speed = 0.0025 * camera.distance.max(0.1);
         let zoom_speed = 0.1;
 
+        // Two fingers pan and pinch; the first finger also drives the pointer, so skip orbiting.
+        if let Some(touch) = response.ctx.input(|i| i.multi_touch()) {
+            camera.target[0] -= touch.translation_delta.x * pan_speed;
+            camera.target[1] += touch.translation_delta.y * pan_speed;
+            if touch.zoom_delta > 0.0 {
+                camera.distance = (camera.distance / touch.zoom_delta).clamp(0.1, 1000.0);
+            }
+            return;
+        }
+
         if response.dragged_by(egui::PointerButton::Primary) {
             let delta = response.drag_motion();
             camera.yaw += delta.x * orbit_speed;
@@ -48,6 +78,217 @@ impl GraphoApp {
         }
     }
 
+    fn handle_fly_input(&mut self, response: &egui::Response) {
+        let ctx = &response.ctx;
+        let scroll_delta = ctx.input(|i| i.raw_scroll_delta.y);
+        if scroll_delta != 0.0 {
+            let fly_speed = &mut self.project.settings.camera.fly_speed;
+            *fly_speed = (*fly_speed * (1.0 + scroll_delta * 0.001)).clamp(0.05, 500.0);
+        }
+        let look = if response.dragged_by(egui::PointerButton::Primary)
+            || response.dragged_by(egui::PointerButton::Secondary)
+        {
+            let delta = response.drag_motion();
+            [delta.x * FLY_LOOK_SPEED, delta.y * FLY_LOOK_SPEED]
+        } else {
+            [0.0, 0.0]
+        };
+        let typing = ctx.wants_keyboard_input() && !panel_has_focus(ctx);
+        let (movement, boost, dt) = ctx.input(|i| {
+            let axis = |negative, positive| {
+                if typing {
+                    return 0.0;
+                }
+                i.key_down(positive) as i32 as f32 - i.key_down(negative) as i32 as f32
+            };
+            (
+                [
+                    axis(egui::Key::A, egui::Key::D),
+                    axis(egui::Key::Q, egui::Key::E),
+                    axis(egui::Key::S, egui::Key::W),
+                ],
+                i.modifiers.shift,
+                i.stable_dt.min(0.1),
+            )
+        });
+        let moving = movement.iter().any(|axis| *axis != 0.0);
+        if !moving && look == [0.0, 0.0] {
+            return;
+        }
+        let step =
+            self.project.settings.camera.fly_speed * dt * if boost { FLY_BOOST } else { 1.0 };
+        let mut camera = self.camera_state();
+        fly_camera(&mut camera, movement.map(|axis| axis * step), look);
+        self.set_camera_state(camera);
+        if moving {
+            ctx.request_repaint();
+        }
+    }
+
+    // Pens and fingers wobble more than a mouse, so taps need a larger click radius.
+    pub(super) fn configure_touch_input(&mut self, ctx: &egui::Context) {
+        if self.touch_input || !ctx.input(|i| i.any_touches()) {
+            return;
+        }
+        ctx.options_mut(|options| options.input_options.max_click_dist = TOUCH_CLICK_DIST);
+        self.touch_input = true;
+    }
+
+    pub(super) fn show_viewport_canvas(&mut self, ui: &mut egui::Ui) -> egui::Rect {
+        let available = ui.available_size();
+        let (rect, response) = ui.allocate_exact_size(available, egui::Sense::click_and_drag());
+        self.handle_viewport_input(&response);
+        self.handle_annotation_click(rect, &response);
+        let [r, g, b] = match self.project.settings.render_debug.background_mode {
+            BackgroundMode::Solid => self.project.settings.render_debug.background_color,
+            BackgroundMode::Gradient => self.project.settings.render_debug.background_bottom,
+        };
+        ui.painter()
+            .rect_filled(rect, 0.0, egui::Color32::from_rgb(r, g, b));
+        if let Some(renderer) = &self.viewport_renderer {
+            let camera = self.camera_state();
+            let mut debug = self.viewport_debug();
+            if self.project.settings.render_debug.show_prim_under_cursor {
+                debug.pick_point = response
+                    .hover_pos()
+                    .map(|pos| [pos.x - rect.min.x, pos.y - rect.min.y]);
+            }
+            let callback = renderer.paint_callback(rect, camera, debug);
+            ui.painter().add(egui::Shape::Callback(callback));
+
+            if self.project.settings.render_debug.show_stats {
+                let stats = renderer.stats_snapshot();
+                let text = format!(
+                    "FPS: {:.1}\nFrame: {:.2} ms\nVerts: {}\nTris: {}\nMeshes: {} ({} drawn / {} culled)\nCache: {} hits / {} misses / {} uploads",
+                    stats.fps,
+                    stats.frame_time_ms,
+                    stats.vertex_count,
+                    stats.triangle_count,
+                    stats.mesh_count,
+                    stats.meshes_drawn,
+                    stats.meshes_culled,
+                    stats.cache_hits,
+                    stats.cache_misses,
+                    stats.cache_uploads
+                );
+                let font_id = egui::FontId::monospace(12.0);
+                let galley = ui.fonts_mut(|f| {
+                    f.layout_no_wrap(text.clone(), font_id.clone(), egui::Color32::WHITE)
+                });
+                let padding = egui::vec2(6.0, 4.0);
+                let bg_rect = egui::Rect::from_min_size(
+                    rect.min + egui::vec2(8.0, 8.0),
+                    galley.size() + padding * 2.0,
+                );
+                let painter = ui.painter();
+                painter.rect_filled(bg_rect, 4.0, egui::Color32::from_black_alpha(160));
+                painter.galley(bg_rect.min + padding, galley, egui::Color32::WHITE);
+            }
+            if self.project.settings.render_debug.show_prim_under_cursor {
+                self.paint_prim_readout(ui, rect, renderer.picked_triangle());
+            }
+        } else {
+            ui.painter().text(
+                rect.center(),
+                egui::Align2::CENTER_CENTER,
+                "WGPU not ready",
+                egui::FontId::proportional(14.0),
+                egui::Color32::GRAY,
+            );
+        }
+        self.paint_annotations(ui, rect);
+        self.show_annotation_toolbar(ui, rect);
+        if self.project.settings.camera.mode == CameraMode::Fly {
+            ui.painter().text(
+                rect.left_bottom() + egui::vec2(8.0, -8.0),
+                egui::Align2::LEFT_BOTTOM,
+                format!(
+                    "Fly: WASD move, Q/E down/up, drag to look, scroll for speed ({:.1})",
+                    self.project.settings.camera.fly_speed
+                ),
+                egui::FontId::proportional(12.0),
+                egui::Color32::from_gray(200),
+            );
+        }
+        let focus = self.panel_focus_anchor(ui, FocusPanel::Viewport, rect);
+        if focus.has_focus() {
+            self.handle_viewport_keys(ui.ctx());
+        }
+        if focus.has_focus() || (response.hovered() && !ui.ctx().wants_keyboard_input()) {
+            self.handle_viewport_frame_keys(ui.ctx());
+        }
+        paint_focus_ring(ui, &focus);
+        rect
+    }
+
+    // The triangle comes from the renderer's id pass; faces are looked up on the cooked mesh.
+    fn paint_prim_readout(&self, ui: &egui::Ui, rect: egui::Rect, triangle: Option<u32>) {
+        let text = match triangle {
+            Some(triangle) => {
+                let face = self
+                    .project
+                    .graph
+                    .display_node()
+                    .and_then(|node| self.eval_state.mesh_for_node(node))
+                    .and_then(|mesh| mesh.triangle_face(triangle as usize));
+                match face {
+                    Some(face) => format!("Prim {face} (tri {triangle})"),
+                    None => format!("Tri {triangle}"),
+                }
+            }
+            None => "Prim: -".to_string(),
+        };
+        ui.painter().text(
+            rect.right_bottom() + egui::vec2(-8.0, -8.0),
+            egui::Align2::RIGHT_BOTTOM,
+            text,
+            egui::FontId::monospace(12.0),
+            egui::Color32::from_gray(220),
+        );
+    }
+
+    fn handle_viewport_keys(&mut self, ctx: &egui::Context) {
+        let (horizontal, vertical, zoom_in, zoom_out, pan) = ctx.input(|i| {
+            let axis = |negative, positive| {
+                i.key_pressed(positive) as i32 as f32 - i.key_pressed(negative) as i32 as f32
+            };
+            (
+                axis(egui::Key::ArrowLeft, egui::Key::ArrowRight),
+                axis(egui::Key::ArrowUp, egui::Key::ArrowDown),
+                i.key_pressed(egui::Key::Plus) || i.key_pressed(egui::Key::Equals),
+                i.key_pressed(egui::Key::Minus),
+                i.modifiers.shift,
+            )
+        });
+        let camera = &mut self.project.settings.camera;
+        if pan {
+            let pan_step = 0.05 * camera.distance.max(0.1);
+            camera.target[0] += horizontal * pan_step;
+            camera.target[1] -= vertical * pan_step;
+        } else {
+            camera.yaw += horizontal * 0.1;
+            camera.pitch = (camera.pitch + vertical * 0.1).clamp(-1.54, 1.54);
+        }
+        if zoom_in {
+            camera.distance = (camera.distance / 1.15).clamp(0.1, 1000.0);
+        } else if zoom_out {
+            camera.distance = (camera.distance * 1.15).clamp(0.1, 1000.0);
+        }
+    }
+
+    // Same keys as framing in the node graph: F for the selection, Home for everything.
+    fn handle_viewport_frame_keys(&mut self, ctx: &egui::Context) {
+        let (frame_all, frame_selected) = ctx.input_mut(|i| {
+            (
+                i.consume_key(egui::Modifiers::NONE, egui::Key::Home),
+                i.consume_key(egui::Modifiers::NONE, egui::Key::F),
+            )
+        });
+        if frame_all || frame_selected {
+            self.frame_viewport(frame_selected);
+        }
+    }
+
     pub(super) fn camera_state(&self) -> CameraState {
         CameraState {
             target: self.project.settings.camera.target,
@@ -56,4 +297,192 @@ impl GraphoApp {
             pitch: self.project.settings.camera.pitch,
         }
     }
+
+    pub(super) fn set_camera_state(&mut self, camera: CameraState) {
+        let settings = &mut self.project.settings.camera;
+        settings.target = camera.target;
+        settings.distance = camera.distance;
+        settings.yaw = camera.yaw;
+        settings.pitch = camera.pitch;
+    }
+
+    pub(super) fn toggle_fly_camera(&mut self) {
+        let camera = &mut self.project.settings.camera;
+        camera.mode = match camera.mode {
+            CameraMode::Orbit => CameraMode::Fly,
+            CameraMode::Fly => CameraMode::Orbit,
+        };
+    }
+
+    // Frames the selected node's output, falling back to the displayed scene when nothing
+    // is selected or the selection has no geometry.
+    pub(super) fn frame_viewport(&mut self, selected: bool) {
+        let selection = selected
+            .then(|| self.inspected_mesh())
+            .flatten()
+            .and_then(|mesh| mesh.bounds());
+        let bounds = selection.or_else(|| {
+            self.project
+                .graph
+                .display_node()
+                .and_then(|node| self.eval_state.mesh_for_node(node))
+                .and_then(|mesh| mesh.bounds())
+        });
+        let Some(bounds) = bounds else {
+            return;
+        };
+        let aspect = self
+            .last_viewport_rect
+            .map_or(1.0, |rect| rect.width() / rect.height().max(1.0));
+        self.project.settings.camera.frame_bounds(bounds, aspect);
+    }
+
+    pub(super) fn add_camera_bookmark(&mut self) {
+        let camera = &mut self.project.settings.camera;
+        let name = format!("Camera {}", camera.bookmarks.len() + 1);
+        let bookmark = camera.bookmark(name);
+        camera.bookmarks.push(bookmark);
+    }
+
+    pub(super) fn show_surface_shader_editor(&mut self, ui: &mut egui::Ui) {
+        let applied = &mut self.project.settings.render_debug.surface_shader;
+        let draft = self
+            .surface_shader_draft
+            .get_or_insert_with(|| applied.clone());
+        ui.add(
+            egui::TextEdit::multiline(draft)
+                .code_editor()
+                .desired_rows(6)
+                .desired_width(f32::INFINITY)
+                .hint_text(SURFACE_SHADER_TEMPLATE),
+        );
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(*draft != *applied, egui::Button::new("Apply"))
+                .clicked()
+            {
+                *applied = draft.clone();
+            }
+            if ui
+                .add_enabled(draft.is_empty(), egui::Button::new("Template"))
+                .clicked()
+            {
+                *draft = SURFACE_SHADER_TEMPLATE.to_string();
+            }
+            if ui
+                .add_enabled(
+                    !draft.is_empty() || !applied.is_empty(),
+                    egui::Button::new("Reset"),
+                )
+                .clicked()
+            {
+                draft.clear();
+                applied.clear();
+            }
+        });
+        if !applied.trim().is_empty() {
+            ui.weak("Overrides lit shading. Compile errors are logged to the console.");
+        }
+    }
+
+    pub(super) fn show_color_attribute_picker(&mut self, ui: &mut egui::Ui) -> bool {
+        let mut names: Vec<String> = self
+            .project
+            .graph
+            .display_node()
+            .and_then(|node| self.eval_state.mesh_for_node(node))
+            .map(|mesh| {
+                mesh.list_attributes()
+                    .into_iter()
+                    .map(|attr| attr.name)
+                    .collect()
+            })
+            .unwrap_or_default();
+        names.sort();
+        names.dedup();
+
+        let selected = &mut self.project.settings.render_debug.color_attribute;
+        let mut changed = false;
+        egui::ComboBox::from_label("Attribute")
+            .selected_text(if selected.is_empty() {
+                "None"
+            } else {
+                selected.as_str()
+            })
+            .show_ui(ui, |ui| {
+                for name in names {
+                    if ui.selectable_label(*selected == name, &name).clicked() && *selected != name
+                    {
+                        *selected = name;
+                        changed = true;
+                    }
+                }
+            });
+        match self.false_color_range {
+            Some(range) => ui.weak(format!("Range {:.3} to {:.3}", range[0], range[1])),
+            None => ui.weak("No attribute displayed"),
+        };
+        changed
+    }
+
+    pub(super) fn show_debug_channel_settings(&mut self, ui: &mut egui::Ui) {
+        let hidden = &mut self.project.settings.render_debug.hidden_debug_channels;
+        for name in &self.debug_channel_names {
+            let mut visible = !hidden.contains(name);
+            if ui.checkbox(&mut visible, name.as_str()).changed() {
+                if visible {
+                    hidden.retain(|hidden_name| hidden_name != name);
+                } else {
+                    hidden.push(name.clone());
+                }
+            }
+        }
+    }
+
+    pub(super) fn show_clipping_settings(&mut self, ui: &mut egui::Ui) {
+        let settings = &mut self.project.settings.render_debug;
+        ui.horizontal(|ui| {
+            ui.label("Near clip");
+            ui.add(
+                egui::DragValue::new(&mut settings.near_clip)
+                    .speed(0.05)
+                    .range(0.0..=1000.0),
+            )
+            .on_hover_text("Hide geometry closer to the camera than this distance (0 = off)");
+        });
+        ui.checkbox(&mut settings.section_enabled, "Section plane");
+        if !settings.section_enabled {
+            return;
+        }
+        ui.horizontal(|ui| {
+            ui.label("Axis");
+            for (label, normal) in [
+                ("X", [1.0, 0.0, 0.0]),
+                ("Y", [0.0, 1.0, 0.0]),
+                ("Z", [0.0, 0.0, 1.0]),
+            ] {
+                if ui
+                    .selectable_label(settings.section_normal == normal, label)
+                    .clicked()
+                {
+                    settings.section_normal = normal;
+                }
+            }
+            if ui.button("Flip").clicked() {
+                settings.section_normal = settings.section_normal.map(|value| -value);
+                settings.section_offset = -settings.section_offset;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Normal");
+            for value in &mut settings.section_normal {
+                ui.add(egui::DragValue::new(value).speed(0.01).range(-1.0..=1.0));
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Offset");
+            ui.add(egui::DragValue::new(&mut settings.section_offset).speed(0.02));
+        });
+        ui.checkbox(&mut settings.section_outline, "Cut outline");
+    }
 }
@@ -33,6 +33,8 @@ impl GraphoApp {
             ui.label("Assignments (use @ for attributes):");
             ui.monospace("@Cd = vec3(1.0, 0.0, 0.0);");
             ui.monospace("@P = @P + vec3(0.0, 1.0, 0.0);");
+            ui.monospace("@P.y += sin(@P.x * 3.0) * 0.2;");
+            ui.monospace("@Cd.rg = vec2(1.0, 0.5);");
             ui.separator();
             ui.label("Swizzles:");
             ui.monospace("@P.x");
@@ -41,17 +43,25 @@ impl GraphoApp {
             ui.separator();
             ui.label("Functions:");
             ui.monospace("sin cos tan abs floor ceil pow min max clamp lerp len dot normalize");
+            ui.monospace("noise(p) snoise(p) worley(p) curl(p)");
+            ui.separator();
+            ui.label("Timeline:");
+            ui.monospace("$F  current frame");
+            ui.monospace("$T  seconds since frame 1");
+            ui.monospace("@P.y += sin($T * 6.28) * 0.1;");
             ui.separator();
             ui.label("Constructors:");
             ui.monospace("vec2(x, y) vec3(x, y, z) vec4(x, y, z, w)");
             ui.separator();
             ui.label("Operators:");
             ui.monospace("+ - * / ( )");
+            ui.monospace("= += -= *= /=");
             ui.separator();
             ui.label("Notes:");
             ui.label("Mode selects vertex/point/prim/detail evaluation.");
             ui.label("@P writes only in Point mode; @N writes in Point/Vertex.");
             ui.label("Missing attributes read as 0.");
+            ui.label("Component writes such as @P.y need an existing vector attribute.");
         });
 
         panel.open = open;
@@ -4,6 +4,12 @@ use std::process;
 use grapho_core::Project;
 use serde::Deserialize;
 
+mod convert;
+mod cook;
+mod info;
+mod regress;
+mod serve;
+
 #[derive(Debug, Deserialize)]
 struct HeadlessPlan {
     #[serde(default)]
@@ -47,6 +53,22 @@ struct HeadlessArgs {
     plan_path: Option<PathBuf>,
     save_path: Option<PathBuf>,
     print: bool,
+    serve: bool,
+    listen: String,
+    workers: usize,
+    root: Option<PathBuf>,
+    allow_file_nodes: bool,
+    expand_env: bool,
+    info_path: Option<PathBuf>,
+    json: bool,
+    cook_path: Option<PathBuf>,
+    output_path: Option<PathBuf>,
+    output_node: Option<String>,
+    overrides: Vec<String>,
+    regress_dir: Option<PathBuf>,
+    update: bool,
+    convert_paths: Option<(PathBuf, PathBuf)>,
+    cache_mode: convert::CacheMode,
 }
 
 pub fn maybe_run_headless(args: &[String]) -> Result<bool, String> {
@@ -58,6 +80,47 @@ pub fn maybe_run_headless(args: &[String]) -> Result<bool, String> {
     }
 
     let parsed = parse_headless_args(args)?;
+    if let Some((input, output)) = &parsed.convert_paths {
+        convert::run_convert(input, output, parsed.cache_mode)?;
+        return Ok(true);
+    }
+    if let Some(path) = &parsed.cook_path {
+        let output = parsed
+            .output_path
+            .as_ref()
+            .ok_or_else(|| "cook requires --output <path>".to_string())?;
+        cook::run_cook(
+            path,
+            &parsed.overrides,
+            parsed.output_node.as_deref(),
+            output,
+        )?;
+        return Ok(true);
+    }
+    if let Some(dir) = &parsed.regress_dir {
+        regress::run_regress(dir, parsed.update)?;
+        return Ok(true);
+    }
+    if let Some(path) = &parsed.info_path {
+        info::print_project_info(path, parsed.json)?;
+        return Ok(true);
+    }
+    if parsed.serve {
+        let root = parsed
+            .root
+            .ok_or_else(|| "serve requires --root <dir>".to_string())?;
+        serve::run_server(
+            &parsed.listen,
+            parsed.workers,
+            serve::ServeOptions {
+                root,
+                allow_file_nodes: parsed.allow_file_nodes,
+                expand_env: parsed.expand_env,
+            },
+        )?;
+        return Ok(true);
+    }
+
     let plan = if let Some(path) = parsed.plan_path {
         load_headless_plan(&path)?
     } else {
@@ -88,6 +151,24 @@ fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
     let mut plan_path = None;
     let mut save_path = None;
     let mut print = false;
+    let mut serve = false;
+    let mut info_path = None;
+    let mut json = false;
+    let mut cook_path = None;
+    let mut output_path = None;
+    let mut output_node = None;
+    let mut overrides = Vec::new();
+    let mut regress_dir = None;
+    let mut update = false;
+    let mut convert_paths = None;
+    let mut cache_mode = convert::CacheMode::Keep;
+    let mut listen = "127.0.0.1:9000".to_string();
+    let mut workers = std::thread::available_parallelism()
+        .map(|count| count.get())
+        .unwrap_or(1);
+    let mut root = None;
+    let mut allow_file_nodes = false;
+    let mut expand_env = false;
     let mut iter = args.iter().peekable();
 
     while let Some(arg) = iter.next() {
@@ -108,6 +189,92 @@ fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
             "--print" => {
                 print = true;
             }
+            "serve" => {
+                serve = true;
+            }
+            "info" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "info requires a project path".to_string())?;
+                info_path = Some(PathBuf::from(value));
+            }
+            "--json" => {
+                json = true;
+            }
+            "cook" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "cook requires a project path".to_string())?;
+                cook_path = Some(PathBuf::from(value));
+            }
+            "regress" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "regress requires a directory".to_string())?;
+                regress_dir = Some(PathBuf::from(value));
+            }
+            "--update" => {
+                update = true;
+            }
+            "convert" => {
+                let input = iter
+                    .next()
+                    .ok_or_else(|| "convert requires an input path".to_string())?;
+                let output = iter
+                    .next()
+                    .ok_or_else(|| "convert requires an output path".to_string())?;
+                convert_paths = Some((PathBuf::from(input), PathBuf::from(output)));
+            }
+            "--embed-cache" => {
+                cache_mode = convert::CacheMode::Embed;
+            }
+            "--strip-cache" => {
+                cache_mode = convert::CacheMode::Strip;
+            }
+            "--output" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--output requires a path".to_string())?;
+                output_path = Some(PathBuf::from(value));
+            }
+            "--node" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--node requires a node name".to_string())?;
+                output_node = Some(value.clone());
+            }
+            "--set" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--set requires Node.param=value".to_string())?;
+                overrides.push(value.clone());
+            }
+            "--listen" => {
+                listen = iter
+                    .next()
+                    .ok_or_else(|| "--listen requires an address".to_string())?
+                    .clone();
+            }
+            "--workers" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--workers requires a count".to_string())?;
+                workers = value
+                    .parse()
+                    .map_err(|_| format!("invalid worker count {}", value))?;
+            }
+            "--root" => {
+                let value = iter
+                    .next()
+                    .ok_or_else(|| "--root requires a directory".to_string())?;
+                root = Some(PathBuf::from(value));
+            }
+            "--allow-file-nodes" => {
+                allow_file_nodes = true;
+            }
+            "--expand-env" => {
+                expand_env = true;
+            }
             "--help" | "-h" => {
                 print_headless_help();
                 process::exit(0);
@@ -120,12 +287,28 @@ fn parse_headless_args(args: &[String]) -> Result<HeadlessArgs, String> {
         plan_path,
         save_path,
         print,
+        serve,
+        listen,
+        workers,
+        root,
+        allow_file_nodes,
+        expand_env,
+        info_path,
+        json,
+        cook_path,
+        output_path,
+        output_node,
+        overrides,
+        regress_dir,
+        update,
+        convert_paths,
+        cache_mode,
     })
 }
 
 fn print_headless_help() {
     println!(
-        "Headless mode options:\n  --headless | -headless\n  --plan <path>\n  --save <path>\n  --print"
+        "Headless mode options:\n  --headless | -headless\n  --plan <path>\n  --save <path>\n  --print\n\nProject info:\n  --headless info <project.json> [--json]\n\nExport:\n  --headless cook <project.json> --output <out.obj> [--node <name>]\n    [--set Node.param=value]...  (${{VAR}} in string params expands from the environment)\n    Nodes are named by id, by reference name (box2) or by type when only one exists\n\nRegression snapshots:\n  --headless regress <dir> [--update]\n    Cooks each <name>.json and compares it to <name>.golden.json (written when missing)\n\nConversion:\n  --headless convert <in> <out> [--embed-cache | --strip-cache]\n    .json <-> .grapho (binary archive), migrating to the current version\n    --embed-cache stores the cooked display node in the archive\n\nServer mode:\n  --headless serve --root <dir> [--listen <addr:port>] [--workers <n>]\n    [--allow-file-nodes] [--expand-env]\n    project_path and output_path are relative to --root\n    --allow-file-nodes lets inline projects and overrides use File, Merge Files and OBJ Output\n    --expand-env expands ${{VAR}} in inline projects too, not just files under --root\n    POST /jobs, GET /jobs, GET /jobs/<id>, GET /jobs/<id>/result\n    Jobs with \"stream\": true write OBJ to output_path in chunks"
     );
 }
 
@@ -194,6 +377,9 @@ fn build_project_from_plan(plan: &HeadlessPlan) -> Result<Project, String> {
                     pin_type: pin.pin_type,
                 })
                 .collect(),
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         });
         name_to_id.insert(node.name.clone(), node_id);
     }
@@ -268,6 +454,17 @@ fn validate_topo_sort(project: &Project, output_node_name: &str) -> Result<(), S
         .topo_sort_from(node_id)
         .map_err(|err| format!("topo sort failed: {:?}", err))?;
     tracing::info!("headless: topo order {:?}", order);
+    let stats = project
+        .graph
+        .stats()
+        .map_err(|err| format!("graph stats failed: {:?}", err))?;
+    tracing::info!(
+        "headless: {} nodes, max depth {}, {} sources, {} sinks",
+        stats.nodes.len(),
+        stats.max_depth,
+        stats.sources.len(),
+        stats.sinks.len()
+    );
     Ok(())
 }
 
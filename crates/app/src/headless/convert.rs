@@ -0,0 +1,94 @@
+use std::path::Path;
+
+use grapho_core::{evaluate_mesh_graph, Mesh, MeshEvalState, Project, ProjectArchive};
+
+const ARCHIVE_EXTENSION: &str = "grapho";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum CacheMode {
+    Keep,
+    Embed,
+    Strip,
+}
+
+// Rewrites a project as JSON or as a binary archive, picked by the output extension, and
+// migrates it to the current version on the way. Archives can carry cooked meshes: `Embed`
+// cooks the display node and stores its output, `Strip` drops them, `Keep` carries over
+// whatever the input had.
+pub(super) fn run_convert(input: &Path, output: &Path, cache: CacheMode) -> Result<(), String> {
+    let (mut project, mut meshes) = read_project_with_cache(input)?;
+    let saved = project
+        .migrate()
+        .map_err(|err| format!("{}: {}", input.display(), err))?;
+    if saved != project.version {
+        println!("migrated from version {} to {}", saved, project.version);
+    }
+    match cache {
+        CacheMode::Keep => {}
+        CacheMode::Strip => meshes.clear(),
+        CacheMode::Embed => meshes = cook_display_node(&project)?,
+    }
+
+    let data = if is_archive_path(output) {
+        let project_json = serde_json::to_vec(&project).map_err(|err| err.to_string())?;
+        ProjectArchive {
+            project_json,
+            meshes,
+        }
+        .to_bytes()
+    } else {
+        if !meshes.is_empty() {
+            if cache == CacheMode::Embed {
+                return Err(format!(
+                    "JSON projects cannot hold cached meshes; write a .{} archive",
+                    ARCHIVE_EXTENSION
+                ));
+            }
+            println!(
+                "dropped {} cached mesh(es); JSON projects cannot hold them",
+                meshes.len()
+            );
+        }
+        serde_json::to_vec_pretty(&project).map_err(|err| err.to_string())?
+    };
+    std::fs::write(output, data).map_err(|err| format!("{}: {}", output.display(), err))?;
+    println!("wrote {}", output.display());
+    Ok(())
+}
+
+// Reads either format; archives are recognised by their header, not their extension.
+pub(super) fn read_project(path: &Path) -> Result<Project, String> {
+    read_project_with_cache(path).map(|(project, _)| project)
+}
+
+fn read_project_with_cache(path: &Path) -> Result<(Project, Vec<(u64, Mesh)>), String> {
+    let data = std::fs::read(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let (json, meshes) = if ProjectArchive::is_archive(&data) {
+        let archive = ProjectArchive::from_bytes(&data)
+            .map_err(|err| format!("{}: {}", path.display(), err))?;
+        (archive.project_json, archive.meshes)
+    } else {
+        (data, Vec::new())
+    };
+    let project: Project =
+        serde_json::from_slice(&json).map_err(|err| format!("{}: {}", path.display(), err))?;
+    Ok((project, meshes))
+}
+
+fn cook_display_node(project: &Project) -> Result<Vec<(u64, Mesh)>, String> {
+    let node = project
+        .graph
+        .display_node()
+        .ok_or_else(|| "project has no display node to cache".to_string())?;
+    let result = evaluate_mesh_graph(&project.graph, node, &mut MeshEvalState::new())
+        .map_err(|err| format!("{:?}", err))?;
+    let mesh = result
+        .output
+        .ok_or_else(|| format!("display node failed to cook: {:?}", result.report.errors))?;
+    Ok(vec![(node.as_u64(), mesh)])
+}
+
+fn is_archive_path(path: &Path) -> bool {
+    path.extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case(ARCHIVE_EXTENSION))
+}
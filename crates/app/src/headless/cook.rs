@@ -0,0 +1,79 @@
+use std::path::Path;
+
+use grapho_core::{
+    apply_param_override, find_node_by_name, stream_mesh_graph, substitute_env_in_params,
+    MeshEvalState, ObjStreamWriter, ProjectInfo,
+};
+
+use super::convert::read_project;
+
+// One-shot export: load a project, apply `--set` overrides and `${VAR}` expansion, then
+// stream the output node to OBJ. Lets one project file drive many export jobs.
+pub(super) fn run_cook(
+    path: &Path,
+    overrides: &[String],
+    output_node: Option<&str>,
+    output: &Path,
+) -> Result<(), String> {
+    let mut project = read_project(path)?;
+    project
+        .migrate()
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+
+    for spec in overrides {
+        apply_param_override(&mut project.graph, spec)?;
+    }
+    let expanded = substitute_env_in_params(&mut project.graph, |name| std::env::var(name).ok())?;
+    if expanded > 0 {
+        tracing::info!("headless: expanded {} string parameter(s)", expanded);
+    }
+
+    let node = match output_node {
+        Some(name) => find_node_by_name(&project.graph, name)?,
+        None => project
+            .graph
+            .display_node()
+            .ok_or_else(|| "project has no display node; pass --node".to_string())?,
+    };
+    // Fail before creating the output when a node this cook needs can't run; issues on
+    // unrelated branches don't matter.
+    let needed = project
+        .graph
+        .topo_sort_from(node)
+        .map_err(|err| format!("{:?}", err))?;
+    let blocking: Vec<String> = ProjectInfo::from_project(&project)
+        .dependency_issues()
+        .iter()
+        .filter(|issue| {
+            issue
+                .node()
+                .is_none_or(|id| needed.iter().any(|node| node.as_u64() == id))
+        })
+        .map(|issue| issue.describe())
+        .collect();
+    if !blocking.is_empty() {
+        return Err(format!(
+            "{} has unresolved dependencies:\n  {}",
+            path.display(),
+            blocking.join("\n  ")
+        ));
+    }
+    let file =
+        std::fs::File::create(output).map_err(|err| format!("{}: {}", output.display(), err))?;
+    let mut writer = ObjStreamWriter::new(std::io::BufWriter::new(file));
+    stream_mesh_graph(
+        &project.graph,
+        node,
+        &mut MeshEvalState::new(),
+        &mut writer,
+        project.graph.units().export_scale(),
+    )?;
+    tracing::info!(
+        "headless: wrote {} points, {} prims to {}",
+        writer.points(),
+        writer.prims(),
+        output.display()
+    );
+    writer.finish()?;
+    Ok(())
+}
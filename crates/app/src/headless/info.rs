@@ -0,0 +1,17 @@
+use std::path::Path;
+
+use grapho_core::ProjectInfo;
+
+use super::convert::read_project;
+
+pub(super) fn print_project_info(path: &Path, json: bool) -> Result<(), String> {
+    let project = read_project(path)?;
+    let info = ProjectInfo::from_project(&project);
+    if json {
+        let text = serde_json::to_string_pretty(&info).map_err(|err| err.to_string())?;
+        println!("{text}");
+    } else {
+        print!("{}", info.to_text());
+    }
+    Ok(())
+}
@@ -0,0 +1,83 @@
+use std::path::{Path, PathBuf};
+
+use grapho_core::{cook_project_snapshot, MeshSnapshot, Project};
+
+const GOLDEN_SUFFIX: &str = ".golden.json";
+
+// Cooks every project in `dir` and compares its display output against the golden file next
+// to it (`name.golden.json`). Missing goldens are written; `update` rewrites them all.
+pub(super) fn run_regress(dir: &Path, update: bool) -> Result<(), String> {
+    let projects = project_files(dir)?;
+    if projects.is_empty() {
+        return Err(format!("{}: no project files found", dir.display()));
+    }
+
+    let mut failures = Vec::new();
+    for path in &projects {
+        let name = path.file_name().unwrap_or_default().to_string_lossy();
+        let golden_path = golden_path(path);
+        let snapshot = match cook_snapshot(path) {
+            Ok(snapshot) => snapshot,
+            Err(err) => {
+                println!("FAIL {}: {}", name, err);
+                failures.push(name.to_string());
+                continue;
+            }
+        };
+        if update || !golden_path.exists() {
+            let json = serde_json::to_string_pretty(&snapshot).map_err(|err| err.to_string())?;
+            std::fs::write(&golden_path, json)
+                .map_err(|err| format!("{}: {}", golden_path.display(), err))?;
+            println!("WROTE {}", golden_path.display());
+            continue;
+        }
+        let data = std::fs::read(&golden_path)
+            .map_err(|err| format!("{}: {}", golden_path.display(), err))?;
+        let golden: MeshSnapshot = serde_json::from_slice(&data)
+            .map_err(|err| format!("{}: {}", golden_path.display(), err))?;
+        let diffs = golden.differences(&snapshot);
+        if diffs.is_empty() {
+            println!("ok   {}", name);
+        } else {
+            println!("FAIL {}:\n  {}", name, diffs.join("\n  "));
+            failures.push(name.to_string());
+        }
+    }
+
+    if failures.is_empty() {
+        println!("{} project(s) match their snapshots", projects.len());
+        Ok(())
+    } else {
+        Err(format!(
+            "{} of {} project(s) changed: {}",
+            failures.len(),
+            projects.len(),
+            failures.join(", ")
+        ))
+    }
+}
+
+fn project_files(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    let entries = std::fs::read_dir(dir).map_err(|err| format!("{}: {}", dir.display(), err))?;
+    let mut files: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|entry| entry.path()))
+        .filter(|path| {
+            let name = path.file_name().unwrap_or_default().to_string_lossy();
+            name.ends_with(".json") && !name.ends_with(GOLDEN_SUFFIX)
+        })
+        .collect();
+    files.sort();
+    Ok(files)
+}
+
+fn golden_path(project: &Path) -> PathBuf {
+    let stem = project.file_stem().unwrap_or_default().to_string_lossy();
+    project.with_file_name(format!("{}{}", stem, GOLDEN_SUFFIX))
+}
+
+fn cook_snapshot(path: &Path) -> Result<MeshSnapshot, String> {
+    let data = std::fs::read(path).map_err(|err| err.to_string())?;
+    let mut project: Project = serde_json::from_slice(&data).map_err(|err| err.to_string())?;
+    project.migrate()?;
+    cook_project_snapshot(&project)
+}
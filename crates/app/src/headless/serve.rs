@@ -0,0 +1,777 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::path::{Component, Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Condvar, Mutex, MutexGuard};
+use std::thread;
+use std::time::{Duration, Instant};
+
+use grapho_core::{
+    builtin_kind_from_name, evaluate_mesh_graph, find_node_by_name, scale_mesh, stream_mesh_graph,
+    substitute_env_in_params, write_obj_to, BuiltinNodeKind, EvalError, Graph, GraphCommand,
+    MeshEvalState, NodeId, ObjStreamWriter, ParamValue, Project,
+};
+use serde::{Deserialize, Serialize};
+
+const MAX_BODY_BYTES: usize = 64 * 1024 * 1024;
+const MAX_HEADER_LINE_BYTES: usize = 8 * 1024;
+const MAX_HEADER_LINES: usize = 100;
+// Queued jobs can each hold an inline project of up to MAX_BODY_BYTES.
+const MAX_PENDING_JOBS: usize = 32;
+// Finished jobs kept for status and result queries; older ones are dropped with their output.
+const MAX_FINISHED_JOBS: usize = 256;
+const MAX_CONNECTIONS: usize = 64;
+const SOCKET_TIMEOUT: Duration = Duration::from_secs(30);
+
+pub(super) struct ServeOptions {
+    // Every project_path and output_path a client sends is resolved under this directory.
+    pub root: PathBuf,
+    // Lets inline projects and overrides use File, Merge Files and OBJ Output.
+    pub allow_file_nodes: bool,
+    // Lets inline projects read server environment variables through ${VAR}.
+    pub expand_env: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct JobRequest {
+    #[serde(default)]
+    project_path: Option<PathBuf>,
+    #[serde(default)]
+    project: Option<Project>,
+    #[serde(default)]
+    overrides: Vec<ParamOverride>,
+    #[serde(default)]
+    project_params: BTreeMap<String, ParamValue>,
+    #[serde(default)]
+    output_node: Option<String>,
+    #[serde(default)]
+    output_format: OutputFormat,
+    #[serde(default)]
+    output_path: Option<PathBuf>,
+    #[serde(default)]
+    stream: bool,
+}
+
+#[derive(Debug, Deserialize)]
+struct ParamOverride {
+    node: String,
+    param: String,
+    value: ParamValue,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum OutputFormat {
+    #[default]
+    Obj,
+    Stats,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum JobState {
+    Queued,
+    Running,
+    Done,
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct JobStatus {
+    id: u64,
+    state: JobState,
+    output_format: OutputFormat,
+    error: Option<String>,
+    points: usize,
+    prims: usize,
+    duration_ms: f32,
+    output_path: Option<PathBuf>,
+}
+
+struct Job {
+    status: JobStatus,
+    request: Option<JobRequest>,
+    result: Option<String>,
+}
+
+struct JobOutput {
+    points: usize,
+    prims: usize,
+    body: Option<String>,
+}
+
+#[derive(Default)]
+struct QueueState {
+    next_id: u64,
+    pending: VecDeque<u64>,
+    finished: VecDeque<u64>,
+    jobs: BTreeMap<u64, Job>,
+}
+
+#[derive(Default)]
+struct JobQueue {
+    state: Mutex<QueueState>,
+    ready: Condvar,
+}
+
+impl JobQueue {
+    fn lock(&self) -> MutexGuard<'_, QueueState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+
+    // None when the queue is full.
+    fn submit(&self, request: JobRequest) -> Option<JobStatus> {
+        let mut state = self.lock();
+        if state.pending.len() >= MAX_PENDING_JOBS {
+            return None;
+        }
+        state.next_id += 1;
+        let id = state.next_id;
+        let status = JobStatus {
+            id,
+            state: JobState::Queued,
+            output_format: request.output_format,
+            error: None,
+            points: 0,
+            prims: 0,
+            duration_ms: 0.0,
+            output_path: request.output_path.clone(),
+        };
+        state.jobs.insert(
+            id,
+            Job {
+                status: status.clone(),
+                request: Some(request),
+                result: None,
+            },
+        );
+        state.pending.push_back(id);
+        drop(state);
+        self.ready.notify_one();
+        Some(status)
+    }
+
+    fn next_job(&self) -> (u64, JobRequest) {
+        let mut state = self.lock();
+        loop {
+            while let Some(id) = state.pending.pop_front() {
+                let Some(job) = state.jobs.get_mut(&id) else {
+                    continue;
+                };
+                if let Some(request) = job.request.take() {
+                    job.status.state = JobState::Running;
+                    return (id, request);
+                }
+            }
+            state = match self.ready.wait(state) {
+                Ok(guard) => guard,
+                Err(poisoned) => poisoned.into_inner(),
+            };
+        }
+    }
+
+    fn finish(&self, id: u64, duration_ms: f32, result: Result<JobOutput, String>) {
+        let mut state = self.lock();
+        let Some(job) = state.jobs.get_mut(&id) else {
+            return;
+        };
+        job.status.duration_ms = duration_ms;
+        match result {
+            Ok(output) => {
+                job.status.state = JobState::Done;
+                job.status.points = output.points;
+                job.status.prims = output.prims;
+                job.result = output.body;
+            }
+            Err(err) => {
+                job.status.state = JobState::Failed;
+                job.status.error = Some(err);
+            }
+        }
+        state.finished.push_back(id);
+        while state.finished.len() > MAX_FINISHED_JOBS {
+            if let Some(old) = state.finished.pop_front() {
+                state.jobs.remove(&old);
+            }
+        }
+    }
+
+    fn status(&self, id: u64) -> Option<JobStatus> {
+        self.lock().jobs.get(&id).map(|job| job.status.clone())
+    }
+
+    fn statuses(&self) -> Vec<JobStatus> {
+        self.lock()
+            .jobs
+            .values()
+            .map(|job| job.status.clone())
+            .collect()
+    }
+
+    fn result(&self, id: u64) -> Option<(JobState, Option<String>)> {
+        self.lock()
+            .jobs
+            .get(&id)
+            .map(|job| (job.status.state, job.result.clone()))
+    }
+}
+
+pub(super) fn run_server(
+    listen: &str,
+    workers: usize,
+    options: ServeOptions,
+) -> Result<(), String> {
+    let root = options
+        .root
+        .canonicalize()
+        .map_err(|err| format!("{}: {}", options.root.display(), err))?;
+    let options = Arc::new(ServeOptions { root, ..options });
+    let listener = TcpListener::bind(listen).map_err(|err| format!("bind {listen}: {err}"))?;
+    let queue = Arc::new(JobQueue::default());
+    let workers = workers.max(1);
+    for index in 0..workers {
+        let queue = Arc::clone(&queue);
+        let options = Arc::clone(&options);
+        thread::Builder::new()
+            .name(format!("grapho-worker-{index}"))
+            .spawn(move || worker_loop(&queue, &options))
+            .map_err(|err| err.to_string())?;
+    }
+    tracing::info!(
+        "headless: serving {} on {} with {} worker(s)",
+        options.root.display(),
+        listen,
+        workers
+    );
+
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                let _ = stream.set_read_timeout(Some(SOCKET_TIMEOUT));
+                let _ = stream.set_write_timeout(Some(SOCKET_TIMEOUT));
+                if connections.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                    let _ = write_response(stream, &Response::text(503, "too many connections"));
+                    continue;
+                }
+                let queue = Arc::clone(&queue);
+                let connections = Arc::clone(&connections);
+                thread::spawn(move || {
+                    if let Err(err) = handle_connection(stream, &queue) {
+                        tracing::warn!("headless: request failed: {}", err);
+                    }
+                    connections.fetch_sub(1, Ordering::SeqCst);
+                });
+            }
+            Err(err) => tracing::warn!("headless: accept failed: {}", err),
+        }
+    }
+    Ok(())
+}
+
+fn worker_loop(queue: &JobQueue, options: &ServeOptions) {
+    loop {
+        let (id, request) = queue.next_job();
+        tracing::info!("headless: job {} started", id);
+        let start = Instant::now();
+        let result = run_job(&request, options);
+        let duration_ms = start.elapsed().as_secs_f32() * 1000.0;
+        match &result {
+            Ok(_) => tracing::info!("headless: job {} done in {:.1} ms", id, duration_ms),
+            Err(err) => tracing::warn!("headless: job {} failed: {}", id, err),
+        }
+        queue.finish(id, duration_ms, result);
+    }
+}
+
+fn run_job(request: &JobRequest, options: &ServeOptions) -> Result<JobOutput, String> {
+    let mut project = match (&request.project, &request.project_path) {
+        (Some(project), _) => {
+            if !options.allow_file_nodes {
+                if let Some(name) = file_node_name(&project.graph) {
+                    return Err(format!(
+                        "inline project uses {}; start the server with --allow-file-nodes",
+                        name
+                    ));
+                }
+            }
+            project.clone()
+        }
+        (None, Some(path)) => {
+            let path = resolve_under_root(&options.root, path)?;
+            let data =
+                std::fs::read(&path).map_err(|err| format!("{}: {}", path.display(), err))?;
+            serde_json::from_slice(&data).map_err(|err| err.to_string())?
+        }
+        (None, None) => return Err("job needs a project or project_path".to_string()),
+    };
+    project.migrate()?;
+
+    for (name, value) in &request.project_params {
+        project.graph.set_project_param(name.clone(), value.clone());
+        if project.graph.project_param(name) != Some(value) {
+            return Err(format!("invalid project parameter name '{}'", name));
+        }
+    }
+    if request.project.is_none() || options.expand_env {
+        substitute_env_in_params(&mut project.graph, |name| std::env::var(name).ok())?;
+    }
+    for item in &request.overrides {
+        let node_id = find_node_by_name(&project.graph, &item.node)?;
+        if !options.allow_file_nodes && project.graph.node(node_id).is_some_and(is_file_node) {
+            return Err(format!("overriding {} needs --allow-file-nodes", item.node));
+        }
+        let command = GraphCommand::SetParam {
+            node: node_id,
+            key: item.param.clone(),
+            value: item.value.clone(),
+        };
+        project
+            .graph
+            .apply(&command)
+            .map_err(|err| format!("{:?}", err))?;
+    }
+
+    let output = match &request.output_node {
+        Some(name) => find_node_by_name(&project.graph, name)?,
+        None => project
+            .graph
+            .display_node()
+            .ok_or_else(|| "project has no display node; set output_node".to_string())?,
+    };
+    let output_path = request
+        .output_path
+        .as_ref()
+        .map(|path| resolve_under_root(&options.root, path))
+        .transpose()?;
+    if request.stream {
+        return run_stream_job(&project, output, request, output_path.as_deref());
+    }
+    let mut state = MeshEvalState::new();
+    let result = evaluate_mesh_graph(&project.graph, output, &mut state)
+        .map_err(|err| format!("{:?}", err))?;
+    let Some(mesh) = result.output else {
+        let message = result
+            .report
+            .errors
+            .iter()
+            .find_map(|err| match err {
+                EvalError::Node { message, .. } => Some(message.clone()),
+                EvalError::Upstream { .. } => None,
+            })
+            .unwrap_or_else(|| "evaluation produced no output".to_string());
+        return Err(message);
+    };
+
+    let body = match request.output_format {
+        OutputFormat::Obj => {
+            let mut exported = mesh.clone();
+            scale_mesh(&mut exported, project.graph.units().export_scale());
+            let mut data = Vec::new();
+            write_obj_to(&mut data, &exported)?;
+            let text = String::from_utf8(data).map_err(|err| err.to_string())?;
+            if let Some(path) = &output_path {
+                std::fs::write(path, &text)
+                    .map_err(|err| format!("{}: {}", path.display(), err))?;
+            }
+            Some(text)
+        }
+        OutputFormat::Stats => None,
+    };
+    Ok(JobOutput {
+        points: mesh.positions.len(),
+        prims: mesh.face_count(),
+        body,
+    })
+}
+
+// Writes OBJ straight to `output_path` in chunks, so Copy to Points results larger than
+// memory can still be exported. The result body stays empty.
+fn run_stream_job(
+    project: &Project,
+    output: NodeId,
+    request: &JobRequest,
+    output_path: Option<&Path>,
+) -> Result<JobOutput, String> {
+    if request.output_format != OutputFormat::Obj {
+        return Err("stream requires obj output".to_string());
+    }
+    let path = output_path.ok_or_else(|| "stream requires output_path".to_string())?;
+    let file = std::fs::File::create(path).map_err(|err| format!("{}: {}", path.display(), err))?;
+    let mut writer = ObjStreamWriter::new(std::io::BufWriter::new(file));
+    stream_mesh_graph(
+        &project.graph,
+        output,
+        &mut MeshEvalState::new(),
+        &mut writer,
+        project.graph.units().export_scale(),
+    )?;
+    let (points, prims) = (writer.points(), writer.prims());
+    writer.finish()?;
+    Ok(JobOutput {
+        points,
+        prims,
+        body: None,
+    })
+}
+
+// Joins a client path onto the server root. Absolute paths and `..` are refused, and the
+// path must still be inside the root once symlinks are resolved.
+fn resolve_under_root(root: &Path, path: &Path) -> Result<PathBuf, String> {
+    let relative = path
+        .components()
+        .all(|component| matches!(component, Component::Normal(_) | Component::CurDir));
+    if !relative || path.as_os_str().is_empty() {
+        return Err(format!(
+            "{}: paths must be relative to the server root and may not contain '..'",
+            path.display()
+        ));
+    }
+    let joined = root.join(path);
+    let parent = joined
+        .parent()
+        .unwrap_or(root)
+        .canonicalize()
+        .map_err(|err| format!("{}: {}", path.display(), err))?;
+    // An existing entry, symlinks included, must resolve inside the root too.
+    let resolved = match joined.symlink_metadata() {
+        Ok(_) => joined
+            .canonicalize()
+            .map_err(|err| format!("{}: {}", path.display(), err))?,
+        Err(_) => joined,
+    };
+    if !parent.starts_with(root) || !resolved.starts_with(root) {
+        return Err(format!("{}: outside the server root", path.display()));
+    }
+    Ok(resolved)
+}
+
+fn is_file_node(node: &grapho_core::Node) -> bool {
+    matches!(
+        builtin_kind_from_name(&node.name),
+        Some(BuiltinNodeKind::File | BuiltinNodeKind::MergeFiles | BuiltinNodeKind::ObjOutput)
+    )
+}
+
+// First node that reads or writes files, searching subnets too.
+fn file_node_name(graph: &Graph) -> Option<String> {
+    graph.nodes().find_map(|node| {
+        if is_file_node(node) {
+            return Some(node.name.clone());
+        }
+        node.subnet.as_deref().and_then(file_node_name)
+    })
+}
+
+fn handle_connection(stream: TcpStream, queue: &JobQueue) -> Result<(), String> {
+    let mut reader = BufReader::new(stream.try_clone().map_err(|err| err.to_string())?);
+    let response = match read_request(&mut reader) {
+        Ok(request) => route(&request, queue),
+        Err(response) => response,
+    };
+    write_response(stream, &response)
+}
+
+struct HttpRequest {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+struct Response {
+    status: u16,
+    content_type: &'static str,
+    body: String,
+}
+
+impl Response {
+    fn text(status: u16, body: &str) -> Self {
+        Self {
+            status,
+            content_type: "text/plain",
+            body: body.to_string(),
+        }
+    }
+
+    fn json<T: Serialize>(status: u16, value: &T) -> Self {
+        match serde_json::to_string(value) {
+            Ok(body) => Self {
+                status,
+                content_type: "application/json",
+                body,
+            },
+            Err(err) => Self::text(500, &err.to_string()),
+        }
+    }
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<HttpRequest, Response> {
+    let mut request_line = String::new();
+    read_header_line(reader, &mut request_line)?;
+    let mut parts = request_line.split_whitespace();
+    let method = parts.next().unwrap_or_default().to_string();
+    let path = parts.next().unwrap_or_default().to_string();
+
+    let mut content_length = 0usize;
+    for count in 0.. {
+        if count == MAX_HEADER_LINES {
+            return Err(Response::text(431, "too many header lines"));
+        }
+        let mut line = String::new();
+        let read = read_header_line(reader, &mut line)?;
+        let line = line.trim_end();
+        if read == 0 || line.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':') {
+            if name.trim().eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            }
+        }
+    }
+    if content_length > MAX_BODY_BYTES {
+        return Err(Response::text(413, "request body too large"));
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body).map_err(bad_request)?;
+    Ok(HttpRequest { method, path, body })
+}
+
+fn read_header_line(reader: &mut impl BufRead, line: &mut String) -> Result<usize, Response> {
+    let limit = MAX_HEADER_LINE_BYTES as u64 + 1;
+    let read = reader
+        .by_ref()
+        .take(limit)
+        .read_line(line)
+        .map_err(bad_request)?;
+    if read > MAX_HEADER_LINE_BYTES {
+        return Err(Response::text(431, "header line too long"));
+    }
+    Ok(read)
+}
+
+fn bad_request(err: std::io::Error) -> Response {
+    Response::text(400, &err.to_string())
+}
+
+fn route(request: &HttpRequest, queue: &JobQueue) -> Response {
+    let segments: Vec<&str> = request
+        .path
+        .trim_matches('/')
+        .split('/')
+        .filter(|segment| !segment.is_empty())
+        .collect();
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["health"]) => Response::text(200, "ok"),
+        ("POST", ["jobs"]) => match serde_json::from_slice::<JobRequest>(&request.body) {
+            Ok(job) => match queue.submit(job) {
+                Some(status) => Response::json(202, &status),
+                None => Response::text(503, "job queue is full"),
+            },
+            Err(err) => Response::text(400, &err.to_string()),
+        },
+        ("GET", ["jobs"]) => Response::json(200, &queue.statuses()),
+        ("GET", ["jobs", id]) => match id.parse().ok().and_then(|id| queue.status(id)) {
+            Some(status) => Response::json(200, &status),
+            None => Response::text(404, "unknown job"),
+        },
+        ("GET", ["jobs", id, "result"]) => match id.parse().ok().and_then(|id| queue.result(id)) {
+            Some((JobState::Done, Some(result))) => Response::text(200, &result),
+            Some((JobState::Done, None)) => Response::text(204, ""),
+            Some((JobState::Failed, _)) => Response::text(409, "job failed"),
+            Some(_) => Response::text(409, "job not finished"),
+            None => Response::text(404, "unknown job"),
+        },
+        _ => Response::text(404, "not found"),
+    }
+}
+
+fn write_response(mut stream: TcpStream, response: &Response) -> Result<(), String> {
+    let reason = match response.status {
+        200 => "OK",
+        202 => "Accepted",
+        204 => "No Content",
+        400 => "Bad Request",
+        404 => "Not Found",
+        409 => "Conflict",
+        413 => "Payload Too Large",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Error",
+    };
+    let header = format!(
+        "HTTP/1.1 {} {reason}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+        response.status,
+        response.content_type,
+        response.body.len()
+    );
+    stream
+        .write_all(header.as_bytes())
+        .and_then(|_| stream.write_all(response.body.as_bytes()))
+        .and_then(|_| stream.flush())
+        .map_err(|err| err.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use grapho_core::node_definition;
+
+    fn request(method: &str, path: &str, body: &str) -> HttpRequest {
+        HttpRequest {
+            method: method.to_string(),
+            path: path.to_string(),
+            body: body.as_bytes().to_vec(),
+        }
+    }
+
+    #[test]
+    fn requests_parse_headers_and_body() {
+        let raw = "POST /jobs HTTP/1.1\r\nHost: x\r\ncontent-length: 7\r\n\r\n{\"a\":1}trailing";
+        let parsed = read_request(&mut raw.as_bytes()).ok().unwrap();
+        assert_eq!(parsed.method, "POST");
+        assert_eq!(parsed.path, "/jobs");
+        assert_eq!(parsed.body, b"{\"a\":1}");
+
+        let raw = format!(
+            "POST /jobs HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        assert_eq!(read_request(&mut raw.as_bytes()).err().unwrap().status, 413);
+        let raw = "POST /jobs HTTP/1.1\r\nContent-Length: 10\r\n\r\nshort";
+        assert_eq!(read_request(&mut raw.as_bytes()).err().unwrap().status, 400);
+        let raw = format!(
+            "GET /{} HTTP/1.1\r\n\r\n",
+            "a".repeat(MAX_HEADER_LINE_BYTES)
+        );
+        assert_eq!(read_request(&mut raw.as_bytes()).err().unwrap().status, 431);
+        let raw = format!(
+            "GET / HTTP/1.1\r\n{}\r\n",
+            "X: y\r\n".repeat(MAX_HEADER_LINES)
+        );
+        assert_eq!(read_request(&mut raw.as_bytes()).err().unwrap().status, 431);
+    }
+
+    #[test]
+    fn routes_submit_and_query_jobs() {
+        let queue = JobQueue::default();
+        assert_eq!(route(&request("GET", "/health", ""), &queue).status, 200);
+        assert_eq!(route(&request("POST", "/jobs", "{"), &queue).status, 400);
+
+        let submitted = route(&request("POST", "/jobs", "{}"), &queue);
+        assert_eq!(submitted.status, 202);
+        assert!(
+            submitted.body.contains("\"state\":\"queued\""),
+            "{}",
+            submitted.body
+        );
+        assert_eq!(route(&request("GET", "/jobs/1", ""), &queue).status, 200);
+        assert_eq!(
+            route(&request("GET", "/jobs/1/result", ""), &queue).status,
+            409
+        );
+        assert_eq!(route(&request("GET", "/jobs/2", ""), &queue).status, 404);
+        assert_eq!(
+            route(&request("GET", "/jobs/x/result", ""), &queue).status,
+            404
+        );
+        assert_eq!(route(&request("DELETE", "/jobs/1", ""), &queue).status, 404);
+
+        let (id, _) = queue.next_job();
+        let output = JobOutput {
+            points: 8,
+            prims: 6,
+            body: Some("v 0 0 0".to_string()),
+        };
+        queue.finish(id, 1.0, Ok(output));
+        let result = route(&request("GET", "/jobs/1/result/", ""), &queue);
+        assert_eq!((result.status, result.body.as_str()), (200, "v 0 0 0"));
+    }
+
+    #[test]
+    fn queue_runs_jobs_in_order_and_drops_old_results() {
+        let queue = JobQueue::default();
+        let job = || serde_json::from_str::<JobRequest>("{}").unwrap();
+        let first = queue.submit(job()).unwrap().id;
+        let second = queue.submit(job()).unwrap().id;
+        assert_eq!(queue.next_job().0, first);
+        assert_eq!(queue.status(first).unwrap().state, JobState::Running);
+        assert_eq!(queue.status(second).unwrap().state, JobState::Queued);
+        queue.finish(first, 1.0, Err("boom".to_string()));
+        let status = queue.status(first).unwrap();
+        assert_eq!(status.state, JobState::Failed);
+        assert_eq!(status.error.as_deref(), Some("boom"));
+
+        assert_eq!(queue.next_job().0, second);
+        queue.finish(second, 1.0, Err("boom".to_string()));
+        for _ in 0..MAX_FINISHED_JOBS {
+            let id = queue.submit(job()).unwrap().id;
+            assert_eq!(queue.next_job().0, id);
+            queue.finish(id, 1.0, Err("boom".to_string()));
+        }
+        assert!(queue.status(first).is_none());
+        assert!(queue.status(second).is_none());
+        assert_eq!(queue.statuses().len(), MAX_FINISHED_JOBS);
+
+        for _ in 0..MAX_PENDING_JOBS {
+            assert!(queue.submit(job()).is_some());
+        }
+        assert!(queue.submit(job()).is_none());
+        assert_eq!(route(&request("POST", "/jobs", "{}"), &queue).status, 503);
+    }
+
+    #[test]
+    fn client_paths_stay_under_the_root() {
+        let root = std::env::temp_dir().canonicalize().unwrap();
+        assert_eq!(
+            resolve_under_root(&root, Path::new("out.obj")),
+            Ok(root.join("out.obj"))
+        );
+        assert!(resolve_under_root(&root, Path::new("../out.obj")).is_err());
+        assert!(resolve_under_root(&root, Path::new("a/../../out.obj")).is_err());
+        assert!(resolve_under_root(&root, &root.join("out.obj")).is_err());
+        assert!(resolve_under_root(&root, Path::new("")).is_err());
+
+        #[cfg(unix)]
+        {
+            let dir = root.join(format!("grapho-serve-test-{}", std::process::id()));
+            std::fs::create_dir_all(&dir).unwrap();
+            let link = dir.join("escape.obj");
+            let _ = std::fs::remove_file(&link);
+            std::os::unix::fs::symlink("/etc/hostname", &link).unwrap();
+            let relative = link.strip_prefix(&dir).unwrap();
+            let result = resolve_under_root(&dir, relative);
+            std::fs::remove_dir_all(&dir).unwrap();
+            assert!(result.is_err(), "{:?}", result);
+        }
+    }
+
+    #[test]
+    fn inline_projects_with_file_nodes_are_refused() {
+        let mut project = Project::default();
+        let output = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::ObjOutput));
+        project.graph.set_display_node(Some(output)).unwrap();
+        let request: JobRequest = serde_json::from_value(serde_json::json!({
+            "project": project,
+        }))
+        .unwrap();
+        let mut options = ServeOptions {
+            root: std::env::temp_dir().canonicalize().unwrap(),
+            allow_file_nodes: false,
+            expand_env: false,
+        };
+        let err = run_job(&request, &options).err().unwrap();
+        assert!(err.contains("--allow-file-nodes"), "{err}");
+
+        options.allow_file_nodes = true;
+        let err = run_job(&request, &options).err().unwrap_or_default();
+        assert!(!err.contains("--allow-file-nodes"), "{err}");
+    }
+}
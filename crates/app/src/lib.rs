@@ -40,6 +40,7 @@ pub async fn start(canvas_id: &str) -> Result<(), wasm_bindgen::JsValue> {
                 let (console, log_level_state) = app::setup_tracing();
                 let mut app = GraphoApp::new(console, log_level_state);
                 app.try_load_default_graph();
+                app.start_onboarding_if_first_run();
                 Ok(Box::new(app))
             }),
         )
@@ -1,3 +1,4 @@
+use std::path::PathBuf;
 use std::process;
 
 use eframe::egui;
@@ -22,17 +23,50 @@ fn main() -> eframe::Result<()> {
         }
     }
 
+    app::migrate_legacy_files();
+    app::install_crash_handler(console.clone());
+
+    let viewer_path = args
+        .iter()
+        .position(|arg| arg == "--viewer")
+        .map(|index| args.get(index + 1).map(PathBuf::from));
+    let viewer_path = match viewer_path {
+        Some(Some(path)) => Some(path),
+        Some(None) => {
+            eprintln!("--viewer requires a project path");
+            process::exit(1);
+        }
+        None => None,
+    };
+
+    let safe_mode = args.iter().any(|arg| arg == "--safe-mode");
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default().with_inner_size([1400.0, 900.0]),
         renderer: eframe::Renderer::Wgpu,
         ..Default::default()
     };
+    let title = if viewer_path.is_some() {
+        "grapho viewer"
+    } else {
+        "grapho"
+    };
     eframe::run_native(
-        "grapho",
+        title,
         native_options,
         Box::new(|_cc| {
             let mut app = app::GraphoApp::new(console, log_level_state);
-            app.try_load_default_graph();
+            if let Some(path) = &viewer_path {
+                app.open_viewer(path);
+            } else {
+                if safe_mode {
+                    app.enable_safe_mode();
+                }
+                app.try_load_default_graph();
+                app.run_startup_hook();
+                app.start_onboarding_if_first_run();
+                app.check_crash_restore();
+            }
             Ok(Box::new(app))
         }),
     )
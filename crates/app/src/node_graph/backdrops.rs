@@ -0,0 +1,127 @@
+use egui::emath::TSTransform;
+use egui::{pos2, vec2, Align2, Color32, CursorIcon, FontId, Rect, Sense, Stroke, Ui, Vec2};
+
+use grapho_core::GraphBackdrop;
+
+const BACKDROP_PADDING: f32 = 24.0;
+const BACKDROP_TITLE_HEIGHT: f32 = 24.0;
+const HANDLE_SIZE: f32 = 14.0;
+const MIN_SIZE: Vec2 = vec2(120.0, 60.0);
+const BACKDROP_COLORS: [[u8; 3]; 4] = [
+    [70, 110, 170],
+    [90, 150, 90],
+    [170, 120, 60],
+    [140, 90, 160],
+];
+
+pub(super) enum BackdropEdit {
+    // Graph-space offset of a backdrop dragged by its title bar; nodes inside follow it.
+    Moved { rect: Rect, delta: Vec2 },
+    Changed,
+}
+
+fn backdrop_rect(backdrop: &GraphBackdrop) -> Rect {
+    Rect::from_min_max(
+        pos2(backdrop.min[0], backdrop.min[1]),
+        pos2(backdrop.max[0], backdrop.max[1]),
+    )
+}
+
+pub(super) fn new_backdrop(bounds: Rect, index: usize) -> GraphBackdrop {
+    let rect = bounds
+        .expand(BACKDROP_PADDING)
+        .with_min_y(bounds.min.y - BACKDROP_PADDING - BACKDROP_TITLE_HEIGHT);
+    GraphBackdrop {
+        title: format!("Backdrop {}", index + 1),
+        min: [rect.min.x, rect.min.y],
+        max: [rect.max.x, rect.max.y],
+        color: BACKDROP_COLORS[index % BACKDROP_COLORS.len()],
+    }
+}
+
+pub(super) fn paint_backdrops(ui: &Ui, to_global: TSTransform, backdrops: &[GraphBackdrop]) {
+    let painter = ui.painter();
+    let scale = to_global.scaling;
+    for backdrop in backdrops {
+        let rect = to_global * backdrop_rect(backdrop);
+        let [r, g, b] = backdrop.color;
+        let title =
+            Rect::from_min_size(rect.min, vec2(rect.width(), BACKDROP_TITLE_HEIGHT * scale));
+        painter.rect_filled(rect, 6.0, Color32::from_rgba_unmultiplied(r, g, b, 40));
+        painter.rect_filled(title, 6.0, Color32::from_rgba_unmultiplied(r, g, b, 120));
+        painter.text(
+            title.left_center() + vec2(8.0 * scale, 0.0),
+            Align2::LEFT_CENTER,
+            &backdrop.title,
+            FontId::proportional(14.0 * scale),
+            Color32::from_gray(230),
+        );
+        let corner = rect.max - vec2(4.0, 4.0);
+        let stroke = Stroke::new(1.0, Color32::from_rgb(r, g, b));
+        for step in [4.0, 8.0] {
+            painter.line_segment([corner - vec2(step, 0.0), corner - vec2(0.0, step)], stroke);
+        }
+    }
+}
+
+// Title bars drag, corner handles resize and a right click on the title edits the backdrop.
+// Called after the graph is drawn so these areas win over canvas panning.
+pub(super) fn interact_backdrops(
+    ui: &mut Ui,
+    to_global: TSTransform,
+    backdrops: &mut Vec<GraphBackdrop>,
+) -> Option<BackdropEdit> {
+    let scale = to_global.scaling.max(0.0001);
+    let mut edit = None;
+    let mut removed = None;
+    for (index, backdrop) in backdrops.iter_mut().enumerate() {
+        let graph_rect = backdrop_rect(backdrop);
+        let rect = to_global * graph_rect;
+        let id = ui.id().with(("backdrop", index));
+        let title =
+            Rect::from_min_size(rect.min, vec2(rect.width(), BACKDROP_TITLE_HEIGHT * scale));
+        let response = ui
+            .interact(title, id.with("title"), Sense::click_and_drag())
+            .on_hover_cursor(CursorIcon::Grab);
+        if response.dragged() {
+            let delta = response.drag_delta() / scale;
+            for axis in 0..2 {
+                backdrop.min[axis] += delta[axis];
+                backdrop.max[axis] += delta[axis];
+            }
+            edit = Some(BackdropEdit::Moved {
+                rect: graph_rect,
+                delta,
+            });
+        }
+        response.context_menu(|ui| {
+            let title_changed = ui.text_edit_singleline(&mut backdrop.title).changed();
+            let color_changed = ui.color_edit_button_srgb(&mut backdrop.color).changed();
+            if title_changed || color_changed {
+                edit = Some(BackdropEdit::Changed);
+            }
+            if ui.button("Delete backdrop").clicked() {
+                removed = Some(index);
+                ui.close();
+            }
+        });
+
+        let handle = Rect::from_min_max(rect.max - vec2(HANDLE_SIZE, HANDLE_SIZE), rect.max);
+        let response = ui
+            .interact(handle, id.with("resize"), Sense::drag())
+            .on_hover_cursor(CursorIcon::ResizeNwSe);
+        if response.dragged() {
+            let delta = response.drag_delta() / scale;
+            for axis in 0..2 {
+                backdrop.max[axis] =
+                    (backdrop.max[axis] + delta[axis]).max(backdrop.min[axis] + MIN_SIZE[axis]);
+            }
+            edit = Some(BackdropEdit::Changed);
+        }
+    }
+    if let Some(index) = removed {
+        backdrops.remove(index);
+        edit = Some(BackdropEdit::Changed);
+    }
+    edit
+}
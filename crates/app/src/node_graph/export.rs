@@ -0,0 +1,452 @@
+use std::collections::HashMap;
+use std::fmt::Write as _;
+
+use egui::epaint::text::FontDefinitions;
+use egui::epaint::{
+    AlphaFromCoverage, ClippedShape, ColorImage, CornerRadius, CubicBezierShape, Fonts, FontsView,
+    Mesh, Primitive, TessellationOptions, Tessellator,
+};
+use egui::{Align2, Color32, FontId, Pos2, Rect, Shape, Stroke, StrokeKind, Vec2};
+use grapho_core::Graph;
+
+use super::state::NodeGraphLayout;
+use super::utils::pin_color;
+
+const NODE_WIDTH: f32 = 180.0;
+const HEADER_HEIGHT: f32 = 26.0;
+const PIN_ROW_HEIGHT: f32 = 20.0;
+const NODE_PADDING: f32 = 6.0;
+const CORNER_RADIUS: u8 = 4;
+const PIN_RADIUS: f32 = 4.0;
+const WIRE_WIDTH: f32 = 2.0;
+const FONT_SIZE: f32 = 13.0;
+const MARGIN: f32 = 40.0;
+const PNG_SCALE: f32 = 2.0;
+const MAX_PNG_SIDE: f32 = 8192.0;
+
+const BACKGROUND: Color32 = Color32::from_rgb(27, 27, 27);
+const NODE_FILL: Color32 = Color32::from_rgb(45, 45, 45);
+const HEADER_FILL: Color32 = Color32::from_rgb(200, 200, 200);
+const TITLE_COLOR: Color32 = Color32::from_rgb(60, 60, 60);
+const LABEL_COLOR: Color32 = Color32::from_rgb(210, 210, 210);
+const OUTLINE_COLOR: Color32 = Color32::from_rgb(100, 100, 100);
+const DISPLAY_COLOR: Color32 = Color32::from_rgb(40, 140, 230);
+const TEMPLATE_COLOR: Color32 = Color32::from_rgb(150, 90, 200);
+
+struct ExportPin {
+    pos: Pos2,
+    label: String,
+    color: Color32,
+}
+
+struct ExportNode {
+    rect: Rect,
+    title: String,
+    outline: Color32,
+    inputs: Vec<ExportPin>,
+    outputs: Vec<ExportPin>,
+}
+
+struct ExportWire {
+    from: Pos2,
+    to: Pos2,
+    color: Color32,
+}
+
+struct ExportGraph {
+    nodes: Vec<ExportNode>,
+    wires: Vec<ExportWire>,
+    bounds: Rect,
+}
+
+pub fn graph_to_svg(graph: &Graph, layout: &NodeGraphLayout) -> Result<String, String> {
+    let export = export_graph(graph, layout)?;
+    let offset = Vec2::splat(MARGIN) - export.bounds.min.to_vec2();
+    let size = export.bounds.size() + Vec2::splat(MARGIN * 2.0);
+    let mut svg = String::new();
+    let _ = writeln!(
+        svg,
+        r#"<svg xmlns="http://www.w3.org/2000/svg" width="{w:.0}" height="{h:.0}" viewBox="0 0 {w:.0} {h:.0}" font-family="sans-serif" font-size="{FONT_SIZE}">"#,
+        w = size.x.ceil(),
+        h = size.y.ceil(),
+    );
+    let _ = writeln!(
+        svg,
+        r#"<rect width="100%" height="100%" fill="{}"/>"#,
+        svg_color(BACKGROUND)
+    );
+    for wire in &export.wires {
+        let [from, c1, c2, to] = wire_points(wire.from + offset, wire.to + offset);
+        let _ = writeln!(
+            svg,
+            r#"<path d="M {:.1} {:.1} C {:.1} {:.1} {:.1} {:.1} {:.1} {:.1}" fill="none" stroke="{}" stroke-width="{WIRE_WIDTH}"/>"#,
+            from.x,
+            from.y,
+            c1.x,
+            c1.y,
+            c2.x,
+            c2.y,
+            to.x,
+            to.y,
+            svg_color(wire.color)
+        );
+    }
+    for node in &export.nodes {
+        let rect = node.rect.translate(offset);
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="{CORNER_RADIUS}" fill="{}"/>"#,
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height(),
+            svg_color(NODE_FILL)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<path d="M {l:.1} {b:.1} V {t:.1} Q {l:.1} {top:.1} {tl:.1} {top:.1} H {tr:.1} Q {r:.1} {top:.1} {r:.1} {t:.1} V {b:.1} Z" fill="{}"/>"#,
+            svg_color(HEADER_FILL),
+            l = rect.left(),
+            r = rect.right(),
+            top = rect.top(),
+            t = rect.top() + CORNER_RADIUS as f32,
+            tl = rect.left() + CORNER_RADIUS as f32,
+            tr = rect.right() - CORNER_RADIUS as f32,
+            b = rect.top() + HEADER_HEIGHT,
+        );
+        let _ = writeln!(
+            svg,
+            r#"<rect x="{:.1}" y="{:.1}" width="{:.1}" height="{:.1}" rx="{CORNER_RADIUS}" fill="none" stroke="{}" stroke-width="{}"/>"#,
+            rect.left(),
+            rect.top(),
+            rect.width(),
+            rect.height(),
+            svg_color(node.outline),
+            outline_width(node.outline)
+        );
+        let _ = writeln!(
+            svg,
+            r#"<text x="{:.1}" y="{:.1}" dominant-baseline="central" font-weight="bold" fill="{}">{}</text>"#,
+            rect.left() + 8.0,
+            rect.top() + HEADER_HEIGHT * 0.5,
+            svg_color(TITLE_COLOR),
+            escape_xml(&node.title)
+        );
+        for (pins, anchor, dx) in [(&node.inputs, "start", 10.0), (&node.outputs, "end", -10.0)] {
+            for pin in pins {
+                let pos = pin.pos + offset;
+                let _ = writeln!(
+                    svg,
+                    r#"<circle cx="{:.1}" cy="{:.1}" r="{PIN_RADIUS}" fill="{}"/>"#,
+                    pos.x,
+                    pos.y,
+                    svg_color(pin.color)
+                );
+                let _ = writeln!(
+                    svg,
+                    r#"<text x="{:.1}" y="{:.1}" dominant-baseline="central" text-anchor="{anchor}" fill="{}">{}</text>"#,
+                    pos.x + dx,
+                    pos.y,
+                    svg_color(LABEL_COLOR),
+                    escape_xml(&pin.label)
+                );
+            }
+        }
+    }
+    svg.push_str("</svg>\n");
+    Ok(svg)
+}
+
+pub fn graph_to_png(graph: &Graph, layout: &NodeGraphLayout) -> Result<Vec<u8>, String> {
+    let export = export_graph(graph, layout)?;
+    let offset = Vec2::splat(MARGIN) - export.bounds.min.to_vec2();
+    let size = export.bounds.size() + Vec2::splat(MARGIN * 2.0);
+    let scale = PNG_SCALE.min(MAX_PNG_SIDE / size.x.max(size.y));
+    let width = (size.x * scale).ceil() as usize;
+    let height = (size.y * scale).ceil() as usize;
+
+    let mut fonts = Fonts::new(
+        MAX_PNG_SIDE as usize,
+        AlphaFromCoverage::default(),
+        FontDefinitions::default(),
+    );
+    let shapes = export_shapes(&export, offset, &mut fonts.with_pixels_per_point(scale));
+    let font_image = fonts.image();
+    let mut tessellator = Tessellator::new(
+        scale,
+        TessellationOptions::default(),
+        font_image.size,
+        fonts.texture_atlas().prepared_discs(),
+    );
+    let clip_rect = Rect::from_min_size(Pos2::ZERO, size);
+    let primitives = tessellator.tessellate_shapes(
+        shapes
+            .into_iter()
+            .map(|shape| ClippedShape { clip_rect, shape })
+            .collect(),
+    );
+
+    let mut pixels = vec![BACKGROUND; width * height];
+    for primitive in primitives {
+        if let Primitive::Mesh(mesh) = primitive.primitive {
+            rasterize_mesh(&mesh, scale, &font_image, width, height, &mut pixels);
+        }
+    }
+    encode_png(width, height, &pixels)
+}
+
+fn export_graph(graph: &Graph, layout: &NodeGraphLayout) -> Result<ExportGraph, String> {
+    let mut pin_positions = HashMap::new();
+    let mut nodes = Vec::new();
+    for node in graph.nodes() {
+        let Some(pos) = layout.positions.get(&node.id) else {
+            continue;
+        };
+        let rows = node.inputs.len().max(node.outputs.len()).max(1);
+        let rect = Rect::from_min_size(
+            *pos,
+            Vec2::new(
+                NODE_WIDTH,
+                HEADER_HEIGHT + rows as f32 * PIN_ROW_HEIGHT + NODE_PADDING,
+            ),
+        );
+        let mut pins = |ids: &[grapho_core::PinId], x: f32| -> Vec<ExportPin> {
+            ids.iter()
+                .enumerate()
+                .filter_map(|(index, pin_id)| {
+                    let pin = graph.pin(*pin_id)?;
+                    let pos = Pos2::new(
+                        x,
+                        rect.top() + HEADER_HEIGHT + (index as f32 + 0.5) * PIN_ROW_HEIGHT,
+                    );
+                    pin_positions.insert(*pin_id, (pos, pin_color(pin.pin_type)));
+                    Some(ExportPin {
+                        pos,
+                        label: pin.name.clone(),
+                        color: pin_color(pin.pin_type),
+                    })
+                })
+                .collect()
+        };
+        let inputs = pins(&node.inputs, rect.left());
+        let outputs = pins(&node.outputs, rect.right());
+        let outline = if node.display {
+            DISPLAY_COLOR
+        } else if node.template {
+            TEMPLATE_COLOR
+        } else {
+            OUTLINE_COLOR
+        };
+        nodes.push(ExportNode {
+            rect,
+            title: node.name.clone(),
+            outline,
+            inputs,
+            outputs,
+        });
+    }
+    let mut bounds = nodes
+        .iter()
+        .map(|node| node.rect)
+        .reduce(|a, b| a.union(b))
+        .ok_or_else(|| "graph has no nodes to export".to_string())?;
+    let wires: Vec<ExportWire> = graph
+        .links()
+        .filter_map(|link| {
+            let (from, color) = pin_positions.get(&link.from)?;
+            let (to, _) = pin_positions.get(&link.to)?;
+            Some(ExportWire {
+                from: *from,
+                to: *to,
+                color: *color,
+            })
+        })
+        .collect();
+    // Wires running backwards bulge past the nodes; keep them inside the image.
+    for wire in &wires {
+        for point in wire_points(wire.from, wire.to) {
+            bounds.extend_with(point);
+        }
+    }
+    Ok(ExportGraph {
+        nodes,
+        wires,
+        bounds,
+    })
+}
+
+fn export_shapes(export: &ExportGraph, offset: Vec2, fonts: &mut FontsView<'_>) -> Vec<Shape> {
+    let font_id = FontId::proportional(FONT_SIZE);
+    let mut shapes = Vec::new();
+    for wire in &export.wires {
+        shapes.push(Shape::CubicBezier(CubicBezierShape::from_points_stroke(
+            wire_points(wire.from + offset, wire.to + offset),
+            false,
+            Color32::TRANSPARENT,
+            Stroke::new(WIRE_WIDTH, wire.color),
+        )));
+    }
+    for node in &export.nodes {
+        let rect = node.rect.translate(offset);
+        let header = Rect::from_min_max(
+            rect.min,
+            Pos2::new(rect.right(), rect.top() + HEADER_HEIGHT),
+        );
+        shapes.push(Shape::rect_filled(rect, CORNER_RADIUS, NODE_FILL));
+        shapes.push(Shape::rect_filled(
+            header,
+            CornerRadius {
+                nw: CORNER_RADIUS,
+                ne: CORNER_RADIUS,
+                sw: 0,
+                se: 0,
+            },
+            HEADER_FILL,
+        ));
+        shapes.push(Shape::rect_stroke(
+            rect,
+            CORNER_RADIUS,
+            Stroke::new(outline_width(node.outline), node.outline),
+            StrokeKind::Inside,
+        ));
+        shapes.push(Shape::text(
+            fonts,
+            header.left_center() + Vec2::new(8.0, 0.0),
+            Align2::LEFT_CENTER,
+            &node.title,
+            FontId::proportional(FONT_SIZE + 1.0),
+            TITLE_COLOR,
+        ));
+        for (pins, anchor, dx) in [
+            (&node.inputs, Align2::LEFT_CENTER, 10.0),
+            (&node.outputs, Align2::RIGHT_CENTER, -10.0),
+        ] {
+            for pin in pins {
+                let pos = pin.pos + offset;
+                shapes.push(Shape::circle_filled(pos, PIN_RADIUS, pin.color));
+                shapes.push(Shape::text(
+                    fonts,
+                    pos + Vec2::new(dx, 0.0),
+                    anchor,
+                    &pin.label,
+                    font_id.clone(),
+                    LABEL_COLOR,
+                ));
+            }
+        }
+    }
+    shapes
+}
+
+fn wire_points(from: Pos2, to: Pos2) -> [Pos2; 4] {
+    let bend = ((to.x - from.x).abs() * 0.5).max(40.0);
+    [
+        from,
+        from + Vec2::new(bend, 0.0),
+        to - Vec2::new(bend, 0.0),
+        to,
+    ]
+}
+
+fn outline_width(color: Color32) -> f32 {
+    if color == OUTLINE_COLOR {
+        1.0
+    } else {
+        2.0
+    }
+}
+
+fn rasterize_mesh(
+    mesh: &Mesh,
+    scale: f32,
+    texture: &ColorImage,
+    width: usize,
+    height: usize,
+    pixels: &mut [Color32],
+) {
+    let [tex_w, tex_h] = texture.size;
+    for triangle in mesh.indices.chunks_exact(3) {
+        let [a, mut b, mut c] = [0, 1, 2].map(|corner| &mesh.vertices[triangle[corner] as usize]);
+        if edge(a.pos, b.pos, c.pos) < 0.0 {
+            std::mem::swap(&mut b, &mut c);
+        }
+        let [pa, pb, pc] = [a, b, c].map(|vertex| vertex.pos * scale);
+        let area = edge(pa, pb, pc);
+        if area <= f32::EPSILON {
+            continue;
+        }
+        let min_x = pa.x.min(pb.x).min(pc.x).floor().max(0.0) as usize;
+        let min_y = pa.y.min(pb.y).min(pc.y).floor().max(0.0) as usize;
+        let max_x = (pa.x.max(pb.x).max(pc.x).ceil() as usize).min(width);
+        let max_y = (pa.y.max(pb.y).max(pc.y).ceil() as usize).min(height);
+        for y in min_y..max_y {
+            for x in min_x..max_x {
+                let p = Pos2::new(x as f32 + 0.5, y as f32 + 0.5);
+                let (ea, eb, ec) = (edge(pb, pc, p), edge(pc, pa, p), edge(pa, pb, p));
+                if !covers(ea, pb, pc) || !covers(eb, pc, pa) || !covers(ec, pa, pb) {
+                    continue;
+                }
+                let (wa, wb, wc) = (ea / area, eb / area, ec / area);
+                let uv = a.uv.to_vec2() * wa + b.uv.to_vec2() * wb + c.uv.to_vec2() * wc;
+                let tx = ((uv.x * tex_w as f32) as usize).min(tex_w - 1);
+                let ty = ((uv.y * tex_h as f32) as usize).min(tex_h - 1);
+                let texel = texture.pixels[ty * tex_w + tx];
+                let src: [f32; 4] = std::array::from_fn(|channel| {
+                    let color = a.color[channel] as f32 * wa
+                        + b.color[channel] as f32 * wb
+                        + c.color[channel] as f32 * wc;
+                    color * texel[channel] as f32 / (255.0 * 255.0)
+                });
+                // Colors are premultiplied, so blending is a plain "over".
+                let dst = &mut pixels[y * width + x];
+                let keep = 1.0 - src[3];
+                let blended: [u8; 4] = std::array::from_fn(|channel| {
+                    let value = src[channel] + dst[channel] as f32 / 255.0 * keep;
+                    (value * 255.0).round().clamp(0.0, 255.0) as u8
+                });
+                *dst = Color32::from_rgba_premultiplied(
+                    blended[0], blended[1], blended[2], blended[3],
+                );
+            }
+        }
+    }
+}
+
+fn edge(a: Pos2, b: Pos2, p: Pos2) -> f32 {
+    (b.x - a.x) * (p.y - a.y) - (b.y - a.y) * (p.x - a.x)
+}
+
+// Pixels exactly on an edge belong to only one of the two triangles sharing it,
+// otherwise feathered seams get blended twice.
+fn covers(weight: f32, from: Pos2, to: Pos2) -> bool {
+    weight > 0.0 || (weight == 0.0 && (to.y > from.y || (to.y == from.y && to.x > from.x)))
+}
+
+fn encode_png(width: usize, height: usize, pixels: &[Color32]) -> Result<Vec<u8>, String> {
+    let data: Vec<u8> = pixels.iter().flat_map(|pixel| pixel.to_array()).collect();
+    let mut bytes = Vec::new();
+    let mut encoder = png::Encoder::new(&mut bytes, width as u32, height as u32);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    let mut writer = encoder
+        .write_header()
+        .map_err(|err| format!("png encode failed: {}", err))?;
+    writer
+        .write_image_data(&data)
+        .map_err(|err| format!("png encode failed: {}", err))?;
+    writer
+        .finish()
+        .map_err(|err| format!("png encode failed: {}", err))?;
+    Ok(bytes)
+}
+
+fn svg_color(color: Color32) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r(), color.g(), color.b())
+}
+
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
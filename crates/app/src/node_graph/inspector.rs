@@ -0,0 +1,66 @@
+use egui::Ui;
+
+use grapho_core::{Graph, GraphCommand, NodeId, ParamValue, StableHasher};
+
+use super::params::int_slider_range;
+
+pub trait NodeInspector {
+    fn show(&self, ui: &mut Ui, graph: &mut Graph, node_id: NodeId) -> bool;
+}
+
+pub(super) struct SeedInspector;
+
+impl NodeInspector for SeedInspector {
+    fn show(&self, ui: &mut Ui, graph: &mut Graph, node_id: NodeId) -> bool {
+        let Some(node) = graph.node(node_id) else {
+            return false;
+        };
+        let seed = node.params.get_int("seed", 1);
+        if !ui
+            .button("Reshuffle seed")
+            .on_hover_text("Pick a new random seed")
+            .clicked()
+        {
+            return false;
+        }
+        let next = reshuffled_seed(seed, node_id.as_u64());
+        graph
+            .apply(&GraphCommand::SetParam {
+                node: node_id,
+                key: "seed".to_string(),
+                value: ParamValue::Int(next),
+            })
+            .is_ok()
+    }
+}
+
+// Hash of the old seed and the node, folded into the seed slider's range.
+fn reshuffled_seed(seed: i32, salt: u64) -> i32 {
+    let range = int_slider_range("", "seed", seed);
+    let span = i64::from(range.end() - range.start()) + 1;
+    let mut hasher = StableHasher::new();
+    hasher.write_i64(seed.into());
+    hasher.write_u64(salt);
+    let mut next = range.start() + (hasher.finish() as i64).rem_euclid(span) as i32;
+    if next == seed {
+        next = range.start() + (i64::from(next - range.start()) + 1).rem_euclid(span) as i32;
+    }
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn reshuffled_seed_stays_in_slider_range() {
+        let range = int_slider_range("", "seed", 0);
+        for seed in [-1_000_000, -1, 0, 1, 50, 100, 101, i32::MIN, i32::MAX] {
+            for salt in 0..20 {
+                let next = reshuffled_seed(seed, salt);
+                assert!(range.contains(&next), "{seed} -> {next}");
+                assert_ne!(next, seed);
+            }
+        }
+    }
+}
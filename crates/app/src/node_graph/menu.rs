@@ -28,6 +28,11 @@ pub(super) fn builtin_menu_items() -> Vec<MenuItem> {
             name: "File",
             category: "Sources",
         },
+        MenuItem {
+            kind: BuiltinNodeKind::MergeFiles,
+            name: "Merge Files",
+            category: "Sources",
+        },
         MenuItem {
             kind: BuiltinNodeKind::Scatter,
             name: "Scatter",
@@ -53,6 +58,11 @@ pub(super) fn builtin_menu_items() -> Vec<MenuItem> {
             name: "Copy to Points",
             category: "Operators",
         },
+        MenuItem {
+            kind: BuiltinNodeKind::Boolean,
+            name: "Boolean",
+            category: "Operators",
+        },
         MenuItem {
             kind: BuiltinNodeKind::Normal,
             name: "Normal",
@@ -68,16 +78,116 @@ pub(super) fn builtin_menu_items() -> Vec<MenuItem> {
             name: "Noise/Mountain",
             category: "Operators",
         },
+        MenuItem {
+            kind: BuiltinNodeKind::Wobble,
+            name: "Wobble",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::ParticleSim,
+            name: "Particle Sim",
+            category: "Operators",
+        },
         MenuItem {
             kind: BuiltinNodeKind::AttributeMath,
             name: "Attribute Math",
             category: "Operators",
         },
+        MenuItem {
+            kind: BuiltinNodeKind::AttributePrecision,
+            name: "Attribute Precision",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::AttributeEdit,
+            name: "Attribute Edit",
+            category: "Operators",
+        },
         MenuItem {
             kind: BuiltinNodeKind::Wrangle,
             name: "Wrangle",
             category: "Operators",
         },
+        MenuItem {
+            kind: BuiltinNodeKind::PolyExtrude,
+            name: "PolyExtrude",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Bevel,
+            name: "Bevel",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Clean,
+            name: "Clean",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Sort,
+            name: "Sort",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Split,
+            name: "Split",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Lod,
+            name: "LOD",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::BakeAo,
+            name: "Bake AO",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Curvature,
+            name: "Curvature",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Slope,
+            name: "Slope",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::GeodesicDistance,
+            name: "Geodesic Distance",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::FloorSplit,
+            name: "Floor Split",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::FacadeRepeat,
+            name: "Facade Repeat",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::WindowScatter,
+            name: "Window Scatter",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::Subnet,
+            name: "Subnet",
+            category: "Operators",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::SubnetInput,
+            name: "Subnet Input",
+            category: "Sources",
+        },
+        MenuItem {
+            kind: BuiltinNodeKind::SubnetOutput,
+            name: "Subnet Output",
+            category: "Outputs",
+        },
         MenuItem {
             kind: BuiltinNodeKind::ObjOutput,
             name: "OBJ Output",
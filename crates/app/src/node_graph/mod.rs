@@ -1,7 +1,12 @@
+mod backdrops;
+mod export;
+mod inspector;
 mod menu;
 mod params;
 mod state;
 mod utils;
 mod viewer;
 
-pub use state::{NodeGraphLayout, NodeGraphState};
+pub use export::{graph_to_png, graph_to_svg};
+pub(crate) use params::edit_param;
+pub use state::{NodeAlign, NodeGraphLayout, NodeGraphState};
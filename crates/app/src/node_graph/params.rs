@@ -1,16 +1,17 @@
-use egui::Ui;
+use egui::{Response, Ui};
 
-use grapho_core::ParamValue;
+use grapho_core::{parse_numeric_entry, LengthUnit, ParamValue};
 
-pub(super) fn edit_param(
+pub(crate) fn edit_param(
     ui: &mut Ui,
     node_name: &str,
     label: &str,
     value: ParamValue,
-) -> (ParamValue, bool) {
+    unit: LengthUnit,
+) -> (ParamValue, bool, Response) {
     match value {
         ParamValue::Float(mut v) => {
-            let changed = param_row(ui, label, |ui| {
+            let (changed, label_response) = param_row(ui, label, |ui| {
                 let mut changed = false;
                 let spacing = 8.0;
                 let value_width = 72.0;
@@ -18,7 +19,7 @@ pub(super) fn edit_param(
                 if ui
                     .add_sized(
                         [value_width, height],
-                        egui::DragValue::new(&mut v).speed(0.1),
+                        float_drag(&mut v, label, unit).speed(0.1),
                     )
                     .changed()
                 {
@@ -38,42 +39,22 @@ pub(super) fn edit_param(
                 }
                 changed
             });
-            (ParamValue::Float(v), changed)
+            (ParamValue::Float(v), changed, label_response)
         }
         ParamValue::Int(mut v) => {
-            let changed = if label == "domain" || label == "mode" {
+            let options = int_options(node_name, label);
+            let (changed, label_response) = if !options.is_empty() {
                 param_row(ui, label, |ui| {
                     let mut changed = false;
-                    let options = [(1, "Vertex"), (0, "Point"), (2, "Primitive"), (3, "Detail")];
                     let selected = options
                         .iter()
                         .find(|(value, _)| *value == v)
-                        .map(|(_, name)| *name)
-                        .unwrap_or("Point");
+                        .map(|(_, name)| name.to_string())
+                        .unwrap_or_else(|| v.to_string());
                     egui::ComboBox::from_id_salt(label)
                         .selected_text(selected)
                         .show_ui(ui, |ui| {
-                            for (value, name) in options {
-                                if ui.selectable_value(&mut v, value, name).changed() {
-                                    changed = true;
-                                }
-                            }
-                        });
-                    changed
-                })
-            } else if label == "op" {
-                param_row(ui, label, |ui| {
-                    let mut changed = false;
-                    let options = [(0, "Add"), (1, "Subtract"), (2, "Multiply"), (3, "Divide")];
-                    let selected = options
-                        .iter()
-                        .find(|(value, _)| *value == v)
-                        .map(|(_, name)| *name)
-                        .unwrap_or("Add");
-                    egui::ComboBox::from_id_salt(label)
-                        .selected_text(selected)
-                        .show_ui(ui, |ui| {
-                            for (value, name) in options {
+                            for &(value, name) in options {
                                 if ui.selectable_value(&mut v, value, name).changed() {
                                     changed = true;
                                 }
@@ -111,17 +92,17 @@ pub(super) fn edit_param(
                     changed
                 })
             };
-            (ParamValue::Int(v), changed)
+            (ParamValue::Int(v), changed, label_response)
         }
         ParamValue::Bool(mut v) => {
-            let changed = param_row(ui, label, |ui| {
+            let (changed, label_response) = param_row(ui, label, |ui| {
                 let checkbox = egui::Checkbox::without_text(&mut v);
                 ui.add(checkbox).changed()
             });
-            (ParamValue::Bool(v), changed)
+            (ParamValue::Bool(v), changed, label_response)
         }
         ParamValue::Vec2(mut v) => {
-            let changed = param_row(ui, label, |ui| {
+            let (changed, label_response) = param_row(ui, label, |ui| {
                 let mut changed = false;
                 let spacing = 8.0;
                 let available = ui.available_width();
@@ -130,7 +111,10 @@ pub(super) fn edit_param(
                 let len = v.len();
                 for (idx, item) in v.iter_mut().enumerate() {
                     if ui
-                        .add_sized([value_width, height], egui::DragValue::new(item).speed(0.1))
+                        .add_sized(
+                            [value_width, height],
+                            float_drag(item, label, unit).speed(0.1),
+                        )
                         .changed()
                     {
                         changed = true;
@@ -141,10 +125,10 @@ pub(super) fn edit_param(
                 }
                 changed
             });
-            (ParamValue::Vec2(v), changed)
+            (ParamValue::Vec2(v), changed, label_response)
         }
         ParamValue::Vec3(mut v) => {
-            let changed = param_row(ui, label, |ui| {
+            let (changed, label_response) = param_row(ui, label, |ui| {
                 let mut changed = false;
                 let spacing = 8.0;
                 let available = ui.available_width();
@@ -153,7 +137,10 @@ pub(super) fn edit_param(
                 let len = v.len();
                 for (idx, item) in v.iter_mut().enumerate() {
                     if ui
-                        .add_sized([value_width, height], egui::DragValue::new(item).speed(0.1))
+                        .add_sized(
+                            [value_width, height],
+                            float_drag(item, label, unit).speed(0.1),
+                        )
                         .changed()
                     {
                         changed = true;
@@ -164,10 +151,10 @@ pub(super) fn edit_param(
                 }
                 changed
             });
-            (ParamValue::Vec3(v), changed)
+            (ParamValue::Vec3(v), changed, label_response)
         }
         ParamValue::String(mut v) => {
-            let changed = if label == "code" {
+            let (changed, label_response) = if label == "code" {
                 param_row_with_height(ui, label, 120.0, |ui| {
                     ui.add_sized(
                         [ui.available_width().max(160.0), 100.0],
@@ -187,12 +174,198 @@ pub(super) fn edit_param(
                     .changed()
                 })
             };
-            (ParamValue::String(v), changed)
+            (ParamValue::String(v), changed, label_response)
+        }
+        ParamValue::Quat(v) => {
+            let (mut axis, mut angle_deg) = quat_to_axis_angle(v);
+            let (changed, label_response) = param_row(ui, label, |ui| {
+                let mut changed = false;
+                let spacing = 8.0;
+                let available = ui.available_width();
+                let value_width = ((available - spacing * 3.0) / 4.0).clamp(44.0, 90.0);
+                let height = ui.spacing().interact_size.y;
+                for item in axis.iter_mut() {
+                    if ui
+                        .add_sized(
+                            [value_width, height],
+                            egui::DragValue::new(item).speed(0.01),
+                        )
+                        .on_hover_text("Rotation axis")
+                        .changed()
+                    {
+                        changed = true;
+                    }
+                    ui.add_space(spacing);
+                }
+                if ui
+                    .add_sized(
+                        [value_width, height],
+                        egui::DragValue::new(&mut angle_deg).speed(1.0).suffix("°"),
+                    )
+                    .on_hover_text("Angle")
+                    .changed()
+                {
+                    changed = true;
+                }
+                changed
+            });
+            let v = if changed {
+                quat_from_axis_angle(axis, angle_deg)
+            } else {
+                v
+            };
+            (ParamValue::Quat(v), changed, label_response)
+        }
+        ParamValue::Mat4(mut v) => {
+            let (changed, label_response) = param_row_with_height(ui, label, 100.0, |ui| {
+                let mut changed = false;
+                egui::Grid::new(("mat4_param", node_name, label))
+                    .num_columns(4)
+                    .spacing([4.0, 2.0])
+                    .show(ui, |ui| {
+                        // Rows on screen, column-major storage.
+                        for row in 0..4 {
+                            for col in 0..4 {
+                                let item = &mut v[col * 4 + row];
+                                if ui
+                                    .add_sized([52.0, 18.0], egui::DragValue::new(item).speed(0.01))
+                                    .changed()
+                                {
+                                    changed = true;
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    });
+                changed
+            });
+            (ParamValue::Mat4(v), changed, label_response)
+        }
+        ParamValue::StringList(mut v) => {
+            let height = ui.spacing().interact_size.y;
+            let row_height = (v.len() + 1) as f32 * (height + 4.0);
+            let (changed, label_response) = param_row_with_height(ui, label, row_height, |ui| {
+                let mut changed = false;
+                let mut remove = None;
+                ui.vertical(|ui| {
+                    for (idx, item) in v.iter_mut().enumerate() {
+                        ui.horizontal(|ui| {
+                            let width = (ui.available_width() - height - 8.0).max(120.0);
+                            if ui
+                                .add_sized([width, height], egui::TextEdit::singleline(item))
+                                .changed()
+                            {
+                                changed = true;
+                            }
+                            if ui.small_button("−").on_hover_text("Remove row").clicked() {
+                                remove = Some(idx);
+                            }
+                        });
+                    }
+                    if ui.small_button("+").on_hover_text("Add row").clicked() {
+                        v.push(String::new());
+                        changed = true;
+                    }
+                });
+                if let Some(idx) = remove {
+                    v.remove(idx);
+                    changed = true;
+                }
+                changed
+            });
+            (ParamValue::StringList(v), changed, label_response)
         }
     }
 }
 
-fn param_row(ui: &mut Ui, label: &str, add_controls: impl FnOnce(&mut Ui) -> bool) -> bool {
+fn quat_to_axis_angle(q: [f32; 4]) -> ([f32; 3], f32) {
+    let len = q.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if len <= f32::EPSILON {
+        return ([0.0, 1.0, 0.0], 0.0);
+    }
+    let [x, y, z, w] = q.map(|c| c / len);
+    let angle = 2.0 * w.clamp(-1.0, 1.0).acos();
+    let s = (1.0 - w * w).max(0.0).sqrt();
+    if s < 1.0e-5 {
+        return ([0.0, 1.0, 0.0], 0.0);
+    }
+    ([x / s, y / s, z / s], angle.to_degrees())
+}
+
+fn quat_from_axis_angle(axis: [f32; 3], angle_deg: f32) -> [f32; 4] {
+    let len = axis.iter().map(|c| c * c).sum::<f32>().sqrt();
+    if len <= f32::EPSILON {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    let half = angle_deg.to_radians() * 0.5;
+    let s = half.sin() / len;
+    [axis[0] * s, axis[1] * s, axis[2] * s, half.cos()]
+}
+
+pub(crate) fn edit_expression(
+    ui: &mut Ui,
+    label: &str,
+    expression: &mut String,
+    link: Option<&str>,
+) -> (bool, Response) {
+    param_row(ui, label, |ui| {
+        let height = ui.spacing().interact_size.y;
+        match link {
+            Some(target) => {
+                ui.label("🔗")
+                    .on_hover_text(format!("Linked to {}", target));
+            }
+            None => {
+                ui.label("=");
+            }
+        }
+        ui.add_sized(
+            [ui.available_width().max(160.0), height],
+            egui::TextEdit::singleline(expression).code_editor(),
+        )
+        .changed()
+    })
+}
+
+// Seed row with a dice button; the extra flag reports a roll so the caller can cook a
+// quick preview instead of waiting out the edit debounce.
+pub(super) fn edit_seed(ui: &mut Ui, label: &str, mut seed: i32) -> (i32, bool, bool, Response) {
+    let mut rolled = false;
+    let (changed, label_response) = param_row(ui, label, |ui| {
+        let height = ui.spacing().interact_size.y;
+        let mut changed = ui
+            .add_sized([64.0, height], egui::DragValue::new(&mut seed).speed(1.0))
+            .changed();
+        if ui
+            .add_sized([height, height], egui::Button::new("🎲"))
+            .on_hover_text("Roll a new seed and preview it at reduced density")
+            .clicked()
+        {
+            seed = ((seed as u32)
+                .wrapping_mul(1_664_525)
+                .wrapping_add(1_013_904_223)
+                >> 8) as i32
+                % 100_000;
+            rolled = true;
+            changed = true;
+        }
+        changed
+    });
+    (seed, changed, rolled, label_response)
+}
+
+// Typed text may be an expression with a unit suffix ("90deg", "2*pi", "10cm").
+fn float_drag<'a>(value: &'a mut f32, label: &str, unit: LengthUnit) -> egui::DragValue<'a> {
+    let label = label.to_string();
+    egui::DragValue::new(value)
+        .custom_parser(move |text| parse_numeric_entry(text, &label, unit).ok().map(f64::from))
+}
+
+fn param_row(
+    ui: &mut Ui,
+    label: &str,
+    add_controls: impl FnOnce(&mut Ui) -> bool,
+) -> (bool, Response) {
     param_row_with_height(ui, label, 36.0, add_controls)
 }
 
@@ -201,53 +374,73 @@ fn param_row_with_height(
     label: &str,
     row_height: f32,
     add_controls: impl FnOnce(&mut Ui) -> bool,
-) -> bool {
+) -> (bool, Response) {
     let total_width = ui.available_width();
     let label_width = (total_width * 0.2).clamp(80.0, 160.0);
     let controls_width = (total_width - label_width).max(120.0);
     let mut changed = false;
-    ui.allocate_ui_with_layout(
-        egui::vec2(total_width, row_height),
-        egui::Layout::left_to_right(egui::Align::Min),
-        |ui| {
-            ui.allocate_ui_with_layout(
-                egui::vec2(label_width, row_height),
-                egui::Layout::right_to_left(egui::Align::Center),
-                |ui| {
-                    ui.set_min_height(row_height);
-                    ui.label(label);
-                },
-            );
-            ui.allocate_ui_with_layout(
-                egui::vec2(controls_width, row_height),
-                egui::Layout::left_to_right(egui::Align::Center),
-                |ui| {
-                    ui.set_min_height(row_height);
-                    if add_controls(ui) {
-                        changed = true;
-                    }
-                },
-            );
-        },
-    );
-    changed
+    let label_response = ui
+        .allocate_ui_with_layout(
+            egui::vec2(total_width, row_height),
+            egui::Layout::left_to_right(egui::Align::Min),
+            |ui| {
+                let label_response = ui
+                    .allocate_ui_with_layout(
+                        egui::vec2(label_width, row_height),
+                        egui::Layout::right_to_left(egui::Align::Center),
+                        |ui| {
+                            ui.set_min_height(row_height);
+                            ui.add(egui::Label::new(label).sense(egui::Sense::click()))
+                        },
+                    )
+                    .inner;
+                ui.allocate_ui_with_layout(
+                    egui::vec2(controls_width, row_height),
+                    egui::Layout::left_to_right(egui::Align::Center),
+                    |ui| {
+                        ui.set_min_height(row_height);
+                        if add_controls(ui) {
+                            changed = true;
+                        }
+                    },
+                );
+                label_response
+            },
+        )
+        .inner;
+    (changed, label_response)
 }
 
-fn float_slider_range(
-    _node_name: &str,
-    label: &str,
-    _value: f32,
-) -> std::ops::RangeInclusive<f32> {
+fn float_slider_range(_node_name: &str, label: &str, _value: f32) -> std::ops::RangeInclusive<f32> {
     match label {
-        "threshold_deg" => 0.0..=180.0,
+        "threshold_deg" | "quad_angle_deg" => 0.0..=180.0,
         "amplitude" => -10.0..=10.0,
         "frequency" => 0.0..=10.0,
         "value_f" => -10.0..=10.0,
+        "distance" => -10.0..=10.0,
+        "inset" => 0.0..=1.0,
+        "width" => 0.0..=1.0,
+        "angle_deg" => 0.0..=180.0,
         _ => -1000.0..=1000.0,
     }
 }
 
-fn int_slider_range(
+// Int params edited as a list of named choices; empty for plain numbers.
+fn int_options(node_name: &str, label: &str) -> &'static [(i32, &'static str)] {
+    const DOMAINS: &[(i32, &str)] = &[(1, "Vertex"), (0, "Point"), (2, "Primitive"), (3, "Detail")];
+    match (node_name, label) {
+        ("Boolean", "mode") => &[(0, "Union"), (1, "Difference"), (2, "Intersect")],
+        ("Curvature", "mode") => &[(0, "Signed"), (1, "Convex"), (2, "Concave")],
+        ("Wrangle", "mode") => DOMAINS,
+        ("Sort", "domain") => &[(0, "Point"), (1, "Primitive")],
+        ("Sort", "key") => &[(0, "Axis"), (1, "Distance"), (2, "Attribute")],
+        (_, "domain") => DOMAINS,
+        (_, "op") => &[(0, "Add"), (1, "Subtract"), (2, "Multiply"), (3, "Divide")],
+        _ => &[],
+    }
+}
+
+pub(super) fn int_slider_range(
     node_name: &str,
     label: &str,
     _value: i32,
@@ -257,6 +450,7 @@ fn int_slider_range(
         "op" => 0..=3,
         "rows" | "cols" => 2..=64,
         "seed" => 0..=100,
+        "segments" => 1..=16,
         "count" if node_name == "Scatter" => 0..=1000,
         "count" if node_name == "Copy/Transform" => 1..=100,
         _ => -1000..=1000,
@@ -6,12 +6,20 @@ use egui::{vec2, Color32, Frame, Pos2, Rect, Stroke, Ui};
 use egui_snarl::ui::{BackgroundPattern, SnarlStyle};
 use egui_snarl::{InPinId, OutPinId, Snarl};
 
-use grapho_core::{BuiltinNodeKind, Graph, NodeId, PinId, PinKind};
+use grapho_core::{
+    builtin_kind_from_name, node_reference_name, param_group, param_reference_expression,
+    param_reference_target, param_value_expression, BuiltinNodeKind, ErrorPolicy, EvalReport,
+    Graph, GraphBackdrop, GraphCommand, KeyframeTrack, NodeId, ParamValue, PinId, PinKind,
+};
+
+use super::backdrops::{interact_backdrops, new_backdrop, paint_backdrops, BackdropEdit};
 
+use super::inspector::{NodeInspector, SeedInspector};
 use super::menu::builtin_menu_items;
-use super::params::edit_param;
+use super::params::{edit_expression, edit_param, edit_seed};
 use super::utils::{
-    add_builtin_node, find_input_of_type, find_output_of_type, point_snarl_wire_distance,
+    add_builtin_node, connect_pins, disconnect_pin, find_input_of_type, find_output_of_type,
+    node_summary, point_snarl_wire_distance,
 };
 use super::viewer::NodeGraphViewer;
 
@@ -27,6 +35,12 @@ pub struct NodeGraphState {
     next_pos: Pos2,
     needs_wire_sync: bool,
     selected_node: Option<NodeId>,
+    selected_nodes: HashSet<NodeId>,
+    pinned_nodes: Vec<NodeId>,
+    inspector_tab: Option<NodeId>,
+    param_filter: String,
+    seed_rolled: bool,
+    snap_to_grid: bool,
     node_ui_rects: HashMap<egui_snarl::NodeId, Rect>,
     prev_node_ui_rects: HashMap<egui_snarl::NodeId, Rect>,
     header_button_rects: HashMap<egui_snarl::NodeId, HeaderButtonRects>,
@@ -38,24 +52,92 @@ pub struct NodeGraphState {
     add_menu_focus: bool,
     pending_wire: Option<PendingWire>,
     info_request: Option<NodeInfoRequest>,
+    cook_request: Option<NodeId>,
     wrangle_help_request: Option<Pos2>,
+    node_help_request: Option<NodeInfoRequest>,
     graph_transform: GraphTransformState,
+    transform_animation: Option<TransformAnimation>,
+    frame_request: Option<bool>,
     input_pin_positions: Rc<RefCell<HashMap<InPinId, Pos2>>>,
     output_pin_positions: Rc<RefCell<HashMap<OutPinId, Pos2>>>,
     error_nodes: HashSet<NodeId>,
     error_messages: HashMap<NodeId, String>,
+    wire_states: Option<HashMap<NodeId, WireState>>,
+    color_wires_by_state: bool,
     node_menu_request: Option<NodeMenuRequest>,
     node_menu_open: bool,
     node_menu_screen_pos: Pos2,
     node_menu_node: Option<NodeId>,
+    backdrop_request: bool,
     last_changed: bool,
     layout_changed: bool,
+    param_clipboard: Option<(NodeId, String)>,
+    custom_inspectors: HashMap<String, Box<dyn NodeInspector>>,
+    keyboard_focus: bool,
+    high_contrast: bool,
+    // Subnet nodes dived into, outermost first; the editor shows the innermost one's graph.
+    subnet_path: Vec<NodeId>,
+    // Views of the levels above the current one, innermost last.
+    parent_views: Vec<GraphView>,
+    // Views of subnets left earlier, so diving back in keeps their layout.
+    subnet_views: HashMap<Vec<NodeId>, GraphView>,
+    subnet_nav: Option<SubnetNav>,
+    frame_new_level: bool,
+}
+
+// Everything about the editor that belongs to one level of the subnet hierarchy.
+struct GraphView {
+    snarl: Snarl<SnarlNode>,
+    core_to_snarl: HashMap<NodeId, egui_snarl::NodeId>,
+    snarl_to_core: HashMap<egui_snarl::NodeId, NodeId>,
+    next_pos: Pos2,
+    selected_node: Option<NodeId>,
+    selected_nodes: HashSet<NodeId>,
+    transform: Option<egui::emath::TSTransform>,
+}
+
+impl Default for GraphView {
+    fn default() -> Self {
+        Self {
+            snarl: Snarl::new(),
+            core_to_snarl: HashMap::new(),
+            snarl_to_core: HashMap::new(),
+            next_pos: Pos2::new(0.0, 0.0),
+            selected_node: None,
+            selected_nodes: HashSet::new(),
+            transform: None,
+        }
+    }
+}
+
+// Applied at the start of the next `show`, never halfway through drawing a level.
+#[derive(Clone, Copy)]
+enum SubnetNav {
+    Enter(NodeId),
+    // Leave subnets until this many remain on the path.
+    Exit(usize),
+}
+
+// Cook outcome of a node in the last eval; wires take the state of their upstream node.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(super) enum WireState {
+    Cached,
+    Recooked,
+    Failed,
 }
 
 #[derive(Clone, Copy)]
 pub(super) struct GraphTransformState {
     pub(super) to_global: egui::emath::TSTransform,
     pub(super) valid: bool,
+    pub(super) requested: Option<egui::emath::TSTransform>,
+}
+
+#[derive(Clone, Copy)]
+struct TransformAnimation {
+    from: egui::emath::TSTransform,
+    to: egui::emath::TSTransform,
+    start: f64,
 }
 
 #[derive(Clone, Copy)]
@@ -66,13 +148,19 @@ pub struct NodeInfoRequest {
 
 impl Default for NodeGraphState {
     fn default() -> Self {
-        Self {
+        let mut state = Self {
             snarl: Snarl::new(),
             core_to_snarl: HashMap::new(),
             snarl_to_core: HashMap::new(),
             next_pos: Pos2::new(0.0, 0.0),
             needs_wire_sync: true,
             selected_node: None,
+            selected_nodes: HashSet::new(),
+            pinned_nodes: Vec::new(),
+            inspector_tab: None,
+            param_filter: String::new(),
+            seed_rolled: false,
+            snap_to_grid: false,
             node_ui_rects: HashMap::new(),
             prev_node_ui_rects: HashMap::new(),
             header_button_rects: HashMap::new(),
@@ -84,25 +172,53 @@ impl Default for NodeGraphState {
             add_menu_focus: false,
             pending_wire: None,
             info_request: None,
+            cook_request: None,
             wrangle_help_request: None,
+            node_help_request: None,
             graph_transform: GraphTransformState {
                 to_global: egui::emath::TSTransform::IDENTITY,
                 valid: false,
+                requested: None,
             },
+            transform_animation: None,
+            frame_request: None,
             input_pin_positions: Rc::new(RefCell::new(HashMap::new())),
             output_pin_positions: Rc::new(RefCell::new(HashMap::new())),
             error_nodes: HashSet::new(),
             error_messages: HashMap::new(),
+            wire_states: None,
+            color_wires_by_state: true,
             node_menu_request: None,
             node_menu_open: false,
             node_menu_screen_pos: Pos2::new(0.0, 0.0),
             node_menu_node: None,
+            backdrop_request: false,
             last_changed: false,
             layout_changed: false,
+            param_clipboard: None,
+            custom_inspectors: HashMap::new(),
+            keyboard_focus: false,
+            high_contrast: false,
+            subnet_path: Vec::new(),
+            parent_views: Vec::new(),
+            subnet_views: HashMap::new(),
+            subnet_nav: None,
+            frame_new_level: false,
+        };
+        for kind in [BuiltinNodeKind::Scatter, BuiltinNodeKind::Noise] {
+            state.register_inspector(kind.name(), Box::new(SeedInspector));
         }
+        state
     }
 }
 
+struct ParamRow {
+    key: String,
+    value: ParamValue,
+    expression: Option<String>,
+    track: Option<KeyframeTrack>,
+}
+
 pub(super) struct NodeMenuRequest {
     pub(super) node_id: NodeId,
     pub(super) screen_pos: Pos2,
@@ -115,6 +231,21 @@ pub(super) struct HeaderButtonRects {
     pub(super) help: Option<Rect>,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum NodeAlign {
+    Left,
+    Top,
+    DistributeHorizontal,
+    DistributeVertical,
+}
+
+const SNAP_GRID: f32 = 32.0;
+const FRAME_MARGIN: f32 = 48.0;
+const MIN_ZOOM: f32 = 0.2;
+const MAX_ZOOM: f32 = 2.0;
+const KEY_ZOOM_STEP: f32 = 1.25;
+const NAV_ANIMATION_SECS: f64 = 0.2;
+
 #[derive(Clone, Default, PartialEq)]
 pub struct NodeGraphLayout {
     pub positions: HashMap<NodeId, Pos2>,
@@ -126,12 +257,54 @@ impl NodeGraphState {
         *self = Self::default();
     }
 
-    pub fn show(&mut self, ui: &mut Ui, graph: &mut Graph, eval_dirty: &mut bool) {
+    pub fn show(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        backdrops: &mut Vec<GraphBackdrop>,
+        eval_dirty: &mut bool,
+    ) {
+        match self.subnet_nav.take() {
+            Some(SubnetNav::Enter(node_id)) => self.enter_subnet(graph, node_id),
+            Some(SubnetNav::Exit(depth)) => {
+                while self.subnet_path.len() > depth && self.exit_subnet() {}
+            }
+            None => {}
+        }
+        // Undo or an edit elsewhere can remove a subnet while it is open.
+        while graph.subnet_at(&self.subnet_path).is_none() && self.exit_subnet() {}
+        if self.subnet_path.is_empty() {
+            self.show_level(ui, graph, backdrops, eval_dirty);
+            return;
+        }
+
+        let path = self.subnet_path.clone();
+        if let Some(subnet) = graph.subnet_at_mut(&path) {
+            // Backdrops are laid out around the top-level graph.
+            self.show_level(ui, subnet, &mut Vec::new(), eval_dirty);
+        }
+        if self.last_changed {
+            self.sync_subnet_pins(graph);
+        }
+        if std::mem::take(&mut self.frame_new_level) {
+            self.frame_request = Some(false);
+        }
+        self.show_subnet_bar(ui, graph);
+    }
+
+    fn show_level(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        backdrops: &mut Vec<GraphBackdrop>,
+        eval_dirty: &mut bool,
+    ) {
         self.ensure_nodes(graph);
         if self.needs_wire_sync {
             self.sync_wires(graph);
             self.needs_wire_sync = false;
         }
+        self.handle_navigation(ui);
 
         self.prev_node_ui_rects = std::mem::take(&mut self.node_ui_rects);
         self.node_ui_rects.clear();
@@ -141,12 +314,20 @@ impl NodeGraphState {
         self.last_changed = false;
         self.layout_changed = false;
 
+        // Eval results are keyed by top-level node ids, which mean nothing inside a subnet.
+        let no_errors = (HashSet::new(), HashMap::new());
+        let (error_nodes, error_messages) = if self.subnet_path.is_empty() {
+            (&self.error_nodes, &self.error_messages)
+        } else {
+            (&no_errors.0, &no_errors.1)
+        };
         let mut viewer = NodeGraphViewer {
             graph,
             core_to_snarl: &mut self.core_to_snarl,
             snarl_to_core: &mut self.snarl_to_core,
             next_pos: &mut self.next_pos,
             selected_node: &mut self.selected_node,
+            selected_nodes: &mut self.selected_nodes,
             node_rects: &mut self.node_ui_rects,
             header_button_rects: &mut self.header_button_rects,
             graph_transform: &mut self.graph_transform,
@@ -160,15 +341,32 @@ impl NodeGraphState {
             pending_wire: &mut self.pending_wire,
             node_menu_request: &mut self.node_menu_request,
             wrangle_help_request: &mut self.wrangle_help_request,
-            error_nodes: &self.error_nodes,
-            error_messages: &self.error_messages,
+            node_help_request: &mut self.node_help_request,
+            error_nodes,
+            error_messages,
+            wire_states: self
+                .wire_states
+                .as_ref()
+                .filter(|_| self.color_wires_by_state && self.subnet_path.is_empty()),
+            high_contrast: self.high_contrast,
             changed: false,
         };
+        let (bg_fill, grid_color) = if self.high_contrast {
+            (Color32::BLACK, Color32::from_gray(70))
+        } else {
+            (Color32::from_rgb(18, 18, 18), Color32::from_rgb(26, 26, 26))
+        };
+        // The canvas fill is painted here rather than by snarl so backdrops sit between it
+        // and the nodes.
+        ui.painter().rect_filled(ui.max_rect(), 0.0, bg_fill);
+        if self.graph_transform.valid {
+            paint_backdrops(ui, self.graph_transform.to_global, backdrops);
+        }
         let style = SnarlStyle {
             pin_size: Some(10.0),
-            bg_frame: Some(Frame::NONE.fill(Color32::from_rgb(18, 18, 18))),
+            bg_frame: Some(Frame::NONE),
             bg_pattern: Some(BackgroundPattern::grid(vec2(64.0, 64.0), 0.0)),
-            bg_pattern_stroke: Some(Stroke::new(1.0, Color32::from_rgb(26, 26, 26))),
+            bg_pattern_stroke: Some(Stroke::new(1.0, grid_color)),
             collapsible: Some(false),
             ..SnarlStyle::default()
         };
@@ -176,6 +374,15 @@ impl NodeGraphState {
         let viewer_changed = viewer.changed;
         self.last_changed |= viewer_changed;
         drop(viewer);
+        if self.graph_transform.valid {
+            match interact_backdrops(ui, self.graph_transform.to_global, backdrops) {
+                Some(BackdropEdit::Moved { rect, delta }) => {
+                    self.layout_changed |= self.move_nodes_inside(rect, delta);
+                }
+                Some(BackdropEdit::Changed) => self.layout_changed = true,
+                None => {}
+            }
+        }
         for (node, rect) in &self.node_ui_rects {
             let Some(prev) = self.prev_node_ui_rects.get(node) else {
                 continue;
@@ -191,6 +398,12 @@ impl NodeGraphState {
             self.needs_wire_sync = true;
         }
 
+        if self.handle_keyboard(ui, graph) {
+            self.last_changed = true;
+            *eval_dirty = true;
+            self.needs_wire_sync = true;
+        }
+
         if self.add_menu_open && self.show_add_menu(ui, graph) {
             self.last_changed = true;
             *eval_dirty = true;
@@ -198,6 +411,7 @@ impl NodeGraphState {
         }
 
         self.update_drag_state(ui);
+        self.snap_dropped_node(ui);
 
         if self.handle_drop_on_wire(ui, graph) {
             self.last_changed = true;
@@ -215,6 +429,16 @@ impl NodeGraphState {
             *eval_dirty = true;
             self.needs_wire_sync = true;
         }
+        if std::mem::take(&mut self.backdrop_request) {
+            if let Some(bounds) = self.selection_graph_rect() {
+                backdrops.push(new_backdrop(bounds, backdrops.len()));
+                self.layout_changed = true;
+            }
+        }
+    }
+
+    pub fn mark_wires_dirty(&mut self) {
+        self.needs_wire_sync = true;
     }
 
     pub fn take_changed(&mut self) -> bool {
@@ -229,14 +453,126 @@ impl NodeGraphState {
         changed
     }
 
+    // Subnet nodes the editor has dived into, outermost first.
+    pub fn subnet_path(&self) -> &[NodeId] {
+        &self.subnet_path
+    }
+
+    fn enter_subnet(&mut self, graph: &Graph, node_id: NodeId) {
+        let mut path = self.subnet_path.clone();
+        path.push(node_id);
+        if graph.subnet_at(&path).is_none() {
+            return;
+        }
+        let view = self.subnet_views.remove(&path);
+        self.frame_new_level = view.is_none();
+        let outer = self.swap_view(view.unwrap_or_default());
+        self.parent_views.push(outer);
+        self.subnet_path = path;
+    }
+
+    fn exit_subnet(&mut self) -> bool {
+        let Some(outer) = self.parent_views.pop() else {
+            return false;
+        };
+        let inner = self.swap_view(outer);
+        self.subnet_views.insert(self.subnet_path.clone(), inner);
+        let subnet = self.subnet_path.pop();
+        self.selected_node = subnet;
+        self.selected_nodes = subnet.into_iter().collect();
+        true
+    }
+
+    fn swap_view(&mut self, view: GraphView) -> GraphView {
+        let transform = self.view_transform();
+        if let Some(transform) = view.transform {
+            self.graph_transform.requested = Some(transform);
+        }
+        self.transform_animation = None;
+        self.node_ui_rects.clear();
+        self.prev_node_ui_rects.clear();
+        self.pinned_nodes.clear();
+        self.inspector_tab = None;
+        self.pending_wire = None;
+        self.dragging_node = None;
+        self.node_menu_open = false;
+        self.node_menu_node = None;
+        self.needs_wire_sync = true;
+        GraphView {
+            snarl: std::mem::replace(&mut self.snarl, view.snarl),
+            core_to_snarl: std::mem::replace(&mut self.core_to_snarl, view.core_to_snarl),
+            snarl_to_core: std::mem::replace(&mut self.snarl_to_core, view.snarl_to_core),
+            next_pos: std::mem::replace(&mut self.next_pos, view.next_pos),
+            selected_node: std::mem::replace(&mut self.selected_node, view.selected_node),
+            selected_nodes: std::mem::replace(&mut self.selected_nodes, view.selected_nodes),
+            transform,
+        }
+    }
+
+    // Refreshes the pins of every subnet on the path, innermost first.
+    fn sync_subnet_pins(&self, graph: &mut Graph) {
+        for depth in (0..self.subnet_path.len()).rev() {
+            if let Some(outer) = graph.subnet_at_mut(&self.subnet_path[..depth]) {
+                if let Err(err) = outer.sync_subnet_pins(self.subnet_path[depth]) {
+                    tracing::error!("failed to update subnet pins: {:?}", err);
+                }
+            }
+        }
+    }
+
+    fn show_subnet_bar(&mut self, ui: &Ui, graph: &Graph) {
+        let mut target = None;
+        egui::Area::new(ui.id().with("subnet_bar"))
+            .order(egui::Order::Foreground)
+            .fixed_pos(ui.max_rect().left_top() + vec2(8.0, 8.0))
+            .show(ui.ctx(), |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.horizontal(|ui| {
+                        if ui
+                            .button("Up")
+                            .on_hover_text("Leave this subnet (U)")
+                            .clicked()
+                        {
+                            target = Some(self.subnet_path.len() - 1);
+                        }
+                        if ui.link("Top").clicked() {
+                            target = Some(0);
+                        }
+                        for (depth, node_id) in self.subnet_path.iter().enumerate() {
+                            ui.label("/");
+                            let nodes = graph
+                                .subnet_at(&self.subnet_path[..=depth])
+                                .map_or(0, |subnet| subnet.nodes().count());
+                            let label = format!("Subnet #{}", node_id.as_u64());
+                            if ui
+                                .link(label)
+                                .on_hover_text(format!("{} nodes", nodes))
+                                .clicked()
+                            {
+                                target = Some(depth + 1);
+                            }
+                        }
+                    });
+                });
+            });
+        if let Some(depth) = target {
+            self.subnet_nav = Some(SubnetNav::Exit(depth));
+        }
+    }
+
+    // Always the top-level graph's layout, even while a subnet is open.
     pub fn layout_snapshot(&self) -> NodeGraphLayout {
+        let (snarl, selected) = match self.parent_views.first() {
+            Some(top) => (&top.snarl, top.selected_node),
+            None => (&self.snarl, self.selected_node),
+        };
         let mut positions = HashMap::new();
-        for (_, pos, node) in self.snarl.nodes_pos_ids() {
+        for (_, pos, node) in snarl.nodes_pos_ids() {
             positions.insert(node.core_id, pos);
         }
         NodeGraphLayout {
             positions,
-            selected: self.selected_node,
+            selected,
         }
     }
 
@@ -259,6 +595,37 @@ impl NodeGraphState {
         self.needs_wire_sync = true;
     }
 
+    // Moves top-level nodes to where a sync peer put them, including nodes it just added.
+    pub fn place_nodes(&mut self, placed: &[(NodeId, Pos2)]) {
+        let (snarl, core_to_snarl, snarl_to_core) = match self.parent_views.first_mut() {
+            Some(top) => (
+                &mut top.snarl,
+                &mut top.core_to_snarl,
+                &mut top.snarl_to_core,
+            ),
+            None => (
+                &mut self.snarl,
+                &mut self.core_to_snarl,
+                &mut self.snarl_to_core,
+            ),
+        };
+        for &(core_id, pos) in placed {
+            match core_to_snarl.get(&core_id) {
+                Some(snarl_id) => {
+                    if let Some(node) = snarl.get_node_info_mut(*snarl_id) {
+                        node.pos = pos;
+                    }
+                }
+                None => {
+                    let snarl_id = snarl.insert_node(pos, SnarlNode { core_id });
+                    core_to_snarl.insert(core_id, snarl_id);
+                    snarl_to_core.insert(snarl_id, core_id);
+                }
+            }
+        }
+        self.needs_wire_sync = true;
+    }
+
     fn show_node_menu(&mut self, ui: &mut Ui, graph: &mut Graph) -> bool {
         let mut close_menu = ui.input(|i| i.key_pressed(egui::Key::Escape));
         let mut menu_rect = None;
@@ -272,7 +639,15 @@ impl NodeGraphState {
             .anchor(egui::Align2::LEFT_TOP, self.node_menu_screen_pos.to_vec2())
             .frame(Frame::popup(ui.style()))
             .show(ui.ctx(), |ui| {
-                if ui.button("Node info").clicked() {
+                if let Some(node_id) = node_id.filter(|node_id| graph.subnet(*node_id).is_some()) {
+                    if ui.button("Dive into subnet").on_hover_text("I").clicked() {
+                        self.subnet_nav = Some(SubnetNav::Enter(node_id));
+                        close_menu = true;
+                    }
+                }
+                // Info, cook and help look nodes up in the top-level graph.
+                let top_level = self.subnet_path.is_empty();
+                if top_level && ui.button("Node info").clicked() {
                     if let Some(node_id) = node_id {
                         self.info_request = Some(NodeInfoRequest {
                             node_id,
@@ -281,20 +656,71 @@ impl NodeGraphState {
                     }
                     close_menu = true;
                 }
+                if top_level
+                    && ui
+                        .button("Cook this node")
+                        .on_hover_text(
+                            "Inspect this node's output without changing the display flag",
+                        )
+                        .clicked()
+                {
+                    if let Some(node_id) = node_id {
+                        self.cook_request = Some(node_id);
+                        self.selected_node = Some(node_id);
+                        self.selected_nodes = HashSet::from([node_id]);
+                    }
+                    close_menu = true;
+                }
+                if top_level && ui.button("Help").clicked() {
+                    if let Some(node_id) = node_id {
+                        self.node_help_request = Some(NodeInfoRequest {
+                            node_id,
+                            screen_pos: self.node_menu_screen_pos,
+                        });
+                    }
+                    close_menu = true;
+                }
                 if ui.button("Delete node").clicked() {
                     if let Some(node_id) = node_id {
-                        graph.remove_node(node_id);
-                        if let Some(snarl_id) = self.core_to_snarl.remove(&node_id) {
-                            self.snarl_to_core.remove(&snarl_id);
-                            let _ = self.snarl.remove_node(snarl_id);
-                        }
-                        if self.selected_node.as_ref() == Some(&node_id) {
-                            self.selected_node = None;
-                        }
+                        self.delete_node(graph, node_id);
                         changed = true;
                     }
                     close_menu = true;
                 }
+                ui.separator();
+                if ui.button("Straighten input wires").clicked() {
+                    self.layout_changed |= self.straighten_nodes(graph);
+                    close_menu = true;
+                }
+                if self.selection().len() > 1 {
+                    for (label, align) in [
+                        ("Align left", NodeAlign::Left),
+                        ("Align top", NodeAlign::Top),
+                        ("Distribute horizontally", NodeAlign::DistributeHorizontal),
+                        ("Distribute vertically", NodeAlign::DistributeVertical),
+                    ] {
+                        if ui.button(label).clicked() {
+                            self.layout_changed |= self.align_selection(align);
+                            close_menu = true;
+                        }
+                    }
+                }
+                if ui.button("Add backdrop around selection").clicked() {
+                    self.backdrop_request = true;
+                    close_menu = true;
+                }
+                ui.checkbox(&mut self.snap_to_grid, "Snap to grid");
+                ui.checkbox(&mut self.color_wires_by_state, "Color wires by cook state")
+                    .on_hover_text(
+                        "Grey: cached, green: recooked, red: failed, faint: not evaluated",
+                    );
+                if let Some(node_id) = node_id {
+                    if graph.variadic_inputs(node_id).is_some()
+                        && show_input_order(ui, graph, node_id)
+                    {
+                        changed = true;
+                    }
+                }
             });
 
         if let Some(inner) = response {
@@ -320,6 +746,18 @@ impl NodeGraphState {
         changed
     }
 
+    fn delete_node(&mut self, graph: &mut Graph, node_id: NodeId) {
+        let _ = graph.apply(&GraphCommand::RemoveNode { node: node_id });
+        if let Some(snarl_id) = self.core_to_snarl.remove(&node_id) {
+            self.snarl_to_core.remove(&snarl_id);
+            let _ = self.snarl.remove_node(snarl_id);
+        }
+        if self.selected_node.as_ref() == Some(&node_id) {
+            self.selected_node = None;
+        }
+        self.selected_nodes.remove(&node_id);
+    }
+
     pub fn open_add_menu(&mut self, pos: Pos2) {
         self.add_menu_open = true;
         self.add_menu_screen_pos = pos;
@@ -375,8 +813,8 @@ impl NodeGraphState {
         if let (Some(box_out), Some(transform_in), Some(transform_out), Some(output_in)) =
             (box_out, transform_in, transform_out, output_in)
         {
-            let _ = graph.add_link(box_out, transform_in);
-            let _ = graph.add_link(transform_out, output_in);
+            let _ = connect_pins(graph, box_out, transform_in);
+            let _ = connect_pins(graph, transform_out, output_in);
         }
 
         self.needs_wire_sync = true;
@@ -416,6 +854,8 @@ impl NodeGraphState {
                 self.selected_node = None;
             }
         }
+        self.selected_nodes
+            .retain(|node_id| graph.node(*node_id).is_some());
     }
 
     fn sync_wires(&mut self, graph: &Graph) {
@@ -478,8 +918,34 @@ impl NodeGraphState {
         }
     }
 
-    pub fn show_inspector(&mut self, ui: &mut Ui, graph: &mut Graph) -> bool {
-        let Some(node_id) = self.selected_node else {
+    pub fn show_inspector(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        output: Option<&Mesh>,
+        frame: f32,
+    ) -> bool {
+        let path = self.subnet_path.clone();
+        let Some(level) = graph.subnet_at_mut(&path) else {
+            ui.label("No selection.");
+            return false;
+        };
+        let changed = self.show_node_inspector(ui, level, output, frame);
+        if changed && !path.is_empty() {
+            self.sync_subnet_pins(graph);
+        }
+        changed
+    }
+
+    fn show_node_inspector(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        output: Option<&Mesh>,
+        frame: f32,
+    ) -> bool {
+        self.show_inspector_tabs(ui, graph);
+        let Some(node_id) = self.inspected_node() else {
             ui.label("No selection.");
             return false;
         };
@@ -491,8 +957,50 @@ impl NodeGraphState {
         };
 
         ui.label(format!("{} ({})", node.name, node.category));
+        show_output_stats(ui, output);
+        let mut changed = false;
+        if node.outputs.len() > 1 {
+            let names: Vec<String> = node
+                .outputs
+                .iter()
+                .filter_map(|pin| graph.pin(*pin).map(|pin| pin.name.clone()))
+                .collect();
+            let mut selected = node.display_output.min(names.len().saturating_sub(1));
+            egui::ComboBox::from_label("Output")
+                .selected_text(names.get(selected).cloned().unwrap_or_default())
+                .show_ui(ui, |ui| {
+                    for (index, name) in names.iter().enumerate() {
+                        ui.selectable_value(&mut selected, index, name);
+                    }
+                });
+            if selected != node.display_output
+                && graph.set_display_output(node_id, selected).is_ok()
+            {
+                changed = true;
+            }
+        }
+        let Some(node) = graph.node(node_id) else {
+            return changed;
+        };
+        let mut policy = node.error_policy;
+        let project_label = format!("Project ({})", graph.error_policy().label());
+        egui::ComboBox::from_label("On error")
+            .selected_text(policy.map_or(project_label.as_str(), ErrorPolicy::label))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(&mut policy, None, project_label.as_str());
+                for option in [ErrorPolicy::Fail, ErrorPolicy::PassThrough] {
+                    ui.selectable_value(&mut policy, Some(option), option.label());
+                }
+            });
+        if policy != node.error_policy && graph.set_node_error_policy(node_id, policy).is_ok() {
+            changed = true;
+        }
         ui.separator();
 
+        let Some(node) = graph.node(node_id) else {
+            return changed;
+        };
+
         let params: Vec<(String, grapho_core::ParamValue)> = node
             .params
             .values
@@ -500,16 +1008,67 @@ impl NodeGraphState {
             .map(|(key, value)| (key.clone(), value.clone()))
             .collect();
         let node_name = node.name.clone();
+        let expressions = node.param_expressions.clone();
+        let keyframes = node.param_keyframes.clone();
 
         if params.is_empty() {
             ui.label("No parameters.");
-            return false;
+        } else {
+            ui.add(
+                egui::TextEdit::singleline(&mut self.param_filter)
+                    .hint_text("Filter parameters")
+                    .desired_width(f32::INFINITY),
+            );
         }
 
-        let mut changed = false;
+        let filter = self.param_filter.trim().to_lowercase();
+        let kind = builtin_kind_from_name(&node_name);
+        let mut groups: Vec<(Option<&'static str>, Vec<ParamRow>)> = Vec::new();
         for (key, value) in params {
-            let (next_value, did_change) = edit_param(ui, &node_name, &key, value);
-            if did_change && graph.set_param(node_id, key, next_value).is_ok() {
+            let group = kind.and_then(|kind| param_group(kind, &key));
+            let matches = filter.is_empty()
+                || key.to_lowercase().contains(&filter)
+                || group.is_some_and(|group| group.to_lowercase().contains(&filter));
+            if !matches {
+                continue;
+            }
+            let row = ParamRow {
+                expression: expressions.get(&key).cloned(),
+                track: keyframes.get(&key).cloned(),
+                key,
+                value,
+            };
+            match groups.iter_mut().find(|(name, _)| *name == group) {
+                Some((_, rows)) => rows.push(row),
+                None => groups.push((group, vec![row])),
+            }
+        }
+        // Ungrouped parameters first, then the sections by name.
+        groups.sort_by_key(|(group, _)| *group);
+        if groups.is_empty() && !filter.is_empty() {
+            ui.label("No parameters match the filter.");
+        }
+        for (group, rows) in groups {
+            let Some(group) = group else {
+                for row in rows {
+                    changed |= self.show_param_row(ui, graph, node_id, &node_name, row, frame);
+                }
+                continue;
+            };
+            egui::CollapsingHeader::new(group)
+                .id_salt(("param_group", node_id, group))
+                .default_open(true)
+                .open((!filter.is_empty()).then_some(true))
+                .show(ui, |ui| {
+                    for row in rows {
+                        changed |= self.show_param_row(ui, graph, node_id, &node_name, row, frame);
+                    }
+                });
+        }
+
+        if let Some(inspector) = self.custom_inspectors.get(&node_name) {
+            ui.separator();
+            if inspector.show(ui, graph, node_id) {
                 changed = true;
             }
         }
@@ -517,19 +1076,255 @@ impl NodeGraphState {
         changed
     }
 
+    fn show_param_row(
+        &mut self,
+        ui: &mut Ui,
+        graph: &mut Graph,
+        node_id: NodeId,
+        node_name: &str,
+        row: ParamRow,
+        frame: f32,
+    ) -> bool {
+        let ParamRow {
+            key,
+            value,
+            expression,
+            track,
+        } = row;
+        let mut changed = false;
+        let has_expression = expression.is_some();
+        let label_response = if let Some(mut expression) = expression {
+            let link = param_reference_target(graph, &expression).map(|(target, param)| {
+                let name = node_reference_name(graph, target).unwrap_or_default();
+                format!("{}.{}", name, param)
+            });
+            let (did_change, label_response) =
+                edit_expression(ui, &key, &mut expression, link.as_deref());
+            if did_change
+                && graph
+                    .apply(&GraphCommand::SetExpression {
+                        node: node_id,
+                        key: key.clone(),
+                        expression: Some(expression),
+                    })
+                    .is_ok()
+            {
+                changed = true;
+            }
+            label_response
+        } else {
+            let (next_value, did_change, label_response) = match value {
+                ParamValue::Int(seed) if key == "seed" => {
+                    let (seed, changed, rolled, response) = edit_seed(ui, &key, seed);
+                    self.seed_rolled |= rolled;
+                    (ParamValue::Int(seed), changed, response)
+                }
+                _ => edit_param(ui, node_name, &key, value.clone(), graph.units().unit),
+            };
+            if did_change {
+                let applied = if track.is_some() {
+                    graph.set_param_keyframe(node_id, key.clone(), frame, next_value)
+                } else {
+                    graph
+                        .apply(&GraphCommand::SetParam {
+                            node: node_id,
+                            key: key.clone(),
+                            value: next_value,
+                        })
+                        .map(|_| ())
+                };
+                changed |= applied.is_ok();
+            }
+            label_response
+        };
+        label_response.context_menu(|ui| {
+            if ui.button("Copy parameter").clicked() {
+                self.param_clipboard = Some((node_id, key.clone()));
+                ui.close();
+            }
+            let paste_target = self
+                .param_clipboard
+                .as_ref()
+                .filter(|(source, source_key)| {
+                    (*source != node_id || *source_key != key)
+                        && graph
+                            .node(*source)
+                            .is_some_and(|node| node.params.values.contains_key(source_key))
+                })
+                .cloned();
+            if ui
+                .add_enabled(
+                    paste_target.is_some() && param_value_expression(&value).is_some(),
+                    egui::Button::new("Paste relative reference"),
+                )
+                .clicked()
+            {
+                if let Some((source, source_key)) = paste_target {
+                    let reference = param_reference_expression(source, &source_key);
+                    if graph
+                        .apply(&GraphCommand::SetExpression {
+                            node: node_id,
+                            key: key.clone(),
+                            expression: Some(reference),
+                        })
+                        .is_ok()
+                    {
+                        changed = true;
+                    }
+                }
+                ui.close();
+            }
+            ui.separator();
+            if has_expression {
+                if ui.button("Remove expression").clicked() {
+                    if graph
+                        .apply(&GraphCommand::SetExpression {
+                            node: node_id,
+                            key: key.clone(),
+                            expression: None,
+                        })
+                        .is_ok()
+                    {
+                        changed = true;
+                    }
+                    ui.close();
+                }
+            } else if let Some(literal) = param_value_expression(&value) {
+                if ui.button("Add expression").clicked() {
+                    if graph
+                        .apply(&GraphCommand::SetExpression {
+                            node: node_id,
+                            key: key.clone(),
+                            expression: Some(literal),
+                        })
+                        .is_ok()
+                    {
+                        changed = true;
+                    }
+                    ui.close();
+                }
+            } else {
+                ui.label("No expression support");
+            }
+            if !has_expression {
+                ui.separator();
+                if ui
+                    .button(format!("Set key at frame {:.0}", frame))
+                    .clicked()
+                {
+                    if graph
+                        .set_param_keyframe(node_id, key.clone(), frame, value.clone())
+                        .is_ok()
+                    {
+                        changed = true;
+                    }
+                    ui.close();
+                }
+                let keyed = track.as_ref().is_some_and(|track| track.has_key(frame));
+                if keyed && ui.button("Remove key").clicked() {
+                    if graph
+                        .remove_param_keyframe(node_id, &key, frame)
+                        .unwrap_or(false)
+                    {
+                        changed = true;
+                    }
+                    ui.close();
+                }
+            }
+        });
+        changed
+    }
+
+    pub fn register_inspector(
+        &mut self,
+        node_name: impl Into<String>,
+        inspector: Box<dyn NodeInspector>,
+    ) {
+        self.custom_inspectors.insert(node_name.into(), inspector);
+    }
+
     pub fn inspector_row_count(&self, graph: &Graph) -> usize {
-        let Some(node_id) = self.selected_node else {
-            return 1;
+        let Some(node_id) = self.inspected_node() else {
+            return 2;
         };
-        let Some(node) = graph.node(node_id) else {
-            return 1;
+        let Some(node) = graph
+            .subnet_at(&self.subnet_path)
+            .and_then(|graph| graph.node(node_id))
+        else {
+            return 2;
         };
-        let count = node.params.values.len();
-        if count == 0 {
-            1
-        } else {
-            count
+        let count =
+            node.params.values.len() + usize::from(self.custom_inspectors.contains_key(&node.name));
+        count.max(1) + 4 + usize::from(node.outputs.len() > 1)
+    }
+
+    // The active pinned tab or the selection; None inside a subnet.
+    pub fn inspected_node_id(&self) -> Option<NodeId> {
+        self.subnet_path
+            .is_empty()
+            .then(|| self.inspected_node())
+            .flatten()
+    }
+
+    fn inspected_node(&self) -> Option<NodeId> {
+        self.inspector_tab.or(self.selected_node)
+    }
+
+    fn show_inspector_tabs(&mut self, ui: &mut Ui, graph: &Graph) {
+        self.pinned_nodes.retain(|node| graph.node(*node).is_some());
+        if self
+            .inspector_tab
+            .is_some_and(|tab| !self.pinned_nodes.contains(&tab))
+        {
+            self.inspector_tab = None;
+        }
+        let mut unpinned = None;
+        ui.horizontal_wrapped(|ui| {
+            if ui
+                .selectable_label(self.inspector_tab.is_none(), "Selection")
+                .clicked()
+            {
+                self.inspector_tab = None;
+            }
+            for node_id in self.pinned_nodes.clone() {
+                let name = graph.node(node_id).map_or("?", |node| node.name.as_str());
+                let response = ui
+                    .selectable_label(self.inspector_tab == Some(node_id), name)
+                    .on_hover_text("Middle-click or right-click to unpin");
+                if response.clicked() {
+                    self.inspector_tab = Some(node_id);
+                }
+                if response.middle_clicked() {
+                    unpinned = Some(node_id);
+                }
+                response.context_menu(|ui| {
+                    if ui.button("Unpin").clicked() {
+                        unpinned = Some(node_id);
+                        ui.close();
+                    }
+                });
+            }
+            let pinnable = self
+                .selected_node
+                .filter(|node| self.inspector_tab.is_none() && !self.pinned_nodes.contains(node));
+            if let Some(node_id) = pinnable {
+                if ui
+                    .small_button("Pin")
+                    .on_hover_text("Keep this node's parameters in a tab")
+                    .clicked()
+                {
+                    self.pinned_nodes.push(node_id);
+                    self.inspector_tab = Some(node_id);
+                }
+            }
+        });
+        if let Some(node_id) = unpinned {
+            self.pinned_nodes.retain(|node| *node != node_id);
+            if self.inspector_tab == Some(node_id) {
+                self.inspector_tab = None;
+            }
         }
+        ui.separator();
     }
 
     pub fn set_error_state(&mut self, nodes: HashSet<NodeId>, messages: HashMap<NodeId, String>) {
@@ -537,11 +1332,484 @@ impl NodeGraphState {
         self.error_messages = messages;
     }
 
+    pub fn set_eval_report(&mut self, report: Option<&EvalReport>) {
+        self.wire_states = report.map(|report| {
+            report
+                .node_reports
+                .values()
+                .map(|node_report| {
+                    let state = if node_report.error.is_some() {
+                        WireState::Failed
+                    } else if node_report.cache_hit {
+                        WireState::Cached
+                    } else {
+                        WireState::Recooked
+                    };
+                    (node_report.node, state)
+                })
+                .collect()
+        });
+    }
+
+    // Inside a subnet this is the top-level subnet node.
     pub fn selected_node_id(&self) -> Option<NodeId> {
-        self.selected_node
+        self.subnet_path.first().copied().or(self.selected_node)
+    }
+
+    pub fn snap_to_grid(&self) -> bool {
+        self.snap_to_grid
+    }
+
+    pub fn set_snap_to_grid(&mut self, enabled: bool) {
+        self.snap_to_grid = enabled;
+    }
+
+    pub fn selection(&self) -> Vec<NodeId> {
+        let mut nodes: Vec<NodeId> = self.selected_nodes.iter().copied().collect();
+        if let Some(selected) = self.selected_node {
+            if !nodes.contains(&selected) {
+                nodes.push(selected);
+            }
+        }
+        nodes.sort();
+        nodes
+    }
+
+    pub fn align_selection(&mut self, align: NodeAlign) -> bool {
+        let selection = self.selection();
+        let mut nodes: Vec<(egui_snarl::NodeId, Rect)> = self
+            .snarl
+            .nodes_pos_ids()
+            .filter(|(_, _, node)| selection.contains(&node.core_id))
+            .map(|(snarl_id, pos, _)| {
+                let size = self
+                    .node_ui_rects
+                    .get(&snarl_id)
+                    .map(|rect| rect.size())
+                    .unwrap_or(egui::Vec2::ZERO);
+                (snarl_id, Rect::from_min_size(pos, size))
+            })
+            .collect();
+        if nodes.len() < 2 {
+            return false;
+        }
+
+        let mut targets = Vec::with_capacity(nodes.len());
+        match align {
+            NodeAlign::Left => {
+                let x = nodes
+                    .iter()
+                    .map(|(_, rect)| rect.left())
+                    .fold(f32::MAX, f32::min);
+                targets.extend(
+                    nodes
+                        .iter()
+                        .map(|(id, rect)| (*id, Pos2::new(x, rect.top()))),
+                );
+            }
+            NodeAlign::Top => {
+                let y = nodes
+                    .iter()
+                    .map(|(_, rect)| rect.top())
+                    .fold(f32::MAX, f32::min);
+                targets.extend(
+                    nodes
+                        .iter()
+                        .map(|(id, rect)| (*id, Pos2::new(rect.left(), y))),
+                );
+            }
+            NodeAlign::DistributeHorizontal | NodeAlign::DistributeVertical => {
+                let axis = usize::from(align == NodeAlign::DistributeVertical);
+                nodes.sort_by(|a, b| a.1.min[axis].total_cmp(&b.1.min[axis]));
+                let start = nodes[0].1.min[axis];
+                let end = nodes
+                    .iter()
+                    .map(|(_, rect)| rect.max[axis])
+                    .fold(start, f32::max);
+                let total: f32 = nodes.iter().map(|(_, rect)| rect.size()[axis]).sum();
+                // Equal gaps between node edges, so mixed node sizes still line up evenly.
+                let gap = (end - start - total) / (nodes.len() - 1) as f32;
+                let mut cursor = start;
+                for (id, rect) in &nodes {
+                    let mut pos = rect.min;
+                    pos[axis] = cursor;
+                    targets.push((*id, pos));
+                    cursor += rect.size()[axis] + gap;
+                }
+            }
+        }
+        let mut moved = false;
+        for (snarl_id, pos) in targets {
+            let pos = if self.snap_to_grid {
+                snap_pos(pos)
+            } else {
+                pos
+            };
+            if let Some(node) = self.snarl.get_node_info_mut(snarl_id) {
+                moved |= node.pos != pos;
+                node.pos = pos;
+            }
+        }
+        moved
+    }
+
+    // Graph-space bounds of the selected nodes.
+    fn selection_graph_rect(&self) -> Option<Rect> {
+        let selection = self.selection();
+        selection
+            .iter()
+            .filter_map(|node| self.node_ui_rects.get(self.core_to_snarl.get(node)?))
+            .copied()
+            .reduce(|a, b| a.union(b))
+    }
+
+    // Moves every node whose center lies in `rect` (graph space) by `delta`.
+    fn move_nodes_inside(&mut self, rect: Rect, delta: egui::Vec2) -> bool {
+        let inside: Vec<egui_snarl::NodeId> = self
+            .snarl
+            .nodes_pos_ids()
+            .filter(|(snarl_id, pos, _)| {
+                let size = self
+                    .node_ui_rects
+                    .get(snarl_id)
+                    .map(|rect| rect.size())
+                    .unwrap_or(egui::Vec2::ZERO);
+                rect.contains(*pos + size * 0.5)
+            })
+            .map(|(snarl_id, _, _)| snarl_id)
+            .collect();
+        for snarl_id in &inside {
+            if let Some(node) = self.snarl.get_node_info_mut(*snarl_id) {
+                node.pos += delta;
+            }
+        }
+        !inside.is_empty()
+    }
+
+    pub fn straighten_selection(&mut self, graph: &Graph) -> bool {
+        match graph.subnet_at(&self.subnet_path) {
+            Some(graph) => self.straighten_nodes(graph),
+            None => false,
+        }
+    }
+
+    fn straighten_nodes(&mut self, graph: &Graph) -> bool {
+        let scale = if self.graph_transform.valid {
+            self.graph_transform.to_global.scaling.max(0.0001)
+        } else {
+            1.0
+        };
+        let mut moved = false;
+        for node_id in self.selection() {
+            let Some(snarl_id) = self.core_to_snarl.get(&node_id).copied() else {
+                continue;
+            };
+            let Some(offset) = self.input_wire_offset(graph, snarl_id) else {
+                continue;
+            };
+            if offset.abs() < 0.5 {
+                continue;
+            }
+            if let Some(node) = self.snarl.get_node_info_mut(snarl_id) {
+                node.pos.y += offset / scale;
+                moved = true;
+            }
+        }
+        moved
+    }
+
+    fn input_wire_offset(&self, graph: &Graph, snarl_id: egui_snarl::NodeId) -> Option<f32> {
+        let (out_pin, in_pin) = self
+            .snarl
+            .wires()
+            .filter(|(_, in_pin)| in_pin.node == snarl_id)
+            .min_by_key(|(_, in_pin)| in_pin.input)?;
+        let from = self.pin_pos_for_output(graph, out_pin)?;
+        let to = self.pin_pos_for_input(graph, in_pin)?;
+        Some(from.y - to.y)
+    }
+
+    pub fn request_frame(&mut self, selected_only: bool) {
+        self.frame_request = Some(selected_only);
+    }
+
+    pub fn view_transform(&self) -> Option<egui::emath::TSTransform> {
+        self.graph_transform
+            .valid
+            .then_some(self.graph_transform.to_global)
+    }
+
+    // Animates to a saved view and, when `nodes` is not empty, selects them.
+    pub fn go_to_view(&mut self, view: egui::emath::TSTransform, nodes: &[NodeId], now: f64) {
+        while self.exit_subnet() {}
+        self.animate_transform(view, now);
+        if !nodes.is_empty() {
+            self.selected_nodes = nodes.iter().copied().collect();
+            self.selected_node = nodes.first().copied();
+        }
+    }
+
+    pub fn set_keyboard_focus(&mut self, focused: bool) {
+        self.keyboard_focus = focused;
+    }
+
+    pub fn set_high_contrast(&mut self, enabled: bool) {
+        self.high_contrast = enabled;
+    }
+
+    fn handle_keyboard(&mut self, ui: &Ui, graph: &mut Graph) -> bool {
+        if !self.keyboard_focus || self.add_menu_open || self.node_menu_open {
+            return false;
+        }
+        let view = ui.max_rect();
+        let (direction, toggle_display, delete, add_node, node_menu, dive, up) =
+            ui.input_mut(|i| {
+                let direction = [
+                    (egui::Key::ArrowLeft, vec2(-1.0, 0.0)),
+                    (egui::Key::ArrowRight, vec2(1.0, 0.0)),
+                    (egui::Key::ArrowUp, vec2(0.0, -1.0)),
+                    (egui::Key::ArrowDown, vec2(0.0, 1.0)),
+                ]
+                .into_iter()
+                .find(|(key, _)| i.consume_key(egui::Modifiers::NONE, *key))
+                .map(|(_, direction)| direction);
+                (
+                    direction,
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::Enter),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::Delete),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::Tab),
+                    i.consume_key(egui::Modifiers::SHIFT, egui::Key::F10),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::I),
+                    i.consume_key(egui::Modifiers::NONE, egui::Key::U),
+                )
+            });
+        if up && !self.subnet_path.is_empty() {
+            self.subnet_nav = Some(SubnetNav::Exit(self.subnet_path.len() - 1));
+        }
+        if let Some(direction) = direction {
+            self.select_in_direction(direction, view);
+        }
+        if add_node {
+            self.open_add_menu(view.center());
+        }
+        let Some(node_id) = self.selected_node else {
+            return false;
+        };
+        if node_menu {
+            let screen_pos = self
+                .node_screen_rect(node_id)
+                .map(|rect| rect.center())
+                .unwrap_or(view.center());
+            self.node_menu_request = Some(NodeMenuRequest {
+                node_id,
+                screen_pos,
+            });
+        }
+        if dive && graph.subnet(node_id).is_some() {
+            self.subnet_nav = Some(SubnetNav::Enter(node_id));
+        }
+        if delete {
+            self.delete_node(graph, node_id);
+            return true;
+        }
+        toggle_display && graph.toggle_display_node(node_id).is_ok()
+    }
+
+    fn select_in_direction(&mut self, direction: egui::Vec2, view: Rect) {
+        let current = self
+            .selected_node
+            .and_then(|node_id| self.node_screen_rect(node_id).map(|rect| (node_id, rect)));
+        let candidates = self.snarl_to_core.values().filter_map(|node_id| {
+            let center = self.node_screen_rect(*node_id)?.center();
+            Some((*node_id, center))
+        });
+        // Prefer nodes straight ahead; sideways distance counts double.
+        let next = match current {
+            Some((current_id, rect)) => candidates
+                .filter(|(node_id, _)| *node_id != current_id)
+                .filter_map(|(node_id, center)| {
+                    let offset = center - rect.center();
+                    let along = offset.dot(direction);
+                    let across = (offset - direction * along).length();
+                    (along > 1.0).then_some((node_id, along + across * 2.0))
+                })
+                .min_by(|a, b| a.1.total_cmp(&b.1)),
+            None => candidates
+                .map(|(node_id, center)| (node_id, center.distance(view.center())))
+                .min_by(|a, b| a.1.total_cmp(&b.1)),
+        };
+        let Some((next, _)) = next else {
+            return;
+        };
+        self.selected_nodes.clear();
+        self.selected_node = Some(next);
+        if self
+            .node_screen_rect(next)
+            .is_some_and(|rect| !view.contains_rect(rect))
+        {
+            self.frame_request = Some(true);
+        }
+    }
+
+    pub fn node_screen_rect(&self, node_id: NodeId) -> Option<Rect> {
+        let rect = self.node_ui_rects.get(self.core_to_snarl.get(&node_id)?)?;
+        Some(if self.graph_transform.valid {
+            self.graph_transform.to_global * *rect
+        } else {
+            *rect
+        })
+    }
+
+    fn handle_navigation(&mut self, ui: &Ui) {
+        let view = ui.max_rect();
+        let now = ui.input(|i| i.time);
+        let hover = ui
+            .input(|i| i.pointer.hover_pos())
+            .filter(|pos| view.contains(*pos));
+        if hover.is_some()
+            && ui.input(|i| i.pointer.any_down() || i.smooth_scroll_delta != egui::Vec2::ZERO)
+        {
+            self.transform_animation = None;
+        }
+        // Keyboard-focused graphs zoom about the view center when the pointer is elsewhere.
+        let pivot = hover.or(self.keyboard_focus.then_some(view.center()));
+        if let Some(pos) = pivot {
+            if (self.keyboard_focus || !ui.ctx().wants_keyboard_input()) && !self.add_menu_open {
+                let (frame_all, frame_selected, zoom_in, zoom_out) = ui.input_mut(|i| {
+                    (
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::Home),
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::F),
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::Plus)
+                            || i.consume_key(egui::Modifiers::NONE, egui::Key::Equals),
+                        i.consume_key(egui::Modifiers::NONE, egui::Key::Minus),
+                    )
+                });
+                if frame_all {
+                    self.frame_request = Some(false);
+                } else if frame_selected {
+                    self.frame_request = Some(true);
+                }
+                if zoom_in {
+                    self.zoom_about(pos, KEY_ZOOM_STEP, now);
+                } else if zoom_out {
+                    self.zoom_about(pos, 1.0 / KEY_ZOOM_STEP, now);
+                }
+            }
+        }
+        // Snarl already pans with the first finger, so a pinch only adds the zoom.
+        if let Some(touch) = ui.input(|i| i.multi_touch()) {
+            if view.contains(touch.center_pos) && touch.zoom_delta != 1.0 {
+                self.transform_animation = None;
+                self.pinch_zoom(touch.center_pos, touch.zoom_delta);
+            }
+        }
+        if let Some(selected_only) = self.frame_request.take() {
+            self.frame_nodes(view, selected_only, now);
+        }
+
+        let Some(animation) = self.transform_animation else {
+            return;
+        };
+        let t = ((now - animation.start) / NAV_ANIMATION_SECS).clamp(0.0, 1.0) as f32;
+        let eased = 1.0 - (1.0 - t).powi(3);
+        let from = animation.from;
+        let to = animation.to;
+        self.graph_transform.requested = Some(egui::emath::TSTransform::new(
+            from.translation + (to.translation - from.translation) * eased,
+            from.scaling + (to.scaling - from.scaling) * eased,
+        ));
+        if t >= 1.0 {
+            self.transform_animation = None;
+        } else {
+            ui.ctx().request_repaint();
+        }
+    }
+
+    fn frame_nodes(&mut self, view: Rect, selected_only: bool, now: f64) {
+        let selection = self.selection();
+        let selected_only = selected_only && !selection.is_empty();
+        let bounds = self
+            .node_ui_rects
+            .iter()
+            .filter(|(snarl_id, _)| {
+                !selected_only
+                    || self
+                        .snarl_to_core
+                        .get(snarl_id)
+                        .is_some_and(|core_id| selection.contains(core_id))
+            })
+            .map(|(_, rect)| *rect)
+            .reduce(|a, b| a.union(b));
+        let Some(bounds) = bounds else {
+            return;
+        };
+        let available = view.shrink(FRAME_MARGIN);
+        let scale = (available.width() / bounds.width().max(1.0))
+            .min(available.height() / bounds.height().max(1.0))
+            .clamp(MIN_ZOOM, MAX_ZOOM);
+        let target = egui::emath::TSTransform::new(
+            view.center().to_vec2() - bounds.center().to_vec2() * scale,
+            scale,
+        );
+        self.animate_transform(target, now);
+    }
+
+    fn zoom_about(&mut self, pivot: Pos2, factor: f32, now: f64) {
+        if !self.graph_transform.valid {
+            return;
+        }
+        let base = self
+            .transform_animation
+            .map(|animation| animation.to)
+            .unwrap_or(self.graph_transform.to_global);
+        self.animate_transform(zoomed_transform(base, pivot, factor), now);
+    }
+
+    fn pinch_zoom(&mut self, pivot: Pos2, factor: f32) {
+        if !self.graph_transform.valid {
+            return;
+        }
+        let base = self
+            .graph_transform
+            .requested
+            .unwrap_or(self.graph_transform.to_global);
+        self.graph_transform.requested = Some(zoomed_transform(base, pivot, factor));
+    }
+
+    fn animate_transform(&mut self, target: egui::emath::TSTransform, now: f64) {
+        if !self.graph_transform.valid {
+            self.graph_transform.requested = Some(target);
+            return;
+        }
+        self.transform_animation = Some(TransformAnimation {
+            from: self.graph_transform.to_global,
+            to: target,
+            start: now,
+        });
+    }
+
+    fn snap_dropped_node(&mut self, ui: &Ui) {
+        if !self.snap_to_grid
+            || !ui.input(|i| i.pointer.button_released(egui::PointerButton::Primary))
+        {
+            return;
+        }
+        let Some(snarl_id) = self.dragging_node.or_else(|| self.find_moved_node()) else {
+            return;
+        };
+        if let Some(node) = self.snarl.get_node_info_mut(snarl_id) {
+            let snapped = snap_pos(node.pos);
+            if snapped != node.pos {
+                node.pos = snapped;
+                self.layout_changed = true;
+            }
+        }
     }
 
     pub fn node_at_screen_pos(&self, pos: Pos2) -> Option<NodeId> {
+        if !self.subnet_path.is_empty() {
+            return None;
+        }
         let snarl_node = self.node_at_pos(pos)?;
         self.snarl_to_core.get(&snarl_node).copied()
     }
@@ -550,10 +1818,23 @@ impl NodeGraphState {
         self.info_request.take()
     }
 
+    pub fn take_cook_request(&mut self) -> Option<NodeId> {
+        self.cook_request.take()
+    }
+
+    // True once after the seed dice changed a parameter.
+    pub fn take_seed_rolled(&mut self) -> bool {
+        std::mem::take(&mut self.seed_rolled)
+    }
+
     pub fn take_wrangle_help_request(&mut self) -> Option<Pos2> {
         self.wrangle_help_request.take()
     }
 
+    pub fn take_node_help_request(&mut self) -> Option<NodeInfoRequest> {
+        self.node_help_request.take()
+    }
+
     fn show_add_menu(&mut self, ui: &mut Ui, graph: &mut Graph) -> bool {
         let mut close_menu = ui.input(|i| i.key_pressed(egui::Key::Escape));
         let mut menu_rect = None;
@@ -601,7 +1882,12 @@ impl NodeGraphState {
                         ui.label(item.category);
                         last_category = Some(item.category);
                     }
-                    if ui.button(item.name).clicked() {
+                    let button = ui.button(item.name);
+                    let button = match node_summary(item.kind) {
+                        Some(summary) => button.on_hover_text(summary),
+                        None => button,
+                    };
+                    if button.clicked() {
                         if let Some(core_id) =
                             self.try_add_node(graph, item.kind, self.add_menu_graph_pos)
                         {
@@ -844,7 +2130,10 @@ impl NodeGraphState {
             return false;
         };
 
-        let _ = graph.remove_link_between(core_out, core_in);
+        let _ = graph.apply(&GraphCommand::Disconnect {
+            from: core_out,
+            to: core_in,
+        });
         let _ = self.snarl.disconnect(out_pin, in_pin);
 
         let new_in_snarl = InPinId {
@@ -856,17 +2145,17 @@ impl NodeGraphState {
             output: new_out_idx,
         };
 
-        if graph.add_link(core_out, new_in_pin).is_err() {
-            let _ = graph.remove_links_for_pin(new_in_pin);
+        if connect_pins(graph, core_out, new_in_pin).is_err() {
+            disconnect_pin(graph, new_in_pin);
             self.snarl.drop_inputs(new_in_snarl);
-            let _ = graph.add_link(core_out, new_in_pin);
+            let _ = connect_pins(graph, core_out, new_in_pin);
         }
         let _ = self.snarl.connect(out_pin, new_in_snarl);
 
-        if graph.add_link(new_out_pin, core_in).is_err() {
-            let _ = graph.remove_links_for_pin(core_in);
+        if connect_pins(graph, new_out_pin, core_in).is_err() {
+            disconnect_pin(graph, core_in);
             self.snarl.drop_inputs(in_pin);
-            let _ = graph.add_link(new_out_pin, core_in);
+            let _ = connect_pins(graph, new_out_pin, core_in);
         }
         let _ = self.snarl.connect(new_out_snarl, in_pin);
         true
@@ -902,10 +2191,10 @@ impl NodeGraphState {
                         node: snarl_node,
                         input: new_in_idx,
                     };
-                    if graph.add_link(core_out, new_in_pin).is_err() {
-                        let _ = graph.remove_links_for_pin(new_in_pin);
+                    if connect_pins(graph, core_out, new_in_pin).is_err() {
+                        disconnect_pin(graph, new_in_pin);
                         self.snarl.drop_inputs(new_in_snarl);
-                        let _ = graph.add_link(core_out, new_in_pin);
+                        let _ = connect_pins(graph, core_out, new_in_pin);
                     }
                     let _ = self.snarl.connect(out_pin, new_in_snarl);
                     return true;
@@ -945,9 +2234,9 @@ impl NodeGraphState {
                     if pin_data.pin_type != target_type {
                         continue;
                     }
-                    let _ = graph.remove_links_for_pin(core_in);
+                    disconnect_pin(graph, core_in);
                     self.snarl.drop_inputs(in_pin);
-                    if graph.add_link(new_out_pin, core_in).is_ok() {
+                    if connect_pins(graph, new_out_pin, core_in).is_ok() {
                         let _ = self.snarl.connect(new_out_snarl, in_pin);
                         connected = true;
                     }
@@ -974,3 +2263,98 @@ pub(super) enum PendingWire {
     FromOutputs(Vec<OutPinId>),
     FromInputs(Vec<InPinId>),
 }
+
+fn zoomed_transform(
+    base: egui::emath::TSTransform,
+    pivot: Pos2,
+    factor: f32,
+) -> egui::emath::TSTransform {
+    let scale = (base.scaling * factor).clamp(MIN_ZOOM, MAX_ZOOM);
+    let anchor = base.inverse() * pivot;
+    egui::emath::TSTransform::new(pivot.to_vec2() - anchor.to_vec2() * scale, scale)
+}
+
+fn snap_pos(pos: Pos2) -> Pos2 {
+    Pos2::new(
+        (pos.x / SNAP_GRID).round() * SNAP_GRID,
+        (pos.y / SNAP_GRID).round() * SNAP_GRID,
+    )
+}
+
+fn show_input_order(ui: &mut Ui, graph: &mut Graph, node_id: NodeId) -> bool {
+    let Some(node) = graph.node(node_id) else {
+        return false;
+    };
+    let inputs = node.inputs.clone();
+    if inputs.len() < 2 {
+        return false;
+    }
+
+    ui.separator();
+    ui.weak("Input order");
+    let mut moved = None;
+    for (index, pin_id) in inputs.iter().enumerate() {
+        let name = graph
+            .pin(*pin_id)
+            .map(|pin| pin.name.clone())
+            .unwrap_or_default();
+        let source = graph
+            .links()
+            .find(|link| link.to == *pin_id)
+            .and_then(|link| graph.pin(link.from))
+            .and_then(|pin| graph.node(pin.node))
+            .map(|node| node.name.clone())
+            .unwrap_or_else(|| "-".to_string());
+        ui.horizontal(|ui| {
+            if ui
+                .add_enabled(index > 0, egui::Button::new("⏶").small())
+                .clicked()
+            {
+                moved = Some((*pin_id, index - 1));
+            }
+            if ui
+                .add_enabled(index + 1 < inputs.len(), egui::Button::new("⏷").small())
+                .clicked()
+            {
+                moved = Some((*pin_id, index + 1));
+            }
+            ui.label(format!("{}: {}", name, source));
+        });
+    }
+    moved.is_some_and(|(pin_id, index)| graph.move_input(node_id, pin_id, index).is_ok())
+}
+
+fn show_output_stats(ui: &mut Ui, output: Option<&Mesh>) {
+    let Some(mesh) = output else {
+        ui.weak("Not cooked");
+        return;
+    };
+    let size = mesh
+        .bounds()
+        .map(|bounds| {
+            format!(
+                "{:.3} x {:.3} x {:.3}",
+                bounds.max[0] - bounds.min[0],
+                bounds.max[1] - bounds.min[1],
+                bounds.max[2] - bounds.min[2]
+            )
+        })
+        .unwrap_or_else(|| "empty".to_string());
+    ui.weak(format!(
+        "{} points, {} prims, size {}",
+        mesh.positions.len(),
+        mesh.face_count(),
+        size
+    ));
+    let attrs: Vec<String> = mesh
+        .list_attributes()
+        .into_iter()
+        .filter(|attr| !attr.implicit)
+        .map(|attr| attr.name)
+        .collect();
+    if attrs.is_empty() {
+        ui.weak("No attributes");
+    } else {
+        ui.weak(format!("Attributes: {}", attrs.join(", ")));
+    }
+}
@@ -4,10 +4,11 @@ use egui::{Color32, Pos2};
 use egui_snarl::Snarl;
 
 use grapho_core::{
-    default_params, node_definition, BuiltinNodeKind, Graph, NodeId, PinId, PinType,
+    node_definition, BuiltinNodeKind, CommandOutput, Graph, GraphCommand, GraphError, NodeId,
+    PinId, PinType,
 };
 
-use super::state::SnarlNode;
+use super::state::{SnarlNode, WireState};
 
 pub(super) fn pin_color(pin_type: PinType) -> Color32 {
     match pin_type {
@@ -17,6 +18,60 @@ pub(super) fn pin_color(pin_type: PinType) -> Color32 {
         PinType::Bool => Color32::from_rgb(140, 220, 140),
         PinType::Vec2 => Color32::from_rgb(255, 160, 90),
         PinType::Vec3 => Color32::from_rgb(90, 210, 210),
+        PinType::PointCloud => Color32::from_rgb(120, 200, 255),
+        PinType::Volume => Color32::from_rgb(170, 140, 255),
+    }
+}
+
+// Snarl has no dashed wires, so nodes outside the last cooked chain get a faint wire.
+pub(super) fn wire_state_color(state: Option<WireState>) -> Color32 {
+    match state {
+        Some(WireState::Cached) => Color32::from_gray(120),
+        Some(WireState::Recooked) => Color32::from_rgb(120, 210, 120),
+        Some(WireState::Failed) => Color32::from_rgb(230, 100, 90),
+        None => Color32::from_gray(50),
+    }
+}
+
+pub(super) fn node_summary(kind: BuiltinNodeKind) -> Option<String> {
+    node_definition(kind).help.map(|help| help.summary)
+}
+
+// Editor nodes are created by the same commands scripts and collaborators send, so they get
+// identical defaults. The first node in a graph becomes the display node.
+pub(super) fn add_core_node(graph: &mut Graph, kind: BuiltinNodeKind) -> NodeId {
+    let was_empty = graph.nodes().next().is_none();
+    let command = GraphCommand::AddNode {
+        kind: kind.name().to_string(),
+    };
+    let Ok(CommandOutput::Node(core_id)) = graph.apply(&command) else {
+        unreachable!("builtin node defaults fit their own ranges");
+    };
+    if was_empty {
+        let _ = graph.apply(&GraphCommand::SetDisplay {
+            node: Some(core_id),
+        });
+    }
+    core_id
+}
+
+pub(super) fn connect_pins(
+    graph: &mut Graph,
+    from: PinId,
+    to: PinId,
+) -> Result<CommandOutput, GraphError> {
+    graph.apply(&GraphCommand::Connect { from, to })
+}
+
+// Every wire on the pin, either end, goes as its own Disconnect command.
+pub(super) fn disconnect_pin(graph: &mut Graph, pin: PinId) {
+    let links: Vec<(PinId, PinId)> = graph
+        .links()
+        .filter(|link| link.from == pin || link.to == pin)
+        .map(|link| (link.from, link.to))
+        .collect();
+    for (from, to) in links {
+        let _ = graph.apply(&GraphCommand::Disconnect { from, to });
     }
 }
 
@@ -28,15 +83,7 @@ pub(super) fn add_builtin_node(
     kind: BuiltinNodeKind,
     pos: Pos2,
 ) -> NodeId {
-    let was_empty = graph.nodes().next().is_none();
-    let core_id = graph.add_node(node_definition(kind));
-    let params = default_params(kind);
-    for (key, value) in params.values {
-        let _ = graph.set_param(core_id, key, value);
-    }
-    if was_empty {
-        let _ = graph.set_display_node(Some(core_id));
-    }
+    let core_id = add_core_node(graph, kind);
     let snarl_id = snarl.insert_node(pos, SnarlNode { core_id });
     core_to_snarl.insert(core_id, snarl_id);
     snarl_to_core.insert(snarl_id, core_id);
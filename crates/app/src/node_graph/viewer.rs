@@ -6,11 +6,11 @@ use egui::{Align2, Color32, FontId, Pos2, Rect, TextStyle, Ui};
 use egui_snarl::ui::{AnyPins, PinInfo, SnarlPin, SnarlViewer};
 use egui_snarl::{InPinId, OutPinId, Snarl};
 
-use grapho_core::{default_params, node_definition, BuiltinNodeKind, Graph, NodeId, PinId};
+use grapho_core::{BuiltinNodeKind, Graph, GraphCommand, GraphError, NodeId, PinId};
 
 use super::menu::builtin_menu_items;
-use super::state::{GraphTransformState, HeaderButtonRects, PendingWire, SnarlNode};
-use super::utils::pin_color;
+use super::state::{GraphTransformState, HeaderButtonRects, PendingWire, SnarlNode, WireState};
+use super::utils::{add_core_node, disconnect_pin, node_summary, pin_color, wire_state_color};
 
 pub(super) struct NodeGraphViewer<'a> {
     pub(super) graph: &'a mut Graph,
@@ -18,6 +18,7 @@ pub(super) struct NodeGraphViewer<'a> {
     pub(super) snarl_to_core: &'a mut HashMap<egui_snarl::NodeId, NodeId>,
     pub(super) next_pos: &'a mut Pos2,
     pub(super) selected_node: &'a mut Option<NodeId>,
+    pub(super) selected_nodes: &'a mut HashSet<NodeId>,
     pub(super) node_rects: &'a mut HashMap<egui_snarl::NodeId, Rect>,
     pub(super) header_button_rects: &'a mut HashMap<egui_snarl::NodeId, HeaderButtonRects>,
     pub(super) graph_transform: &'a mut GraphTransformState,
@@ -31,8 +32,11 @@ pub(super) struct NodeGraphViewer<'a> {
     pub(super) pending_wire: &'a mut Option<PendingWire>,
     pub(super) node_menu_request: &'a mut Option<super::state::NodeMenuRequest>,
     pub(super) wrangle_help_request: &'a mut Option<Pos2>,
+    pub(super) node_help_request: &'a mut Option<super::state::NodeInfoRequest>,
     pub(super) error_nodes: &'a HashSet<NodeId>,
     pub(super) error_messages: &'a HashMap<NodeId, String>,
+    pub(super) wire_states: Option<&'a HashMap<NodeId, WireState>>,
+    pub(super) high_contrast: bool,
     pub(super) changed: bool,
 }
 
@@ -89,27 +93,39 @@ impl<'a> NodeGraphViewer<'a> {
         node.inputs.get(pin.input).copied()
     }
 
+    fn is_variadic_slot(&self, snarl: &Snarl<SnarlNode>, pin: InPinId) -> bool {
+        self.core_node_id(snarl, pin.node)
+            .and_then(|core_node| self.graph.node(core_node))
+            .is_some_and(|node| {
+                pin.input == node.inputs.len() && self.graph.variadic_inputs(node.id).is_some()
+            })
+    }
+
     fn core_pin_for_output(&self, snarl: &Snarl<SnarlNode>, pin: OutPinId) -> Option<PinId> {
         let core_node = self.core_node_id(snarl, pin.node)?;
         let node = self.graph.node(core_node)?;
         node.outputs.get(pin.output).copied()
     }
 
+    // Pin info for a connection whose data comes from `upstream`, colored by its last cook
+    // when wire state coloring is on.
+    fn wire_pin(&self, fill: Color32, upstream: Option<NodeId>) -> PinInfo {
+        let pin = PinInfo::circle().with_fill(fill);
+        match (self.wire_states, upstream) {
+            (Some(states), Some(node)) => {
+                pin.with_wire_color(wire_state_color(states.get(&node).copied()))
+            }
+            _ => pin,
+        }
+    }
+
     fn add_node(&mut self, snarl: &mut Snarl<SnarlNode>, kind: BuiltinNodeKind, pos: Pos2) {
         if kind == BuiltinNodeKind::Output && self.graph.nodes().any(|node| node.name == "Output") {
             tracing::warn!("Only one Output node is supported right now.");
             return;
         }
 
-        let was_empty = self.graph.nodes().next().is_none();
-        let core_id = self.graph.add_node(node_definition(kind));
-        let params = default_params(kind);
-        for (key, value) in params.values {
-            let _ = self.graph.set_param(core_id, key, value);
-        }
-        if was_empty {
-            let _ = self.graph.set_display_node(Some(core_id));
-        }
+        let core_id = add_core_node(self.graph, kind);
 
         let snarl_id = snarl.insert_node(pos, SnarlNode { core_id });
         self.core_to_snarl.insert(core_id, snarl_id);
@@ -155,19 +171,20 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         let left_pad = 8.0;
         let min_title_width = 32.0;
         let core_id = self.core_node_id(snarl, node);
-        let (display_active, template_active, show_help) = core_id
+        let (display_active, template_active, is_wrangle) = core_id
             .and_then(|id| self.graph.node(id))
             .map(|node| (node.display, node.template, node.name == "Wrangle"))
             .unwrap_or((false, false, false));
 
-        let button_count = if show_help { 3usize } else { 2usize };
+        let button_count = 3usize;
         let button_width =
             icon_size * button_count as f32 + button_gap * (button_count.saturating_sub(1)) as f32;
         let desired_width =
             (title_width.max(min_title_width) + left_pad + button_width + right_pad).max(24.0);
         let width = ui.available_width().max(desired_width);
-        let (rect, _response) =
+        let (rect, response) =
             ui.allocate_exact_size(egui::vec2(width, height), egui::Sense::hover());
+        response.widget_info(|| egui::WidgetInfo::labeled(egui::WidgetType::Label, true, &title));
         let right_min_x = (rect.right() - right_pad - button_width).max(rect.left());
         let left_rect = Rect::from_min_max(rect.min, Pos2::new(right_min_x, rect.bottom()));
 
@@ -180,14 +197,10 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
             Pos2::new(display_rect.left() - button_gap - icon_size, icon_y),
             egui::vec2(icon_size, icon_size),
         );
-        let help_rect = if show_help {
-            Some(Rect::from_min_size(
-                Pos2::new(template_rect.left() - button_gap - icon_size, icon_y),
-                egui::vec2(icon_size, icon_size),
-            ))
-        } else {
-            None
-        };
+        let help_rect = Some(Rect::from_min_size(
+            Pos2::new(template_rect.left() - button_gap - icon_size, icon_y),
+            egui::vec2(icon_size, icon_size),
+        ));
         self.header_button_rects.insert(
             node,
             HeaderButtonRects {
@@ -214,10 +227,25 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
                 egui::Sense::hover(),
             )
         });
+        let help_label = if is_wrangle { "Wrangle help" } else { "Help" };
+        display_response.widget_info(|| {
+            egui::WidgetInfo::selected(egui::WidgetType::Checkbox, true, display_active, "Display")
+        });
+        template_response.widget_info(|| {
+            egui::WidgetInfo::selected(
+                egui::WidgetType::Checkbox,
+                true,
+                template_active,
+                "Template",
+            )
+        });
         display_response.on_hover_text("Display");
         template_response.on_hover_text("Template");
         if let Some(response) = help_response {
-            response.on_hover_text("Wrangle help");
+            response.widget_info(|| {
+                egui::WidgetInfo::labeled(egui::WidgetType::Button, true, help_label)
+            });
+            response.on_hover_text(help_label);
         }
 
         let drag_response = ui.interact(
@@ -234,7 +262,11 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
             }
         }
 
-        let title_color = Color32::from_rgb(60, 60, 60);
+        let title_color = if self.high_contrast {
+            Color32::WHITE
+        } else {
+            Color32::from_rgb(60, 60, 60)
+        };
         let text_pos = left_rect.left_center() + egui::vec2(4.0, 0.0);
         ui.painter().with_clip_rect(left_rect).text(
             text_pos,
@@ -246,7 +278,11 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
 
         let painter = ui.painter();
         let inactive_fill = Color32::from_rgb(70, 70, 70);
-        let inactive_stroke = Color32::from_rgb(100, 100, 100);
+        let inactive_stroke = if self.high_contrast {
+            Color32::WHITE
+        } else {
+            Color32::from_rgb(100, 100, 100)
+        };
         let inactive_text = Color32::from_rgb(230, 230, 230);
         let display_fill = if display_active {
             Color32::from_rgb(40, 140, 230)
@@ -316,10 +352,15 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
     }
 
     fn inputs(&mut self, node: &SnarlNode) -> usize {
-        self.graph
+        let count = self
+            .graph
             .node(node.core_id)
             .map(|node| node.inputs.len())
-            .unwrap_or(0)
+            .unwrap_or(0);
+        match self.graph.variadic_inputs(node.core_id) {
+            Some(variadic) if count < variadic.max => count + 1,
+            _ => count,
+        }
     }
 
     fn outputs(&mut self, node: &SnarlNode) -> usize {
@@ -338,13 +379,27 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         if let Some(core_pin) = self.core_pin_for_input(snarl, pin.id) {
             if let Some(pin_data) = self.graph.pin(core_pin) {
                 ui.label(&pin_data.name);
+                let upstream = self
+                    .graph
+                    .links()
+                    .find(|link| link.to == core_pin)
+                    .and_then(|link| self.graph.pin(link.from))
+                    .map(|from| from.node);
                 return RecordedPin {
-                    pin: PinInfo::circle().with_fill(pin_color(pin_data.pin_type)),
+                    pin: self.wire_pin(pin_color(pin_data.pin_type), upstream),
                     record: PinRecord::In(pin.id, Rc::clone(&self.input_pin_positions)),
                     graph_to_screen: self.graph_transform.to_global,
                 };
             }
         }
+        if self.is_variadic_slot(snarl, pin.id) {
+            ui.weak("+");
+            return RecordedPin {
+                pin: PinInfo::circle().with_fill(Color32::from_gray(70)),
+                record: PinRecord::In(pin.id, Rc::clone(&self.input_pin_positions)),
+                graph_to_screen: self.graph_transform.to_global,
+            };
+        }
         ui.label("?");
         RecordedPin {
             pin: PinInfo::circle(),
@@ -363,7 +418,7 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
             if let Some(pin_data) = self.graph.pin(core_pin) {
                 ui.label(&pin_data.name);
                 return RecordedPin {
-                    pin: PinInfo::circle().with_fill(pin_color(pin_data.pin_type)),
+                    pin: self.wire_pin(pin_color(pin_data.pin_type), Some(pin_data.node)),
                     record: PinRecord::Out(pin.id, Rc::clone(&self.output_pin_positions)),
                     graph_to_screen: self.graph_transform.to_global,
                 };
@@ -384,7 +439,12 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
     fn show_graph_menu(&mut self, pos: Pos2, ui: &mut Ui, snarl: &mut Snarl<SnarlNode>) {
         ui.label("Add node");
         for item in builtin_menu_items() {
-            if ui.button(item.name).clicked() {
+            let button = ui.button(item.name);
+            let button = match node_summary(item.kind) {
+                Some(summary) => button.on_hover_text(summary),
+                None => button,
+            };
+            if button.clicked() {
                 self.add_node(snarl, item.kind, pos);
                 ui.close();
             }
@@ -447,6 +507,10 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
             let stroke = egui::Stroke::new(4.0, egui::Color32::from_rgb(235, 200, 60));
             ui.painter()
                 .rect_stroke(ui_rect, 6.0, stroke, egui::StrokeKind::Inside);
+        } else if self.selected_nodes.contains(&core_id) {
+            let stroke = egui::Stroke::new(2.0, egui::Color32::from_rgb(235, 200, 60));
+            ui.painter()
+                .rect_stroke(ui_rect, 6.0, stroke, egui::StrokeKind::Inside);
         }
 
         if self.error_nodes.contains(&core_id) {
@@ -504,7 +568,18 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
                 return;
             }
             if buttons.help.is_some_and(|rect| rect.contains(pos)) {
-                *self.wrangle_help_request = Some(pos_screen);
+                if self
+                    .graph
+                    .node(core_id)
+                    .is_some_and(|node| node.name == "Wrangle")
+                {
+                    *self.wrangle_help_request = Some(pos_screen);
+                } else {
+                    *self.node_help_request = Some(super::state::NodeInfoRequest {
+                        node_id: core_id,
+                        screen_pos: pos_screen,
+                    });
+                }
                 return;
             }
         }
@@ -535,8 +610,27 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
                 egui::Sense::click()
             },
         );
+        let selected =
+            *self.selected_node == Some(core_id) || self.selected_nodes.contains(&core_id);
+        let title = self.title(&snarl[node]);
+        response.widget_info(|| {
+            egui::WidgetInfo::selected(egui::WidgetType::SelectableLabel, true, selected, &title)
+        });
         if !blocked && response.clicked_by(egui::PointerButton::Primary) {
-            *self.selected_node = Some(core_id);
+            if ui.input(|i| i.modifiers.shift) {
+                if let Some(previous) = *self.selected_node {
+                    self.selected_nodes.insert(previous);
+                }
+                if self.selected_nodes.insert(core_id) {
+                    *self.selected_node = Some(core_id);
+                } else {
+                    self.selected_nodes.remove(&core_id);
+                    *self.selected_node = self.selected_nodes.iter().min().copied();
+                }
+            } else {
+                self.selected_nodes.clear();
+                *self.selected_node = Some(core_id);
+            }
         }
         if !blocked && response.clicked_by(egui::PointerButton::Secondary) {
             let pos = ui
@@ -555,6 +649,9 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         to_global: &mut egui::emath::TSTransform,
         _snarl: &mut Snarl<SnarlNode>,
     ) {
+        if let Some(requested) = self.graph_transform.requested.take() {
+            *to_global = requested;
+        }
         if to_global.is_valid() {
             self.graph_transform.to_global = *to_global;
             self.graph_transform.valid = true;
@@ -570,25 +667,46 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         let Some(from_pin) = self.core_pin_for_output(snarl, from.id) else {
             return;
         };
-        let Some(to_pin) = self.core_pin_for_input(snarl, to.id) else {
-            return;
+        let to_pin = if self.is_variadic_slot(snarl, to.id) {
+            let Some(core_node) = self.core_node_id(snarl, to.id.node) else {
+                return;
+            };
+            match self.graph.add_variadic_input(core_node) {
+                Ok(pin) => pin,
+                Err(err) => {
+                    tracing::warn!("link rejected: {:?}", err);
+                    return;
+                }
+            }
+        } else {
+            let Some(to_pin) = self.core_pin_for_input(snarl, to.id) else {
+                return;
+            };
+            to_pin
         };
 
-        match self.graph.add_link(from_pin, to_pin) {
+        let connect = GraphCommand::Connect {
+            from: from_pin,
+            to: to_pin,
+        };
+        match self.graph.apply(&connect) {
             Ok(_) => {
                 let _ = snarl.connect(from.id, to.id);
                 self.changed = true;
             }
-            Err(grapho_core::GraphError::InputAlreadyConnected { .. }) => {
-                let _ = self.graph.remove_links_for_pin(to_pin);
+            Err(GraphError::InputAlreadyConnected { .. }) => {
+                disconnect_pin(self.graph, to_pin);
                 snarl.drop_inputs(to.id);
-                if self.graph.add_link(from_pin, to_pin).is_ok() {
+                if self.graph.apply(&connect).is_ok() {
                     let _ = snarl.connect(from.id, to.id);
                     self.changed = true;
                 }
             }
             Err(err) => {
                 tracing::warn!("link rejected: {:?}", err);
+                if let Some(core_node) = self.core_node_id(snarl, to.id.node) {
+                    self.graph.compact_variadic_inputs(core_node);
+                }
             }
         }
     }
@@ -605,14 +723,20 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
         let Some(to_pin) = self.core_pin_for_input(snarl, to.id) else {
             return;
         };
-        let _ = self.graph.remove_link_between(from_pin, to_pin);
+        let _ = self.graph.apply(&GraphCommand::Disconnect {
+            from: from_pin,
+            to: to_pin,
+        });
         let _ = snarl.disconnect(from.id, to.id);
+        if let Some(core_node) = self.core_node_id(snarl, to.id.node) {
+            self.graph.compact_variadic_inputs(core_node);
+        }
         self.changed = true;
     }
 
     fn drop_outputs(&mut self, pin: &egui_snarl::OutPin, snarl: &mut Snarl<SnarlNode>) {
         if let Some(core_pin) = self.core_pin_for_output(snarl, pin.id) {
-            let _ = self.graph.remove_links_for_pin(core_pin);
+            disconnect_pin(self.graph, core_pin);
         }
         snarl.drop_outputs(pin.id);
         self.changed = true;
@@ -620,9 +744,12 @@ impl SnarlViewer<SnarlNode> for NodeGraphViewer<'_> {
 
     fn drop_inputs(&mut self, pin: &egui_snarl::InPin, snarl: &mut Snarl<SnarlNode>) {
         if let Some(core_pin) = self.core_pin_for_input(snarl, pin.id) {
-            let _ = self.graph.remove_links_for_pin(core_pin);
+            disconnect_pin(self.graph, core_pin);
         }
         snarl.drop_inputs(pin.id);
+        if let Some(core_node) = self.core_node_id(snarl, pin.id.node) {
+            self.graph.compact_variadic_inputs(core_node);
+        }
         self.changed = true;
     }
 }
@@ -0,0 +1,96 @@
+// The library is named `core`; alias it so it doesn't shadow the standard `core`.
+extern crate core as grapho_core;
+
+use criterion::{black_box, criterion_group, criterion_main, BatchSize, Criterion};
+use glam::{Mat4, Quat, Vec3};
+use grapho_core::{
+    compute_mesh_node, default_params, make_grid, par_zip_mut, BuiltinNodeKind, Mesh,
+};
+
+// A 1000x1000 grid: ~1M points and ~2M triangles.
+fn dense_grid() -> Mesh {
+    let mut mesh = make_grid([10.0, 10.0], [999, 999]);
+    let _ = mesh.compute_normals();
+    mesh
+}
+
+fn bench_transform(c: &mut Criterion) {
+    let mesh = dense_grid();
+    let matrix = Mat4::from_scale_rotation_translation(
+        Vec3::splat(1.5),
+        Quat::from_rotation_y(0.3),
+        Vec3::new(1.0, 2.0, 3.0),
+    );
+    let mut group = c.benchmark_group("transform_1m");
+    group.sample_size(10);
+    group.bench_function("serial", |b| {
+        b.iter_batched_ref(
+            || mesh.positions.clone(),
+            |positions| {
+                for p in positions.iter_mut() {
+                    *p = matrix.transform_point3(Vec3::from(*p)).to_array();
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("parallel", |b| {
+        b.iter_batched_ref(
+            || mesh.clone(),
+            |mesh| mesh.transform(black_box(matrix)),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_normals(c: &mut Criterion) {
+    let mesh = dense_grid();
+    let mut group = c.benchmark_group("compute_normals_1m");
+    group.sample_size(10);
+    group.bench_function("parallel", |b| {
+        b.iter_batched_ref(
+            || mesh.clone(),
+            |mesh| mesh.compute_normals(),
+            BatchSize::LargeInput,
+        )
+    });
+    group.finish();
+}
+
+fn bench_noise(c: &mut Criterion) {
+    let mesh = dense_grid();
+    let params = default_params(BuiltinNodeKind::Noise);
+    let normals = mesh.normals.clone().unwrap_or_default();
+    let mut group = c.benchmark_group("noise_displace_1m");
+    group.sample_size(10);
+    group.bench_function("serial_zip", |b| {
+        b.iter_batched_ref(
+            || mesh.positions.clone(),
+            |positions| {
+                for (p, n) in positions.iter_mut().zip(&normals) {
+                    *p = (Vec3::from(*p) + Vec3::from(*n) * 0.1).to_array();
+                }
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("parallel_zip", |b| {
+        b.iter_batched_ref(
+            || mesh.positions.clone(),
+            |positions| {
+                par_zip_mut(positions, &normals, |p, n| {
+                    *p = (Vec3::from(*p) + Vec3::from(*n) * 0.1).to_array();
+                })
+            },
+            BatchSize::LargeInput,
+        )
+    });
+    group.bench_function("node", |b| {
+        b.iter(|| compute_mesh_node(BuiltinNodeKind::Noise, &params, std::slice::from_ref(&mesh)))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, bench_transform, bench_normals, bench_noise);
+criterion_main!(benches);
@@ -0,0 +1,53 @@
+// The library is named `core`; alias it so it doesn't shadow the standard `core`.
+extern crate core as grapho_core;
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use grapho_core::{Fbm, NoiseBasis};
+
+fn points(count: usize) -> Vec<[f32; 3]> {
+    (0..count)
+        .map(|i| {
+            let t = i as f32 * 0.0137;
+            [t.sin() * 10.0, t * 0.25, t.cos() * 10.0]
+        })
+        .collect()
+}
+
+fn bench_bases(c: &mut Criterion) {
+    let points = points(100_000);
+    let mut group = c.benchmark_group("noise_100k");
+    for (name, basis) in [
+        ("value", NoiseBasis::Value),
+        ("perlin", NoiseBasis::Perlin),
+        ("simplex", NoiseBasis::Simplex),
+        ("worley", NoiseBasis::Worley),
+    ] {
+        let fbm = Fbm {
+            basis,
+            octaves: 1,
+            ..Fbm::default()
+        };
+        group.bench_function(name, |b| {
+            b.iter(|| fbm.sample_points(black_box(&points), 1))
+        });
+    }
+    group.finish();
+}
+
+fn bench_fbm(c: &mut Criterion) {
+    let points = points(100_000);
+    let fbm = Fbm {
+        basis: NoiseBasis::Perlin,
+        octaves: 4,
+        ..Fbm::default()
+    };
+    c.bench_function("fbm_perlin_4_octaves_100k", |b| {
+        b.iter(|| fbm.sample_points(black_box(&points), 1))
+    });
+    c.bench_function("curl_perlin_4_octaves_100k", |b| {
+        b.iter(|| fbm.curl_points(black_box(&points), 1))
+    });
+}
+
+criterion_group!(benches, bench_bases, bench_fbm);
+criterion_main!(benches);
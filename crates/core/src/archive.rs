@@ -0,0 +1,330 @@
+use crate::attributes::{AttributeDomain, AttributePrecision, AttributeStorage};
+use crate::mesh::Mesh;
+
+const MAGIC: &[u8; 8] = b"GRAPHOAR";
+const ARCHIVE_VERSION: u32 = 1;
+const PROJECT_CHUNK: &[u8; 4] = b"PROJ";
+const MESH_CHUNK: &[u8; 4] = b"MESH";
+// Attribute domains by their index in the file.
+const DOMAINS: [AttributeDomain; 4] = [
+    AttributeDomain::Point,
+    AttributeDomain::Vertex,
+    AttributeDomain::Primitive,
+    AttributeDomain::Detail,
+];
+
+// Binary project file (`.grapho`): a header, then tagged chunks in the spirit of glTF's GLB.
+// The project stays JSON inside its chunk so it loads and migrates exactly like a .json
+// file; cooked meshes ride along as binary chunks, keyed by node id, so a pipeline can read
+// results without cooking. Readers skip chunk tags they do not know.
+#[derive(Debug, Clone, Default)]
+pub struct ProjectArchive {
+    pub project_json: Vec<u8>,
+    pub meshes: Vec<(u64, Mesh)>,
+}
+
+impl ProjectArchive {
+    pub fn is_archive(bytes: &[u8]) -> bool {
+        bytes.starts_with(MAGIC)
+    }
+
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        out.extend_from_slice(MAGIC);
+        write_u32(&mut out, ARCHIVE_VERSION);
+        write_chunk(&mut out, PROJECT_CHUNK, &self.project_json);
+        for (node, mesh) in &self.meshes {
+            let mut payload = Vec::new();
+            write_u64(&mut payload, *node);
+            encode_mesh(mesh, &mut payload);
+            write_chunk(&mut out, MESH_CHUNK, &payload);
+        }
+        out
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut reader = Reader { bytes };
+        if reader.take(MAGIC.len())? != MAGIC {
+            return Err("not a grapho archive".to_string());
+        }
+        let version = reader.u32()?;
+        if version > ARCHIVE_VERSION {
+            return Err(format!(
+                "archive version {} is newer than supported ({})",
+                version, ARCHIVE_VERSION
+            ));
+        }
+        let mut project_json = None;
+        let mut meshes = Vec::new();
+        while !reader.bytes.is_empty() {
+            let tag = reader.take(4)?;
+            let len = reader.len()?;
+            let mut chunk = Reader {
+                bytes: reader.take(len)?,
+            };
+            if tag == PROJECT_CHUNK {
+                project_json = Some(chunk.bytes.to_vec());
+            } else if tag == MESH_CHUNK {
+                let node = chunk.u64()?;
+                meshes.push((node, decode_mesh(&mut chunk)?));
+            }
+        }
+        Ok(Self {
+            project_json: project_json.ok_or_else(|| "archive has no project".to_string())?,
+            meshes,
+        })
+    }
+}
+
+fn write_chunk(out: &mut Vec<u8>, tag: &[u8; 4], payload: &[u8]) {
+    out.extend_from_slice(tag);
+    write_u64(out, payload.len() as u64);
+    out.extend_from_slice(payload);
+}
+
+fn write_u32(out: &mut Vec<u8>, value: u32) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_u64(out: &mut Vec<u8>, value: u64) {
+    out.extend_from_slice(&value.to_le_bytes());
+}
+
+fn write_floats<const N: usize>(out: &mut Vec<u8>, values: &[[f32; N]]) {
+    write_u64(out, values.len() as u64);
+    for value in values.iter().flatten() {
+        out.extend_from_slice(&value.to_le_bytes());
+    }
+}
+
+fn write_optional<const N: usize>(out: &mut Vec<u8>, values: Option<&Vec<[f32; N]>>) {
+    match values {
+        Some(values) => {
+            out.push(1);
+            write_floats(out, values);
+        }
+        None => out.push(0),
+    }
+}
+
+// Debug channels are not written; evaluation strips them from node outputs anyway.
+fn encode_mesh(mesh: &Mesh, out: &mut Vec<u8>) {
+    write_floats(out, &mesh.positions);
+    write_u64(out, mesh.indices.len() as u64);
+    for index in &mesh.indices {
+        write_u32(out, *index);
+    }
+    write_u64(out, mesh.face_counts.len() as u64);
+    for count in &mesh.face_counts {
+        write_u32(out, *count);
+    }
+    write_optional(out, mesh.normals.as_ref());
+    write_optional(out, mesh.corner_normals.as_ref());
+    write_optional(out, mesh.uvs.as_ref());
+
+    let mut attributes = Vec::new();
+    for (domain_index, domain) in DOMAINS.into_iter().enumerate() {
+        let mut names: Vec<&String> = mesh.attributes.map(domain).keys().collect();
+        names.sort();
+        for name in names {
+            attributes.push((domain_index as u8, domain, name));
+        }
+    }
+    write_u32(out, attributes.len() as u32);
+    for (domain_index, domain, name) in attributes {
+        out.push(domain_index);
+        write_u32(out, name.len() as u32);
+        out.extend_from_slice(name.as_bytes());
+        out.push(match mesh.attributes.precision(domain, name) {
+            AttributePrecision::Full => 0,
+            AttributePrecision::Half => 1,
+            AttributePrecision::Unorm8 => 2,
+        });
+        match &mesh.attributes.map(domain)[name] {
+            AttributeStorage::Float(values) => {
+                out.push(0);
+                let values: Vec<[f32; 1]> = values.iter().map(|value| [*value]).collect();
+                write_floats(out, &values);
+            }
+            AttributeStorage::Int(values) => {
+                out.push(1);
+                write_u64(out, values.len() as u64);
+                for value in values {
+                    out.extend_from_slice(&value.to_le_bytes());
+                }
+            }
+            AttributeStorage::Vec2(values) => {
+                out.push(2);
+                write_floats(out, values);
+            }
+            AttributeStorage::Vec3(values) => {
+                out.push(3);
+                write_floats(out, values);
+            }
+            AttributeStorage::Vec4(values) => {
+                out.push(4);
+                write_floats(out, values);
+            }
+        }
+    }
+}
+
+fn decode_mesh(reader: &mut Reader<'_>) -> Result<Mesh, String> {
+    let positions = reader.floats::<3>()?;
+    let indices = reader.u32s()?;
+    let face_counts = reader.u32s()?;
+    let mut mesh = Mesh::with_positions_indices(positions, indices);
+    mesh.face_counts = face_counts;
+    mesh.normals = reader.optional::<3>()?;
+    mesh.corner_normals = reader.optional::<3>()?;
+    mesh.uvs = reader.optional::<2>()?;
+
+    for _ in 0..reader.u32()? {
+        let domain = *DOMAINS
+            .get(reader.u8()? as usize)
+            .ok_or_else(|| "invalid attribute domain".to_string())?;
+        let name_len = reader.u32()? as usize;
+        let name = String::from_utf8(reader.take(name_len)?.to_vec())
+            .map_err(|_| "invalid attribute name".to_string())?;
+        let precision = AttributePrecision::from_index(reader.u8()? as i32);
+        let storage = match reader.u8()? {
+            0 => AttributeStorage::Float(reader.floats::<1>()?.into_iter().map(|[v]| v).collect()),
+            1 => {
+                let count = reader.len()?;
+                let bytes = reader.take(count.checked_mul(4).ok_or("attribute too large")?)?;
+                AttributeStorage::Int(
+                    bytes
+                        .chunks_exact(4)
+                        .map(|chunk| i32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+                        .collect(),
+                )
+            }
+            2 => AttributeStorage::Vec2(reader.floats::<2>()?),
+            3 => AttributeStorage::Vec3(reader.floats::<3>()?),
+            4 => AttributeStorage::Vec4(reader.floats::<4>()?),
+            other => return Err(format!("invalid attribute type {}", other)),
+        };
+        // Written straight into the maps: the stored mesh was valid when it was cooked.
+        mesh.attributes
+            .map_mut(domain)
+            .insert(name.clone(), storage);
+        mesh.attributes.set_precision(domain, &name, precision);
+    }
+    Ok(mesh)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, count: usize) -> Result<&'a [u8], String> {
+        if count > self.bytes.len() {
+            return Err("archive is truncated".to_string());
+        }
+        let (head, rest) = self.bytes.split_at(count);
+        self.bytes = rest;
+        Ok(head)
+    }
+
+    fn u8(&mut self) -> Result<u8, String> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u32(&mut self) -> Result<u32, String> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    fn u64(&mut self) -> Result<u64, String> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn len(&mut self) -> Result<usize, String> {
+        usize::try_from(self.u64()?).map_err(|_| "archive length out of range".to_string())
+    }
+
+    fn u32s(&mut self) -> Result<Vec<u32>, String> {
+        let count = self.len()?;
+        let bytes = self.take(count.checked_mul(4).ok_or("archive length out of range")?)?;
+        Ok(bytes
+            .chunks_exact(4)
+            .map(|chunk| u32::from_le_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]))
+            .collect())
+    }
+
+    fn floats<const N: usize>(&mut self) -> Result<Vec<[f32; N]>, String> {
+        let count = self.len()?;
+        let size = count
+            .checked_mul(N * 4)
+            .ok_or("archive length out of range")?;
+        let bytes = self.take(size)?;
+        Ok(bytes
+            .chunks_exact(N * 4)
+            .map(|item| {
+                std::array::from_fn(|i| {
+                    let b = &item[i * 4..i * 4 + 4];
+                    f32::from_le_bytes([b[0], b[1], b[2], b[3]])
+                })
+            })
+            .collect())
+    }
+
+    fn optional<const N: usize>(&mut self) -> Result<Option<Vec<[f32; N]>>, String> {
+        match self.u8()? {
+            0 => Ok(None),
+            _ => self.floats::<N>().map(Some),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::content_hash::mesh_content_hash;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn archive_round_trips_project_and_meshes() {
+        let mut mesh = make_box([1.0, 2.0, 3.0]);
+        mesh.uvs = Some(vec![[0.5, 0.25]; mesh.indices.len()]);
+        let points = mesh.positions.len();
+        mesh.set_attribute(
+            AttributeDomain::Point,
+            "Cd",
+            AttributeStorage::Vec3(vec![[0.2, 0.4, 0.6]; points]),
+        )
+        .unwrap();
+        mesh.set_attribute(
+            AttributeDomain::Detail,
+            "id",
+            AttributeStorage::Int(vec![-7]),
+        )
+        .unwrap();
+        mesh.attributes
+            .set_precision(AttributeDomain::Point, "Cd", AttributePrecision::Half);
+        let archive = ProjectArchive {
+            project_json: br#"{"version":1}"#.to_vec(),
+            meshes: vec![(42, mesh.clone())],
+        };
+
+        let bytes = archive.to_bytes();
+        assert!(ProjectArchive::is_archive(&bytes));
+        let read = ProjectArchive::from_bytes(&bytes).unwrap();
+        assert_eq!(read.project_json, archive.project_json);
+        assert_eq!(read.meshes.len(), 1);
+        assert_eq!(read.meshes[0].0, 42);
+        let restored = &read.meshes[0].1;
+        assert_eq!(mesh_content_hash(restored), mesh_content_hash(&mesh));
+        assert_eq!(restored.uvs, mesh.uvs);
+        assert_eq!(
+            restored.attributes.precision(AttributeDomain::Point, "Cd"),
+            AttributePrecision::Half
+        );
+
+        assert!(ProjectArchive::from_bytes(&bytes[..bytes.len() - 3]).is_err());
+        assert!(ProjectArchive::from_bytes(br#"{"version":1}"#).is_err());
+    }
+}
@@ -26,6 +26,68 @@ pub enum AttributeType {
     Vec4,
 }
 
+// Storage precision an attribute is rounded to; values stay f32 in memory so every
+// operator can read them, but they only carry what the reduced format can hold.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum AttributePrecision {
+    #[default]
+    Full,
+    Half,
+    Unorm8,
+}
+
+const HALF_MAX: f32 = 65504.0;
+const HALF_MIN_NORMAL: f32 = 6.103_515_6e-5;
+const HALF_SUBNORMAL_STEP: f32 = 5.960_464_5e-8;
+
+impl AttributePrecision {
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => AttributePrecision::Half,
+            2 => AttributePrecision::Unorm8,
+            _ => AttributePrecision::Full,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            AttributePrecision::Full => "f32",
+            AttributePrecision::Half => "f16",
+            AttributePrecision::Unorm8 => "u8",
+        }
+    }
+
+    pub fn component_bytes(self) -> usize {
+        match self {
+            AttributePrecision::Full => 4,
+            AttributePrecision::Half => 2,
+            AttributePrecision::Unorm8 => 1,
+        }
+    }
+
+    pub fn quantize(self, value: f32) -> f32 {
+        match self {
+            AttributePrecision::Full => value,
+            AttributePrecision::Half => round_to_half(value),
+            AttributePrecision::Unorm8 => (value.clamp(0.0, 1.0) * 255.0).round() / 255.0,
+        }
+    }
+}
+
+fn round_to_half(value: f32) -> f32 {
+    if !value.is_finite() {
+        return value;
+    }
+    let magnitude = value.abs();
+    if magnitude < HALF_MIN_NORMAL {
+        return (value / HALF_SUBNORMAL_STEP).round() * HALF_SUBNORMAL_STEP;
+    }
+    // Round to nearest even on the 10 mantissa bits a half float keeps.
+    let bits = value.to_bits();
+    let rounded = f32::from_bits((bits + 0x0fff + ((bits >> 13) & 1)) & !0x1fff);
+    rounded.clamp(-HALF_MAX, HALF_MAX)
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub enum AttributeStorage {
     Float(Vec<f32>),
@@ -60,6 +122,36 @@ impl AttributeStorage {
         }
     }
 
+    pub fn gather(&self, sources: &[usize]) -> AttributeStorage {
+        fn pick<T: Copy + Default>(values: &[T], sources: &[usize]) -> Vec<T> {
+            sources
+                .iter()
+                .map(|&idx| values.get(idx).copied().unwrap_or_default())
+                .collect()
+        }
+        match self {
+            AttributeStorage::Float(values) => AttributeStorage::Float(pick(values, sources)),
+            AttributeStorage::Int(values) => AttributeStorage::Int(pick(values, sources)),
+            AttributeStorage::Vec2(values) => AttributeStorage::Vec2(pick(values, sources)),
+            AttributeStorage::Vec3(values) => AttributeStorage::Vec3(pick(values, sources)),
+            AttributeStorage::Vec4(values) => AttributeStorage::Vec4(pick(values, sources)),
+        }
+    }
+
+    pub fn quantize(&mut self, precision: AttributePrecision) {
+        if precision == AttributePrecision::Full {
+            return;
+        }
+        let round = |value: &mut f32| *value = precision.quantize(*value);
+        match self {
+            AttributeStorage::Float(values) => values.iter_mut().for_each(round),
+            AttributeStorage::Int(_) => {}
+            AttributeStorage::Vec2(values) => values.iter_mut().flatten().for_each(round),
+            AttributeStorage::Vec3(values) => values.iter_mut().flatten().for_each(round),
+            AttributeStorage::Vec4(values) => values.iter_mut().flatten().for_each(round),
+        }
+    }
+
     pub fn as_ref(&self) -> AttributeRef<'_> {
         match self {
             AttributeStorage::Float(values) => AttributeRef::Float(values.as_slice()),
@@ -91,6 +183,7 @@ pub struct AttributeInfo {
     pub data_type: AttributeType,
     pub len: usize,
     pub implicit: bool,
+    pub precision: AttributePrecision,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -134,6 +227,7 @@ pub struct MeshAttributes {
     vertex: HashMap<String, AttributeStorage>,
     primitive: HashMap<String, AttributeStorage>,
     detail: HashMap<String, AttributeStorage>,
+    precision: HashMap<(AttributeDomain, String), AttributePrecision>,
 }
 
 impl MeshAttributes {
@@ -160,6 +254,30 @@ impl MeshAttributes {
     }
 
     pub fn remove(&mut self, domain: AttributeDomain, name: &str) -> Option<AttributeStorage> {
+        self.precision.remove(&(domain, name.to_string()));
         self.map_mut(domain).remove(name)
     }
+
+    pub fn precision(&self, domain: AttributeDomain, name: &str) -> AttributePrecision {
+        self.precision
+            .get(&(domain, name.to_string()))
+            .copied()
+            .unwrap_or_default()
+    }
+
+    pub fn set_precision(
+        &mut self,
+        domain: AttributeDomain,
+        name: &str,
+        precision: AttributePrecision,
+    ) {
+        if let Some(storage) = self.map_mut(domain).get_mut(name) {
+            storage.quantize(precision);
+        }
+        if precision == AttributePrecision::Full {
+            self.precision.remove(&(domain, name.to_string()));
+        } else {
+            self.precision.insert((domain, name.to_string()), precision);
+        }
+    }
 }
@@ -0,0 +1,153 @@
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::bvh::Bvh;
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone)]
+pub struct BakeAoSettings {
+    pub samples: u32,
+    pub max_distance: f32,
+    pub light_mix: f32,
+    pub light_dir: [f32; 3],
+    pub attr: String,
+}
+
+pub fn bake_ao(input: &Mesh, settings: &BakeAoSettings) -> Result<Mesh, String> {
+    let attr = settings.attr.trim();
+    if attr.is_empty() {
+        return Err("Bake AO requires an attribute name".to_string());
+    }
+    let mut mesh = input.clone();
+    if mesh.normals.is_none() && !mesh.compute_normals() {
+        return Err("Bake AO requires a polygon mesh input".to_string());
+    }
+    let normals = mesh.normals.clone().unwrap_or_default();
+    let extent = mesh
+        .bounds()
+        .map(|bounds| (Vec3::from(bounds.max) - Vec3::from(bounds.min)).length())
+        .unwrap_or(1.0)
+        .max(1.0e-6);
+    let max_distance = if settings.max_distance > 0.0 {
+        settings.max_distance
+    } else {
+        f32::INFINITY
+    };
+    let bias = extent * 1.0e-4;
+    let samples = settings.samples.clamp(1, 1024);
+    let light_mix = settings.light_mix.clamp(0.0, 1.0);
+    let light_dir = Vec3::from(settings.light_dir).normalize_or_zero();
+    let bvh = Bvh::build(&mesh);
+
+    let colors = mesh
+        .positions
+        .iter()
+        .zip(normals.iter())
+        .enumerate()
+        .map(|(point, (position, normal))| {
+            let normal = Vec3::from(*normal).normalize_or_zero();
+            if normal == Vec3::ZERO {
+                return [1.0; 3];
+            }
+            let origin = Vec3::from(*position) + normal * bias;
+            let (tangent, bitangent) = normal.any_orthonormal_pair();
+            // Rotate the sample pattern per point so the banding turns into noise.
+            let rotation = hash_unit(point as u32) * std::f32::consts::TAU;
+            let open = (0..samples)
+                .filter(|sample| {
+                    let (u, v) = hammersley(*sample, samples);
+                    let phi = u * std::f32::consts::TAU + rotation;
+                    let radius = v.sqrt();
+                    let dir = tangent * (radius * phi.cos())
+                        + bitangent * (radius * phi.sin())
+                        + normal * (1.0 - v).max(0.0).sqrt();
+                    !bvh.occluded(origin, dir, max_distance)
+                })
+                .count();
+            let mut value = open as f32 / samples as f32;
+            if light_mix > 0.0 && light_dir != Vec3::ZERO {
+                let lit = if bvh.occluded(origin, light_dir, f32::INFINITY) {
+                    0.0
+                } else {
+                    normal.dot(light_dir).max(0.0)
+                };
+                value *= 1.0 - light_mix + light_mix * lit;
+            }
+            [value; 3]
+        })
+        .collect();
+
+    mesh.set_attribute(AttributeDomain::Point, attr, AttributeStorage::Vec3(colors))
+        .map_err(|err| format!("Bake AO attribute error: {:?}", err))?;
+    Ok(mesh)
+}
+
+fn hammersley(index: u32, count: u32) -> (f32, f32) {
+    let u = (index as f32 + 0.5) / count as f32;
+    let v = index.reverse_bits() as f32 / 4_294_967_296.0;
+    (u, v)
+}
+
+fn hash_unit(value: u32) -> f32 {
+    let mut x = value.wrapping_mul(0x9E37_79B9) ^ 0x85EB_CA6B;
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x as f32 / u32::MAX as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Mat4, Vec3};
+
+    use super::{bake_ao, BakeAoSettings};
+    use crate::attributes::{AttributeDomain, AttributeRef};
+    use crate::mesh::{make_box, make_grid, Mesh};
+
+    #[test]
+    fn bake_ao_darkens_points_under_geometry() {
+        let mut block = make_box([1.0, 1.0, 1.0]);
+        block.transform(Mat4::from_translation(Vec3::new(0.0, 0.6, 0.0)));
+        let scene = Mesh::merge(&[make_grid([6.0, 6.0], [6, 6]), block]);
+        let settings = BakeAoSettings {
+            samples: 64,
+            max_distance: 0.0,
+            light_mix: 0.0,
+            light_dir: [0.0, 1.0, 0.0],
+            attr: "Cd".to_string(),
+        };
+        let baked = bake_ao(&scene, &settings).unwrap();
+        let Some(AttributeRef::Vec3(colors)) = baked.attribute(AttributeDomain::Point, "Cd") else {
+            panic!("missing Cd");
+        };
+        let value_at = |target: [f32; 3]| {
+            let index = baked
+                .positions
+                .iter()
+                .position(|p| Vec3::from(*p).distance(Vec3::from(target)) < 1.0e-4)
+                .unwrap();
+            colors[index][0]
+        };
+        let center = value_at([0.0, 0.0, 0.0]);
+        let corner = value_at([3.0, 0.0, 3.0]);
+        assert!(center < 0.5, "center {}", center);
+        assert!(corner > 0.9, "corner {}", corner);
+
+        let lit = bake_ao(
+            &scene,
+            &BakeAoSettings {
+                light_mix: 1.0,
+                ..settings
+            },
+        )
+        .unwrap();
+        let Some(AttributeRef::Vec3(lit_colors)) = lit.attribute(AttributeDomain::Point, "Cd")
+        else {
+            panic!("missing Cd");
+        };
+        assert!(lit_colors
+            .iter()
+            .zip(colors)
+            .all(|(a, b)| a[0] <= b[0] + 1.0e-6));
+    }
+}
@@ -0,0 +1,360 @@
+use std::collections::{HashMap, HashSet};
+
+use glam::Vec3;
+
+use crate::attributes::AttributeDomain;
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone)]
+pub struct BevelSettings {
+    pub width: f32,
+    pub segments: usize,
+    pub angle_deg: f32,
+    pub group: String,
+}
+
+pub fn bevel(source: &Mesh, settings: &BevelSettings) -> Result<Mesh, String> {
+    if !source.has_valid_faces() {
+        return Err("Bevel requires a polygon mesh input".to_string());
+    }
+    let triangulated = source.triangulate();
+    let input = &triangulated;
+    if input
+        .indices
+        .iter()
+        .any(|idx| *idx as usize >= input.positions.len())
+    {
+        return Err("Bevel input has out of range indices".to_string());
+    }
+    let width = settings.width.max(0.0);
+    let segments = settings.segments.max(1);
+    if width <= 0.0 {
+        return Ok(source.clone());
+    }
+
+    let tri_count = input.indices.len() / 3;
+    let position = |idx: u32| Vec3::from(input.positions[idx as usize]);
+    let corner_vertex = |corner: usize| input.indices[corner];
+    let face_normals: Vec<Vec3> = (0..tri_count)
+        .map(|face| {
+            let p0 = position(input.indices[face * 3]);
+            let p1 = position(input.indices[face * 3 + 1]);
+            let p2 = position(input.indices[face * 3 + 2]);
+            (p1 - p0).cross(p2 - p0).normalize_or_zero()
+        })
+        .collect();
+
+    let mut directed: HashMap<(u32, u32), usize> = HashMap::new();
+    for face in 0..tri_count {
+        for corner in 0..3 {
+            let a = input.indices[face * 3 + corner];
+            let b = input.indices[face * 3 + (corner + 1) % 3];
+            directed.insert((a, b), face * 3 + corner);
+        }
+    }
+
+    let point_group = if settings.group.trim().is_empty() {
+        None
+    } else {
+        match input.group_mask(AttributeDomain::Point, settings.group.trim()) {
+            Some(mask) => Some(mask),
+            None => {
+                tracing::warn!(
+                    "Bevel: point group '{}' not found; passing input through",
+                    settings.group
+                );
+                return Ok(source.clone());
+            }
+        }
+    };
+    let cos_threshold = settings.angle_deg.clamp(0.0, 180.0).to_radians().cos();
+
+    let mut beveled: HashSet<(u32, u32)> = HashSet::new();
+    for (&(a, b), &corner) in &directed {
+        if a >= b {
+            continue;
+        }
+        let Some(&other) = directed.get(&(b, a)) else {
+            continue;
+        };
+        let selected = match &point_group {
+            Some(mask) => {
+                mask.get(a as usize).copied().unwrap_or(false)
+                    && mask.get(b as usize).copied().unwrap_or(false)
+            }
+            None => face_normals[corner / 3].dot(face_normals[other / 3]) < cos_threshold,
+        };
+        if selected {
+            beveled.insert((a, b));
+        }
+    }
+    if beveled.is_empty() {
+        return Ok(source.clone());
+    }
+    let is_beveled = |a: u32, b: u32| beveled.contains(&(a.min(b), a.max(b)));
+    let next_corner = |corner: usize| corner - corner % 3 + (corner + 1) % 3;
+    let prev_corner = |corner: usize| corner - corner % 3 + (corner + 2) % 3;
+
+    let mut beveled_valence: HashMap<u32, usize> = HashMap::new();
+    for &(a, b) in &beveled {
+        *beveled_valence.entry(a).or_insert(0) += 1;
+        *beveled_valence.entry(b).or_insert(0) += 1;
+    }
+
+    let mut parent: Vec<usize> = (0..input.indices.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+    for corner in 0..input.indices.len() {
+        let v = corner_vertex(corner);
+        if !beveled_valence.contains_key(&v) {
+            continue;
+        }
+        let n = corner_vertex(next_corner(corner));
+        if is_beveled(v, n) {
+            continue;
+        }
+        if let Some(&reverse) = directed.get(&(n, v)) {
+            let other = next_corner(reverse);
+            let a = find(&mut parent, corner);
+            let b = find(&mut parent, other);
+            if a != b {
+                parent[a] = b;
+            }
+        }
+    }
+
+    let mut sector_members: HashMap<usize, Vec<usize>> = HashMap::new();
+    for corner in 0..input.indices.len() {
+        if beveled_valence.contains_key(&corner_vertex(corner)) {
+            let root = find(&mut parent, corner);
+            sector_members.entry(root).or_default().push(corner);
+        }
+    }
+
+    let mut positions = input.positions.clone();
+    let mut point_sources: Vec<usize> = (0..input.positions.len()).collect();
+    let mut sector_vertex: HashMap<usize, u32> = HashMap::new();
+    let mut roots: Vec<usize> = sector_members.keys().copied().collect();
+    roots.sort_unstable();
+    for root in roots {
+        let members = &sector_members[&root];
+        let v = corner_vertex(root);
+        let origin = position(v);
+        let mut dirs: Vec<(u32, Vec3)> = Vec::new();
+        let mut normal = Vec3::ZERO;
+        let mut interior = Vec3::ZERO;
+        for &corner in members {
+            normal += face_normals[corner / 3];
+            for neighbor in [
+                corner_vertex(next_corner(corner)),
+                corner_vertex(prev_corner(corner)),
+            ] {
+                interior += position(neighbor) - origin;
+                if is_beveled(v, neighbor) && !dirs.iter().any(|(n, _)| *n == neighbor) {
+                    dirs.push((neighbor, (position(neighbor) - origin).normalize_or_zero()));
+                }
+            }
+        }
+        let offset = if dirs.len() == 2 {
+            let u1 = dirs[0].1;
+            let u2 = dirs[1].1;
+            let sin = u1.cross(u2).length();
+            let mut offset = if sin > 0.05 {
+                (u1 + u2) * (width / sin)
+            } else {
+                normal.normalize_or_zero().cross(u1) * width
+            };
+            if offset.dot(interior) < 0.0 {
+                offset = -offset;
+            }
+            offset
+        } else {
+            Vec3::ZERO
+        };
+        let index = positions.len() as u32;
+        positions.push((origin + offset).to_array());
+        point_sources.push(v as usize);
+        for &corner in members {
+            sector_vertex.insert(corner, index);
+        }
+    }
+
+    let mut indices = Vec::with_capacity(input.indices.len());
+    let mut corner_sources = Vec::with_capacity(input.indices.len());
+    let mut prim_sources = Vec::with_capacity(tri_count);
+    for corner in 0..input.indices.len() {
+        indices.push(
+            sector_vertex
+                .get(&corner)
+                .copied()
+                .unwrap_or(input.indices[corner]),
+        );
+        corner_sources.push(corner);
+        if corner % 3 == 0 {
+            prim_sources.push(corner / 3);
+        }
+    }
+
+    let mut profiles: HashMap<(u32, u32, u32), Vec<u32>> = HashMap::new();
+    let mut profile = |positions: &mut Vec<[f32; 3]>,
+                       point_sources: &mut Vec<usize>,
+                       v: u32,
+                       from: u32,
+                       to: u32|
+     -> Vec<u32> {
+        if let Some(existing) = profiles.get(&(v, from, to)) {
+            return existing.clone();
+        }
+        let start = Vec3::from(positions[from as usize]);
+        let end = Vec3::from(positions[to as usize]);
+        let control = Vec3::from(input.positions[v as usize]);
+        let mut row = Vec::with_capacity(segments + 1);
+        row.push(from);
+        for step in 1..segments {
+            let t = step as f32 / segments as f32;
+            let s = 1.0 - t;
+            let p = start * (s * s) + control * (2.0 * s * t) + end * (t * t);
+            row.push(positions.len() as u32);
+            positions.push(p.to_array());
+            point_sources.push(v as usize);
+        }
+        row.push(to);
+        let mut reversed = row.clone();
+        reversed.reverse();
+        profiles.insert((v, to, from), reversed);
+        profiles.insert((v, from, to), row.clone());
+        row
+    };
+
+    let mut edges: Vec<(u32, u32)> = beveled.iter().copied().collect();
+    edges.sort_unstable();
+    for (lo, hi) in edges {
+        let (a, b, c1) = match directed.get(&(lo, hi)) {
+            Some(&corner) => (lo, hi, corner),
+            None => continue,
+        };
+        let Some(&c2) = directed.get(&(b, a)) else {
+            continue;
+        };
+        let a1 = sector_vertex.get(&c1).copied().unwrap_or(a);
+        let b1 = sector_vertex.get(&next_corner(c1)).copied().unwrap_or(b);
+        let b2 = sector_vertex.get(&c2).copied().unwrap_or(b);
+        let a2 = sector_vertex.get(&next_corner(c2)).copied().unwrap_or(a);
+        let row_a = profile(&mut positions, &mut point_sources, a, a1, a2);
+        let row_b = profile(&mut positions, &mut point_sources, b, b1, b2);
+        let face = c1 / 3;
+        for step in 0..segments {
+            let quad = [row_b[step], row_a[step], row_a[step + 1], row_b[step + 1]];
+            let sources = [next_corner(c1), c1, c1, next_corner(c1)];
+            for tri in [[0, 1, 2], [0, 2, 3]] {
+                let ids = [quad[tri[0]], quad[tri[1]], quad[tri[2]]];
+                if ids[0] == ids[1] || ids[1] == ids[2] || ids[0] == ids[2] {
+                    continue;
+                }
+                indices.extend_from_slice(&ids);
+                corner_sources.extend_from_slice(&[
+                    sources[tri[0]],
+                    sources[tri[1]],
+                    sources[tri[2]],
+                ]);
+                prim_sources.push(face);
+            }
+        }
+    }
+
+    let mut corner_vertices: Vec<u32> = beveled_valence
+        .iter()
+        .filter(|(_, count)| **count >= 3)
+        .map(|(v, _)| *v)
+        .collect();
+    corner_vertices.sort_unstable();
+    let mut vertex_corners: HashMap<u32, Vec<usize>> = HashMap::new();
+    for corner in 0..input.indices.len() {
+        let v = corner_vertex(corner);
+        if beveled_valence.get(&v).is_some_and(|count| *count >= 3) {
+            vertex_corners.entry(v).or_default().push(corner);
+        }
+    }
+    for v in corner_vertices {
+        let Some(around) = vertex_corners.get(&v) else {
+            continue;
+        };
+        let start = around[0];
+        let mut ring: Vec<u32> = Vec::new();
+        let mut corner = start;
+        let mut closed = false;
+        for _ in 0..input.indices.len() {
+            let current = sector_vertex.get(&corner).copied().unwrap_or(v);
+            if ring.last() != Some(&current) {
+                ring.push(current);
+            }
+            let n = corner_vertex(next_corner(corner));
+            let Some(&reverse) = directed.get(&(n, v)) else {
+                break;
+            };
+            let next = next_corner(reverse);
+            if is_beveled(v, n) {
+                let target = sector_vertex.get(&next).copied().unwrap_or(v);
+                let row = profile(&mut positions, &mut point_sources, v, current, target);
+                ring.extend(row.iter().skip(1).take(row.len().saturating_sub(2)));
+            }
+            corner = next;
+            if corner == start {
+                closed = true;
+                break;
+            }
+        }
+        if ring.len() > 1 && ring.first() == ring.last() {
+            ring.pop();
+        }
+        if !closed || ring.len() < 3 {
+            continue;
+        }
+
+        let origin = position(v);
+        let mut center = Vec3::ZERO;
+        for idx in &ring {
+            center += Vec3::from(positions[*idx as usize]);
+        }
+        center /= ring.len() as f32;
+        if segments > 1 {
+            center = center.lerp(origin, 0.5);
+        }
+        let center_index = positions.len() as u32;
+        positions.push(center.to_array());
+        point_sources.push(v as usize);
+
+        let vertex_normal: Vec3 = around.iter().map(|c| face_normals[c / 3]).sum();
+        let face_corner = start;
+        let p0 = Vec3::from(positions[ring[0] as usize]);
+        let p1 = Vec3::from(positions[ring[1] as usize]);
+        let flip = (p0 - center).cross(p1 - center).dot(vertex_normal) < 0.0;
+        for i in 0..ring.len() {
+            let a = ring[i];
+            let b = ring[(i + 1) % ring.len()];
+            if flip {
+                indices.extend_from_slice(&[center_index, b, a]);
+            } else {
+                indices.extend_from_slice(&[center_index, a, b]);
+            }
+            corner_sources.extend_from_slice(&[face_corner, face_corner, face_corner]);
+            prim_sources.push(face_corner / 3);
+        }
+    }
+
+    let mut mesh = input.rebuild_from_sources(
+        positions,
+        indices,
+        &point_sources,
+        &corner_sources,
+        &prim_sources,
+    );
+    mesh.corner_normals = None;
+    mesh.compute_normals();
+    Ok(mesh)
+}
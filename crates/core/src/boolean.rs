@@ -0,0 +1,475 @@
+use std::collections::HashMap;
+
+use glam::DVec3;
+
+use crate::mesh::Mesh;
+
+// Distance under which a point counts as lying on a splitting plane.
+const PLANE_EPSILON: f64 = 1.0e-5;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BooleanMode {
+    Union,
+    Difference,
+    Intersect,
+}
+
+impl BooleanMode {
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => BooleanMode::Difference,
+            2 => BooleanMode::Intersect,
+            _ => BooleanMode::Union,
+        }
+    }
+}
+
+// Combines two closed meshes with a BSP tree per input, after the classic csg.js scheme.
+// Open or self-intersecting inputs give holes or stray faces rather than an error. Output
+// faces keep their source face's primitive attributes; points and corners take the values of
+// the nearest source corner, which is exact for uncut faces and approximate on cut ones.
+pub fn mesh_boolean(a: &Mesh, b: &Mesh, mode: BooleanMode) -> Result<Mesh, String> {
+    if !a.has_valid_faces() || !b.has_valid_faces() {
+        return Err("Boolean requires polygon mesh inputs".to_string());
+    }
+    let source = Mesh::merge(&[a.clone(), b.clone()]);
+    let a_faces = a.face_count();
+    let faces = source.face_ranges();
+    let polygons = mesh_polygons(&source, &faces);
+    let (a_polygons, b_polygons): (Vec<Polygon>, Vec<Polygon>) = polygons
+        .into_iter()
+        .partition(|polygon| polygon.source < a_faces);
+
+    let mut a_tree = BspTree::new(a_polygons);
+    let mut b_tree = BspTree::new(b_polygons);
+    match mode {
+        BooleanMode::Union => {
+            a_tree.clip_to(&b_tree);
+            b_tree.clip_to(&a_tree);
+            b_tree.invert();
+            b_tree.clip_to(&a_tree);
+            b_tree.invert();
+            a_tree.insert(b_tree.into_polygons());
+        }
+        BooleanMode::Difference => {
+            a_tree.invert();
+            a_tree.clip_to(&b_tree);
+            b_tree.clip_to(&a_tree);
+            b_tree.invert();
+            b_tree.clip_to(&a_tree);
+            b_tree.invert();
+            a_tree.insert(b_tree.into_polygons());
+            a_tree.invert();
+        }
+        BooleanMode::Intersect => {
+            a_tree.invert();
+            b_tree.clip_to(&a_tree);
+            b_tree.invert();
+            a_tree.clip_to(&b_tree);
+            b_tree.clip_to(&a_tree);
+            a_tree.insert(b_tree.into_polygons());
+            a_tree.invert();
+        }
+    }
+    Ok(polygons_to_mesh(&source, &faces, a_tree.into_polygons()))
+}
+
+#[derive(Clone, Copy)]
+struct Plane {
+    normal: DVec3,
+    w: f64,
+}
+
+impl Plane {
+    // Newell's method, so concave and slightly non-planar faces still get a stable normal.
+    fn from_points(points: &[DVec3]) -> Option<Self> {
+        let mut normal = DVec3::ZERO;
+        for (i, p) in points.iter().enumerate() {
+            let q = points[(i + 1) % points.len()];
+            normal.x += (p.y - q.y) * (p.z + q.z);
+            normal.y += (p.z - q.z) * (p.x + q.x);
+            normal.z += (p.x - q.x) * (p.y + q.y);
+        }
+        let normal = normal.try_normalize()?;
+        let centroid = points.iter().copied().sum::<DVec3>() / points.len() as f64;
+        Some(Self {
+            normal,
+            w: normal.dot(centroid),
+        })
+    }
+
+    fn flipped(self) -> Self {
+        Self {
+            normal: -self.normal,
+            w: -self.w,
+        }
+    }
+
+    fn distance(&self, point: DVec3) -> f64 {
+        self.normal.dot(point) - self.w
+    }
+}
+
+#[derive(Clone)]
+struct Polygon {
+    points: Vec<DVec3>,
+    plane: Plane,
+    // Face index in the merged source mesh.
+    source: usize,
+}
+
+impl Polygon {
+    fn flip(&mut self) {
+        self.points.reverse();
+        self.plane = self.plane.flipped();
+    }
+}
+
+const COPLANAR: u8 = 0;
+const FRONT: u8 = 1;
+const BACK: u8 = 2;
+const SPANNING: u8 = 3;
+
+// Sorts `polygon` against `plane`. Coplanar polygons go to `coplanar_front` or
+// `coplanar_back` by facing; spanning ones are cut in two.
+fn split_polygon(
+    plane: &Plane,
+    polygon: Polygon,
+    coplanar_front: &mut Vec<Polygon>,
+    coplanar_back: &mut Vec<Polygon>,
+    front: &mut Vec<Polygon>,
+    back: &mut Vec<Polygon>,
+) {
+    let sides: Vec<u8> = polygon
+        .points
+        .iter()
+        .map(|point| {
+            let distance = plane.distance(*point);
+            if distance < -PLANE_EPSILON {
+                BACK
+            } else if distance > PLANE_EPSILON {
+                FRONT
+            } else {
+                COPLANAR
+            }
+        })
+        .collect();
+    match sides.iter().fold(COPLANAR, |acc, side| acc | side) {
+        COPLANAR => {
+            if plane.normal.dot(polygon.plane.normal) > 0.0 {
+                coplanar_front.push(polygon);
+            } else {
+                coplanar_back.push(polygon);
+            }
+        }
+        FRONT => front.push(polygon),
+        BACK => back.push(polygon),
+        _ => {
+            let count = polygon.points.len();
+            let mut front_points = Vec::with_capacity(count + 1);
+            let mut back_points = Vec::with_capacity(count + 1);
+            for i in 0..count {
+                let j = (i + 1) % count;
+                let (side_i, side_j) = (sides[i], sides[j]);
+                let (p, q) = (polygon.points[i], polygon.points[j]);
+                if side_i != BACK {
+                    front_points.push(p);
+                }
+                if side_i != FRONT {
+                    back_points.push(p);
+                }
+                if side_i | side_j == SPANNING {
+                    let t = (plane.w - plane.normal.dot(p)) / plane.normal.dot(q - p);
+                    let cut = p.lerp(q, t);
+                    front_points.push(cut);
+                    back_points.push(cut);
+                }
+            }
+            if front_points.len() >= 3 {
+                front.push(Polygon {
+                    points: front_points,
+                    plane: polygon.plane,
+                    source: polygon.source,
+                });
+            }
+            if back_points.len() >= 3 {
+                back.push(Polygon {
+                    points: back_points,
+                    plane: polygon.plane,
+                    source: polygon.source,
+                });
+            }
+        }
+    }
+}
+
+struct BspNode {
+    plane: Plane,
+    front: Option<usize>,
+    back: Option<usize>,
+    polygons: Vec<Polygon>,
+}
+
+// Nodes live in a flat list and every walk uses an explicit stack: a tree over a dense
+// convex mesh is as deep as it has faces.
+struct BspTree {
+    nodes: Vec<BspNode>,
+}
+
+#[derive(Clone, Copy)]
+enum Slot {
+    Root,
+    Front(usize),
+    Back(usize),
+}
+
+impl BspTree {
+    fn new(polygons: Vec<Polygon>) -> Self {
+        let mut tree = Self { nodes: Vec::new() };
+        tree.insert(polygons);
+        tree
+    }
+
+    fn insert(&mut self, polygons: Vec<Polygon>) {
+        let mut work = vec![(Slot::Root, polygons)];
+        while let Some((slot, polygons)) = work.pop() {
+            let Some(first) = polygons.first() else {
+                continue;
+            };
+            let existing = match slot {
+                Slot::Root => (!self.nodes.is_empty()).then_some(0),
+                Slot::Front(parent) => self.nodes[parent].front,
+                Slot::Back(parent) => self.nodes[parent].back,
+            };
+            let node = existing.unwrap_or_else(|| {
+                let node = self.nodes.len();
+                self.nodes.push(BspNode {
+                    plane: first.plane,
+                    front: None,
+                    back: None,
+                    polygons: Vec::new(),
+                });
+                match slot {
+                    Slot::Root => {}
+                    Slot::Front(parent) => self.nodes[parent].front = Some(node),
+                    Slot::Back(parent) => self.nodes[parent].back = Some(node),
+                }
+                node
+            });
+            let plane = self.nodes[node].plane;
+            let mut coplanar = Vec::new();
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            for polygon in polygons {
+                let mut coplanar_back = Vec::new();
+                split_polygon(
+                    &plane,
+                    polygon,
+                    &mut coplanar,
+                    &mut coplanar_back,
+                    &mut front,
+                    &mut back,
+                );
+                coplanar.append(&mut coplanar_back);
+            }
+            self.nodes[node].polygons.append(&mut coplanar);
+            work.push((Slot::Front(node), front));
+            work.push((Slot::Back(node), back));
+        }
+    }
+
+    // Removes the parts of `polygons` inside this tree's solid.
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        if self.nodes.is_empty() {
+            return polygons;
+        }
+        let mut kept = Vec::new();
+        let mut work = vec![(0, polygons)];
+        while let Some((node, polygons)) = work.pop() {
+            let node = &self.nodes[node];
+            let (mut front, mut back) = (Vec::new(), Vec::new());
+            for polygon in polygons {
+                let mut coplanar_front = Vec::new();
+                let mut coplanar_back = Vec::new();
+                split_polygon(
+                    &node.plane,
+                    polygon,
+                    &mut coplanar_front,
+                    &mut coplanar_back,
+                    &mut front,
+                    &mut back,
+                );
+                front.append(&mut coplanar_front);
+                back.append(&mut coplanar_back);
+            }
+            match node.front {
+                Some(child) => work.push((child, front)),
+                None => kept.append(&mut front),
+            }
+            if let Some(child) = node.back {
+                work.push((child, back));
+            }
+        }
+        kept
+    }
+
+    fn clip_to(&mut self, other: &BspTree) {
+        for node in 0..self.nodes.len() {
+            let polygons = std::mem::take(&mut self.nodes[node].polygons);
+            self.nodes[node].polygons = other.clip_polygons(polygons);
+        }
+    }
+
+    // Swaps inside and outside.
+    fn invert(&mut self) {
+        for node in &mut self.nodes {
+            node.polygons.iter_mut().for_each(Polygon::flip);
+            node.plane = node.plane.flipped();
+            std::mem::swap(&mut node.front, &mut node.back);
+        }
+    }
+
+    fn into_polygons(self) -> Vec<Polygon> {
+        self.nodes
+            .into_iter()
+            .flat_map(|node| node.polygons)
+            .collect()
+    }
+}
+
+// Faces that bend out of their plane are fanned into triangles so every polygon is flat.
+fn mesh_polygons(mesh: &Mesh, faces: &[std::ops::Range<usize>]) -> Vec<Polygon> {
+    let mut polygons = Vec::with_capacity(faces.len());
+    for (face, range) in faces.iter().enumerate() {
+        let points: Vec<DVec3> = mesh.indices[range.clone()]
+            .iter()
+            .map(|&point| DVec3::from(mesh.positions[point as usize].map(f64::from)))
+            .collect();
+        let Some(plane) = Plane::from_points(&points) else {
+            continue;
+        };
+        let flat = points
+            .iter()
+            .all(|point| plane.distance(*point).abs() <= PLANE_EPSILON);
+        if flat {
+            polygons.push(Polygon {
+                points,
+                plane,
+                source: face,
+            });
+            continue;
+        }
+        for i in 1..points.len() - 1 {
+            let triangle = vec![points[0], points[i], points[i + 1]];
+            if let Some(plane) = Plane::from_points(&triangle) {
+                polygons.push(Polygon {
+                    points: triangle,
+                    plane,
+                    source: face,
+                });
+            }
+        }
+    }
+    polygons
+}
+
+fn polygons_to_mesh(
+    source: &Mesh,
+    faces: &[std::ops::Range<usize>],
+    polygons: Vec<Polygon>,
+) -> Mesh {
+    // Cut points are computed once and shared by both halves, so exact matches weld them.
+    let mut welded: HashMap<[u32; 3], u32> = HashMap::new();
+    let mut positions = Vec::new();
+    let mut point_sources = Vec::new();
+    let mut indices = Vec::new();
+    let mut corner_sources = Vec::new();
+    let mut prim_sources = Vec::with_capacity(polygons.len());
+    let mut face_counts = Vec::with_capacity(polygons.len());
+    for polygon in polygons {
+        let range = faces[polygon.source].clone();
+        for point in &polygon.points {
+            let position = point.as_vec3().to_array();
+            let corner = range
+                .clone()
+                .min_by(|&x, &y| {
+                    let distance = |corner: usize| {
+                        let p = source.positions[source.indices[corner] as usize];
+                        DVec3::from(p.map(f64::from)).distance_squared(*point)
+                    };
+                    distance(x).total_cmp(&distance(y))
+                })
+                .unwrap_or(range.start);
+            let index = *welded.entry(position.map(f32::to_bits)).or_insert_with(|| {
+                positions.push(position);
+                point_sources.push(source.indices[corner] as usize);
+                (positions.len() - 1) as u32
+            });
+            indices.push(index);
+            corner_sources.push(corner);
+        }
+        prim_sources.push(polygon.source);
+        face_counts.push(polygon.points.len() as u32);
+    }
+
+    let mut mesh = source.rebuild_from_sources(
+        positions,
+        indices,
+        &point_sources,
+        &corner_sources,
+        &prim_sources,
+    );
+    if face_counts.iter().any(|count| *count != 3) {
+        mesh.face_counts = face_counts;
+    }
+    mesh.normals = None;
+    mesh.corner_normals = None;
+    mesh.compute_normals();
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Mat4, Vec3};
+
+    use super::*;
+    use crate::mesh::make_box;
+
+    fn volume(mesh: &Mesh) -> f32 {
+        let triangles = mesh.triangulate();
+        triangles
+            .indices
+            .chunks(3)
+            .map(|tri| {
+                let [a, b, c] = [0, 1, 2].map(|i| Vec3::from(triangles.positions[tri[i] as usize]));
+                a.dot(b.cross(c)) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn boolean_modes_give_expected_volumes() {
+        let a = make_box([1.0, 1.0, 1.0]);
+        let mut b = make_box([1.0, 1.0, 1.0]);
+        b.transform(Mat4::from_translation(Vec3::new(0.5, 0.25, 0.0)));
+
+        for (mode, expected) in [
+            (BooleanMode::Union, 1.625),
+            (BooleanMode::Difference, 0.625),
+            (BooleanMode::Intersect, 0.375),
+        ] {
+            let mesh = mesh_boolean(&a, &b, mode).unwrap();
+            assert!(
+                (volume(&mesh) - expected).abs() < 1.0e-4,
+                "{:?}: volume {}",
+                mode,
+                volume(&mesh)
+            );
+            assert!(mesh.has_valid_faces());
+        }
+
+        let mut far = make_box([1.0, 1.0, 1.0]);
+        far.transform(Mat4::from_translation(Vec3::new(5.0, 0.0, 0.0)));
+        let empty = mesh_boolean(&a, &far, BooleanMode::Intersect).unwrap();
+        assert_eq!(empty.face_count(), 0);
+    }
+}
@@ -0,0 +1,321 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+use crate::mesh::{DebugChannel, Mesh};
+
+// Quads whose normal is flatter than this count as walls; roofs and floors pass through.
+const WALL_NORMAL_Y: f32 = 0.5;
+const T_QUANTUM: f32 = 1.0e5;
+
+#[derive(Debug, Clone)]
+pub struct FloorSplitSettings {
+    pub floor_height: f32,
+    pub attr: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct FacadeRepeatSettings {
+    pub bay_width: f32,
+    pub attr: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct WindowScatterSettings {
+    pub density: f32,
+    pub seed: u32,
+    pub offset: f32,
+    pub skip_ground_floor: bool,
+}
+
+pub fn floor_split(input: &Mesh, settings: &FloorSplitSettings) -> Result<Mesh, String> {
+    if settings.floor_height <= 0.0 {
+        return Err("Floor Split requires a positive floor height".to_string());
+    }
+    split_walls(input, &settings.attr, |quad| {
+        let vertical = vertical_pair(quad);
+        let height = pair_rise(quad, vertical);
+        let count = (height / settings.floor_height).round().max(1.0) as usize;
+        (vertical, count)
+    })
+}
+
+pub fn facade_repeat(input: &Mesh, settings: &FacadeRepeatSettings) -> Result<Mesh, String> {
+    if settings.bay_width <= 0.0 {
+        return Err("Facade Repeat requires a positive bay width".to_string());
+    }
+    split_walls(input, &settings.attr, |quad| {
+        let horizontal = !vertical_pair(quad);
+        let width = pair_length(quad, horizontal);
+        let count = (width / settings.bay_width).round().max(1.0) as usize;
+        (horizontal, count)
+    })
+}
+
+pub fn window_scatter(input: &Mesh, settings: &WindowScatterSettings) -> Result<Mesh, String> {
+    let floors = match input.attribute(AttributeDomain::Primitive, "floor") {
+        Some(AttributeRef::Int(values)) => Some(values),
+        _ => None,
+    };
+    let density = settings.density.clamp(0.0, 1.0);
+    let mut positions = Vec::new();
+    let mut normals = Vec::new();
+    let mut faces = Vec::new();
+    let mut rejected = Vec::new();
+    for (face, range) in input.face_ranges().into_iter().enumerate() {
+        let Some(quad) = face_quad(input, &range) else {
+            continue;
+        };
+        let normal = quad_normal(&quad);
+        if normal.y.abs() >= WALL_NORMAL_Y {
+            continue;
+        }
+        let center = quad.iter().copied().sum::<Vec3>() * 0.25;
+        let floor = floors.and_then(|values| values.get(face).copied());
+        if (settings.skip_ground_floor && floor == Some(0))
+            || hash_unit(face as u32, settings.seed) >= density
+        {
+            rejected.push((center + normal * settings.offset).to_array());
+            continue;
+        }
+        positions.push((center + normal * settings.offset).to_array());
+        normals.push(normal.to_array());
+        faces.push(face);
+    }
+
+    let mut points = Mesh::with_positions_indices(positions, Vec::new());
+    points.normals = Some(normals);
+    for (name, storage) in input.attributes.map(AttributeDomain::Primitive) {
+        points
+            .set_attribute(AttributeDomain::Point, name.clone(), storage.gather(&faces))
+            .map_err(|err| format!("Window Scatter attribute error: {:?}", err))?;
+    }
+    if !rejected.is_empty() {
+        points.debug_channels.push(DebugChannel::points(
+            "window_rejected",
+            [0.9, 0.3, 0.3],
+            rejected,
+        ));
+    }
+    Ok(points)
+}
+
+// Splits every wall quad into strips. `pick` returns which pair of opposite edges to
+// divide (true: c0-c1 and c3-c2) and how many strips to cut. Points on shared edges are
+// keyed by the edge and parameter, so neighbouring walls stay connected.
+fn split_walls(
+    input: &Mesh,
+    attr: &str,
+    pick: impl Fn(&[Vec3; 4]) -> (bool, usize),
+) -> Result<Mesh, String> {
+    if input.positions.is_empty() {
+        return Err("building nodes require a mesh input".to_string());
+    }
+    let mut positions = input.positions.clone();
+    let mut point_sources: Vec<usize> = (0..positions.len()).collect();
+    let mut edge_points: HashMap<(u32, u32, i64), u32> = HashMap::new();
+    let mut indices = Vec::new();
+    let mut corner_sources = Vec::new();
+    let mut prim_sources = Vec::new();
+    let mut face_counts = Vec::new();
+    let mut strip_index = Vec::new();
+
+    for (face, range) in input.face_ranges().into_iter().enumerate() {
+        let split = face_quad(input, &range)
+            .filter(|quad| quad_normal(quad).y.abs() < WALL_NORMAL_Y)
+            .map(|quad| (quad, pick(&quad)));
+        let Some((quad, (first_pair, count))) = split else {
+            indices.extend_from_slice(&input.indices[range.clone()]);
+            corner_sources.extend(range.clone());
+            prim_sources.push(face);
+            face_counts.push(range.len() as u32);
+            strip_index.push(-1);
+            continue;
+        };
+
+        let corners: [usize; 4] = std::array::from_fn(|i| range.start + i);
+        // (start, end) corner slots of the two edges being divided.
+        let (side_a, side_b) = if first_pair {
+            ((0, 1), (3, 2))
+        } else {
+            ((1, 2), (0, 3))
+        };
+        let mut edge_point = |(from, to): (usize, usize), step: usize| -> u32 {
+            let a = input.indices[corners[from]];
+            let b = input.indices[corners[to]];
+            if step == 0 {
+                return a;
+            }
+            if step == count {
+                return b;
+            }
+            let t = step as f32 / count as f32;
+            let key_t = if a <= b { t } else { 1.0 - t };
+            let near = if t < 0.5 { a } else { b };
+            let key = (a.min(b), a.max(b), (key_t * T_QUANTUM).round() as i64);
+            *edge_points.entry(key).or_insert_with(|| {
+                let position = quad[from].lerp(quad[to], t);
+                positions.push(position.to_array());
+                point_sources.push(near as usize);
+                (positions.len() - 1) as u32
+            })
+        };
+
+        let rises = quad[side_a.1].y + quad[side_b.1].y > quad[side_a.0].y + quad[side_b.0].y;
+        for step in 0..count {
+            let a0 = edge_point(side_a, step);
+            let a1 = edge_point(side_a, step + 1);
+            let b0 = edge_point(side_b, step);
+            let b1 = edge_point(side_b, step + 1);
+            if first_pair {
+                indices.extend_from_slice(&[a0, a1, b1, b0]);
+            } else {
+                indices.extend_from_slice(&[b0, a0, a1, b1]);
+            }
+            corner_sources.extend_from_slice(&corners);
+            prim_sources.push(face);
+            face_counts.push(4);
+            let index = if rises { step } else { count - 1 - step };
+            strip_index.push(index as i32);
+        }
+    }
+
+    let mut mesh = input.rebuild_from_sources(
+        positions,
+        indices,
+        &point_sources,
+        &corner_sources,
+        &prim_sources,
+    );
+    mesh.face_counts = face_counts;
+    if input.normals.is_some() {
+        mesh.compute_normals();
+    }
+    if !attr.trim().is_empty() {
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            attr.trim(),
+            AttributeStorage::Int(strip_index),
+        )
+        .map_err(|err| format!("building attribute error: {:?}", err))?;
+    }
+    Ok(mesh)
+}
+
+fn face_quad(mesh: &Mesh, range: &std::ops::Range<usize>) -> Option<[Vec3; 4]> {
+    if range.len() != 4 {
+        return None;
+    }
+    let mut quad = [Vec3::ZERO; 4];
+    for (slot, corner) in range.clone().enumerate() {
+        let point = *mesh.indices.get(corner)? as usize;
+        quad[slot] = Vec3::from(*mesh.positions.get(point)?);
+    }
+    Some(quad)
+}
+
+fn quad_normal(quad: &[Vec3; 4]) -> Vec3 {
+    (quad[2] - quad[0])
+        .cross(quad[3] - quad[1])
+        .try_normalize()
+        .unwrap_or(Vec3::Y)
+}
+
+// True when the c0-c1 / c3-c2 edges are the more vertical pair.
+fn vertical_pair(quad: &[Vec3; 4]) -> bool {
+    pair_rise(quad, true) >= pair_rise(quad, false)
+}
+
+fn pair_rise(quad: &[Vec3; 4], first_pair: bool) -> f32 {
+    let (a, b) = pair_edges(quad, first_pair);
+    ((a.1.y - a.0.y).abs() + (b.1.y - b.0.y).abs()) * 0.5
+}
+
+fn pair_length(quad: &[Vec3; 4], first_pair: bool) -> f32 {
+    let (a, b) = pair_edges(quad, first_pair);
+    (a.0.distance(a.1) + b.0.distance(b.1)) * 0.5
+}
+
+type Edge = (Vec3, Vec3);
+
+fn pair_edges(quad: &[Vec3; 4], first_pair: bool) -> (Edge, Edge) {
+    if first_pair {
+        ((quad[0], quad[1]), (quad[3], quad[2]))
+    } else {
+        ((quad[1], quad[2]), (quad[0], quad[3]))
+    }
+}
+
+fn hash_unit(value: u32, seed: u32) -> f32 {
+    let mut x = value.wrapping_mul(0x9E37_79B9) ^ seed.wrapping_mul(0x85EB_CA6B);
+    x ^= x >> 16;
+    x = x.wrapping_mul(0x7FEB_352D);
+    x ^= x >> 15;
+    x as f32 / u32::MAX as f32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn floors_and_bays_split_walls_only() {
+        let tower = make_box([2.0, 3.0, 2.0]);
+        let floors = floor_split(
+            &tower,
+            &FloorSplitSettings {
+                floor_height: 1.0,
+                attr: "floor".to_string(),
+            },
+        )
+        .unwrap();
+        // Four walls of three floors plus the untouched roof and base.
+        assert_eq!(floors.face_count(), 4 * 3 + 2);
+        // Each vertical edge gains two shared points.
+        assert_eq!(floors.positions.len(), 8 + 4 * 2);
+        let Some(AttributeRef::Int(levels)) = floors.attribute(AttributeDomain::Primitive, "floor")
+        else {
+            panic!("missing floor attribute");
+        };
+        assert_eq!(levels.iter().filter(|level| **level == -1).count(), 2);
+        assert_eq!(levels.iter().filter(|level| **level == 2).count(), 4);
+
+        let bays = facade_repeat(
+            &floors,
+            &FacadeRepeatSettings {
+                bay_width: 0.5,
+                attr: "bay".to_string(),
+            },
+        )
+        .unwrap();
+        assert_eq!(bays.face_count(), 4 * 3 * 4 + 2);
+        assert!(bays
+            .indices
+            .iter()
+            .all(|idx| (*idx as usize) < bays.positions.len()));
+
+        let windows = window_scatter(
+            &bays,
+            &WindowScatterSettings {
+                density: 1.0,
+                seed: 1,
+                offset: 0.0,
+                skip_ground_floor: true,
+            },
+        )
+        .unwrap();
+        assert_eq!(windows.positions.len(), 4 * 2 * 4);
+        assert!(windows
+            .normals
+            .as_ref()
+            .unwrap()
+            .iter()
+            .all(|normal| normal[1].abs() < 1.0e-4));
+        assert!(windows.attribute(AttributeDomain::Point, "bay").is_some());
+        // The skipped ground floor shows up as a debug channel instead.
+        assert_eq!(windows.debug_channels.len(), 1);
+        assert_eq!(windows.debug_channels[0].points.len(), 4 * 4);
+    }
+}
@@ -0,0 +1,167 @@
+use glam::Vec3;
+
+use crate::mesh::Mesh;
+
+const LEAF_SIZE: usize = 4;
+
+struct BvhNode {
+    min: Vec3,
+    max: Vec3,
+    start: usize,
+    count: usize,
+    right: usize,
+}
+
+pub(crate) struct Bvh {
+    nodes: Vec<BvhNode>,
+    triangles: Vec<[Vec3; 3]>,
+}
+
+impl Bvh {
+    pub(crate) fn build(mesh: &Mesh) -> Self {
+        let (corners, _) = mesh.triangulation();
+        let mut triangles: Vec<[Vec3; 3]> = corners
+            .chunks_exact(3)
+            .filter_map(|tri| {
+                let corner = |i: usize| {
+                    mesh.positions
+                        .get(mesh.indices[tri[i]] as usize)
+                        .copied()
+                        .map(Vec3::from)
+                };
+                Some([corner(0)?, corner(1)?, corner(2)?])
+            })
+            .collect();
+        let mut nodes = Vec::new();
+        if !triangles.is_empty() {
+            let count = triangles.len();
+            build_node(&mut nodes, &mut triangles, 0, count);
+        }
+        Self { nodes, triangles }
+    }
+
+    pub(crate) fn occluded(&self, origin: Vec3, dir: Vec3, max_dist: f32) -> bool {
+        if self.nodes.is_empty() {
+            return false;
+        }
+        let inv_dir = dir.recip();
+        let mut stack = vec![0usize];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !ray_hits_box(origin, inv_dir, node.min, node.max, max_dist) {
+                continue;
+            }
+            if node.count > 0 {
+                let hit = self.triangles[node.start..node.start + node.count]
+                    .iter()
+                    .any(|tri| ray_triangle(origin, dir, tri).is_some_and(|t| t < max_dist));
+                if hit {
+                    return true;
+                }
+            } else {
+                stack.push(index + 1);
+                stack.push(node.right);
+            }
+        }
+        false
+    }
+
+    pub(crate) fn closest_hit(&self, origin: Vec3, dir: Vec3) -> Option<f32> {
+        if self.nodes.is_empty() {
+            return None;
+        }
+        let inv_dir = dir.recip();
+        let mut closest = f32::INFINITY;
+        let mut stack = vec![0usize];
+        while let Some(index) = stack.pop() {
+            let node = &self.nodes[index];
+            if !ray_hits_box(origin, inv_dir, node.min, node.max, closest) {
+                continue;
+            }
+            if node.count > 0 {
+                for tri in &self.triangles[node.start..node.start + node.count] {
+                    if let Some(t) = ray_triangle(origin, dir, tri) {
+                        closest = closest.min(t);
+                    }
+                }
+            } else {
+                stack.push(index + 1);
+                stack.push(node.right);
+            }
+        }
+        closest.is_finite().then_some(closest)
+    }
+}
+
+fn build_node(nodes: &mut Vec<BvhNode>, triangles: &mut [[Vec3; 3]], start: usize, end: usize) {
+    let slice = &mut triangles[start..end];
+    let mut min = Vec3::splat(f32::INFINITY);
+    let mut max = Vec3::splat(f32::NEG_INFINITY);
+    for tri in slice.iter() {
+        for p in tri {
+            min = min.min(*p);
+            max = max.max(*p);
+        }
+    }
+    let index = nodes.len();
+    nodes.push(BvhNode {
+        min,
+        max,
+        start,
+        count: end - start,
+        right: 0,
+    });
+    if slice.len() <= LEAF_SIZE {
+        return;
+    }
+
+    let extent = max - min;
+    let axis = if extent.x >= extent.y && extent.x >= extent.z {
+        0
+    } else if extent.y >= extent.z {
+        1
+    } else {
+        2
+    };
+    let mid = slice.len() / 2;
+    slice.select_nth_unstable_by(mid, |a, b| {
+        let ca = a[0][axis] + a[1][axis] + a[2][axis];
+        let cb = b[0][axis] + b[1][axis] + b[2][axis];
+        ca.total_cmp(&cb)
+    });
+    nodes[index].count = 0;
+    build_node(nodes, triangles, start, start + mid);
+    nodes[index].right = nodes.len();
+    build_node(nodes, triangles, start + mid, end);
+}
+
+fn ray_hits_box(origin: Vec3, inv_dir: Vec3, min: Vec3, max: Vec3, max_dist: f32) -> bool {
+    let t0 = (min - origin) * inv_dir;
+    let t1 = (max - origin) * inv_dir;
+    let near = t0.min(t1).max_element().max(0.0);
+    let far = t0.max(t1).min_element().min(max_dist);
+    near <= far
+}
+
+fn ray_triangle(origin: Vec3, dir: Vec3, tri: &[Vec3; 3]) -> Option<f32> {
+    let edge1 = tri[1] - tri[0];
+    let edge2 = tri[2] - tri[0];
+    let p = dir.cross(edge2);
+    let det = edge1.dot(p);
+    if det.abs() < 1.0e-12 {
+        return None;
+    }
+    let inv_det = 1.0 / det;
+    let s = origin - tri[0];
+    let u = s.dot(p) * inv_det;
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+    let q = s.cross(edge1);
+    let v = dir.dot(q) * inv_det;
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+    let t = edge2.dot(q) * inv_det;
+    (t > 0.0).then_some(t)
+}
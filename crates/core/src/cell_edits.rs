@@ -0,0 +1,206 @@
+use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+use crate::mesh::Mesh;
+
+pub const CELL_EDITS_PARAM: &str = "edits";
+const DOMAIN_NAMES: [(&str, AttributeDomain); 4] = [
+    ("point", AttributeDomain::Point),
+    ("vertex", AttributeDomain::Vertex),
+    ("prim", AttributeDomain::Primitive),
+    ("detail", AttributeDomain::Detail),
+];
+const COMPONENTS: [char; 4] = ['x', 'y', 'z', 'w'];
+
+// One spreadsheet cell written back to an Attribute Edit node. Stored as a line of the
+// node's `edits` list, e.g. `point Cd.y 3 0.5`, so edits save, undo and diff like any
+// other parameter.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CellEdit {
+    pub domain: AttributeDomain,
+    pub attribute: String,
+    pub component: Option<usize>,
+    pub element: usize,
+    pub value: f32,
+}
+
+impl CellEdit {
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let tokens: Vec<&str> = text.split_whitespace().collect();
+        let [domain, attribute, element, value] = tokens.as_slice() else {
+            return Err(format!(
+                "expected 'domain attribute element value', got '{}'",
+                text
+            ));
+        };
+        let domain = DOMAIN_NAMES
+            .iter()
+            .find(|(name, _)| name == domain)
+            .map(|(_, domain)| *domain)
+            .ok_or_else(|| format!("unknown domain '{}'", domain))?;
+        let (attribute, component) = match attribute.split_once('.') {
+            Some((name, axis)) => {
+                let component = COMPONENTS
+                    .iter()
+                    .position(|c| axis.len() == 1 && axis.starts_with(*c))
+                    .ok_or_else(|| format!("unknown component '{}'", axis))?;
+                (name, Some(component))
+            }
+            None => (*attribute, None),
+        };
+        Ok(Self {
+            domain,
+            attribute: attribute.to_string(),
+            component,
+            element: element
+                .parse()
+                .map_err(|_| format!("expected an element index, got '{}'", element))?,
+            value: value
+                .parse()
+                .map_err(|_| format!("expected a number, got '{}'", value))?,
+        })
+    }
+
+    pub fn to_text(&self) -> String {
+        let domain = DOMAIN_NAMES
+            .iter()
+            .find(|(_, domain)| *domain == self.domain)
+            .map_or("point", |(name, _)| name);
+        let component = self
+            .component
+            .map(|index| format!(".{}", COMPONENTS[index]))
+            .unwrap_or_default();
+        format!(
+            "{} {}{} {} {}",
+            domain, self.attribute, component, self.element, self.value
+        )
+    }
+
+    fn same_cell(&self, other: &CellEdit) -> bool {
+        self.domain == other.domain
+            && self.attribute == other.attribute
+            && self.component == other.component
+            && self.element == other.element
+    }
+}
+
+// Records an edit, replacing any earlier edit of the same cell so the list stays one line
+// per cell however often it is changed.
+pub fn set_cell_edit(edits: &mut Vec<String>, edit: &CellEdit) {
+    edits.retain(|line| CellEdit::parse(line).map_or(true, |old| !old.same_cell(edit)));
+    edits.push(edit.to_text());
+}
+
+// Edits that no longer fit the input, after an upstream change of topology or attributes,
+// fail the node rather than landing on whatever element now has that index.
+pub(crate) fn apply_cell_edits(mesh: &mut Mesh, edits: &[String]) -> Result<(), String> {
+    let edits = edits
+        .iter()
+        .map(|line| CellEdit::parse(line))
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("Attribute Edit: {}", err))?;
+    let mut done = vec![false; edits.len()];
+    for first in 0..edits.len() {
+        if done[first] {
+            continue;
+        }
+        let (domain, name) = (edits[first].domain, edits[first].attribute.as_str());
+        let mut storage = match mesh.attribute(domain, name) {
+            Some(values) => owned_storage(values),
+            None => {
+                return Err(format!(
+                    "Attribute Edit: no {:?} attribute '{}'",
+                    domain, name
+                ))
+            }
+        };
+        for (index, edit) in edits.iter().enumerate().skip(first) {
+            if edit.domain != domain || edit.attribute != name {
+                continue;
+            }
+            done[index] = true;
+            write_cell(&mut storage, edit)?;
+        }
+        mesh.set_attribute(domain, name, storage)
+            .map_err(|err| format!("Attribute Edit: {:?}", err))?;
+    }
+    Ok(())
+}
+
+fn owned_storage(values: AttributeRef<'_>) -> AttributeStorage {
+    match values {
+        AttributeRef::Float(values) => AttributeStorage::Float(values.to_vec()),
+        AttributeRef::Int(values) => AttributeStorage::Int(values.to_vec()),
+        AttributeRef::Vec2(values) => AttributeStorage::Vec2(values.to_vec()),
+        AttributeRef::Vec3(values) => AttributeStorage::Vec3(values.to_vec()),
+        AttributeRef::Vec4(values) => AttributeStorage::Vec4(values.to_vec()),
+    }
+}
+
+fn write_cell(storage: &mut AttributeStorage, edit: &CellEdit) -> Result<(), String> {
+    let out_of_range = || {
+        format!(
+            "Attribute Edit: '{}' has no element {}",
+            edit.attribute, edit.element
+        )
+    };
+    let component = edit.component.unwrap_or(0);
+    let slot = match storage {
+        AttributeStorage::Float(values) if edit.component.is_none() => values.get_mut(edit.element),
+        AttributeStorage::Int(values) if edit.component.is_none() => {
+            let value = values.get_mut(edit.element).ok_or_else(out_of_range)?;
+            *value = edit.value.round() as i32;
+            return Ok(());
+        }
+        AttributeStorage::Vec2(values) => values
+            .get_mut(edit.element)
+            .and_then(|value| value.get_mut(component)),
+        AttributeStorage::Vec3(values) => values
+            .get_mut(edit.element)
+            .and_then(|value| value.get_mut(component)),
+        AttributeStorage::Vec4(values) => values
+            .get_mut(edit.element)
+            .and_then(|value| value.get_mut(component)),
+        _ => {
+            return Err(format!(
+                "Attribute Edit: '{}' has no components",
+                edit.attribute
+            ))
+        }
+    };
+    *slot.ok_or_else(out_of_range)? = edit.value;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn cell_edits_replace_and_apply() {
+        let mut edits = Vec::new();
+        let edit = CellEdit::parse("point P.y 2 1.5").unwrap();
+        assert_eq!(edit.component, Some(1));
+        set_cell_edit(&mut edits, &edit);
+        set_cell_edit(&mut edits, &CellEdit::parse("point P.y 2 4").unwrap());
+        set_cell_edit(&mut edits, &CellEdit::parse("detail id 0 7.4").unwrap());
+        assert_eq!(edits, ["point P.y 2 4", "detail id 0 7.4"]);
+
+        let mut mesh = make_box([1.0; 3]);
+        mesh.set_attribute(
+            AttributeDomain::Detail,
+            "id",
+            AttributeStorage::Int(vec![0]),
+        )
+        .unwrap();
+        apply_cell_edits(&mut mesh, &edits).unwrap();
+        assert_eq!(mesh.positions[2][1], 4.0);
+        assert_eq!(
+            mesh.attribute(AttributeDomain::Detail, "id"),
+            Some(AttributeRef::Int(&[7]))
+        );
+
+        let stale = ["point P.x 999 0".to_string()];
+        assert!(apply_cell_edits(&mut mesh, &stale).is_err());
+        assert!(CellEdit::parse("edge P.x 0 1").is_err());
+    }
+}
@@ -0,0 +1,285 @@
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::mesh::Mesh;
+
+pub fn flip_winding(mesh: &mut Mesh) {
+    let faces = mesh.face_ranges();
+    flip_faces(mesh, &faces);
+    if let Some(normals) = &mut mesh.normals {
+        for n in normals {
+            *n = [-n[0], -n[1], -n[2]];
+        }
+    }
+}
+
+pub fn unify_winding(mesh: &mut Mesh) -> Result<usize, String> {
+    if !mesh.has_valid_faces() {
+        return Err("Clean requires a polygon mesh input".to_string());
+    }
+    let faces = mesh.face_ranges();
+    let face_edges = |face: usize| {
+        let corners = &mesh.indices[faces[face].clone()];
+        (0..corners.len())
+            .map(move |corner| (corners[corner], corners[(corner + 1) % corners.len()]))
+    };
+    let mut edges: HashMap<(u32, u32), Vec<(usize, bool)>> = HashMap::new();
+    for face in 0..faces.len() {
+        for (a, b) in face_edges(face) {
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((face, a < b));
+        }
+    }
+
+    let mut flip: Vec<Option<bool>> = vec![None; faces.len()];
+    let mut to_flip = Vec::new();
+    for seed in 0..faces.len() {
+        if flip[seed].is_some() {
+            continue;
+        }
+        flip[seed] = Some(false);
+        let mut component = vec![seed];
+        let mut closed = true;
+        let mut queue = VecDeque::from([seed]);
+        while let Some(face) = queue.pop_front() {
+            let face_flipped = flip[face].unwrap_or(false);
+            for (a, b) in face_edges(face) {
+                let Some(shared) = edges.get(&(a.min(b), a.max(b))) else {
+                    continue;
+                };
+                if shared.len() != 2 {
+                    closed = false;
+                }
+                let forward = (a < b) != face_flipped;
+                for &(other, other_forward) in shared {
+                    if other == face || flip[other].is_some() {
+                        continue;
+                    }
+                    flip[other] = Some(other_forward == forward);
+                    component.push(other);
+                    queue.push_back(other);
+                }
+            }
+        }
+
+        let mut component_flips: Vec<usize> = component
+            .iter()
+            .copied()
+            .filter(|face| flip[*face] == Some(true))
+            .collect();
+        if closed && signed_volume(mesh, &faces, &component, &flip) < 0.0 {
+            component_flips = component
+                .iter()
+                .copied()
+                .filter(|face| flip[*face] != Some(true))
+                .collect();
+        }
+        to_flip.extend(component_flips);
+    }
+
+    if !to_flip.is_empty() {
+        to_flip.sort_unstable();
+        let ranges: Vec<Range<usize>> = to_flip.iter().map(|face| faces[*face].clone()).collect();
+        flip_faces(mesh, &ranges);
+        if mesh.normals.is_some() {
+            mesh.compute_normals();
+        }
+    }
+    Ok(to_flip.len())
+}
+
+// Pairs triangles that share an edge into quads when their normals differ by at most
+// `max_angle_deg` and the quad stays convex, flattest pairs first. Returns the quad count.
+pub fn quadrangulate(mesh: &mut Mesh, max_angle_deg: f32) -> Result<usize, String> {
+    if !mesh.has_valid_faces() {
+        return Err("Clean requires a polygon mesh input".to_string());
+    }
+    let faces = mesh.face_ranges();
+    let max_angle = max_angle_deg.clamp(0.0, 180.0).to_radians();
+    let position = |corner: usize| {
+        let point = mesh.indices[corner] as usize;
+        mesh.positions.get(point).copied().map(Vec3::from)
+    };
+    let normal = |face: usize| {
+        let range = faces[face].clone();
+        let (a, b, c) = (
+            position(range.start)?,
+            position(range.start + 1)?,
+            position(range.start + 2)?,
+        );
+        (b - a).cross(c - a).try_normalize()
+    };
+
+    let mut edges: HashMap<(u32, u32), Vec<(usize, usize)>> = HashMap::new();
+    for (face, range) in faces.iter().enumerate() {
+        if range.len() != 3 {
+            continue;
+        }
+        for k in 0..3 {
+            let a = mesh.indices[range.start + k];
+            let b = mesh.indices[range.start + (k + 1) % 3];
+            edges
+                .entry((a.min(b), a.max(b)))
+                .or_default()
+                .push((face, k));
+        }
+    }
+
+    let mut candidates = Vec::new();
+    for shared in edges.values() {
+        let &[(face_a, edge_a), (face_b, edge_b)] = shared.as_slice() else {
+            continue;
+        };
+        let quad = quad_corners(&faces, face_a, edge_a, face_b, edge_b);
+        // Opposite directions along the shared edge, so the pair agrees on winding.
+        if mesh.indices[quad[0]] != mesh.indices[faces[face_b].start + edge_b] {
+            continue;
+        }
+        let (Some(n_a), Some(n_b)) = (normal(face_a), normal(face_b)) else {
+            continue;
+        };
+        let angle = n_a.angle_between(n_b);
+        let convex = quad.iter().enumerate().all(|(k, corner)| {
+            let (Some(prev), Some(here), Some(next)) = (
+                position(quad[(k + 3) % 4]),
+                position(*corner),
+                position(quad[(k + 1) % 4]),
+            ) else {
+                return false;
+            };
+            (here - prev).cross(next - here).dot(n_a + n_b) > 0.0
+        });
+        if angle <= max_angle && convex {
+            candidates.push((angle, face_a.min(face_b), quad));
+        }
+    }
+    candidates.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+
+    let mut quads: Vec<Option<[usize; 4]>> = vec![None; faces.len()];
+    let mut merged = vec![false; faces.len()];
+    for (_, _, quad) in candidates {
+        let (face_a, face_b) = (face_of(&faces, quad[0]), face_of(&faces, quad[3]));
+        if merged[face_a] || merged[face_b] {
+            continue;
+        }
+        merged[face_a] = true;
+        merged[face_b] = true;
+        quads[face_a.min(face_b)] = Some(quad);
+    }
+    let quad_count = quads.iter().flatten().count();
+    if quad_count == 0 {
+        return Ok(0);
+    }
+
+    let mut corners = Vec::with_capacity(mesh.indices.len());
+    let mut prims = Vec::with_capacity(faces.len() - quad_count);
+    let mut counts = Vec::with_capacity(faces.len() - quad_count);
+    for (face, range) in faces.iter().enumerate() {
+        match quads[face] {
+            Some(quad) => corners.extend_from_slice(&quad),
+            None if merged[face] => continue,
+            None => corners.extend(range.clone()),
+        }
+        prims.push(face);
+        counts.push(quads[face].map_or(range.len(), |_| 4) as u32);
+    }
+    let indices = corners.iter().map(|corner| mesh.indices[*corner]).collect();
+    let points: Vec<usize> = (0..mesh.positions.len()).collect();
+    let mut rebuilt =
+        mesh.rebuild_from_sources(mesh.positions.clone(), indices, &points, &corners, &prims);
+    rebuilt.face_counts = counts;
+    *mesh = rebuilt;
+    Ok(quad_count)
+}
+
+// Corners of the quad made from triangle `a`, whose corner `edge_a` starts the shared edge,
+// and triangle `b`: the far corner of `b` slots in between the shared edge's end points.
+fn quad_corners(
+    faces: &[Range<usize>],
+    face_a: usize,
+    edge_a: usize,
+    face_b: usize,
+    edge_b: usize,
+) -> [usize; 4] {
+    let (a, b) = (faces[face_a].start, faces[face_b].start);
+    [
+        a + (edge_a + 1) % 3,
+        a + (edge_a + 2) % 3,
+        a + edge_a,
+        b + (edge_b + 2) % 3,
+    ]
+}
+
+fn face_of(faces: &[Range<usize>], corner: usize) -> usize {
+    faces.partition_point(|range| range.end <= corner)
+}
+
+fn signed_volume(
+    mesh: &Mesh,
+    faces: &[Range<usize>],
+    component: &[usize],
+    flip: &[Option<bool>],
+) -> f32 {
+    let mut volume = 0.0;
+    for &face in component {
+        let corners = &mesh.indices[faces[face].clone()];
+        let Some(p0) = mesh.positions.get(corners[0] as usize) else {
+            continue;
+        };
+        for k in 1..corners.len() - 1 {
+            let Some(p1) = mesh.positions.get(corners[k] as usize) else {
+                continue;
+            };
+            let Some(p2) = mesh.positions.get(corners[k + 1] as usize) else {
+                continue;
+            };
+            let v = Vec3::from(*p0).dot(Vec3::from(*p1).cross(Vec3::from(*p2)));
+            if flip[face] == Some(true) {
+                volume -= v;
+            } else {
+                volume += v;
+            }
+        }
+    }
+    volume / 6.0
+}
+
+fn flip_faces(mesh: &mut Mesh, faces: &[Range<usize>]) {
+    for range in faces {
+        mesh.indices[range.start + 1..range.end].reverse();
+    }
+    if let Some(corner_normals) = &mut mesh.corner_normals {
+        for range in faces {
+            if range.end <= corner_normals.len() {
+                corner_normals[range.start + 1..range.end].reverse();
+                for n in &mut corner_normals[range.clone()] {
+                    *n = [-n[0], -n[1], -n[2]];
+                }
+            }
+        }
+    }
+    for storage in mesh
+        .attributes
+        .map_mut(AttributeDomain::Vertex)
+        .values_mut()
+    {
+        for range in faces {
+            let corners = range.start + 1..range.end;
+            if range.end > storage.len() {
+                continue;
+            }
+            match storage {
+                AttributeStorage::Float(values) => values[corners].reverse(),
+                AttributeStorage::Int(values) => values[corners].reverse(),
+                AttributeStorage::Vec2(values) => values[corners].reverse(),
+                AttributeStorage::Vec3(values) => values[corners].reverse(),
+                AttributeStorage::Vec4(values) => values[corners].reverse(),
+            }
+        }
+    }
+}
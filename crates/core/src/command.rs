@@ -0,0 +1,436 @@
+use std::collections::BTreeSet;
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{Graph, GraphError, LinkId, Node, NodeId, ParamValue, PinId};
+use crate::nodes_builtin::{builtin_kind_from_name, default_params, node_definition};
+
+// One graph edit in a form that can be stored or sent over the wire. Ids are allocated in
+// order, so replaying the same commands on the same graph produces the same ids, which is
+// what lets a recorded session or a remote peer refer to nodes it created earlier.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum GraphCommand {
+    AddNode {
+        kind: String,
+    },
+    RemoveNode {
+        node: NodeId,
+    },
+    SetParam {
+        node: NodeId,
+        key: String,
+        value: ParamValue,
+    },
+    SetExpression {
+        node: NodeId,
+        key: String,
+        expression: Option<String>,
+    },
+    Connect {
+        from: PinId,
+        to: PinId,
+    },
+    Disconnect {
+        from: PinId,
+        to: PinId,
+    },
+    AddInput {
+        node: NodeId,
+    },
+    SetDisplay {
+        node: Option<NodeId>,
+    },
+    SetTemplate {
+        node: NodeId,
+        enabled: bool,
+    },
+}
+
+// What a command created, for callers that go on to refer to it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CommandOutput {
+    Done,
+    Node(NodeId),
+    Link(LinkId),
+    Pin(PinId),
+}
+
+impl Graph {
+    // Editor, scripted and remote edits. Undo restores whole-graph snapshots and keyframe
+    // edits have no command yet, so neither comes through here. Failures change nothing.
+    pub fn apply(&mut self, command: &GraphCommand) -> Result<CommandOutput, GraphError> {
+        match command {
+            GraphCommand::AddNode { kind } => {
+                let kind = builtin_kind_from_name(kind)
+                    .ok_or_else(|| GraphError::UnknownNodeKind(kind.clone()))?;
+                let node_id = self.add_node(node_definition(kind));
+                for (key, value) in default_params(kind).values {
+                    if let Err(err) = self.set_param(node_id, key, value) {
+                        self.undo_add_node(node_id);
+                        return Err(err);
+                    }
+                }
+                Ok(CommandOutput::Node(node_id))
+            }
+            GraphCommand::RemoveNode { node } => {
+                if !self.remove_node(*node) {
+                    return Err(GraphError::MissingNode(*node));
+                }
+                Ok(CommandOutput::Done)
+            }
+            GraphCommand::SetParam { node, key, value } => self
+                .set_param(*node, key.clone(), value.clone())
+                .map(|_| CommandOutput::Done),
+            GraphCommand::SetExpression {
+                node,
+                key,
+                expression,
+            } => self
+                .set_param_expression(*node, key.clone(), expression.clone())
+                .map(|_| CommandOutput::Done),
+            GraphCommand::Connect { from, to } => {
+                self.add_link(*from, *to).map(CommandOutput::Link)
+            }
+            // Disconnecting pins that are not linked is a no-op, so replays stay idempotent.
+            GraphCommand::Disconnect { from, to } => {
+                self.remove_link_between(*from, *to);
+                Ok(CommandOutput::Done)
+            }
+            GraphCommand::AddInput { node } => {
+                self.add_variadic_input(*node).map(CommandOutput::Pin)
+            }
+            GraphCommand::SetDisplay { node } => {
+                self.set_display_node(*node).map(|_| CommandOutput::Done)
+            }
+            GraphCommand::SetTemplate { node, enabled } => self
+                .set_template_node(*node, *enabled)
+                .map(|_| CommandOutput::Done),
+        }
+    }
+}
+
+// Commands from one graph version to another, with the ids they are expected to create.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct GraphDelta {
+    pub commands: Vec<GraphCommand>,
+    created_nodes: Vec<NodeId>,
+    created_pins: Vec<PinId>,
+}
+
+impl GraphDelta {
+    // None for keyframe, subnet, project setting or custom node changes.
+    pub fn between(base: &Graph, current: &Graph) -> Option<Self> {
+        if base.project_params() != current.project_params()
+            || base.symmetry() != current.symmetry()
+            || base.units() != current.units()
+            || base.seeds() != current.seeds()
+            || base.error_policy() != current.error_policy()
+        {
+            return None;
+        }
+        let mut delta = Self::default();
+        for node in base.nodes() {
+            if current.node(node.id).is_none() {
+                delta
+                    .commands
+                    .push(GraphCommand::RemoveNode { node: node.id });
+            }
+        }
+        for node in current.nodes() {
+            match base.node(node.id) {
+                Some(old) => {
+                    if !node.inputs.starts_with(&old.inputs) || node.outputs != old.outputs {
+                        return None;
+                    }
+                    delta.add_inputs(node, old.inputs.len());
+                    delta.edit_node(old, node)?;
+                }
+                None => {
+                    let mut scratch = Graph::default();
+                    let CommandOutput::Node(fresh) = scratch
+                        .apply(&GraphCommand::AddNode {
+                            kind: node.name.clone(),
+                        })
+                        .ok()?
+                    else {
+                        return None;
+                    };
+                    let fresh = scratch.node(fresh)?;
+                    if node.inputs.len() < fresh.inputs.len()
+                        || node.outputs.len() != fresh.outputs.len()
+                    {
+                        return None;
+                    }
+                    delta.commands.push(GraphCommand::AddNode {
+                        kind: node.name.clone(),
+                    });
+                    delta.created_nodes.push(node.id);
+                    let pins = node.inputs[..fresh.inputs.len()].iter();
+                    delta.created_pins.extend(pins.chain(&node.outputs));
+                    delta.add_inputs(node, fresh.inputs.len());
+                    delta.edit_node(fresh, node)?;
+                }
+            }
+        }
+
+        let old_links: BTreeSet<(PinId, PinId)> =
+            base.links().map(|link| (link.from, link.to)).collect();
+        let new_links: BTreeSet<(PinId, PinId)> =
+            current.links().map(|link| (link.from, link.to)).collect();
+        for &(from, to) in old_links.difference(&new_links) {
+            if current.pin(from).is_some() && current.pin(to).is_some() {
+                delta.commands.push(GraphCommand::Disconnect { from, to });
+            }
+        }
+        for &(from, to) in new_links.difference(&old_links) {
+            delta.commands.push(GraphCommand::Connect { from, to });
+        }
+        if base.display_node() != current.display_node() {
+            delta.commands.push(GraphCommand::SetDisplay {
+                node: current.display_node(),
+            });
+        }
+        Some(delta)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    fn add_inputs(&mut self, node: &Node, existing: usize) {
+        for pin in &node.inputs[existing..] {
+            self.commands.push(GraphCommand::AddInput { node: node.id });
+            self.created_pins.push(*pin);
+        }
+    }
+
+    fn edit_node(&mut self, old: &Node, node: &Node) -> Option<()> {
+        let unchanged_subnet = match (&old.subnet, &node.subnet) {
+            (None, None) => true,
+            (Some(old), Some(new)) => Self::between(old, new).is_some_and(|d| d.is_empty()),
+            _ => false,
+        };
+        if old.name != node.name
+            || old.param_keyframes != node.param_keyframes
+            || old.display_output != node.display_output
+            || old.error_policy != node.error_policy
+            || !unchanged_subnet
+            || old
+                .params
+                .values
+                .keys()
+                .any(|key| !node.params.values.contains_key(key))
+        {
+            return None;
+        }
+        for (key, value) in &node.params.values {
+            if old.params.values.get(key) != Some(value) {
+                self.commands.push(GraphCommand::SetParam {
+                    node: node.id,
+                    key: key.clone(),
+                    value: value.clone(),
+                });
+            }
+        }
+        let keys: BTreeSet<&String> = old
+            .param_expressions
+            .keys()
+            .chain(node.param_expressions.keys())
+            .collect();
+        for key in keys {
+            let expression = node.param_expressions.get(key);
+            if old.param_expressions.get(key) != expression {
+                self.commands.push(GraphCommand::SetExpression {
+                    node: node.id,
+                    key: key.clone(),
+                    expression: expression.cloned(),
+                });
+            }
+        }
+        if old.template != node.template {
+            self.commands.push(GraphCommand::SetTemplate {
+                node: node.id,
+                enabled: node.template,
+            });
+        }
+        Some(())
+    }
+}
+
+impl Graph {
+    // All or nothing; fails with `IdsDiverged` when this graph allocates different ids.
+    pub fn apply_delta(&mut self, delta: &GraphDelta) -> Result<(), GraphError> {
+        let mut edited = self.clone();
+        let mut nodes = Vec::new();
+        let mut pins = Vec::new();
+        for command in &delta.commands {
+            match edited.apply(command)? {
+                CommandOutput::Node(node_id) => {
+                    let node = edited
+                        .node(node_id)
+                        .ok_or(GraphError::MissingNode(node_id))?;
+                    nodes.push(node_id);
+                    pins.extend(node.inputs.iter().chain(&node.outputs));
+                }
+                CommandOutput::Pin(pin_id) => pins.push(pin_id),
+                CommandOutput::Done | CommandOutput::Link(_) => continue,
+            }
+            // Checked as they are made, since later commands refer to them.
+            if !delta.created_nodes.starts_with(&nodes) || !delta.created_pins.starts_with(&pins) {
+                return Err(GraphError::IdsDiverged);
+            }
+        }
+        if nodes != delta.created_nodes || pins != delta.created_pins {
+            return Err(GraphError::IdsDiverged);
+        }
+        *self = edited;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build(graph: &mut Graph) -> Result<(NodeId, NodeId), GraphError> {
+        let node = |output| match output {
+            CommandOutput::Node(node) => node,
+            other => panic!("expected a node, got {:?}", other),
+        };
+        let source = node(graph.apply(&GraphCommand::AddNode {
+            kind: "Box".to_string(),
+        })?);
+        let target = node(graph.apply(&GraphCommand::AddNode {
+            kind: "Transform".to_string(),
+        })?);
+        let from = graph.node(source).unwrap().outputs[0];
+        let to = graph.node(target).unwrap().inputs[0];
+        for command in [
+            GraphCommand::SetParam {
+                node: source,
+                key: "size".to_string(),
+                value: ParamValue::Vec3([2.0, 2.0, 2.0]),
+            },
+            GraphCommand::Connect { from, to },
+            GraphCommand::SetDisplay { node: Some(target) },
+        ] {
+            graph.apply(&command)?;
+        }
+        Ok((source, target))
+    }
+
+    #[test]
+    fn commands_edit_the_graph_and_replay_to_the_same_ids() {
+        let mut graph = Graph::default();
+        let (source, target) = build(&mut graph).unwrap();
+        assert_eq!(graph.links().count(), 1);
+        assert_eq!(graph.display_node(), Some(target));
+        assert_eq!(
+            graph.node(source).unwrap().params.values.get("size"),
+            Some(&ParamValue::Vec3([2.0, 2.0, 2.0]))
+        );
+        // Defaults come with the node, as they do when it is added from the menu.
+        assert!(graph
+            .node(target)
+            .unwrap()
+            .params
+            .values
+            .contains_key("translate"));
+
+        let mut replay = Graph::default();
+        assert_eq!(build(&mut replay).unwrap(), (source, target));
+
+        assert!(matches!(
+            graph.apply(&GraphCommand::AddNode {
+                kind: "Nope".to_string()
+            }),
+            Err(GraphError::UnknownNodeKind(_))
+        ));
+        assert!(graph
+            .apply(&GraphCommand::SetParam {
+                node: source,
+                key: "size".to_string(),
+                value: ParamValue::Bool(true),
+            })
+            .is_err());
+        graph
+            .apply(&GraphCommand::RemoveNode { node: source })
+            .unwrap();
+        assert_eq!(graph.links().count(), 0);
+        assert!(graph
+            .apply(&GraphCommand::RemoveNode { node: source })
+            .is_err());
+
+        // An add that is rolled back hands its ids to the next one.
+        let sphere = GraphCommand::AddNode {
+            kind: "Sphere".to_string(),
+        };
+        let CommandOutput::Node(added) = graph.apply(&sphere).unwrap() else {
+            panic!("expected a node");
+        };
+        let outputs = graph.node(added).unwrap().outputs.clone();
+        graph.undo_add_node(added);
+        assert!(graph.node(added).is_none() && graph.pin(outputs[0]).is_none());
+        assert_eq!(graph.apply(&sphere).unwrap(), CommandOutput::Node(added));
+        assert_eq!(graph.node(added).unwrap().outputs, outputs);
+    }
+
+    #[test]
+    fn every_builtin_node_adds_through_a_command() {
+        let mut graph = Graph::default();
+        for definition in crate::nodes_builtin::builtin_definitions() {
+            let output = graph.apply(&GraphCommand::AddNode {
+                kind: definition.name.clone(),
+            });
+            assert!(
+                matches!(output, Ok(CommandOutput::Node(_))),
+                "{}: {:?}",
+                definition.name,
+                output
+            );
+        }
+    }
+
+    #[test]
+    fn deltas_carry_edits_to_a_copy_and_refuse_diverged_ids() {
+        let mut base = Graph::default();
+        let (source, target) = build(&mut base).unwrap();
+
+        let mut edited = base.clone();
+        let merge = edited
+            .apply(&GraphCommand::AddNode {
+                kind: "Merge".to_string(),
+            })
+            .unwrap();
+        let CommandOutput::Node(merge) = merge else {
+            panic!("expected a node");
+        };
+        edited.add_variadic_input(merge).unwrap();
+        let from = edited.node(target).unwrap().outputs[0];
+        let to = edited.node(merge).unwrap().inputs[0];
+        edited.add_link(from, to).unwrap();
+        edited
+            .set_param(source, "size", ParamValue::Vec3([3.0, 1.0, 1.0]))
+            .unwrap();
+        edited.set_display_node(Some(merge)).unwrap();
+
+        let delta = GraphDelta::between(&base, &edited).unwrap();
+        let mut peer = base.clone();
+        peer.apply_delta(&delta).unwrap();
+        assert!(GraphDelta::between(&peer, &edited).unwrap().is_empty());
+        assert_eq!(peer.display_node(), Some(merge));
+
+        // A peer that added a node of its own would give the new one a different id.
+        let mut diverged = base.clone();
+        diverged
+            .apply(&GraphCommand::AddNode {
+                kind: "Sphere".to_string(),
+            })
+            .unwrap();
+        assert!(matches!(
+            diverged.apply_delta(&delta),
+            Err(GraphError::IdsDiverged)
+        ));
+        assert!(diverged.node(merge).unwrap().name == "Sphere");
+    }
+}
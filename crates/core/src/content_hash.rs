@@ -0,0 +1,288 @@
+use std::collections::BTreeMap;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::graph::{NodeParams, ParamValue};
+use crate::keyframes::KeyframeTrack;
+use crate::mesh::Mesh;
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+const FNV_PRIME: u64 = 0x0000_0100_0000_01b3;
+const FLOAT_STEPS: f32 = 65536.0;
+
+#[derive(Debug, Clone, Copy)]
+pub struct StableHasher {
+    state: u64,
+}
+
+impl Default for StableHasher {
+    fn default() -> Self {
+        Self { state: FNV_OFFSET }
+    }
+}
+
+impl StableHasher {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn finish(&self) -> u64 {
+        self.state
+    }
+
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.state ^= u64::from(*byte);
+            self.state = self.state.wrapping_mul(FNV_PRIME);
+        }
+    }
+
+    pub fn write_u8(&mut self, value: u8) {
+        self.write_bytes(&[value]);
+    }
+
+    pub fn write_u64(&mut self, value: u64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_i64(&mut self, value: i64) {
+        self.write_bytes(&value.to_le_bytes());
+    }
+
+    pub fn write_len(&mut self, len: usize) {
+        self.write_u64(len as u64);
+    }
+
+    pub fn write_str(&mut self, value: &str) {
+        self.write_len(value.len());
+        self.write_bytes(value.as_bytes());
+    }
+
+    pub fn write_f32(&mut self, value: f32) {
+        self.write_i64(quantize(value));
+    }
+
+    pub fn write_floats(&mut self, values: &[f32]) {
+        self.write_len(values.len());
+        for value in values {
+            self.write_f32(*value);
+        }
+    }
+}
+
+pub fn quantize(value: f32) -> i64 {
+    if value.is_nan() {
+        return i64::MIN;
+    }
+    (f64::from(value) * f64::from(FLOAT_STEPS)).round() as i64
+}
+
+pub fn mesh_content_hash(mesh: &Mesh) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_floats(mesh.positions.as_flattened());
+    hasher.write_len(mesh.indices.len());
+    for idx in &mesh.indices {
+        hasher.write_u64(u64::from(*idx));
+    }
+    hasher.write_len(mesh.face_counts.len());
+    for count in &mesh.face_counts {
+        hasher.write_u64(u64::from(*count));
+    }
+    write_optional(
+        &mut hasher,
+        mesh.normals.as_deref().map(<[_]>::as_flattened),
+    );
+    write_optional(
+        &mut hasher,
+        mesh.corner_normals.as_deref().map(<[_]>::as_flattened),
+    );
+    write_optional(&mut hasher, mesh.uvs.as_deref().map(<[_]>::as_flattened));
+
+    for domain in [
+        AttributeDomain::Point,
+        AttributeDomain::Vertex,
+        AttributeDomain::Primitive,
+        AttributeDomain::Detail,
+    ] {
+        let map = mesh.attributes.map(domain);
+        let mut names: Vec<&String> = map.keys().collect();
+        names.sort();
+        hasher.write_len(names.len());
+        for name in names {
+            hasher.write_str(name);
+            write_storage(&mut hasher, &map[name]);
+            hasher.write_u8(mesh.attributes.precision(domain, name) as u8);
+        }
+    }
+    hasher.finish()
+}
+
+pub fn params_content_hash(params: &NodeParams) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_len(params.values.len());
+    for (key, value) in &params.values {
+        hasher.write_str(key);
+        write_param(&mut hasher, value);
+    }
+    hasher.finish()
+}
+
+pub(crate) fn keyframes_content_hash(tracks: &BTreeMap<String, KeyframeTrack>) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_len(tracks.len());
+    for (key, track) in tracks {
+        hasher.write_str(key);
+        hasher.write_len(track.keys.len());
+        for keyframe in &track.keys {
+            hasher.write_f32(keyframe.frame);
+            write_param(&mut hasher, &keyframe.value);
+        }
+    }
+    hasher.finish()
+}
+
+fn write_optional(hasher: &mut StableHasher, values: Option<&[f32]>) {
+    match values {
+        Some(values) => {
+            hasher.write_u8(1);
+            hasher.write_floats(values);
+        }
+        None => hasher.write_u8(0),
+    }
+}
+
+fn write_storage(hasher: &mut StableHasher, storage: &AttributeStorage) {
+    match storage {
+        AttributeStorage::Float(values) => {
+            hasher.write_u8(0);
+            hasher.write_floats(values);
+        }
+        AttributeStorage::Int(values) => {
+            hasher.write_u8(1);
+            hasher.write_len(values.len());
+            for value in values {
+                hasher.write_i64(i64::from(*value));
+            }
+        }
+        AttributeStorage::Vec2(values) => {
+            hasher.write_u8(2);
+            hasher.write_floats(values.as_flattened());
+        }
+        AttributeStorage::Vec3(values) => {
+            hasher.write_u8(3);
+            hasher.write_floats(values.as_flattened());
+        }
+        AttributeStorage::Vec4(values) => {
+            hasher.write_u8(4);
+            hasher.write_floats(values.as_flattened());
+        }
+    }
+}
+
+fn write_param(hasher: &mut StableHasher, value: &ParamValue) {
+    match value {
+        ParamValue::Float(v) => {
+            hasher.write_u8(0);
+            hasher.write_f32(*v);
+        }
+        ParamValue::Int(v) => {
+            hasher.write_u8(1);
+            hasher.write_i64(i64::from(*v));
+        }
+        ParamValue::Bool(v) => {
+            hasher.write_u8(2);
+            hasher.write_u8(u8::from(*v));
+        }
+        ParamValue::Vec2(v) => {
+            hasher.write_u8(3);
+            hasher.write_floats(v);
+        }
+        ParamValue::Vec3(v) => {
+            hasher.write_u8(4);
+            hasher.write_floats(v);
+        }
+        ParamValue::String(v) => {
+            hasher.write_u8(5);
+            hasher.write_str(v);
+        }
+        ParamValue::Quat(v) => {
+            hasher.write_u8(6);
+            hasher.write_floats(v);
+        }
+        ParamValue::Mat4(v) => {
+            hasher.write_u8(7);
+            hasher.write_floats(v);
+        }
+        ParamValue::StringList(v) => {
+            hasher.write_u8(8);
+            hasher.write_u64(v.len() as u64);
+            for item in v {
+                hasher.write_str(item);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeMap;
+
+    use super::*;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn mesh_hash_is_stable_and_quantized() {
+        let mesh = make_box([1.0, 2.0, 3.0]);
+        assert_eq!(mesh_content_hash(&mesh), mesh_content_hash(&mesh.clone()));
+
+        let mut jittered = mesh.clone();
+        jittered.positions[0][0] += 1.0e-7;
+        assert_eq!(mesh_content_hash(&mesh), mesh_content_hash(&jittered));
+
+        let mut moved = mesh.clone();
+        moved.positions[0][0] += 0.01;
+        assert_ne!(mesh_content_hash(&mesh), mesh_content_hash(&moved));
+    }
+
+    #[test]
+    fn mesh_hash_matches_known_value() {
+        let mesh = Mesh::with_positions_indices(
+            vec![[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.5]],
+            vec![0, 1, 2],
+        );
+        assert_eq!(mesh_content_hash(&mesh), 0x3d36_8508_c2a0_97e4);
+    }
+
+    #[test]
+    fn attribute_order_does_not_change_hash() {
+        let mut a = make_box([1.0, 1.0, 1.0]);
+        let mut b = a.clone();
+        let ones = AttributeStorage::Float(vec![1.0; 8]);
+        let twos = AttributeStorage::Int(vec![2; 8]);
+        a.set_attribute(AttributeDomain::Point, "a", ones.clone())
+            .unwrap();
+        a.set_attribute(AttributeDomain::Point, "b", twos.clone())
+            .unwrap();
+        b.set_attribute(AttributeDomain::Point, "b", twos).unwrap();
+        b.set_attribute(AttributeDomain::Point, "a", ones).unwrap();
+        assert_eq!(mesh_content_hash(&a), mesh_content_hash(&b));
+    }
+
+    #[test]
+    fn params_hash_ignores_signed_zero() {
+        let params = |x: f32| NodeParams {
+            values: BTreeMap::from([
+                ("size".to_string(), ParamValue::Vec3([x, 1.0, 2.0])),
+                ("name".to_string(), ParamValue::String("box".to_string())),
+            ]),
+        };
+        assert_eq!(
+            params_content_hash(&params(0.0)),
+            params_content_hash(&params(-0.0))
+        );
+        assert_ne!(
+            params_content_hash(&params(0.0)),
+            params_content_hash(&params(0.5))
+        );
+        assert_eq!(params_content_hash(&params(0.25)), 0xde4f_28b9_5d80_ca7c);
+    }
+}
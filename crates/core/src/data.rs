@@ -0,0 +1,137 @@
+use std::sync::Arc;
+
+use crate::graph::{ParamValue, PinType};
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PointCloud {
+    pub positions: Vec<[f32; 3]>,
+}
+
+impl PointCloud {
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        Self {
+            positions: mesh.positions.clone(),
+        }
+    }
+
+    pub fn to_mesh(&self) -> Mesh {
+        Mesh::with_positions_indices(self.positions.clone(), Vec::new())
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Volume {
+    pub origin: [f32; 3],
+    pub voxel_size: f32,
+    pub dims: [u32; 3],
+    pub values: Vec<f32>,
+}
+
+impl Volume {
+    pub fn new(origin: [f32; 3], voxel_size: f32, dims: [u32; 3]) -> Self {
+        let len = dims[0] as usize * dims[1] as usize * dims[2] as usize;
+        Self {
+            origin,
+            voxel_size,
+            dims,
+            values: vec![0.0; len],
+        }
+    }
+
+    pub fn index(&self, x: u32, y: u32, z: u32) -> Option<usize> {
+        if x >= self.dims[0] || y >= self.dims[1] || z >= self.dims[2] {
+            return None;
+        }
+        Some((z as usize * self.dims[1] as usize + y as usize) * self.dims[0] as usize + x as usize)
+    }
+
+    pub fn value(&self, x: u32, y: u32, z: u32) -> Option<f32> {
+        self.index(x, y, z)
+            .and_then(|index| self.values.get(index).copied())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub enum Data {
+    // Shared so cloning an eval state does not copy every cooked mesh.
+    Mesh(Arc<Mesh>),
+    PointCloud(PointCloud),
+    Volume(Volume),
+    Float(f32),
+    Int(i32),
+    Bool(bool),
+    Vec2([f32; 2]),
+    Vec3([f32; 3]),
+}
+
+impl Data {
+    pub fn pin_type(&self) -> PinType {
+        match self {
+            Data::Mesh(_) => PinType::Mesh,
+            Data::PointCloud(_) => PinType::PointCloud,
+            Data::Volume(_) => PinType::Volume,
+            Data::Float(_) => PinType::Float,
+            Data::Int(_) => PinType::Int,
+            Data::Bool(_) => PinType::Bool,
+            Data::Vec2(_) => PinType::Vec2,
+            Data::Vec3(_) => PinType::Vec3,
+        }
+    }
+
+    pub fn as_mesh(&self) -> Option<&Mesh> {
+        match self {
+            Data::Mesh(mesh) => Some(mesh),
+            _ => None,
+        }
+    }
+
+    pub fn into_mesh(self) -> Result<Mesh, String> {
+        match self {
+            Data::Mesh(mesh) => Ok(Arc::unwrap_or_clone(mesh)),
+            Data::PointCloud(points) => Ok(points.to_mesh()),
+            other => Err(format!("expected mesh data, got {:?}", other.pin_type())),
+        }
+    }
+
+    pub fn from_param(value: &ParamValue) -> Option<Self> {
+        match value {
+            ParamValue::Float(v) => Some(Data::Float(*v)),
+            ParamValue::Int(v) => Some(Data::Int(*v)),
+            ParamValue::Bool(v) => Some(Data::Bool(*v)),
+            ParamValue::Vec2(v) => Some(Data::Vec2(*v)),
+            ParamValue::Vec3(v) => Some(Data::Vec3(*v)),
+            ParamValue::String(_)
+            | ParamValue::Quat(_)
+            | ParamValue::Mat4(_)
+            | ParamValue::StringList(_) => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Data, PointCloud, Volume};
+    use crate::graph::{ParamValue, PinType};
+
+    #[test]
+    fn data_reports_pin_types_and_converts_to_mesh() {
+        let points = PointCloud {
+            positions: vec![[0.0, 0.0, 0.0], [1.0, 2.0, 3.0]],
+        };
+        let mesh = Data::PointCloud(points).into_mesh().unwrap();
+        assert_eq!(mesh.positions.len(), 2);
+        assert!(mesh.indices.is_empty());
+
+        let value = Data::from_param(&ParamValue::Vec3([1.0, 2.0, 3.0])).unwrap();
+        assert_eq!(value.pin_type(), PinType::Vec3);
+        assert!(value.into_mesh().is_err());
+
+        let mut volume = Volume::new([0.0; 3], 0.5, [2, 3, 4]);
+        assert_eq!(volume.values.len(), 24);
+        let index = volume.index(1, 2, 3).unwrap();
+        volume.values[index] = 1.0;
+        assert_eq!(volume.value(1, 2, 3), Some(1.0));
+        assert_eq!(volume.value(2, 0, 0), None);
+    }
+}
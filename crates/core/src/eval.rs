@@ -6,7 +6,9 @@ use std::time::Instant;
 #[cfg(target_arch = "wasm32")]
 use web_time::Instant;
 
-use crate::graph::{Graph, GraphError, NodeId, NodeParams};
+use crate::content_hash::params_content_hash;
+use crate::graph::{Graph, GraphError, Node, NodeId, NodeParams, PinId};
+use crate::param_expr::{expression_signature, reads_time, resolve_params};
 
 #[derive(Debug, Clone, Copy, Default)]
 pub struct EvalCacheStats {
@@ -14,16 +16,17 @@ pub struct EvalCacheStats {
     pub misses: u64,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct EvalState {
     nodes: BTreeMap<NodeId, NodeEvalState>,
     pub stats: EvalCacheStats,
 }
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 struct NodeEvalState {
     last_signature: u64,
     last_param_version: u64,
+    last_expression_signature: u64,
     last_upstream_signature: u64,
     initialized: bool,
     output_version: u64,
@@ -38,6 +41,7 @@ pub struct EvalReport {
     pub node_reports: BTreeMap<NodeId, EvalNodeReport>,
     pub dirty: Vec<DirtyNodeReport>,
     pub errors: Vec<EvalError>,
+    pub warnings: Vec<EvalWarning>,
     pub output_valid: bool,
 }
 
@@ -54,6 +58,8 @@ pub struct EvalNodeReport {
 pub enum DirtyReason {
     NewNode,
     ParamChanged,
+    // Only parameters the node's expressions reference changed.
+    ReferenceChanged,
     UpstreamChanged,
     ParamAndUpstreamChanged,
 }
@@ -70,6 +76,13 @@ pub enum EvalError {
     Upstream { node: NodeId, upstream: Vec<NodeId> },
 }
 
+// A node that failed but recovered under its error policy; the message is the original error.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EvalWarning {
+    pub node: NodeId,
+    pub message: String,
+}
+
 impl EvalState {
     pub fn new() -> Self {
         Self::default()
@@ -108,6 +121,8 @@ where
         ..Default::default()
     };
     let mut failed_nodes = Vec::<NodeId>::new();
+    let links_by_input: BTreeMap<PinId, PinId> =
+        graph.links().map(|link| (link.to, link.from)).collect();
 
     for node_id in &report.ordered {
         let node = graph
@@ -116,14 +131,28 @@ where
         let mut upstream = graph.upstream_nodes(*node_id);
         upstream.sort();
 
-        let mut upstream_versions = Vec::with_capacity(upstream.len());
-        for upstream_id in &upstream {
-            let upstream_state = state.node_state_mut(*upstream_id);
-            upstream_versions.push((*upstream_id, upstream_state.output_version));
+        let mut upstream_versions = Vec::with_capacity(node.inputs.len());
+        for input in &node.inputs {
+            let Some(&from) = links_by_input.get(input) else {
+                continue;
+            };
+            let Some(from_pin) = graph.pin(from) else {
+                continue;
+            };
+            let upstream_state = state.node_state_mut(from_pin.node);
+            upstream_versions.push((from, upstream_state.output_version));
         }
 
+        let resolved_params = resolve_params(graph, node);
+        let expression_signature = resolved_params
+            .as_ref()
+            .map(|params| expression_signature(params, &node.param_expressions))
+            .unwrap_or_default()
+            ^ subnet_signature(graph, node)
+            ^ time_signature(graph, node);
         let upstream_signature = hash_upstream(&upstream_versions);
-        let signature = hash_signature(node.param_version, &upstream_versions);
+        let signature =
+            hash_signature(node.param_version, expression_signature, &upstream_versions);
         let (last_signature, output_version) = {
             let node_state = state.node_state_mut(*node_id);
             (node_state.last_signature, node_state.output_version)
@@ -161,10 +190,13 @@ where
             } else if last_signature == signature {
                 None
             } else {
-                let param_changed = node.param_version != node_state.last_param_version;
+                let own_changed = node.param_version != node_state.last_param_version;
+                let reference_changed =
+                    expression_signature != node_state.last_expression_signature;
                 let upstream_changed = upstream_signature != node_state.last_upstream_signature;
-                match (param_changed, upstream_changed) {
+                match (own_changed || reference_changed, upstream_changed) {
                     (true, true) => Some(DirtyReason::ParamAndUpstreamChanged),
+                    (true, false) if !own_changed => Some(DirtyReason::ReferenceChanged),
                     (true, false) => Some(DirtyReason::ParamChanged),
                     (false, true) => Some(DirtyReason::UpstreamChanged),
                     (false, false) => None,
@@ -185,6 +217,7 @@ where
             node_report.output_version = output_version;
             let node_state = state.node_state_mut(*node_id);
             node_state.last_param_version = node.param_version;
+            node_state.last_expression_signature = expression_signature;
             node_state.last_upstream_signature = upstream_signature;
             node_state.initialized = true;
             report.node_reports.insert(*node_id, node_report);
@@ -192,7 +225,7 @@ where
         }
 
         let start = Instant::now();
-        let compute_result = compute(*node_id, &node.params);
+        let compute_result = resolved_params.and_then(|params| compute(*node_id, &params));
         node_report.duration_ms = start.elapsed().as_secs_f32() * 1000.0;
 
         match compute_result {
@@ -200,6 +233,7 @@ where
                 let node_state = state.node_state_mut(*node_id);
                 node_state.last_signature = signature;
                 node_state.last_param_version = node.param_version;
+                node_state.last_expression_signature = expression_signature;
                 node_state.last_upstream_signature = upstream_signature;
                 node_state.initialized = true;
                 node_state.output_version = node_state.output_version.wrapping_add(1);
@@ -226,14 +260,45 @@ where
     Ok(report)
 }
 
-fn hash_signature(param_version: u64, upstream_versions: &[(NodeId, u64)]) -> u64 {
+fn hash_signature(
+    param_version: u64,
+    expression_signature: u64,
+    upstream_versions: &[(PinId, u64)],
+) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     param_version.hash(&mut hasher);
+    expression_signature.hash(&mut hasher);
     upstream_versions.hash(&mut hasher);
     hasher.finish()
 }
 
-fn hash_upstream(upstream_versions: &[(NodeId, u64)]) -> u64 {
+// A subnet recooks when anything inside it changes, or any project param its nodes may read.
+fn subnet_signature(graph: &Graph, node: &Node) -> u64 {
+    let Some(child) = node.subnet.as_deref() else {
+        return 0;
+    };
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    child.revision().hash(&mut hasher);
+    params_content_hash(&NodeParams {
+        values: graph.project_params().clone(),
+    })
+    .hash(&mut hasher);
+    hasher.finish()
+}
+
+// Nodes that read `$F` or `$T` recook when the timeline moves, though no parameter changed.
+fn time_signature(graph: &Graph, node: &Node) -> u64 {
+    if !reads_time(node) {
+        return 0;
+    }
+    let time = graph.time();
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    time.frame.to_bits().hash(&mut hasher);
+    time.fps.to_bits().hash(&mut hasher);
+    hasher.finish()
+}
+
+fn hash_upstream(upstream_versions: &[(PinId, u64)]) -> u64 {
     let mut hasher = std::collections::hash_map::DefaultHasher::new();
     upstream_versions.hash(&mut hasher);
     hasher.finish()
@@ -257,6 +322,9 @@ mod tests {
             outputs: (0..outputs)
                 .map(|i| make_pin(&format!("out{}", i)))
                 .collect(),
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         }
     }
 
@@ -349,4 +417,36 @@ mod tests {
             .iter()
             .any(|err| matches!(err, EvalError::Upstream { node, .. } if *node == c)));
     }
+
+    #[test]
+    fn project_param_change_recomputes_expression_nodes() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_def("A", 0, 1));
+        let b = graph.add_node(node_def("B", 1, 0));
+        connect(&mut graph, a, b);
+        graph.set_param(a, "size", ParamValue::Float(1.0)).unwrap();
+        graph
+            .set_param_expression(a, "size", Some("$scale * 2".to_string()))
+            .unwrap();
+        graph.set_project_param("scale", ParamValue::Float(1.5));
+
+        let mut state = EvalState::new();
+        let mut seen = Vec::new();
+        evaluate_from_with(&graph, b, &mut state, |node_id, params| {
+            if node_id == a {
+                seen.push(params.get_float("size", 0.0));
+            }
+            Ok(())
+        })
+        .unwrap();
+        assert_eq!(seen, vec![3.0]);
+
+        let report = evaluate_from(&graph, b, &mut state).unwrap();
+        assert_eq!(report.computed.len(), 0);
+
+        graph.set_project_param("scale", ParamValue::Float(2.0));
+        let report = evaluate_from(&graph, b, &mut state).unwrap();
+        assert_eq!(report.computed, vec![a, b]);
+        assert_eq!(report.dirty[0].reason, DirtyReason::ReferenceChanged);
+    }
 }
@@ -0,0 +1,155 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::Arc;
+
+use crate::content_hash::{
+    keyframes_content_hash, mesh_content_hash, params_content_hash, StableHasher,
+};
+use crate::graph::{Node, NodeParams};
+use crate::keyframes::FRAME_PARAM;
+use crate::mesh::Mesh;
+
+// Older frames are dropped past this and cook again when revisited.
+const MAX_CACHED_FRAMES: usize = 500;
+
+#[derive(Debug, Clone)]
+struct CachedFrame {
+    input_key: u64,
+    outputs: Vec<Arc<Mesh>>,
+}
+
+// Cooked outputs of one time-dependent node by frame, each with the key it was cooked from.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct FrameCache {
+    key: u64,
+    frames: BTreeMap<i32, CachedFrame>,
+}
+
+impl FrameCache {
+    // Drops every frame when the frame-independent key changed.
+    pub(crate) fn sync(&mut self, key: u64) {
+        if key != self.key {
+            self.key = key;
+            self.frames.clear();
+        }
+    }
+
+    // A mismatched frame means an edit that may reach any frame, so the whole range goes.
+    pub(crate) fn get(&mut self, frame: i32, input_key: u64) -> Option<Vec<Mesh>> {
+        let cached = self.frames.get(&frame)?;
+        if cached.input_key != input_key {
+            self.frames.clear();
+            return None;
+        }
+        Some(
+            cached
+                .outputs
+                .iter()
+                .map(|mesh| Mesh::clone(mesh))
+                .collect(),
+        )
+    }
+
+    // The latest frame in `start..frame`, for simulations to step on from.
+    pub(crate) fn latest_before(&self, start: i32, frame: i32) -> Option<(i32, &Mesh)> {
+        self.frames
+            .range(start..frame)
+            .next_back()
+            .and_then(|(frame, cached)| Some((*frame, cached.outputs.first()?.as_ref())))
+    }
+
+    pub(crate) fn insert(&mut self, frame: i32, input_key: u64, outputs: Vec<Mesh>) {
+        let outputs = outputs.into_iter().map(Arc::new).collect();
+        self.frames
+            .insert(frame, CachedFrame { input_key, outputs });
+        if self.frames.len() > MAX_CACHED_FRAMES {
+            self.frames.pop_first();
+        }
+    }
+
+    pub(crate) fn frames(&self) -> BTreeSet<i32> {
+        self.frames.keys().copied().collect()
+    }
+}
+
+// Keyed and expression params count by their keys and expressions, not this frame's values.
+pub(crate) fn frame_independent_key(node: &Node, params: &NodeParams) -> u64 {
+    let mut stable = params.clone();
+    stable.values.remove(FRAME_PARAM);
+    for key in node
+        .param_keyframes
+        .keys()
+        .chain(node.param_expressions.keys())
+    {
+        stable.values.remove(key);
+    }
+    let mut hasher = StableHasher::new();
+    hasher.write_u64(params_content_hash(&stable));
+    hasher.write_len(node.param_expressions.len());
+    for (key, expression) in &node.param_expressions {
+        hasher.write_str(key);
+        hasher.write_str(expression);
+    }
+    hasher.write_u64(keyframes_content_hash(&node.param_keyframes));
+    hasher.finish()
+}
+
+// What one frame was cooked from: its inputs, resolved parameters and the frame rate.
+pub(crate) fn frame_input_key(inputs: &[Option<Mesh>], params: &NodeParams, fps: f32) -> u64 {
+    let mut hasher = StableHasher::new();
+    hasher.write_len(inputs.len());
+    for input in inputs {
+        hasher.write_u64(input.as_ref().map_or(0, mesh_content_hash));
+    }
+    hasher.write_u64(params_content_hash(params));
+    hasher.write_f32(fps);
+    hasher.finish()
+}
+
+// Only whole frames are cached; scrubbing can leave the timeline between two.
+pub(crate) fn cache_frame(frame: f32) -> Option<i32> {
+    (frame.fract() == 0.0).then_some(frame as i32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Graph, ParamValue};
+    use crate::mesh::make_box;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn edits_invalidate_cached_frames() {
+        let mut graph = Graph::default();
+        let wobble = graph.add_node(node_definition(BuiltinNodeKind::Wobble));
+        let node = graph.node(wobble).unwrap();
+        let mut params = NodeParams::default();
+        params
+            .values
+            .insert(FRAME_PARAM.to_string(), ParamValue::Float(3.0));
+        let mut cache = FrameCache::default();
+        cache.sync(frame_independent_key(node, &params));
+        for frame in 1..=3 {
+            cache.insert(frame, 7, vec![make_box([1.0; 3])]);
+        }
+        assert_eq!(cache.frames(), BTreeSet::from([1, 2, 3]));
+
+        // Moving along the timeline keeps the frames; the cached inputs must match.
+        params
+            .values
+            .insert(FRAME_PARAM.to_string(), ParamValue::Float(9.0));
+        cache.sync(frame_independent_key(node, &params));
+        assert!(cache.get(2, 7).is_some());
+        assert_eq!(cache.latest_before(1, 9).map(|(frame, _)| frame), Some(3));
+
+        // An upstream edit shows up as different inputs and drops the whole range.
+        assert!(cache.get(2, 8).is_none());
+        assert!(cache.frames().is_empty());
+
+        cache.insert(1, 7, vec![make_box([1.0; 3])]);
+        params
+            .values
+            .insert("amplitude".to_string(), ParamValue::Float(2.0));
+        cache.sync(frame_independent_key(node, &params));
+        assert!(cache.frames().is_empty());
+    }
+}
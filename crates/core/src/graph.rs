@@ -1,10 +1,27 @@
 use std::collections::{BTreeMap, HashSet};
+use std::hash::{Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 
+use crate::keyframes::{KeyframeTrack, PlaybackTime, FRAME_PARAM};
+use crate::node_tests::NodeTestCase;
+use crate::nodes_builtin::{
+    builtin_kind_from_name, default_params, node_definition, param_range, BuiltinNodeKind,
+};
+use crate::param_expr::{named_references, reads_time, retarget_references};
+use crate::seeds::SeedSettings;
+use crate::symmetry::{SymmetryPlane, MIRROR_PARAM};
+use crate::units::UnitSettings;
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct NodeId(u64);
 
+impl NodeId {
+    pub fn as_u64(self) -> u64 {
+        self.0
+    }
+}
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
 pub struct PinId(u64);
 
@@ -19,6 +36,19 @@ pub struct Graph {
     next_node_id: u64,
     next_pin_id: u64,
     next_link_id: u64,
+    #[serde(default)]
+    project_params: BTreeMap<String, ParamValue>,
+    #[serde(default)]
+    symmetry: Option<SymmetryPlane>,
+    #[serde(default)]
+    units: UnitSettings,
+    #[serde(default)]
+    seeds: SeedSettings,
+    #[serde(default)]
+    error_policy: ErrorPolicy,
+    // Set by `apply_keyframes`; the project timeline is what gets saved.
+    #[serde(skip)]
+    time: PlaybackTime,
 }
 
 impl Default for Graph {
@@ -30,6 +60,12 @@ impl Default for Graph {
             next_node_id: 1,
             next_pin_id: 1,
             next_link_id: 1,
+            project_params: BTreeMap::new(),
+            symmetry: None,
+            units: UnitSettings::default(),
+            seeds: SeedSettings::default(),
+            error_policy: ErrorPolicy::default(),
+            time: PlaybackTime::default(),
         }
     }
 }
@@ -43,6 +79,11 @@ impl Graph {
         self.nodes.get(&id)
     }
 
+    // Unchecked access for project migrations, which rewrite params saved under old ranges.
+    pub(crate) fn node_mut(&mut self, id: NodeId) -> Option<&mut Node> {
+        self.nodes.get_mut(&id)
+    }
+
     pub fn display_node(&self) -> Option<NodeId> {
         self.nodes
             .values()
@@ -83,6 +124,15 @@ impl Graph {
         }
     }
 
+    pub fn set_display_output(&mut self, node_id: NodeId, output: usize) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        node.display_output = output.min(node.outputs.len().saturating_sub(1));
+        Ok(())
+    }
+
     pub fn set_template_node(&mut self, node_id: NodeId, enabled: bool) -> Result<(), GraphError> {
         let node = self
             .nodes
@@ -101,12 +151,53 @@ impl Graph {
         Ok(())
     }
 
+    pub fn error_policy(&self) -> ErrorPolicy {
+        self.error_policy
+    }
+
+    pub fn set_error_policy(&mut self, policy: ErrorPolicy) -> bool {
+        if self.error_policy == policy {
+            return false;
+        }
+        self.error_policy = policy;
+        for node in self.nodes.values_mut() {
+            if node.error_policy.is_none() {
+                node.param_version = node.param_version.wrapping_add(1);
+            }
+        }
+        true
+    }
+
+    pub fn node_error_policy(&self, node_id: NodeId) -> ErrorPolicy {
+        self.nodes
+            .get(&node_id)
+            .and_then(|node| node.error_policy)
+            .unwrap_or(self.error_policy)
+    }
+
+    pub fn set_node_error_policy(
+        &mut self,
+        node_id: NodeId,
+        policy: Option<ErrorPolicy>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        if node.error_policy != policy {
+            node.error_policy = policy;
+            node.param_version = node.param_version.wrapping_add(1);
+        }
+        Ok(())
+    }
+
     pub fn pin(&self, id: PinId) -> Option<&Pin> {
         self.pins.get(&id)
     }
 
     pub fn add_node(&mut self, def: NodeDefinition) -> NodeId {
         let node_id = self.alloc_node_id();
+        let subnet = new_subnet_contents(&def.name);
         let mut input_ids = Vec::new();
         let mut output_ids = Vec::new();
 
@@ -146,12 +237,18 @@ impl Graph {
                 id: node_id,
                 name: def.name,
                 inputs: input_ids,
+                variadic_inputs: def.variadic_inputs,
                 outputs: output_ids,
                 params: NodeParams::default(),
                 category: def.category,
                 param_version: 0,
+                param_expressions: BTreeMap::new(),
+                param_keyframes: BTreeMap::new(),
                 display: false,
+                display_output: 0,
                 template: false,
+                error_policy: None,
+                subnet,
             },
         );
 
@@ -159,6 +256,7 @@ impl Graph {
     }
 
     pub fn remove_node(&mut self, node_id: NodeId) -> bool {
+        let references = named_references(self);
         let Some(node) = self.nodes.remove(&node_id) else {
             return false;
         };
@@ -173,10 +271,97 @@ impl Graph {
         for pin_id in pins_to_remove {
             self.pins.remove(&pin_id);
         }
+        retarget_references(self, references);
 
         true
     }
 
+    // Turns a node into another definition while keeping its id, flags and layout. Pins are
+    // matched by position; a wire survives while its pin still exists with the same type.
+    // Expressions and keyframes are kept only for keys present in `params`.
+    pub fn replace_node_definition(
+        &mut self,
+        node_id: NodeId,
+        def: NodeDefinition,
+        params: NodeParams,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let (old_inputs, old_outputs) = (node.inputs.clone(), node.outputs.clone());
+        let references = named_references(self);
+        let inputs = self.reuse_pins(node_id, &old_inputs, def.inputs, PinKind::Input);
+        let outputs = self.reuse_pins(node_id, &old_outputs, def.outputs, PinKind::Output);
+
+        let Some(node) = self.nodes.get_mut(&node_id) else {
+            return Err(GraphError::MissingNode(node_id));
+        };
+        if node.subnet.is_none() || node.name != def.name {
+            node.subnet = new_subnet_contents(&def.name);
+        }
+        node.name = def.name;
+        node.category = def.category;
+        node.inputs = inputs;
+        node.outputs = outputs;
+        node.variadic_inputs = def.variadic_inputs;
+        node.display_output = node
+            .display_output
+            .min(node.outputs.len().saturating_sub(1));
+        node.param_expressions
+            .retain(|key, _| params.values.contains_key(key));
+        node.param_keyframes
+            .retain(|key, _| params.values.contains_key(key));
+        node.params = params;
+        node.param_version = node.param_version.wrapping_add(1);
+        retarget_references(self, references);
+        Ok(())
+    }
+
+    fn reuse_pins(
+        &mut self,
+        node_id: NodeId,
+        old: &[PinId],
+        new: Vec<PinDefinition>,
+        kind: PinKind,
+    ) -> Vec<PinId> {
+        for pin_id in old.iter().skip(new.len()) {
+            self.remove_links_for_pin(*pin_id);
+            self.pins.remove(pin_id);
+        }
+        let mut ids = Vec::with_capacity(new.len());
+        for (index, def) in new.into_iter().enumerate() {
+            if let Some(pin_id) = old.get(index).copied() {
+                let type_changed = self
+                    .pins
+                    .get(&pin_id)
+                    .is_some_and(|pin| pin.pin_type != def.pin_type);
+                if type_changed {
+                    self.remove_links_for_pin(pin_id);
+                }
+                if let Some(pin) = self.pins.get_mut(&pin_id) {
+                    pin.name = def.name;
+                    pin.pin_type = def.pin_type;
+                }
+                ids.push(pin_id);
+            } else {
+                let pin_id = self.alloc_pin_id();
+                self.pins.insert(
+                    pin_id,
+                    Pin {
+                        id: pin_id,
+                        node: node_id,
+                        name: def.name,
+                        kind,
+                        pin_type: def.pin_type,
+                    },
+                );
+                ids.push(pin_id);
+            }
+        }
+        ids
+    }
+
     pub fn add_link(&mut self, from: PinId, to: PinId) -> Result<LinkId, GraphError> {
         let from_pin = self.pins.get(&from).ok_or(GraphError::MissingPin(from))?;
         let to_pin = self.pins.get(&to).ok_or(GraphError::MissingPin(to))?;
@@ -235,6 +420,118 @@ impl Graph {
         before - self.links.len()
     }
 
+    pub fn variadic_inputs(&self, node_id: NodeId) -> Option<VariadicInputs> {
+        let node = self.nodes.get(&node_id)?;
+        node.variadic_inputs.or_else(|| {
+            builtin_kind_from_name(&node.name)
+                .and_then(|kind| node_definition(kind).variadic_inputs)
+        })
+    }
+
+    pub fn add_variadic_input(&mut self, node_id: NodeId) -> Result<PinId, GraphError> {
+        let node = self
+            .nodes
+            .get(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let variadic = self
+            .variadic_inputs(node_id)
+            .filter(|variadic| node.inputs.len() < variadic.max)
+            .ok_or(GraphError::InputLimitReached(node_id))?;
+        let pin_type = node
+            .inputs
+            .first()
+            .and_then(|pin| self.pins.get(pin))
+            .map(|pin| pin.pin_type)
+            .unwrap_or(PinType::Mesh);
+        let index = node.inputs.len().max(variadic.min);
+
+        let pin_id = self.alloc_pin_id();
+        self.pins.insert(
+            pin_id,
+            Pin {
+                id: pin_id,
+                node: node_id,
+                name: variadic_pin_name(index),
+                kind: PinKind::Input,
+                pin_type,
+            },
+        );
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.inputs.push(pin_id);
+        }
+        Ok(pin_id)
+    }
+
+    pub fn compact_variadic_inputs(&mut self, node_id: NodeId) -> bool {
+        let Some(node) = self.nodes.get(&node_id) else {
+            return false;
+        };
+        let Some(variadic) = self.variadic_inputs(node_id) else {
+            return false;
+        };
+        let connected: HashSet<PinId> = self.links.values().map(|link| link.to).collect();
+        let mut inputs = node.inputs.clone();
+        let mut removed = Vec::new();
+        for index in (0..inputs.len()).rev() {
+            if inputs.len() <= variadic.min {
+                break;
+            }
+            if !connected.contains(&inputs[index]) {
+                removed.push(inputs.remove(index));
+            }
+        }
+        if removed.is_empty() {
+            return false;
+        }
+
+        for pin_id in removed {
+            self.pins.remove(&pin_id);
+        }
+        for (index, pin_id) in inputs.iter().enumerate() {
+            if let Some(pin) = self.pins.get_mut(pin_id) {
+                pin.name = variadic_pin_name(index);
+            }
+        }
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.inputs = inputs;
+        }
+        true
+    }
+
+    pub fn move_input(
+        &mut self,
+        node_id: NodeId,
+        pin_id: PinId,
+        index: usize,
+    ) -> Result<(), GraphError> {
+        if self.variadic_inputs(node_id).is_none() {
+            return Err(GraphError::InputsNotReorderable(node_id));
+        }
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let from = node
+            .inputs
+            .iter()
+            .position(|pin| *pin == pin_id)
+            .ok_or(GraphError::MissingPin(pin_id))?;
+        let to = index.min(node.inputs.len() - 1);
+        if from == to {
+            return Ok(());
+        }
+
+        let pin = node.inputs.remove(from);
+        node.inputs.insert(to, pin);
+        node.param_version = node.param_version.wrapping_add(1);
+        for (index, pin_id) in node.inputs.iter().enumerate() {
+            if let Some(pin) = self.pins.get_mut(pin_id) {
+                pin.name = variadic_pin_name(index);
+            }
+        }
+        Ok(())
+    }
+
     pub fn set_param(
         &mut self,
         node_id: NodeId,
@@ -246,6 +543,10 @@ impl Graph {
             .get_mut(&node_id)
             .ok_or(GraphError::MissingNode(node_id))?;
         let key = key.into();
+        let value = match builtin_kind_from_name(&node.name) {
+            Some(kind) => checked_param_value(node_id, kind, &key, value)?,
+            None => value,
+        };
         let changed = node
             .params
             .values
@@ -261,6 +562,307 @@ impl Graph {
         Ok(())
     }
 
+    pub fn set_param_keyframe(
+        &mut self,
+        node_id: NodeId,
+        key: impl Into<String>,
+        frame: f32,
+        value: ParamValue,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let key = key.into();
+        let value = match builtin_kind_from_name(&node.name) {
+            Some(kind) => checked_param_value(node_id, kind, &key, value)?,
+            None => value,
+        };
+        self.set_param(node_id, key.clone(), value.clone())?;
+        if let Some(node) = self.nodes.get_mut(&node_id) {
+            node.param_keyframes
+                .entry(key)
+                .or_default()
+                .set_key(frame, value);
+        }
+        Ok(())
+    }
+
+    pub fn remove_param_keyframe(
+        &mut self,
+        node_id: NodeId,
+        key: &str,
+        frame: f32,
+    ) -> Result<bool, GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let Some(track) = node.param_keyframes.get_mut(key) else {
+            return Ok(false);
+        };
+        let removed = track.remove_key(frame);
+        if track.keys.is_empty() {
+            node.param_keyframes.remove(key);
+        }
+        Ok(removed)
+    }
+
+    pub fn time(&self) -> PlaybackTime {
+        self.time
+    }
+
+    // Whether moving the timeline can change any output.
+    pub fn is_time_dependent(&self) -> bool {
+        self.nodes.values().any(|node| {
+            !node.param_keyframes.is_empty()
+                || builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::is_animated)
+                || reads_time(node)
+        })
+    }
+
+    // Moves the graph to `frame`: animated nodes get the frame, keyed parameters their
+    // values. Returns whether anything needs a new cook, including nodes that only read the
+    // time through `$F` or `$T`.
+    pub fn apply_keyframes(&mut self, frame: f32, fps: f32) -> bool {
+        let time = PlaybackTime { frame, fps };
+        let mut changed = time != self.time && self.nodes.values().any(reads_time);
+        self.time = time;
+        for node in self.nodes.values_mut() {
+            let mut node_changed = false;
+            let animated =
+                builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::is_animated);
+            if animated && !node.param_keyframes.contains_key(FRAME_PARAM) {
+                let value = ParamValue::Float(frame);
+                if node.params.values.get(FRAME_PARAM) != Some(&value) {
+                    node.params.values.insert(FRAME_PARAM.to_string(), value);
+                    node_changed = true;
+                }
+            }
+            for (key, track) in &node.param_keyframes {
+                let Some(value) = track.evaluate(frame) else {
+                    continue;
+                };
+                if node.params.values.get(key) != Some(&value) {
+                    node.params.values.insert(key.clone(), value);
+                    node_changed = true;
+                }
+            }
+            if node_changed {
+                node.param_version = node.param_version.wrapping_add(1);
+                changed = true;
+            }
+        }
+        changed
+    }
+
+    pub fn set_param_expression(
+        &mut self,
+        node_id: NodeId,
+        key: impl Into<String>,
+        expression: Option<String>,
+    ) -> Result<(), GraphError> {
+        let node = self
+            .nodes
+            .get_mut(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let key = key.into();
+        let changed = match &expression {
+            Some(expression) => node.param_expressions.get(&key) != Some(expression),
+            None => node.param_expressions.contains_key(&key),
+        };
+
+        if changed {
+            match expression {
+                Some(expression) => {
+                    node.param_expressions.insert(key, expression);
+                }
+                None => {
+                    node.param_expressions.remove(&key);
+                }
+            }
+            node.param_version = node.param_version.wrapping_add(1);
+        }
+
+        Ok(())
+    }
+
+    pub fn project_params(&self) -> &BTreeMap<String, ParamValue> {
+        &self.project_params
+    }
+
+    pub fn project_param(&self, name: &str) -> Option<&ParamValue> {
+        self.project_params.get(name)
+    }
+
+    pub fn set_project_param(&mut self, name: impl Into<String>, value: ParamValue) -> bool {
+        let name = name.into();
+        if !is_param_identifier(&name) {
+            return false;
+        }
+        if self.project_params.get(&name) == Some(&value) {
+            return false;
+        }
+        self.project_params.insert(name, value);
+        true
+    }
+
+    pub fn remove_project_param(&mut self, name: &str) -> bool {
+        self.project_params.remove(name).is_some()
+    }
+
+    pub fn symmetry(&self) -> Option<SymmetryPlane> {
+        self.symmetry
+    }
+
+    pub fn set_symmetry(&mut self, symmetry: Option<SymmetryPlane>) -> bool {
+        if self.symmetry == symmetry {
+            return false;
+        }
+        self.symmetry = symmetry;
+        for node in self.nodes.values_mut() {
+            if node.params.get_bool(MIRROR_PARAM, false) {
+                node.param_version = node.param_version.wrapping_add(1);
+            }
+        }
+        true
+    }
+
+    pub fn units(&self) -> UnitSettings {
+        self.units
+    }
+
+    pub fn set_units(&mut self, units: UnitSettings) -> bool {
+        if self.units == units {
+            return false;
+        }
+        let rescaled = self.units.import_scale() != units.import_scale();
+        self.units = units;
+        if rescaled {
+            for node in self.nodes.values_mut() {
+                if builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::uses_unit_scale)
+                {
+                    node.param_version = node.param_version.wrapping_add(1);
+                }
+            }
+        }
+        true
+    }
+
+    pub fn seeds(&self) -> SeedSettings {
+        self.seeds
+    }
+
+    pub fn set_seeds(&mut self, seeds: SeedSettings) -> bool {
+        if self.seeds == seeds {
+            return false;
+        }
+        self.seeds = seeds;
+        for node in self.nodes.values_mut() {
+            if builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::is_stochastic) {
+                node.param_version = node.param_version.wrapping_add(1);
+            }
+        }
+        true
+    }
+
+    pub fn subnet(&self, node_id: NodeId) -> Option<&Graph> {
+        self.nodes.get(&node_id)?.subnet.as_deref()
+    }
+
+    pub fn subnet_mut(&mut self, node_id: NodeId) -> Option<&mut Graph> {
+        self.nodes.get_mut(&node_id)?.subnet.as_deref_mut()
+    }
+
+    // Follows nested subnet nodes down from this graph; an empty path is the graph itself.
+    pub fn subnet_at(&self, path: &[NodeId]) -> Option<&Graph> {
+        path.iter()
+            .try_fold(self, |graph, node_id| graph.subnet(*node_id))
+    }
+
+    pub fn subnet_at_mut(&mut self, path: &[NodeId]) -> Option<&mut Graph> {
+        path.iter()
+            .try_fold(self, |graph, node_id| graph.subnet_mut(*node_id))
+    }
+
+    // The Subnet Input or Output nodes of this graph in pin order: by `index`, then by id.
+    pub fn subnet_ports(&self, kind: BuiltinNodeKind) -> Vec<NodeId> {
+        let mut ports: Vec<(i32, NodeId)> = self
+            .nodes
+            .values()
+            .filter(|node| node.name == kind.name())
+            .map(|node| (node.params.get_int("index", 0), node.id))
+            .collect();
+        ports.sort();
+        ports.into_iter().map(|(_, node_id)| node_id).collect()
+    }
+
+    // Rebuilds a subnet node's pins from its port nodes, reusing pins by position.
+    pub fn sync_subnet_pins(&mut self, node_id: NodeId) -> Result<(), GraphError> {
+        let child = self
+            .subnet(node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let port_pins = |kind: BuiltinNodeKind, default: &str| -> Vec<PinDefinition> {
+            child
+                .subnet_ports(kind)
+                .into_iter()
+                .filter_map(|port| child.node(port))
+                .map(|port| PinDefinition {
+                    name: port.params.get_string("name", default).to_string(),
+                    pin_type: PinType::Mesh,
+                })
+                .collect()
+        };
+        let inputs = port_pins(BuiltinNodeKind::SubnetInput, "in");
+        let outputs = port_pins(BuiltinNodeKind::SubnetOutput, "out");
+        let node = self
+            .nodes
+            .get(&node_id)
+            .ok_or(GraphError::MissingNode(node_id))?;
+        let (old_inputs, old_outputs) = (node.inputs.clone(), node.outputs.clone());
+        let inputs = self.reuse_pins(node_id, &old_inputs, inputs, PinKind::Input);
+        let outputs = self.reuse_pins(node_id, &old_outputs, outputs, PinKind::Output);
+        let Some(node) = self.nodes.get_mut(&node_id) else {
+            return Err(GraphError::MissingNode(node_id));
+        };
+        node.inputs = inputs;
+        node.outputs = outputs;
+        node.display_output = node
+            .display_output
+            .min(node.outputs.len().saturating_sub(1));
+        Ok(())
+    }
+
+    // Changes with any node, param or wire here or in a nested subnet.
+    pub fn revision(&self) -> u64 {
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        (self.error_policy as u8).hash(&mut hasher);
+        for node in self.nodes.values() {
+            node.id.hash(&mut hasher);
+            node.name.hash(&mut hasher);
+            node.param_version.hash(&mut hasher);
+            node.error_policy
+                .map(|policy| policy as u8)
+                .hash(&mut hasher);
+            node.subnet
+                .as_deref()
+                .map(Graph::revision)
+                .hash(&mut hasher);
+        }
+        for link in self.links.values() {
+            (link.from, link.to).hash(&mut hasher);
+        }
+        hasher.finish()
+    }
+
+    // Subnets cook as a copy of their contents that reads the outer project params.
+    pub(crate) fn with_project_params_of(&self, outer: &Graph) -> Graph {
+        let mut graph = self.clone();
+        graph.project_params = outer.project_params.clone();
+        graph.time = outer.time;
+        graph
+    }
+
     pub fn topo_sort_from(&self, output: NodeId) -> Result<Vec<NodeId>, GraphError> {
         if !self.nodes.contains_key(&output) {
             return Err(GraphError::MissingNode(output));
@@ -335,6 +937,18 @@ impl Graph {
         self.pins.get(&pin_id).map(|pin| pin.node)
     }
 
+    // Takes back the node `add_node` just returned, ids included.
+    pub(crate) fn undo_add_node(&mut self, node_id: NodeId) {
+        let Some(node) = self.nodes.remove(&node_id) else {
+            return;
+        };
+        for pin in node.inputs.iter().chain(&node.outputs) {
+            self.pins.remove(pin);
+        }
+        self.next_node_id = node_id.0;
+        self.next_pin_id -= (node.inputs.len() + node.outputs.len()) as u64;
+    }
+
     fn alloc_node_id(&mut self) -> NodeId {
         let id = self.next_node_id;
         self.next_node_id += 1;
@@ -361,13 +975,46 @@ pub struct Node {
     pub category: String,
     pub inputs: Vec<PinId>,
     pub outputs: Vec<PinId>,
+    #[serde(default)]
+    pub variadic_inputs: Option<VariadicInputs>,
     pub params: NodeParams,
     #[serde(default)]
     pub param_version: u64,
     #[serde(default)]
+    pub param_expressions: BTreeMap<String, String>,
+    #[serde(default)]
+    pub param_keyframes: BTreeMap<String, KeyframeTrack>,
+    #[serde(default)]
     pub display: bool,
     #[serde(default)]
+    pub display_output: usize,
+    #[serde(default)]
     pub template: bool,
+    // None follows the project policy.
+    #[serde(default)]
+    pub error_policy: Option<ErrorPolicy>,
+    // The nested graph of a Subnet node.
+    #[serde(default)]
+    pub subnet: Option<Box<Graph>>,
+}
+
+// What a node does when its cook fails. `PassThrough` forwards the first input unchanged and
+// reports a warning, so one broken branch doesn't blank everything downstream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorPolicy {
+    #[default]
+    Fail,
+    PassThrough,
+}
+
+impl ErrorPolicy {
+    pub fn label(self) -> &'static str {
+        match self {
+            ErrorPolicy::Fail => "Fail",
+            ErrorPolicy::PassThrough => "Pass through",
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -396,6 +1043,26 @@ impl NodeParams {
             .unwrap_or(default)
     }
 
+    pub fn get_quat(&self, key: &str, default: [f32; 4]) -> [f32; 4] {
+        self.values
+            .get(key)
+            .and_then(|value| match value {
+                ParamValue::Quat(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(default)
+    }
+
+    pub fn get_mat4(&self, key: &str, default: [f32; 16]) -> [f32; 16] {
+        self.values
+            .get(key)
+            .and_then(|value| match value {
+                ParamValue::Mat4(v) => Some(*v),
+                _ => None,
+            })
+            .unwrap_or(default)
+    }
+
     pub fn get_float(&self, key: &str, default: f32) -> f32 {
         self.values
             .get(key)
@@ -436,6 +1103,16 @@ impl NodeParams {
             })
             .unwrap_or(default)
     }
+
+    pub fn get_string_list(&self, key: &str) -> &[String] {
+        self.values
+            .get(key)
+            .and_then(|value| match value {
+                ParamValue::StringList(v) => Some(v.as_slice()),
+                _ => None,
+            })
+            .unwrap_or(&[])
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
@@ -446,6 +1123,28 @@ pub enum ParamValue {
     Vec2([f32; 2]),
     Vec3([f32; 3]),
     String(String),
+    // x, y, z, w
+    Quat([f32; 4]),
+    // Column-major
+    Mat4([f32; 16]),
+    // Paths, tags and the like; edited as rows.
+    StringList(Vec<String>),
+}
+
+impl ParamValue {
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            ParamValue::Float(_) => "Float",
+            ParamValue::Int(_) => "Int",
+            ParamValue::Bool(_) => "Bool",
+            ParamValue::Vec2(_) => "Vec2",
+            ParamValue::Vec3(_) => "Vec3",
+            ParamValue::String(_) => "String",
+            ParamValue::Quat(_) => "Quat",
+            ParamValue::Mat4(_) => "Mat4",
+            ParamValue::StringList(_) => "StringList",
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -454,6 +1153,21 @@ pub struct NodeDefinition {
     pub category: String,
     pub inputs: Vec<PinDefinition>,
     pub outputs: Vec<PinDefinition>,
+    pub variadic_inputs: Option<VariadicInputs>,
+    pub help: Option<NodeHelp>,
+    pub self_tests: Vec<NodeTestCase>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NodeHelp {
+    pub summary: String,
+    pub page: Option<String>,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct VariadicInputs {
+    pub min: usize,
+    pub max: usize,
 }
 
 #[derive(Debug, Clone)]
@@ -470,6 +1184,8 @@ pub enum PinType {
     Bool,
     Vec2,
     Vec3,
+    PointCloud,
+    Volume,
 }
 
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -498,10 +1214,96 @@ pub struct Link {
 pub enum GraphError {
     MissingNode(NodeId),
     MissingPin(PinId),
-    WrongPinDirection { from: PinId, to: PinId },
-    InputAlreadyConnected { to: PinId },
-    IncompatiblePinTypes { from: PinType, to: PinType },
+    WrongPinDirection {
+        from: PinId,
+        to: PinId,
+    },
+    InputAlreadyConnected {
+        to: PinId,
+    },
+    IncompatiblePinTypes {
+        from: PinType,
+        to: PinType,
+    },
     CycleDetected(Vec<NodeId>),
+    InputLimitReached(NodeId),
+    InputsNotReorderable(NodeId),
+    ParamTypeMismatch {
+        node: NodeId,
+        key: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+    UnknownNodeKind(String),
+    // A delta created different ids here than on the graph it was taken from.
+    IdsDiverged,
+}
+
+// A new subnet starts as a pass-through: one input wired to one output.
+fn new_subnet_contents(name: &str) -> Option<Box<Graph>> {
+    if builtin_kind_from_name(name) != Some(BuiltinNodeKind::Subnet) {
+        return None;
+    }
+    let mut graph = Graph::default();
+    let [input, output] =
+        [BuiltinNodeKind::SubnetInput, BuiltinNodeKind::SubnetOutput].map(|kind| {
+            let node_id = graph.add_node(node_definition(kind));
+            for (key, value) in default_params(kind).values {
+                let _ = graph.set_param(node_id, key, value);
+            }
+            node_id
+        });
+    let from = graph.nodes[&input].outputs[0];
+    let to = graph.nodes[&output].inputs[0];
+    graph.add_link(from, to).ok()?;
+    graph.set_display_node(Some(output)).ok()?;
+    Some(Box::new(graph))
+}
+
+fn variadic_pin_name(index: usize) -> String {
+    match u8::try_from(index) {
+        Ok(index) if index < 26 => char::from(b'a' + index).to_string(),
+        _ => format!("in{}", index),
+    }
+}
+
+fn checked_param_value(
+    node_id: NodeId,
+    kind: BuiltinNodeKind,
+    key: &str,
+    value: ParamValue,
+) -> Result<ParamValue, GraphError> {
+    let Some(declared) = default_params(kind).values.remove(key) else {
+        return Ok(value);
+    };
+    let value = match (&declared, value) {
+        (ParamValue::Float(_), ParamValue::Int(v)) => ParamValue::Float(v as f32),
+        (declared, value) if std::mem::discriminant(declared) == std::mem::discriminant(&value) => {
+            value
+        }
+        (declared, value) => {
+            return Err(GraphError::ParamTypeMismatch {
+                node: node_id,
+                key: key.to_string(),
+                expected: declared.type_name(),
+                found: value.type_name(),
+            })
+        }
+    };
+    Ok(match (param_range(kind, key), value) {
+        (Some((min, max)), ParamValue::Float(v)) => ParamValue::Float(v.clamp(min, max)),
+        (Some((min, max)), ParamValue::Int(v)) => ParamValue::Int(v.clamp(min as i32, max as i32)),
+        (_, value) => value,
+    })
+}
+
+fn is_param_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {}
+        _ => return false,
+    }
+    chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
 }
 
 #[cfg(test)]
@@ -520,6 +1322,9 @@ mod tests {
                 name: "out".to_string(),
                 pin_type: PinType::Mesh,
             }],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         }
     }
 
@@ -543,6 +1348,9 @@ mod tests {
                 name: "out".to_string(),
                 pin_type: PinType::Float,
             }],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         });
         let b = graph.add_node(NodeDefinition {
             name: "B".to_string(),
@@ -552,6 +1360,9 @@ mod tests {
                 pin_type: PinType::Mesh,
             }],
             outputs: vec![],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         });
 
         let from = graph.nodes.get(&a).unwrap().outputs[0];
@@ -590,9 +1401,155 @@ mod tests {
             outputs: (0..outputs)
                 .map(|i| make_pin(&format!("out{}", i)))
                 .collect(),
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         }
     }
 
+    #[test]
+    fn set_param_checks_declared_types_and_ranges() {
+        let mut graph = Graph::default();
+        let grid = graph.add_node(node_definition(BuiltinNodeKind::Grid));
+        let err = graph
+            .set_param(grid, "rows", ParamValue::Vec3([1.0, 2.0, 3.0]))
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            GraphError::ParamTypeMismatch {
+                expected: "Int",
+                found: "Vec3",
+                ..
+            }
+        ));
+        assert!(!graph.node(grid).unwrap().params.values.contains_key("rows"));
+
+        graph.set_param(grid, "rows", ParamValue::Int(-4)).unwrap();
+        assert_eq!(graph.node(grid).unwrap().params.get_int("rows", 0), 1);
+
+        let normal = graph.add_node(node_definition(BuiltinNodeKind::Normal));
+        graph
+            .set_param(normal, "threshold_deg", ParamValue::Int(400))
+            .unwrap();
+        assert_eq!(
+            graph
+                .node(normal)
+                .unwrap()
+                .params
+                .values
+                .get("threshold_deg"),
+            Some(&ParamValue::Float(180.0))
+        );
+    }
+
+    #[test]
+    fn variadic_inputs_grow_and_compact() {
+        let mut graph = Graph::default();
+        let merge = graph.add_node(node_definition(BuiltinNodeKind::Merge));
+        let sources: Vec<NodeId> = (0..3)
+            .map(|i| graph.add_node(node_def(&format!("S{}", i), 0, 1)))
+            .collect();
+        assert_eq!(graph.node(merge).unwrap().inputs.len(), 2);
+
+        let extra = graph.add_variadic_input(merge).unwrap();
+        assert_eq!(graph.pin(extra).unwrap().name, "c");
+        let inputs = graph.node(merge).unwrap().inputs.clone();
+        for (source, input) in sources.iter().zip(&inputs) {
+            let from = graph.node(*source).unwrap().outputs[0];
+            graph.add_link(from, *input).unwrap();
+        }
+
+        assert!(graph.remove_links_for_pin(inputs[1]) > 0);
+        assert!(graph.compact_variadic_inputs(merge));
+        let compacted = graph.node(merge).unwrap().inputs.clone();
+        assert_eq!(compacted, vec![inputs[0], inputs[2]]);
+        assert_eq!(graph.pin(inputs[2]).unwrap().name, "b");
+        assert!(graph.pin(inputs[1]).is_none());
+
+        assert!(graph.remove_links_for_pin(inputs[2]) > 0);
+        assert!(!graph.compact_variadic_inputs(merge));
+        assert_eq!(graph.node(merge).unwrap().inputs.len(), 2);
+    }
+
+    #[test]
+    fn move_input_reorders_merge_inputs() {
+        let mut graph = Graph::default();
+        let merge = graph.add_node(node_definition(BuiltinNodeKind::Merge));
+        graph.add_variadic_input(merge).unwrap();
+        let inputs = graph.node(merge).unwrap().inputs.clone();
+        let version = graph.node(merge).unwrap().param_version;
+
+        graph.move_input(merge, inputs[2], 0).unwrap();
+        let node = graph.node(merge).unwrap();
+        assert_eq!(node.inputs, vec![inputs[2], inputs[0], inputs[1]]);
+        assert_ne!(node.param_version, version);
+        assert_eq!(graph.pin(inputs[2]).unwrap().name, "a");
+        assert_eq!(graph.pin(inputs[1]).unwrap().name, "c");
+
+        let other = graph.add_node(node_def("Fixed", 2, 1));
+        let pin = graph.node(other).unwrap().inputs[1];
+        assert!(matches!(
+            graph.move_input(other, pin, 0),
+            Err(GraphError::InputsNotReorderable(_))
+        ));
+    }
+
+    #[test]
+    fn apply_keyframes_updates_params() {
+        let mut graph = Graph::default();
+        let sphere = graph.add_node(node_definition(BuiltinNodeKind::Sphere));
+        graph
+            .set_param_keyframe(sphere, "radius", 0.0, ParamValue::Float(1.0))
+            .unwrap();
+        graph
+            .set_param_keyframe(sphere, "radius", 10.0, ParamValue::Float(3.0))
+            .unwrap();
+        assert!(graph
+            .set_param_keyframe(sphere, "radius", 5.0, ParamValue::Bool(true))
+            .is_err());
+
+        let version = graph.node(sphere).unwrap().param_version;
+        assert!(graph.apply_keyframes(5.0, 24.0));
+        let node = graph.node(sphere).unwrap();
+        assert_eq!(node.params.get_float("radius", 0.0), 2.0);
+        assert_ne!(node.param_version, version);
+        assert!(!graph.apply_keyframes(5.0, 24.0));
+
+        assert!(graph.remove_param_keyframe(sphere, "radius", 0.0).unwrap());
+        assert!(graph.remove_param_keyframe(sphere, "radius", 10.0).unwrap());
+        assert!(graph.node(sphere).unwrap().param_keyframes.is_empty());
+    }
+
+    #[test]
+    fn apply_keyframes_drives_animated_nodes() {
+        let mut graph = Graph::default();
+        let wobble = graph.add_node(node_definition(BuiltinNodeKind::Wobble));
+        assert!(graph.apply_keyframes(12.0, 24.0));
+        assert_eq!(
+            graph
+                .node(wobble)
+                .unwrap()
+                .params
+                .get_float(FRAME_PARAM, 0.0),
+            12.0
+        );
+        assert!(!graph.apply_keyframes(12.0, 24.0));
+
+        // A keyed frame wins over the timeline, e.g. to hold or retime the motion.
+        graph
+            .set_param_keyframe(wobble, FRAME_PARAM, 0.0, ParamValue::Float(3.0))
+            .unwrap();
+        graph.apply_keyframes(20.0, 24.0);
+        assert_eq!(
+            graph
+                .node(wobble)
+                .unwrap()
+                .params
+                .get_float(FRAME_PARAM, 0.0),
+            3.0
+        );
+    }
+
     #[test]
     fn topo_sort_orders_upstream_first() {
         let mut graph = Graph::default();
@@ -0,0 +1,190 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use serde::Serialize;
+
+use crate::graph::{Graph, GraphError, NodeId};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize)]
+pub struct NodeStats {
+    pub fan_in: usize,
+    pub fan_out: usize,
+    // Links on the longest path from any source node.
+    pub depth: usize,
+    pub upstream: usize,
+    pub downstream: usize,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct GraphStats {
+    pub nodes: BTreeMap<NodeId, NodeStats>,
+    pub max_depth: usize,
+    pub sources: Vec<NodeId>,
+    pub sinks: Vec<NodeId>,
+}
+
+type Adjacency = BTreeMap<NodeId, Vec<NodeId>>;
+
+impl Graph {
+    // One entry per outgoing link, mirroring `upstream_nodes`.
+    pub fn downstream_nodes(&self, node_id: NodeId) -> Vec<NodeId> {
+        self.node_edges()
+            .filter(|(from, _)| *from == node_id)
+            .map(|(_, to)| to)
+            .collect()
+    }
+
+    pub fn all_upstream(&self, node_id: NodeId) -> BTreeSet<NodeId> {
+        reachable(&self.adjacency().0, node_id)
+    }
+
+    pub fn all_downstream(&self, node_id: NodeId) -> BTreeSet<NodeId> {
+        reachable(&self.adjacency().1, node_id)
+    }
+
+    pub fn node_depths(&self) -> Result<BTreeMap<NodeId, usize>, GraphError> {
+        let (upstream, downstream) = self.adjacency();
+        longest_paths(&upstream, &downstream)
+    }
+
+    pub fn stats(&self) -> Result<GraphStats, GraphError> {
+        let (upstream, downstream) = self.adjacency();
+        let depths = longest_paths(&upstream, &downstream)?;
+        let mut stats = GraphStats::default();
+        for (&node, &depth) in &depths {
+            let fan_in = upstream[&node].len();
+            let fan_out = downstream[&node].len();
+            if fan_in == 0 {
+                stats.sources.push(node);
+            }
+            if fan_out == 0 {
+                stats.sinks.push(node);
+            }
+            stats.max_depth = stats.max_depth.max(depth);
+            stats.nodes.insert(
+                node,
+                NodeStats {
+                    fan_in,
+                    fan_out,
+                    depth,
+                    upstream: reachable(&upstream, node).len(),
+                    downstream: reachable(&downstream, node).len(),
+                },
+            );
+        }
+        Ok(stats)
+    }
+
+    fn node_edges(&self) -> impl Iterator<Item = (NodeId, NodeId)> + '_ {
+        self.links()
+            .filter_map(|link| Some((self.pin(link.from)?.node, self.pin(link.to)?.node)))
+    }
+
+    fn adjacency(&self) -> (Adjacency, Adjacency) {
+        let mut upstream: Adjacency = self.nodes().map(|node| (node.id, Vec::new())).collect();
+        let mut downstream = upstream.clone();
+        for (from, to) in self.node_edges() {
+            upstream.entry(to).or_default().push(from);
+            downstream.entry(from).or_default().push(to);
+        }
+        (upstream, downstream)
+    }
+}
+
+fn reachable(edges: &Adjacency, start: NodeId) -> BTreeSet<NodeId> {
+    let mut seen = BTreeSet::new();
+    let mut stack = edges.get(&start).cloned().unwrap_or_default();
+    while let Some(node) = stack.pop() {
+        if node != start && seen.insert(node) {
+            stack.extend(edges.get(&node).into_iter().flatten());
+        }
+    }
+    seen
+}
+
+// Kahn's algorithm; whatever never reaches zero in-degree sits on a cycle.
+fn longest_paths(
+    upstream: &Adjacency,
+    downstream: &Adjacency,
+) -> Result<BTreeMap<NodeId, usize>, GraphError> {
+    let mut pending: BTreeMap<NodeId, usize> = upstream
+        .iter()
+        .map(|(node, inputs)| (*node, inputs.len()))
+        .collect();
+    let mut ready: Vec<NodeId> = pending
+        .iter()
+        .filter(|(_, count)| **count == 0)
+        .map(|(node, _)| *node)
+        .collect();
+    let mut depths = BTreeMap::new();
+    while let Some(node) = ready.pop() {
+        let depth = upstream[&node]
+            .iter()
+            .filter_map(|input| depths.get(input))
+            .map(|depth| depth + 1)
+            .max()
+            .unwrap_or(0);
+        depths.insert(node, depth);
+        for next in &downstream[&node] {
+            let count = pending.get_mut(next).expect("adjacency covers every node");
+            *count -= 1;
+            if *count == 0 {
+                ready.push(*next);
+            }
+        }
+    }
+    if depths.len() < upstream.len() {
+        let cycle = upstream
+            .keys()
+            .filter(|node| !depths.contains_key(*node))
+            .copied()
+            .collect();
+        return Err(GraphError::CycleDetected(cycle));
+    }
+    Ok(depths)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn traversal_depths_and_fan_counts() {
+        let mut graph = Graph::default();
+        let cube = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let sphere = graph.add_node(node_definition(BuiltinNodeKind::Sphere));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        let merge = graph.add_node(node_definition(BuiltinNodeKind::Merge));
+        let lone = graph.add_node(node_definition(BuiltinNodeKind::Grid));
+        let connect = |graph: &mut Graph, from: NodeId, to: NodeId, input: usize| {
+            let out = graph.node(from).unwrap().outputs[0];
+            let pin = graph.node(to).unwrap().inputs[input];
+            graph.add_link(out, pin).unwrap();
+        };
+        connect(&mut graph, cube, transform, 0);
+        connect(&mut graph, transform, merge, 0);
+        connect(&mut graph, sphere, merge, 1);
+
+        assert_eq!(graph.downstream_nodes(cube), vec![transform]);
+        assert_eq!(
+            graph.all_upstream(merge),
+            BTreeSet::from([cube, sphere, transform])
+        );
+        assert_eq!(
+            graph.all_downstream(cube),
+            BTreeSet::from([transform, merge])
+        );
+        assert!(graph.all_downstream(lone).is_empty());
+
+        let depths = graph.node_depths().unwrap();
+        assert_eq!(depths[&cube], 0);
+        assert_eq!(depths[&merge], 2);
+
+        let stats = graph.stats().unwrap();
+        assert_eq!(stats.max_depth, 2);
+        assert_eq!(stats.nodes[&merge].fan_in, 2);
+        assert_eq!(stats.nodes[&cube].downstream, 2);
+        assert_eq!(stats.sources, vec![cube, sphere, lone]);
+        assert_eq!(stats.sinks, vec![merge, lone]);
+    }
+}
@@ -0,0 +1,241 @@
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::hash::{Hash, Hasher};
+use std::sync::{Arc, Mutex};
+
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HalfEdge {
+    pub origin: u32,
+    pub face: usize,
+    pub next: usize,
+    pub prev: usize,
+    pub twin: Option<usize>,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct HalfEdgeMesh {
+    half_edges: Vec<HalfEdge>,
+    face_half_edges: Vec<usize>,
+    point_half_edges: Vec<Vec<usize>>,
+}
+
+impl HalfEdgeMesh {
+    pub fn build(mesh: &Mesh) -> Self {
+        let faces = mesh.face_ranges();
+        let mut half_edges = Vec::with_capacity(mesh.indices.len());
+        let mut face_half_edges = Vec::with_capacity(faces.len());
+        let mut point_half_edges = vec![Vec::new(); mesh.positions.len()];
+        for (face, range) in faces.iter().enumerate() {
+            face_half_edges.push(range.start);
+            let len = range.len();
+            for (offset, corner) in range.clone().enumerate() {
+                let origin = mesh.indices[corner];
+                half_edges.push(HalfEdge {
+                    origin,
+                    face,
+                    next: range.start + (offset + 1) % len,
+                    prev: range.start + (offset + len - 1) % len,
+                    twin: None,
+                });
+                if let Some(list) = point_half_edges.get_mut(origin as usize) {
+                    list.push(corner);
+                }
+            }
+        }
+
+        let mut directed: HashMap<(u32, u32), usize> = HashMap::with_capacity(half_edges.len());
+        for (index, edge) in half_edges.iter().enumerate() {
+            let dest = half_edges[edge.next].origin;
+            directed.insert((edge.origin, dest), index);
+        }
+        for index in 0..half_edges.len() {
+            let origin = half_edges[index].origin;
+            let dest = half_edges[half_edges[index].next].origin;
+            half_edges[index].twin = directed.get(&(dest, origin)).copied();
+        }
+
+        Self {
+            half_edges,
+            face_half_edges,
+            point_half_edges,
+        }
+    }
+
+    pub fn half_edges(&self) -> &[HalfEdge] {
+        &self.half_edges
+    }
+
+    pub fn half_edge(&self, index: usize) -> Option<&HalfEdge> {
+        self.half_edges.get(index)
+    }
+
+    pub fn destination(&self, index: usize) -> Option<u32> {
+        let edge = self.half_edges.get(index)?;
+        Some(self.half_edges[edge.next].origin)
+    }
+
+    pub fn is_boundary(&self, index: usize) -> bool {
+        self.half_edges
+            .get(index)
+            .is_some_and(|edge| edge.twin.is_none())
+    }
+
+    pub fn face_half_edges(&self, face: usize) -> Vec<usize> {
+        let Some(&start) = self.face_half_edges.get(face) else {
+            return Vec::new();
+        };
+        let mut edges = vec![start];
+        let mut current = self.half_edges[start].next;
+        while current != start {
+            edges.push(current);
+            current = self.half_edges[current].next;
+        }
+        edges
+    }
+
+    pub fn face_neighbors(&self, face: usize) -> Vec<usize> {
+        let mut neighbors = Vec::new();
+        for edge in self.face_half_edges(face) {
+            if let Some(twin) = self.half_edges[edge].twin {
+                let other = self.half_edges[twin].face;
+                if other != face && !neighbors.contains(&other) {
+                    neighbors.push(other);
+                }
+            }
+        }
+        neighbors
+    }
+
+    pub fn point_faces(&self, point: u32) -> Vec<usize> {
+        let mut faces: Vec<usize> = self
+            .point_half_edges
+            .get(point as usize)
+            .map(|edges| {
+                edges
+                    .iter()
+                    .map(|edge| self.half_edges[*edge].face)
+                    .collect()
+            })
+            .unwrap_or_default();
+        faces.dedup();
+        faces
+    }
+
+    pub fn point_neighbors(&self, point: u32) -> Vec<u32> {
+        let mut neighbors = Vec::new();
+        let Some(edges) = self.point_half_edges.get(point as usize) else {
+            return neighbors;
+        };
+        for &edge in edges {
+            let half_edge = &self.half_edges[edge];
+            for other in [
+                self.half_edges[half_edge.next].origin,
+                self.half_edges[half_edge.prev].origin,
+            ] {
+                if other != point && !neighbors.contains(&other) {
+                    neighbors.push(other);
+                }
+            }
+        }
+        neighbors
+    }
+
+    pub fn is_boundary_point(&self, point: u32) -> bool {
+        self.point_half_edges
+            .get(point as usize)
+            .is_some_and(|edges| {
+                edges.iter().any(|edge| {
+                    self.is_boundary(*edge) || self.is_boundary(self.half_edges[*edge].prev)
+                })
+            })
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct HalfEdgeCache {
+    cached: Mutex<Option<(u64, Arc<HalfEdgeMesh>)>>,
+}
+
+impl HalfEdgeCache {
+    pub(crate) fn get_or_build(&self, mesh: &Mesh) -> Arc<HalfEdgeMesh> {
+        let key = topology_key(mesh);
+        let mut cached = match self.cached.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if let Some((cached_key, half_edges)) = cached.as_ref() {
+            if *cached_key == key {
+                return Arc::clone(half_edges);
+            }
+        }
+        let half_edges = Arc::new(HalfEdgeMesh::build(mesh));
+        *cached = Some((key, Arc::clone(&half_edges)));
+        half_edges
+    }
+}
+
+impl Clone for HalfEdgeCache {
+    fn clone(&self) -> Self {
+        let cached = match self.cached.lock() {
+            Ok(guard) => guard.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        };
+        Self {
+            cached: Mutex::new(cached),
+        }
+    }
+}
+
+impl std::fmt::Debug for HalfEdgeCache {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("HalfEdgeCache")
+    }
+}
+
+fn topology_key(mesh: &Mesh) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    mesh.positions.len().hash(&mut hasher);
+    mesh.indices.hash(&mut hasher);
+    mesh.face_counts.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::mesh::{make_box, make_grid};
+
+    #[test]
+    fn box_half_edges_are_closed() {
+        let mesh = make_box([1.0, 1.0, 1.0]);
+        let half_edges = mesh.half_edges();
+        assert_eq!(half_edges.half_edges().len(), 24);
+        assert!(half_edges
+            .half_edges()
+            .iter()
+            .all(|edge| edge.twin.is_some()));
+        assert_eq!(half_edges.face_neighbors(0).len(), 4);
+        assert_eq!(half_edges.point_neighbors(0).len(), 3);
+        assert_eq!(half_edges.point_faces(0).len(), 3);
+    }
+
+    #[test]
+    fn grid_reports_boundary() {
+        let mesh = make_grid([1.0, 1.0], [2, 2]);
+        let half_edges = mesh.half_edges();
+        assert!(half_edges.is_boundary_point(0));
+        assert!(!half_edges.is_boundary_point(4));
+        assert_eq!(half_edges.point_neighbors(4).len(), 4);
+    }
+
+    #[test]
+    fn cache_rebuilds_after_topology_change() {
+        let mut mesh = make_grid([1.0, 1.0], [1, 1]);
+        let first = mesh.half_edges();
+        assert!(std::sync::Arc::ptr_eq(&first, &mesh.half_edges()));
+        mesh.indices.reverse();
+        let rebuilt = mesh.half_edges();
+        assert!(!std::sync::Arc::ptr_eq(&first, &rebuilt));
+    }
+}
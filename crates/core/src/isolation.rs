@@ -0,0 +1,47 @@
+use std::cell::Cell;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+
+thread_local! {
+    static ISOLATED: Cell<bool> = const { Cell::new(false) };
+}
+
+// Runs one node's cook so that a panic inside it becomes that node's error instead of taking
+// down the evaluation, or the app with it. Needed before nodes come from scripts or third
+// parties; builtin nodes go through it too since it costs nothing when nothing panics.
+// Web builds abort on panic, so there it only passes the result through.
+pub(crate) fn isolate<T>(cook: impl FnOnce() -> Result<T, String>) -> Result<T, String> {
+    let outer = ISOLATED.with(|flag| flag.replace(true));
+    let result = catch_unwind(AssertUnwindSafe(cook));
+    ISOLATED.with(|flag| flag.set(outer));
+    result.unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|text| text.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "non-string panic payload".to_string());
+        Err(format!("node panicked: {}", message))
+    })
+}
+
+// Lets a panic hook tell a caught node panic from a real crash. Only the cooking thread is
+// marked, so a panic on a parallel worker inside a node still looks like a crash to the hook.
+pub fn cook_is_isolated() -> bool {
+    ISOLATED.with(Cell::get)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn panics_become_errors() {
+        assert!(!cook_is_isolated());
+        let result: Result<(), String> = isolate(|| {
+            assert!(cook_is_isolated());
+            panic!("bad index {}", 3)
+        });
+        assert_eq!(result, Err("node panicked: bad index 3".to_string()));
+        assert!(!cook_is_isolated());
+        assert_eq!(isolate(|| Ok(2)), Ok(2));
+    }
+}
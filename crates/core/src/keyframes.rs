@@ -0,0 +1,172 @@
+use glam::{Mat4, Quat};
+use serde::{Deserialize, Serialize};
+
+use crate::graph::ParamValue;
+
+const KEY_EPSILON: f32 = 1.0e-3;
+// Parameter that animated nodes read the current timeline frame from.
+pub const FRAME_PARAM: &str = "frame";
+// Seconds since frame 1, handed to nodes whose code reads `$T`.
+pub const TIME_PARAM: &str = "time";
+
+// Where the timeline is, as `$F` and `$T` read it. Frame 1 is time zero.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PlaybackTime {
+    pub frame: f32,
+    pub fps: f32,
+}
+
+impl Default for PlaybackTime {
+    fn default() -> Self {
+        Self {
+            frame: 1.0,
+            fps: 24.0,
+        }
+    }
+}
+
+impl PlaybackTime {
+    pub fn seconds(self) -> f32 {
+        (self.frame - 1.0) / self.fps.max(1.0)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Keyframe {
+    pub frame: f32,
+    pub value: ParamValue,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct KeyframeTrack {
+    pub keys: Vec<Keyframe>,
+}
+
+impl KeyframeTrack {
+    pub fn set_key(&mut self, frame: f32, value: ParamValue) {
+        if let Some(key) = self
+            .keys
+            .iter_mut()
+            .find(|key| (key.frame - frame).abs() < KEY_EPSILON)
+        {
+            key.value = value;
+            return;
+        }
+        let index = self.keys.partition_point(|key| key.frame < frame);
+        self.keys.insert(index, Keyframe { frame, value });
+    }
+
+    pub fn remove_key(&mut self, frame: f32) -> bool {
+        let before = self.keys.len();
+        self.keys
+            .retain(|key| (key.frame - frame).abs() >= KEY_EPSILON);
+        self.keys.len() != before
+    }
+
+    pub fn has_key(&self, frame: f32) -> bool {
+        self.keys
+            .iter()
+            .any(|key| (key.frame - frame).abs() < KEY_EPSILON)
+    }
+
+    pub fn evaluate(&self, frame: f32) -> Option<ParamValue> {
+        let first = self.keys.first()?;
+        if frame <= first.frame {
+            return Some(first.value.clone());
+        }
+        let next = self.keys.partition_point(|key| key.frame <= frame);
+        let Some(b) = self.keys.get(next) else {
+            return self.keys.last().map(|key| key.value.clone());
+        };
+        let a = &self.keys[next - 1];
+        let t = (frame - a.frame) / (b.frame - a.frame).max(f32::EPSILON);
+        Some(lerp_value(&a.value, &b.value, t))
+    }
+}
+
+fn lerp_value(a: &ParamValue, b: &ParamValue, t: f32) -> ParamValue {
+    let lerp = |a: f32, b: f32| a + (b - a) * t;
+    match (a, b) {
+        (ParamValue::Float(a), ParamValue::Float(b)) => ParamValue::Float(lerp(*a, *b)),
+        (ParamValue::Int(a), ParamValue::Int(b)) => {
+            ParamValue::Int(lerp(*a as f32, *b as f32).round() as i32)
+        }
+        (ParamValue::Vec2(a), ParamValue::Vec2(b)) => {
+            ParamValue::Vec2([lerp(a[0], b[0]), lerp(a[1], b[1])])
+        }
+        (ParamValue::Vec3(a), ParamValue::Vec3(b)) => {
+            ParamValue::Vec3([lerp(a[0], b[0]), lerp(a[1], b[1]), lerp(a[2], b[2])])
+        }
+        (ParamValue::Quat(a), ParamValue::Quat(b)) => {
+            let a = Quat::from_array(*a).normalize();
+            let b = Quat::from_array(*b).normalize();
+            ParamValue::Quat(a.slerp(b, t).to_array())
+        }
+        // Blend the decomposed transforms so rotations don't shear mid-way.
+        (ParamValue::Mat4(a), ParamValue::Mat4(b)) => {
+            let (sa, ra, ta) = Mat4::from_cols_array(a).to_scale_rotation_translation();
+            let (sb, rb, tb) = Mat4::from_cols_array(b).to_scale_rotation_translation();
+            let matrix = Mat4::from_scale_rotation_translation(
+                sa.lerp(sb, t),
+                ra.slerp(rb, t),
+                ta.lerp(tb, t),
+            );
+            ParamValue::Mat4(matrix.to_cols_array())
+        }
+        _ => a.clone(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use glam::{Mat4, Quat, Vec3};
+
+    use super::KeyframeTrack;
+    use crate::graph::ParamValue;
+
+    #[test]
+    fn track_interpolates_between_keys() {
+        let mut track = KeyframeTrack::default();
+        track.set_key(10.0, ParamValue::Float(2.0));
+        track.set_key(0.0, ParamValue::Float(0.0));
+        track.set_key(20.0, ParamValue::Bool(true));
+        assert_eq!(track.keys[0].frame, 0.0);
+
+        assert_eq!(track.evaluate(-5.0), Some(ParamValue::Float(0.0)));
+        assert_eq!(track.evaluate(5.0), Some(ParamValue::Float(1.0)));
+        assert_eq!(track.evaluate(15.0), Some(ParamValue::Float(2.0)));
+        assert_eq!(track.evaluate(25.0), Some(ParamValue::Bool(true)));
+
+        track.set_key(10.0, ParamValue::Float(4.0));
+        assert_eq!(track.keys.len(), 3);
+        assert!(track.remove_key(20.0));
+        assert!(!track.has_key(20.0));
+        assert_eq!(track.evaluate(25.0), Some(ParamValue::Float(4.0)));
+    }
+
+    #[test]
+    fn rotations_interpolate_along_the_arc() {
+        let quarter = Quat::from_rotation_y(std::f32::consts::FRAC_PI_2);
+        let mut track = KeyframeTrack::default();
+        track.set_key(0.0, ParamValue::Quat(Quat::IDENTITY.to_array()));
+        track.set_key(10.0, ParamValue::Quat(quarter.to_array()));
+        let Some(ParamValue::Quat(mid)) = track.evaluate(5.0) else {
+            panic!("expected a quaternion");
+        };
+        let expected = Quat::from_rotation_y(std::f32::consts::FRAC_PI_4);
+        assert!(Quat::from_array(mid).angle_between(expected) < 1.0e-4);
+
+        let mut track = KeyframeTrack::default();
+        track.set_key(0.0, ParamValue::Mat4(Mat4::IDENTITY.to_cols_array()));
+        let end = Mat4::from_scale_rotation_translation(Vec3::splat(3.0), quarter, Vec3::X * 4.0);
+        track.set_key(10.0, ParamValue::Mat4(end.to_cols_array()));
+        let Some(ParamValue::Mat4(mid)) = track.evaluate(5.0) else {
+            panic!("expected a matrix");
+        };
+        let (scale, rotation, translation) =
+            Mat4::from_cols_array(&mid).to_scale_rotation_translation();
+        assert!(scale.abs_diff_eq(Vec3::splat(2.0), 1.0e-4));
+        assert!(rotation.angle_between(expected) < 1.0e-4);
+        assert!(translation.abs_diff_eq(Vec3::X * 2.0, 1.0e-4));
+    }
+}
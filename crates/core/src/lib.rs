@@ -1,33 +1,102 @@
+mod archive;
 mod attributes;
+mod bake_ao;
+mod bevel;
+mod boolean;
+mod building;
+mod bvh;
+mod cell_edits;
+mod clean;
+mod command;
+mod content_hash;
+mod data;
 mod eval;
+mod frame_cache;
 mod graph;
+mod graph_stats;
+mod half_edge;
+mod isolation;
+mod keyframes;
+mod lod;
 mod mesh;
 mod mesh_eval;
+mod node_audit;
+mod node_help;
+mod node_tests;
 mod nodes_builtin;
+mod noise;
+mod overrides;
+mod parallel;
+mod param_expr;
+mod particles;
+mod poly_extrude;
 mod project;
+mod project_info;
+mod regression;
 mod scene;
+mod seeds;
+mod sort;
+mod split;
+mod startup_script;
+mod stream;
+mod surface_masks;
+mod symmetry;
+mod units;
+mod uv_layout;
 mod wrangle;
 
+pub use archive::ProjectArchive;
 pub use attributes::{
-    AttributeDomain, AttributeError, AttributeInfo, AttributeRef, AttributeStorage, AttributeType,
-    MeshAttributes,
+    AttributeDomain, AttributeError, AttributeInfo, AttributePrecision, AttributeRef,
+    AttributeStorage, AttributeType, MeshAttributes,
 };
+pub use cell_edits::{set_cell_edit, CellEdit, CELL_EDITS_PARAM};
+pub use command::{CommandOutput, GraphCommand, GraphDelta};
+pub use content_hash::{mesh_content_hash, params_content_hash, quantize, StableHasher};
+pub use data::{Data, PointCloud, Volume};
 pub use eval::{
     evaluate_from, evaluate_from_with, DirtyNodeReport, DirtyReason, EvalCacheStats, EvalError,
-    EvalNodeReport, EvalReport, EvalState,
+    EvalNodeReport, EvalReport, EvalState, EvalWarning,
 };
 pub use graph::{
-    Graph, GraphError, Link, LinkId, Node, NodeDefinition, NodeId, NodeParams, ParamValue, Pin,
-    PinDefinition, PinId, PinKind, PinType,
+    ErrorPolicy, Graph, GraphError, Link, LinkId, Node, NodeDefinition, NodeHelp, NodeId,
+    NodeParams, ParamValue, Pin, PinDefinition, PinId, PinKind, PinType, VariadicInputs,
 };
-pub use mesh::{make_box, make_grid, Aabb, Mesh};
-pub use mesh_eval::{evaluate_mesh_graph, MeshEvalResult, MeshEvalState};
+pub use graph_stats::{GraphStats, NodeStats};
+pub use half_edge::{HalfEdge, HalfEdgeMesh};
+pub use isolation::cook_is_isolated;
+pub use keyframes::{Keyframe, KeyframeTrack, PlaybackTime, FRAME_PARAM, TIME_PARAM};
+pub use mesh::{make_box, make_grid, Aabb, DebugChannel, Mesh};
+pub use mesh_eval::{
+    evaluate_mesh_graph, evaluate_mesh_graph_cancellable, evaluate_mesh_graph_with_progress,
+    MeshEvalResult, MeshEvalState,
+};
+pub use node_audit::{map_node_params, node_kind_usage, replace_node_kind};
+pub use node_help::builtin_node_help;
+pub use node_tests::{run_node_self_tests, NodeTestCase, NodeTestResult};
 pub use nodes_builtin::{
-    builtin_definitions, builtin_kind_from_name, compute_mesh_node, default_params,
-    node_definition, BuiltinNodeKind,
+    builtin_definitions, builtin_kind_from_name, compute_mesh_node, compute_mesh_node_outputs,
+    default_params, node_definition, param_group, param_range, write_obj_to, BuiltinNodeKind,
+};
+pub use noise::{Fbm, NoiseBasis};
+pub use overrides::{apply_param_override, find_node_by_name, substitute_env_in_params};
+pub use parallel::{par_for_each_mut, par_map, par_zip_mut, PARALLEL_MIN_LEN};
+pub use param_expr::{
+    evaluate_param_expression, node_reference_name, param_dependencies, param_reference_expression,
+    param_reference_target, param_value_expression,
 };
 pub use project::{
-    CameraSettings, PanelSettings, Project, ProjectSettings, RenderDebugSettings, ShadingMode,
+    Annotation, BackgroundMode, CameraBookmark, CameraMode, CameraSettings, CullMode,
+    GraphBackdrop, GraphBookmark, GraphFrame, Measurement, PanelSettings, Project, ProjectSettings,
+    RenderDebugSettings, ShadingMode, SpaceMouseSettings, StandardView, TimelineSettings, UiTheme,
     PROJECT_VERSION,
 };
+pub use project_info::{AssetReference, DependencyIssue, ProjectInfo};
+pub use regression::{cook_project_snapshot, MeshSnapshot};
 pub use scene::{SceneMesh, SceneSnapshot};
+pub use seeds::SeedSettings;
+pub use startup_script::{run_startup_script, ScriptShortcut, StartupScriptOutput};
+pub use stream::{stream_mesh_graph, ObjStreamWriter};
+pub use symmetry::{mirror_deformation, SymmetryAxis, SymmetryPlane};
+pub use units::{parse_numeric_entry, scale_mesh, LengthUnit, UnitSettings};
+pub use uv_layout::{uv_layout, UvLayout};
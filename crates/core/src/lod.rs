@@ -0,0 +1,200 @@
+use std::collections::HashMap;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::mesh::Mesh;
+
+const MAX_LEVELS: usize = 8;
+const SEARCH_STEPS: usize = 16;
+
+pub fn parse_lod_ratios(text: &str) -> Result<Vec<f32>, String> {
+    let ratios = text
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|token| !token.is_empty())
+        .map(|token| {
+            token
+                .parse::<f32>()
+                .map(|ratio| ratio.clamp(0.0, 1.0))
+                .map_err(|_| format!("LOD: invalid ratio '{}'", token))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if ratios.is_empty() {
+        return Err("LOD requires at least one ratio".to_string());
+    }
+    if ratios.len() > MAX_LEVELS {
+        return Err(format!("LOD supports at most {} levels", MAX_LEVELS));
+    }
+    Ok(ratios)
+}
+
+pub fn lod_mesh(input: &Mesh, ratios: &[f32], lod_attr: &str) -> Result<Mesh, String> {
+    let lod_attr = lod_attr.trim();
+    if lod_attr.is_empty() {
+        return Err("LOD requires an attribute name".to_string());
+    }
+    let mut levels = Vec::with_capacity(ratios.len());
+    for (level, ratio) in ratios.iter().enumerate() {
+        let mut mesh = decimate_mesh(input, *ratio);
+        let faces = mesh.face_count();
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            lod_attr,
+            AttributeStorage::Int(vec![level as i32; faces]),
+        )
+        .map_err(|err| format!("LOD: {:?}", err))?;
+        for group in 0..ratios.len() {
+            let value = i32::from(group == level);
+            mesh.set_attribute(
+                AttributeDomain::Primitive,
+                format!("{}{}", lod_attr, group),
+                AttributeStorage::Int(vec![value; faces]),
+            )
+            .map_err(|err| format!("LOD: {:?}", err))?;
+        }
+        levels.push(mesh);
+    }
+    Ok(Mesh::merge(&levels))
+}
+
+pub fn decimate_mesh(input: &Mesh, ratio: f32) -> Mesh {
+    let point_count = input.positions.len();
+    if ratio >= 1.0 || point_count < 4 || input.indices.is_empty() {
+        return input.clone();
+    }
+    let Some(bounds) = input.bounds() else {
+        return input.clone();
+    };
+    let extent = (0..3)
+        .map(|axis| bounds.max[axis] - bounds.min[axis])
+        .fold(0.0f32, f32::max);
+    if extent <= f32::EPSILON {
+        return input.clone();
+    }
+
+    let target = ((point_count as f32 * ratio).round() as usize).max(3);
+    let cluster_of = |resolution: u32| -> (Vec<usize>, usize) {
+        let cell = extent / resolution as f32;
+        let mut cells = HashMap::new();
+        let clusters = input
+            .positions
+            .iter()
+            .map(|p| {
+                let key =
+                    [0, 1, 2].map(|axis| ((p[axis] - bounds.min[axis]) / cell).floor() as i64);
+                let next = cells.len();
+                *cells.entry(key).or_insert(next)
+            })
+            .collect();
+        (clusters, cells.len())
+    };
+
+    let (mut lo, mut hi) = (1u32, 1u32 << SEARCH_STEPS);
+    let mut best = cluster_of(lo);
+    while lo < hi {
+        let mid = lo + (hi - lo).div_ceil(2);
+        let candidate = cluster_of(mid);
+        if candidate.1 <= target {
+            best = candidate;
+            lo = mid;
+        } else {
+            hi = mid - 1;
+        }
+    }
+    let (clusters, cluster_count) = best;
+
+    let mut sums = vec![[0.0f32; 4]; cluster_count];
+    for (point, cluster) in clusters.iter().enumerate() {
+        let p = input.positions[point];
+        let sum = &mut sums[*cluster];
+        sum[0] += p[0];
+        sum[1] += p[1];
+        sum[2] += p[2];
+        sum[3] += 1.0;
+    }
+
+    let mut remap = vec![u32::MAX; cluster_count];
+    let mut points = Vec::new();
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    let mut corners = Vec::new();
+    let mut prims = Vec::new();
+    let mut face_counts = Vec::new();
+    let mut face = Vec::new();
+    for (prim, range) in input.face_ranges().into_iter().enumerate() {
+        face.clear();
+        for corner in range {
+            let point = input.indices[corner] as usize;
+            let cluster = clusters[point];
+            if face.last().map(|(last, _, _)| *last) != Some(cluster) {
+                face.push((cluster, point, corner));
+            }
+        }
+        while face.len() > 1
+            && face.first().map(|entry| entry.0) == face.last().map(|entry| entry.0)
+        {
+            face.pop();
+        }
+        if face.len() < 3 {
+            continue;
+        }
+        for (cluster, point, corner) in &face {
+            if remap[*cluster] == u32::MAX {
+                remap[*cluster] = positions.len() as u32;
+                let sum = sums[*cluster];
+                positions.push([sum[0] / sum[3], sum[1] / sum[3], sum[2] / sum[3]]);
+                points.push(*point);
+            }
+            indices.push(remap[*cluster]);
+            corners.push(*corner);
+        }
+        prims.push(prim);
+        face_counts.push(face.len() as u32);
+    }
+
+    let mut mesh = input.rebuild_from_sources(positions, indices, &points, &corners, &prims);
+    if !input.face_counts.is_empty() || face_counts.iter().any(|count| *count != 3) {
+        mesh.face_counts = face_counts;
+    }
+    if mesh.normals.is_some() || mesh.corner_normals.is_some() {
+        mesh.corner_normals = None;
+        mesh.compute_normals();
+    }
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decimate_mesh, lod_mesh, parse_lod_ratios};
+    use crate::attributes::AttributeDomain;
+    use crate::mesh::make_grid;
+
+    #[test]
+    fn lod_reduces_levels_and_tags_groups() {
+        let grid = make_grid([2.0, 2.0], [20, 20]);
+        let half = decimate_mesh(&grid, 0.25);
+        assert!(half.positions.len() <= grid.positions.len() / 4);
+        assert!(half.face_count() > 0);
+        assert!(half
+            .indices
+            .iter()
+            .all(|idx| (*idx as usize) < half.positions.len()));
+
+        let ratios = parse_lod_ratios("1, 0.5 0.1").unwrap();
+        assert_eq!(ratios, vec![1.0, 0.5, 0.1]);
+        assert!(parse_lod_ratios("").is_err());
+        assert!(parse_lod_ratios("0.5 x").is_err());
+
+        let merged = lod_mesh(&grid, &ratios, "lod").unwrap();
+        let lod0 = merged
+            .group_mask(AttributeDomain::Primitive, "lod0")
+            .unwrap();
+        assert_eq!(
+            lod0.iter().filter(|inside| **inside).count(),
+            grid.face_count()
+        );
+        let lod2 = merged
+            .group_mask(AttributeDomain::Primitive, "lod2")
+            .unwrap();
+        let coarse = lod2.iter().filter(|inside| **inside).count();
+        assert!(coarse > 0 && coarse < grid.face_count() / 2);
+    }
+}
@@ -1,9 +1,14 @@
+use std::sync::Arc;
+
 use glam::{Mat4, Vec3};
 
 use crate::attributes::{
-    AttributeDomain, AttributeError, AttributeInfo, AttributeRef, AttributeStorage, AttributeType,
-    MeshAttributes,
+    AttributeDomain, AttributeError, AttributeInfo, AttributePrecision, AttributeRef,
+    AttributeStorage, AttributeType, MeshAttributes,
 };
+use crate::bvh::Bvh;
+use crate::half_edge::{HalfEdgeCache, HalfEdgeMesh};
+use crate::parallel::{par_for_each_mut, par_map};
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub struct Aabb {
@@ -11,6 +16,27 @@ pub struct Aabb {
     pub max: [f32; 3],
 }
 
+// Secondary geometry a node wants shown as a viewport overlay, such as rejected samples.
+// Channels are taken off node outputs during evaluation, so they never flow downstream.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DebugChannel {
+    pub name: String,
+    pub color: [f32; 3],
+    pub points: Vec<[f32; 3]>,
+    pub lines: Vec<[[f32; 3]; 2]>,
+}
+
+impl DebugChannel {
+    pub fn points(name: impl Into<String>, color: [f32; 3], points: Vec<[f32; 3]>) -> Self {
+        Self {
+            name: name.into(),
+            color,
+            points,
+            lines: Vec::new(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Default)]
 pub struct Mesh {
     pub positions: Vec<[f32; 3]>,
@@ -19,6 +45,9 @@ pub struct Mesh {
     pub corner_normals: Option<Vec<[f32; 3]>>,
     pub uvs: Option<Vec<[f32; 2]>>,
     pub attributes: MeshAttributes,
+    pub face_counts: Vec<u32>,
+    pub debug_channels: Vec<DebugChannel>,
+    pub(crate) half_edge_cache: HalfEdgeCache,
 }
 
 impl Mesh {
@@ -34,14 +63,133 @@ impl Mesh {
             corner_normals: None,
             uvs: None,
             attributes: MeshAttributes::default(),
+            face_counts: Vec::new(),
+            debug_channels: Vec::new(),
+            half_edge_cache: HalfEdgeCache::default(),
+        }
+    }
+
+    pub fn with_polygons(
+        positions: Vec<[f32; 3]>,
+        indices: Vec<u32>,
+        face_counts: Vec<u32>,
+    ) -> Self {
+        let mut mesh = Self::with_positions_indices(positions, indices);
+        if face_counts.iter().any(|count| *count != 3) {
+            mesh.face_counts = face_counts;
+        }
+        mesh
+    }
+
+    pub fn face_count(&self) -> usize {
+        if self.face_counts.is_empty() {
+            self.indices.len() / 3
+        } else {
+            self.face_counts.len()
+        }
+    }
+
+    pub fn face_ranges(&self) -> Vec<std::ops::Range<usize>> {
+        if self.face_counts.is_empty() {
+            return (0..self.indices.len() / 3)
+                .map(|face| face * 3..face * 3 + 3)
+                .collect();
+        }
+        let mut ranges = Vec::with_capacity(self.face_counts.len());
+        let mut start = 0usize;
+        for count in &self.face_counts {
+            let end = (start + *count as usize).min(self.indices.len());
+            ranges.push(start..end);
+            start = end;
+        }
+        ranges
+    }
+
+    pub fn is_triangulated(&self) -> bool {
+        self.face_counts.is_empty() || self.face_counts.iter().all(|count| *count == 3)
+    }
+
+    pub fn has_valid_faces(&self) -> bool {
+        if self.face_counts.is_empty() {
+            self.indices.len().is_multiple_of(3)
+        } else {
+            self.face_counts.iter().all(|count| *count >= 3)
+                && self
+                    .face_counts
+                    .iter()
+                    .map(|count| *count as usize)
+                    .sum::<usize>()
+                    == self.indices.len()
+        }
+    }
+
+    pub fn triangulation(&self) -> (Vec<usize>, Vec<usize>) {
+        let mut corners = Vec::with_capacity(self.indices.len());
+        let mut faces = Vec::with_capacity(self.face_count());
+        for (face, range) in self.face_ranges().into_iter().enumerate() {
+            if range.len() < 3 {
+                continue;
+            }
+            for k in 1..range.len() - 1 {
+                corners.extend_from_slice(&[range.start, range.start + k, range.start + k + 1]);
+                faces.push(face);
+            }
         }
+        (corners, faces)
+    }
+
+    // Face that fan triangle `triangle` of `triangulation` came from, without building it.
+    pub fn triangle_face(&self, triangle: usize) -> Option<usize> {
+        if self.face_counts.is_empty() {
+            return (triangle < self.indices.len() / 3).then_some(triangle);
+        }
+        let mut remaining = triangle;
+        for (face, count) in self.face_counts.iter().enumerate() {
+            let fan = (*count as usize).saturating_sub(2);
+            if remaining < fan {
+                return Some(face);
+            }
+            remaining -= fan;
+        }
+        None
+    }
+
+    pub fn triangulate(&self) -> Mesh {
+        if self.face_counts.is_empty() {
+            return self.clone();
+        }
+        let (corners, faces) = self.triangulation();
+        let indices = corners.iter().map(|corner| self.indices[*corner]).collect();
+        let point_sources: Vec<usize> = (0..self.positions.len()).collect();
+        self.rebuild_from_sources(
+            self.positions.clone(),
+            indices,
+            &point_sources,
+            &corners,
+            &faces,
+        )
+    }
+
+    pub fn half_edges(&self) -> Arc<HalfEdgeMesh> {
+        self.half_edge_cache.get_or_build(self)
+    }
+
+    pub(crate) fn polygon_normal(&self, face: &[u32]) -> Vec3 {
+        let mut normal = Vec3::ZERO;
+        for (i, idx) in face.iter().enumerate() {
+            let next = face[(i + 1) % face.len()];
+            let current = Vec3::from(self.positions[*idx as usize]);
+            let next = Vec3::from(self.positions[next as usize]);
+            normal += current.cross(next);
+        }
+        normal
     }
 
     pub fn attribute_domain_len(&self, domain: AttributeDomain) -> usize {
         match domain {
             AttributeDomain::Point => self.positions.len(),
             AttributeDomain::Vertex => self.indices.len(),
-            AttributeDomain::Primitive => self.indices.len() / 3,
+            AttributeDomain::Primitive => self.face_count(),
             AttributeDomain::Detail => 1,
         }
     }
@@ -55,6 +203,7 @@ impl Mesh {
                 data_type: AttributeType::Vec3,
                 len: self.positions.len(),
                 implicit: true,
+                precision: AttributePrecision::Full,
             });
         }
         if let Some(normals) = &self.normals {
@@ -64,6 +213,7 @@ impl Mesh {
                 data_type: AttributeType::Vec3,
                 len: normals.len(),
                 implicit: true,
+                precision: AttributePrecision::Full,
             });
         }
         if let Some(normals) = &self.corner_normals {
@@ -73,6 +223,7 @@ impl Mesh {
                 data_type: AttributeType::Vec3,
                 len: normals.len(),
                 implicit: true,
+                precision: AttributePrecision::Full,
             });
         }
         for domain in AttributeDomain::ALL {
@@ -83,6 +234,7 @@ impl Mesh {
                     data_type: storage.data_type(),
                     len: storage.len(),
                     implicit: false,
+                    precision: self.attributes.precision(domain, name),
                 });
             }
         }
@@ -136,7 +288,7 @@ impl Mesh {
         &mut self,
         domain: AttributeDomain,
         name: impl Into<String>,
-        storage: AttributeStorage,
+        mut storage: AttributeStorage,
     ) -> Result<(), AttributeError> {
         let name = name.into();
         let expected_len = self.attribute_domain_len(domain);
@@ -189,6 +341,7 @@ impl Mesh {
             _ => {}
         }
 
+        storage.quantize(self.attributes.precision(domain, &name));
         self.attributes.map_mut(domain).insert(name, storage);
         Ok(())
     }
@@ -212,6 +365,14 @@ impl Mesh {
         }
     }
 
+    pub(crate) fn group_mask(&self, domain: AttributeDomain, name: &str) -> Option<Vec<bool>> {
+        match self.attribute(domain, name)? {
+            AttributeRef::Int(values) => Some(values.iter().map(|v| *v != 0).collect()),
+            AttributeRef::Float(values) => Some(values.iter().map(|v| *v != 0.0).collect()),
+            _ => None,
+        }
+    }
+
     pub fn bounds(&self) -> Option<Aabb> {
         let mut iter = self.positions.iter();
         let first = iter.next()?;
@@ -230,44 +391,41 @@ impl Mesh {
         Some(Aabb { min, max })
     }
 
+    // Nearest surface point along a ray, for viewport picking.
+    pub fn raycast(&self, origin: [f32; 3], dir: [f32; 3]) -> Option<[f32; 3]> {
+        let origin = Vec3::from(origin);
+        let dir = Vec3::from(dir).try_normalize()?;
+        let t = Bvh::build(self).closest_hit(origin, dir)?;
+        Some((origin + dir * t).to_array())
+    }
+
     pub fn compute_normals(&mut self) -> bool {
-        if !self.indices.len().is_multiple_of(3) || self.positions.is_empty() {
+        if !self.has_valid_faces() || self.positions.is_empty() {
             return false;
         }
 
-        let mut accum = vec![Vec3::ZERO; self.positions.len()];
+        // Face normals are independent; only the scatter into points stays serial.
+        let face_ranges = self.face_ranges();
+        let face_normals = par_map(&face_ranges, |range| {
+            let face = &self.indices[range.clone()];
+            if face.iter().any(|idx| *idx as usize >= self.positions.len()) {
+                None
+            } else {
+                Some(self.polygon_normal(face))
+            }
+        });
 
-        for tri in self.indices.chunks_exact(3) {
-            let i0 = tri[0] as usize;
-            let i1 = tri[1] as usize;
-            let i2 = tri[2] as usize;
-            if i0 >= self.positions.len()
-                || i1 >= self.positions.len()
-                || i2 >= self.positions.len()
-            {
+        let mut accum = vec![Vec3::ZERO; self.positions.len()];
+        for (range, normal) in face_ranges.into_iter().zip(face_normals) {
+            let Some(normal) = normal else {
                 continue;
+            };
+            for idx in &self.indices[range] {
+                accum[*idx as usize] += normal;
             }
+        }
 
-            let p0 = Vec3::from(self.positions[i0]);
-            let p1 = Vec3::from(self.positions[i1]);
-            let p2 = Vec3::from(self.positions[i2]);
-            let normal = (p1 - p0).cross(p2 - p0);
-            accum[i0] += normal;
-            accum[i1] += normal;
-            accum[i2] += normal;
-        }
-
-        let normals = accum
-            .into_iter()
-            .map(|n| {
-                let len = n.length();
-                if len > 0.0 {
-                    (n / len).to_array()
-                } else {
-                    [0.0, 1.0, 0.0]
-                }
-            })
-            .collect();
+        let normals = par_map(&accum, |n| normalize_or_up(*n));
 
         self.normals = Some(normals);
         self.corner_normals = None;
@@ -275,7 +433,7 @@ impl Mesh {
     }
 
     pub fn compute_normals_with_threshold(&mut self, threshold_degrees: f32) -> bool {
-        if !self.indices.len().is_multiple_of(3) || self.positions.is_empty() {
+        if !self.has_valid_faces() || self.positions.is_empty() {
             return false;
         }
 
@@ -285,31 +443,21 @@ impl Mesh {
         }
 
         let cos_threshold = threshold.to_radians().cos();
-        let tri_count = self.indices.len() / 3;
-        let mut face_normals = Vec::with_capacity(tri_count);
-        let mut face_indices = Vec::with_capacity(tri_count);
-
-        for tri in self.indices.chunks_exact(3) {
-            let i0 = tri[0] as usize;
-            let i1 = tri[1] as usize;
-            let i2 = tri[2] as usize;
-            if i0 >= self.positions.len()
-                || i1 >= self.positions.len()
-                || i2 >= self.positions.len()
-            {
+        let face_ranges = self.face_ranges();
+        let mut face_normals = Vec::with_capacity(face_ranges.len());
+
+        for range in &face_ranges {
+            let face = &self.indices[range.clone()];
+            if face.iter().any(|idx| *idx as usize >= self.positions.len()) {
                 return false;
             }
-            let p0 = Vec3::from(self.positions[i0]);
-            let p1 = Vec3::from(self.positions[i1]);
-            let p2 = Vec3::from(self.positions[i2]);
-            let normal = (p1 - p0).cross(p2 - p0);
+            let normal = self.polygon_normal(face);
             let normal = if normal.length_squared() > 0.0 {
                 normal.normalize()
             } else {
                 Vec3::Y
             };
             face_normals.push(normal);
-            face_indices.push([i0, i1, i2]);
         }
 
         let mut groups = std::collections::HashMap::new();
@@ -319,8 +467,9 @@ impl Mesh {
         }
 
         let mut adjacency: Vec<Vec<usize>> = vec![Vec::new(); self.positions.len()];
-        for (face_index, indices) in face_indices.iter().enumerate() {
-            for &pos_index in indices {
+        for (face_index, range) in face_ranges.iter().enumerate() {
+            for &pos_index in &self.indices[range.clone()] {
+                let pos_index = pos_index as usize;
                 let key = quantize_position(self.positions[pos_index]);
                 if let Some(group) = groups.get(&key) {
                     for &member in group {
@@ -333,11 +482,11 @@ impl Mesh {
         }
 
         let mut corner_normals = Vec::with_capacity(self.indices.len());
-        for (face_index, indices) in face_indices.iter().enumerate() {
+        for (face_index, range) in face_ranges.iter().enumerate() {
             let face_normal = face_normals[face_index];
-            for &pos_index in indices {
+            for &pos_index in &self.indices[range.clone()] {
                 let mut sum = Vec3::ZERO;
-                for &adj_face in &adjacency[pos_index] {
+                for &adj_face in &adjacency[pos_index as usize] {
                     let candidate = face_normals[adj_face];
                     if candidate.dot(face_normal) >= cos_threshold {
                         sum += candidate;
@@ -358,36 +507,72 @@ impl Mesh {
     }
 
     pub fn transform(&mut self, matrix: Mat4) {
-        for p in &mut self.positions {
-            let v = matrix.transform_point3(Vec3::from(*p));
-            *p = v.to_array();
-        }
-
+        par_for_each_mut(&mut self.positions, |p| {
+            *p = matrix.transform_point3(Vec3::from(*p)).to_array();
+        });
+
+        let normal_matrix = matrix.inverse().transpose();
+        let transform_normal = |n: &mut [f32; 3]| {
+            *n = normalize_or_up(normal_matrix.transform_vector3(Vec3::from(*n)))
+        };
         if let Some(normals) = &mut self.normals {
-            let normal_matrix = matrix.inverse().transpose();
-            for n in normals {
-                let v = normal_matrix.transform_vector3(Vec3::from(*n));
-                let len = v.length();
-                *n = if len > 0.0 {
-                    (v / len).to_array()
-                } else {
-                    [0.0, 1.0, 0.0]
-                };
-            }
+            par_for_each_mut(normals, transform_normal);
         }
-
         if let Some(corner_normals) = &mut self.corner_normals {
-            let normal_matrix = matrix.inverse().transpose();
-            for n in corner_normals {
-                let v = normal_matrix.transform_vector3(Vec3::from(*n));
-                let len = v.length();
-                *n = if len > 0.0 {
-                    (v / len).to_array()
-                } else {
-                    [0.0, 1.0, 0.0]
-                };
+            par_for_each_mut(corner_normals, transform_normal);
+        }
+    }
+
+    pub(crate) fn rebuild_from_sources(
+        &self,
+        positions: Vec<[f32; 3]>,
+        indices: Vec<u32>,
+        point_sources: &[usize],
+        corner_sources: &[usize],
+        prim_sources: &[usize],
+    ) -> Mesh {
+        let gather_vec3 = |values: &Vec<[f32; 3]>, sources: &[usize]| -> Vec<[f32; 3]> {
+            sources
+                .iter()
+                .map(|&idx| values.get(idx).copied().unwrap_or([0.0, 1.0, 0.0]))
+                .collect()
+        };
+        let mut mesh = Mesh::with_positions_indices(positions, indices);
+        if let Some(normals) = &self.normals {
+            mesh.normals = Some(gather_vec3(normals, point_sources));
+        }
+        if let Some(corner_normals) = &self.corner_normals {
+            mesh.corner_normals = Some(gather_vec3(corner_normals, corner_sources));
+        }
+        if let Some(uvs) = &self.uvs {
+            mesh.uvs = Some(
+                point_sources
+                    .iter()
+                    .map(|&idx| uvs.get(idx).copied().unwrap_or([0.0, 0.0]))
+                    .collect(),
+            );
+        }
+        for domain in AttributeDomain::ALL {
+            let sources = match domain {
+                AttributeDomain::Point => point_sources,
+                AttributeDomain::Vertex => corner_sources,
+                AttributeDomain::Primitive => prim_sources,
+                AttributeDomain::Detail => {
+                    for (name, storage) in self.attributes.map(domain) {
+                        mesh.attributes
+                            .map_mut(domain)
+                            .insert(name.clone(), storage.clone());
+                    }
+                    continue;
+                }
+            };
+            for (name, storage) in self.attributes.map(domain) {
+                mesh.attributes
+                    .map_mut(domain)
+                    .insert(name.clone(), storage.gather(sources));
             }
         }
+        mesh
     }
 
     pub fn merge(meshes: &[Mesh]) -> Mesh {
@@ -397,6 +582,7 @@ impl Mesh {
         let mut include_uvs = true;
         let mut include_corner_normals = true;
 
+        let include_face_counts = meshes.iter().any(|mesh| !mesh.face_counts.is_empty());
         for mesh in meshes {
             include_normals &= mesh.normals.is_some();
             include_uvs &= mesh.uvs.is_some();
@@ -409,6 +595,15 @@ impl Mesh {
                 .indices
                 .extend(mesh.indices.iter().map(|i| i + vertex_offset));
             vertex_offset += mesh.positions.len() as u32;
+            if include_face_counts {
+                if mesh.face_counts.is_empty() {
+                    merged
+                        .face_counts
+                        .extend(std::iter::repeat_n(3, mesh.indices.len() / 3));
+                } else {
+                    merged.face_counts.extend_from_slice(&mesh.face_counts);
+                }
+            }
         }
 
         if include_normals {
@@ -528,12 +723,26 @@ fn merge_attributes(meshes: &[Mesh]) -> MeshAttributes {
                     }
                 }
             }
+            // The first input decides the precision of the merged attribute.
+            let precision = meshes[0].attributes.precision(domain, name);
+            if merged.get(domain, name).is_some() {
+                merged.set_precision(domain, name, precision);
+            }
         }
     }
 
     merged
 }
 
+fn normalize_or_up(n: Vec3) -> [f32; 3] {
+    let len = n.length();
+    if len > 0.0 {
+        (n / len).to_array()
+    } else {
+        [0.0, 1.0, 0.0]
+    }
+}
+
 fn quantize_position(position: [f32; 3]) -> (i32, i32, i32) {
     let epsilon = 1.0e-5;
     (
@@ -560,15 +769,15 @@ pub fn make_box(size: [f32; 3]) -> Mesh {
     ];
 
     let indices = vec![
-        0, 2, 1, 0, 3, 2, // -Z
-        4, 5, 6, 4, 6, 7, // +Z
-        0, 1, 5, 0, 5, 4, // -Y
-        2, 3, 7, 2, 7, 6, // +Y
-        1, 2, 6, 1, 6, 5, // +X
-        3, 0, 4, 3, 4, 7, // -X
+        0, 3, 2, 1, // -Z
+        4, 5, 6, 7, // +Z
+        0, 1, 5, 4, // -Y
+        2, 3, 7, 6, // +Y
+        1, 2, 6, 5, // +X
+        3, 0, 4, 7, // -X
     ];
 
-    Mesh::with_positions_indices(positions, indices)
+    Mesh::with_polygons(positions, indices, vec![4; 6])
 }
 
 pub fn make_grid(size: [f32; 2], divisions: [u32; 2]) -> Mesh {
@@ -602,11 +811,12 @@ pub fn make_grid(size: [f32; 2], divisions: [u32; 2]) -> Mesh {
             let i2 = i0 + stride;
             let i3 = i2 + 1;
 
-            indices.extend_from_slice(&[i0, i2, i1, i1, i2, i3]);
+            indices.extend_from_slice(&[i0, i2, i3, i1]);
         }
     }
 
-    Mesh::with_positions_indices(positions, indices)
+    let face_count = (div_x * div_z) as usize;
+    Mesh::with_polygons(positions, indices, vec![4; face_count])
 }
 
 pub fn make_uv_sphere(radius: f32, rows: u32, cols: u32) -> Mesh {
@@ -665,6 +875,9 @@ pub fn make_uv_sphere(radius: f32, rows: u32, cols: u32) -> Mesh {
         corner_normals: None,
         uvs: None,
         attributes: MeshAttributes::default(),
+        face_counts: Vec::new(),
+        debug_channels: Vec::new(),
+        half_edge_cache: HalfEdgeCache::default(),
     }
 }
 
@@ -672,6 +885,30 @@ pub fn make_uv_sphere(radius: f32, rows: u32, cols: u32) -> Mesh {
 mod tests {
     use super::*;
 
+    #[test]
+    fn raycast_hits_nearest_face() {
+        let mesh = make_box([2.0, 2.0, 2.0]);
+        let hit = mesh
+            .raycast([0.0, 0.0, 5.0], [0.0, 0.0, -1.0])
+            .expect("hit");
+        assert!((hit[2] - 1.0).abs() < 1.0e-5);
+        assert!(mesh.raycast([0.0, 5.0, 5.0], [0.0, 0.0, -1.0]).is_none());
+    }
+
+    #[test]
+    fn triangle_face_follows_fan_triangulation() {
+        let mesh = Mesh::with_polygons(
+            vec![[0.0; 3]; 6],
+            vec![0, 1, 2, 3, 0, 2, 4, 5, 1, 0],
+            vec![4, 2, 4],
+        );
+        let faces = mesh.triangulation().1;
+        for (triangle, face) in faces.iter().enumerate() {
+            assert_eq!(mesh.triangle_face(triangle), Some(*face));
+        }
+        assert_eq!(mesh.triangle_face(faces.len()), None);
+    }
+
     #[test]
     fn bounds_for_simple_points() {
         let mesh =
@@ -706,14 +943,17 @@ mod tests {
     fn box_has_expected_counts() {
         let mesh = make_box([2.0, 2.0, 2.0]);
         assert_eq!(mesh.positions.len(), 8);
-        assert_eq!(mesh.indices.len(), 36);
+        assert_eq!(mesh.indices.len(), 24);
+        assert_eq!(mesh.face_count(), 6);
+        assert_eq!(mesh.triangulate().indices.len(), 36);
     }
 
     #[test]
     fn grid_has_expected_counts() {
         let mesh = make_grid([2.0, 2.0], [2, 3]);
         assert_eq!(mesh.positions.len(), (2 + 1) * (3 + 1));
-        assert_eq!(mesh.indices.len(), 2 * 3 * 6);
+        assert_eq!(mesh.indices.len(), 2 * 3 * 4);
+        assert_eq!(mesh.triangulate().indices.len(), 2 * 3 * 6);
     }
 
     #[test]
@@ -722,4 +962,71 @@ mod tests {
         assert_eq!(mesh.positions.len(), (4 + 1) * (8 + 1));
         assert_eq!(mesh.indices.len(), 4 * 8 * 6);
     }
+
+    #[test]
+    fn triangulate_remaps_polygon_attributes() {
+        let mut mesh = make_box([1.0, 1.0, 1.0]);
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            "id",
+            AttributeStorage::Int((0..6).collect()),
+        )
+        .unwrap();
+        let tris = mesh.triangulate();
+        assert!(tris.face_counts.is_empty());
+        assert_eq!(
+            tris.attribute(AttributeDomain::Primitive, "id"),
+            Some(AttributeRef::Int(&[0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5]))
+        );
+    }
+
+    #[test]
+    fn attribute_precision_sticks_to_later_writes() {
+        let mut mesh = make_box([1.0, 1.0, 1.0]);
+        let colors = vec![[0.1234, 1.5, -0.2]; 8];
+        mesh.set_attribute(
+            AttributeDomain::Point,
+            "Cd",
+            AttributeStorage::Vec3(colors.clone()),
+        )
+        .unwrap();
+        mesh.attributes
+            .set_precision(AttributeDomain::Point, "Cd", AttributePrecision::Unorm8);
+        let expected = [31.0 / 255.0, 1.0, 0.0];
+        assert_eq!(
+            mesh.attribute(AttributeDomain::Point, "Cd"),
+            Some(AttributeRef::Vec3(&[expected; 8]))
+        );
+
+        mesh.set_attribute(AttributeDomain::Point, "Cd", AttributeStorage::Vec3(colors))
+            .unwrap();
+        let merged = Mesh::merge(&[mesh.clone(), mesh]);
+        assert_eq!(
+            merged.attributes.precision(AttributeDomain::Point, "Cd"),
+            AttributePrecision::Unorm8
+        );
+        assert_eq!(
+            merged.attribute(AttributeDomain::Point, "Cd"),
+            Some(AttributeRef::Vec3(&[expected; 16]))
+        );
+    }
+
+    #[test]
+    fn half_precision_rounds_mantissa() {
+        let half = AttributePrecision::Half;
+        assert_eq!(half.quantize(1.0), 1.0);
+        assert_eq!(half.quantize(1.0 + 1.0 / 4096.0), 1.0);
+        assert_eq!(half.quantize(2049.0), 2048.0);
+        assert_eq!(half.quantize(1.0e6), 65504.0);
+        assert!((half.quantize(0.1) - 0.1).abs() < 1.0e-4);
+    }
+
+    #[test]
+    fn box_quads_face_outward() {
+        let mut mesh = make_box([2.0, 2.0, 2.0]);
+        assert!(mesh.compute_normals());
+        for (p, n) in mesh.positions.iter().zip(mesh.normals.unwrap()) {
+            assert!(Vec3::from(*p).dot(Vec3::from(n)) > 0.0);
+        }
+    }
 }
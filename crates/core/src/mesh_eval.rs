@@ -1,20 +1,36 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, BTreeSet};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
 
-use crate::eval::{evaluate_from_with, EvalReport, EvalState};
-use crate::graph::{Graph, GraphError, NodeId};
-use crate::mesh::Mesh;
-use crate::nodes_builtin::{builtin_kind_from_name, compute_mesh_node};
+use crate::data::Data;
+use crate::eval::{evaluate_from_with, EvalError, EvalReport, EvalState, EvalWarning};
+use crate::frame_cache::{cache_frame, frame_independent_key, frame_input_key, FrameCache};
+use crate::graph::{ErrorPolicy, Graph, GraphError, NodeId, NodeParams, PinId};
+use crate::isolation::isolate;
+use crate::keyframes::FRAME_PARAM;
+use crate::mesh::{DebugChannel, Mesh};
+use crate::nodes_builtin::{builtin_kind_from_name, compute_mesh_node_outputs, BuiltinNodeKind};
+use crate::param_expr::reads_time;
+use crate::particles::{particle_cache_key, simulate_particles, ParticleSettings};
 
-#[derive(Debug, Default)]
+#[derive(Debug, Clone, Default)]
 pub struct MeshEvalState {
     pub eval: EvalState,
-    outputs: BTreeMap<NodeId, Mesh>,
+    outputs: BTreeMap<NodeId, Vec<Data>>,
+    display_outputs: BTreeMap<NodeId, usize>,
+    debug_channels: BTreeMap<NodeId, Vec<DebugChannel>>,
+    recovered: BTreeMap<NodeId, String>,
+    // Inside a subnet cook: what each Subnet Input node hands out.
+    subnet_inputs: BTreeMap<NodeId, Option<Mesh>>,
+    // Per-frame outputs of time-dependent nodes, for playback and simulation stepping.
+    frame_caches: BTreeMap<NodeId, FrameCache>,
 }
 
 #[derive(Debug)]
 pub struct MeshEvalResult {
     pub report: EvalReport,
     pub output: Option<Mesh>,
+    pub debug_channels: Vec<DebugChannel>,
 }
 
 impl MeshEvalState {
@@ -23,7 +39,31 @@ impl MeshEvalState {
     }
 
     pub fn mesh_for_node(&self, node_id: NodeId) -> Option<&Mesh> {
-        self.outputs.get(&node_id)
+        let output = self.display_outputs.get(&node_id).copied().unwrap_or(0);
+        self.mesh_for_output(node_id, output)
+    }
+
+    pub fn mesh_for_output(&self, node_id: NodeId, output: usize) -> Option<&Mesh> {
+        self.data_for_output(node_id, output)?.as_mesh()
+    }
+
+    pub fn data_for_output(&self, node_id: NodeId, output: usize) -> Option<&Data> {
+        self.outputs.get(&node_id)?.get(output)
+    }
+
+    // Frames every time-dependent node has cached; empty when there are none.
+    pub fn cached_frames(&self) -> BTreeSet<i32> {
+        let mut caches = self.frame_caches.values();
+        let Some(first) = caches.next() else {
+            return BTreeSet::new();
+        };
+        caches.fold(first.frames(), |frames, cache| {
+            frames.intersection(&cache.frames()).copied().collect()
+        })
+    }
+
+    pub fn clear_frame_caches(&mut self) {
+        self.frame_caches.clear();
     }
 }
 
@@ -31,103 +71,208 @@ pub fn evaluate_mesh_graph(
     graph: &Graph,
     output: NodeId,
     state: &mut MeshEvalState,
+) -> Result<MeshEvalResult, GraphError> {
+    evaluate_mesh_graph_with_progress(graph, output, state, |_, _| {})
+}
+
+// Like `evaluate_mesh_graph`, but hands every freshly cooked node's first output to
+// `progress` as soon as it is ready. Cache hits are skipped.
+pub fn evaluate_mesh_graph_with_progress(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut MeshEvalState,
+    progress: impl FnMut(NodeId, &Mesh),
+) -> Result<MeshEvalResult, GraphError> {
+    evaluate_mesh_graph_cancellable(graph, output, state, &AtomicBool::new(false), progress)
+}
+
+// Checks `cancel` before each node cooks. Once it is set the node in flight still finishes,
+// every remaining node fails with "evaluation cancelled" and keeps its stale signature, so
+// the next evaluation reuses what was cooked and redoes the rest.
+pub fn evaluate_mesh_graph_cancellable(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut MeshEvalState,
+    cancel: &AtomicBool,
+    mut progress: impl FnMut(NodeId, &Mesh),
 ) -> Result<MeshEvalResult, GraphError> {
     let outputs = &mut state.outputs;
-    let report = evaluate_from_with(graph, output, &mut state.eval, |node_id, params| {
+    let channels = &mut state.debug_channels;
+    let recovered = &mut state.recovered;
+    let subnet_inputs = &state.subnet_inputs;
+    let frame_caches = &mut state.frame_caches;
+    frame_caches.retain(|node_id, _| graph.node(*node_id).is_some());
+    state.display_outputs = graph
+        .nodes()
+        .filter(|node| node.display_output > 0)
+        .map(|node| (node.id, node.display_output))
+        .collect();
+    let links_by_input: BTreeMap<PinId, PinId> =
+        graph.links().map(|link| (link.to, link.from)).collect();
+    let mut report = evaluate_from_with(graph, output, &mut state.eval, |node_id, params| {
+        if cancel.load(Ordering::Relaxed) {
+            return Err("evaluation cancelled".to_string());
+        }
         let node = graph
             .node(node_id)
             .ok_or_else(|| "missing node".to_string())?;
         let kind = builtin_kind_from_name(&node.name)
             .ok_or_else(|| format!("unknown node type {}", node.name))?;
 
-        let mut input_meshes = Vec::with_capacity(node.inputs.len());
+        let mut input_data = Vec::with_capacity(node.inputs.len());
         let mut input_names = Vec::with_capacity(node.inputs.len());
         for pin_id in &node.inputs {
             let pin = graph
                 .pin(*pin_id)
                 .ok_or_else(|| "missing input pin".to_string())?;
             input_names.push(pin.name.clone());
-            let link = graph.links().find(|link| link.to == *pin_id);
-            let mesh = if let Some(link) = link {
+            let data = if let Some(&from) = links_by_input.get(pin_id) {
                 let from_pin = graph
-                    .pin(link.from)
+                    .pin(from)
                     .ok_or_else(|| "missing upstream pin".to_string())?;
                 let upstream_id = from_pin.node;
-                let mesh = outputs
+                let output_index = graph
+                    .node(upstream_id)
+                    .and_then(|upstream| upstream.outputs.iter().position(|id| *id == from))
+                    .unwrap_or(0);
+                let data = outputs
                     .get(&upstream_id)
+                    .and_then(|data| data.get(output_index))
                     .ok_or_else(|| format!("missing upstream output {:?}", upstream_id))?;
-                Some(mesh.clone())
+                Some(data.clone())
             } else {
                 None
             };
-            input_meshes.push(mesh);
+            input_data.push(data);
         }
+        let input_meshes = input_data
+            .into_iter()
+            .zip(&input_names)
+            .map(|(data, name)| {
+                data.map(|data| {
+                    data.into_mesh()
+                        .map_err(|err| format!("input '{}': {}", name, err))
+                })
+                .transpose()
+            })
+            .collect::<Result<Vec<_>, String>>()?;
 
-        let inputs = match kind {
-            crate::nodes_builtin::BuiltinNodeKind::Transform
-            | crate::nodes_builtin::BuiltinNodeKind::CopyTransform
-            | crate::nodes_builtin::BuiltinNodeKind::Normal
-            | crate::nodes_builtin::BuiltinNodeKind::Scatter
-            | crate::nodes_builtin::BuiltinNodeKind::Color
-            | crate::nodes_builtin::BuiltinNodeKind::Noise
-            | crate::nodes_builtin::BuiltinNodeKind::AttributeMath
-            | crate::nodes_builtin::BuiltinNodeKind::Wrangle
-            | crate::nodes_builtin::BuiltinNodeKind::ObjOutput
-            | crate::nodes_builtin::BuiltinNodeKind::Output => {
-                if let Some(mesh) = input_meshes.first().and_then(|mesh| mesh.clone()) {
-                    vec![mesh]
-                } else {
-                    let name = input_names
-                        .first()
-                        .cloned()
-                        .unwrap_or_else(|| "in".to_string());
-                    return Err(format!("missing input '{}'", name));
-                }
-            }
-            crate::nodes_builtin::BuiltinNodeKind::CopyToPoints => {
-                let source = input_meshes.first().and_then(|mesh| mesh.clone());
-                let template = input_meshes.get(1).and_then(|mesh| mesh.clone());
-                if source.is_none() {
-                    let name = input_names
-                        .first()
-                        .cloned()
-                        .unwrap_or_else(|| "source".to_string());
-                    return Err(format!("missing input '{}'", name));
+        let time_dependent =
+            kind.is_animated() || !node.param_keyframes.is_empty() || reads_time(node);
+        let frame = if kind.is_animated() {
+            params.get_float(FRAME_PARAM, 0.0)
+        } else {
+            graph.time().frame
+        };
+        let pass_through = (graph.node_error_policy(node_id) == ErrorPolicy::PassThrough)
+            .then(|| input_meshes.first().cloned().flatten())
+            .flatten();
+        let cooked = isolate(|| match kind {
+            BuiltinNodeKind::Subnet => match node.subnet.as_deref() {
+                Some(child) => cook_subnet(graph, child, input_meshes),
+                None => Err("subnet has no contents".to_string()),
+            },
+            BuiltinNodeKind::SubnetInput => match subnet_inputs.get(&node_id) {
+                Some(Some(mesh)) => Ok(vec![mesh.clone()]),
+                Some(None) => Err(format!(
+                    "subnet input '{}' is not connected",
+                    params.get_string("name", "in")
+                )),
+                None => Err("Subnet Input only works inside a subnet".to_string()),
+            },
+            BuiltinNodeKind::ParticleSim => match input_meshes.first().cloned().flatten() {
+                Some(input) => {
+                    let cache = frame_caches.entry(node_id).or_default();
+                    cache.sync(particle_cache_key(&input, node, params));
+                    let frame = params.get_float(FRAME_PARAM, 0.0).floor() as i32;
+                    let settings = ParticleSettings::from_params(params);
+                    simulate_particles(cache, &input, &settings, frame).map(|mesh| vec![mesh])
                 }
-                if template.is_none() {
-                    let name = input_names
-                        .get(1)
-                        .cloned()
-                        .unwrap_or_else(|| "template".to_string());
-                    return Err(format!("missing input '{}'", name));
+                None => Err(format!(
+                    "missing input '{}'",
+                    input_names.first().map_or("in", String::as_str)
+                )),
+            },
+            _ if time_dependent => match cache_frame(frame) {
+                Some(frame) => {
+                    let cache = frame_caches.entry(node_id).or_default();
+                    cache.sync(frame_independent_key(node, params));
+                    let input_key = frame_input_key(&input_meshes, params, graph.time().fps);
+                    match cache.get(frame, input_key) {
+                        Some(meshes) => Ok(meshes),
+                        None => cook_node(kind, params, input_meshes, &input_names)
+                            .inspect(|meshes| cache.insert(frame, input_key, meshes.clone())),
+                    }
                 }
-                vec![source.unwrap(), template.unwrap()]
+                None => cook_node(kind, params, input_meshes, &input_names),
+            },
+            _ => {
+                frame_caches.remove(&node_id);
+                cook_node(kind, params, input_meshes, &input_names)
+            }
+        });
+        let mut meshes = match cooked {
+            Ok(meshes) => {
+                recovered.remove(&node_id);
+                meshes
             }
-            crate::nodes_builtin::BuiltinNodeKind::Merge => {
-                input_meshes.into_iter().flatten().collect()
+            Err(message) => {
+                // The cook may have panicked halfway through writing its frames.
+                frame_caches.remove(&node_id);
+                let Some(mesh) = pass_through else {
+                    recovered.remove(&node_id);
+                    return Err(message);
+                };
+                recovered.insert(node_id, message);
+                vec![mesh; node.outputs.len().max(1)]
             }
-            _ => Vec::new(),
         };
-
-        if matches!(kind, crate::nodes_builtin::BuiltinNodeKind::Merge) && inputs.is_empty() {
-            return Err("Merge requires at least one mesh input".to_string());
+        let tagged: Vec<DebugChannel> = meshes
+            .iter_mut()
+            .flat_map(|mesh| std::mem::take(&mut mesh.debug_channels))
+            .collect();
+        if tagged.is_empty() {
+            channels.remove(&node_id);
+        } else {
+            channels.insert(node_id, tagged);
         }
-
-        let mesh = compute_mesh_node(kind, params, &inputs)?;
-        outputs.insert(node_id, mesh);
+        if let Some(mesh) = meshes.first() {
+            progress(node_id, mesh);
+        }
+        outputs.insert(
+            node_id,
+            meshes
+                .into_iter()
+                .map(|mesh| Data::Mesh(Arc::new(mesh)))
+                .collect(),
+        );
         Ok(())
     })?;
+    report.warnings = report
+        .ordered
+        .iter()
+        .filter_map(|node| {
+            let message = state.recovered.get(node)?;
+            Some(EvalWarning {
+                node: *node,
+                message: message.clone(),
+            })
+        })
+        .collect();
 
     if !report.output_valid {
         for err in &report.errors {
             match err {
-                crate::eval::EvalError::Node { node, .. } => {
+                EvalError::Node { node, .. } => {
                     outputs.remove(node);
+                    channels.remove(node);
                 }
-                crate::eval::EvalError::Upstream { node, upstream } => {
+                EvalError::Upstream { node, upstream } => {
                     outputs.remove(node);
+                    channels.remove(node);
                     for upstream_node in upstream {
                         outputs.remove(upstream_node);
+                        channels.remove(upstream_node);
                     }
                 }
             }
@@ -136,12 +281,399 @@ pub fn evaluate_mesh_graph(
         return Ok(MeshEvalResult {
             report,
             output: None,
+            debug_channels: Vec::new(),
         });
     }
 
-    let output_mesh = outputs.get(&output).cloned();
+    let output_mesh = state.mesh_for_node(output).cloned();
+    let debug_channels = report
+        .ordered
+        .iter()
+        .filter_map(|node_id| state.debug_channels.get(node_id))
+        .flatten()
+        .cloned()
+        .collect();
     Ok(MeshEvalResult {
         report,
         output: output_mesh,
+        debug_channels,
     })
 }
+
+// Cooks a subnet with `inputs` bound to its Subnet Input nodes, one mesh per Subnet Output.
+fn cook_subnet(
+    graph: &Graph,
+    child: &Graph,
+    inputs: Vec<Option<Mesh>>,
+) -> Result<Vec<Mesh>, String> {
+    let child = child.with_project_params_of(graph);
+    let outputs = child.subnet_ports(BuiltinNodeKind::SubnetOutput);
+    if outputs.is_empty() {
+        return Err("subnet has no Subnet Output node".to_string());
+    }
+    let mut state = MeshEvalState::new();
+    state.subnet_inputs = child
+        .subnet_ports(BuiltinNodeKind::SubnetInput)
+        .into_iter()
+        .zip(inputs.into_iter().chain(std::iter::repeat(None)))
+        .collect();
+    outputs
+        .into_iter()
+        .map(|output| {
+            let result = evaluate_mesh_graph(&child, output, &mut state)
+                .map_err(|err| format!("{:?}", err))?;
+            result.output.ok_or_else(|| {
+                let message = result
+                    .report
+                    .errors
+                    .iter()
+                    .find_map(|err| match err {
+                        EvalError::Node { message, .. } => Some(message.as_str()),
+                        EvalError::Upstream { .. } => None,
+                    })
+                    .unwrap_or("no output");
+                format!("inside subnet: {}", message)
+            })
+        })
+        .collect()
+}
+
+fn cook_node(
+    kind: BuiltinNodeKind,
+    params: &NodeParams,
+    input_meshes: Vec<Option<Mesh>>,
+    input_names: &[String],
+) -> Result<Vec<Mesh>, String> {
+    let inputs = match kind {
+        BuiltinNodeKind::Transform
+        | BuiltinNodeKind::CopyTransform
+        | BuiltinNodeKind::Normal
+        | BuiltinNodeKind::Scatter
+        | BuiltinNodeKind::Color
+        | BuiltinNodeKind::Noise
+        | BuiltinNodeKind::Wobble
+        | BuiltinNodeKind::ParticleSim
+        | BuiltinNodeKind::AttributeMath
+        | BuiltinNodeKind::AttributePrecision
+        | BuiltinNodeKind::AttributeEdit
+        | BuiltinNodeKind::Wrangle
+        | BuiltinNodeKind::PolyExtrude
+        | BuiltinNodeKind::Bevel
+        | BuiltinNodeKind::Clean
+        | BuiltinNodeKind::Sort
+        | BuiltinNodeKind::Split
+        | BuiltinNodeKind::Lod
+        | BuiltinNodeKind::BakeAo
+        | BuiltinNodeKind::Curvature
+        | BuiltinNodeKind::Slope
+        | BuiltinNodeKind::GeodesicDistance
+        | BuiltinNodeKind::FloorSplit
+        | BuiltinNodeKind::FacadeRepeat
+        | BuiltinNodeKind::WindowScatter
+        | BuiltinNodeKind::SubnetOutput
+        | BuiltinNodeKind::ObjOutput
+        | BuiltinNodeKind::Output => {
+            if let Some(mesh) = input_meshes.first().and_then(|mesh| mesh.clone()) {
+                vec![mesh]
+            } else {
+                let name = input_names
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "in".to_string());
+                return Err(format!("missing input '{}'", name));
+            }
+        }
+        BuiltinNodeKind::CopyToPoints | BuiltinNodeKind::Boolean => {
+            let source = input_meshes.first().and_then(|mesh| mesh.clone());
+            let template = input_meshes.get(1).and_then(|mesh| mesh.clone());
+            if source.is_none() {
+                let name = input_names
+                    .first()
+                    .cloned()
+                    .unwrap_or_else(|| "source".to_string());
+                return Err(format!("missing input '{}'", name));
+            }
+            if template.is_none() {
+                let name = input_names
+                    .get(1)
+                    .cloned()
+                    .unwrap_or_else(|| "template".to_string());
+                return Err(format!("missing input '{}'", name));
+            }
+            vec![source.unwrap(), template.unwrap()]
+        }
+        BuiltinNodeKind::Merge => input_meshes.into_iter().flatten().collect(),
+        _ => Vec::new(),
+    };
+
+    if matches!(kind, BuiltinNodeKind::Merge) && inputs.is_empty() {
+        return Err("Merge requires at least one mesh input".to_string());
+    }
+
+    compute_mesh_node_outputs(kind, params, &inputs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::attributes::{AttributeDomain, AttributeRef};
+    use crate::graph::ParamValue;
+    use crate::nodes_builtin::node_definition;
+
+    #[test]
+    fn pass_through_policy_recovers_failed_nodes() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let wrangle = graph.add_node(node_definition(BuiltinNodeKind::Wrangle));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        graph
+            .set_param(
+                wrangle,
+                "code".to_string(),
+                ParamValue::String("@P = ;".to_string()),
+            )
+            .unwrap();
+        for (from, to) in [(source, wrangle), (wrangle, out)] {
+            let from_pin = graph.node(from).unwrap().outputs[0];
+            let to_pin = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from_pin, to_pin).unwrap();
+        }
+
+        let mut state = MeshEvalState::new();
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert!(result.output.is_none());
+        assert!(result.report.warnings.is_empty());
+
+        graph
+            .set_node_error_policy(wrangle, Some(ErrorPolicy::PassThrough))
+            .unwrap();
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert!(result.report.errors.is_empty());
+        assert_eq!(result.report.warnings.len(), 1);
+        assert_eq!(result.report.warnings[0].node, wrangle);
+        assert_eq!(result.output.unwrap().face_count(), 6);
+
+        graph.set_node_error_policy(wrangle, None).unwrap();
+        assert!(evaluate_mesh_graph(&graph, out, &mut state)
+            .unwrap()
+            .output
+            .is_none());
+        graph.set_error_policy(ErrorPolicy::PassThrough);
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert!(result.output.is_some());
+        assert_eq!(result.report.warnings.len(), 1);
+    }
+
+    #[test]
+    fn cloned_state_shares_cooked_meshes() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let mut state = MeshEvalState::new();
+        evaluate_mesh_graph(&graph, source, &mut state).unwrap();
+
+        let copy = state.clone();
+        let (Some(Data::Mesh(original)), Some(Data::Mesh(shared))) = (
+            state.data_for_output(source, 0),
+            copy.data_for_output(source, 0),
+        ) else {
+            panic!("box should cook to a mesh");
+        };
+        assert!(Arc::ptr_eq(original, shared));
+    }
+
+    #[test]
+    fn time_variables_follow_the_timeline() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let wrangle = graph.add_node(node_definition(BuiltinNodeKind::Wrangle));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        graph
+            .set_param(
+                wrangle,
+                "code".to_string(),
+                ParamValue::String("@w = $F + $T".to_string()),
+            )
+            .unwrap();
+        graph
+            .set_param(source, "size", ParamValue::Vec3([1.0; 3]))
+            .unwrap();
+        graph
+            .set_param_expression(source, "size", Some("vec3($F)".to_string()))
+            .unwrap();
+        for (from, to) in [(source, wrangle), (wrangle, out)] {
+            let from_pin = graph.node(from).unwrap().outputs[0];
+            let to_pin = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from_pin, to_pin).unwrap();
+        }
+        let w = |mesh: &Mesh| match mesh.attribute(AttributeDomain::Point, "w") {
+            Some(AttributeRef::Float(values)) => values[0],
+            _ => f32::NAN,
+        };
+
+        let mut state = MeshEvalState::new();
+        assert!(graph.apply_keyframes(25.0, 24.0));
+        let mesh = evaluate_mesh_graph(&graph, out, &mut state)
+            .unwrap()
+            .output
+            .unwrap();
+        assert_eq!(w(&mesh), 26.0);
+        assert_eq!(mesh.bounds().unwrap().max[0], 12.5);
+
+        // Moving the timeline recooks both readers; staying put cooks nothing.
+        assert!(!graph.apply_keyframes(25.0, 24.0));
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert!(result.report.computed.is_empty());
+        assert!(graph.apply_keyframes(49.0, 24.0));
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert_eq!(result.report.computed, vec![source, wrangle, out]);
+        assert_eq!(w(&result.output.unwrap()), 51.0);
+    }
+
+    #[test]
+    fn keyframed_and_time_reading_nodes_stay_cached_during_playback() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let wobble = graph.add_node(node_definition(BuiltinNodeKind::Wobble));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        for (from, to) in [(source, wobble), (wobble, transform)] {
+            let from_pin = graph.node(from).unwrap().outputs[0];
+            let to_pin = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from_pin, to_pin).unwrap();
+        }
+        graph
+            .set_param_keyframe(wobble, "amplitude", 1.0, ParamValue::Float(0.1))
+            .unwrap();
+        graph
+            .set_param_keyframe(wobble, "amplitude", 3.0, ParamValue::Float(0.5))
+            .unwrap();
+        graph
+            .set_param_expression(transform, "translate", Some("vec3($F, 0, 0)".to_string()))
+            .unwrap();
+
+        let mut state = MeshEvalState::new();
+        let play = |graph: &mut Graph, state: &mut MeshEvalState| {
+            for frame in 1..=3 {
+                graph.apply_keyframes(frame as f32, 24.0);
+                evaluate_mesh_graph(graph, transform, state).unwrap();
+            }
+        };
+        play(&mut graph, &mut state);
+        assert_eq!(state.cached_frames(), BTreeSet::from([1, 2, 3]));
+
+        // Editing a key drops the range; replaying fills it again.
+        graph
+            .set_param_keyframe(wobble, "amplitude", 3.0, ParamValue::Float(0.8))
+            .unwrap();
+        graph.apply_keyframes(1.0, 24.0);
+        evaluate_mesh_graph(&graph, transform, &mut state).unwrap();
+        assert_eq!(state.frame_caches[&wobble].frames(), BTreeSet::from([1]));
+        play(&mut graph, &mut state);
+        assert_eq!(
+            state.frame_caches[&wobble].frames(),
+            BTreeSet::from([1, 2, 3])
+        );
+    }
+
+    #[test]
+    fn progress_reports_each_cooked_node() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        let from_pin = graph.node(source).unwrap().outputs[0];
+        let to_pin = graph.node(out).unwrap().inputs[0];
+        graph.add_link(from_pin, to_pin).unwrap();
+
+        let mut state = MeshEvalState::new();
+        let mut cooked = Vec::new();
+        evaluate_mesh_graph_with_progress(&graph, out, &mut state, |node, mesh| {
+            cooked.push((node, mesh.face_count()))
+        })
+        .unwrap();
+        assert_eq!(cooked, vec![(source, 6), (out, 6)]);
+
+        cooked.clear();
+        evaluate_mesh_graph_with_progress(&graph, out, &mut state, |node, mesh| {
+            cooked.push((node, mesh.face_count()))
+        })
+        .unwrap();
+        assert!(cooked.is_empty());
+    }
+
+    #[test]
+    fn subnet_cooks_its_contents_and_recooks_on_inner_edits() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let subnet = graph.add_node(node_definition(BuiltinNodeKind::Subnet));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        for (from, to) in [(source, subnet), (subnet, out)] {
+            let from_pin = graph.node(from).unwrap().outputs[0];
+            let to_pin = graph.node(to).unwrap().inputs[0];
+            graph.add_link(from_pin, to_pin).unwrap();
+        }
+
+        let mut state = MeshEvalState::new();
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert_eq!(result.output.unwrap().face_count(), 6);
+
+        let child = graph.subnet_mut(subnet).unwrap();
+        let input = child.subnet_ports(BuiltinNodeKind::SubnetInput)[0];
+        let output = child.subnet_ports(BuiltinNodeKind::SubnetOutput)[0];
+        let link = child.links().next().unwrap().id;
+        child.remove_link(link);
+        let transform = child.add_node(node_definition(BuiltinNodeKind::Transform));
+        child
+            .set_param(transform, "translate", ParamValue::Vec3([0.0, 2.0, 0.0]))
+            .unwrap();
+        for (from, to) in [(input, transform), (transform, output)] {
+            let from_pin = child.node(from).unwrap().outputs[0];
+            let to_pin = child.node(to).unwrap().inputs[0];
+            child.add_link(from_pin, to_pin).unwrap();
+        }
+        let extra = child.add_node(node_definition(BuiltinNodeKind::SubnetOutput));
+        child.set_param(extra, "index", ParamValue::Int(1)).unwrap();
+        let from_pin = child.node(input).unwrap().outputs[0];
+        let to_pin = child.node(extra).unwrap().inputs[0];
+        child.add_link(from_pin, to_pin).unwrap();
+        graph.sync_subnet_pins(subnet).unwrap();
+        assert_eq!(graph.node(subnet).unwrap().outputs.len(), 2);
+        assert!(graph
+            .links()
+            .any(|link| link.from == graph.node(subnet).unwrap().outputs[0]));
+
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert_eq!(result.report.computed, vec![subnet, out]);
+        let bounds = result.output.unwrap().bounds().unwrap();
+        assert!((bounds.min[1] - 1.5).abs() < 1e-5);
+        let unmoved = state.mesh_for_output(subnet, 1).unwrap().bounds().unwrap();
+        assert!((unmoved.min[1] + 0.5).abs() < 1e-5);
+
+        assert!(evaluate_mesh_graph(&graph, out, &mut state)
+            .unwrap()
+            .report
+            .computed
+            .is_empty());
+    }
+
+    #[test]
+    fn cancelled_cook_resumes_from_finished_nodes() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        let from_pin = graph.node(source).unwrap().outputs[0];
+        let to_pin = graph.node(out).unwrap().inputs[0];
+        graph.add_link(from_pin, to_pin).unwrap();
+
+        let mut state = MeshEvalState::new();
+        let cancel = AtomicBool::new(false);
+        let result = evaluate_mesh_graph_cancellable(&graph, out, &mut state, &cancel, |_, _| {
+            cancel.store(true, Ordering::Relaxed)
+        })
+        .unwrap();
+        assert!(result.output.is_none());
+        assert_eq!(result.report.computed, vec![source]);
+
+        let result = evaluate_mesh_graph(&graph, out, &mut state).unwrap();
+        assert_eq!(result.report.computed, vec![out]);
+        assert_eq!(result.output.unwrap().face_count(), 6);
+    }
+}
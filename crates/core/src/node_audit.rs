@@ -0,0 +1,104 @@
+use std::collections::BTreeMap;
+
+use crate::graph::{Graph, GraphError, NodeId, NodeParams};
+use crate::nodes_builtin::{
+    builtin_kind_from_name, default_params, node_definition, BuiltinNodeKind,
+};
+
+// Every node of the named kind with its effective parameters: stored values over the
+// kind's defaults.
+pub fn node_kind_usage(graph: &Graph, kind: &str) -> Vec<(NodeId, NodeParams)> {
+    graph
+        .nodes()
+        .filter(|node| node.name == kind)
+        .map(|node| {
+            let mut params = builtin_kind_from_name(kind)
+                .map(default_params)
+                .unwrap_or_default();
+            params.values.extend(
+                node.params
+                    .values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.clone())),
+            );
+            (node.id, params)
+        })
+        .collect()
+}
+
+// Builds `target`'s parameters from a source node's. `mapping` names the source key for a
+// target key, and unmapped keys look for the same name. A value carries over only when its
+// type matches; otherwise the target default stays.
+pub fn map_node_params(
+    source: &NodeParams,
+    target: BuiltinNodeKind,
+    mapping: &BTreeMap<String, String>,
+) -> NodeParams {
+    let mut params = default_params(target);
+    for (key, value) in params.values.iter_mut() {
+        let source_key = mapping.get(key).unwrap_or(key);
+        if let Some(source_value) = source
+            .values
+            .get(source_key)
+            .filter(|source_value| source_value.type_name() == value.type_name())
+        {
+            *value = source_value.clone();
+        }
+    }
+    params
+}
+
+// Swaps every node of the named kind to `target` in place; returns the nodes changed.
+pub fn replace_node_kind(
+    graph: &mut Graph,
+    kind: &str,
+    target: BuiltinNodeKind,
+    mapping: &BTreeMap<String, String>,
+) -> Result<Vec<NodeId>, GraphError> {
+    let usage = node_kind_usage(graph, kind);
+    for (node_id, params) in &usage {
+        let params = map_node_params(params, target, mapping);
+        graph.replace_node_definition(*node_id, node_definition(target), params)?;
+    }
+    Ok(usage.into_iter().map(|(node_id, _)| node_id).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ParamValue;
+
+    #[test]
+    fn replaces_kind_in_place_with_mapped_params() {
+        let mut graph = Graph::default();
+        let sphere = graph.add_node(node_definition(BuiltinNodeKind::Sphere));
+        let transform = graph.add_node(node_definition(BuiltinNodeKind::Transform));
+        graph
+            .set_param(sphere, "radius", ParamValue::Float(2.5))
+            .unwrap();
+        graph.set_param(sphere, "rows", ParamValue::Int(8)).unwrap();
+        graph
+            .set_param_expression(sphere, "radius", Some("1 + 1".to_string()))
+            .unwrap();
+        let from = graph.node(sphere).unwrap().outputs[0];
+        let to = graph.node(transform).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let usage = node_kind_usage(&graph, "Sphere");
+        assert_eq!(usage.len(), 1);
+        assert_eq!(usage[0].1.get_int("cols", 0), 32);
+
+        let mapping = BTreeMap::from([("cols".to_string(), "rows".to_string())]);
+        let replaced = replace_node_kind(&mut graph, "Sphere", BuiltinNodeKind::Grid, &mapping);
+        assert_eq!(replaced.unwrap(), vec![sphere]);
+
+        let node = graph.node(sphere).unwrap();
+        assert_eq!(node.name, "Grid");
+        assert_eq!(node.params.get_int("rows", 0), 8);
+        assert_eq!(node.params.get_int("cols", 0), 8);
+        assert_eq!(node.params.get_vec2("size", [0.0; 2]), [2.0, 2.0]);
+        assert!(node.param_expressions.is_empty());
+        assert!(graph.links().any(|link| link.from == from && link.to == to));
+        assert!(node_kind_usage(&graph, "Sphere").is_empty());
+    }
+}
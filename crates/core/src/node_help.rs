@@ -0,0 +1,316 @@
+use crate::graph::NodeHelp;
+use crate::nodes_builtin::BuiltinNodeKind;
+
+pub fn builtin_node_help(kind: BuiltinNodeKind) -> NodeHelp {
+    let (summary, page) = match kind {
+        BuiltinNodeKind::Box => (
+            "Creates an axis-aligned box.",
+            "## Parameters\n\
+             - size: Edge lengths along X, Y and Z.\n\
+             - center: Position of the box center.",
+        ),
+        BuiltinNodeKind::Grid => (
+            "Creates a flat grid in the XZ plane.",
+            "## Parameters\n\
+             - size: Width and depth of the grid.\n\
+             - rows / cols: Number of divisions along each side.\n\
+             - center: Position of the grid center.",
+        ),
+        BuiltinNodeKind::Sphere => (
+            "Creates a UV sphere.",
+            "## Parameters\n\
+             - radius: Sphere radius.\n\
+             - rows / cols: Latitude and longitude divisions.\n\
+             - center: Position of the sphere center.",
+        ),
+        BuiltinNodeKind::File => (
+            "Loads a mesh from an OBJ file on disk.",
+            "## Parameters\n\
+             - path: OBJ file to load. Not available in web builds.",
+        ),
+        BuiltinNodeKind::MergeFiles => (
+            "Loads several OBJ files and merges them into one mesh.",
+            "## Parameters\n\
+             - paths: OBJ files to load, one per row. Empty rows are skipped.\n\n\
+             The primitive attribute `file` holds the row each primitive came from.",
+        ),
+        BuiltinNodeKind::Transform => (
+            "Translates, rotates and scales the input mesh.",
+            "## Parameters\n\
+             - translate: Offset applied after rotation and scale.\n\
+             - rotate_deg: Euler rotation in degrees.\n\
+             - scale: Per-axis scale factors.\n\
+             - pivot: Point that rotation and scale happen around.\n\
+             - mirror: Mirror the result across the project symmetry plane.",
+        ),
+        BuiltinNodeKind::CopyTransform => (
+            "Makes copies of the input, each transformed a step further than the last.",
+            "## Parameters\n\
+             - count: Number of copies, including the original.\n\
+             - translate_step: Offset added per copy.\n\
+             - rotate_step_deg: Rotation added per copy, in degrees.\n\
+             - scale_step: Scale added per copy.",
+        ),
+        BuiltinNodeKind::Merge => (
+            "Combines all connected meshes into one.",
+            "Connect two or more meshes. Use the node menu to reorder inputs.",
+        ),
+        BuiltinNodeKind::CopyToPoints => (
+            "Places a copy of the source mesh on every template point.",
+            "## Inputs\n\
+             - source: Mesh to copy.\n\
+             - template: Points to copy onto.\n\
+             \n\
+             ## Parameters\n\
+             - align_to_normals: Orient copies along the template point normals.\n\
+             - translate / rotate_deg / scale: Extra transform applied to every copy.\n\
+             - orient: Quaternion rotation applied on top of rotate_deg.",
+        ),
+        BuiltinNodeKind::Boolean => (
+            "Combines two closed meshes as a union, difference or intersection.",
+            "## Inputs\n\
+             - a: First mesh; difference keeps the parts of a outside b.\n\
+             - b: Second mesh.\n\
+             \n\
+             ## Parameters\n\
+             - mode: 0 union, 1 difference (a minus b), 2 intersection.\n\n\
+             Both inputs should be closed and not intersect themselves; open meshes leave \
+             holes. Faces keep their primitive attributes. Point attributes on cut faces \
+             come from the nearest original corner.",
+        ),
+        BuiltinNodeKind::Scatter => (
+            "Scatters random points across the input surface.",
+            "## Parameters\n\
+             - count: Number of points to generate.\n\
+             - seed: Random seed; change it to get a different distribution.\n\
+             - density_attr: Optional float attribute (point, vertex or primitive) that \
+             scales how many points land on each face. Empty scatters by area alone.",
+        ),
+        BuiltinNodeKind::Normal => (
+            "Recomputes normals, splitting them across sharp edges.",
+            "## Parameters\n\
+             - threshold_deg: Edges sharper than this angle get split normals.",
+        ),
+        BuiltinNodeKind::Color => (
+            "Sets a constant Cd color attribute.",
+            "## Parameters\n\
+             - color: RGB color to write.\n\
+             - domain: 0 point, 1 vertex, 2 primitive, 3 detail.",
+        ),
+        BuiltinNodeKind::Noise => (
+            "Displaces points along their normals with fractal noise.",
+            "## Parameters\n\
+             - amplitude: Maximum displacement distance.\n\
+             - frequency: Noise feature scale.\n\
+             - seed: Noise variation.\n\
+             - offset: Shifts the noise field.\n\
+             - basis: 0 value, 1 perlin, 2 simplex, 3 worley.\n\
+             - octaves: Number of fbm layers.\n\
+             - mirror: Mirror the result across the project symmetry plane.",
+        ),
+        BuiltinNodeKind::Wobble => (
+            "Animates points with a wave or jitter that follows the timeline.",
+            "## Parameters\n\
+             - amplitude: Largest offset.\n\
+             - frequency: Spatial frequency; higher values give shorter waves.\n\
+             - period: Frames per cycle.\n\
+             - jitter: Wander along curl noise instead of waving along the normals.\n\
+             - seed: Noise seed for jitter.\n\
+             - frame: Set from the timeline on every frame unless it is keyframed.",
+        ),
+        BuiltinNodeKind::ParticleSim => (
+            "Advects points over the timeline by their velocity, gravity and a noise field.",
+            "Simulated frames are cached, so playing forward steps once per frame and scrubbing \
+             back is instant. Changing the input or any parameter starts over.\n\n\
+             ## Parameters\n\
+             - start_frame: Frame at which the points sit at their input positions.\n\
+             - fps: Frames per second; sets the time step.\n\
+             - attr: Point vector attribute holding the velocity. Missing means at rest; the \
+             output carries the simulated velocity in it.\n\
+             - gravity: Constant acceleration.\n\
+             - drag: Fraction of velocity lost per second.\n\
+             - noise: Strength of the curl-noise swirl.\n\
+             - frequency: Spatial scale of the noise.\n\
+             - seed: Noise variation.\n\
+             - frame: Set from the timeline on every frame unless it is keyframed.",
+        ),
+        BuiltinNodeKind::AttributeMath => (
+            "Applies a math operation to an attribute.",
+            "## Parameters\n\
+             - attr: Attribute to read.\n\
+             - result: Attribute to write; defaults to attr.\n\
+             - domain: 0 point, 1 vertex, 2 primitive, 3 detail.\n\
+             - op: 0 add, 1 subtract, 2 multiply, 3 divide.\n\
+             - value_f: Operand for float and int attributes.\n\
+             - value_v3: Operand for vector attributes.",
+        ),
+        BuiltinNodeKind::AttributePrecision => (
+            "Rounds an attribute to a reduced storage precision.",
+            "## Parameters\n\
+             - attr: Attribute to round; implicit P and N stay full precision.\n\
+             - domain: 0 point, 1 vertex, 2 primitive, 3 detail.\n\
+             - precision: 0 f32, 1 f16 half float, 2 u8 normalized (clamped to 0-1).\n\n\
+             Later writes to the attribute keep the precision.",
+        ),
+        BuiltinNodeKind::AttributeEdit => (
+            "Overrides single attribute values, as edited in the spreadsheet.",
+            "## Parameters\n\
+             - edits: One cell per line as `domain attribute element value`, e.g. \
+             `point Cd.y 3 0.5`. Domains are point, vertex, prim and detail.\n\n\
+             Select the node and double-click a spreadsheet cell to add or change an edit. \
+             An edit whose attribute or element is missing upstream fails the node.",
+        ),
+        BuiltinNodeKind::Wrangle => (
+            "Runs a small expression snippet over every element.",
+            "## Parameters\n\
+             - mode: 0 point, 1 vertex, 2 primitive, 3 detail.\n\
+             - code: Statements such as @Cd = vec3(1.0, 0.0, 0.0); or @P.y += sin(@P.x * 3) * 0.2;\n\
+             - mirror: Mirror point positions across the project symmetry plane.\n\
+             \n\
+             $F and $T read the timeline frame and seconds; code that uses them recooks on \
+             every frame. Use the ? button on the node header for the full cheat sheet.",
+        ),
+        BuiltinNodeKind::PolyExtrude => (
+            "Extrudes faces along their normals.",
+            "## Parameters\n\
+             - distance: Extrusion distance.\n\
+             - inset: Shrinks the extruded caps toward their centers.\n\
+             - individual: Extrude each face separately instead of connected regions.\n\
+             - group: Primitive group to extrude; empty extrudes everything.\n\
+             - side_group: Group name written to the new side faces.\n\
+             - extrude_side: Keep the side walls.",
+        ),
+        BuiltinNodeKind::Bevel => (
+            "Bevels sharp edges.",
+            "## Parameters\n\
+             - width: Bevel width.\n\
+             - segments: Number of rounding segments.\n\
+             - angle_deg: Only edges sharper than this angle are beveled.\n\
+             - group: Primitive group to limit the bevel to.",
+        ),
+        BuiltinNodeKind::Clean => (
+            "Fixes common mesh problems.",
+            "## Parameters\n\
+             - fix_winding: Make face winding consistent.\n\
+             - flip: Reverse all faces.\n\
+             - triangulate: Split polygons into triangles.\n\
+             - quadrangulate: Pair triangles sharing an edge into convex quads.\n\
+             - quad_angle_deg: Largest angle between paired triangles' normals.",
+        ),
+        BuiltinNodeKind::Sort => (
+            "Reorders points or primitives.",
+            "## Parameters\n\
+             - domain: 0 point or 1 primitive.\n\
+             - key: 0 along an axis, 1 by distance to a point, 2 by an attribute.\n\
+             - axis / point / attr: Inputs for the chosen key.\n\
+             - reverse: Reverse the resulting order.",
+        ),
+        BuiltinNodeKind::Split => (
+            "Splits the mesh by a primitive group.",
+            "The first output holds primitives in the group, the second everything else.\n\
+             \n\
+             ## Parameters\n\
+             - group: Primitive group name.",
+        ),
+        BuiltinNodeKind::Lod => (
+            "Builds decimated levels of detail and merges them, tagged by group.",
+            "Each level gets an integer attribute with its index and a group named after the \
+             attribute plus the index, e.g. lod0, lod1. Use Split to pull out a level.\n\
+             \n\
+             ## Parameters\n\
+             - ratios: Point count ratio per level, e.g. 1 0.5 0.25.\n\
+             - attr: Attribute and group prefix.",
+        ),
+        BuiltinNodeKind::BakeAo => (
+            "Bakes ambient occlusion into a point color attribute.",
+            "## Parameters\n\
+             - samples: Rays cast per point.\n\
+             - max_distance: Occluders farther away are ignored; 0 means unlimited.\n\
+             - light_mix: Blend in a shadowed directional light.\n\
+             - light_dir: Direction toward the light.\n\
+             - attr: Attribute to write.",
+        ),
+        BuiltinNodeKind::Curvature => (
+            "Writes a float attribute measuring how curved the surface is.",
+            "Positive values are convex, negative values concave.\n\
+             \n\
+             ## Parameters\n\
+             - mode: 0 signed, 1 convex only, 2 concave only.\n\
+             - scale: Multiplier applied to the result.\n\
+             - smooth: Number of smoothing passes.\n\
+             - attr: Attribute to write.",
+        ),
+        BuiltinNodeKind::GeodesicDistance => (
+            "Writes the distance along the surface from a group of seed points.",
+            "## Parameters\n\
+             - group: Point group holding the seed points.\n\
+             - normalize: Divide by the largest distance to get 0-1.\n\
+             - attr: Point float attribute to write.\n\n\
+             Distances follow mesh edges, so they run slightly long on coarse meshes. \
+             Points not connected to a seed get the largest distance and are shown in the \
+             geodesic_unreachable debug channel.",
+        ),
+        BuiltinNodeKind::FloorSplit => (
+            "Cuts wall quads into horizontal floor bands.",
+            "## Parameters\n\
+             - floor_height: Target floor height; each wall gets a whole number of floors.\n\
+             - attr: Primitive int attribute for the floor index, 0 at the bottom.\n\n\
+             Faces that are not upright quads, such as roofs, pass through with -1.",
+        ),
+        BuiltinNodeKind::FacadeRepeat => (
+            "Cuts wall quads into vertical bays along the facade.",
+            "## Parameters\n\
+             - bay_width: Target bay width; each wall gets a whole number of bays.\n\
+             - attr: Primitive int attribute for the bay index.\n\n\
+             Run after Floor Split to get one panel per floor and bay.",
+        ),
+        BuiltinNodeKind::WindowScatter => (
+            "Places a point on wall panels for instancing windows with Copy to Points.",
+            "## Parameters\n\
+             - density: Fraction of panels that get a window.\n\
+             - seed: Random variation.\n\
+             - offset: Pushes points out along the wall normal.\n\
+             - skip_ground_floor: Leave panels with floor 0 empty.\n\n\
+             Points carry the wall normal and the panel's primitive attributes. Skipped panels \
+             are shown in the window_rejected debug channel.",
+        ),
+        BuiltinNodeKind::Slope => (
+            "Writes a 0-1 float attribute from the angle between the normal and up.",
+            "## Parameters\n\
+             - up: Up direction.\n\
+             - min_angle / max_angle: Angles in degrees mapped to 0 and 1.\n\
+             - smooth: Number of smoothing passes.\n\
+             - attr: Attribute to write.",
+        ),
+        BuiltinNodeKind::Subnet => (
+            "Groups a network of nodes behind a single node.",
+            "Dive in to edit the nodes inside. Each Subnet Input node inside becomes an \
+             input pin and each Subnet Output node an output pin, ordered by their index.",
+        ),
+        BuiltinNodeKind::SubnetInput => (
+            "Brings a subnet's input into the network inside it.",
+            "## Parameters\n\
+             - name: Label of the subnet's input pin.\n\
+             - index: Pin order among the subnet's inputs.",
+        ),
+        BuiltinNodeKind::SubnetOutput => (
+            "Passes a result out of the subnet it is in.",
+            "## Parameters\n\
+             - name: Label of the subnet's output pin.\n\
+             - index: Pin order among the subnet's outputs.",
+        ),
+        BuiltinNodeKind::ObjOutput => (
+            "Writes the input mesh to an OBJ file and passes it through.",
+            "## Parameters\n\
+             - path: File to write. Not available in web builds.",
+        ),
+        BuiltinNodeKind::Output => (
+            "Marks the final result of the graph.",
+            "Only one Output node is supported per graph.",
+        ),
+    };
+    NodeHelp {
+        summary: summary.to_string(),
+        page: Some(page.to_string()),
+    }
+}
@@ -0,0 +1,249 @@
+use glam::{Mat4, Vec3};
+
+use crate::attributes::{AttributeDomain, AttributeRef};
+use crate::graph::{NodeDefinition, NodeParams, ParamValue};
+use crate::isolation::isolate;
+use crate::mesh::{make_box, make_grid, Mesh};
+use crate::nodes_builtin::{
+    builtin_kind_from_name, compute_mesh_node, default_params, BuiltinNodeKind,
+};
+
+// A verification case attached to a node definition: cook the node with `params` on
+// `inputs` and hand the result to `check`.
+#[derive(Debug, Clone)]
+pub struct NodeTestCase {
+    pub name: String,
+    pub params: NodeParams,
+    pub inputs: Vec<Mesh>,
+    pub check: fn(&Mesh) -> Result<(), String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct NodeTestResult {
+    pub node: String,
+    pub case: String,
+    pub result: Result<(), String>,
+}
+
+impl NodeTestResult {
+    pub fn passed(&self) -> bool {
+        self.result.is_ok()
+    }
+}
+
+pub fn run_node_self_tests(definition: &NodeDefinition) -> Vec<NodeTestResult> {
+    let kind = builtin_kind_from_name(&definition.name);
+    definition
+        .self_tests
+        .iter()
+        .map(|case| {
+            let result = match kind {
+                Some(kind) => isolate(|| {
+                    compute_mesh_node(kind, &case.params, &case.inputs)
+                        .and_then(|mesh| (case.check)(&mesh))
+                }),
+                None => Err(format!("no compute function for {}", definition.name)),
+            };
+            NodeTestResult {
+                node: definition.name.clone(),
+                case: case.name.clone(),
+                result,
+            }
+        })
+        .collect()
+}
+
+pub(crate) fn builtin_node_tests(kind: BuiltinNodeKind) -> Vec<NodeTestCase> {
+    let case = |name: &str,
+                overrides: &[(&str, ParamValue)],
+                inputs: Vec<Mesh>,
+                check: fn(&Mesh) -> Result<(), String>| {
+        let mut params = default_params(kind);
+        for (key, value) in overrides {
+            params.values.insert(key.to_string(), value.clone());
+        }
+        NodeTestCase {
+            name: name.to_string(),
+            params,
+            inputs,
+            check,
+        }
+    };
+    let unit_box = || make_box([1.0, 1.0, 1.0]);
+    match kind {
+        BuiltinNodeKind::Box => vec![case("six faces", &[], Vec::new(), |mesh| {
+            expect_eq("faces", mesh.face_count(), 6)
+        })],
+        BuiltinNodeKind::Grid => vec![case(
+            "point count",
+            &[("rows", ParamValue::Int(2)), ("cols", ParamValue::Int(3))],
+            Vec::new(),
+            |mesh| expect_eq("points", mesh.positions.len(), 12),
+        )],
+        BuiltinNodeKind::Sphere => vec![case(
+            "points on radius",
+            &[("radius", ParamValue::Float(2.0))],
+            Vec::new(),
+            |mesh| {
+                for p in &mesh.positions {
+                    let radius = (p[0] * p[0] + p[1] * p[1] + p[2] * p[2]).sqrt();
+                    expect_near("radius", radius, 2.0)?;
+                }
+                Ok(())
+            },
+        )],
+        BuiltinNodeKind::Transform => vec![case(
+            "translate moves bounds",
+            &[("translate", ParamValue::Vec3([1.0, 0.0, 0.0]))],
+            vec![unit_box()],
+            |mesh| {
+                let bounds = mesh.bounds().ok_or("empty output")?;
+                expect_near("min x", bounds.min[0], 0.5)
+            },
+        )],
+        BuiltinNodeKind::Merge => vec![case(
+            "sums points",
+            &[],
+            vec![unit_box(), unit_box()],
+            |mesh| {
+                expect_eq(
+                    "points",
+                    mesh.positions.len(),
+                    2 * make_box([1.0; 3]).positions.len(),
+                )
+            },
+        )],
+        BuiltinNodeKind::CopyToPoints => vec![case(
+            "one copy per point",
+            &[],
+            vec![unit_box(), make_grid([1.0, 1.0], [1, 1])],
+            |mesh| expect_eq("faces", mesh.face_count(), 6 * 4),
+        )],
+        BuiltinNodeKind::Boolean => vec![case(
+            "difference trims bounds",
+            &[("mode", ParamValue::Int(1))],
+            vec![unit_box(), {
+                let mut cutter = unit_box();
+                cutter.transform(Mat4::from_translation(Vec3::new(0.5, 0.0, 0.0)));
+                cutter
+            }],
+            |mesh| {
+                let bounds = mesh.bounds().ok_or("empty output")?;
+                expect_near("max x", bounds.max[0], 0.0)
+            },
+        )],
+        BuiltinNodeKind::Scatter => vec![case(
+            "point count",
+            &[("count", ParamValue::Int(25))],
+            vec![unit_box()],
+            |mesh| expect_eq("points", mesh.positions.len(), 25),
+        )],
+        BuiltinNodeKind::Normal => vec![case("unit normals", &[], vec![unit_box()], |mesh| {
+            let normals = mesh.normals.as_ref().ok_or("missing normals")?;
+            for n in normals {
+                expect_near(
+                    "length",
+                    (n[0] * n[0] + n[1] * n[1] + n[2] * n[2]).sqrt(),
+                    1.0,
+                )?;
+            }
+            Ok(())
+        })],
+        BuiltinNodeKind::Color => vec![case(
+            "writes Cd",
+            &[("color", ParamValue::Vec3([0.25, 0.5, 1.0]))],
+            vec![unit_box()],
+            |mesh| match mesh.attribute(AttributeDomain::Point, "Cd") {
+                Some(AttributeRef::Vec3(values))
+                    if values.iter().all(|c| *c == [0.25, 0.5, 1.0]) =>
+                {
+                    Ok(())
+                }
+                _ => Err("Cd does not match the color param".to_string()),
+            },
+        )],
+        BuiltinNodeKind::Noise => vec![case(
+            "zero amplitude is identity",
+            &[("amplitude", ParamValue::Float(0.0))],
+            vec![unit_box()],
+            |mesh| {
+                let expected = make_box([1.0; 3]).positions;
+                if mesh.positions == expected {
+                    Ok(())
+                } else {
+                    Err("positions moved".to_string())
+                }
+            },
+        )],
+        BuiltinNodeKind::Wobble => vec![case(
+            "zero amplitude is identity",
+            &[
+                ("amplitude", ParamValue::Float(0.0)),
+                ("frame", ParamValue::Float(7.0)),
+            ],
+            vec![unit_box()],
+            |mesh| {
+                if mesh.positions == make_box([1.0; 3]).positions {
+                    Ok(())
+                } else {
+                    Err("positions moved".to_string())
+                }
+            },
+        )],
+        BuiltinNodeKind::ParticleSim => vec![case(
+            "falls under gravity",
+            &[
+                ("noise", ParamValue::Float(0.0)),
+                ("frame", ParamValue::Float(25.0)),
+            ],
+            vec![unit_box()],
+            |mesh| {
+                let start = make_box([1.0; 3]).positions;
+                let fell = mesh
+                    .positions
+                    .iter()
+                    .zip(&start)
+                    .all(|(p, q)| p[1] < q[1] - 1.0 && p[0] == q[0] && p[2] == q[2]);
+                if fell {
+                    Ok(())
+                } else {
+                    Err("points did not fall straight down".to_string())
+                }
+            },
+        )],
+        _ => Vec::new(),
+    }
+}
+
+fn expect_eq(label: &str, actual: usize, expected: usize) -> Result<(), String> {
+    if actual == expected {
+        Ok(())
+    } else {
+        Err(format!("{label}: expected {expected}, got {actual}"))
+    }
+}
+
+fn expect_near(label: &str, actual: f32, expected: f32) -> Result<(), String> {
+    if (actual - expected).abs() <= 1.0e-4 {
+        Ok(())
+    } else {
+        Err(format!("{label}: expected {expected}, got {actual}"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes_builtin::builtin_definitions;
+
+    #[test]
+    fn builtin_self_tests_pass() {
+        let results: Vec<_> = builtin_definitions()
+            .iter()
+            .flat_map(run_node_self_tests)
+            .collect();
+        assert!(results.len() >= 10);
+        let failures: Vec<_> = results.iter().filter(|result| !result.passed()).collect();
+        assert!(failures.is_empty(), "{:?}", failures);
+    }
+}
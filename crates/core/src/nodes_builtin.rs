@@ -5,9 +5,38 @@ use std::path::Path;
 use glam::{EulerRot, Mat4, Quat, Vec3};
 use tracing::warn;
 
-use crate::attributes::{AttributeDomain, AttributeStorage};
-use crate::graph::{NodeDefinition, NodeParams, ParamValue, PinDefinition, PinType};
+use crate::attributes::{AttributeDomain, AttributePrecision, AttributeRef, AttributeStorage};
+use crate::bake_ao::{bake_ao, BakeAoSettings};
+use crate::bevel::{bevel, BevelSettings};
+use crate::boolean::{mesh_boolean, BooleanMode};
+use crate::building::{
+    facade_repeat, floor_split, window_scatter, FacadeRepeatSettings, FloorSplitSettings,
+    WindowScatterSettings,
+};
+use crate::cell_edits::{apply_cell_edits, CELL_EDITS_PARAM};
+use crate::clean::{flip_winding, quadrangulate, unify_winding};
+use crate::frame_cache::FrameCache;
+use crate::graph::{
+    NodeDefinition, NodeParams, ParamValue, PinDefinition, PinType, VariadicInputs,
+};
+use crate::keyframes::{FRAME_PARAM, TIME_PARAM};
+use crate::lod::{lod_mesh, parse_lod_ratios};
 use crate::mesh::{make_box, make_grid, make_uv_sphere, Mesh};
+use crate::node_help::builtin_node_help;
+use crate::node_tests::builtin_node_tests;
+use crate::noise::{Fbm, NoiseBasis};
+use crate::parallel::{par_for_each_mut, par_zip_mut};
+use crate::particles::{simulate_particles, ParticleSettings};
+use crate::poly_extrude::{poly_extrude, PolyExtrudeSettings};
+use crate::sort::{sort_mesh, SortKey, SortSettings};
+use crate::split::split_mesh;
+use crate::stream::ObjStreamWriter;
+use crate::surface_masks::{
+    curvature_mask, geodesic_distance, slope_mask, CurvatureMode, CurvatureSettings,
+    GeodesicSettings, SlopeSettings,
+};
+use crate::symmetry::{mirror_deformation, SymmetryPlane, MIRROR_AXIS_PARAM};
+use crate::units::{scale_mesh, UNIT_SCALE_PARAM};
 use crate::wrangle::apply_wrangle;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -16,16 +45,38 @@ pub enum BuiltinNodeKind {
     Grid,
     Sphere,
     File,
+    MergeFiles,
     Transform,
     CopyTransform,
     Merge,
     CopyToPoints,
+    Boolean,
     Scatter,
     Normal,
     Color,
     Noise,
+    Wobble,
+    ParticleSim,
     AttributeMath,
+    AttributePrecision,
+    AttributeEdit,
     Wrangle,
+    PolyExtrude,
+    Bevel,
+    Clean,
+    Sort,
+    Split,
+    Lod,
+    BakeAo,
+    Curvature,
+    Slope,
+    GeodesicDistance,
+    FloorSplit,
+    FacadeRepeat,
+    WindowScatter,
+    Subnet,
+    SubnetInput,
+    SubnetOutput,
     ObjOutput,
     Output,
 }
@@ -37,20 +88,119 @@ impl BuiltinNodeKind {
             BuiltinNodeKind::Grid => "Grid",
             BuiltinNodeKind::Sphere => "Sphere",
             BuiltinNodeKind::File => "File",
+            BuiltinNodeKind::MergeFiles => "Merge Files",
             BuiltinNodeKind::Transform => "Transform",
             BuiltinNodeKind::CopyTransform => "Copy/Transform",
             BuiltinNodeKind::Merge => "Merge",
             BuiltinNodeKind::CopyToPoints => "Copy to Points",
+            BuiltinNodeKind::Boolean => "Boolean",
             BuiltinNodeKind::Scatter => "Scatter",
             BuiltinNodeKind::Normal => "Normal",
             BuiltinNodeKind::Color => "Color",
             BuiltinNodeKind::Noise => "Noise/Mountain",
+            BuiltinNodeKind::Wobble => "Wobble",
+            BuiltinNodeKind::ParticleSim => "Particle Sim",
             BuiltinNodeKind::AttributeMath => "Attribute Math",
+            BuiltinNodeKind::AttributePrecision => "Attribute Precision",
+            BuiltinNodeKind::AttributeEdit => "Attribute Edit",
             BuiltinNodeKind::Wrangle => "Wrangle",
+            BuiltinNodeKind::PolyExtrude => "PolyExtrude",
+            BuiltinNodeKind::Bevel => "Bevel",
+            BuiltinNodeKind::Clean => "Clean",
+            BuiltinNodeKind::Sort => "Sort",
+            BuiltinNodeKind::Split => "Split",
+            BuiltinNodeKind::Lod => "LOD",
+            BuiltinNodeKind::BakeAo => "Bake AO",
+            BuiltinNodeKind::Curvature => "Curvature",
+            BuiltinNodeKind::Slope => "Slope",
+            BuiltinNodeKind::GeodesicDistance => "Geodesic Distance",
+            BuiltinNodeKind::FloorSplit => "Floor Split",
+            BuiltinNodeKind::FacadeRepeat => "Facade Repeat",
+            BuiltinNodeKind::WindowScatter => "Window Scatter",
+            BuiltinNodeKind::Subnet => "Subnet",
+            BuiltinNodeKind::SubnetInput => "Subnet Input",
+            BuiltinNodeKind::SubnetOutput => "Subnet Output",
             BuiltinNodeKind::ObjOutput => "OBJ Output",
             BuiltinNodeKind::Output => "Output",
         }
     }
+
+    // Point deformers that honour the project symmetry plane through their `mirror` param.
+    pub fn supports_symmetry(self) -> bool {
+        matches!(
+            self,
+            BuiltinNodeKind::Transform | BuiltinNodeKind::Noise | BuiltinNodeKind::Wrangle
+        )
+    }
+
+    // File import and export nodes convert between file units and project units.
+    pub fn uses_unit_scale(self) -> bool {
+        matches!(
+            self,
+            BuiltinNodeKind::File | BuiltinNodeKind::MergeFiles | BuiltinNodeKind::ObjOutput
+        )
+    }
+
+    // Nodes with a `seed` parameter; they pick up the project's per-node seed offsets.
+    pub fn is_stochastic(self) -> bool {
+        matches!(
+            self,
+            BuiltinNodeKind::Scatter
+                | BuiltinNodeKind::Noise
+                | BuiltinNodeKind::Wobble
+                | BuiltinNodeKind::ParticleSim
+                | BuiltinNodeKind::WindowScatter
+        )
+    }
+
+    // Nodes that follow the timeline through their `frame` parameter.
+    pub fn is_animated(self) -> bool {
+        matches!(self, BuiltinNodeKind::Wobble | BuiltinNodeKind::ParticleSim)
+    }
+
+    // Rough relative cook weight for project summaries; not a measurement.
+    pub fn cook_cost(self) -> u32 {
+        match self {
+            // A subnet's contents are not counted.
+            BuiltinNodeKind::Output
+            | BuiltinNodeKind::Merge
+            | BuiltinNodeKind::Color
+            | BuiltinNodeKind::Subnet
+            | BuiltinNodeKind::SubnetInput
+            | BuiltinNodeKind::SubnetOutput => 1,
+            BuiltinNodeKind::Box
+            | BuiltinNodeKind::Grid
+            | BuiltinNodeKind::Sphere
+            | BuiltinNodeKind::Transform
+            | BuiltinNodeKind::AttributePrecision
+            | BuiltinNodeKind::AttributeEdit
+            | BuiltinNodeKind::Split
+            | BuiltinNodeKind::Slope => 2,
+            BuiltinNodeKind::CopyTransform
+            | BuiltinNodeKind::Scatter
+            | BuiltinNodeKind::Normal
+            | BuiltinNodeKind::Noise
+            | BuiltinNodeKind::Wobble
+            | BuiltinNodeKind::AttributeMath
+            | BuiltinNodeKind::Sort
+            | BuiltinNodeKind::FloorSplit
+            | BuiltinNodeKind::FacadeRepeat
+            | BuiltinNodeKind::WindowScatter => 5,
+            BuiltinNodeKind::File
+            | BuiltinNodeKind::MergeFiles
+            | BuiltinNodeKind::ObjOutput
+            | BuiltinNodeKind::CopyToPoints
+            | BuiltinNodeKind::Wrangle
+            | BuiltinNodeKind::PolyExtrude
+            | BuiltinNodeKind::Bevel
+            | BuiltinNodeKind::Clean
+            | BuiltinNodeKind::Lod
+            | BuiltinNodeKind::Curvature
+            | BuiltinNodeKind::GeodesicDistance => 10,
+            // Scrubbing to a frame that is not cached steps every frame before it.
+            BuiltinNodeKind::BakeAo | BuiltinNodeKind::Boolean | BuiltinNodeKind::ParticleSim => 50,
+        }
+    }
 }
 
 pub fn builtin_kind_from_name(name: &str) -> Option<BuiltinNodeKind> {
@@ -59,16 +209,38 @@ pub fn builtin_kind_from_name(name: &str) -> Option<BuiltinNodeKind> {
         "Grid" => Some(BuiltinNodeKind::Grid),
         "Sphere" => Some(BuiltinNodeKind::Sphere),
         "File" => Some(BuiltinNodeKind::File),
+        "Merge Files" => Some(BuiltinNodeKind::MergeFiles),
         "Transform" => Some(BuiltinNodeKind::Transform),
         "Copy/Transform" => Some(BuiltinNodeKind::CopyTransform),
         "Merge" => Some(BuiltinNodeKind::Merge),
         "Copy to Points" => Some(BuiltinNodeKind::CopyToPoints),
+        "Boolean" => Some(BuiltinNodeKind::Boolean),
         "Scatter" => Some(BuiltinNodeKind::Scatter),
         "Normal" => Some(BuiltinNodeKind::Normal),
         "Color" => Some(BuiltinNodeKind::Color),
         "Noise/Mountain" => Some(BuiltinNodeKind::Noise),
+        "Wobble" => Some(BuiltinNodeKind::Wobble),
+        "Particle Sim" => Some(BuiltinNodeKind::ParticleSim),
         "Attribute Math" => Some(BuiltinNodeKind::AttributeMath),
+        "Attribute Precision" => Some(BuiltinNodeKind::AttributePrecision),
+        "Attribute Edit" => Some(BuiltinNodeKind::AttributeEdit),
         "Wrangle" => Some(BuiltinNodeKind::Wrangle),
+        "PolyExtrude" => Some(BuiltinNodeKind::PolyExtrude),
+        "Bevel" => Some(BuiltinNodeKind::Bevel),
+        "Clean" => Some(BuiltinNodeKind::Clean),
+        "Sort" => Some(BuiltinNodeKind::Sort),
+        "Split" => Some(BuiltinNodeKind::Split),
+        "LOD" => Some(BuiltinNodeKind::Lod),
+        "Bake AO" => Some(BuiltinNodeKind::BakeAo),
+        "Curvature" => Some(BuiltinNodeKind::Curvature),
+        "Slope" => Some(BuiltinNodeKind::Slope),
+        "Geodesic Distance" => Some(BuiltinNodeKind::GeodesicDistance),
+        "Floor Split" => Some(BuiltinNodeKind::FloorSplit),
+        "Facade Repeat" => Some(BuiltinNodeKind::FacadeRepeat),
+        "Window Scatter" => Some(BuiltinNodeKind::WindowScatter),
+        "Subnet" => Some(BuiltinNodeKind::Subnet),
+        "Subnet Input" => Some(BuiltinNodeKind::SubnetInput),
+        "Subnet Output" => Some(BuiltinNodeKind::SubnetOutput),
         "OBJ Output" => Some(BuiltinNodeKind::ObjOutput),
         "Output" => Some(BuiltinNodeKind::Output),
         _ => None,
@@ -81,16 +253,38 @@ pub fn builtin_definitions() -> Vec<NodeDefinition> {
         node_definition(BuiltinNodeKind::Grid),
         node_definition(BuiltinNodeKind::Sphere),
         node_definition(BuiltinNodeKind::File),
+        node_definition(BuiltinNodeKind::MergeFiles),
         node_definition(BuiltinNodeKind::Transform),
         node_definition(BuiltinNodeKind::CopyTransform),
         node_definition(BuiltinNodeKind::Merge),
         node_definition(BuiltinNodeKind::CopyToPoints),
+        node_definition(BuiltinNodeKind::Boolean),
         node_definition(BuiltinNodeKind::Scatter),
         node_definition(BuiltinNodeKind::Normal),
         node_definition(BuiltinNodeKind::Color),
         node_definition(BuiltinNodeKind::Noise),
+        node_definition(BuiltinNodeKind::Wobble),
+        node_definition(BuiltinNodeKind::ParticleSim),
         node_definition(BuiltinNodeKind::AttributeMath),
+        node_definition(BuiltinNodeKind::AttributePrecision),
+        node_definition(BuiltinNodeKind::AttributeEdit),
         node_definition(BuiltinNodeKind::Wrangle),
+        node_definition(BuiltinNodeKind::PolyExtrude),
+        node_definition(BuiltinNodeKind::Bevel),
+        node_definition(BuiltinNodeKind::Clean),
+        node_definition(BuiltinNodeKind::Sort),
+        node_definition(BuiltinNodeKind::Split),
+        node_definition(BuiltinNodeKind::Lod),
+        node_definition(BuiltinNodeKind::BakeAo),
+        node_definition(BuiltinNodeKind::Curvature),
+        node_definition(BuiltinNodeKind::Slope),
+        node_definition(BuiltinNodeKind::GeodesicDistance),
+        node_definition(BuiltinNodeKind::FloorSplit),
+        node_definition(BuiltinNodeKind::FacadeRepeat),
+        node_definition(BuiltinNodeKind::WindowScatter),
+        node_definition(BuiltinNodeKind::Subnet),
+        node_definition(BuiltinNodeKind::SubnetInput),
+        node_definition(BuiltinNodeKind::SubnetOutput),
         node_definition(BuiltinNodeKind::ObjOutput),
         node_definition(BuiltinNodeKind::Output),
     ]
@@ -106,42 +300,60 @@ pub fn node_definition(kind: BuiltinNodeKind) -> NodeDefinition {
         pin_type: PinType::Mesh,
     };
 
-    match kind {
+    let mut definition = match kind {
         BuiltinNodeKind::Box => NodeDefinition {
             name: kind.name().to_string(),
             category: "Sources".to_string(),
             inputs: Vec::new(),
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Grid => NodeDefinition {
             name: kind.name().to_string(),
             category: "Sources".to_string(),
             inputs: Vec::new(),
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Sphere => NodeDefinition {
             name: kind.name().to_string(),
             category: "Sources".to_string(),
             inputs: Vec::new(),
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
-        BuiltinNodeKind::File => NodeDefinition {
+        BuiltinNodeKind::File | BuiltinNodeKind::MergeFiles => NodeDefinition {
             name: kind.name().to_string(),
             category: "Sources".to_string(),
             inputs: Vec::new(),
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Transform => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::CopyTransform => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Merge => NodeDefinition {
             name: kind.name().to_string(),
@@ -157,6 +369,9 @@ pub fn node_definition(kind: BuiltinNodeKind) -> NodeDefinition {
                 },
             ],
             outputs: vec![mesh_out()],
+            variadic_inputs: Some(VariadicInputs { min: 2, max: 16 }),
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::CopyToPoints => NodeDefinition {
             name: kind.name().to_string(),
@@ -172,56 +387,270 @@ pub fn node_definition(kind: BuiltinNodeKind) -> NodeDefinition {
                 },
             ],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Boolean => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![
+                PinDefinition {
+                    name: "a".to_string(),
+                    pin_type: PinType::Mesh,
+                },
+                PinDefinition {
+                    name: "b".to_string(),
+                    pin_type: PinType::Mesh,
+                },
+            ],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Scatter => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Normal => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Color => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Noise => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Wobble => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::ParticleSim => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::AttributeMath => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::AttributePrecision => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::AttributeEdit => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Wrangle => NodeDefinition {
             name: kind.name().to_string(),
             category: "Operators".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::PolyExtrude => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Bevel => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Clean => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Sort => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Split => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![
+                PinDefinition {
+                    name: "inside".to_string(),
+                    pin_type: PinType::Mesh,
+                },
+                PinDefinition {
+                    name: "outside".to_string(),
+                    pin_type: PinType::Mesh,
+                },
+            ],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Lod => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::BakeAo => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Curvature => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::GeodesicDistance
+        | BuiltinNodeKind::FloorSplit
+        | BuiltinNodeKind::FacadeRepeat
+        | BuiltinNodeKind::WindowScatter => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::Slope => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        // Its pins mirror the Subnet Input / Output nodes inside; see `Graph::sync_subnet_pins`.
+        BuiltinNodeKind::Subnet => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Operators".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::SubnetInput => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Sources".to_string(),
+            inputs: Vec::new(),
+            outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        },
+        BuiltinNodeKind::SubnetOutput => NodeDefinition {
+            name: kind.name().to_string(),
+            category: "Outputs".to_string(),
+            inputs: vec![mesh_in()],
+            outputs: Vec::new(),
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::ObjOutput => NodeDefinition {
             name: kind.name().to_string(),
             category: "Outputs".to_string(),
             inputs: vec![mesh_in()],
             outputs: vec![mesh_out()],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
         BuiltinNodeKind::Output => NodeDefinition {
             name: kind.name().to_string(),
             category: "Outputs".to_string(),
             inputs: vec![mesh_in()],
             outputs: Vec::new(),
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
         },
-    }
+    };
+    definition.help = Some(builtin_node_help(kind));
+    definition.self_tests = builtin_node_tests(kind);
+    definition
 }
 
 pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
@@ -249,11 +678,15 @@ pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
                 ParamValue::String(r"C:\code\grapho\geo\pig.obj".to_string()),
             );
         }
+        BuiltinNodeKind::MergeFiles => {
+            values.insert("paths".to_string(), ParamValue::StringList(Vec::new()));
+        }
         BuiltinNodeKind::Transform => {
             values.insert("translate".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
             values.insert("rotate_deg".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
             values.insert("scale".to_string(), ParamValue::Vec3([1.0, 1.0, 1.0]));
             values.insert("pivot".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
+            values.insert("mirror".to_string(), ParamValue::Bool(false));
         }
         BuiltinNodeKind::CopyTransform => {
             values.insert("count".to_string(), ParamValue::Int(5));
@@ -272,11 +705,19 @@ pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
             values.insert("align_to_normals".to_string(), ParamValue::Bool(true));
             values.insert("translate".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
             values.insert("rotate_deg".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
+            values.insert("orient".to_string(), ParamValue::Quat([0.0, 0.0, 0.0, 1.0]));
             values.insert("scale".to_string(), ParamValue::Vec3([1.0, 1.0, 1.0]));
         }
+        BuiltinNodeKind::Boolean => {
+            values.insert("mode".to_string(), ParamValue::Int(0));
+        }
         BuiltinNodeKind::Scatter => {
             values.insert("count".to_string(), ParamValue::Int(100));
             values.insert("seed".to_string(), ParamValue::Int(1));
+            values.insert(
+                "density_attr".to_string(),
+                ParamValue::String(String::new()),
+            );
         }
         BuiltinNodeKind::Normal => {
             values.insert("threshold_deg".to_string(), ParamValue::Float(60.0));
@@ -290,6 +731,28 @@ pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
             values.insert("frequency".to_string(), ParamValue::Float(1.0));
             values.insert("seed".to_string(), ParamValue::Int(1));
             values.insert("offset".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
+            values.insert("basis".to_string(), ParamValue::Int(0));
+            values.insert("octaves".to_string(), ParamValue::Int(3));
+            values.insert("mirror".to_string(), ParamValue::Bool(false));
+        }
+        BuiltinNodeKind::Wobble => {
+            values.insert("amplitude".to_string(), ParamValue::Float(0.1));
+            values.insert("frequency".to_string(), ParamValue::Float(2.0));
+            values.insert("period".to_string(), ParamValue::Float(24.0));
+            values.insert("jitter".to_string(), ParamValue::Bool(false));
+            values.insert("seed".to_string(), ParamValue::Int(1));
+            values.insert(FRAME_PARAM.to_string(), ParamValue::Float(0.0));
+        }
+        BuiltinNodeKind::ParticleSim => {
+            values.insert("start_frame".to_string(), ParamValue::Int(1));
+            values.insert("fps".to_string(), ParamValue::Float(24.0));
+            values.insert("attr".to_string(), ParamValue::String("v".to_string()));
+            values.insert("gravity".to_string(), ParamValue::Vec3([0.0, -9.8, 0.0]));
+            values.insert("drag".to_string(), ParamValue::Float(0.0));
+            values.insert("noise".to_string(), ParamValue::Float(0.5));
+            values.insert("frequency".to_string(), ParamValue::Float(1.0));
+            values.insert("seed".to_string(), ParamValue::Int(1));
+            values.insert(FRAME_PARAM.to_string(), ParamValue::Float(0.0));
         }
         BuiltinNodeKind::AttributeMath => {
             values.insert("attr".to_string(), ParamValue::String("Cd".to_string()));
@@ -299,12 +762,107 @@ pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
             values.insert("value_f".to_string(), ParamValue::Float(1.0));
             values.insert("value_v3".to_string(), ParamValue::Vec3([1.0, 1.0, 1.0]));
         }
+        BuiltinNodeKind::AttributePrecision => {
+            values.insert("attr".to_string(), ParamValue::String("Cd".to_string()));
+            values.insert("domain".to_string(), ParamValue::Int(0));
+            values.insert("precision".to_string(), ParamValue::Int(2));
+        }
+        BuiltinNodeKind::AttributeEdit => {
+            values.insert(
+                CELL_EDITS_PARAM.to_string(),
+                ParamValue::StringList(Vec::new()),
+            );
+        }
         BuiltinNodeKind::Wrangle => {
             values.insert("mode".to_string(), ParamValue::Int(0));
             values.insert(
                 "code".to_string(),
                 ParamValue::String("@Cd = vec3(1.0, 1.0, 1.0);".to_string()),
             );
+            values.insert("mirror".to_string(), ParamValue::Bool(false));
+        }
+        BuiltinNodeKind::PolyExtrude => {
+            values.insert("distance".to_string(), ParamValue::Float(0.25));
+            values.insert("inset".to_string(), ParamValue::Float(0.0));
+            values.insert("individual".to_string(), ParamValue::Bool(false));
+            values.insert("group".to_string(), ParamValue::String(String::new()));
+            values.insert(
+                "side_group".to_string(),
+                ParamValue::String("extrude_side".to_string()),
+            );
+        }
+        BuiltinNodeKind::Bevel => {
+            values.insert("width".to_string(), ParamValue::Float(0.05));
+            values.insert("segments".to_string(), ParamValue::Int(1));
+            values.insert("angle_deg".to_string(), ParamValue::Float(30.0));
+            values.insert("group".to_string(), ParamValue::String(String::new()));
+        }
+        BuiltinNodeKind::Clean => {
+            values.insert("fix_winding".to_string(), ParamValue::Bool(true));
+            values.insert("flip".to_string(), ParamValue::Bool(false));
+            values.insert("triangulate".to_string(), ParamValue::Bool(false));
+            values.insert("quadrangulate".to_string(), ParamValue::Bool(false));
+            values.insert("quad_angle_deg".to_string(), ParamValue::Float(5.0));
+        }
+        BuiltinNodeKind::Sort => {
+            values.insert("domain".to_string(), ParamValue::Int(0));
+            values.insert("key".to_string(), ParamValue::Int(0));
+            values.insert("axis".to_string(), ParamValue::Vec3([1.0, 0.0, 0.0]));
+            values.insert("point".to_string(), ParamValue::Vec3([0.0, 0.0, 0.0]));
+            values.insert("attr".to_string(), ParamValue::String(String::new()));
+            values.insert("reverse".to_string(), ParamValue::Bool(false));
+        }
+        BuiltinNodeKind::Split => {
+            values.insert("group".to_string(), ParamValue::String(String::new()));
+        }
+        BuiltinNodeKind::Lod => {
+            values.insert(
+                "ratios".to_string(),
+                ParamValue::String("1 0.5 0.25".to_string()),
+            );
+            values.insert("attr".to_string(), ParamValue::String("lod".to_string()));
+        }
+        BuiltinNodeKind::BakeAo => {
+            values.insert("samples".to_string(), ParamValue::Int(32));
+            values.insert("max_distance".to_string(), ParamValue::Float(1.0));
+            values.insert("light_mix".to_string(), ParamValue::Float(0.0));
+            values.insert("light_dir".to_string(), ParamValue::Vec3([0.6, 1.0, 0.2]));
+            values.insert("attr".to_string(), ParamValue::String("Cd".to_string()));
+        }
+        BuiltinNodeKind::Curvature => {
+            values.insert("mode".to_string(), ParamValue::Int(0));
+            values.insert("scale".to_string(), ParamValue::Float(1.0));
+            values.insert("smooth".to_string(), ParamValue::Int(0));
+            values.insert(
+                "attr".to_string(),
+                ParamValue::String("curvature".to_string()),
+            );
+        }
+        BuiltinNodeKind::Slope => {
+            values.insert("up".to_string(), ParamValue::Vec3([0.0, 1.0, 0.0]));
+            values.insert("min_angle".to_string(), ParamValue::Float(0.0));
+            values.insert("max_angle".to_string(), ParamValue::Float(90.0));
+            values.insert("smooth".to_string(), ParamValue::Int(0));
+            values.insert("attr".to_string(), ParamValue::String("slope".to_string()));
+        }
+        BuiltinNodeKind::GeodesicDistance => {
+            values.insert("group".to_string(), ParamValue::String(String::new()));
+            values.insert("normalize".to_string(), ParamValue::Bool(false));
+            values.insert("attr".to_string(), ParamValue::String("dist".to_string()));
+        }
+        BuiltinNodeKind::FloorSplit => {
+            values.insert("floor_height".to_string(), ParamValue::Float(1.0));
+            values.insert("attr".to_string(), ParamValue::String("floor".to_string()));
+        }
+        BuiltinNodeKind::FacadeRepeat => {
+            values.insert("bay_width".to_string(), ParamValue::Float(1.0));
+            values.insert("attr".to_string(), ParamValue::String("bay".to_string()));
+        }
+        BuiltinNodeKind::WindowScatter => {
+            values.insert("density".to_string(), ParamValue::Float(0.8));
+            values.insert("seed".to_string(), ParamValue::Int(1));
+            values.insert("offset".to_string(), ParamValue::Float(0.0));
+            values.insert("skip_ground_floor".to_string(), ParamValue::Bool(true));
         }
         BuiltinNodeKind::ObjOutput => {
             values.insert(
@@ -312,17 +870,98 @@ pub fn default_params(kind: BuiltinNodeKind) -> NodeParams {
                 ParamValue::String("output.obj".to_string()),
             );
         }
-        BuiltinNodeKind::Output => {}
+        BuiltinNodeKind::SubnetInput => {
+            values.insert("name".to_string(), ParamValue::String("in".to_string()));
+            values.insert("index".to_string(), ParamValue::Int(0));
+        }
+        BuiltinNodeKind::SubnetOutput => {
+            values.insert("name".to_string(), ParamValue::String("out".to_string()));
+            values.insert("index".to_string(), ParamValue::Int(0));
+        }
+        BuiltinNodeKind::Subnet | BuiltinNodeKind::Output => {}
     }
 
     NodeParams { values }
 }
 
+pub fn param_range(kind: BuiltinNodeKind, key: &str) -> Option<(f32, f32)> {
+    match (kind, key) {
+        (BuiltinNodeKind::Grid, "rows" | "cols") => Some((1.0, f32::INFINITY)),
+        (BuiltinNodeKind::Sphere, "rows" | "cols") => Some((3.0, f32::INFINITY)),
+        (BuiltinNodeKind::CopyTransform | BuiltinNodeKind::Scatter, "count") => {
+            Some((0.0, f32::INFINITY))
+        }
+        (BuiltinNodeKind::Boolean, "mode") => Some((0.0, 2.0)),
+        (BuiltinNodeKind::Normal, "threshold_deg") => Some((0.0, 180.0)),
+        (BuiltinNodeKind::Clean, "quad_angle_deg") => Some((0.0, 180.0)),
+        (
+            BuiltinNodeKind::Color
+            | BuiltinNodeKind::AttributeMath
+            | BuiltinNodeKind::AttributePrecision,
+            "domain",
+        ) => Some((0.0, 3.0)),
+        (BuiltinNodeKind::AttributeMath, "op") => Some((0.0, 3.0)),
+        (BuiltinNodeKind::AttributePrecision, "precision") => Some((0.0, 2.0)),
+        (BuiltinNodeKind::Noise, "basis") => Some((0.0, 3.0)),
+        (BuiltinNodeKind::Noise, "octaves") => Some((1.0, 12.0)),
+        (BuiltinNodeKind::Wobble, "frequency") => Some((0.0, f32::INFINITY)),
+        (BuiltinNodeKind::Wobble, "period") => Some((1.0, f32::INFINITY)),
+        (BuiltinNodeKind::ParticleSim, "fps") => Some((1.0, f32::INFINITY)),
+        (BuiltinNodeKind::ParticleSim, "drag" | "frequency") => Some((0.0, f32::INFINITY)),
+        (BuiltinNodeKind::Wrangle, "mode") => Some((0.0, 3.0)),
+        (BuiltinNodeKind::PolyExtrude, "inset") => Some((0.0, 1.0)),
+        (BuiltinNodeKind::Bevel, "width") => Some((0.0, f32::INFINITY)),
+        (BuiltinNodeKind::Bevel, "segments") => Some((1.0, 64.0)),
+        (BuiltinNodeKind::Sort, "domain") => Some((0.0, 1.0)),
+        (BuiltinNodeKind::Sort, "key") => Some((0.0, 2.0)),
+        (BuiltinNodeKind::BakeAo, "samples") => Some((1.0, 1024.0)),
+        (BuiltinNodeKind::BakeAo, "max_distance") => Some((0.0, f32::INFINITY)),
+        (BuiltinNodeKind::BakeAo, "light_mix") => Some((0.0, 1.0)),
+        (BuiltinNodeKind::Curvature, "mode") => Some((0.0, 2.0)),
+        (BuiltinNodeKind::Curvature | BuiltinNodeKind::Slope, "smooth") => Some((0.0, 100.0)),
+        (BuiltinNodeKind::Slope, "min_angle" | "max_angle") => Some((0.0, 180.0)),
+        (BuiltinNodeKind::FloorSplit, "floor_height") => Some((0.01, f32::INFINITY)),
+        (BuiltinNodeKind::FacadeRepeat, "bay_width") => Some((0.01, f32::INFINITY)),
+        (BuiltinNodeKind::WindowScatter, "density") => Some((0.0, 1.0)),
+        (BuiltinNodeKind::SubnetInput | BuiltinNodeKind::SubnetOutput, "index") => {
+            Some((0.0, f32::INFINITY))
+        }
+        _ => None,
+    }
+}
+
+// Collapsible inspector sections. Parameters without a group are listed first, ungrouped.
+pub fn param_group(kind: BuiltinNodeKind, key: &str) -> Option<&'static str> {
+    match (kind, key) {
+        (_, "mirror") if kind.supports_symmetry() => Some("Symmetry"),
+        (BuiltinNodeKind::CopyToPoints, "translate" | "rotate_deg" | "orient" | "scale") => {
+            Some("Transform")
+        }
+        (BuiltinNodeKind::Noise, "basis" | "octaves" | "frequency" | "offset") => Some("Pattern"),
+        (BuiltinNodeKind::AttributeMath, "value_f" | "value_v3") => Some("Operand"),
+        (BuiltinNodeKind::Sort, "axis" | "point" | "attr") => Some("Sort Key"),
+        (BuiltinNodeKind::BakeAo, "light_mix" | "light_dir") => Some("Light"),
+        (BuiltinNodeKind::Slope, "min_angle" | "max_angle" | "smooth") => Some("Range"),
+        _ => None,
+    }
+}
+
 pub fn compute_mesh_node(
     kind: BuiltinNodeKind,
     params: &NodeParams,
     inputs: &[Mesh],
 ) -> Result<Mesh, String> {
+    if let Some(plane) = SymmetryPlane::from_params(params) {
+        if kind.supports_symmetry() {
+            let mut unmirrored = params.clone();
+            unmirrored.values.remove(MIRROR_AXIS_PARAM);
+            let output = compute_mesh_node(kind, &unmirrored, inputs)?;
+            return Ok(match inputs.first() {
+                Some(source) => mirror_deformation(source, output, plane),
+                None => output,
+            });
+        }
+    }
     match kind {
         BuiltinNodeKind::Box => {
             let size = params.get_vec3("size", [1.0, 1.0, 1.0]);
@@ -370,7 +1009,33 @@ pub fn compute_mesh_node(
             if path.trim().is_empty() {
                 return Err("File node requires a path".to_string());
             }
-            load_obj_mesh(path)
+            let mut mesh = load_obj_mesh(path)?;
+            scale_mesh(&mut mesh, params.get_float(UNIT_SCALE_PARAM, 1.0));
+            Ok(mesh)
+        }
+        BuiltinNodeKind::MergeFiles => {
+            // Empty rows are skipped so a half-filled list still cooks.
+            let mut meshes = Vec::new();
+            for (index, path) in params.get_string_list("paths").iter().enumerate() {
+                if path.trim().is_empty() {
+                    continue;
+                }
+                let mut mesh = load_obj_mesh(path).map_err(|err| format!("{}: {}", path, err))?;
+                let prims = mesh.face_count();
+                mesh.set_attribute(
+                    AttributeDomain::Primitive,
+                    "file",
+                    AttributeStorage::Int(vec![index as i32; prims]),
+                )
+                .map_err(|err| format!("{:?}", err))?;
+                meshes.push(mesh);
+            }
+            if meshes.is_empty() {
+                return Err("Merge Files node requires at least one path".to_string());
+            }
+            let mut mesh = Mesh::merge(&meshes);
+            scale_mesh(&mut mesh, params.get_float(UNIT_SCALE_PARAM, 1.0));
+            Ok(mesh)
         }
         BuiltinNodeKind::Transform => {
             let input = require_input_at(inputs, 0, "Transform requires a mesh input")?;
@@ -428,52 +1093,26 @@ pub fn compute_mesh_node(
                 return Err("Copy to Points requires template points".to_string());
             }
 
-            let align_to_normals = params.get_bool("align_to_normals", true);
-            let translate = params.get_vec3("translate", [0.0, 0.0, 0.0]);
-            let rotate_deg = params.get_vec3("rotate_deg", [0.0, 0.0, 0.0]);
-            let scale = params.get_vec3("scale", [1.0, 1.0, 1.0]);
-
-            let mut normals = template.normals.clone().unwrap_or_default();
-            if align_to_normals && normals.len() != template.positions.len() {
-                let mut temp = template.clone();
-                if temp.normals.is_none() {
-                    temp.compute_normals();
-                }
-                normals = temp.normals.unwrap_or_default();
-            }
-
-            let rot = Vec3::from(rotate_deg) * std::f32::consts::PI / 180.0;
-            let user_quat = Quat::from_euler(EulerRot::XYZ, rot.x, rot.y, rot.z);
-            let scale = Vec3::from(scale);
-            let translate = Vec3::from(translate);
-
-            let mut copies = Vec::with_capacity(template.positions.len());
-            for (idx, pos) in template.positions.iter().enumerate() {
-                let mut rotation = user_quat;
-                if align_to_normals {
-                    let normal = normals.get(idx).copied().unwrap_or([0.0, 1.0, 0.0]);
-                    let normal = Vec3::from(normal);
-                    if normal.length_squared() > 0.0001 {
-                        let align = Quat::from_rotation_arc(Vec3::Y, normal.normalize());
-                        rotation = align * user_quat;
-                    }
-                }
-                let matrix = Mat4::from_scale_rotation_translation(
-                    scale,
-                    rotation,
-                    Vec3::from(*pos) + translate,
-                );
-                let mut mesh = source.clone();
-                mesh.transform(matrix);
-                copies.push(mesh);
-            }
+            let copies: Vec<Mesh> = copy_to_points_copies(&source, &template, params).collect();
             Ok(Mesh::merge(&copies))
         }
+        BuiltinNodeKind::Boolean => {
+            let a = require_input_at(inputs, 0, "Boolean requires mesh input a")?;
+            let b = require_input_at(inputs, 1, "Boolean requires mesh input b")?;
+            mesh_boolean(&a, &b, BooleanMode::from_index(params.get_int("mode", 0)))
+        }
         BuiltinNodeKind::Scatter => {
             let input = require_input_at(inputs, 0, "Scatter requires a mesh input")?;
             let count = params.get_int("count", 200).max(0) as usize;
             let seed = params.get_int("seed", 1) as u32;
-            scatter_points(&input, count, seed)
+            let density_attr = params.get_string("density_attr", "").trim();
+            let input = input.triangulate();
+            let density = if density_attr.is_empty() {
+                None
+            } else {
+                Some(triangle_density(&input, density_attr)?)
+            };
+            scatter_points(&input, count, seed, density.as_deref())
         }
         BuiltinNodeKind::Normal => {
             let mut input = require_input_at(inputs, 0, "Normal requires a mesh input")?;
@@ -505,6 +1144,11 @@ pub fn compute_mesh_node(
             let frequency = params.get_float("frequency", 1.0).max(0.0);
             let seed = params.get_int("seed", 1) as u32;
             let offset = Vec3::from(params.get_vec3("offset", [0.0, 0.0, 0.0]));
+            let fbm = Fbm {
+                basis: NoiseBasis::from_index(params.get_int("basis", 0)),
+                octaves: params.get_int("octaves", 3).clamp(1, 12) as u32,
+                ..Fbm::default()
+            };
 
             if input.normals.is_none() {
                 let _ = input.compute_normals();
@@ -514,16 +1158,56 @@ pub fn compute_mesh_node(
                 .clone()
                 .ok_or_else(|| "Noise/Mountain requires point normals".to_string())?;
 
-            for (pos, normal) in input.positions.iter_mut().zip(normals.iter()) {
+            par_zip_mut(&mut input.positions, &normals, |pos, normal| {
                 let p = Vec3::from(*pos) * frequency + offset;
-                let n = fractal_noise(p, seed);
+                let n = fbm.sample(p, seed);
                 let displacement = Vec3::from(*normal) * (n * amplitude);
-                let next = Vec3::from(*pos) + displacement;
-                *pos = next.to_array();
+                *pos = (Vec3::from(*pos) + displacement).to_array();
+            });
+
+            Ok(input)
+        }
+        BuiltinNodeKind::Wobble => {
+            let mut input = require_input_at(inputs, 0, "Wobble requires a mesh input")?;
+            let amplitude = params.get_float("amplitude", 0.1);
+            let frequency = params.get_float("frequency", 2.0).max(0.0);
+            let period = params.get_float("period", 24.0).max(1.0);
+            let seed = params.get_int("seed", 1) as u32;
+            let time = params.get_float(FRAME_PARAM, 0.0) / period;
+
+            if params.get_bool("jitter", false) {
+                // Curl noise drifting through time: points wander without bunching up.
+                let fbm = Fbm::default();
+                let drift = Vec3::splat(time);
+                par_for_each_mut(&mut input.positions, |pos| {
+                    let offset = fbm.curl(Vec3::from(*pos) * frequency + drift, seed);
+                    *pos = (Vec3::from(*pos) + offset * amplitude).to_array();
+                });
+                return Ok(input);
             }
 
+            if input.normals.is_none() {
+                let _ = input.compute_normals();
+            }
+            let normals = input
+                .normals
+                .clone()
+                .ok_or_else(|| "Wobble requires point normals".to_string())?;
+            // A wave travelling diagonally through the mesh, one cycle every `period` frames.
+            par_zip_mut(&mut input.positions, &normals, |pos, normal| {
+                let p = Vec3::from(*pos);
+                let phase = std::f32::consts::TAU * time + frequency * (p.x + p.y + p.z);
+                *pos = (p + Vec3::from(*normal) * (phase.sin() * amplitude)).to_array();
+            });
             Ok(input)
         }
+        BuiltinNodeKind::ParticleSim => {
+            // Simulates from the start; `evaluate_mesh_graph` caches frames per node instead.
+            let input = require_input_at(inputs, 0, "Particle Sim requires a mesh input")?;
+            let settings = ParticleSettings::from_params(params);
+            let frame = params.get_float(FRAME_PARAM, 0.0).floor() as i32;
+            simulate_particles(&mut FrameCache::default(), &input, &settings, frame)
+        }
         BuiltinNodeKind::AttributeMath => {
             let mut input = require_input_at(inputs, 0, "Attribute Math requires a mesh input")?;
             let attr = params.get_string("attr", "Cd");
@@ -607,6 +1291,32 @@ pub fn compute_mesh_node(
             }
             Ok(input)
         }
+        BuiltinNodeKind::AttributePrecision => {
+            let mut input =
+                require_input_at(inputs, 0, "Attribute Precision requires a mesh input")?;
+            let attr = params.get_string("attr", "Cd");
+            let domain = match params.get_int("domain", 0).clamp(0, 3) {
+                0 => AttributeDomain::Point,
+                1 => AttributeDomain::Vertex,
+                2 => AttributeDomain::Primitive,
+                _ => AttributeDomain::Detail,
+            };
+            let precision = AttributePrecision::from_index(params.get_int("precision", 2));
+            if input.attributes.get(domain, attr).is_none() {
+                warn!(
+                    "Attribute Precision: '{}' not found on {:?}; passing input through",
+                    attr, domain
+                );
+                return Ok(input);
+            }
+            input.attributes.set_precision(domain, attr, precision);
+            Ok(input)
+        }
+        BuiltinNodeKind::AttributeEdit => {
+            let mut input = require_input_at(inputs, 0, "Attribute Edit requires a mesh input")?;
+            apply_cell_edits(&mut input, params.get_string_list(CELL_EDITS_PARAM))?;
+            Ok(input)
+        }
         BuiltinNodeKind::Wrangle => {
             let mut input = require_input_at(inputs, 0, "Wrangle requires a mesh input")?;
             let code = params.get_string("code", "");
@@ -617,26 +1327,255 @@ pub fn compute_mesh_node(
                 _ => AttributeDomain::Detail,
             };
             if !code.trim().is_empty() {
-                apply_wrangle(&mut input, domain, code)?;
+                let frame = params.get_float(FRAME_PARAM, 1.0);
+                let seconds = params.get_float(TIME_PARAM, 0.0);
+                apply_wrangle(&mut input, domain, code, frame, seconds)?;
+            }
+            Ok(input)
+        }
+        BuiltinNodeKind::PolyExtrude => {
+            let input = require_input_at(inputs, 0, "PolyExtrude requires a mesh input")?;
+            let settings = PolyExtrudeSettings {
+                distance: params.get_float("distance", 0.25),
+                inset: params.get_float("inset", 0.0).clamp(0.0, 1.0),
+                individual: params.get_bool("individual", false),
+                group: params.get_string("group", "").to_string(),
+                side_group: params.get_string("side_group", "extrude_side").to_string(),
+            };
+            poly_extrude(&input, &settings)
+        }
+        BuiltinNodeKind::Bevel => {
+            let input = require_input_at(inputs, 0, "Bevel requires a mesh input")?;
+            let settings = BevelSettings {
+                width: params.get_float("width", 0.05).max(0.0),
+                segments: params.get_int("segments", 1).clamp(1, 64) as usize,
+                angle_deg: params.get_float("angle_deg", 30.0),
+                group: params.get_string("group", "").to_string(),
+            };
+            bevel(&input, &settings)
+        }
+        BuiltinNodeKind::Clean => {
+            let mut input = require_input_at(inputs, 0, "Clean requires a mesh input")?;
+            if params.get_bool("triangulate", false) {
+                input = input.triangulate();
+            }
+            if params.get_bool("fix_winding", true) {
+                unify_winding(&mut input)?;
+            }
+            if params.get_bool("quadrangulate", false) {
+                quadrangulate(&mut input, params.get_float("quad_angle_deg", 5.0))?;
+            }
+            if params.get_bool("flip", false) {
+                flip_winding(&mut input);
             }
             Ok(input)
         }
+        BuiltinNodeKind::Sort => {
+            let input = require_input_at(inputs, 0, "Sort requires a mesh input")?;
+            let domain = if params.get_int("domain", 0) > 0 {
+                AttributeDomain::Primitive
+            } else {
+                AttributeDomain::Point
+            };
+            let key = match params.get_int("key", 0).clamp(0, 2) {
+                0 => SortKey::Axis,
+                1 => SortKey::Distance,
+                _ => SortKey::Attribute,
+            };
+            let settings = SortSettings {
+                domain,
+                key,
+                axis: params.get_vec3("axis", [1.0, 0.0, 0.0]),
+                point: params.get_vec3("point", [0.0, 0.0, 0.0]),
+                attr: params.get_string("attr", "").to_string(),
+                reverse: params.get_bool("reverse", false),
+            };
+            sort_mesh(&input, &settings)
+        }
+        BuiltinNodeKind::Split => {
+            let input = require_input_at(inputs, 0, "Split requires a mesh input")?;
+            let (inside, _) = split_mesh(&input, params.get_string("group", ""))?;
+            Ok(inside)
+        }
+        BuiltinNodeKind::Lod => {
+            let input = require_input_at(inputs, 0, "LOD requires a mesh input")?;
+            let ratios = parse_lod_ratios(params.get_string("ratios", "1 0.5 0.25"))?;
+            lod_mesh(&input, &ratios, params.get_string("attr", "lod"))
+        }
+        BuiltinNodeKind::BakeAo => {
+            let input = require_input_at(inputs, 0, "Bake AO requires a mesh input")?;
+            let settings = BakeAoSettings {
+                samples: params.get_int("samples", 32).max(1) as u32,
+                max_distance: params.get_float("max_distance", 1.0),
+                light_mix: params.get_float("light_mix", 0.0),
+                light_dir: params.get_vec3("light_dir", [0.6, 1.0, 0.2]),
+                attr: params.get_string("attr", "Cd").to_string(),
+            };
+            bake_ao(&input, &settings)
+        }
+        BuiltinNodeKind::Curvature => {
+            let input = require_input_at(inputs, 0, "Curvature requires a mesh input")?;
+            let settings = CurvatureSettings {
+                mode: match params.get_int("mode", 0) {
+                    1 => CurvatureMode::Convex,
+                    2 => CurvatureMode::Concave,
+                    _ => CurvatureMode::Signed,
+                },
+                scale: params.get_float("scale", 1.0),
+                smooth_iterations: params.get_int("smooth", 0).max(0) as u32,
+                attr: params.get_string("attr", "curvature").to_string(),
+            };
+            curvature_mask(&input, &settings)
+        }
+        BuiltinNodeKind::Slope => {
+            let input = require_input_at(inputs, 0, "Slope requires a mesh input")?;
+            let settings = SlopeSettings {
+                up: params.get_vec3("up", [0.0, 1.0, 0.0]),
+                min_angle: params.get_float("min_angle", 0.0),
+                max_angle: params.get_float("max_angle", 90.0),
+                smooth_iterations: params.get_int("smooth", 0).max(0) as u32,
+                attr: params.get_string("attr", "slope").to_string(),
+            };
+            slope_mask(&input, &settings)
+        }
+        BuiltinNodeKind::GeodesicDistance => {
+            let input = require_input_at(inputs, 0, "Geodesic Distance requires a mesh input")?;
+            let settings = GeodesicSettings {
+                group: params.get_string("group", "").to_string(),
+                normalize: params.get_bool("normalize", false),
+                attr: params.get_string("attr", "dist").to_string(),
+            };
+            geodesic_distance(&input, &settings)
+        }
+        BuiltinNodeKind::FloorSplit => {
+            let input = require_input_at(inputs, 0, "Floor Split requires a mesh input")?;
+            let settings = FloorSplitSettings {
+                floor_height: params.get_float("floor_height", 1.0),
+                attr: params.get_string("attr", "floor").to_string(),
+            };
+            floor_split(&input, &settings)
+        }
+        BuiltinNodeKind::FacadeRepeat => {
+            let input = require_input_at(inputs, 0, "Facade Repeat requires a mesh input")?;
+            let settings = FacadeRepeatSettings {
+                bay_width: params.get_float("bay_width", 1.0),
+                attr: params.get_string("attr", "bay").to_string(),
+            };
+            facade_repeat(&input, &settings)
+        }
+        BuiltinNodeKind::WindowScatter => {
+            let input = require_input_at(inputs, 0, "Window Scatter requires a mesh input")?;
+            let settings = WindowScatterSettings {
+                density: params.get_float("density", 0.8),
+                seed: params.get_int("seed", 1) as u32,
+                offset: params.get_float("offset", 0.0),
+                skip_ground_floor: params.get_bool("skip_ground_floor", true),
+            };
+            window_scatter(&input, &settings)
+        }
         BuiltinNodeKind::ObjOutput => {
             let input = require_input_at(inputs, 0, "OBJ Output requires a mesh input")?;
             let path = params.get_string("path", "output.obj");
             if path.trim().is_empty() {
                 return Err("OBJ Output requires a path".to_string());
             }
-            write_obj(path, &input)?;
+            let unit_scale = params.get_float(UNIT_SCALE_PARAM, 1.0);
+            if unit_scale == 1.0 {
+                write_obj(path, &input)?;
+            } else {
+                let mut exported = input.clone();
+                scale_mesh(&mut exported, 1.0 / unit_scale);
+                write_obj(path, &exported)?;
+            }
             Ok(input)
         }
         BuiltinNodeKind::Output => {
             let input = require_input_at(inputs, 0, "Output requires a mesh input")?;
             Ok(input)
         }
+        BuiltinNodeKind::SubnetOutput => {
+            let input = require_input_at(inputs, 0, "Subnet Output requires a mesh input")?;
+            Ok(input)
+        }
+        BuiltinNodeKind::Subnet | BuiltinNodeKind::SubnetInput => Err(format!(
+            "{} only cooks as part of a graph evaluation",
+            kind.name()
+        )),
     }
 }
 
+pub fn compute_mesh_node_outputs(
+    kind: BuiltinNodeKind,
+    params: &NodeParams,
+    inputs: &[Mesh],
+) -> Result<Vec<Mesh>, String> {
+    match kind {
+        BuiltinNodeKind::Split => {
+            let input = require_input_at(inputs, 0, "Split requires a mesh input")?;
+            let (inside, outside) = split_mesh(&input, params.get_string("group", ""))?;
+            Ok(vec![inside, outside])
+        }
+        _ => compute_mesh_node(kind, params, inputs).map(|mesh| vec![mesh]),
+    }
+}
+
+// One transformed copy of `source` per template point. Copies are produced lazily so the
+// streaming exporter can write them without merging.
+pub(crate) fn copy_to_points_copies<'a>(
+    source: &'a Mesh,
+    template: &'a Mesh,
+    params: &NodeParams,
+) -> impl Iterator<Item = Mesh> + 'a {
+    let align_to_normals = params.get_bool("align_to_normals", true);
+    let translate = params.get_vec3("translate", [0.0, 0.0, 0.0]);
+    let rotate_deg = params.get_vec3("rotate_deg", [0.0, 0.0, 0.0]);
+    let orient = Quat::from_array(params.get_quat("orient", [0.0, 0.0, 0.0, 1.0]));
+    let scale = params.get_vec3("scale", [1.0, 1.0, 1.0]);
+
+    let mut normals = template.normals.clone().unwrap_or_default();
+    if align_to_normals && normals.len() != template.positions.len() {
+        let mut temp = template.clone();
+        if temp.normals.is_none() {
+            temp.compute_normals();
+        }
+        normals = temp.normals.unwrap_or_default();
+    }
+
+    let rot = Vec3::from(rotate_deg) * std::f32::consts::PI / 180.0;
+    let orient = if orient.length_squared() > 0.0 {
+        orient.normalize()
+    } else {
+        Quat::IDENTITY
+    };
+    let user_quat = orient * Quat::from_euler(EulerRot::XYZ, rot.x, rot.y, rot.z);
+    let scale = Vec3::from(scale);
+    let translate = Vec3::from(translate);
+
+    template
+        .positions
+        .iter()
+        .enumerate()
+        .map(move |(idx, pos)| {
+            let mut rotation = user_quat;
+            if align_to_normals {
+                let normal = normals.get(idx).copied().unwrap_or([0.0, 1.0, 0.0]);
+                let normal = Vec3::from(normal);
+                if normal.length_squared() > 0.0001 {
+                    let align = Quat::from_rotation_arc(Vec3::Y, normal.normalize());
+                    rotation = align * user_quat;
+                }
+            }
+            let matrix = Mat4::from_scale_rotation_translation(
+                scale,
+                rotation,
+                Vec3::from(*pos) + translate,
+            );
+            let mut mesh = source.clone();
+            mesh.transform(matrix);
+            mesh
+        })
+}
+
 fn require_input_at(inputs: &[Mesh], index: usize, message: &str) -> Result<Mesh, String> {
     inputs
         .get(index)
@@ -676,64 +1615,6 @@ fn apply_op_i(value: i32, rhs: i32, op: i32) -> i32 {
     }
 }
 
-fn fractal_noise(p: Vec3, seed: u32) -> f32 {
-    let mut value = 0.0;
-    let mut amp = 1.0;
-    let mut freq = 1.0;
-    for _ in 0..3 {
-        value += value_noise(p * freq, seed) * amp;
-        amp *= 0.5;
-        freq *= 2.0;
-    }
-    value
-}
-
-fn value_noise(p: Vec3, seed: u32) -> f32 {
-    let base = p.floor();
-    let frac = p - base;
-    let f = frac * frac * (Vec3::splat(3.0) - 2.0 * frac);
-
-    let x0 = base.x as i32;
-    let y0 = base.y as i32;
-    let z0 = base.z as i32;
-    let x1 = x0 + 1;
-    let y1 = y0 + 1;
-    let z1 = z0 + 1;
-
-    let c000 = hash3(x0, y0, z0, seed);
-    let c100 = hash3(x1, y0, z0, seed);
-    let c010 = hash3(x0, y1, z0, seed);
-    let c110 = hash3(x1, y1, z0, seed);
-    let c001 = hash3(x0, y0, z1, seed);
-    let c101 = hash3(x1, y0, z1, seed);
-    let c011 = hash3(x0, y1, z1, seed);
-    let c111 = hash3(x1, y1, z1, seed);
-
-    let x00 = lerp(c000, c100, f.x);
-    let x10 = lerp(c010, c110, f.x);
-    let x01 = lerp(c001, c101, f.x);
-    let x11 = lerp(c011, c111, f.x);
-    let y0 = lerp(x00, x10, f.y);
-    let y1 = lerp(x01, x11, f.y);
-    lerp(y0, y1, f.z) * 2.0 - 1.0
-}
-
-fn lerp(a: f32, b: f32, t: f32) -> f32 {
-    a + (b - a) * t
-}
-
-fn hash3(x: i32, y: i32, z: i32, seed: u32) -> f32 {
-    let mut h = x as u32;
-    h ^= (y as u32).wrapping_mul(374761393);
-    h = h.rotate_left(13);
-    h ^= (z as u32).wrapping_mul(668265263);
-    h = h.rotate_left(17);
-    h ^= seed.wrapping_mul(2246822519);
-    h = h.wrapping_mul(3266489917);
-    h = (h ^ (h >> 16)).wrapping_mul(2246822519);
-    (h as f32) / (u32::MAX as f32)
-}
-
 #[cfg(target_arch = "wasm32")]
 fn load_obj_mesh(_path: &str) -> Result<Mesh, String> {
     Err("File node is not supported in web builds".to_string())
@@ -748,7 +1629,7 @@ fn load_obj_mesh(path: &str) -> Result<Mesh, String> {
 
     let (models, _) = {
         let options = tobj::LoadOptions {
-            triangulate: true,
+            triangulate: false,
             single_index: true,
             ..Default::default()
         };
@@ -761,6 +1642,7 @@ fn load_obj_mesh(path: &str) -> Result<Mesh, String> {
 
     let mut positions: Vec<[f32; 3]> = Vec::new();
     let mut indices: Vec<u32> = Vec::new();
+    let mut face_counts: Vec<u32> = Vec::new();
     let mut normals: Vec<[f32; 3]> = Vec::new();
     let mut uvs: Vec<[f32; 2]> = Vec::new();
     let mut include_normals = true;
@@ -777,6 +1659,11 @@ fn load_obj_mesh(path: &str) -> Result<Mesh, String> {
         positions.extend(mesh.positions.chunks_exact(3).map(|v| [v[0], v[1], v[2]]));
         indices.extend(mesh.indices.iter().map(|i| i + vertex_offset));
         vertex_offset += vertex_count as u32;
+        if mesh.face_arities.is_empty() {
+            face_counts.extend(std::iter::repeat_n(3, mesh.indices.len() / 3));
+        } else {
+            face_counts.extend_from_slice(&mesh.face_arities);
+        }
 
         if mesh.normals.len() == mesh.positions.len() {
             normals.extend(mesh.normals.chunks_exact(3).map(|n| [n[0], n[1], n[2]]));
@@ -791,7 +1678,7 @@ fn load_obj_mesh(path: &str) -> Result<Mesh, String> {
         }
     }
 
-    let mut mesh = Mesh::with_positions_indices(positions, indices);
+    let mut mesh = Mesh::with_polygons(positions, indices, face_counts);
     if include_normals && !normals.is_empty() {
         mesh.normals = Some(normals);
     }
@@ -825,58 +1712,59 @@ fn write_obj(_path: &str, _mesh: &Mesh) -> Result<(), String> {
 
 #[cfg(not(target_arch = "wasm32"))]
 fn write_obj(path: &str, mesh: &Mesh) -> Result<(), String> {
-    use std::io::Write;
-
-    let mut file = std::fs::File::create(path).map_err(|err| err.to_string())?;
-    for p in &mesh.positions {
-        writeln!(file, "v {} {} {}", p[0], p[1], p[2]).map_err(|err| err.to_string())?;
-    }
-
-    let has_uv = mesh
-        .uvs
-        .as_ref()
-        .is_some_and(|uvs| uvs.len() == mesh.positions.len());
-    if let Some(uvs) = &mesh.uvs {
-        if has_uv {
-            for uv in uvs {
-                writeln!(file, "vt {} {}", uv[0], uv[1]).map_err(|err| err.to_string())?;
-            }
-        }
-    }
+    let file = std::fs::File::create(path).map_err(|err| err.to_string())?;
+    let mut writer = std::io::BufWriter::new(file);
+    write_obj_to(&mut writer, mesh)?;
+    std::io::Write::flush(&mut writer).map_err(|err| err.to_string())
+}
 
-    let has_normals = mesh
-        .normals
-        .as_ref()
-        .is_some_and(|normals| normals.len() == mesh.positions.len());
-    if let Some(normals) = &mesh.normals {
-        if has_normals {
-            for n in normals {
-                writeln!(file, "vn {} {} {}", n[0], n[1], n[2]).map_err(|err| err.to_string())?;
-            }
-        }
-    }
+pub fn write_obj_to<W: std::io::Write>(file: &mut W, mesh: &Mesh) -> Result<(), String> {
+    ObjStreamWriter::new(file).write_chunk(mesh)
+}
 
-    if !mesh.indices.is_empty() {
-        for tri in mesh.indices.chunks_exact(3) {
-            let a = tri[0] + 1;
-            let b = tri[1] + 1;
-            let c = tri[2] + 1;
-            if has_uv && has_normals {
-                writeln!(file, "f {a}/{a}/{a} {b}/{b}/{b} {c}/{c}/{c}")
-                    .map_err(|err| err.to_string())?;
-            } else if has_uv {
-                writeln!(file, "f {a}/{a} {b}/{b} {c}/{c}").map_err(|err| err.to_string())?;
-            } else if has_normals {
-                writeln!(file, "f {a}//{a} {b}//{b} {c}//{c}").map_err(|err| err.to_string())?;
-            } else {
-                writeln!(file, "f {a} {b} {c}").map_err(|err| err.to_string())?;
-            }
-        }
-    }
-    Ok(())
+// Mean of a float attribute over each triangle of a triangulated mesh, clamped to zero so
+// negative values mean "no points" rather than cancelling out neighbouring area.
+fn triangle_density(input: &Mesh, name: &str) -> Result<Vec<f32>, String> {
+    let domains = [
+        AttributeDomain::Point,
+        AttributeDomain::Vertex,
+        AttributeDomain::Primitive,
+    ];
+    let Some((domain, attr)) = domains
+        .into_iter()
+        .find_map(|domain| Some((domain, input.attribute(domain, name)?)))
+    else {
+        return Err(format!("Scatter: density attribute '{}' not found", name));
+    };
+    let AttributeRef::Float(values) = attr else {
+        return Err(format!(
+            "Scatter: density '{}' must be a float attribute",
+            name
+        ));
+    };
+    let value = |index: usize| values.get(index).copied().unwrap_or(0.0);
+    let density = input
+        .indices
+        .chunks_exact(3)
+        .enumerate()
+        .map(|(tri, corners)| match domain {
+            AttributeDomain::Point => corners.iter().map(|&i| value(i as usize)).sum::<f32>() / 3.0,
+            AttributeDomain::Vertex => (0..3).map(|i| value(tri * 3 + i)).sum::<f32>() / 3.0,
+            _ => value(tri),
+        })
+        .map(|density| density.max(0.0))
+        .collect();
+    Ok(density)
 }
 
-fn scatter_points(input: &Mesh, count: usize, seed: u32) -> Result<Mesh, String> {
+// Samples `count` points on the triangles, with each triangle's chance proportional to its
+// area, scaled by `density` when given.
+fn scatter_points(
+    input: &Mesh,
+    count: usize,
+    seed: u32,
+    density: Option<&[f32]>,
+) -> Result<Mesh, String> {
     if count == 0 {
         return Ok(Mesh::default());
     }
@@ -898,13 +1786,20 @@ fn scatter_points(input: &Mesh, count: usize, seed: u32) -> Result<Mesh, String>
         let p0 = Vec3::from(input.positions[i0]);
         let p1 = Vec3::from(input.positions[i1]);
         let p2 = Vec3::from(input.positions[i2]);
+        let weight = density.map_or(1.0, |density| {
+            density.get(areas.len()).copied().unwrap_or(0.0)
+        });
         let area = 0.5 * (p1 - p0).cross(p2 - p0).length();
-        total += area.max(0.0);
+        total += area.max(0.0) * weight;
         areas.push(total);
     }
 
     if total <= 0.0 {
-        return Err("Scatter requires non-degenerate triangles".to_string());
+        return Err(if density.is_some() {
+            "Scatter: density is zero everywhere".to_string()
+        } else {
+            "Scatter requires non-degenerate triangles".to_string()
+        });
     }
 
     let mut rng = XorShift32::new(seed);
@@ -951,6 +1846,9 @@ fn scatter_points(input: &Mesh, count: usize, seed: u32) -> Result<Mesh, String>
         corner_normals: None,
         uvs: None,
         attributes: Default::default(),
+        face_counts: Vec::new(),
+        debug_channels: Vec::new(),
+        half_edge_cache: Default::default(),
     })
 }
 
@@ -1008,6 +1906,31 @@ mod tests {
         assert!((bounds.max[0] - 1.0).abs() < 0.01);
     }
 
+    #[test]
+    fn param_groups_name_existing_params() {
+        for def in builtin_definitions() {
+            let kind = builtin_kind_from_name(&def.name).unwrap();
+            let params = default_params(kind);
+            for key in [
+                "mirror",
+                "octaves",
+                "orient",
+                "light_dir",
+                "max_angle",
+                "point",
+            ] {
+                if param_group(kind, key).is_some() {
+                    assert!(params.values.contains_key(key), "{}.{}", def.name, key);
+                }
+            }
+        }
+        assert_eq!(
+            param_group(BuiltinNodeKind::Noise, "mirror"),
+            Some("Symmetry")
+        );
+        assert_eq!(param_group(BuiltinNodeKind::Noise, "amplitude"), None);
+    }
+
     #[test]
     fn merge_combines_meshes() {
         let a = make_box([1.0, 1.0, 1.0]);
@@ -1032,6 +1955,35 @@ mod tests {
         assert_eq!(mesh.normals.as_ref().map(|n| n.len()), Some(12));
     }
 
+    #[test]
+    fn scatter_follows_density_attribute() {
+        let mut input = make_box([1.0, 1.0, 1.0]);
+        let faces = input.face_counts.len();
+        let mut density = vec![0.0; faces];
+        density[0] = 2.0;
+        input
+            .set_attribute(
+                AttributeDomain::Primitive,
+                "density",
+                AttributeStorage::Float(density),
+            )
+            .unwrap();
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("count".to_string(), ParamValue::Int(50)),
+                (
+                    "density_attr".to_string(),
+                    ParamValue::String("density".to_string()),
+                ),
+            ]),
+        };
+        let mesh = compute_mesh_node(BuiltinNodeKind::Scatter, &params, &[input]).unwrap();
+        assert_eq!(mesh.positions.len(), 50);
+        // Only the first face has any density, so every point shares its normal.
+        let normals = mesh.normals.unwrap();
+        assert!(normals.iter().all(|normal| *normal == normals[0]));
+    }
+
     #[test]
     fn normal_recomputes_normals() {
         let mut input = make_box([1.0, 1.0, 1.0]);
@@ -1040,4 +1992,210 @@ mod tests {
             compute_mesh_node(BuiltinNodeKind::Normal, &NodeParams::default(), &[input]).unwrap();
         assert!(mesh.normals.is_some());
     }
+
+    #[test]
+    fn poly_extrude_individual_adds_side_faces() {
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("distance".to_string(), ParamValue::Float(1.0)),
+                ("individual".to_string(), ParamValue::Bool(true)),
+            ]),
+        };
+        let input = make_grid([2.0, 1.0], [2, 1]);
+        let mesh = compute_mesh_node(BuiltinNodeKind::PolyExtrude, &params, &[input]).unwrap();
+        assert_eq!(mesh.positions.len(), 6 + 8);
+        assert_eq!(mesh.face_count(), 2 + 2 * 4);
+        assert!(mesh.face_counts.iter().all(|count| *count == 4));
+        let side = mesh
+            .attribute(AttributeDomain::Primitive, "extrude_side")
+            .expect("side group");
+        let crate::attributes::AttributeRef::Int(side) = side else {
+            panic!("side group should be int");
+        };
+        assert_eq!(side.iter().filter(|v| **v != 0).count(), 8);
+    }
+
+    #[test]
+    fn poly_extrude_group_only_extrudes_boundary() {
+        let params = NodeParams {
+            values: BTreeMap::from([("distance".to_string(), ParamValue::Float(1.0))]),
+        };
+        let input = make_grid([2.0, 1.0], [2, 1]);
+        let mesh = compute_mesh_node(BuiltinNodeKind::PolyExtrude, &params, &[input]).unwrap();
+        assert_eq!(mesh.positions.len(), 12);
+        assert_eq!(mesh.face_count(), 2 + 6);
+        let bounds = mesh.bounds().expect("bounds");
+        assert!((bounds.max[1] - bounds.min[1]).abs() > 0.99);
+    }
+
+    #[test]
+    fn poly_extrude_respects_primitive_group() {
+        let mut input = make_grid([2.0, 1.0], [2, 1]);
+        input
+            .set_attribute(
+                AttributeDomain::Primitive,
+                "top",
+                AttributeStorage::Int(vec![1, 0]),
+            )
+            .unwrap();
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("group".to_string(), ParamValue::String("top".to_string())),
+                ("individual".to_string(), ParamValue::Bool(true)),
+            ]),
+        };
+        let mesh = compute_mesh_node(BuiltinNodeKind::PolyExtrude, &params, &[input]).unwrap();
+        assert_eq!(mesh.positions.len(), 6 + 4);
+        assert_eq!(mesh.face_count(), 2 + 4);
+    }
+
+    #[test]
+    fn bevel_box_stays_closed() {
+        for segments in [1, 3] {
+            let params = NodeParams {
+                values: BTreeMap::from([
+                    ("width".to_string(), ParamValue::Float(0.1)),
+                    ("segments".to_string(), ParamValue::Int(segments)),
+                ]),
+            };
+            let input = make_box([1.0, 1.0, 1.0]);
+            let mesh = compute_mesh_node(BuiltinNodeKind::Bevel, &params, &[input]).unwrap();
+            assert!(mesh.indices.len() / 3 > 12);
+            let bounds = mesh.bounds().expect("bounds");
+            assert!((bounds.max[0] - 0.5).abs() < 1.0e-4);
+            assert!((bounds.min[1] + 0.5).abs() < 1.0e-4);
+
+            let mut edges = std::collections::HashMap::new();
+            for tri in mesh.indices.chunks_exact(3) {
+                for i in 0..3 {
+                    *edges.entry((tri[i], tri[(i + 1) % 3])).or_insert(0) += 1;
+                }
+            }
+            for (&(a, b), &count) in &edges {
+                assert_eq!(count, 1);
+                assert_eq!(edges.get(&(b, a)), Some(&1));
+            }
+        }
+    }
+
+    #[test]
+    fn bevel_skips_flat_edges() {
+        let params = NodeParams {
+            values: BTreeMap::from([("width".to_string(), ParamValue::Float(0.1))]),
+        };
+        let input = make_grid([1.0, 1.0], [2, 2]);
+        let mesh = compute_mesh_node(
+            BuiltinNodeKind::Bevel,
+            &params,
+            std::slice::from_ref(&input),
+        )
+        .unwrap();
+        assert_eq!(mesh.positions.len(), input.positions.len());
+        assert_eq!(mesh.indices, input.indices);
+    }
+
+    #[test]
+    fn clean_fixes_flipped_face() {
+        let mut input = make_box([1.0, 1.0, 1.0]);
+        input.indices[5..8].reverse();
+        input.indices[21..24].reverse();
+        let mesh =
+            compute_mesh_node(BuiltinNodeKind::Clean, &NodeParams::default(), &[input]).unwrap();
+        assert_eq!(mesh.indices, make_box([1.0, 1.0, 1.0]).indices);
+    }
+
+    #[test]
+    fn clean_quadrangulates_triangulated_box() {
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("triangulate".to_string(), ParamValue::Bool(true)),
+                ("quadrangulate".to_string(), ParamValue::Bool(true)),
+            ]),
+        };
+        let input = make_box([1.0, 1.0, 1.0]);
+        let mesh = compute_mesh_node(BuiltinNodeKind::Clean, &params, &[input]).unwrap();
+        assert_eq!(mesh.face_counts, vec![4; 6]);
+        let volume: f32 = mesh
+            .face_ranges()
+            .into_iter()
+            .map(|range| {
+                let p = |k: usize| Vec3::from(mesh.positions[mesh.indices[k] as usize]);
+                (range.start + 1..range.end - 1)
+                    .map(|k| p(range.start).dot(p(k).cross(p(k + 1))))
+                    .sum::<f32>()
+            })
+            .sum();
+        assert!((volume / 6.0 - 1.0).abs() < 1e-5);
+
+        let mut bent = make_grid([1.0, 1.0], [1, 1]).triangulate();
+        bent.positions[0][1] = 0.5;
+        let mesh = compute_mesh_node(BuiltinNodeKind::Clean, &params, &[bent]).unwrap();
+        assert!(mesh.face_counts.is_empty());
+    }
+
+    #[test]
+    fn clean_flip_reverses_winding() {
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("fix_winding".to_string(), ParamValue::Bool(false)),
+                ("flip".to_string(), ParamValue::Bool(true)),
+            ]),
+        };
+        let input = make_grid([1.0, 1.0], [1, 1]);
+        let mut mesh = compute_mesh_node(BuiltinNodeKind::Clean, &params, &[input]).unwrap();
+        mesh.compute_normals();
+        let normals = mesh.normals.expect("normals");
+        assert!(normals.iter().all(|n| n[1] < -0.99));
+    }
+
+    #[test]
+    fn sort_points_by_axis_keeps_faces() {
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("axis".to_string(), ParamValue::Vec3([0.0, 0.0, 1.0])),
+                ("reverse".to_string(), ParamValue::Bool(true)),
+            ]),
+        };
+        let input = make_grid([1.0, 1.0], [1, 1]);
+        let mesh = compute_mesh_node(BuiltinNodeKind::Sort, &params, std::slice::from_ref(&input))
+            .unwrap();
+        let zs: Vec<f32> = mesh.positions.iter().map(|p| p[2]).collect();
+        assert_eq!(zs, vec![0.5, 0.5, -0.5, -0.5]);
+        let face: Vec<[f32; 3]> = mesh
+            .indices
+            .iter()
+            .map(|idx| mesh.positions[*idx as usize])
+            .collect();
+        let original: Vec<[f32; 3]> = input
+            .indices
+            .iter()
+            .map(|idx| input.positions[*idx as usize])
+            .collect();
+        assert_eq!(face, original);
+    }
+
+    #[test]
+    fn sort_primitives_by_attribute() {
+        let mut input = make_grid([3.0, 1.0], [3, 1]);
+        input
+            .set_attribute(
+                AttributeDomain::Primitive,
+                "rank",
+                AttributeStorage::Int(vec![2, 0, 1]),
+            )
+            .unwrap();
+        let params = NodeParams {
+            values: BTreeMap::from([
+                ("domain".to_string(), ParamValue::Int(1)),
+                ("key".to_string(), ParamValue::Int(2)),
+                ("attr".to_string(), ParamValue::String("rank".to_string())),
+            ]),
+        };
+        let mesh = compute_mesh_node(BuiltinNodeKind::Sort, &params, &[input]).unwrap();
+        assert_eq!(
+            mesh.attribute(AttributeDomain::Primitive, "rank"),
+            Some(crate::attributes::AttributeRef::Int(&[0, 1, 2]))
+        );
+        assert_eq!(mesh.face_count(), 3);
+    }
 }
@@ -0,0 +1,287 @@
+use glam::Vec3;
+
+use crate::parallel::par_map;
+
+// Shared noise basis functions. Every function takes an explicit seed so two nodes with the
+// same seed and position always agree.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum NoiseBasis {
+    #[default]
+    Value,
+    Perlin,
+    Simplex,
+    Worley,
+}
+
+impl NoiseBasis {
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => NoiseBasis::Perlin,
+            2 => NoiseBasis::Simplex,
+            3 => NoiseBasis::Worley,
+            _ => NoiseBasis::Value,
+        }
+    }
+
+    // Signed sample in roughly [-1, 1]; Worley is remapped from its cell distance.
+    pub fn sample(self, p: Vec3, seed: u32) -> f32 {
+        match self {
+            NoiseBasis::Value => value(p, seed),
+            NoiseBasis::Perlin => perlin(p, seed),
+            NoiseBasis::Simplex => simplex(p, seed),
+            NoiseBasis::Worley => 1.0 - 2.0 * worley(p, seed).min(1.0),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fbm {
+    pub basis: NoiseBasis,
+    pub octaves: u32,
+    pub lacunarity: f32,
+    pub gain: f32,
+}
+
+impl Default for Fbm {
+    fn default() -> Self {
+        Self {
+            basis: NoiseBasis::Value,
+            octaves: 3,
+            lacunarity: 2.0,
+            gain: 0.5,
+        }
+    }
+}
+
+impl Fbm {
+    pub fn sample(&self, p: Vec3, seed: u32) -> f32 {
+        let mut value = 0.0;
+        let mut amp = 1.0;
+        let mut freq = 1.0;
+        for _ in 0..self.octaves.max(1) {
+            value += self.basis.sample(p * freq, seed) * amp;
+            amp *= self.gain;
+            freq *= self.lacunarity;
+        }
+        value
+    }
+
+    pub fn sample_points(&self, points: &[[f32; 3]], seed: u32) -> Vec<f32> {
+        par_map(points, |p| self.sample(Vec3::from(*p), seed))
+    }
+
+    // Divergence-free field from the curl of three decorrelated fbm potentials.
+    pub fn curl(&self, p: Vec3, seed: u32) -> Vec3 {
+        const EPS: f32 = 1.0e-2;
+        let potential = |q: Vec3| {
+            Vec3::new(
+                self.sample(q, seed),
+                self.sample(q, seed.wrapping_add(1)),
+                self.sample(q, seed.wrapping_add(2)),
+            )
+        };
+        let dx = (potential(p + Vec3::X * EPS) - potential(p - Vec3::X * EPS)) / (2.0 * EPS);
+        let dy = (potential(p + Vec3::Y * EPS) - potential(p - Vec3::Y * EPS)) / (2.0 * EPS);
+        let dz = (potential(p + Vec3::Z * EPS) - potential(p - Vec3::Z * EPS)) / (2.0 * EPS);
+        Vec3::new(dy.z - dz.y, dz.x - dx.z, dx.y - dy.x)
+    }
+
+    pub fn curl_points(&self, points: &[[f32; 3]], seed: u32) -> Vec<[f32; 3]> {
+        par_map(points, |p| self.curl(Vec3::from(*p), seed).to_array())
+    }
+}
+
+pub fn value(p: Vec3, seed: u32) -> f32 {
+    let base = p.floor();
+    let frac = p - base;
+    let f = frac * frac * (Vec3::splat(3.0) - 2.0 * frac);
+    let [x0, y0, z0] = [base.x as i32, base.y as i32, base.z as i32];
+    let corner = |dx: i32, dy: i32, dz: i32| hash_unit(x0 + dx, y0 + dy, z0 + dz, seed);
+
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), f.x);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), f.x);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), f.x);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), f.x);
+    let y0 = lerp(x00, x10, f.y);
+    let y1 = lerp(x01, x11, f.y);
+    lerp(y0, y1, f.z) * 2.0 - 1.0
+}
+
+pub fn perlin(p: Vec3, seed: u32) -> f32 {
+    let base = p.floor();
+    let frac = p - base;
+    let f = frac * frac * frac * (frac * (frac * 6.0 - Vec3::splat(15.0)) + Vec3::splat(10.0));
+    let [x0, y0, z0] = [base.x as i32, base.y as i32, base.z as i32];
+    let corner = |dx: i32, dy: i32, dz: i32| {
+        let offset = frac - Vec3::new(dx as f32, dy as f32, dz as f32);
+        gradient(hash(x0 + dx, y0 + dy, z0 + dz, seed)).dot(offset)
+    };
+
+    let x00 = lerp(corner(0, 0, 0), corner(1, 0, 0), f.x);
+    let x10 = lerp(corner(0, 1, 0), corner(1, 1, 0), f.x);
+    let x01 = lerp(corner(0, 0, 1), corner(1, 0, 1), f.x);
+    let x11 = lerp(corner(0, 1, 1), corner(1, 1, 1), f.x);
+    let y0 = lerp(x00, x10, f.y);
+    let y1 = lerp(x01, x11, f.y);
+    lerp(y0, y1, f.z)
+}
+
+pub fn simplex(p: Vec3, seed: u32) -> f32 {
+    const F3: f32 = 1.0 / 3.0;
+    const G3: f32 = 1.0 / 6.0;
+    let skew = (p.x + p.y + p.z) * F3;
+    let cell = (p + Vec3::splat(skew)).floor();
+    let unskew = (cell.x + cell.y + cell.z) * G3;
+    let d0 = p - (cell - Vec3::splat(unskew));
+
+    // Order the offsets by the largest component to pick the enclosing tetrahedron.
+    let (o1, o2) = if d0.x >= d0.y {
+        if d0.y >= d0.z {
+            (Vec3::X, Vec3::new(1.0, 1.0, 0.0))
+        } else if d0.x >= d0.z {
+            (Vec3::X, Vec3::new(1.0, 0.0, 1.0))
+        } else {
+            (Vec3::Z, Vec3::new(1.0, 0.0, 1.0))
+        }
+    } else if d0.y < d0.z {
+        (Vec3::Z, Vec3::new(0.0, 1.0, 1.0))
+    } else if d0.x < d0.z {
+        (Vec3::Y, Vec3::new(0.0, 1.0, 1.0))
+    } else {
+        (Vec3::Y, Vec3::new(1.0, 1.0, 0.0))
+    };
+
+    let [cx, cy, cz] = [cell.x as i32, cell.y as i32, cell.z as i32];
+    let mut total = 0.0;
+    for (offset, d) in [
+        (Vec3::ZERO, d0),
+        (o1, d0 - o1 + Vec3::splat(G3)),
+        (o2, d0 - o2 + Vec3::splat(2.0 * G3)),
+        (Vec3::ONE, d0 - Vec3::ONE + Vec3::splat(3.0 * G3)),
+    ] {
+        let t = 0.6 - d.length_squared();
+        if t > 0.0 {
+            let h = hash(
+                cx + offset.x as i32,
+                cy + offset.y as i32,
+                cz + offset.z as i32,
+                seed,
+            );
+            total += t * t * t * t * gradient(h).dot(d);
+        }
+    }
+    32.0 * total
+}
+
+// Distance to the nearest feature point, one jittered point per unit cell.
+pub fn worley(p: Vec3, seed: u32) -> f32 {
+    let base = p.floor();
+    let [x0, y0, z0] = [base.x as i32, base.y as i32, base.z as i32];
+    let mut nearest = f32::INFINITY;
+    for dz in -1..=1 {
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                let (x, y, z) = (x0 + dx, y0 + dy, z0 + dz);
+                let feature = Vec3::new(x as f32, y as f32, z as f32)
+                    + Vec3::new(
+                        hash_unit(x, y, z, seed),
+                        hash_unit(x, y, z, seed ^ 0x68E3_1DA4),
+                        hash_unit(x, y, z, seed ^ 0xB529_7A4D),
+                    );
+                nearest = nearest.min(feature.distance_squared(p));
+            }
+        }
+    }
+    nearest.sqrt()
+}
+
+fn gradient(hash: u32) -> Vec3 {
+    // The twelve cube edge directions used by improved Perlin noise.
+    const GRADIENTS: [[f32; 3]; 12] = [
+        [1.0, 1.0, 0.0],
+        [-1.0, 1.0, 0.0],
+        [1.0, -1.0, 0.0],
+        [-1.0, -1.0, 0.0],
+        [1.0, 0.0, 1.0],
+        [-1.0, 0.0, 1.0],
+        [1.0, 0.0, -1.0],
+        [-1.0, 0.0, -1.0],
+        [0.0, 1.0, 1.0],
+        [0.0, -1.0, 1.0],
+        [0.0, 1.0, -1.0],
+        [0.0, -1.0, -1.0],
+    ];
+    Vec3::from(GRADIENTS[(hash % 12) as usize])
+}
+
+fn lerp(a: f32, b: f32, t: f32) -> f32 {
+    a + (b - a) * t
+}
+
+fn hash(x: i32, y: i32, z: i32, seed: u32) -> u32 {
+    let mut h = x as u32;
+    h ^= (y as u32).wrapping_mul(374761393);
+    h = h.rotate_left(13);
+    h ^= (z as u32).wrapping_mul(668265263);
+    h = h.rotate_left(17);
+    h ^= seed.wrapping_mul(2246822519);
+    h = h.wrapping_mul(3266489917);
+    (h ^ (h >> 16)).wrapping_mul(2246822519)
+}
+
+fn hash_unit(x: i32, y: i32, z: i32, seed: u32) -> f32 {
+    (hash(x, y, z, seed) as f32) / (u32::MAX as f32)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bases_are_seeded_and_bounded() {
+        let points: Vec<[f32; 3]> = (0..200)
+            .map(|i| {
+                let t = i as f32 * 0.173;
+                [t.sin() * 4.0, t * 0.5, t.cos() * 3.0]
+            })
+            .collect();
+        for basis in [
+            NoiseBasis::Value,
+            NoiseBasis::Perlin,
+            NoiseBasis::Simplex,
+            NoiseBasis::Worley,
+        ] {
+            let fbm = Fbm {
+                basis,
+                octaves: 1,
+                ..Fbm::default()
+            };
+            let a = fbm.sample_points(&points, 7);
+            assert_eq!(a, fbm.sample_points(&points, 7));
+            assert_ne!(a, fbm.sample_points(&points, 8));
+            assert!(a.iter().all(|v| v.is_finite() && v.abs() <= 1.0 + 1.0e-4));
+        }
+        // Lattice points are zero for gradient noise.
+        assert_eq!(perlin(Vec3::new(2.0, -3.0, 5.0), 1), 0.0);
+    }
+
+    #[test]
+    fn curl_is_divergence_free() {
+        let fbm = Fbm {
+            basis: NoiseBasis::Perlin,
+            octaves: 1,
+            ..Fbm::default()
+        };
+        let p = Vec3::new(0.37, 1.21, -0.58);
+        let h = 2.0e-2;
+        let div = (fbm.curl(p + Vec3::X * h, 3).x - fbm.curl(p - Vec3::X * h, 3).x
+            + fbm.curl(p + Vec3::Y * h, 3).y
+            - fbm.curl(p - Vec3::Y * h, 3).y
+            + fbm.curl(p + Vec3::Z * h, 3).z
+            - fbm.curl(p - Vec3::Z * h, 3).z)
+            / (2.0 * h);
+        let magnitude = fbm.curl(p, 3).length();
+        assert!(div.abs() < 0.05 * magnitude.max(1.0), "div {}", div);
+    }
+}
@@ -0,0 +1,225 @@
+use crate::graph::{Graph, NodeId, ParamValue};
+use crate::nodes_builtin::{builtin_kind_from_name, default_params};
+use crate::param_expr::{evaluate_param_expression, node_by_reference_name, node_reference_name};
+
+// Resolves a node named on the command line or in a job: a node id, a reference name such
+// as `box2`, or a node type name when only one node of that type exists.
+pub fn find_node_by_name(graph: &Graph, name: &str) -> Result<NodeId, String> {
+    if let Ok(id) = name.parse::<u64>() {
+        return graph
+            .nodes()
+            .find(|node| node.id.as_u64() == id)
+            .map(|node| node.id)
+            .ok_or_else(|| format!("node {} not found", name));
+    }
+    let matches: Vec<NodeId> = graph
+        .nodes()
+        .filter(|node| node.name == name)
+        .map(|node| node.id)
+        .collect();
+    match matches.as_slice() {
+        [node_id] => Ok(*node_id),
+        [] => node_by_reference_name(graph, name).ok_or_else(|| format!("node {} not found", name)),
+        _ => Err(format!(
+            "{} matches {} nodes; use a reference name such as {} or a node id",
+            name,
+            matches.len(),
+            node_reference_name(graph, matches[0]).unwrap_or_default()
+        )),
+    }
+}
+
+// Applies a `Node.param=value` override. The value is parsed against the parameter's type:
+// strings are taken verbatim, everything else goes through the parameter expression parser,
+// with `1,2,3` accepted for vectors and true/false for toggles. Quaternions and matrices
+// take plain comma lists (x,y,z,w and 16 column-major values). Any expression on the
+// parameter is dropped so the override wins.
+pub fn apply_param_override(graph: &mut Graph, spec: &str) -> Result<(), String> {
+    let (target, text) = spec
+        .split_once('=')
+        .ok_or_else(|| format!("override '{}' must look like Node.param=value", spec))?;
+    let (node_name, key) = target
+        .rsplit_once('.')
+        .ok_or_else(|| format!("override '{}' must look like Node.param=value", spec))?;
+    let node_id = find_node_by_name(graph, node_name)?;
+    let node = graph
+        .node(node_id)
+        .ok_or_else(|| format!("node {} not found", node_name))?;
+    let current = node
+        .params
+        .values
+        .get(key)
+        .cloned()
+        .or_else(|| {
+            builtin_kind_from_name(&node.name)
+                .and_then(|kind| default_params(kind).values.get(key).cloned())
+        })
+        .ok_or_else(|| format!("{} has no parameter '{}'", node_name, key))?;
+
+    let value = match &current {
+        ParamValue::String(_) => ParamValue::String(text.to_string()),
+        // `;` rather than `,` so Windows paths and commas in tags survive.
+        ParamValue::StringList(_) => ParamValue::StringList(
+            text.split(';')
+                .map(str::trim)
+                .filter(|item| !item.is_empty())
+                .map(str::to_string)
+                .collect(),
+        ),
+        ParamValue::Vec2(_) if text.contains(',') && !text.contains('(') => {
+            evaluate_param_expression(&format!("vec2({})", text), &current, graph)?
+        }
+        ParamValue::Vec3(_) if text.contains(',') && !text.contains('(') => {
+            evaluate_param_expression(&format!("vec3({})", text), &current, graph)?
+        }
+        ParamValue::Quat(_) => ParamValue::Quat(parse_floats(text)?),
+        ParamValue::Mat4(_) => ParamValue::Mat4(parse_floats(text)?),
+        ParamValue::Bool(_) if matches!(text, "true" | "false") => ParamValue::Bool(text == "true"),
+        _ => evaluate_param_expression(text, &current, graph)?,
+    };
+    graph
+        .set_param_expression(node_id, key, None)
+        .and_then(|_| graph.set_param(node_id, key, value))
+        .map_err(|err| format!("{}.{}: {:?}", node_name, key, err))
+}
+
+fn parse_floats<const N: usize>(text: &str) -> Result<[f32; N], String> {
+    let values = text
+        .split(',')
+        .map(|part| part.trim().parse::<f32>())
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|err| format!("invalid number in '{}': {}", text, err))?;
+    values
+        .try_into()
+        .map_err(|values: Vec<f32>| format!("expected {} values, got {}", N, values.len()))
+}
+
+// Expands `${NAME}` in every string and string list parameter through `lookup`, usually the process
+// environment. Unknown names are an error so a misconfigured job fails instead of writing
+// to a literal `${...}` path. Returns the number of parameters changed.
+pub fn substitute_env_in_params(
+    graph: &mut Graph,
+    lookup: impl Fn(&str) -> Option<String>,
+) -> Result<usize, String> {
+    let mut updates = Vec::new();
+    for node in graph.nodes() {
+        for (key, value) in &node.params.values {
+            let expand = |text: &String| {
+                expand_env(text, &lookup).map_err(|err| format!("{}.{}: {}", node.name, key, err))
+            };
+            let expanded = match value {
+                ParamValue::String(text) if text.contains("${") => {
+                    ParamValue::String(expand(text)?)
+                }
+                ParamValue::StringList(items) if items.iter().any(|item| item.contains("${")) => {
+                    ParamValue::StringList(items.iter().map(expand).collect::<Result<_, _>>()?)
+                }
+                _ => continue,
+            };
+            updates.push((node.id, key.clone(), expanded));
+        }
+    }
+    let count = updates.len();
+    for (node_id, key, value) in updates {
+        graph
+            .set_param(node_id, key, value)
+            .map_err(|err| format!("{:?}", err))?;
+    }
+    Ok(count)
+}
+
+fn expand_env(text: &str, lookup: &impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while let Some(start) = rest.find("${") {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .ok_or_else(|| format!("unterminated '${{' in \"{}\"", text))?;
+        let name = &after[..end];
+        let value =
+            lookup(name).ok_or_else(|| format!("environment variable {} is not set", name))?;
+        out.push_str(&value);
+        rest = &after[end + 1..];
+    }
+    out.push_str(rest);
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn overrides_and_env_substitution() {
+        let mut graph = Graph::default();
+        let node = graph.add_node(node_definition(BuiltinNodeKind::ObjOutput));
+        let cube = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        graph
+            .set_param(
+                node,
+                "path",
+                ParamValue::String("${OUT}/${NAME}.obj".to_string()),
+            )
+            .unwrap();
+
+        apply_param_override(&mut graph, "Box.size=2,3,4").unwrap();
+        apply_param_override(&mut graph, "Box.center=vec3(1, 1, 1)").unwrap();
+        let params = &graph.node(cube).unwrap().params;
+        assert_eq!(params.get_vec3("size", [0.0; 3]), [2.0, 3.0, 4.0]);
+        assert_eq!(params.get_vec3("center", [0.0; 3]), [1.0, 1.0, 1.0]);
+        assert!(apply_param_override(&mut graph, "Box.missing=1").is_err());
+
+        let copy = graph.add_node(node_definition(BuiltinNodeKind::CopyToPoints));
+        apply_param_override(&mut graph, "Copy to Points.orient=0, 0.6, 0, 0.8").unwrap();
+        let params = &graph.node(copy).unwrap().params;
+        assert_eq!(params.get_quat("orient", [0.0; 4]), [0.0, 0.6, 0.0, 0.8]);
+        assert!(apply_param_override(&mut graph, "Copy to Points.orient=1,2").is_err());
+        assert!(apply_param_override(&mut graph, "Sphere.radius=1").is_err());
+
+        let lookup = |name: &str| match name {
+            "OUT" => Some("/tmp/jobs".to_string()),
+            "NAME" => Some("a".to_string()),
+            _ => None,
+        };
+        assert_eq!(substitute_env_in_params(&mut graph, lookup), Ok(1));
+        let params = &graph.node(node).unwrap().params;
+        assert_eq!(params.get_string("path", ""), "/tmp/jobs/a.obj");
+
+        apply_param_override(&mut graph, "OBJ Output.path=${MISSING}").unwrap();
+        assert!(substitute_env_in_params(&mut graph, lookup).is_err());
+    }
+
+    #[test]
+    fn overrides_need_unambiguous_node_names() {
+        let mut graph = Graph::default();
+        let first = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let second = graph.add_node(node_definition(BuiltinNodeKind::Box));
+
+        let err = apply_param_override(&mut graph, "Box.size=2,2,2").unwrap_err();
+        assert!(err.contains("matches 2 nodes"), "{err}");
+        apply_param_override(&mut graph, "box2.size=2,3,4").unwrap();
+        apply_param_override(&mut graph, &format!("{}.size=5,5,5", first.as_u64())).unwrap();
+        let size = |node| graph.node(node).unwrap().params.get_vec3("size", [0.0; 3]);
+        assert_eq!(size(first), [5.0, 5.0, 5.0]);
+        assert_eq!(size(second), [2.0, 3.0, 4.0]);
+        assert!(apply_param_override(&mut graph, "box3.size=1").is_err());
+        assert!(apply_param_override(&mut graph, "99.size=1").is_err());
+    }
+
+    #[test]
+    fn string_list_params_split_and_expand() {
+        let mut graph = Graph::default();
+        let node = graph.add_node(node_definition(BuiltinNodeKind::MergeFiles));
+        apply_param_override(&mut graph, r"Merge Files.paths=${OUT}\a.obj; b.obj;").unwrap();
+        let lookup = |name: &str| (name == "OUT").then(|| r"C:\geo".to_string());
+        assert_eq!(substitute_env_in_params(&mut graph, lookup), Ok(1));
+        let params = &graph.node(node).unwrap().params;
+        assert_eq!(
+            params.get_string_list("paths"),
+            [r"C:\geo\a.obj".to_string(), "b.obj".to_string()]
+        );
+    }
+}
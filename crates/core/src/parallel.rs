@@ -0,0 +1,80 @@
+// Per-element helpers for attribute arrays. Large arrays are split across the rayon pool on
+// native builds; small arrays and wasm builds run inline, where threading costs more than it
+// saves.
+
+#[cfg(not(target_arch = "wasm32"))]
+use rayon::prelude::*;
+
+pub const PARALLEL_MIN_LEN: usize = 16 * 1024;
+
+pub fn par_for_each_mut<T, F>(values: &mut [T], f: F)
+where
+    T: Send,
+    F: Fn(&mut T) + Sync + Send,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    if values.len() >= PARALLEL_MIN_LEN {
+        values
+            .par_iter_mut()
+            .with_min_len(PARALLEL_MIN_LEN / 4)
+            .for_each(f);
+        return;
+    }
+    values.iter_mut().for_each(f);
+}
+
+pub fn par_zip_mut<T, U, F>(values: &mut [T], other: &[U], f: F)
+where
+    T: Send,
+    U: Sync,
+    F: Fn(&mut T, &U) + Sync + Send,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    if values.len() >= PARALLEL_MIN_LEN {
+        values
+            .par_iter_mut()
+            .zip(other.par_iter())
+            .with_min_len(PARALLEL_MIN_LEN / 4)
+            .for_each(|(value, other)| f(value, other));
+        return;
+    }
+    for (value, other) in values.iter_mut().zip(other) {
+        f(value, other);
+    }
+}
+
+pub fn par_map<T, U, F>(values: &[T], f: F) -> Vec<U>
+where
+    T: Sync,
+    U: Send,
+    F: Fn(&T) -> U + Sync + Send,
+{
+    #[cfg(not(target_arch = "wasm32"))]
+    if values.len() >= PARALLEL_MIN_LEN {
+        return values
+            .par_iter()
+            .with_min_len(PARALLEL_MIN_LEN / 4)
+            .map(f)
+            .collect();
+    }
+    values.iter().map(f).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parallel_and_inline_paths_agree() {
+        for len in [10, PARALLEL_MIN_LEN * 3 + 7] {
+            let values: Vec<u32> = (0..len as u32).collect();
+            let doubled = par_map(&values, |v| v * 2);
+            assert!(doubled.iter().enumerate().all(|(i, v)| *v == i as u32 * 2));
+
+            let mut sums = values.clone();
+            par_zip_mut(&mut sums, &doubled, |a, b| *a += b);
+            par_for_each_mut(&mut sums, |v| *v += 1);
+            assert!(sums.iter().enumerate().all(|(i, v)| *v == i as u32 * 3 + 1));
+        }
+    }
+}
@@ -0,0 +1,551 @@
+use std::collections::{BTreeMap, BTreeSet};
+use std::hash::{Hash, Hasher};
+
+use crate::graph::{Graph, Node, NodeId, NodeParams, ParamValue};
+use crate::keyframes::{FRAME_PARAM, TIME_PARAM};
+use crate::nodes_builtin::{builtin_kind_from_name, BuiltinNodeKind};
+use crate::seeds::SEED_PARAM;
+use crate::symmetry::{MIRROR_AXIS_PARAM, MIRROR_OFFSET_PARAM, MIRROR_PARAM};
+use crate::units::UNIT_SCALE_PARAM;
+use crate::wrangle::{
+    parse_expression, reads_time as wrangle_reads_time, swizzle_value, ExprContext, Value,
+};
+
+struct ParamExprContext<'a> {
+    graph: &'a Graph,
+    stack: &'a mut Vec<(NodeId, String)>,
+}
+
+impl ExprContext for ParamExprContext<'_> {
+    fn read_attr(&mut self, name: &str, _idx: usize) -> Result<Value, String> {
+        Err(format!(
+            "Attribute '@{}' is not available in parameter expressions",
+            name
+        ))
+    }
+
+    fn read_var(&mut self, name: &str) -> Result<Value, String> {
+        // Project parameters win so older projects that defined `F` or `T` keep working.
+        let Some(value) = self.graph.project_param(name) else {
+            return match name {
+                "F" => Ok(Value::Float(self.graph.time().frame)),
+                "T" => Ok(Value::Float(self.graph.time().seconds())),
+                _ => Err(format!("Unknown project parameter '${}'", name)),
+            };
+        };
+        value_from_param(value)
+            .ok_or_else(|| format!("Project parameter '${}' is not numeric", name))
+    }
+
+    fn read_channel(&mut self, path: &str) -> Result<Value, String> {
+        let (node_id, key, components) = parse_reference_path(self.graph, path)
+            .ok_or_else(|| format!("Invalid parameter reference \"{}\"", path))?;
+        let value = resolve_param_value(self.graph, node_id, &key, self.stack)?;
+        let value = value_from_param(&value)
+            .ok_or_else(|| format!("Referenced parameter \"{}\" is not numeric", path))?;
+        match components {
+            Some(mask) => swizzle_value(value, &mask),
+            None => Ok(value),
+        }
+    }
+}
+
+pub fn evaluate_param_expression(
+    expression: &str,
+    target: &ParamValue,
+    graph: &Graph,
+) -> Result<ParamValue, String> {
+    let mut stack = Vec::new();
+    evaluate_with_stack(expression, target, graph, &mut stack)
+}
+
+pub fn param_reference_expression(node_id: NodeId, key: &str) -> String {
+    format!("ch(\"../{}/{}\")", node_id.as_u64(), key)
+}
+
+pub fn param_reference_target(graph: &Graph, expression: &str) -> Option<(NodeId, String)> {
+    let path = expression
+        .trim()
+        .strip_prefix("ch(\"")?
+        .strip_suffix("\")")?;
+    parse_reference_path(graph, path).map(|(node_id, key, _)| (node_id, key))
+}
+
+// The name a hand-written `ch()` path can use instead of the node id: the lowercased kind
+// plus its 1-based index among nodes of that kind, e.g. `box1`. Indices follow node ids;
+// removing a node rewrites paths that name the nodes after it, see `retarget_references`.
+pub fn node_reference_name(graph: &Graph, node_id: NodeId) -> Option<String> {
+    let stem = reference_stem(&graph.node(node_id)?.name);
+    let index = graph
+        .nodes()
+        .filter(|other| other.id <= node_id && reference_stem(&other.name) == stem)
+        .count();
+    Some(format!("{}{}", stem, index))
+}
+
+// Finds the node a reference name such as `box2` currently points at.
+pub(crate) fn node_by_reference_name(graph: &Graph, name: &str) -> Option<NodeId> {
+    let stem = name.trim_end_matches(|c: char| c.is_ascii_digit());
+    let index = name[stem.len()..].parse::<usize>().ok()?;
+    graph
+        .nodes()
+        .filter(|other| reference_stem(&other.name) == stem)
+        .nth(index.checked_sub(1)?)
+        .map(|node| node.id)
+}
+
+// A `ch()` path that names its node by reference name, with the node it points at now.
+pub(crate) struct NamedReference {
+    node: NodeId,
+    key: String,
+    path: String,
+    target: NodeId,
+}
+
+// Every name-based reference in the graph's expressions, for `retarget_references`.
+pub(crate) fn named_references(graph: &Graph) -> Vec<NamedReference> {
+    let mut found = Vec::new();
+    for node in graph.nodes() {
+        for (key, expression) in &node.param_expressions {
+            let Ok(expr) = parse_expression(expression) else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            expr.channels(&mut paths);
+            for path in paths {
+                let name = path.strip_prefix("../").unwrap_or(&path).split('/').next();
+                if name.is_some_and(|name| name.parse::<u64>().is_err()) {
+                    if let Some((target, _, _)) = parse_reference_path(graph, &path) {
+                        found.push(NamedReference {
+                            node: node.id,
+                            key: key.clone(),
+                            path,
+                            target,
+                        });
+                    }
+                }
+            }
+        }
+    }
+    found
+}
+
+// Reference names are positional, so removing or retyping a node renumbers others of the
+// same type. Rewrites each path from `named_references` to its target's current name, or to
+// its id once the target is gone so the expression reports it instead of moving on.
+pub(crate) fn retarget_references(graph: &mut Graph, references: Vec<NamedReference>) {
+    for reference in references {
+        let name = match node_reference_name(graph, reference.target) {
+            Some(name) => name,
+            None => reference.target.as_u64().to_string(),
+        };
+        let (prefix, rest) = match reference.path.strip_prefix("../") {
+            Some(rest) => ("../", rest),
+            None => ("", reference.path.as_str()),
+        };
+        let Some((_, tail)) = rest.split_once('/') else {
+            continue;
+        };
+        let path = format!("{}{}/{}", prefix, name, tail);
+        if path == reference.path {
+            continue;
+        }
+        let Some(expression) = graph
+            .node(reference.node)
+            .and_then(|node| node.param_expressions.get(&reference.key))
+        else {
+            continue;
+        };
+        let expression =
+            expression.replace(&format!("\"{}\"", reference.path), &format!("\"{}\"", path));
+        let _ = graph.set_param_expression(reference.node, &reference.key, Some(expression));
+    }
+}
+
+// Nodes whose parameters feed this node's expressions, following references of references.
+pub fn param_dependencies(graph: &Graph, node_id: NodeId) -> BTreeSet<NodeId> {
+    let mut found = BTreeSet::new();
+    let mut pending = vec![node_id];
+    while let Some(current) = pending.pop() {
+        let Some(node) = graph.node(current) else {
+            continue;
+        };
+        for expression in node.param_expressions.values() {
+            let Ok(expr) = parse_expression(expression) else {
+                continue;
+            };
+            let mut paths = Vec::new();
+            expr.channels(&mut paths);
+            for path in paths {
+                let Some((target, _, _)) = parse_reference_path(graph, &path) else {
+                    continue;
+                };
+                if target != node_id && found.insert(target) {
+                    pending.push(target);
+                }
+            }
+        }
+    }
+    found
+}
+
+pub fn param_value_expression(value: &ParamValue) -> Option<String> {
+    match value {
+        ParamValue::Float(v) => Some(format!("{}", v)),
+        ParamValue::Int(v) => Some(format!("{}", v)),
+        ParamValue::Bool(v) => Some(if *v { "1" } else { "0" }.to_string()),
+        ParamValue::Vec2(v) => Some(format!("vec2({}, {})", v[0], v[1])),
+        ParamValue::Vec3(v) => Some(format!("vec3({}, {}, {})", v[0], v[1], v[2])),
+        ParamValue::String(_)
+        | ParamValue::Quat(_)
+        | ParamValue::Mat4(_)
+        | ParamValue::StringList(_) => None,
+    }
+}
+
+pub(crate) fn resolve_params(graph: &Graph, node: &Node) -> Result<NodeParams, String> {
+    let mut resolved = node.params.clone();
+    for key in node.param_expressions.keys() {
+        if !node.params.values.contains_key(key) {
+            continue;
+        }
+        let mut stack = Vec::new();
+        let value = resolve_param_value(graph, node.id, key, &mut stack)
+            .map_err(|err| format!("{}: {}", key, err))?;
+        resolved.values.insert(key.clone(), value);
+    }
+    if builtin_kind_from_name(&node.name) == Some(BuiltinNodeKind::Wrangle) && reads_time(node) {
+        let time = graph.time();
+        resolved
+            .values
+            .insert(FRAME_PARAM.to_string(), ParamValue::Float(time.frame));
+        resolved
+            .values
+            .insert(TIME_PARAM.to_string(), ParamValue::Float(time.seconds()));
+    }
+    if builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::uses_unit_scale) {
+        resolved.values.insert(
+            UNIT_SCALE_PARAM.to_string(),
+            ParamValue::Float(graph.units().import_scale()),
+        );
+    }
+    if builtin_kind_from_name(&node.name).is_some_and(BuiltinNodeKind::is_stochastic) {
+        let seed = resolved.get_int(SEED_PARAM, 1);
+        resolved.values.insert(
+            SEED_PARAM.to_string(),
+            ParamValue::Int(graph.seeds().node_seed(node.id, seed)),
+        );
+    }
+    if let Some(plane) = graph.symmetry() {
+        if resolved.get_bool(MIRROR_PARAM, false) {
+            resolved.values.insert(
+                MIRROR_AXIS_PARAM.to_string(),
+                ParamValue::Int(plane.axis.index() as i32),
+            );
+            resolved.values.insert(
+                MIRROR_OFFSET_PARAM.to_string(),
+                ParamValue::Float(plane.offset),
+            );
+        }
+    }
+    Ok(resolved)
+}
+
+// Whether the node follows the timeline through `$F` or `$T` in an expression, its Wrangle
+// code, or anything inside it when it is a subnet.
+pub(crate) fn reads_time(node: &Node) -> bool {
+    let code_reads_time = builtin_kind_from_name(&node.name) == Some(BuiltinNodeKind::Wrangle)
+        && wrangle_reads_time(node.params.get_string("code", ""));
+    code_reads_time
+        || node
+            .param_expressions
+            .values()
+            .any(|expression| wrangle_reads_time(expression))
+        || node
+            .subnet
+            .as_deref()
+            .is_some_and(|child| child.nodes().any(reads_time))
+}
+
+fn resolve_param_value(
+    graph: &Graph,
+    node_id: NodeId,
+    key: &str,
+    stack: &mut Vec<(NodeId, String)>,
+) -> Result<ParamValue, String> {
+    let node = graph
+        .node(node_id)
+        .ok_or_else(|| format!("Referenced node {:?} does not exist", node_id))?;
+    let value = node
+        .params
+        .values
+        .get(key)
+        .ok_or_else(|| format!("{} has no parameter '{}'", node.name, key))?;
+    let Some(expression) = node.param_expressions.get(key) else {
+        return Ok(value.clone());
+    };
+    if stack.iter().any(|(id, name)| *id == node_id && name == key) {
+        return Err(format!("Reference cycle through {}.{}", node.name, key));
+    }
+    stack.push((node_id, key.to_string()));
+    let result = evaluate_with_stack(expression, value, graph, stack);
+    stack.pop();
+    result
+}
+
+fn evaluate_with_stack(
+    expression: &str,
+    target: &ParamValue,
+    graph: &Graph,
+    stack: &mut Vec<(NodeId, String)>,
+) -> Result<ParamValue, String> {
+    let expr = parse_expression(expression)?;
+    let mut ctx = ParamExprContext { graph, stack };
+    let value = ctx.eval_expr(&expr, 0)?;
+    param_from_value(value, target)
+}
+
+// `../<node>/<param>[.<components>]`, where the node is an id or a reference name and the
+// optional components swizzle a vector parameter, e.g. `../box1/size.x`.
+fn parse_reference_path(graph: &Graph, path: &str) -> Option<(NodeId, String, Option<String>)> {
+    let path = path.strip_prefix("../").unwrap_or(path);
+    let (node, key) = path.split_once('/')?;
+    let node_id = match node.parse::<u64>() {
+        Ok(id) => graph.nodes().find(|node| node.id.as_u64() == id)?.id,
+        Err(_) => node_by_reference_name(graph, node)?,
+    };
+    match key.split_once('.') {
+        Some((key, mask)) => Some((node_id, key.to_string(), Some(mask.to_string()))),
+        None => Some((node_id, key.to_string(), None)),
+    }
+}
+
+fn reference_stem(name: &str) -> String {
+    name.chars()
+        .filter(char::is_ascii_alphanumeric)
+        .map(|c| c.to_ascii_lowercase())
+        .collect()
+}
+
+pub(crate) fn expression_signature(
+    resolved: &NodeParams,
+    expressions: &BTreeMap<String, String>,
+) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    for key in expressions.keys() {
+        key.hash(&mut hasher);
+        match resolved.values.get(key) {
+            Some(ParamValue::Float(v)) => v.to_bits().hash(&mut hasher),
+            Some(ParamValue::Int(v)) => v.hash(&mut hasher),
+            Some(ParamValue::Bool(v)) => v.hash(&mut hasher),
+            Some(ParamValue::Vec2(v)) => v.map(f32::to_bits).hash(&mut hasher),
+            Some(ParamValue::Vec3(v)) => v.map(f32::to_bits).hash(&mut hasher),
+            Some(ParamValue::String(v)) => v.hash(&mut hasher),
+            Some(ParamValue::Quat(v)) => v.map(f32::to_bits).hash(&mut hasher),
+            Some(ParamValue::Mat4(v)) => v.map(f32::to_bits).hash(&mut hasher),
+            Some(ParamValue::StringList(v)) => v.hash(&mut hasher),
+            None => {}
+        }
+    }
+    hasher.finish()
+}
+
+fn value_from_param(value: &ParamValue) -> Option<Value> {
+    match value {
+        ParamValue::Float(v) => Some(Value::Float(*v)),
+        ParamValue::Int(v) => Some(Value::Float(*v as f32)),
+        ParamValue::Bool(v) => Some(Value::Float(if *v { 1.0 } else { 0.0 })),
+        ParamValue::Vec2(v) => Some(Value::Vec2(*v)),
+        ParamValue::Vec3(v) => Some(Value::Vec3(*v)),
+        ParamValue::String(_)
+        | ParamValue::Quat(_)
+        | ParamValue::Mat4(_)
+        | ParamValue::StringList(_) => None,
+    }
+}
+
+fn param_from_value(value: Value, target: &ParamValue) -> Result<ParamValue, String> {
+    match (target, value) {
+        (ParamValue::Float(_), Value::Float(v)) => Ok(ParamValue::Float(v)),
+        (ParamValue::Int(_), Value::Float(v)) => Ok(ParamValue::Int(v.round() as i32)),
+        (ParamValue::Bool(_), Value::Float(v)) => Ok(ParamValue::Bool(v != 0.0)),
+        (ParamValue::Vec2(_), Value::Float(v)) => Ok(ParamValue::Vec2([v; 2])),
+        (ParamValue::Vec2(_), Value::Vec2(v)) => Ok(ParamValue::Vec2(v)),
+        (ParamValue::Vec3(_), Value::Float(v)) => Ok(ParamValue::Vec3([v; 3])),
+        (ParamValue::Vec3(_), Value::Vec3(v)) => Ok(ParamValue::Vec3(v)),
+        (ParamValue::String(_) | ParamValue::StringList(_), _) => {
+            Err("string parameters do not support expressions".to_string())
+        }
+        (ParamValue::Quat(_) | ParamValue::Mat4(_), _) => {
+            Err("quaternion and matrix parameters do not support expressions".to_string())
+        }
+        _ => Err("expression result does not match the parameter type".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{NodeDefinition, PinDefinition, PinType};
+
+    fn param_node(graph: &mut Graph, name: &str) -> NodeId {
+        graph.add_node(NodeDefinition {
+            name: name.to_string(),
+            category: "Test".to_string(),
+            inputs: Vec::new(),
+            outputs: vec![PinDefinition {
+                name: "out".to_string(),
+                pin_type: PinType::Mesh,
+            }],
+            variadic_inputs: None,
+            help: None,
+            self_tests: Vec::new(),
+        })
+    }
+
+    #[test]
+    fn expressions_read_project_params() {
+        let mut graph = Graph::default();
+        graph.set_project_param("size", ParamValue::Float(2.0));
+        graph.set_project_param("offset", ParamValue::Vec3([1.0, 2.0, 3.0]));
+        let value =
+            evaluate_param_expression("$size * 0.5 + 1", &ParamValue::Float(0.0), &graph).unwrap();
+        assert_eq!(value, ParamValue::Float(2.0));
+        let value =
+            evaluate_param_expression("$offset * $size", &ParamValue::Vec3([0.0; 3]), &graph)
+                .unwrap();
+        assert_eq!(value, ParamValue::Vec3([2.0, 4.0, 6.0]));
+        let value =
+            evaluate_param_expression("$offset.y + 0.6", &ParamValue::Int(0), &graph).unwrap();
+        assert_eq!(value, ParamValue::Int(3));
+        assert!(evaluate_param_expression("$missing", &ParamValue::Float(0.0), &graph).is_err());
+    }
+
+    #[test]
+    fn literal_expressions_round_trip() {
+        let value = ParamValue::Vec3([1.5, -2.0, 0.25]);
+        let expression = param_value_expression(&value).unwrap();
+        let resolved = evaluate_param_expression(&expression, &value, &Graph::default()).unwrap();
+        assert_eq!(resolved, value);
+    }
+
+    #[test]
+    fn references_follow_other_nodes_and_detect_cycles() {
+        let mut graph = Graph::default();
+        let a = param_node(&mut graph, "A");
+        let b = param_node(&mut graph, "B");
+        graph.set_param(a, "size", ParamValue::Float(4.0)).unwrap();
+        graph.set_param(b, "size", ParamValue::Float(1.0)).unwrap();
+        let reference = param_reference_expression(a, "size");
+        assert_eq!(
+            param_reference_target(&graph, &reference),
+            Some((a, "size".to_string()))
+        );
+        graph
+            .set_param_expression(b, "size", Some(format!("{} * 0.5", reference)))
+            .unwrap();
+        let resolved = resolve_params(&graph, graph.node(b).unwrap()).unwrap();
+        assert_eq!(resolved.get_float("size", 0.0), 2.0);
+
+        graph
+            .set_param_expression(a, "size", Some(param_reference_expression(b, "size")))
+            .unwrap();
+        assert!(resolve_params(&graph, graph.node(b).unwrap()).is_err());
+    }
+
+    #[test]
+    fn named_references_read_components_and_track_dependencies() {
+        let mut graph = Graph::default();
+        let first = param_node(&mut graph, "Box");
+        let second = param_node(&mut graph, "Box");
+        let sphere = param_node(&mut graph, "Sphere");
+        let target = param_node(&mut graph, "Target");
+        graph
+            .set_param(second, "size", ParamValue::Vec3([1.0, 3.0, 5.0]))
+            .unwrap();
+        graph
+            .set_param(sphere, "radius", ParamValue::Float(0.5))
+            .unwrap();
+        graph
+            .set_param(target, "scale", ParamValue::Vec2([0.0, 0.0]))
+            .unwrap();
+        assert_eq!(node_reference_name(&graph, first).as_deref(), Some("box1"));
+        assert_eq!(node_reference_name(&graph, second).as_deref(), Some("box2"));
+
+        graph
+            .set_param_expression(
+                target,
+                "scale",
+                Some(r#"ch("../box2/size.zx") * ch("../sphere1/radius")"#.to_string()),
+            )
+            .unwrap();
+        let resolved = resolve_params(&graph, graph.node(target).unwrap()).unwrap();
+        assert_eq!(resolved.get_vec2("scale", [0.0; 2]), [2.5, 0.5]);
+        assert_eq!(
+            param_dependencies(&graph, target),
+            BTreeSet::from([second, sphere])
+        );
+
+        // Dependencies are followed through the referenced node's own expressions.
+        graph
+            .set_param_expression(
+                sphere,
+                "radius",
+                Some(r#"ch("../box1/size.y")"#.to_string()),
+            )
+            .unwrap();
+        assert_eq!(
+            param_dependencies(&graph, target),
+            BTreeSet::from([first, second, sphere])
+        );
+        assert!(graph
+            .set_param_expression(target, "scale", Some(r#"ch("../box9/size")"#.to_string()))
+            .is_ok());
+        assert!(resolve_params(&graph, graph.node(target).unwrap()).is_err());
+    }
+
+    #[test]
+    fn removing_a_node_keeps_named_references_on_their_targets() {
+        let mut graph = Graph::default();
+        let first = param_node(&mut graph, "Block");
+        let second = param_node(&mut graph, "Block");
+        let third = param_node(&mut graph, "Block");
+        let target = param_node(&mut graph, "Target");
+        for (node, size) in [(first, 1.0), (second, 2.0), (third, 3.0)] {
+            graph
+                .set_param(node, "size", ParamValue::Float(size))
+                .unwrap();
+        }
+        graph
+            .set_param(target, "a", ParamValue::Float(0.0))
+            .unwrap();
+        graph
+            .set_param(target, "b", ParamValue::Float(0.0))
+            .unwrap();
+        graph
+            .set_param_expression(
+                target,
+                "a",
+                Some(r#"ch("../block2/size") + ch("block3/size")"#.to_string()),
+            )
+            .unwrap();
+        graph
+            .set_param_expression(target, "b", Some(r#"ch("../block1/size")"#.to_string()))
+            .unwrap();
+
+        assert!(graph.remove_node(first));
+        let node = graph.node(target).unwrap();
+        assert_eq!(
+            node.param_expressions["a"],
+            r#"ch("../block1/size") + ch("block2/size")"#
+        );
+        assert_eq!(
+            node.param_expressions["b"],
+            format!(r#"ch("../{}/size")"#, first.as_u64())
+        );
+        assert!(resolve_params(&graph, node).is_err());
+        graph.set_param_expression(target, "b", None).unwrap();
+        let resolved = resolve_params(&graph, graph.node(target).unwrap()).unwrap();
+        assert_eq!(resolved.get_float("a", 0.0), 5.0);
+    }
+}
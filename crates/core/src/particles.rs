@@ -0,0 +1,180 @@
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+use crate::content_hash::mesh_content_hash;
+use crate::frame_cache::{frame_independent_key, FrameCache};
+use crate::graph::{Node, NodeParams};
+use crate::mesh::Mesh;
+use crate::noise::Fbm;
+
+#[derive(Debug, Clone)]
+pub struct ParticleSettings {
+    pub start_frame: i32,
+    pub fps: f32,
+    pub velocity_attr: String,
+    pub gravity: [f32; 3],
+    pub drag: f32,
+    pub noise: f32,
+    pub frequency: f32,
+    pub seed: u32,
+}
+
+impl ParticleSettings {
+    pub(crate) fn from_params(params: &NodeParams) -> Self {
+        Self {
+            start_frame: params.get_int("start_frame", 1),
+            fps: params.get_float("fps", 24.0).max(1.0),
+            velocity_attr: params.get_string("attr", "v").to_string(),
+            gravity: params.get_vec3("gravity", [0.0, -9.8, 0.0]),
+            drag: params.get_float("drag", 0.0).max(0.0),
+            noise: params.get_float("noise", 0.5),
+            frequency: params.get_float("frequency", 1.0).max(0.0),
+            seed: params.get_int("seed", 1) as u32,
+        }
+    }
+}
+
+// Frames step from one another, so the input, read once at `start_frame`, is part of the key.
+pub(crate) fn particle_cache_key(input: &Mesh, node: &Node, params: &NodeParams) -> u64 {
+    frame_independent_key(node, params) ^ mesh_content_hash(input).rotate_left(1)
+}
+
+// Steps the points to `frame` with gravity, drag and curl noise from the latest cached frame.
+pub(crate) fn simulate_particles(
+    cache: &mut FrameCache,
+    input: &Mesh,
+    settings: &ParticleSettings,
+    frame: i32,
+) -> Result<Mesh, String> {
+    if frame <= settings.start_frame {
+        return initial_state(input, settings);
+    }
+    if let Some(mut outputs) = cache.get(frame, 0) {
+        return Ok(outputs.swap_remove(0));
+    }
+    let (mut current, mut mesh) = match cache.latest_before(settings.start_frame, frame) {
+        Some((cached, mesh)) => (cached, mesh.clone()),
+        None => (settings.start_frame, initial_state(input, settings)?),
+    };
+    let dt = 1.0 / settings.fps.max(1.0);
+    let fbm = Fbm::default();
+    while current < frame {
+        current += 1;
+        step(&mut mesh, settings, &fbm, dt, current);
+        cache.insert(current, 0, vec![mesh.clone()]);
+    }
+    Ok(mesh)
+}
+
+fn initial_state(input: &Mesh, settings: &ParticleSettings) -> Result<Mesh, String> {
+    let velocities = match input.attribute(AttributeDomain::Point, &settings.velocity_attr) {
+        Some(AttributeRef::Vec3(values)) => values.to_vec(),
+        Some(_) => {
+            return Err(format!(
+                "Particle Sim: '{}' must be a point vector attribute",
+                settings.velocity_attr
+            ))
+        }
+        None => vec![[0.0; 3]; input.positions.len()],
+    };
+    let mut mesh = input.clone();
+    mesh.set_attribute(
+        AttributeDomain::Point,
+        &settings.velocity_attr,
+        AttributeStorage::Vec3(velocities),
+    )
+    .map_err(|err| format!("Particle Sim: {:?}", err))?;
+    Ok(mesh)
+}
+
+fn step(mesh: &mut Mesh, settings: &ParticleSettings, fbm: &Fbm, dt: f32, frame: i32) {
+    let mut velocities = match mesh.attribute(AttributeDomain::Point, &settings.velocity_attr) {
+        Some(AttributeRef::Vec3(values)) => values.to_vec(),
+        _ => vec![[0.0; 3]; mesh.positions.len()],
+    };
+    let gravity = Vec3::from(settings.gravity);
+    let damping = (1.0 - settings.drag * dt).max(0.0);
+    // The field drifts slowly so particles sharing a path do not all follow it forever.
+    let drift = Vec3::splat(frame as f32 * dt * 0.25);
+    for (position, velocity) in mesh.positions.iter_mut().zip(&mut velocities) {
+        let p = Vec3::from(*position);
+        let v = (Vec3::from(*velocity) + gravity * dt) * damping;
+        let swirl = if settings.noise != 0.0 {
+            fbm.curl(p * settings.frequency + drift, settings.seed) * settings.noise
+        } else {
+            Vec3::ZERO
+        };
+        *position = (p + (v + swirl) * dt).to_array();
+        *velocity = v.to_array();
+    }
+    let _ = mesh.set_attribute(
+        AttributeDomain::Point,
+        &settings.velocity_attr,
+        AttributeStorage::Vec3(velocities),
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Graph, ParamValue};
+    use crate::keyframes::FRAME_PARAM;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    fn settings() -> ParticleSettings {
+        ParticleSettings {
+            start_frame: 1,
+            fps: 10.0,
+            velocity_attr: "v".to_string(),
+            gravity: [0.0, 0.0, 0.0],
+            drag: 0.0,
+            noise: 0.0,
+            frequency: 1.0,
+            seed: 1,
+        }
+    }
+
+    #[test]
+    fn particles_advect_and_reuse_cached_frames() {
+        let mut input = Mesh::with_positions_indices(vec![[0.0, 0.0, 0.0]], Vec::new());
+        input
+            .set_attribute(
+                AttributeDomain::Point,
+                "v",
+                AttributeStorage::Vec3(vec![[1.0, 0.0, 0.0]]),
+            )
+            .unwrap();
+        let mut graph = Graph::default();
+        let sim = graph.add_node(node_definition(BuiltinNodeKind::ParticleSim));
+        let node = graph.node(sim).unwrap();
+        let params = NodeParams::default();
+        let mut cache = FrameCache::default();
+        cache.sync(particle_cache_key(&input, node, &params));
+
+        let mesh = simulate_particles(&mut cache, &input, &settings(), 11).unwrap();
+        assert!((mesh.positions[0][0] - 1.0).abs() < 1.0e-5);
+        assert_eq!(cache.frames().len(), 10);
+
+        // Going back reads the cache; going forward continues from the last cached frame.
+        let earlier = simulate_particles(&mut cache, &input, &settings(), 6).unwrap();
+        assert!((earlier.positions[0][0] - 0.5).abs() < 1.0e-5);
+        simulate_particles(&mut cache, &input, &settings(), 13).unwrap();
+        assert_eq!(cache.frames().len(), 12);
+
+        // The frame itself is not part of the key; other params and the input are.
+        let mut params = NodeParams::default();
+        params
+            .values
+            .insert(FRAME_PARAM.to_string(), ParamValue::Float(40.0));
+        cache.sync(particle_cache_key(&input, node, &params));
+        assert_eq!(cache.frames().len(), 12);
+        params
+            .values
+            .insert("drag".to_string(), ParamValue::Float(1.0));
+        cache.sync(particle_cache_key(&input, node, &params));
+        assert!(cache.frames().is_empty());
+
+        let start = simulate_particles(&mut cache, &input, &settings(), 0).unwrap();
+        assert_eq!(start.positions[0], [0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,184 @@
+use std::collections::HashMap;
+
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone)]
+pub struct PolyExtrudeSettings {
+    pub distance: f32,
+    pub inset: f32,
+    pub individual: bool,
+    pub group: String,
+    pub side_group: String,
+}
+
+pub fn poly_extrude(input: &Mesh, settings: &PolyExtrudeSettings) -> Result<Mesh, String> {
+    if !input.has_valid_faces() {
+        return Err("PolyExtrude requires a polygon mesh input".to_string());
+    }
+    if input
+        .indices
+        .iter()
+        .any(|idx| *idx as usize >= input.positions.len())
+    {
+        return Err("PolyExtrude input has out of range indices".to_string());
+    }
+    let faces = input.face_ranges();
+    let face_count = faces.len();
+
+    let selected = if settings.group.trim().is_empty() {
+        vec![true; face_count]
+    } else {
+        match input.group_mask(AttributeDomain::Primitive, settings.group.trim()) {
+            Some(mask) => mask,
+            None => {
+                tracing::warn!(
+                    "PolyExtrude: primitive group '{}' not found; passing input through",
+                    settings.group
+                );
+                return Ok(input.clone());
+            }
+        }
+    };
+
+    let regions: Vec<Vec<usize>> = if settings.individual {
+        (0..face_count)
+            .filter(|face| selected[*face])
+            .map(|face| vec![face])
+            .collect()
+    } else {
+        let picked: Vec<usize> = (0..face_count).filter(|face| selected[*face]).collect();
+        if picked.is_empty() {
+            Vec::new()
+        } else {
+            vec![picked]
+        }
+    };
+
+    let face_normal = |face: usize| -> Vec3 {
+        input
+            .polygon_normal(&input.indices[faces[face].clone()])
+            .normalize_or_zero()
+    };
+    let face_centroid = |face: usize| -> Vec3 {
+        let corners = &input.indices[faces[face].clone()];
+        corners
+            .iter()
+            .map(|idx| Vec3::from(input.positions[*idx as usize]))
+            .sum::<Vec3>()
+            / corners.len() as f32
+    };
+
+    let mut positions = input.positions.clone();
+    let mut point_sources: Vec<usize> = (0..input.positions.len()).collect();
+    let mut side_indices = Vec::new();
+    let mut side_corners = Vec::new();
+    let mut side_prims = Vec::new();
+
+    let mut replaced: Vec<Option<Vec<u32>>> = vec![None; face_count];
+    for region in &regions {
+        let mut accum: HashMap<u32, (Vec3, Vec3, f32)> = HashMap::new();
+        for &face in region {
+            let normal = face_normal(face);
+            let centroid = face_centroid(face);
+            for &idx in &input.indices[faces[face].clone()] {
+                let entry = accum.entry(idx).or_insert((Vec3::ZERO, Vec3::ZERO, 0.0));
+                entry.0 += normal;
+                entry.1 += centroid;
+                entry.2 += 1.0;
+            }
+        }
+
+        let mut remap = HashMap::with_capacity(accum.len());
+        let mut keys: Vec<u32> = accum.keys().copied().collect();
+        keys.sort_unstable();
+        for idx in keys {
+            let (normal_sum, centroid_sum, count) = accum[&idx];
+            let origin = Vec3::from(input.positions[idx as usize]);
+            let center = centroid_sum / count;
+            let inset = settings.inset.clamp(0.0, 1.0);
+            let moved =
+                origin.lerp(center, inset) + normal_sum.normalize_or_zero() * settings.distance;
+            remap.insert(idx, positions.len() as u32);
+            positions.push(moved.to_array());
+            point_sources.push(idx as usize);
+        }
+
+        let mut edge_faces: HashMap<(u32, u32), usize> = HashMap::new();
+        for &face in region {
+            let corners = &input.indices[faces[face].clone()];
+            for corner in 0..corners.len() {
+                let a = corners[corner];
+                let b = corners[(corner + 1) % corners.len()];
+                *edge_faces.entry((a.min(b), a.max(b))).or_insert(0) += 1;
+            }
+        }
+
+        for &face in region {
+            let range = faces[face].clone();
+            let corners = &input.indices[range.clone()];
+            replaced[face] = Some(corners.iter().map(|idx| remap[idx]).collect());
+            for corner in 0..corners.len() {
+                let next = (corner + 1) % corners.len();
+                let a = corners[corner];
+                let b = corners[next];
+                if edge_faces[&(a.min(b), a.max(b))] != 1 {
+                    continue;
+                }
+                let ca = range.start + corner;
+                let cb = range.start + next;
+                side_indices.extend_from_slice(&[a, b, remap[&b], remap[&a]]);
+                side_corners.extend_from_slice(&[ca, cb, cb, ca]);
+                side_prims.push(face);
+            }
+        }
+    }
+
+    let mut indices = Vec::with_capacity(input.indices.len() + side_indices.len());
+    let mut corner_sources = Vec::with_capacity(indices.capacity());
+    let mut prim_sources = Vec::with_capacity(face_count + side_prims.len());
+    let mut face_counts = Vec::with_capacity(prim_sources.capacity());
+    let mut side_flags = Vec::with_capacity(prim_sources.capacity());
+    for (face, replacement) in replaced.iter().enumerate() {
+        let range = faces[face].clone();
+        match replacement {
+            Some(corners) => indices.extend_from_slice(corners),
+            None => indices.extend_from_slice(&input.indices[range.clone()]),
+        }
+        face_counts.push(range.len() as u32);
+        corner_sources.extend(range);
+        prim_sources.push(face);
+        side_flags.push(0);
+    }
+    indices.extend(side_indices);
+    corner_sources.extend(side_corners);
+    face_counts.extend(std::iter::repeat_n(4, side_prims.len()));
+    side_flags.extend(std::iter::repeat_n(1, side_prims.len()));
+    prim_sources.extend(side_prims);
+
+    let mut mesh = input.rebuild_from_sources(
+        positions,
+        indices,
+        &point_sources,
+        &corner_sources,
+        &prim_sources,
+    );
+    if face_counts.iter().any(|count| *count != 3) {
+        mesh.face_counts = face_counts;
+    }
+    mesh.corner_normals = None;
+    mesh.compute_normals();
+
+    let side_group = settings.side_group.trim();
+    if !side_group.is_empty() {
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            side_group,
+            AttributeStorage::Int(side_flags),
+        )
+        .map_err(|err| format!("PolyExtrude group error: {:?}", err))?;
+    }
+    Ok(mesh)
+}
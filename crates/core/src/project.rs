@@ -1,8 +1,9 @@
 use serde::{Deserialize, Serialize};
 
-use crate::graph::Graph;
+use crate::graph::{Graph, NodeId, ParamValue};
+use crate::mesh::Aabb;
 
-pub const PROJECT_VERSION: u32 = 1;
+pub const PROJECT_VERSION: u32 = 2;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Project {
@@ -11,6 +12,42 @@ pub struct Project {
     pub graph: Graph,
 }
 
+impl Project {
+    // Brings a project read from disk up to `PROJECT_VERSION`, one version at a time, and
+    // returns the version it was saved with. Projects from newer builds are refused.
+    pub fn migrate(&mut self) -> Result<u32, String> {
+        let saved = self.version;
+        if saved > PROJECT_VERSION {
+            return Err(format!(
+                "project version {} is newer than supported ({})",
+                saved, PROJECT_VERSION
+            ));
+        }
+        if saved < 2 {
+            migrate_sort_domains(&mut self.graph);
+        }
+        self.version = PROJECT_VERSION;
+        Ok(saved)
+    }
+}
+
+// Version 2: Sort's domain went from Point/Vertex/Primitive/Detail to Point/Primitive.
+// Vertex and Detail never cooked, so they fall back to Point.
+fn migrate_sort_domains(graph: &mut Graph) {
+    let ids: Vec<NodeId> = graph.nodes().map(|node| node.id).collect();
+    for id in ids {
+        if let Some(child) = graph.subnet_mut(id) {
+            migrate_sort_domains(child);
+        }
+        let Some(node) = graph.node_mut(id).filter(|node| node.name == "Sort") else {
+            continue;
+        };
+        if let Some(ParamValue::Int(domain)) = node.params.values.get_mut("domain") {
+            *domain = i32::from(*domain == 2);
+        }
+    }
+}
+
 impl Default for Project {
     fn default() -> Self {
         Self {
@@ -30,6 +67,13 @@ pub struct ProjectSettings {
     pub panels: PanelSettings,
     pub camera: CameraSettings,
     pub render_debug: RenderDebugSettings,
+    pub timeline: TimelineSettings,
+    pub theme: UiTheme,
+    pub measurements: Vec<Measurement>,
+    pub annotations: Vec<Annotation>,
+    pub graph_bookmarks: Vec<GraphBookmark>,
+    pub graph_frames: Vec<GraphFrame>,
+    pub graph_backdrops: Vec<GraphBackdrop>,
 }
 
 impl Default for ProjectSettings {
@@ -41,6 +85,93 @@ impl Default for ProjectSettings {
             panels: PanelSettings::default(),
             camera: CameraSettings::default(),
             render_debug: RenderDebugSettings::default(),
+            timeline: TimelineSettings::default(),
+            theme: UiTheme::Dark,
+            measurements: Vec::new(),
+            annotations: Vec::new(),
+            graph_bookmarks: Vec::new(),
+            graph_frames: Vec::new(),
+            graph_backdrops: Vec::new(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum UiTheme {
+    Dark,
+    HighContrast,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub start: [f32; 3],
+    pub end: [f32; 3],
+}
+
+impl Measurement {
+    pub fn length(&self) -> f32 {
+        let d = [
+            self.end[0] - self.start[0],
+            self.end[1] - self.start[1],
+            self.end[2] - self.start[2],
+        ];
+        (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Annotation {
+    pub position: [f32; 3],
+    pub text: String,
+}
+
+// A saved node graph view: canvas pan and zoom, plus the nodes to select on return.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphBookmark {
+    pub name: String,
+    pub translation: [f32; 2],
+    pub zoom: f32,
+    #[serde(default)]
+    pub nodes: Vec<NodeId>,
+}
+
+// Titled backdrop drawn around a group of nodes; it follows the nodes as they move.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphFrame {
+    pub title: String,
+    pub nodes: Vec<NodeId>,
+    pub color: [u8; 3],
+}
+
+// Free-standing colored rectangle in graph space. Unlike a frame it owns no node list:
+// whatever nodes sit inside it when it is dragged move along with it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct GraphBackdrop {
+    pub title: String,
+    pub min: [f32; 2],
+    pub max: [f32; 2],
+    pub color: [u8; 3],
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TimelineSettings {
+    pub frame: f32,
+    pub start: f32,
+    pub end: f32,
+    pub fps: f32,
+    pub loop_playback: bool,
+}
+
+impl Default for TimelineSettings {
+    fn default() -> Self {
+        Self {
+            frame: 1.0,
+            start: 1.0,
+            end: 120.0,
+            fps: 24.0,
+            loop_playback: true,
         }
     }
 }
@@ -52,6 +183,12 @@ pub struct PanelSettings {
     pub show_spreadsheet: bool,
     pub show_debug: bool,
     pub show_console: bool,
+    pub show_project_params: bool,
+    pub show_uv_view: bool,
+    pub show_dope_sheet: bool,
+    pub show_graph_stats: bool,
+    pub show_dependencies: bool,
+    pub show_node_audit: bool,
 }
 
 impl Default for PanelSettings {
@@ -61,16 +198,27 @@ impl Default for PanelSettings {
             show_spreadsheet: true,
             show_debug: false,
             show_console: false,
+            show_project_params: false,
+            show_uv_view: false,
+            show_dope_sheet: false,
+            show_graph_stats: false,
+            show_dependencies: false,
+            show_node_audit: false,
         }
     }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct CameraSettings {
     pub target: [f32; 3],
     pub distance: f32,
     pub yaw: f32,
     pub pitch: f32,
+    pub mode: CameraMode,
+    pub fly_speed: f32,
+    pub space_mouse: SpaceMouseSettings,
+    pub bookmarks: Vec<CameraBookmark>,
 }
 
 impl Default for CameraSettings {
@@ -80,16 +228,157 @@ impl Default for CameraSettings {
             distance: 5.0,
             yaw: 0.0,
             pitch: 0.0,
+            mode: CameraMode::Orbit,
+            fly_speed: 2.0,
+            space_mouse: SpaceMouseSettings::default(),
+            bookmarks: Vec::new(),
+        }
+    }
+}
+
+// Vertical field of view of the viewport projection; framing has to agree with it.
+const VIEW_FOV_DEGREES: f32 = 45.0;
+// Orbit pitch stops short of the poles, where the view's up vector flips.
+const MAX_PITCH: f32 = 1.54;
+
+impl CameraSettings {
+    pub fn bookmark(&self, name: String) -> CameraBookmark {
+        CameraBookmark {
+            name,
+            target: self.target,
+            distance: self.distance,
+            yaw: self.yaw,
+            pitch: self.pitch,
+        }
+    }
+
+    pub fn recall(&mut self, bookmark: &CameraBookmark) {
+        self.target = bookmark.target;
+        self.distance = bookmark.distance;
+        self.yaw = bookmark.yaw;
+        self.pitch = bookmark.pitch;
+    }
+
+    // Keeps target and distance, so switching views stays centered on the same thing.
+    pub fn set_standard_view(&mut self, view: StandardView) {
+        let (yaw, pitch) = view.angles();
+        self.yaw = yaw;
+        self.pitch = pitch;
+    }
+
+    // Centers on the box and backs off until its bounding sphere fits the narrower of the
+    // two fields of view, so the framing holds whichever way the camera is turned.
+    pub fn frame_bounds(&mut self, bounds: Aabb, aspect: f32) {
+        let center: [f32; 3] = std::array::from_fn(|i| (bounds.min[i] + bounds.max[i]) * 0.5);
+        let radius = (0..3)
+            .map(|i| (bounds.max[i] - bounds.min[i]).powi(2))
+            .sum::<f32>()
+            .sqrt()
+            * 0.5;
+        let half_fov = (VIEW_FOV_DEGREES.to_radians() * 0.5).tan();
+        let half_angle = half_fov.min(half_fov * aspect.max(0.01)).atan();
+        self.target = center;
+        self.distance = (radius / half_angle.sin()).clamp(0.1, 1000.0);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StandardView {
+    Front,
+    Top,
+    Side,
+    Perspective,
+}
+
+impl StandardView {
+    pub const ALL: [StandardView; 4] = [
+        StandardView::Front,
+        StandardView::Top,
+        StandardView::Side,
+        StandardView::Perspective,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            StandardView::Front => "Front",
+            StandardView::Top => "Top",
+            StandardView::Side => "Side",
+            StandardView::Perspective => "Perspective",
+        }
+    }
+
+    // (yaw, pitch) of the eye around the target: front looks down -Z, side down -X.
+    fn angles(self) -> (f32, f32) {
+        match self {
+            StandardView::Front => (std::f32::consts::FRAC_PI_2, 0.0),
+            StandardView::Top => (std::f32::consts::FRAC_PI_2, MAX_PITCH),
+            StandardView::Side => (0.0, 0.0),
+            StandardView::Perspective => (std::f32::consts::FRAC_PI_4, 0.45),
+        }
+    }
+}
+
+// A saved viewport camera: the orbit target, distance and angles.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CameraBookmark {
+    pub name: String,
+    pub target: [f32; 3],
+    pub distance: f32,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CameraMode {
+    Orbit,
+    Fly,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SpaceMouseSettings {
+    pub enabled: bool,
+    pub translation_speed: f32,
+    pub rotation_speed: f32,
+    pub deadzone: f32,
+}
+
+impl Default for SpaceMouseSettings {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            translation_speed: 1.0,
+            rotation_speed: 1.0,
+            deadzone: 0.05,
         }
     }
 }
 
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum BackgroundMode {
+    Solid,
+    Gradient,
+}
+
 #[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum ShadingMode {
     Lit,
     Normals,
     Depth,
+    Attribute,
+    FlatShaded,
+    Wireframe,
+}
+
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum CullMode {
+    None,
+    Back,
+    Front,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -100,13 +389,35 @@ pub struct RenderDebugSettings {
     pub show_normals: bool,
     pub show_bounds: bool,
     pub normal_length: f32,
+    pub normal_density: f32,
     pub show_stats: bool,
     pub show_points: bool,
     pub point_size: f32,
     pub key_shadows: bool,
+    pub contact_shadow: bool,
     pub shading_mode: ShadingMode,
     pub depth_near: f32,
     pub depth_far: f32,
+    pub surface_shader: String,
+    pub color_attribute: String,
+    pub cull_mode: CullMode,
+    pub double_sided: bool,
+    pub flat_shading: bool,
+    pub section_enabled: bool,
+    pub section_normal: [f32; 3],
+    pub section_offset: f32,
+    pub section_outline: bool,
+    pub near_clip: f32,
+    pub hidden_debug_channels: Vec<String>,
+    pub show_scale_reference: bool,
+    pub mesh_opacity: f32,
+    pub template_opacity: f32,
+    pub outline_selection: bool,
+    pub show_prim_under_cursor: bool,
+    pub background_mode: BackgroundMode,
+    pub background_color: [u8; 3],
+    pub background_top: [u8; 3],
+    pub background_bottom: [u8; 3],
 }
 
 impl Default for RenderDebugSettings {
@@ -117,13 +428,87 @@ impl Default for RenderDebugSettings {
             show_normals: false,
             show_bounds: false,
             normal_length: 0.3,
+            normal_density: 1.0,
             show_stats: true,
             show_points: false,
             point_size: 4.0,
             key_shadows: false,
+            contact_shadow: false,
             shading_mode: ShadingMode::Lit,
             depth_near: 0.5,
             depth_far: 20.0,
+            surface_shader: String::new(),
+            color_attribute: String::new(),
+            cull_mode: CullMode::None,
+            double_sided: false,
+            flat_shading: false,
+            section_enabled: false,
+            section_normal: [1.0, 0.0, 0.0],
+            section_offset: 0.0,
+            section_outline: true,
+            near_clip: 0.0,
+            hidden_debug_channels: Vec::new(),
+            show_scale_reference: false,
+            mesh_opacity: 1.0,
+            template_opacity: 0.3,
+            outline_selection: true,
+            show_prim_under_cursor: false,
+            background_mode: BackgroundMode::Solid,
+            background_color: [28, 28, 28],
+            background_top: [58, 62, 70],
+            background_bottom: [22, 22, 24],
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn migrate_upgrades_version_one_sort_domains() {
+        let mut project = Project::default();
+        let sort = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::Sort));
+        let node = project.graph.node_mut(sort).unwrap();
+        node.params
+            .values
+            .insert("domain".to_string(), ParamValue::Int(2));
+        project.version = 1;
+
+        assert_eq!(project.migrate(), Ok(1));
+        assert_eq!(project.version, PROJECT_VERSION);
+        let node = project.graph.node(sort).unwrap();
+        assert_eq!(node.params.get_int("domain", 0), 1);
+
+        project.version = PROJECT_VERSION + 1;
+        assert!(project.migrate().is_err());
+    }
+
+    #[test]
+    fn camera_frames_bounds_and_recalls_views() {
+        let mut camera = CameraSettings::default();
+        let bounds = Aabb {
+            min: [1.0, -1.0, -1.0],
+            max: [3.0, 1.0, 1.0],
+        };
+        camera.frame_bounds(bounds, 2.0);
+        assert_eq!(camera.target, [2.0, 0.0, 0.0]);
+        let radius = 3.0_f32.sqrt();
+        let expected = radius / (22.5_f32.to_radians()).sin();
+        assert!((camera.distance - expected).abs() < 1.0e-4);
+        // A tall, narrow viewport has to back off further to fit the same box.
+        let wide = camera.distance;
+        camera.frame_bounds(bounds, 0.5);
+        assert!(camera.distance > wide);
+
+        let saved = camera.bookmark("Close".to_string());
+        camera.set_standard_view(StandardView::Top);
+        assert_eq!(camera.pitch, MAX_PITCH);
+        assert_eq!(camera.target, [2.0, 0.0, 0.0]);
+        camera.recall(&saved);
+        assert_eq!(camera.bookmark("Close".to_string()), saved);
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::path::Path;
+
+use serde::Serialize;
+
+use crate::nodes_builtin::{builtin_kind_from_name, BuiltinNodeKind};
+use crate::project::{Project, PROJECT_VERSION};
+
+// Cheap summary of a project file for indexing; nothing is cooked.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ProjectInfo {
+    pub version: u32,
+    pub node_count: usize,
+    pub nodes_by_kind: BTreeMap<String, usize>,
+    pub link_count: usize,
+    pub assets: Vec<AssetReference>,
+    pub unknown_nodes: Vec<(u64, String)>,
+    pub estimated_cost: u32,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct AssetReference {
+    pub node: u64,
+    pub kind: String,
+    pub path: String,
+    pub output: bool,
+}
+
+// Something a cook of this project would trip over, found without cooking it.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(tag = "issue", rename_all = "snake_case")]
+pub enum DependencyIssue {
+    MissingInput { node: u64, path: String },
+    MissingOutputDir { node: u64, path: String },
+    UnknownNodeKind { node: u64, kind: String },
+    NewerProjectVersion { version: u32, supported: u32 },
+}
+
+impl DependencyIssue {
+    pub fn node(&self) -> Option<u64> {
+        match self {
+            Self::MissingInput { node, .. }
+            | Self::MissingOutputDir { node, .. }
+            | Self::UnknownNodeKind { node, .. } => Some(*node),
+            Self::NewerProjectVersion { .. } => None,
+        }
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::MissingInput { node, path } => {
+                format!("#{}: input file {} not found", node, path)
+            }
+            Self::MissingOutputDir { node, path } => {
+                format!("#{}: output folder for {} does not exist", node, path)
+            }
+            Self::UnknownNodeKind { node, kind } => {
+                format!("#{}: node type {} is not available", node, kind)
+            }
+            Self::NewerProjectVersion { version, supported } => format!(
+                "project version {} is newer than supported version {}",
+                version, supported
+            ),
+        }
+    }
+}
+
+impl ProjectInfo {
+    pub fn from_project(project: &Project) -> Self {
+        let graph = &project.graph;
+        let mut nodes_by_kind = BTreeMap::new();
+        let mut assets = Vec::new();
+        let mut unknown_nodes = Vec::new();
+        let mut estimated_cost = 0;
+        for node in graph.nodes() {
+            *nodes_by_kind.entry(node.name.clone()).or_insert(0) += 1;
+            let kind = builtin_kind_from_name(&node.name);
+            if kind.is_none() {
+                unknown_nodes.push((node.id.as_u64(), node.name.clone()));
+            }
+            estimated_cost += kind.map_or(1, BuiltinNodeKind::cook_cost);
+            let (paths, output) = match kind {
+                Some(BuiltinNodeKind::File) => (vec![node.params.get_string("path", "")], false),
+                Some(BuiltinNodeKind::MergeFiles) => (
+                    node.params
+                        .get_string_list("paths")
+                        .iter()
+                        .map(String::as_str)
+                        .collect(),
+                    false,
+                ),
+                Some(BuiltinNodeKind::ObjOutput) => {
+                    (vec![node.params.get_string("path", "")], true)
+                }
+                _ => continue,
+            };
+            for path in paths {
+                if !path.is_empty() {
+                    assets.push(AssetReference {
+                        node: node.id.as_u64(),
+                        kind: node.name.clone(),
+                        path: path.to_string(),
+                        output,
+                    });
+                }
+            }
+        }
+        Self {
+            version: project.version,
+            node_count: graph.nodes().count(),
+            nodes_by_kind,
+            link_count: graph.links().count(),
+            assets,
+            unknown_nodes,
+            estimated_cost,
+        }
+    }
+
+    // File paths are checked the way the File and OBJ Output nodes open them, relative to
+    // the working directory.
+    pub fn dependency_issues(&self) -> Vec<DependencyIssue> {
+        let mut issues = Vec::new();
+        if self.version > PROJECT_VERSION {
+            issues.push(DependencyIssue::NewerProjectVersion {
+                version: self.version,
+                supported: PROJECT_VERSION,
+            });
+        }
+        for (node, kind) in &self.unknown_nodes {
+            issues.push(DependencyIssue::UnknownNodeKind {
+                node: *node,
+                kind: kind.clone(),
+            });
+        }
+        for asset in &self.assets {
+            let path = Path::new(&asset.path);
+            let (node, path_text) = (asset.node, asset.path.clone());
+            if !asset.output && !path.is_file() {
+                issues.push(DependencyIssue::MissingInput {
+                    node,
+                    path: path_text,
+                });
+            } else if asset.output
+                && path
+                    .parent()
+                    .is_some_and(|dir| !dir.as_os_str().is_empty() && !dir.is_dir())
+            {
+                issues.push(DependencyIssue::MissingOutputDir {
+                    node,
+                    path: path_text,
+                });
+            }
+        }
+        issues
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        let _ = writeln!(text, "version: {}", self.version);
+        let _ = writeln!(text, "nodes: {}", self.node_count);
+        for (kind, count) in &self.nodes_by_kind {
+            let _ = writeln!(text, "  {}: {}", kind, count);
+        }
+        let _ = writeln!(text, "links: {}", self.link_count);
+        let _ = writeln!(text, "assets: {}", self.assets.len());
+        for asset in &self.assets {
+            let direction = if asset.output { "out" } else { "in" };
+            let _ = writeln!(
+                text,
+                "  {:<3} {} #{}: {}",
+                direction, asset.kind, asset.node, asset.path
+            );
+        }
+        let _ = writeln!(text, "estimated cook cost: {}", self.estimated_cost);
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ParamValue;
+    use crate::nodes_builtin::node_definition;
+
+    #[test]
+    fn summarizes_nodes_links_and_assets() {
+        let mut project = Project::default();
+        let graph = &mut project.graph;
+        let file = graph.add_node(node_definition(BuiltinNodeKind::File));
+        let export = graph.add_node(node_definition(BuiltinNodeKind::ObjOutput));
+        graph.add_node(node_definition(BuiltinNodeKind::Box));
+        graph
+            .set_param(
+                file,
+                "path".to_string(),
+                ParamValue::String("in.obj".into()),
+            )
+            .unwrap();
+        graph
+            .set_param(
+                export,
+                "path".to_string(),
+                ParamValue::String("out.obj".into()),
+            )
+            .unwrap();
+        let from = graph.node(file).unwrap().outputs[0];
+        let to = graph.node(export).unwrap().inputs[0];
+        graph.add_link(from, to).unwrap();
+
+        let info = ProjectInfo::from_project(&project);
+        assert_eq!(info.node_count, 3);
+        assert_eq!(info.link_count, 1);
+        assert_eq!(info.nodes_by_kind.get("Box"), Some(&1));
+        assert_eq!(info.assets.len(), 2);
+        assert!(info.assets.iter().any(|a| a.path == "out.obj" && a.output));
+        assert_eq!(
+            info.estimated_cost,
+            BuiltinNodeKind::File.cook_cost()
+                + BuiltinNodeKind::ObjOutput.cook_cost()
+                + BuiltinNodeKind::Box.cook_cost()
+        );
+        assert!(info.to_text().contains("in  File"));
+    }
+
+    #[test]
+    fn flags_missing_files_and_unknown_nodes() {
+        let mut project = Project::default();
+        let graph = &mut project.graph;
+        let file = graph.add_node(node_definition(BuiltinNodeKind::File));
+        let export = graph.add_node(node_definition(BuiltinNodeKind::ObjOutput));
+        let mut custom = node_definition(BuiltinNodeKind::Box);
+        custom.name = "Plugin Scatter".to_string();
+        let custom = graph.add_node(custom);
+        graph
+            .set_param(
+                file,
+                "path".to_string(),
+                ParamValue::String("/nonexistent/grapho/in.obj".into()),
+            )
+            .unwrap();
+        graph
+            .set_param(
+                export,
+                "path".to_string(),
+                ParamValue::String("out.obj".into()),
+            )
+            .unwrap();
+
+        let issues = ProjectInfo::from_project(&project).dependency_issues();
+        assert_eq!(
+            issues,
+            vec![
+                DependencyIssue::UnknownNodeKind {
+                    node: custom.as_u64(),
+                    kind: "Plugin Scatter".to_string(),
+                },
+                DependencyIssue::MissingInput {
+                    node: file.as_u64(),
+                    path: "/nonexistent/grapho/in.obj".to_string(),
+                },
+            ]
+        );
+
+        project.version = PROJECT_VERSION + 1;
+        let issues = ProjectInfo::from_project(&project).dependency_issues();
+        assert_eq!(issues[0].node(), None);
+    }
+}
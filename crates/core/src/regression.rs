@@ -0,0 +1,156 @@
+use serde::{Deserialize, Serialize};
+
+use crate::attributes::AttributeDomain;
+use crate::content_hash::mesh_content_hash;
+use crate::eval::EvalError;
+use crate::mesh::Mesh;
+use crate::mesh_eval::{evaluate_mesh_graph, MeshEvalState};
+use crate::project::Project;
+
+// Bounds drift below this is float noise, not a behavior change.
+const BOUNDS_TOLERANCE: f32 = 1.0e-4;
+
+// What a golden file records about a cooked mesh. The hash catches any change to the
+// geometry or attributes; the other fields say what kind of change it was.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MeshSnapshot {
+    pub points: usize,
+    pub vertices: usize,
+    pub prims: usize,
+    pub bounds_min: [f32; 3],
+    pub bounds_max: [f32; 3],
+    pub attributes: Vec<String>,
+    pub hash: String,
+}
+
+impl MeshSnapshot {
+    pub fn from_mesh(mesh: &Mesh) -> Self {
+        let (bounds_min, bounds_max) = mesh
+            .bounds()
+            .map(|bounds| (bounds.min, bounds.max))
+            .unwrap_or_default();
+        let mut attributes: Vec<String> = mesh
+            .list_attributes()
+            .into_iter()
+            .map(|info| format!("{}:{}", domain_name(info.domain), info.name))
+            .collect();
+        attributes.sort();
+        Self {
+            points: mesh.positions.len(),
+            vertices: mesh.indices.len(),
+            prims: mesh.face_count(),
+            bounds_min,
+            bounds_max,
+            attributes,
+            hash: format!("{:016x}", mesh_content_hash(mesh)),
+        }
+    }
+
+    // One line per field that differs from `self` (the golden); empty when they match.
+    pub fn differences(&self, actual: &MeshSnapshot) -> Vec<String> {
+        let mut diffs = Vec::new();
+        for (field, expected, got) in [
+            ("points", self.points, actual.points),
+            ("vertices", self.vertices, actual.vertices),
+            ("prims", self.prims, actual.prims),
+        ] {
+            if expected != got {
+                diffs.push(format!("{}: expected {}, got {}", field, expected, got));
+            }
+        }
+        for (field, expected, got) in [
+            ("bounds_min", self.bounds_min, actual.bounds_min),
+            ("bounds_max", self.bounds_max, actual.bounds_max),
+        ] {
+            let moved = expected
+                .iter()
+                .zip(got)
+                .any(|(a, b)| (a - b).abs() > BOUNDS_TOLERANCE);
+            if moved {
+                diffs.push(format!("{}: expected {:?}, got {:?}", field, expected, got));
+            }
+        }
+        let added: Vec<&String> = actual
+            .attributes
+            .iter()
+            .filter(|name| !self.attributes.contains(name))
+            .collect();
+        let removed: Vec<&String> = self
+            .attributes
+            .iter()
+            .filter(|name| !actual.attributes.contains(name))
+            .collect();
+        if !added.is_empty() || !removed.is_empty() {
+            diffs.push(format!(
+                "attributes: added {:?}, removed {:?}",
+                added, removed
+            ));
+        }
+        if self.hash != actual.hash {
+            diffs.push(format!("hash: expected {}, got {}", self.hash, actual.hash));
+        }
+        diffs
+    }
+}
+
+// Cooks the project's display node from scratch and snapshots the result.
+pub fn cook_project_snapshot(project: &Project) -> Result<MeshSnapshot, String> {
+    let node = project
+        .graph
+        .display_node()
+        .ok_or_else(|| "project has no display node".to_string())?;
+    let result = evaluate_mesh_graph(&project.graph, node, &mut MeshEvalState::new())
+        .map_err(|err| format!("{:?}", err))?;
+    let Some(mesh) = result.output else {
+        return Err(result
+            .report
+            .errors
+            .iter()
+            .find_map(|err| match err {
+                EvalError::Node { message, .. } => Some(message.clone()),
+                EvalError::Upstream { .. } => None,
+            })
+            .unwrap_or_else(|| "evaluation produced no output".to_string()));
+    };
+    Ok(MeshSnapshot::from_mesh(&mesh))
+}
+
+fn domain_name(domain: AttributeDomain) -> &'static str {
+    match domain {
+        AttributeDomain::Point => "point",
+        AttributeDomain::Vertex => "vertex",
+        AttributeDomain::Primitive => "primitive",
+        AttributeDomain::Detail => "detail",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ParamValue;
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+
+    #[test]
+    fn golden_snapshot_flags_changed_output() {
+        let mut project = Project::default();
+        let node = project
+            .graph
+            .add_node(node_definition(BuiltinNodeKind::Box));
+        project.graph.set_display_node(Some(node)).unwrap();
+
+        let golden = cook_project_snapshot(&project).unwrap();
+        assert_eq!(golden.prims, 6);
+        assert!(golden
+            .differences(&cook_project_snapshot(&project).unwrap())
+            .is_empty());
+
+        project
+            .graph
+            .set_param(node, "size", ParamValue::Vec3([2.0, 1.0, 1.0]))
+            .unwrap();
+        let diffs = golden.differences(&cook_project_snapshot(&project).unwrap());
+        assert!(diffs.iter().any(|diff| diff.starts_with("bounds_max")));
+        assert!(diffs.iter().any(|diff| diff.starts_with("hash")));
+        assert!(!diffs.iter().any(|diff| diff.starts_with("prims")));
+    }
+}
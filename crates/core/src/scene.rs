@@ -1,5 +1,6 @@
 use crate::attributes::{AttributeDomain, AttributeRef};
-use crate::mesh::Mesh;
+use crate::content_hash::{mesh_content_hash, StableHasher};
+use crate::mesh::{DebugChannel, Mesh};
 
 #[derive(Debug, Clone)]
 pub struct SceneMesh {
@@ -9,16 +10,27 @@ pub struct SceneMesh {
     pub corner_normals: Option<Vec<[f32; 3]>>,
     pub colors: Option<Vec<[f32; 3]>>,
     pub corner_colors: Option<Vec<[f32; 3]>>,
+    // Content hash of the source mesh and colouring; the GPU mesh cache is keyed on it.
+    pub content_hash: u64,
 }
 
 #[derive(Debug, Clone)]
 pub struct SceneSnapshot {
     pub mesh: SceneMesh,
     pub base_color: [f32; 3],
+    pub debug_channels: Vec<DebugChannel>,
 }
 
 impl SceneMesh {
     pub fn from_mesh(mesh: &Mesh) -> Self {
+        let content_hash = mesh_content_hash(mesh);
+        if !mesh.face_counts.is_empty() {
+            return Self::from_triangles(&mesh.triangulate(), content_hash);
+        }
+        Self::from_triangles(mesh, content_hash)
+    }
+
+    fn from_triangles(mesh: &Mesh, content_hash: u64) -> Self {
         let mut normals = fallback_normals(mesh);
         let mut corner_normals = mesh.corner_normals.clone();
         if let Some((domain, attr)) = mesh.attribute_with_precedence("N") {
@@ -58,31 +70,9 @@ impl SceneMesh {
         let mut corner_colors = None;
         if let Some((domain, attr)) = mesh.attribute_with_precedence("Cd") {
             if let Some(values) = attr_vec3(attr) {
-                match domain {
-                    AttributeDomain::Vertex => {
-                        if values.len() == mesh.indices.len() {
-                            corner_colors = Some(values);
-                        }
-                    }
-                    AttributeDomain::Point => {
-                        if values.len() == mesh.positions.len() {
-                            colors = Some(values);
-                        }
-                    }
-                    AttributeDomain::Primitive => {
-                        if let Some(expanded) = expand_primitive_vec3(mesh, &values) {
-                            corner_colors = Some(expanded);
-                        }
-                    }
-                    AttributeDomain::Detail => {
-                        if let Some(value) = values.first().copied() {
-                            if mesh.indices.is_empty() {
-                                colors = Some(vec![value; mesh.positions.len()]);
-                            } else {
-                                corner_colors = Some(vec![value; mesh.indices.len()]);
-                            }
-                        }
-                    }
+                if let Some((point, corner)) = domain_colors(mesh, domain, values) {
+                    colors = point;
+                    corner_colors = corner;
                 }
             }
         }
@@ -94,6 +84,7 @@ impl SceneMesh {
             corner_normals,
             colors,
             corner_colors,
+            content_hash,
         }
     }
 }
@@ -103,10 +94,144 @@ impl SceneSnapshot {
         Self {
             mesh: SceneMesh::from_mesh(mesh),
             base_color,
+            debug_channels: Vec::new(),
+        }
+    }
+
+    pub fn from_mesh_false_color(mesh: &Mesh, attribute: &str) -> Option<(Self, [f32; 2])> {
+        if !mesh.face_counts.is_empty() {
+            return Self::from_mesh_false_color(&mesh.triangulate(), attribute);
         }
+        let (domain, attr) = mesh.attribute_with_precedence(attribute)?;
+        let (values, range) = false_color_values(attr);
+        let (colors, corner_colors) = domain_colors(mesh, domain, values)?;
+        let mut scene_mesh = SceneMesh::from_mesh(mesh);
+        scene_mesh.colors = colors;
+        scene_mesh.corner_colors = corner_colors;
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(scene_mesh.content_hash);
+        hasher.write_str(attribute);
+        scene_mesh.content_hash = hasher.finish();
+        Some((
+            Self {
+                mesh: scene_mesh,
+                base_color: [1.0, 1.0, 1.0],
+                debug_channels: Vec::new(),
+            },
+            range,
+        ))
     }
 }
 
+type ColorChannels = (Option<Vec<[f32; 3]>>, Option<Vec<[f32; 3]>>);
+
+fn domain_colors(
+    mesh: &Mesh,
+    domain: AttributeDomain,
+    values: Vec<[f32; 3]>,
+) -> Option<ColorChannels> {
+    match domain {
+        AttributeDomain::Vertex => {
+            (values.len() == mesh.indices.len()).then_some((None, Some(values)))
+        }
+        AttributeDomain::Point => {
+            (values.len() == mesh.positions.len()).then_some((Some(values), None))
+        }
+        AttributeDomain::Primitive => {
+            expand_primitive_vec3(mesh, &values).map(|expanded| (None, Some(expanded)))
+        }
+        AttributeDomain::Detail => {
+            let value = values.first().copied()?;
+            if mesh.indices.is_empty() {
+                Some((Some(vec![value; mesh.positions.len()]), None))
+            } else {
+                Some((None, Some(vec![value; mesh.indices.len()])))
+            }
+        }
+    }
+}
+
+fn false_color_values(attr: AttributeRef<'_>) -> (Vec<[f32; 3]>, [f32; 2]) {
+    let scalars: Vec<f32> = match attr {
+        AttributeRef::Float(values) => values.to_vec(),
+        AttributeRef::Int(values) => values.iter().map(|v| *v as f32).collect(),
+        AttributeRef::Vec2(values) => {
+            let vectors: Vec<[f32; 3]> = values.iter().map(|v| [v[0], v[1], 0.0]).collect();
+            return normalize_vectors(vectors);
+        }
+        AttributeRef::Vec3(values) => return normalize_vectors(values.to_vec()),
+        AttributeRef::Vec4(values) => {
+            let vectors: Vec<[f32; 3]> = values.iter().map(|v| [v[0], v[1], v[2]]).collect();
+            return normalize_vectors(vectors);
+        }
+    };
+    let range = value_range(scalars.iter().copied());
+    let colors = scalars
+        .iter()
+        .map(|value| false_color_ramp(normalize(*value, range)))
+        .collect();
+    (colors, range)
+}
+
+fn normalize_vectors(values: Vec<[f32; 3]>) -> (Vec<[f32; 3]>, [f32; 2]) {
+    let range = value_range(values.iter().flatten().copied());
+    let colors = values
+        .iter()
+        .map(|v| {
+            [
+                normalize(v[0], range),
+                normalize(v[1], range),
+                normalize(v[2], range),
+            ]
+        })
+        .collect();
+    (colors, range)
+}
+
+fn value_range(values: impl Iterator<Item = f32>) -> [f32; 2] {
+    let mut range = [f32::INFINITY, f32::NEG_INFINITY];
+    for value in values.filter(|value| value.is_finite()) {
+        range[0] = range[0].min(value);
+        range[1] = range[1].max(value);
+    }
+    if range[0] > range[1] {
+        [0.0, 0.0]
+    } else {
+        range
+    }
+}
+
+fn normalize(value: f32, range: [f32; 2]) -> f32 {
+    let span = range[1] - range[0];
+    if !value.is_finite() {
+        0.0
+    } else if span <= f32::EPSILON {
+        0.5
+    } else {
+        ((value - range[0]) / span).clamp(0.0, 1.0)
+    }
+}
+
+fn false_color_ramp(t: f32) -> [f32; 3] {
+    const STOPS: [[f32; 3]; 5] = [
+        [0.05, 0.1, 0.6],
+        [0.0, 0.6, 0.9],
+        [0.1, 0.8, 0.2],
+        [0.95, 0.85, 0.1],
+        [0.85, 0.1, 0.05],
+    ];
+    let scaled = t.clamp(0.0, 1.0) * (STOPS.len() - 1) as f32;
+    let index = (scaled.floor() as usize).min(STOPS.len() - 2);
+    let frac = scaled - index as f32;
+    let a = STOPS[index];
+    let b = STOPS[index + 1];
+    [
+        a[0] + (b[0] - a[0]) * frac,
+        a[1] + (b[1] - a[1]) * frac,
+        a[2] + (b[2] - a[2]) * frac,
+    ]
+}
+
 fn fallback_normals(mesh: &Mesh) -> Vec<[f32; 3]> {
     match &mesh.normals {
         Some(normals) => normals.clone(),
@@ -138,3 +263,67 @@ fn expand_primitive_vec3(mesh: &Mesh, values: &[[f32; 3]]) -> Option<Vec<[f32; 3
     }
     Some(expanded)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::SceneSnapshot;
+    use crate::attributes::{AttributeDomain, AttributeStorage};
+    use crate::content_hash::mesh_content_hash;
+    use crate::mesh::make_grid;
+
+    #[test]
+    fn false_color_ramps_float_attribute_over_range() {
+        let mut mesh = make_grid([1.0, 1.0], [1, 1]);
+        let values: Vec<f32> = (0..mesh.positions.len()).map(|i| i as f32 * 2.0).collect();
+        mesh.set_attribute(
+            AttributeDomain::Point,
+            "density",
+            AttributeStorage::Float(values),
+        )
+        .unwrap();
+
+        let (snapshot, range) = SceneSnapshot::from_mesh_false_color(&mesh, "density").unwrap();
+        assert_eq!(range, [0.0, 6.0]);
+        let colors = snapshot.mesh.colors.unwrap();
+        for (color, expected) in [
+            (colors[0], [0.05, 0.1, 0.6]),
+            (colors[3], [0.85, 0.1, 0.05]),
+        ] {
+            for axis in 0..3 {
+                assert!((color[axis] - expected[axis]).abs() < 1e-5);
+            }
+        }
+        assert!(SceneSnapshot::from_mesh_false_color(&mesh, "missing").is_none());
+    }
+
+    #[test]
+    fn false_color_maps_vectors_to_rgb() {
+        let mesh = make_grid([2.0, 2.0], [1, 1]);
+        let (snapshot, range) = SceneSnapshot::from_mesh_false_color(&mesh, "P").unwrap();
+        assert_eq!(range, [-1.0, 1.0]);
+        let colors = snapshot.mesh.colors.unwrap();
+        for (color, p) in colors.iter().zip(&mesh.positions) {
+            assert!((color[0] - (p[0] + 1.0) * 0.5).abs() < 1e-6);
+            assert!((color[2] - (p[2] + 1.0) * 0.5).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn content_hash_follows_mesh_and_colouring() {
+        let mesh = make_grid([1.0, 1.0], [1, 1]);
+        let lit = SceneSnapshot::from_mesh(&mesh, [1.0, 1.0, 1.0])
+            .mesh
+            .content_hash;
+        assert_eq!(lit, mesh_content_hash(&mesh));
+        let (false_color, _) = SceneSnapshot::from_mesh_false_color(&mesh, "P").unwrap();
+        assert_ne!(false_color.mesh.content_hash, lit);
+
+        let moved = make_grid([2.0, 1.0], [1, 1]);
+        assert_ne!(
+            SceneSnapshot::from_mesh(&moved, [1.0, 1.0, 1.0])
+                .mesh
+                .content_hash,
+            lit
+        );
+    }
+}
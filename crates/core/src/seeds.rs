@@ -0,0 +1,85 @@
+use serde::{Deserialize, Serialize};
+
+use crate::content_hash::StableHasher;
+use crate::graph::NodeId;
+
+pub(crate) const SEED_PARAM: &str = "seed";
+
+// With `per_node` on, every stochastic node cooks with its own seed mixed with the project
+// seed and a hash of its node id, so identical nodes no longer repeat the same pattern and
+// one number varies the whole graph. Off keeps each node's seed exactly as authored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SeedSettings {
+    pub per_node: bool,
+    pub seed: u32,
+}
+
+impl SeedSettings {
+    pub fn node_seed(&self, node: NodeId, seed: i32) -> i32 {
+        if !self.per_node {
+            return seed;
+        }
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(u64::from(self.seed));
+        hasher.write_u64(node.as_u64());
+        hasher.write_i64(i64::from(seed));
+        (hasher.finish() as u32 & 0x7fff_ffff) as i32
+    }
+
+    // Next project seed for "reseed all"; derived from the current one so the sequence of
+    // variations is reproducible.
+    pub fn reseeded(self) -> Self {
+        let mut hasher = StableHasher::new();
+        hasher.write_u64(u64::from(self.seed));
+        Self {
+            per_node: true,
+            seed: hasher.finish() as u32 % 100_000,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::{Graph, ParamValue};
+    use crate::nodes_builtin::{node_definition, BuiltinNodeKind};
+    use crate::param_expr::resolve_params;
+
+    #[test]
+    fn per_node_seeds_follow_project_seed() {
+        let mut graph = Graph::default();
+        let a = graph.add_node(node_definition(BuiltinNodeKind::Scatter));
+        let b = graph.add_node(node_definition(BuiltinNodeKind::Scatter));
+        let cube = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let seed_of = |graph: &Graph, id| {
+            let params = resolve_params(graph, graph.node(id).unwrap()).unwrap();
+            params.get_int(SEED_PARAM, 0)
+        };
+        assert_eq!(seed_of(&graph, a), seed_of(&graph, b));
+
+        let version = graph.node(a).unwrap().param_version;
+        assert!(graph.set_seeds(SeedSettings {
+            per_node: true,
+            seed: 7,
+        }));
+        assert_ne!(graph.node(a).unwrap().param_version, version);
+        let first = (seed_of(&graph, a), seed_of(&graph, b));
+        assert_ne!(first.0, first.1);
+        assert_eq!(first, (seed_of(&graph, a), seed_of(&graph, b)));
+        assert!(!resolve_params(&graph, graph.node(cube).unwrap())
+            .unwrap()
+            .values
+            .contains_key(SEED_PARAM));
+
+        graph
+            .set_param(a, SEED_PARAM.to_string(), ParamValue::Int(2))
+            .unwrap();
+        assert_ne!(seed_of(&graph, a), first.0);
+
+        let reseeded = graph.seeds().reseeded();
+        assert_ne!(reseeded.seed, 7);
+        graph.set_seeds(reseeded);
+        assert_ne!(seed_of(&graph, b), first.1);
+    }
+}
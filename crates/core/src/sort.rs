@@ -0,0 +1,135 @@
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeRef};
+use crate::mesh::Mesh;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Axis,
+    Distance,
+    Attribute,
+}
+
+#[derive(Debug, Clone)]
+pub struct SortSettings {
+    pub domain: AttributeDomain,
+    pub key: SortKey,
+    pub axis: [f32; 3],
+    pub point: [f32; 3],
+    pub attr: String,
+    pub reverse: bool,
+}
+
+pub fn sort_mesh(input: &Mesh, settings: &SortSettings) -> Result<Mesh, String> {
+    match settings.domain {
+        AttributeDomain::Point => sort_points(input, settings),
+        AttributeDomain::Primitive => sort_primitives(input, settings),
+        _ => Err("Sort supports the point or primitive domain".to_string()),
+    }
+}
+
+fn sort_points(input: &Mesh, settings: &SortSettings) -> Result<Mesh, String> {
+    let keys: Vec<f32> = match settings.key {
+        SortKey::Axis | SortKey::Distance => input
+            .positions
+            .iter()
+            .map(|p| position_key(Vec3::from(*p), settings))
+            .collect(),
+        SortKey::Attribute => attribute_keys(input, AttributeDomain::Point, &settings.attr)?,
+    };
+    let order = stable_order(&keys, settings.reverse);
+
+    let mut remap = vec![0u32; order.len()];
+    for (new_index, old_index) in order.iter().enumerate() {
+        remap[*old_index] = new_index as u32;
+    }
+    let positions = order.iter().map(|idx| input.positions[*idx]).collect();
+    let indices = input
+        .indices
+        .iter()
+        .map(|idx| remap.get(*idx as usize).copied().unwrap_or(*idx))
+        .collect();
+    let corners: Vec<usize> = (0..input.indices.len()).collect();
+    let prims: Vec<usize> = (0..input.face_count()).collect();
+    let mut mesh = input.rebuild_from_sources(positions, indices, &order, &corners, &prims);
+    mesh.face_counts = input.face_counts.clone();
+    Ok(mesh)
+}
+
+fn sort_primitives(input: &Mesh, settings: &SortSettings) -> Result<Mesh, String> {
+    let faces = input.face_ranges();
+    let keys: Vec<f32> = match settings.key {
+        SortKey::Axis | SortKey::Distance => faces
+            .iter()
+            .map(|range| {
+                let corners = &input.indices[range.clone()];
+                let mut center = Vec3::ZERO;
+                for idx in corners {
+                    center += Vec3::from(
+                        input
+                            .positions
+                            .get(*idx as usize)
+                            .copied()
+                            .unwrap_or([0.0; 3]),
+                    );
+                }
+                if !corners.is_empty() {
+                    center /= corners.len() as f32;
+                }
+                position_key(center, settings)
+            })
+            .collect(),
+        SortKey::Attribute => attribute_keys(input, AttributeDomain::Primitive, &settings.attr)?,
+    };
+    let order = stable_order(&keys, settings.reverse);
+
+    let mut indices = Vec::with_capacity(input.indices.len());
+    let mut corners = Vec::with_capacity(input.indices.len());
+    let mut face_counts = Vec::with_capacity(order.len());
+    for face in &order {
+        let range = faces[*face].clone();
+        indices.extend_from_slice(&input.indices[range.clone()]);
+        face_counts.push(range.len() as u32);
+        corners.extend(range);
+    }
+    let points: Vec<usize> = (0..input.positions.len()).collect();
+    let mut mesh =
+        input.rebuild_from_sources(input.positions.clone(), indices, &points, &corners, &order);
+    if !input.face_counts.is_empty() {
+        mesh.face_counts = face_counts;
+    }
+    Ok(mesh)
+}
+
+fn position_key(p: Vec3, settings: &SortSettings) -> f32 {
+    match settings.key {
+        SortKey::Distance => p.distance(Vec3::from(settings.point)),
+        _ => p.dot(Vec3::from(settings.axis).normalize_or_zero()),
+    }
+}
+
+fn attribute_keys(mesh: &Mesh, domain: AttributeDomain, name: &str) -> Result<Vec<f32>, String> {
+    let attr = mesh
+        .attribute(domain, name)
+        .ok_or_else(|| format!("Sort attribute '{}' not found on {:?}", name, domain))?;
+    Ok(match attr {
+        AttributeRef::Float(values) => values.to_vec(),
+        AttributeRef::Int(values) => values.iter().map(|v| *v as f32).collect(),
+        AttributeRef::Vec2(values) => values.iter().map(|v| v[0]).collect(),
+        AttributeRef::Vec3(values) => values.iter().map(|v| v[0]).collect(),
+        AttributeRef::Vec4(values) => values.iter().map(|v| v[0]).collect(),
+    })
+}
+
+fn stable_order(keys: &[f32], reverse: bool) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    order.sort_by(|a, b| {
+        let ordering = keys[*a].total_cmp(&keys[*b]);
+        if reverse {
+            ordering.reverse()
+        } else {
+            ordering
+        }
+    });
+    order
+}
@@ -0,0 +1,80 @@
+use crate::attributes::AttributeDomain;
+use crate::mesh::Mesh;
+
+pub fn split_mesh(input: &Mesh, group: &str) -> Result<(Mesh, Mesh), String> {
+    let group = group.trim();
+    if group.is_empty() {
+        return Err("Split requires a primitive group".to_string());
+    }
+    let mask = input
+        .group_mask(AttributeDomain::Primitive, group)
+        .ok_or_else(|| format!("Split: primitive group '{}' not found", group))?;
+    Ok((
+        extract_faces(input, &mask, true),
+        extract_faces(input, &mask, false),
+    ))
+}
+
+fn extract_faces(input: &Mesh, mask: &[bool], keep: bool) -> Mesh {
+    let faces = input.face_ranges();
+    let mut remap = vec![u32::MAX; input.positions.len()];
+    let mut points = Vec::new();
+    let mut positions = Vec::new();
+    let mut indices = Vec::new();
+    let mut corners = Vec::new();
+    let mut prims = Vec::new();
+    let mut face_counts = Vec::new();
+    for (face, range) in faces.iter().enumerate() {
+        if mask.get(face).copied().unwrap_or(false) != keep {
+            continue;
+        }
+        for corner in range.clone() {
+            let point = input.indices[corner] as usize;
+            if remap[point] == u32::MAX {
+                remap[point] = positions.len() as u32;
+                positions.push(input.positions[point]);
+                points.push(point);
+            }
+            indices.push(remap[point]);
+            corners.push(corner);
+        }
+        prims.push(face);
+        face_counts.push(range.len() as u32);
+    }
+
+    let mut mesh = input.rebuild_from_sources(positions, indices, &points, &corners, &prims);
+    if !input.face_counts.is_empty() {
+        mesh.face_counts = face_counts;
+    }
+    mesh
+}
+
+#[cfg(test)]
+mod tests {
+    use super::split_mesh;
+    use crate::attributes::{AttributeDomain, AttributeStorage};
+    use crate::mesh::make_grid;
+
+    #[test]
+    fn split_separates_group_faces() {
+        let mut mesh = make_grid([1.0, 1.0], [2, 2]);
+        let faces = mesh.face_count();
+        let group = (0..faces as i32).map(|face| i32::from(face == 0)).collect();
+        mesh.set_attribute(
+            AttributeDomain::Primitive,
+            "top",
+            AttributeStorage::Int(group),
+        )
+        .unwrap();
+
+        let (inside, outside) = split_mesh(&mesh, "top").unwrap();
+        assert_eq!(inside.face_count(), 1);
+        assert_eq!(outside.face_count(), faces - 1);
+        assert_eq!(inside.positions.len(), 4);
+        assert!(inside
+            .indices
+            .iter()
+            .all(|idx| (*idx as usize) < inside.positions.len()));
+        assert!(split_mesh(&mesh, "missing").is_err());
+    }
+}
@@ -0,0 +1,229 @@
+use std::collections::HashMap;
+
+use crate::command::{CommandOutput, GraphCommand};
+use crate::graph::{Graph, NodeId, ParamValue, PinId};
+use crate::nodes_builtin::builtin_kind_from_name;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptShortcut {
+    pub chord: String,
+    pub action: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StartupScriptOutput {
+    pub nodes: Vec<NodeId>,
+    pub shortcuts: Vec<ScriptShortcut>,
+}
+
+pub fn run_startup_script(graph: &mut Graph, source: &str) -> Result<StartupScriptOutput, String> {
+    let mut scratch = graph.clone();
+    let mut runner = ScriptRunner {
+        graph: &mut scratch,
+        names: HashMap::new(),
+        output: StartupScriptOutput::default(),
+    };
+    for (index, line) in source.lines().enumerate() {
+        let line = line.split('#').next().unwrap_or("").trim();
+        if line.is_empty() {
+            continue;
+        }
+        runner
+            .run_line(line)
+            .map_err(|err| format!("line {}: {}", index + 1, err))?;
+    }
+    let output = runner.output;
+    *graph = scratch;
+    Ok(output)
+}
+
+struct ScriptRunner<'a> {
+    graph: &'a mut Graph,
+    names: HashMap<String, NodeId>,
+    output: StartupScriptOutput,
+}
+
+impl ScriptRunner<'_> {
+    fn run_line(&mut self, line: &str) -> Result<(), String> {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        match tokens.as_slice() {
+            ["node", name, kind @ ..] if !kind.is_empty() => {
+                if self.names.contains_key(*name) {
+                    return Err(format!("node '{}' is already defined", name));
+                }
+                let kind = kind.join(" ");
+                let kind = kind.trim_matches('"');
+                let kind = builtin_kind_from_name(kind)
+                    .ok_or_else(|| format!("unknown node type '{}'", kind))?;
+                let CommandOutput::Node(node_id) = self.apply(GraphCommand::AddNode {
+                    kind: kind.name().to_string(),
+                })?
+                else {
+                    return Err("node was not created".to_string());
+                };
+                self.names.insert(name.to_string(), node_id);
+                self.output.nodes.push(node_id);
+                Ok(())
+            }
+            ["set", name, key, values @ ..] if !values.is_empty() => {
+                let node_id = self.node(name)?;
+                let current = self
+                    .graph
+                    .node(node_id)
+                    .and_then(|node| node.params.values.get(*key))
+                    .ok_or_else(|| format!("node '{}' has no parameter '{}'", name, key))?;
+                let value = parse_value(current, values)?;
+                self.apply(GraphCommand::SetParam {
+                    node: node_id,
+                    key: key.to_string(),
+                    value,
+                })
+                .map(|_| ())
+            }
+            ["connect", from, to] => {
+                let from = self.pin(from, false)?;
+                let to = self.pin(to, true)?;
+                self.apply(GraphCommand::Connect { from, to }).map(|_| ())
+            }
+            ["display", name] => {
+                let node_id = self.node(name)?;
+                self.apply(GraphCommand::SetDisplay {
+                    node: Some(node_id),
+                })
+                .map(|_| ())
+            }
+            ["shortcut", chord, action @ ..] if !action.is_empty() => {
+                self.output.shortcuts.push(ScriptShortcut {
+                    chord: chord.to_string(),
+                    action: action.join(" "),
+                });
+                Ok(())
+            }
+            _ => Err(format!("unrecognized command '{}'", line)),
+        }
+    }
+
+    fn apply(&mut self, command: GraphCommand) -> Result<CommandOutput, String> {
+        self.graph
+            .apply(&command)
+            .map_err(|err| format!("{:?}", err))
+    }
+
+    fn node(&self, name: &str) -> Result<NodeId, String> {
+        self.names
+            .get(name)
+            .copied()
+            .ok_or_else(|| format!("unknown node '{}'", name))
+    }
+
+    fn pin(&mut self, spec: &str, input: bool) -> Result<PinId, String> {
+        let (name, pin) = spec.split_once('.').unwrap_or((spec, "0"));
+        let node_id = self.node(name)?;
+        let node = self
+            .graph
+            .node(node_id)
+            .ok_or_else(|| format!("unknown node '{}'", name))?;
+        let pins = if input { &node.inputs } else { &node.outputs };
+        let found = match pin.parse::<usize>() {
+            Ok(index) => pins.get(index).copied(),
+            Err(_) => pins.iter().copied().find(|pin_id| {
+                self.graph
+                    .pin(*pin_id)
+                    .is_some_and(|existing| existing.name == pin)
+            }),
+        };
+        if let Some(pin_id) = found {
+            return Ok(pin_id);
+        }
+        if input && pin.parse::<usize>().ok() == Some(pins.len()) {
+            return match self.apply(GraphCommand::AddInput { node: node_id })? {
+                CommandOutput::Pin(pin_id) => Ok(pin_id),
+                _ => Err(format!("node '{}' did not add an input", name)),
+            };
+        }
+        let side = if input { "input" } else { "output" };
+        Err(format!("node '{}' has no {} '{}'", name, side, pin))
+    }
+}
+
+fn parse_value(current: &ParamValue, values: &[&str]) -> Result<ParamValue, String> {
+    let float = |token: &str| {
+        token
+            .parse::<f32>()
+            .map_err(|_| format!("expected a number, got '{}'", token))
+    };
+    match (current, values) {
+        (ParamValue::Float(_), [value]) => Ok(ParamValue::Float(float(value)?)),
+        (ParamValue::Int(_), [value]) => value
+            .parse::<i32>()
+            .map(ParamValue::Int)
+            .map_err(|_| format!("expected an integer, got '{}'", value)),
+        (ParamValue::Bool(_), [value]) => match *value {
+            "true" | "1" | "on" => Ok(ParamValue::Bool(true)),
+            "false" | "0" | "off" => Ok(ParamValue::Bool(false)),
+            _ => Err(format!("expected true or false, got '{}'", value)),
+        },
+        (ParamValue::Vec2(_), [x, y]) => Ok(ParamValue::Vec2([float(x)?, float(y)?])),
+        (ParamValue::Vec3(_), [x, y, z]) => Ok(ParamValue::Vec3([float(x)?, float(y)?, float(z)?])),
+        (ParamValue::String(_), values) => {
+            let text = values.join(" ");
+            let text = text
+                .strip_prefix('"')
+                .and_then(|text| text.strip_suffix('"'))
+                .unwrap_or(&text);
+            Ok(ParamValue::String(text.to_string()))
+        }
+        // One item per token, so items cannot contain spaces.
+        (ParamValue::StringList(_), values) => Ok(ParamValue::StringList(
+            values
+                .iter()
+                .map(|value| value.trim_matches('"').to_string())
+                .collect(),
+        )),
+        (current, values) => Err(format!(
+            "expected a {} value, got {} token(s)",
+            current.type_name(),
+            values.len()
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::run_startup_script;
+    use crate::graph::{Graph, ParamValue};
+
+    #[test]
+    fn startup_script_builds_graph_and_collects_shortcuts() {
+        let mut graph = Graph::default();
+        let source = "\
+# bootstrap
+node box Box
+node xform Transform
+node copies Copy to Points
+set box size 2 3 4
+set xform translate 0 1 0
+connect box xform.0
+display xform
+shortcut Ctrl+Shift+D View: Toggle dope sheet
+";
+        let output = run_startup_script(&mut graph, source).unwrap();
+        assert_eq!(output.nodes.len(), 3);
+        assert_eq!(graph.links().count(), 1);
+        assert_eq!(graph.display_node(), Some(output.nodes[1]));
+        assert_eq!(graph.node(output.nodes[2]).unwrap().name, "Copy to Points");
+        let size = graph
+            .node(output.nodes[0])
+            .unwrap()
+            .params
+            .values
+            .get("size");
+        assert_eq!(size, Some(&ParamValue::Vec3([2.0, 3.0, 4.0])));
+        assert_eq!(output.shortcuts[0].chord, "Ctrl+Shift+D");
+        assert_eq!(output.shortcuts[0].action, "View: Toggle dope sheet");
+
+        let err = run_startup_script(&mut graph, "node a Box\nset a size 1\n").unwrap_err();
+        assert!(err.starts_with("line 2:"));
+        assert_eq!(graph.nodes().count(), 3);
+    }
+}
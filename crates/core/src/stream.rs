@@ -0,0 +1,240 @@
+use std::io::Write;
+
+use crate::eval::EvalError;
+use crate::graph::{Graph, NodeId};
+use crate::mesh::Mesh;
+use crate::mesh_eval::{evaluate_mesh_graph, MeshEvalState};
+use crate::nodes_builtin::{builtin_kind_from_name, copy_to_points_copies, BuiltinNodeKind};
+use crate::param_expr::resolve_params;
+use crate::units::scale_mesh;
+
+// Writes OBJ one chunk at a time. Face indices are offset by everything written so far, so
+// the chunks read back as a single mesh without ever being merged in memory.
+pub struct ObjStreamWriter<W: Write> {
+    writer: W,
+    positions: usize,
+    uvs: usize,
+    normals: usize,
+    prims: usize,
+}
+
+impl<W: Write> ObjStreamWriter<W> {
+    pub fn new(writer: W) -> Self {
+        Self {
+            writer,
+            positions: 0,
+            uvs: 0,
+            normals: 0,
+            prims: 0,
+        }
+    }
+
+    pub fn points(&self) -> usize {
+        self.positions
+    }
+
+    pub fn prims(&self) -> usize {
+        self.prims
+    }
+
+    pub fn write_chunk(&mut self, mesh: &Mesh) -> Result<(), String> {
+        let file = &mut self.writer;
+        for p in &mesh.positions {
+            writeln!(file, "v {} {} {}", p[0], p[1], p[2]).map_err(|err| err.to_string())?;
+        }
+
+        let uvs = mesh
+            .uvs
+            .as_ref()
+            .filter(|uvs| uvs.len() == mesh.positions.len());
+        for uv in uvs.into_iter().flatten() {
+            writeln!(file, "vt {} {}", uv[0], uv[1]).map_err(|err| err.to_string())?;
+        }
+
+        let normals = mesh
+            .normals
+            .as_ref()
+            .filter(|normals| normals.len() == mesh.positions.len());
+        for n in normals.into_iter().flatten() {
+            writeln!(file, "vn {} {} {}", n[0], n[1], n[2]).map_err(|err| err.to_string())?;
+        }
+
+        let faces = mesh.face_ranges();
+        for range in &faces {
+            let mut line = String::from("f");
+            for idx in &mesh.indices[range.clone()] {
+                let local = *idx as usize + 1;
+                let v = self.positions + local;
+                let vt = self.uvs + local;
+                let vn = self.normals + local;
+                match (uvs.is_some(), normals.is_some()) {
+                    (true, true) => line.push_str(&format!(" {v}/{vt}/{vn}")),
+                    (true, false) => line.push_str(&format!(" {v}/{vt}")),
+                    (false, true) => line.push_str(&format!(" {v}//{vn}")),
+                    (false, false) => line.push_str(&format!(" {v}")),
+                }
+            }
+            writeln!(file, "{line}").map_err(|err| err.to_string())?;
+        }
+
+        self.positions += mesh.positions.len();
+        self.uvs += uvs.map_or(0, Vec::len);
+        self.normals += normals.map_or(0, Vec::len);
+        self.prims += faces.len();
+        Ok(())
+    }
+
+    pub fn finish(mut self) -> Result<W, String> {
+        self.writer.flush().map_err(|err| err.to_string())?;
+        Ok(self.writer)
+    }
+}
+
+// Evaluates `output` and writes it through `writer`, scaled by `scale`. When the output is a
+// Copy to Points node, possibly behind Output or OBJ Output pass-throughs, only its inputs
+// are evaluated and the copies are written one by one, so peak memory stays near a single
+// copy instead of the merged result.
+pub fn stream_mesh_graph<W: Write>(
+    graph: &Graph,
+    output: NodeId,
+    state: &mut MeshEvalState,
+    writer: &mut ObjStreamWriter<W>,
+    scale: f32,
+) -> Result<(), String> {
+    let mut node_id = output;
+    loop {
+        let node = graph
+            .node(node_id)
+            .ok_or_else(|| "missing output node".to_string())?;
+        match builtin_kind_from_name(&node.name) {
+            Some(BuiltinNodeKind::Output | BuiltinNodeKind::ObjOutput) => {
+                match upstream_output(graph, node_id, 0) {
+                    Some((upstream, _)) => node_id = upstream,
+                    None => break,
+                }
+            }
+            Some(BuiltinNodeKind::CopyToPoints) => {
+                return stream_copy_to_points(graph, node_id, state, writer, scale);
+            }
+            _ => break,
+        }
+    }
+
+    let result = evaluate_mesh_graph(graph, output, state).map_err(|err| format!("{:?}", err))?;
+    let mut mesh = result
+        .output
+        .ok_or_else(|| eval_failure(&result.report.errors))?;
+    scale_mesh(&mut mesh, scale);
+    writer.write_chunk(&mesh)
+}
+
+fn stream_copy_to_points<W: Write>(
+    graph: &Graph,
+    node_id: NodeId,
+    state: &mut MeshEvalState,
+    writer: &mut ObjStreamWriter<W>,
+    scale: f32,
+) -> Result<(), String> {
+    let source = evaluate_input(graph, node_id, 0, state)?
+        .ok_or_else(|| "Copy to Points requires a source mesh".to_string())?;
+    let template = evaluate_input(graph, node_id, 1, state)?
+        .ok_or_else(|| "Copy to Points requires a template mesh".to_string())?;
+    if template.positions.is_empty() {
+        return Err("Copy to Points requires template points".to_string());
+    }
+    let node = graph
+        .node(node_id)
+        .ok_or_else(|| "missing node".to_string())?;
+    let params = resolve_params(graph, node)?;
+    for mut copy in copy_to_points_copies(&source, &template, &params) {
+        scale_mesh(&mut copy, scale);
+        writer.write_chunk(&copy)?;
+    }
+    Ok(())
+}
+
+fn evaluate_input(
+    graph: &Graph,
+    node_id: NodeId,
+    input: usize,
+    state: &mut MeshEvalState,
+) -> Result<Option<Mesh>, String> {
+    let Some((upstream, output)) = upstream_output(graph, node_id, input) else {
+        return Ok(None);
+    };
+    let result = evaluate_mesh_graph(graph, upstream, state).map_err(|err| format!("{:?}", err))?;
+    if !result.report.output_valid {
+        return Err(eval_failure(&result.report.errors));
+    }
+    Ok(state.mesh_for_output(upstream, output).cloned())
+}
+
+fn upstream_output(graph: &Graph, node_id: NodeId, input: usize) -> Option<(NodeId, usize)> {
+    let pin_id = *graph.node(node_id)?.inputs.get(input)?;
+    let link = graph.links().find(|link| link.to == pin_id)?;
+    let upstream = graph.pin(link.from)?.node;
+    let output = graph
+        .node(upstream)?
+        .outputs
+        .iter()
+        .position(|id| *id == link.from)?;
+    Some((upstream, output))
+}
+
+fn eval_failure(errors: &[EvalError]) -> String {
+    errors
+        .iter()
+        .find_map(|err| match err {
+            EvalError::Node { message, .. } => Some(message.clone()),
+            EvalError::Upstream { .. } => None,
+        })
+        .unwrap_or_else(|| "evaluation produced no output".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::ParamValue;
+    use crate::nodes_builtin::{node_definition, write_obj_to};
+
+    #[test]
+    fn streamed_copies_match_merged_export() {
+        let mut graph = Graph::default();
+        let source = graph.add_node(node_definition(BuiltinNodeKind::Box));
+        let template = graph.add_node(node_definition(BuiltinNodeKind::Grid));
+        let copy = graph.add_node(node_definition(BuiltinNodeKind::CopyToPoints));
+        let out = graph.add_node(node_definition(BuiltinNodeKind::Output));
+        graph
+            .set_param(source, "size".to_string(), ParamValue::Vec3([0.2; 3]))
+            .unwrap();
+        for (from, to, input) in [(source, copy, 0), (template, copy, 1), (copy, out, 0)] {
+            let from_pin = graph.node(from).unwrap().outputs[0];
+            let to_pin = graph.node(to).unwrap().inputs[input];
+            graph.add_link(from_pin, to_pin).unwrap();
+        }
+
+        let mut state = MeshEvalState::new();
+        let merged = evaluate_mesh_graph(&graph, out, &mut state)
+            .unwrap()
+            .output
+            .unwrap();
+        let mut expected = Vec::new();
+        write_obj_to(&mut expected, &merged).unwrap();
+
+        let mut writer = ObjStreamWriter::new(Vec::new());
+        stream_mesh_graph(&graph, out, &mut MeshEvalState::new(), &mut writer, 1.0).unwrap();
+        assert_eq!(writer.points(), merged.positions.len());
+        assert_eq!(writer.prims(), merged.face_count());
+        let streamed = writer.finish().unwrap();
+        let lines = |data: &[u8]| {
+            let mut lines: Vec<String> = String::from_utf8(data.to_vec())
+                .unwrap()
+                .lines()
+                .map(str::to_string)
+                .collect();
+            lines.sort();
+            lines
+        };
+        assert_eq!(lines(&streamed), lines(&expected));
+    }
+}
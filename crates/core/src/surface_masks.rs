@@ -0,0 +1,408 @@
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap};
+
+use glam::Vec3;
+
+use crate::attributes::{AttributeDomain, AttributeStorage};
+use crate::mesh::{DebugChannel, Mesh};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CurvatureMode {
+    Signed,
+    Convex,
+    Concave,
+}
+
+#[derive(Debug, Clone)]
+pub struct CurvatureSettings {
+    pub mode: CurvatureMode,
+    pub scale: f32,
+    pub smooth_iterations: u32,
+    pub attr: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct SlopeSettings {
+    pub up: [f32; 3],
+    pub min_angle: f32,
+    pub max_angle: f32,
+    pub smooth_iterations: u32,
+    pub attr: String,
+}
+
+pub fn curvature_mask(input: &Mesh, settings: &CurvatureSettings) -> Result<Mesh, String> {
+    let mut mesh = input.clone();
+    let normals = point_normals(&mut mesh, "Curvature")?;
+    let half_edges = mesh.half_edges();
+    let mut values: Vec<f32> = (0..mesh.positions.len())
+        .map(|point| {
+            let p = Vec3::from(mesh.positions[point]);
+            let n = Vec3::from(normals[point]).normalize_or_zero();
+            let neighbors = half_edges.point_neighbors(point as u32);
+            let mut sum = 0.0;
+            let mut count = 0;
+            for neighbor in neighbors {
+                let offset = Vec3::from(mesh.positions[neighbor as usize]) - p;
+                let length_sq = offset.length_squared();
+                if length_sq > 1.0e-12 {
+                    // Neighbors falling away along the normal mean the surface bends outward.
+                    sum += -2.0 * n.dot(offset) / length_sq;
+                    count += 1;
+                }
+            }
+            if count == 0 {
+                return 0.0;
+            }
+            let curvature = sum / count as f32 * settings.scale;
+            match settings.mode {
+                CurvatureMode::Signed => curvature,
+                CurvatureMode::Convex => curvature.max(0.0),
+                CurvatureMode::Concave => (-curvature).max(0.0),
+            }
+        })
+        .collect();
+    smooth_point_values(&mesh, &mut values, settings.smooth_iterations);
+    write_point_float(&mut mesh, &settings.attr, values, "Curvature")?;
+    Ok(mesh)
+}
+
+pub fn slope_mask(input: &Mesh, settings: &SlopeSettings) -> Result<Mesh, String> {
+    let mut mesh = input.clone();
+    let normals = point_normals(&mut mesh, "Slope")?;
+    let up = Vec3::from(settings.up).normalize_or_zero();
+    if up == Vec3::ZERO {
+        return Err("Slope requires a non-zero up vector".to_string());
+    }
+    let min_angle = settings.min_angle.clamp(0.0, 180.0);
+    let max_angle = settings.max_angle.clamp(0.0, 180.0);
+    let span = max_angle - min_angle;
+    let mut values: Vec<f32> = normals
+        .iter()
+        .map(|normal| {
+            let n = Vec3::from(*normal).normalize_or_zero();
+            let angle = n.dot(up).clamp(-1.0, 1.0).acos().to_degrees();
+            if span.abs() <= f32::EPSILON {
+                if angle >= min_angle {
+                    1.0
+                } else {
+                    0.0
+                }
+            } else {
+                ((angle - min_angle) / span).clamp(0.0, 1.0)
+            }
+        })
+        .collect();
+    smooth_point_values(&mesh, &mut values, settings.smooth_iterations);
+    write_point_float(&mut mesh, &settings.attr, values, "Slope")?;
+    Ok(mesh)
+}
+
+#[derive(Debug, Clone)]
+pub struct GeodesicSettings {
+    pub group: String,
+    pub normalize: bool,
+    pub attr: String,
+}
+
+pub fn geodesic_distance(input: &Mesh, settings: &GeodesicSettings) -> Result<Mesh, String> {
+    let mut mesh = input.clone();
+    let group = settings.group.trim();
+    if group.is_empty() {
+        return Err("Geodesic Distance requires a seed point group".to_string());
+    }
+    let seeds = mesh
+        .group_mask(AttributeDomain::Point, group)
+        .ok_or_else(|| format!("Geodesic Distance: point group '{}' not found", group))?;
+    if !seeds.iter().any(|seed| *seed) {
+        return Err(format!(
+            "Geodesic Distance: point group '{}' is empty",
+            group
+        ));
+    }
+    let distances = edge_distances(&mesh, &seeds);
+    // Points that cannot be reached from a seed read as the farthest reached distance.
+    let max = distances
+        .iter()
+        .copied()
+        .filter(|distance| distance.is_finite())
+        .fold(0.0, f32::max);
+    let values = distances
+        .iter()
+        .copied()
+        .map(|distance| {
+            let distance = if distance.is_finite() { distance } else { max };
+            if settings.normalize && max > 0.0 {
+                distance / max
+            } else {
+                distance
+            }
+        })
+        .collect();
+    write_point_float(&mut mesh, &settings.attr, values, "Geodesic Distance")?;
+    let unreachable: Vec<[f32; 3]> = mesh
+        .positions
+        .iter()
+        .zip(&distances)
+        .filter(|(_, distance)| !distance.is_finite())
+        .map(|(position, _)| *position)
+        .collect();
+    if !unreachable.is_empty() {
+        mesh.debug_channels.push(DebugChannel::points(
+            "geodesic_unreachable",
+            [1.0, 0.25, 0.2],
+            unreachable,
+        ));
+    }
+    Ok(mesh)
+}
+
+struct Visit {
+    distance: f32,
+    point: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    // Reversed so the max-heap pops the closest point first.
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+// Dijkstra over mesh edges. Points sharing a position, such as UV seams and poles, are
+// collapsed first so the distance flows across unwelded seams.
+fn edge_distances(mesh: &Mesh, seeds: &[bool]) -> Vec<f32> {
+    let mut welded = HashMap::new();
+    let representative: Vec<usize> = mesh
+        .positions
+        .iter()
+        .enumerate()
+        .map(|(point, position)| {
+            let key = position.map(|value| (value * 1.0e5).round() as i64);
+            *welded.entry(key).or_insert(point)
+        })
+        .collect();
+
+    let half_edges = mesh.half_edges();
+    let mut neighbors: Vec<Vec<(usize, f32)>> = vec![Vec::new(); mesh.positions.len()];
+    for point in 0..mesh.positions.len() {
+        let from = representative[point];
+        for neighbor in half_edges.point_neighbors(point as u32) {
+            let to = representative[neighbor as usize];
+            if to != from {
+                let length = Vec3::from(mesh.positions[point])
+                    .distance(Vec3::from(mesh.positions[neighbor as usize]));
+                neighbors[from].push((to, length));
+            }
+        }
+    }
+
+    let mut distances = vec![f32::INFINITY; mesh.positions.len()];
+    let mut heap = BinaryHeap::new();
+    for (point, seed) in seeds.iter().enumerate() {
+        let root = representative[point];
+        if *seed && distances[root] > 0.0 {
+            distances[root] = 0.0;
+            heap.push(Visit {
+                distance: 0.0,
+                point: root,
+            });
+        }
+    }
+    while let Some(Visit { distance, point }) = heap.pop() {
+        if distance > distances[point] {
+            continue;
+        }
+        for &(next, length) in &neighbors[point] {
+            let candidate = distance + length;
+            if candidate < distances[next] {
+                distances[next] = candidate;
+                heap.push(Visit {
+                    distance: candidate,
+                    point: next,
+                });
+            }
+        }
+    }
+    representative.iter().map(|root| distances[*root]).collect()
+}
+
+fn point_normals(mesh: &mut Mesh, label: &str) -> Result<Vec<[f32; 3]>, String> {
+    if mesh.normals.is_none() && !mesh.compute_normals() {
+        return Err(format!("{} requires a polygon mesh input", label));
+    }
+    mesh.normals
+        .clone()
+        .ok_or_else(|| format!("{} requires point normals", label))
+}
+
+fn smooth_point_values(mesh: &Mesh, values: &mut Vec<f32>, iterations: u32) {
+    if iterations == 0 {
+        return;
+    }
+    let half_edges = mesh.half_edges();
+    let neighbors: Vec<Vec<u32>> = (0..values.len())
+        .map(|point| half_edges.point_neighbors(point as u32))
+        .collect();
+    for _ in 0..iterations {
+        let next = neighbors
+            .iter()
+            .enumerate()
+            .map(|(point, around)| {
+                let sum: f32 = around.iter().map(|other| values[*other as usize]).sum();
+                (values[point] + sum) / (around.len() + 1) as f32
+            })
+            .collect();
+        *values = next;
+    }
+}
+
+fn write_point_float(
+    mesh: &mut Mesh,
+    attr: &str,
+    values: Vec<f32>,
+    label: &str,
+) -> Result<(), String> {
+    let attr = attr.trim();
+    if attr.is_empty() {
+        return Err(format!("{} requires an attribute name", label));
+    }
+    mesh.set_attribute(
+        AttributeDomain::Point,
+        attr,
+        AttributeStorage::Float(values),
+    )
+    .map_err(|err| format!("{} attribute error: {:?}", label, err))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        curvature_mask, geodesic_distance, slope_mask, CurvatureMode, CurvatureSettings,
+        GeodesicSettings, SlopeSettings,
+    };
+    use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage};
+    use crate::mesh::{make_grid, make_uv_sphere};
+
+    fn point_floats(mesh: &crate::mesh::Mesh, name: &str) -> Vec<f32> {
+        match mesh.attribute(AttributeDomain::Point, name) {
+            Some(AttributeRef::Float(values)) => values.to_vec(),
+            _ => panic!("missing {}", name),
+        }
+    }
+
+    #[test]
+    fn curvature_and_slope_masks_follow_shape() {
+        let sphere = make_uv_sphere(1.0, 12, 16);
+        let settings = CurvatureSettings {
+            mode: CurvatureMode::Signed,
+            scale: 1.0,
+            smooth_iterations: 2,
+            attr: "curvature".to_string(),
+        };
+        let curved = curvature_mask(&sphere, &settings).unwrap();
+        let values = point_floats(&curved, "curvature");
+        assert!(values.iter().all(|value| *value > 0.5));
+        let concave = curvature_mask(
+            &sphere,
+            &CurvatureSettings {
+                mode: CurvatureMode::Concave,
+                ..settings
+            },
+        )
+        .unwrap();
+        assert!(point_floats(&concave, "curvature")
+            .iter()
+            .all(|value| *value == 0.0));
+
+        let slope = slope_mask(
+            &sphere,
+            &SlopeSettings {
+                up: [0.0, 1.0, 0.0],
+                min_angle: 0.0,
+                max_angle: 90.0,
+                smooth_iterations: 0,
+                attr: "slope".to_string(),
+            },
+        )
+        .unwrap();
+        let values = point_floats(&slope, "slope");
+        assert!(values.iter().all(|value| (0.0..=1.0).contains(value)));
+        assert!(values.iter().any(|value| *value < 0.1));
+        assert!(values.iter().any(|value| *value > 0.9));
+    }
+
+    #[test]
+    fn geodesic_distance_grows_from_seeds() {
+        let mut grid = make_grid([4.0, 4.0], [4, 4]);
+        let seeds = (0..grid.positions.len() as i32)
+            .map(|point| i32::from(point == 0))
+            .collect();
+        grid.set_attribute(AttributeDomain::Point, "seed", AttributeStorage::Int(seeds))
+            .unwrap();
+        let settings = GeodesicSettings {
+            group: "seed".to_string(),
+            normalize: false,
+            attr: "dist".to_string(),
+        };
+        let measured = geodesic_distance(&grid, &settings).unwrap();
+        let values = point_floats(&measured, "dist");
+        assert_eq!(values[0], 0.0);
+        // The far corner is eight unit edges away along the grid lines.
+        let far = values.iter().copied().fold(0.0, f32::max);
+        assert!((far - 8.0).abs() < 1.0e-4);
+
+        let normalized = geodesic_distance(
+            &grid,
+            &GeodesicSettings {
+                normalize: true,
+                ..settings.clone()
+            },
+        )
+        .unwrap();
+        assert!(point_floats(&normalized, "dist")
+            .iter()
+            .all(|value| (0.0..=1.0).contains(value)));
+        assert!(geodesic_distance(
+            &grid,
+            &GeodesicSettings {
+                group: "missing".to_string(),
+                ..settings
+            }
+        )
+        .is_err());
+
+        // Distance crosses the unwelded seam of a UV sphere.
+        let mut sphere = make_uv_sphere(1.0, 8, 12);
+        let seeds = (0..sphere.positions.len() as i32)
+            .map(|point| i32::from(point == 0))
+            .collect();
+        sphere
+            .set_attribute(AttributeDomain::Point, "seed", AttributeStorage::Int(seeds))
+            .unwrap();
+        let measured = geodesic_distance(
+            &sphere,
+            &GeodesicSettings {
+                group: "seed".to_string(),
+                normalize: false,
+                attr: "dist".to_string(),
+            },
+        )
+        .unwrap();
+        let values = point_floats(&measured, "dist");
+        assert!(values.iter().all(|value| *value < 4.0));
+    }
+}
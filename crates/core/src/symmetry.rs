@@ -0,0 +1,155 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::graph::NodeParams;
+use crate::mesh::Mesh;
+
+const POSITION_QUANTUM: f32 = 1.0e4;
+const PLANE_EPSILON: f32 = 1.0e-5;
+
+pub(crate) const MIRROR_PARAM: &str = "mirror";
+pub(crate) const MIRROR_AXIS_PARAM: &str = "mirror_axis";
+pub(crate) const MIRROR_OFFSET_PARAM: &str = "mirror_offset";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SymmetryAxis {
+    #[default]
+    X,
+    Y,
+    Z,
+}
+
+impl SymmetryAxis {
+    pub fn index(self) -> usize {
+        match self {
+            SymmetryAxis::X => 0,
+            SymmetryAxis::Y => 1,
+            SymmetryAxis::Z => 2,
+        }
+    }
+
+    pub fn from_index(index: i32) -> Self {
+        match index {
+            1 => SymmetryAxis::Y,
+            2 => SymmetryAxis::Z,
+            _ => SymmetryAxis::X,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            SymmetryAxis::X => "X",
+            SymmetryAxis::Y => "Y",
+            SymmetryAxis::Z => "Z",
+        }
+    }
+}
+
+// Plane `position[axis] == offset`. The positive side is the one being edited; the
+// negative side is rebuilt from it.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SymmetryPlane {
+    pub axis: SymmetryAxis,
+    pub offset: f32,
+}
+
+impl SymmetryPlane {
+    // Plane as normal + distance, the form the viewport expects.
+    pub fn equation(&self) -> [f32; 4] {
+        let mut plane = [0.0, 0.0, 0.0, self.offset];
+        plane[self.axis.index()] = 1.0;
+        plane
+    }
+
+    // The plane injected by param resolution for nodes that have `mirror` enabled.
+    pub(crate) fn from_params(params: &NodeParams) -> Option<Self> {
+        if !params.values.contains_key(MIRROR_AXIS_PARAM) {
+            return None;
+        }
+        Some(Self {
+            axis: SymmetryAxis::from_index(params.get_int(MIRROR_AXIS_PARAM, 0)),
+            offset: params.get_float(MIRROR_OFFSET_PARAM, 0.0),
+        })
+    }
+
+    fn reflect_point(&self, mut p: [f32; 3]) -> [f32; 3] {
+        let axis = self.axis.index();
+        p[axis] = 2.0 * self.offset - p[axis];
+        p
+    }
+
+    fn reflect_vector(&self, mut v: [f32; 3]) -> [f32; 3] {
+        v[self.axis.index()] *= -1.0;
+        v
+    }
+}
+
+// Post-transform for point deformers: points on the negative side take the reflected
+// result of their counterpart on the positive side, and points on the plane stay on it.
+// Topology-changing outputs and points without a counterpart are left as computed.
+pub fn mirror_deformation(source: &Mesh, mut output: Mesh, plane: SymmetryPlane) -> Mesh {
+    if source.positions.len() != output.positions.len() {
+        return output;
+    }
+    let axis = plane.axis.index();
+    let key = |p: [f32; 3]| p.map(|v| (v * POSITION_QUANTUM).round() as i64);
+    let mut positive = HashMap::new();
+    for (index, p) in source.positions.iter().enumerate() {
+        if p[axis] - plane.offset > -PLANE_EPSILON {
+            positive.entry(key(*p)).or_insert(index);
+        }
+    }
+
+    let deformed = output.positions.clone();
+    let normals = output
+        .normals
+        .clone()
+        .filter(|normals| normals.len() == deformed.len());
+    for (index, p) in source.positions.iter().enumerate() {
+        let side = p[axis] - plane.offset;
+        if side.abs() <= PLANE_EPSILON {
+            output.positions[index][axis] = plane.offset;
+            continue;
+        }
+        if side > 0.0 {
+            continue;
+        }
+        let Some(&mirror) = positive.get(&key(plane.reflect_point(*p))) else {
+            continue;
+        };
+        output.positions[index] = plane.reflect_point(deformed[mirror]);
+        if let (Some(normals), Some(out)) = (&normals, output.normals.as_mut()) {
+            out[index] = plane.reflect_vector(normals[mirror]);
+        }
+    }
+    output
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn negative_side_follows_positive_deformation() {
+        let source = Mesh::with_positions_indices(
+            vec![[-1.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.0]],
+            Vec::new(),
+        );
+        let deformed = Mesh::with_positions_indices(
+            vec![[-5.0, 5.0, 5.0], [1.5, 0.5, 0.25], [0.3, 1.0, 0.0]],
+            Vec::new(),
+        );
+        let plane = SymmetryPlane {
+            axis: SymmetryAxis::X,
+            offset: 0.0,
+        };
+        let mirrored = mirror_deformation(&source, deformed, plane);
+        assert_eq!(mirrored.positions[0], [-1.5, 0.5, 0.25]);
+        assert_eq!(mirrored.positions[1], [1.5, 0.5, 0.25]);
+        assert_eq!(mirrored.positions[2], [0.0, 1.0, 0.0]);
+        assert_eq!(plane.equation(), [1.0, 0.0, 0.0, 0.0]);
+    }
+}
@@ -0,0 +1,149 @@
+use serde::{Deserialize, Serialize};
+
+use crate::graph::{Graph, ParamValue};
+use crate::mesh::Mesh;
+use crate::param_expr::evaluate_param_expression;
+
+pub(crate) const UNIT_SCALE_PARAM: &str = "unit_scale";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LengthUnit {
+    #[default]
+    Meters,
+    Centimeters,
+}
+
+impl LengthUnit {
+    pub fn label(self) -> &'static str {
+        match self {
+            LengthUnit::Meters => "m",
+            LengthUnit::Centimeters => "cm",
+        }
+    }
+
+    pub fn units_per_meter(self) -> f32 {
+        match self {
+            LengthUnit::Meters => 1.0,
+            LengthUnit::Centimeters => 100.0,
+        }
+    }
+}
+
+// `scale` converts file units to project units: imports multiply by it and exports divide
+// by it, so a file written by grapho reads back at the same size.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct UnitSettings {
+    pub unit: LengthUnit,
+    pub scale: f32,
+}
+
+impl Default for UnitSettings {
+    fn default() -> Self {
+        Self {
+            unit: LengthUnit::Meters,
+            scale: 1.0,
+        }
+    }
+}
+
+impl UnitSettings {
+    pub fn import_scale(&self) -> f32 {
+        if self.scale.is_finite() && self.scale > 0.0 {
+            self.scale
+        } else {
+            1.0
+        }
+    }
+
+    pub fn export_scale(&self) -> f32 {
+        1.0 / self.import_scale()
+    }
+
+    pub fn format_length(&self, length: f32) -> String {
+        format!("{:.3} {}", length, self.unit.label())
+    }
+}
+
+pub fn scale_mesh(mesh: &mut Mesh, scale: f32) {
+    if scale == 1.0 {
+        return;
+    }
+    for p in &mut mesh.positions {
+        *p = p.map(|v| v * scale);
+    }
+}
+
+// Text typed into a numeric field: a parameter expression ("2*pi") with an optional unit
+// suffix ("90deg", "10cm"). Angles land in degrees for `*_deg`/angle parameters and in
+// radians otherwise; lengths land in the project unit.
+pub fn parse_numeric_entry(text: &str, key: &str, unit: LengthUnit) -> Result<f32, String> {
+    let text = text.trim();
+    let body = text.trim_end_matches(|c: char| c.is_ascii_alphabetic() || c == '°');
+    let suffix = &text[body.len()..];
+    let body = body.trim_end();
+    let after_number = body.ends_with(|c: char| c.is_ascii_digit() || c == '.' || c == ')');
+    let degrees = key.ends_with("_deg") || key.contains("angle");
+    let scale = match suffix {
+        _ if !after_number => None,
+        "deg" | "°" if degrees => Some(1.0),
+        "deg" | "°" => Some(std::f32::consts::PI / 180.0),
+        "rad" if degrees => Some(180.0 / std::f32::consts::PI),
+        "rad" => Some(1.0),
+        "mm" => Some(unit.units_per_meter() / 1000.0),
+        "cm" => Some(unit.units_per_meter() / 100.0),
+        "m" => Some(unit.units_per_meter()),
+        _ => None,
+    };
+    let (expression, scale) = match scale {
+        Some(scale) => (body, scale),
+        None => (text, 1.0),
+    };
+    match evaluate_param_expression(expression, &ParamValue::Float(0.0), &Graph::default())? {
+        ParamValue::Float(value) => Ok(value * scale),
+        other => Err(format!("expected a number, got {}", other.type_name())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn numeric_entry_applies_unit_suffixes() {
+        let parse = |text, key| parse_numeric_entry(text, key, LengthUnit::Meters).unwrap();
+        assert_eq!(parse("90deg", "rotate_deg"), 90.0);
+        assert!((parse("90 deg", "angle") - 90.0).abs() < 1e-4);
+        assert!((parse("90deg", "twist") - std::f32::consts::FRAC_PI_2).abs() < 1e-6);
+        assert!((parse("2*pi", "twist") - std::f32::consts::TAU).abs() < 1e-6);
+        assert!((parse("10cm", "width") - 0.1).abs() < 1e-6);
+        assert_eq!(
+            parse_numeric_entry("1.5m", "width", LengthUnit::Centimeters),
+            Ok(150.0)
+        );
+        assert_eq!(parse("(1+2)*3", "width"), 9.0);
+        assert!(parse_numeric_entry("3 apples", "width", LengthUnit::Meters).is_err());
+    }
+
+    #[test]
+    fn export_undoes_import_scale() {
+        let units = UnitSettings {
+            unit: LengthUnit::Centimeters,
+            scale: 100.0,
+        };
+        let mut mesh = Mesh::with_positions_indices(vec![[0.5, 1.0, -2.0]], Vec::new());
+        scale_mesh(&mut mesh, units.import_scale());
+        assert_eq!(mesh.positions[0], [50.0, 100.0, -200.0]);
+        scale_mesh(&mut mesh, units.export_scale());
+        assert_eq!(mesh.positions[0], [0.5, 1.0, -2.0]);
+        assert_eq!(
+            UnitSettings {
+                scale: 0.0,
+                ..units
+            }
+            .import_scale(),
+            1.0
+        );
+    }
+}
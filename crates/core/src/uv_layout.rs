@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::attributes::{AttributeDomain, AttributeRef};
+use crate::content_hash::quantize;
+use crate::mesh::Mesh;
+
+const OVERLAP_EPSILON: f32 = 1.0e-6;
+
+type UvKey = (i64, i64);
+
+#[derive(Debug, Clone, Default)]
+pub struct UvLayout {
+    pub triangles: Vec<[[f32; 2]; 3]>,
+    pub islands: Vec<usize>,
+    pub island_count: usize,
+    pub overlapping: Vec<bool>,
+    pub out_of_bounds: Vec<bool>,
+}
+
+impl UvLayout {
+    pub fn overlap_count(&self) -> usize {
+        self.overlapping.iter().filter(|flag| **flag).count()
+    }
+
+    pub fn out_of_bounds_count(&self) -> usize {
+        self.out_of_bounds.iter().filter(|flag| **flag).count()
+    }
+}
+
+pub fn uv_layout(mesh: &Mesh) -> Option<UvLayout> {
+    let corner_uvs = corner_uvs(mesh)?;
+    let (corners, _) = mesh.triangulation();
+    let triangles: Vec<[[f32; 2]; 3]> = corners
+        .chunks_exact(3)
+        .map(|tri| [corner_uvs[tri[0]], corner_uvs[tri[1]], corner_uvs[tri[2]]])
+        .collect();
+    let (islands, island_count) = find_islands(&triangles);
+    let overlapping = find_overlaps(&triangles);
+    let out_of_bounds = triangles
+        .iter()
+        .map(|tri| {
+            tri.iter()
+                .flatten()
+                .any(|value| !(-OVERLAP_EPSILON..=1.0 + OVERLAP_EPSILON).contains(value))
+        })
+        .collect();
+    Some(UvLayout {
+        triangles,
+        islands,
+        island_count,
+        overlapping,
+        out_of_bounds,
+    })
+}
+
+fn corner_uvs(mesh: &Mesh) -> Option<Vec<[f32; 2]>> {
+    if let Some((domain, AttributeRef::Vec2(values))) = mesh.attribute_with_precedence("uv") {
+        match domain {
+            AttributeDomain::Vertex if values.len() == mesh.indices.len() => {
+                return Some(values.to_vec());
+            }
+            AttributeDomain::Point if values.len() == mesh.positions.len() => {
+                return Some(gather_points(mesh, values));
+            }
+            _ => {}
+        }
+    }
+    match &mesh.uvs {
+        Some(uvs) if uvs.len() == mesh.positions.len() => Some(gather_points(mesh, uvs)),
+        _ => None,
+    }
+}
+
+fn gather_points(mesh: &Mesh, values: &[[f32; 2]]) -> Vec<[f32; 2]> {
+    mesh.indices
+        .iter()
+        .map(|idx| values.get(*idx as usize).copied().unwrap_or([0.0, 0.0]))
+        .collect()
+}
+
+fn find_islands(triangles: &[[[f32; 2]; 3]]) -> (Vec<usize>, usize) {
+    let mut parent: Vec<usize> = (0..triangles.len()).collect();
+    fn find(parent: &mut [usize], mut x: usize) -> usize {
+        while parent[x] != x {
+            parent[x] = parent[parent[x]];
+            x = parent[x];
+        }
+        x
+    }
+
+    let key = |uv: [f32; 2]| (quantize(uv[0]), quantize(uv[1]));
+    let mut edges: HashMap<(UvKey, UvKey), usize> = HashMap::new();
+    for (index, tri) in triangles.iter().enumerate() {
+        for corner in 0..3 {
+            let a = key(tri[corner]);
+            let b = key(tri[(corner + 1) % 3]);
+            let edge = if a <= b { (a, b) } else { (b, a) };
+            match edges.get(&edge) {
+                Some(&other) => {
+                    let root_a = find(&mut parent, index);
+                    let root_b = find(&mut parent, other);
+                    if root_a != root_b {
+                        parent[root_a] = root_b;
+                    }
+                }
+                None => {
+                    edges.insert(edge, index);
+                }
+            }
+        }
+    }
+
+    let mut ids: HashMap<usize, usize> = HashMap::new();
+    let mut islands = Vec::with_capacity(triangles.len());
+    for index in 0..triangles.len() {
+        let root = find(&mut parent, index);
+        let next = ids.len();
+        islands.push(*ids.entry(root).or_insert(next));
+    }
+    (islands, ids.len())
+}
+
+fn find_overlaps(triangles: &[[[f32; 2]; 3]]) -> Vec<bool> {
+    let mut overlapping = vec![false; triangles.len()];
+    if triangles.len() < 2 {
+        return overlapping;
+    }
+    let bounds: Vec<[f32; 4]> = triangles.iter().map(triangle_bounds).collect();
+    let mut min = [f32::INFINITY; 2];
+    let mut max = [f32::NEG_INFINITY; 2];
+    for b in &bounds {
+        min = [min[0].min(b[0]), min[1].min(b[1])];
+        max = [max[0].max(b[2]), max[1].max(b[3])];
+    }
+    if !min[0].is_finite() || !min[1].is_finite() || !max[0].is_finite() || !max[1].is_finite() {
+        return overlapping;
+    }
+
+    let resolution = ((triangles.len() as f32).sqrt().ceil() as usize).clamp(1, 256);
+    let extent = [
+        (max[0] - min[0]).max(f32::EPSILON),
+        (max[1] - min[1]).max(f32::EPSILON),
+    ];
+    let cell = |value: f32, axis: usize| -> usize {
+        (((value - min[axis]) / extent[axis]) * resolution as f32)
+            .clamp(0.0, resolution as f32 - 1.0) as usize
+    };
+    let mut grid: Vec<Vec<usize>> = vec![Vec::new(); resolution * resolution];
+    for (index, b) in bounds.iter().enumerate() {
+        for y in cell(b[1], 1)..=cell(b[3], 1) {
+            for x in cell(b[0], 0)..=cell(b[2], 0) {
+                grid[y * resolution + x].push(index);
+            }
+        }
+    }
+
+    let mut tested: HashSet<(usize, usize)> = HashSet::new();
+    for bucket in &grid {
+        for (i, &a) in bucket.iter().enumerate() {
+            for &b in &bucket[i + 1..] {
+                if (overlapping[a] && overlapping[b]) || !bounds_overlap(&bounds[a], &bounds[b]) {
+                    continue;
+                }
+                if !tested.insert((a.min(b), a.max(b))) {
+                    continue;
+                }
+                if triangles_overlap(&triangles[a], &triangles[b]) {
+                    overlapping[a] = true;
+                    overlapping[b] = true;
+                }
+            }
+        }
+    }
+    overlapping
+}
+
+fn triangle_bounds(tri: &[[f32; 2]; 3]) -> [f32; 4] {
+    [
+        tri[0][0].min(tri[1][0]).min(tri[2][0]),
+        tri[0][1].min(tri[1][1]).min(tri[2][1]),
+        tri[0][0].max(tri[1][0]).max(tri[2][0]),
+        tri[0][1].max(tri[1][1]).max(tri[2][1]),
+    ]
+}
+
+fn bounds_overlap(a: &[f32; 4], b: &[f32; 4]) -> bool {
+    a[0] < b[2] && b[0] < a[2] && a[1] < b[3] && b[1] < a[3]
+}
+
+fn triangles_overlap(a: &[[f32; 2]; 3], b: &[[f32; 2]; 3]) -> bool {
+    if signed_area(a).abs() <= OVERLAP_EPSILON || signed_area(b).abs() <= OVERLAP_EPSILON {
+        return false;
+    }
+    for tri in [a, b] {
+        for corner in 0..3 {
+            let p = tri[corner];
+            let q = tri[(corner + 1) % 3];
+            let axis = [q[1] - p[1], p[0] - q[0]];
+            let (min_a, max_a) = project(a, axis);
+            let (min_b, max_b) = project(b, axis);
+            let scale = (axis[0] * axis[0] + axis[1] * axis[1]).sqrt();
+            if max_a.min(max_b) - min_a.max(min_b) <= OVERLAP_EPSILON * scale {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+fn project(tri: &[[f32; 2]; 3], axis: [f32; 2]) -> (f32, f32) {
+    let mut min = f32::INFINITY;
+    let mut max = f32::NEG_INFINITY;
+    for p in tri {
+        let d = p[0] * axis[0] + p[1] * axis[1];
+        min = min.min(d);
+        max = max.max(d);
+    }
+    (min, max)
+}
+
+fn signed_area(tri: &[[f32; 2]; 3]) -> f32 {
+    0.5 * ((tri[1][0] - tri[0][0]) * (tri[2][1] - tri[0][1])
+        - (tri[2][0] - tri[0][0]) * (tri[1][1] - tri[0][1]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::uv_layout;
+    use crate::attributes::{AttributeDomain, AttributeStorage};
+    use crate::mesh::Mesh;
+
+    fn quad_mesh(uvs: Vec<[f32; 2]>) -> Mesh {
+        let positions = vec![
+            [0.0, 0.0, 0.0],
+            [1.0, 0.0, 0.0],
+            [1.0, 0.0, 1.0],
+            [0.0, 0.0, 1.0],
+            [2.0, 0.0, 0.0],
+            [3.0, 0.0, 0.0],
+            [3.0, 0.0, 1.0],
+            [2.0, 0.0, 1.0],
+        ];
+        let mut mesh = Mesh::with_polygons(positions, vec![0, 1, 2, 3, 4, 5, 6, 7], vec![4, 4]);
+        mesh.set_attribute(AttributeDomain::Vertex, "uv", AttributeStorage::Vec2(uvs))
+            .unwrap();
+        mesh
+    }
+
+    #[test]
+    fn separate_islands_do_not_overlap() {
+        let mesh = quad_mesh(vec![
+            [0.0, 0.0],
+            [0.4, 0.0],
+            [0.4, 0.4],
+            [0.0, 0.4],
+            [0.5, 0.5],
+            [0.9, 0.5],
+            [0.9, 0.9],
+            [0.5, 0.9],
+        ]);
+        let layout = uv_layout(&mesh).unwrap();
+        assert_eq!(layout.triangles.len(), 4);
+        assert_eq!(layout.island_count, 2);
+        assert_eq!(layout.islands[0], layout.islands[1]);
+        assert_ne!(layout.islands[0], layout.islands[2]);
+        assert_eq!(layout.overlap_count(), 0);
+        assert_eq!(layout.out_of_bounds_count(), 0);
+    }
+
+    #[test]
+    fn stacked_islands_overlap_and_flag_out_of_bounds() {
+        let mesh = quad_mesh(vec![
+            [0.0, 0.0],
+            [0.5, 0.0],
+            [0.5, 0.5],
+            [0.0, 0.5],
+            [0.25, 0.25],
+            [1.25, 0.25],
+            [1.25, 0.75],
+            [0.25, 0.75],
+        ]);
+        let layout = uv_layout(&mesh).unwrap();
+        assert!(layout.overlapping[2] || layout.overlapping[3]);
+        assert!(layout.overlapping[0] || layout.overlapping[1]);
+        assert_eq!(layout.out_of_bounds, vec![false, false, true, true]);
+    }
+
+    #[test]
+    fn mesh_without_uvs_has_no_layout() {
+        let mesh = crate::mesh::make_box([1.0, 1.0, 1.0]);
+        assert!(uv_layout(&mesh).is_none());
+    }
+}
@@ -4,6 +4,7 @@ use glam::Vec3;
 
 use crate::attributes::{AttributeDomain, AttributeRef, AttributeStorage, AttributeType};
 use crate::mesh::Mesh;
+use crate::noise::{self, Fbm, NoiseBasis};
 
 #[derive(Debug, Clone)]
 struct Program {
@@ -12,13 +13,21 @@ struct Program {
 
 #[derive(Debug, Clone)]
 enum Statement {
-    Assign { target: String, expr: Expr },
+    // `@name`, `@name.mask`, optionally with a compound operator such as `+=`.
+    Assign {
+        target: String,
+        mask: Option<String>,
+        op: Option<BinaryOp>,
+        expr: Expr,
+    },
 }
 
 #[derive(Debug, Clone)]
-enum Expr {
+pub(crate) enum Expr {
     Literal(Value),
     Attr(String),
+    Var(String),
+    Channel(String),
     Swizzle {
         expr: Box<Expr>,
         mask: String,
@@ -38,14 +47,34 @@ enum Expr {
     },
 }
 
+impl Expr {
+    // Every `ch()` path the expression reads, in source order.
+    pub(crate) fn channels(&self, out: &mut Vec<String>) {
+        match self {
+            Expr::Literal(_) | Expr::Attr(_) | Expr::Var(_) => {}
+            Expr::Channel(path) => out.push(path.clone()),
+            Expr::Swizzle { expr, .. } | Expr::Unary { expr, .. } => expr.channels(out),
+            Expr::Binary { left, right, .. } => {
+                left.channels(out);
+                right.channels(out);
+            }
+            Expr::Call { args, .. } => {
+                for arg in args {
+                    arg.channels(out);
+                }
+            }
+        }
+    }
+}
+
 #[derive(Debug, Clone, Copy)]
-enum UnaryOp {
+pub(crate) enum UnaryOp {
     Pos,
     Neg,
 }
 
 #[derive(Debug, Clone, Copy)]
-enum BinaryOp {
+pub(crate) enum BinaryOp {
     Add,
     Sub,
     Mul,
@@ -53,7 +82,7 @@ enum BinaryOp {
 }
 
 #[derive(Debug, Clone, Copy)]
-enum Value {
+pub(crate) enum Value {
     Float(f32),
     Vec2([f32; 2]),
     Vec3([f32; 3]),
@@ -79,7 +108,9 @@ impl Value {
 enum Token {
     Ident(String),
     Number(f32),
+    Str(String),
     At,
+    Dollar,
     Dot,
     Plus,
     Minus,
@@ -92,7 +123,14 @@ enum Token {
     Semicolon,
 }
 
-pub fn apply_wrangle(mesh: &mut Mesh, domain: AttributeDomain, code: &str) -> Result<(), String> {
+// `frame` and `seconds` are what `$F` and `$T` read.
+pub fn apply_wrangle(
+    mesh: &mut Mesh,
+    domain: AttributeDomain,
+    code: &str,
+    frame: f32,
+    seconds: f32,
+) -> Result<(), String> {
     let program = parse_program(code)?;
     if program.statements.is_empty() {
         return Ok(());
@@ -102,7 +140,7 @@ pub fn apply_wrangle(mesh: &mut Mesh, domain: AttributeDomain, code: &str) -> Re
         return Ok(());
     }
 
-    let mut ctx = WrangleContext::new(mesh, domain);
+    let mut ctx = WrangleContext::new(mesh, domain, frame, seconds);
     for stmt in program.statements {
         ctx.apply_statement(stmt)?;
     }
@@ -115,6 +153,8 @@ struct WrangleContext<'a> {
     mesh: &'a Mesh,
     domain: AttributeDomain,
     len: usize,
+    frame: f32,
+    seconds: f32,
     written: HashMap<String, AttributeStorage>,
     point_normals: Option<Vec<[f32; 3]>>,
     vertex_normals: Option<Vec<[f32; 3]>>,
@@ -125,12 +165,14 @@ struct WrangleContext<'a> {
 }
 
 impl<'a> WrangleContext<'a> {
-    fn new(mesh: &'a Mesh, domain: AttributeDomain) -> Self {
+    fn new(mesh: &'a Mesh, domain: AttributeDomain, frame: f32, seconds: f32) -> Self {
         let len = mesh.attribute_domain_len(domain);
         Self {
             mesh,
             domain,
             len,
+            frame,
+            seconds,
             written: HashMap::new(),
             point_normals: None,
             vertex_normals: None,
@@ -143,11 +185,36 @@ impl<'a> WrangleContext<'a> {
 
     fn apply_statement(&mut self, stmt: Statement) -> Result<(), String> {
         match stmt {
-            Statement::Assign { target, expr } => self.assign(target, expr),
+            Statement::Assign {
+                target,
+                mask,
+                op,
+                expr,
+            } => {
+                // `@P.y += e` reads as `@P.y = @P.y + (e)`.
+                let expr = match op {
+                    Some(op) => {
+                        let mut current = Expr::Attr(target.clone());
+                        if let Some(mask) = &mask {
+                            current = Expr::Swizzle {
+                                expr: Box::new(current),
+                                mask: mask.clone(),
+                            };
+                        }
+                        Expr::Binary {
+                            op,
+                            left: Box::new(current),
+                            right: Box::new(expr),
+                        }
+                    }
+                    None => expr,
+                };
+                self.assign(target, mask, expr)
+            }
         }
     }
 
-    fn assign(&mut self, target: String, expr: Expr) -> Result<(), String> {
+    fn assign(&mut self, target: String, mask: Option<String>, expr: Expr) -> Result<(), String> {
         if target == "P" && self.domain != AttributeDomain::Point {
             return Err("Wrangle can only write @P in Point mode".to_string());
         }
@@ -157,7 +224,11 @@ impl<'a> WrangleContext<'a> {
 
         let mut values = Vec::with_capacity(self.len.max(1));
         for idx in 0..self.len.max(1) {
-            let value = self.eval_expr(&expr, idx)?;
+            let mut value = self.eval_expr(&expr, idx)?;
+            if let Some(mask) = &mask {
+                let current = self.read_attr(&target, idx)?;
+                value = write_components(current, mask, value)?;
+            }
             values.push(value);
         }
 
@@ -186,125 +257,6 @@ impl<'a> WrangleContext<'a> {
             .map(|attr| attr.data_type())
     }
 
-    fn eval_expr(&mut self, expr: &Expr, idx: usize) -> Result<Value, String> {
-        match expr {
-            Expr::Literal(value) => Ok(*value),
-            Expr::Attr(name) => self.read_attr(name, idx),
-            Expr::Swizzle { expr, mask } => {
-                let value = self.eval_expr(expr, idx)?;
-                swizzle_value(value, mask)
-            }
-            Expr::Unary { op, expr } => {
-                let value = self.eval_expr(expr, idx)?;
-                Ok(match op {
-                    UnaryOp::Pos => value,
-                    UnaryOp::Neg => value.negate(),
-                })
-            }
-            Expr::Binary { op, left, right } => {
-                let a = self.eval_expr(left, idx)?;
-                let b = self.eval_expr(right, idx)?;
-                match op {
-                    BinaryOp::Add => add_values(a, b),
-                    BinaryOp::Sub => sub_values(a, b),
-                    BinaryOp::Mul => mul_values(a, b),
-                    BinaryOp::Div => div_values(a, b),
-                }
-            }
-            Expr::Call { name, args } => self.eval_call(name, args, idx),
-        }
-    }
-
-    fn eval_call(&mut self, name: &str, args: &[Expr], idx: usize) -> Result<Value, String> {
-        let name = name.to_lowercase();
-        match name.as_str() {
-            "sin" | "cos" | "tan" | "abs" | "floor" | "ceil" => {
-                let value = self.eval_args(args, idx, 1)?[0];
-                Ok(match name.as_str() {
-                    "sin" => map_value(value, f32::sin),
-                    "cos" => map_value(value, f32::cos),
-                    "tan" => map_value(value, f32::tan),
-                    "abs" => map_value(value, f32::abs),
-                    "floor" => map_value(value, f32::floor),
-                    _ => map_value(value, f32::ceil),
-                })
-            }
-            "pow" => {
-                let values = self.eval_args(args, idx, 2)?;
-                pow_values(values[0], values[1])
-            }
-            "min" => {
-                let values = self.eval_args(args, idx, 2)?;
-                min_values(values[0], values[1])
-            }
-            "max" => {
-                let values = self.eval_args(args, idx, 2)?;
-                max_values(values[0], values[1])
-            }
-            "clamp" => {
-                let values = self.eval_args(args, idx, 3)?;
-                clamp_values(values[0], values[1], values[2])
-            }
-            "lerp" => {
-                let values = self.eval_args(args, idx, 3)?;
-                lerp_values(values[0], values[1], values[2])
-            }
-            "len" => {
-                let value = self.eval_args(args, idx, 1)?[0];
-                Ok(Value::Float(length_value(value)))
-            }
-            "dot" => {
-                let values = self.eval_args(args, idx, 2)?;
-                let dot = dot_values(values[0], values[1])?;
-                Ok(Value::Float(dot))
-            }
-            "normalize" => {
-                let value = self.eval_args(args, idx, 1)?[0];
-                normalize_value(value)
-            }
-            "vec2" => build_vec(args, idx, 2, self),
-            "vec3" => build_vec(args, idx, 3, self),
-            "vec4" => build_vec(args, idx, 4, self),
-            _ => Err(format!("Unknown function '{}'", name)),
-        }
-    }
-
-    fn eval_args(
-        &mut self,
-        args: &[Expr],
-        idx: usize,
-        expected: usize,
-    ) -> Result<Vec<Value>, String> {
-        if args.len() != expected {
-            return Err(format!(
-                "Expected {} argument(s), got {}",
-                expected,
-                args.len()
-            ));
-        }
-        let mut out = Vec::with_capacity(args.len());
-        for arg in args {
-            out.push(self.eval_expr(arg, idx)?);
-        }
-        Ok(out)
-    }
-
-    fn read_attr(&mut self, name: &str, idx: usize) -> Result<Value, String> {
-        if let Some(storage) = self.written.get(name) {
-            return value_from_storage(storage, idx);
-        }
-        if name == "P" {
-            return Ok(Value::Vec3(self.read_p(idx)));
-        }
-        if name == "N" {
-            return Ok(Value::Vec3(self.read_n(idx)));
-        }
-        if let Some(attr) = self.mesh.attribute(self.domain, name) {
-            return value_from_attr_ref(attr, idx);
-        }
-        Ok(Value::Float(0.0))
-    }
-
     fn read_p(&mut self, idx: usize) -> [f32; 3] {
         match self.domain {
             AttributeDomain::Point => self.mesh.positions.get(idx).copied().unwrap_or([0.0; 3]),
@@ -390,14 +342,16 @@ impl<'a> WrangleContext<'a> {
             return;
         }
         let mut normals = Vec::new();
-        for tri in self.mesh.indices.chunks_exact(3) {
-            let i0 = tri[0] as usize;
-            let i1 = tri[1] as usize;
-            let i2 = tri[2] as usize;
-            let p0 = Vec3::from(self.mesh.positions.get(i0).copied().unwrap_or([0.0; 3]));
-            let p1 = Vec3::from(self.mesh.positions.get(i1).copied().unwrap_or([0.0; 3]));
-            let p2 = Vec3::from(self.mesh.positions.get(i2).copied().unwrap_or([0.0; 3]));
-            let n = (p1 - p0).cross(p2 - p0);
+        for range in self.mesh.face_ranges() {
+            let face = &self.mesh.indices[range];
+            let n = if face
+                .iter()
+                .all(|idx| (*idx as usize) < self.mesh.positions.len())
+            {
+                self.mesh.polygon_normal(face)
+            } else {
+                Vec3::ZERO
+            };
             let n = if n.length_squared() > 0.0 {
                 n.normalize().to_array()
             } else {
@@ -413,14 +367,21 @@ impl<'a> WrangleContext<'a> {
             return;
         }
         let mut centers = Vec::new();
-        for tri in self.mesh.indices.chunks_exact(3) {
-            let i0 = tri[0] as usize;
-            let i1 = tri[1] as usize;
-            let i2 = tri[2] as usize;
-            let p0 = Vec3::from(self.mesh.positions.get(i0).copied().unwrap_or([0.0; 3]));
-            let p1 = Vec3::from(self.mesh.positions.get(i1).copied().unwrap_or([0.0; 3]));
-            let p2 = Vec3::from(self.mesh.positions.get(i2).copied().unwrap_or([0.0; 3]));
-            let center = (p0 + p1 + p2) / 3.0;
+        for range in self.mesh.face_ranges() {
+            let face = &self.mesh.indices[range];
+            let mut center = Vec3::ZERO;
+            for idx in face {
+                center += Vec3::from(
+                    self.mesh
+                        .positions
+                        .get(*idx as usize)
+                        .copied()
+                        .unwrap_or([0.0; 3]),
+                );
+            }
+            if !face.is_empty() {
+                center /= face.len() as f32;
+            }
             centers.push(center.to_array());
         }
         self.prim_centers = Some(centers);
@@ -459,6 +420,175 @@ impl<'a> WrangleContext<'a> {
     }
 }
 
+pub(crate) trait ExprContext {
+    fn read_attr(&mut self, name: &str, idx: usize) -> Result<Value, String>;
+
+    fn read_var(&mut self, name: &str) -> Result<Value, String>;
+
+    fn read_channel(&mut self, path: &str) -> Result<Value, String>;
+
+    fn eval_expr(&mut self, expr: &Expr, idx: usize) -> Result<Value, String> {
+        match expr {
+            Expr::Literal(value) => Ok(*value),
+            Expr::Attr(name) => self.read_attr(name, idx),
+            Expr::Var(name) => self.read_var(name),
+            Expr::Channel(path) => self.read_channel(path),
+            Expr::Swizzle { expr, mask } => {
+                let value = self.eval_expr(expr, idx)?;
+                swizzle_value(value, mask)
+            }
+            Expr::Unary { op, expr } => {
+                let value = self.eval_expr(expr, idx)?;
+                Ok(match op {
+                    UnaryOp::Pos => value,
+                    UnaryOp::Neg => value.negate(),
+                })
+            }
+            Expr::Binary { op, left, right } => {
+                let a = self.eval_expr(left, idx)?;
+                let b = self.eval_expr(right, idx)?;
+                match op {
+                    BinaryOp::Add => add_values(a, b),
+                    BinaryOp::Sub => sub_values(a, b),
+                    BinaryOp::Mul => mul_values(a, b),
+                    BinaryOp::Div => div_values(a, b),
+                }
+            }
+            Expr::Call { name, args } => self.eval_call(name, args, idx),
+        }
+    }
+
+    fn eval_call(&mut self, name: &str, args: &[Expr], idx: usize) -> Result<Value, String> {
+        let name = name.to_lowercase();
+        match name.as_str() {
+            "sin" | "cos" | "tan" | "abs" | "floor" | "ceil" => {
+                let value = self.eval_args(args, idx, 1)?[0];
+                Ok(match name.as_str() {
+                    "sin" => map_value(value, f32::sin),
+                    "cos" => map_value(value, f32::cos),
+                    "tan" => map_value(value, f32::tan),
+                    "abs" => map_value(value, f32::abs),
+                    "floor" => map_value(value, f32::floor),
+                    _ => map_value(value, f32::ceil),
+                })
+            }
+            "pow" => {
+                let values = self.eval_args(args, idx, 2)?;
+                pow_values(values[0], values[1])
+            }
+            "min" => {
+                let values = self.eval_args(args, idx, 2)?;
+                min_values(values[0], values[1])
+            }
+            "max" => {
+                let values = self.eval_args(args, idx, 2)?;
+                max_values(values[0], values[1])
+            }
+            "clamp" => {
+                let values = self.eval_args(args, idx, 3)?;
+                clamp_values(values[0], values[1], values[2])
+            }
+            "lerp" => {
+                let values = self.eval_args(args, idx, 3)?;
+                lerp_values(values[0], values[1], values[2])
+            }
+            "len" => {
+                let value = self.eval_args(args, idx, 1)?[0];
+                Ok(Value::Float(length_value(value)))
+            }
+            "dot" => {
+                let values = self.eval_args(args, idx, 2)?;
+                let dot = dot_values(values[0], values[1])?;
+                Ok(Value::Float(dot))
+            }
+            "normalize" => {
+                let value = self.eval_args(args, idx, 1)?[0];
+                normalize_value(value)
+            }
+            "noise" | "snoise" | "worley" => {
+                let value = self.eval_args(args, idx, 1)?[0];
+                let p = Vec3::from(value_to_vec3(value));
+                Ok(Value::Float(match name.as_str() {
+                    "noise" => noise::perlin(p, 0),
+                    "snoise" => noise::simplex(p, 0),
+                    _ => noise::worley(p, 0),
+                }))
+            }
+            "curl" => {
+                let value = self.eval_args(args, idx, 1)?[0];
+                let fbm = Fbm {
+                    basis: NoiseBasis::Perlin,
+                    octaves: 1,
+                    ..Fbm::default()
+                };
+                Ok(Value::Vec3(
+                    fbm.curl(Vec3::from(value_to_vec3(value)), 0).to_array(),
+                ))
+            }
+            "vec2" => build_vec(args, idx, 2, self),
+            "vec3" => build_vec(args, idx, 3, self),
+            "vec4" => build_vec(args, idx, 4, self),
+            _ => Err(format!("Unknown function '{}'", name)),
+        }
+    }
+
+    fn eval_args(
+        &mut self,
+        args: &[Expr],
+        idx: usize,
+        expected: usize,
+    ) -> Result<Vec<Value>, String> {
+        if args.len() != expected {
+            return Err(format!(
+                "Expected {} argument(s), got {}",
+                expected,
+                args.len()
+            ));
+        }
+        let mut out = Vec::with_capacity(args.len());
+        for arg in args {
+            out.push(self.eval_expr(arg, idx)?);
+        }
+        Ok(out)
+    }
+}
+
+impl ExprContext for WrangleContext<'_> {
+    fn read_attr(&mut self, name: &str, idx: usize) -> Result<Value, String> {
+        if let Some(storage) = self.written.get(name) {
+            return value_from_storage(storage, idx);
+        }
+        if name == "P" {
+            return Ok(Value::Vec3(self.read_p(idx)));
+        }
+        if name == "N" {
+            return Ok(Value::Vec3(self.read_n(idx)));
+        }
+        if let Some(attr) = self.mesh.attribute(self.domain, name) {
+            return value_from_attr_ref(attr, idx);
+        }
+        Ok(Value::Float(0.0))
+    }
+
+    fn read_var(&mut self, name: &str) -> Result<Value, String> {
+        match name {
+            "F" => Ok(Value::Float(self.frame)),
+            "T" => Ok(Value::Float(self.seconds)),
+            _ => Err(format!(
+                "Parameter reference '${}' is only available in parameter expressions",
+                name
+            )),
+        }
+    }
+
+    fn read_channel(&mut self, path: &str) -> Result<Value, String> {
+        Err(format!(
+            "ch(\"{}\") is only available in parameter expressions",
+            path
+        ))
+    }
+}
+
 fn value_from_attr_ref(attr: AttributeRef<'_>, idx: usize) -> Result<Value, String> {
     match attr {
         AttributeRef::Float(values) => Ok(Value::Float(values.get(idx).copied().unwrap_or(0.0))),
@@ -565,34 +695,16 @@ fn apply_written(
 }
 
 fn compute_point_normals(mesh: &Mesh) -> Vec<[f32; 3]> {
-    if !mesh.indices.len().is_multiple_of(3) || mesh.positions.is_empty() {
+    if !mesh.has_valid_faces() || mesh.positions.is_empty() {
         return vec![];
     }
-    let mut accum = vec![Vec3::ZERO; mesh.positions.len()];
-    for tri in mesh.indices.chunks_exact(3) {
-        let i0 = tri[0] as usize;
-        let i1 = tri[1] as usize;
-        let i2 = tri[2] as usize;
-        let p0 = Vec3::from(mesh.positions.get(i0).copied().unwrap_or([0.0; 3]));
-        let p1 = Vec3::from(mesh.positions.get(i1).copied().unwrap_or([0.0; 3]));
-        let p2 = Vec3::from(mesh.positions.get(i2).copied().unwrap_or([0.0; 3]));
-        let normal = (p1 - p0).cross(p2 - p0);
-        accum[i0] += normal;
-        accum[i1] += normal;
-        accum[i2] += normal;
-    }
-
-    accum
-        .into_iter()
-        .map(|n| {
-            let len = n.length();
-            if len > 0.0 {
-                (n / len).to_array()
-            } else {
-                [0.0, 1.0, 0.0]
-            }
-        })
-        .collect()
+    let mut temp = Mesh::with_polygons(
+        mesh.positions.clone(),
+        mesh.indices.clone(),
+        mesh.face_counts.clone(),
+    );
+    temp.compute_normals();
+    temp.normals.unwrap_or_default()
 }
 
 fn map_value(value: Value, f: impl Fn(f32) -> f32) -> Value {
@@ -604,6 +716,16 @@ fn map_value(value: Value, f: impl Fn(f32) -> f32) -> Value {
     }
 }
 
+// Noise lookups take a position; scalars and vec2s are padded with zeros.
+fn value_to_vec3(value: Value) -> [f32; 3] {
+    match value {
+        Value::Float(v) => [v, 0.0, 0.0],
+        Value::Vec2(v) => [v[0], v[1], 0.0],
+        Value::Vec3(v) => v,
+        Value::Vec4(v) => [v[0], v[1], v[2]],
+    }
+}
+
 fn length_value(value: Value) -> f32 {
     match value {
         Value::Float(v) => v.abs(),
@@ -660,7 +782,7 @@ fn normalize_value(value: Value) -> Result<Value, String> {
     }
 }
 
-fn swizzle_value(value: Value, mask: &str) -> Result<Value, String> {
+pub(crate) fn swizzle_value(value: Value, mask: &str) -> Result<Value, String> {
     let mask = mask.to_ascii_lowercase();
     let count = mask.chars().count();
     if count == 0 || count > 4 {
@@ -685,6 +807,53 @@ fn swizzle_value(value: Value, mask: &str) -> Result<Value, String> {
     }
 }
 
+// `current` with the components named by `mask` replaced by `value`, one component per
+// mask letter, or the same scalar in each.
+fn write_components(current: Value, mask: &str, value: Value) -> Result<Value, String> {
+    let mut components: Vec<f32> = match current {
+        Value::Float(_) => {
+            return Err(format!(
+                "Cannot assign .{} of a float attribute; it must exist as a vector",
+                mask
+            ))
+        }
+        Value::Vec2(v) => v.to_vec(),
+        Value::Vec3(v) => v.to_vec(),
+        Value::Vec4(v) => v.to_vec(),
+    };
+    let sources: Vec<f32> = match value {
+        Value::Float(v) => vec![v; mask.len()],
+        Value::Vec2(v) => v.to_vec(),
+        Value::Vec3(v) => v.to_vec(),
+        Value::Vec4(v) => v.to_vec(),
+    };
+    if sources.len() != mask.len() {
+        return Err(format!(
+            "Cannot assign a {}-component value to .{}",
+            sources.len(),
+            mask
+        ));
+    }
+    for (ch, source) in mask.to_ascii_lowercase().chars().zip(sources) {
+        let idx = match ch {
+            'x' | 'r' => 0,
+            'y' | 'g' => 1,
+            'z' | 'b' => 2,
+            'w' | 'a' => 3,
+            _ => return Err(format!("Invalid swizzle component '{}'", ch)),
+        };
+        *components
+            .get_mut(idx)
+            .ok_or_else(|| "Swizzle component out of range".to_string())? = source;
+    }
+    Ok(match components.as_slice() {
+        [x, y] => Value::Vec2([*x, *y]),
+        [x, y, z] => Value::Vec3([*x, *y, *z]),
+        [x, y, z, w] => Value::Vec4([*x, *y, *z, *w]),
+        _ => unreachable!("vector values have 2-4 components"),
+    })
+}
+
 fn swizzle_from_slice(values: &[f32], indices: Vec<usize>) -> Result<Value, String> {
     for &idx in &indices {
         if idx >= values.len() {
@@ -789,11 +958,11 @@ fn binary_op(a: Value, b: Value, f: impl Fn(f32, f32) -> f32) -> Result<Value, S
     }
 }
 
-fn build_vec(
+fn build_vec<C: ExprContext + ?Sized>(
     args: &[Expr],
     idx: usize,
     size: usize,
-    ctx: &mut WrangleContext<'_>,
+    ctx: &mut C,
 ) -> Result<Value, String> {
     let values = if args.len() == 1 {
         vec![ctx.eval_expr(&args[0], idx)?; size]
@@ -839,6 +1008,26 @@ fn parse_program(code: &str) -> Result<Program, String> {
     Ok(Program { statements })
 }
 
+// Whether code reads the timeline through `$F` or `$T`. Code that does not tokenize
+// counts as not; it fails to cook anyway.
+pub(crate) fn reads_time(code: &str) -> bool {
+    tokenize(code).is_ok_and(|tokens| {
+        tokens.windows(2).any(|pair| {
+            matches!(pair, [Token::Dollar, Token::Ident(name)] if name == "F" || name == "T")
+        })
+    })
+}
+
+pub(crate) fn parse_expression(code: &str) -> Result<Expr, String> {
+    let tokens = tokenize(code)?;
+    let mut parser = Parser::new(tokens);
+    let expr = parser.parse_expr()?;
+    if !parser.is_end() {
+        return Err(format!("Unexpected token {:?}", parser.peek()));
+    }
+    Ok(expr)
+}
+
 fn tokenize(code: &str) -> Result<Vec<Token>, String> {
     let mut tokens = Vec::new();
     let chars: Vec<char> = code.chars().collect();
@@ -896,6 +1085,22 @@ fn tokenize(code: &str) -> Result<Vec<Token>, String> {
                 tokens.push(Token::At);
                 i += 1;
             }
+            '$' => {
+                tokens.push(Token::Dollar);
+                i += 1;
+            }
+            '"' => {
+                let start = i + 1;
+                i += 1;
+                while i < chars.len() && chars[i] != '"' {
+                    i += 1;
+                }
+                if i >= chars.len() {
+                    return Err("Unterminated string literal".to_string());
+                }
+                tokens.push(Token::Str(chars[start..i].iter().collect()));
+                i += 1;
+            }
             '.' => {
                 if i + 1 < chars.len() && chars[i + 1].is_ascii_digit() {
                     let start = i;
@@ -970,9 +1175,33 @@ impl Parser {
             Some(Token::Ident(name)) => name,
             _ => return Err("Expected attribute name after '@'".to_string()),
         };
+        let mask = if matches!(self.peek(), Some(Token::Dot)) {
+            self.pos += 1;
+            match self.next() {
+                Some(Token::Ident(mask)) => Some(mask),
+                _ => return Err("Expected swizzle mask after '.'".to_string()),
+            }
+        } else {
+            None
+        };
+        let op = match (self.peek(), self.tokens.get(self.pos + 1)) {
+            (Some(Token::Plus), Some(Token::Equal)) => Some(BinaryOp::Add),
+            (Some(Token::Minus), Some(Token::Equal)) => Some(BinaryOp::Sub),
+            (Some(Token::Star), Some(Token::Equal)) => Some(BinaryOp::Mul),
+            (Some(Token::Slash), Some(Token::Equal)) => Some(BinaryOp::Div),
+            _ => None,
+        };
+        if op.is_some() {
+            self.pos += 1;
+        }
         self.expect(Token::Equal)?;
         let expr = self.parse_expr()?;
-        Ok(Statement::Assign { target, expr })
+        Ok(Statement::Assign {
+            target,
+            mask,
+            op,
+            expr,
+        })
     }
 
     fn parse_expr(&mut self) -> Result<Expr, String> {
@@ -1083,8 +1312,20 @@ impl Parser {
                 Some(Token::Ident(name)) => Ok(Expr::Attr(name)),
                 _ => Err("Expected attribute name after '@'".to_string()),
             },
+            Some(Token::Dollar) => match self.next() {
+                Some(Token::Ident(name)) => Ok(Expr::Var(name)),
+                _ => Err("Expected parameter name after '$'".to_string()),
+            },
             Some(Token::Ident(name)) => {
-                if matches!(self.peek(), Some(Token::LParen)) {
+                if name == "ch" && matches!(self.peek(), Some(Token::LParen)) {
+                    self.pos += 1;
+                    let path = match self.next() {
+                        Some(Token::Str(path)) => path,
+                        _ => return Err("ch() expects a quoted parameter path".to_string()),
+                    };
+                    self.expect(Token::RParen)?;
+                    Ok(Expr::Channel(path))
+                } else if matches!(self.peek(), Some(Token::LParen)) {
                     self.pos += 1;
                     let mut args = Vec::new();
                     if !matches!(self.peek(), Some(Token::RParen)) {
@@ -1101,7 +1342,7 @@ impl Parser {
                     }
                     self.expect(Token::RParen)?;
                     Ok(Expr::Call { name, args })
-                } else if name == "PI" {
+                } else if name == "PI" || name == "pi" {
                     Ok(Expr::Literal(Value::Float(std::f32::consts::PI)))
                 } else if name == "E" {
                     Ok(Expr::Literal(Value::Float(std::f32::consts::E)))
@@ -1138,3 +1379,36 @@ impl Parser {
         Some(token)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::make_box;
+
+    #[test]
+    fn wrangle_assigns_components_and_compound_ops() {
+        let mut mesh = make_box([1.0, 1.0, 1.0]);
+        let before = mesh.positions.clone();
+        apply_wrangle(
+            &mut mesh,
+            AttributeDomain::Point,
+            "@P.y += sin(@P.x * 3) * 0.2\n@P.xz *= 2\n@w = 1\n@w -= 0.25",
+            1.0,
+            0.0,
+        )
+        .unwrap();
+        for (p, q) in mesh.positions.iter().zip(&before) {
+            assert!((p[0] - q[0] * 2.0).abs() < 1.0e-6);
+            assert!((p[1] - (q[1] + (q[0] * 3.0).sin() * 0.2)).abs() < 1.0e-6);
+            assert!((p[2] - q[2] * 2.0).abs() < 1.0e-6);
+        }
+        match mesh.attribute(AttributeDomain::Point, "w") {
+            Some(AttributeRef::Float(values)) => assert!(values.iter().all(|v| *v == 0.75)),
+            other => panic!("unexpected @w: {:?}", other),
+        }
+
+        let err =
+            apply_wrangle(&mut mesh, AttributeDomain::Point, "@w.x = 1", 1.0, 0.0).unwrap_err();
+        assert!(err.contains("float attribute"), "{}", err);
+    }
+}
@@ -1,4 +1,4 @@
-use egui::epaint::Rect;
+use egui::epaint::{Pos2, Rect};
 use egui_wgpu::ScreenDescriptor;
 use glam::{Mat4, Vec3};
 
@@ -21,16 +21,119 @@ pub fn camera_view_proj(
     rect: Rect,
     screen_descriptor: &ScreenDescriptor,
 ) -> Mat4 {
-    let viewport_width = (rect.width() * screen_descriptor.pixels_per_point).max(1.0);
-    let viewport_height = (rect.height() * screen_descriptor.pixels_per_point).max(1.0);
-    let aspect = viewport_width / viewport_height;
+    camera_projection(rect, screen_descriptor) * camera_view(camera)
+}
 
+fn camera_view(camera: CameraState) -> Mat4 {
     let target = Vec3::from(camera.target);
     let position = camera_position(camera);
+    Mat4::look_at_rh(position, target, Vec3::Y)
+}
+
+// Same view as `camera_view_proj` but with the camera target at the origin, so the
+// matrix never carries a large translation when the scene sits far from world zero.
+pub fn camera_relative_view_proj(
+    camera: CameraState,
+    rect: Rect,
+    screen_descriptor: &ScreenDescriptor,
+) -> Mat4 {
+    let eye = camera_direction(camera) * camera.distance.max(0.1);
+    let view = Mat4::look_at_rh(eye, Vec3::ZERO, Vec3::Y);
+    camera_projection(rect, screen_descriptor) * view
+}
+
+// Conservative box-vs-frustum test for CPU culling: a box is rejected only when it lies
+// entirely outside one of the six clip planes (wgpu depth range, 0..1).
+pub(crate) fn aabb_in_frustum(view_proj: Mat4, min: Vec3, max: Vec3) -> bool {
+    let rows = [
+        view_proj.row(0),
+        view_proj.row(1),
+        view_proj.row(2),
+        view_proj.row(3),
+    ];
+    let planes = [
+        rows[3] + rows[0],
+        rows[3] - rows[0],
+        rows[3] + rows[1],
+        rows[3] - rows[1],
+        rows[2],
+        rows[3] - rows[2],
+    ];
+    planes.iter().all(|plane| {
+        let corner = Vec3::new(
+            if plane.x >= 0.0 { max.x } else { min.x },
+            if plane.y >= 0.0 { max.y } else { min.y },
+            if plane.z >= 0.0 { max.z } else { min.z },
+        );
+        plane.truncate().dot(corner) + plane.w >= 0.0
+    })
+}
+
+fn camera_projection(rect: Rect, screen_descriptor: &ScreenDescriptor) -> Mat4 {
+    let viewport_width = (rect.width() * screen_descriptor.pixels_per_point).max(1.0);
+    let viewport_height = (rect.height() * screen_descriptor.pixels_per_point).max(1.0);
+    perspective(viewport_width / viewport_height)
+}
+
+fn perspective(aspect: f32) -> Mat4 {
+    Mat4::perspective_rh(45_f32.to_radians(), aspect, 0.01, 1000.0)
+}
+
+// World-to-UI mapping for overlays painted with egui; pixels per point cancel out of the
+// aspect ratio, so the UI rect alone is enough.
+fn ui_view_proj(camera: CameraState, rect: Rect) -> Mat4 {
+    let aspect = rect.width().max(1.0) / rect.height().max(1.0);
+    perspective(aspect) * camera_view(camera)
+}
+
+pub fn project_to_screen(camera: CameraState, rect: Rect, point: [f32; 3]) -> Option<Pos2> {
+    let clip = ui_view_proj(camera, rect) * Vec3::from(point).extend(1.0);
+    if clip.w <= 1.0e-6 {
+        return None;
+    }
+    let ndc = clip.truncate() / clip.w;
+    Some(Pos2::new(
+        rect.left() + (ndc.x * 0.5 + 0.5) * rect.width(),
+        rect.top() + (0.5 - ndc.y * 0.5) * rect.height(),
+    ))
+}
+
+// Ray through a UI position as (origin, direction) in world space.
+pub fn screen_ray(camera: CameraState, rect: Rect, pos: Pos2) -> ([f32; 3], [f32; 3]) {
+    let inverse = ui_view_proj(camera, rect).inverse();
+    let x = (pos.x - rect.left()) / rect.width().max(1.0) * 2.0 - 1.0;
+    let y = 1.0 - (pos.y - rect.top()) / rect.height().max(1.0) * 2.0;
+    let near = inverse.project_point3(Vec3::new(x, y, 0.0));
+    let far = inverse.project_point3(Vec3::new(x, y, 1.0));
+    (near.to_array(), (far - near).normalize_or_zero().to_array())
+}
+
+// Translation is in view space (x right, y up, z forward) scaled by the orbit distance;
+// rotation is [yaw, pitch] in radians.
+pub fn apply_camera_motion(camera: &mut CameraState, translation: [f32; 3], rotation: [f32; 2]) {
+    let direction = camera_direction(*camera);
+    let right = Vec3::Y.cross(direction).normalize_or_zero();
+    let up = direction.cross(right);
+    let distance = camera.distance.max(0.1);
+    let target =
+        Vec3::from(camera.target) + (right * translation[0] + up * translation[1]) * distance;
+    camera.target = target.to_array();
+    camera.distance = (distance * (1.0 - translation[2]).max(0.1)).clamp(0.1, 1000.0);
+    camera.yaw += rotation[0];
+    camera.pitch = (camera.pitch + rotation[1]).clamp(-1.54, 1.54);
+}
 
-    let view = Mat4::look_at_rh(position, target, Vec3::Y);
-    let projection = Mat4::perspective_rh(45_f32.to_radians(), aspect, 0.01, 1000.0);
-    projection * view
+// Moves the eye instead of the target: movement is in view space (x right, y world up,
+// z forward) and look is [yaw, pitch] in radians about the eye.
+pub fn fly_camera(camera: &mut CameraState, movement: [f32; 3], look: [f32; 2]) {
+    let distance = camera.distance.max(0.1);
+    let eye = camera_position(*camera);
+    camera.yaw += look[0];
+    camera.pitch = (camera.pitch + look[1]).clamp(-1.54, 1.54);
+    let direction = camera_direction(*camera);
+    let right = Vec3::Y.cross(direction).normalize_or_zero();
+    let eye = eye + right * movement[0] + Vec3::Y * movement[1] - direction * movement[2];
+    camera.target = (eye - direction * distance).to_array();
 }
 
 fn camera_direction(camera: CameraState) -> Vec3 {
@@ -1,8 +1,15 @@
 mod camera;
 mod mesh_cache;
+mod overlay;
 mod scene;
 mod viewport;
 
-pub use camera::{camera_view_proj, CameraState};
-pub use scene::{RenderMesh, RenderScene};
-pub use viewport::{ViewportDebug, ViewportRenderer, ViewportShadingMode, ViewportStats};
+pub use camera::{
+    apply_camera_motion, camera_view_proj, fly_camera, project_to_screen, screen_ray, CameraState,
+};
+pub use overlay::{paint_annotation, paint_measurement};
+pub use scene::{RenderDebugChannel, RenderHighlight, RenderMesh, RenderScene};
+pub use viewport::{
+    ViewportBackground, ViewportCullMode, ViewportDebug, ViewportRenderer, ViewportShadingMode,
+    ViewportStats,
+};
@@ -1,5 +1,4 @@
 use std::collections::HashMap;
-use std::hash::{Hash, Hasher};
 use std::sync::atomic::{AtomicU64, Ordering};
 
 use egui_wgpu::wgpu;
@@ -47,18 +46,24 @@ impl GpuMeshCache {
         mesh
     }
 
+    pub fn is_current(&self, mesh_id: u64, content_hash: u64) -> bool {
+        self.meshes
+            .get(&mesh_id)
+            .is_some_and(|mesh| mesh.hash == content_hash)
+    }
+
     pub fn upload_or_update(
         &mut self,
         device: &wgpu::Device,
         mesh_id: u64,
+        content_hash: u64,
         vertices: &[u8],
         indices: &[u32],
     ) -> &GpuMesh {
-        let hash = hash_mesh(vertices, indices);
         let needs_upload = self
             .meshes
             .get(&mesh_id)
-            .map(|mesh| mesh.hash != hash || mesh.index_count != indices.len() as u32)
+            .map(|mesh| mesh.hash != content_hash || mesh.index_count != indices.len() as u32)
             .unwrap_or(true);
 
         if needs_upload {
@@ -76,7 +81,7 @@ impl GpuMeshCache {
                 vertex_buffer,
                 index_buffer,
                 index_count: indices.len() as u32,
-                hash,
+                hash: content_hash,
             };
             self.meshes.insert(mesh_id, mesh);
             self.uploads.fetch_add(1, Ordering::Relaxed);
@@ -85,6 +90,10 @@ impl GpuMeshCache {
         self.meshes.get(&mesh_id).expect("mesh cache insert")
     }
 
+    pub fn remove(&mut self, mesh_id: u64) {
+        self.meshes.remove(&mesh_id);
+    }
+
     pub fn stats_snapshot(&self) -> GpuMeshCacheStats {
         GpuMeshCacheStats {
             hits: self.hits.load(Ordering::Relaxed),
@@ -94,10 +103,3 @@ impl GpuMeshCache {
         }
     }
 }
-
-fn hash_mesh(vertices: &[u8], indices: &[u32]) -> u64 {
-    let mut hasher = std::collections::hash_map::DefaultHasher::new();
-    vertices.hash(&mut hasher);
-    indices.hash(&mut hasher);
-    hasher.finish()
-}
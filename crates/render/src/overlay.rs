@@ -0,0 +1,55 @@
+use egui::{Align2, Color32, FontId, Painter, Pos2, Rect, Stroke};
+
+use crate::camera::{project_to_screen, CameraState};
+
+const MEASURE_COLOR: Color32 = Color32::from_rgb(255, 210, 80);
+const NOTE_COLOR: Color32 = Color32::from_rgb(120, 200, 255);
+
+// Measurement line with end ticks and a length label at its midpoint. Drawn on top of
+// the viewport image, so it stays visible through geometry.
+pub fn paint_measurement(
+    painter: &Painter,
+    rect: Rect,
+    camera: CameraState,
+    start: [f32; 3],
+    end: [f32; 3],
+    label: &str,
+) {
+    let (Some(a), Some(b)) = (
+        project_to_screen(camera, rect, start),
+        project_to_screen(camera, rect, end),
+    ) else {
+        return;
+    };
+    let stroke = Stroke::new(2.0, MEASURE_COLOR);
+    painter.line_segment([a, b], stroke);
+    painter.circle_filled(a, 3.0, MEASURE_COLOR);
+    painter.circle_filled(b, 3.0, MEASURE_COLOR);
+    if !label.is_empty() {
+        paint_label(painter, a + (b - a) * 0.5, label, MEASURE_COLOR);
+    }
+}
+
+pub fn paint_annotation(
+    painter: &Painter,
+    rect: Rect,
+    camera: CameraState,
+    position: [f32; 3],
+    text: &str,
+) {
+    let Some(anchor) = project_to_screen(camera, rect, position) else {
+        return;
+    };
+    let label_pos = anchor + egui::vec2(12.0, -18.0);
+    painter.line_segment([anchor, label_pos], Stroke::new(1.0, NOTE_COLOR));
+    painter.circle_filled(anchor, 3.0, NOTE_COLOR);
+    paint_label(painter, label_pos, text, NOTE_COLOR);
+}
+
+fn paint_label(painter: &Painter, pos: Pos2, text: &str, color: Color32) {
+    let galley = painter.layout_no_wrap(text.to_string(), FontId::proportional(12.0), color);
+    let padding = egui::vec2(4.0, 2.0);
+    let bg = Align2::LEFT_BOTTOM.anchor_size(pos, galley.size() + padding * 2.0);
+    painter.rect_filled(bg, 3.0, Color32::from_black_alpha(170));
+    painter.galley(bg.min + padding, galley, color);
+}
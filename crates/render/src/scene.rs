@@ -6,6 +6,18 @@ pub struct RenderMesh {
     pub corner_normals: Option<Vec<[f32; 3]>>,
     pub colors: Option<Vec<[f32; 3]>>,
     pub corner_colors: Option<Vec<[f32; 3]>>,
+    // Content hash from the core mesh; equal hashes reuse the uploaded GPU buffers.
+    pub content_hash: u64,
+    // Below 1.0 the mesh is alpha blended after all opaque geometry.
+    pub opacity: f32,
+}
+
+#[derive(Debug, Clone)]
+pub struct RenderDebugChannel {
+    pub name: String,
+    pub color: [f32; 3],
+    pub points: Vec<[f32; 3]>,
+    pub lines: Vec<[[f32; 3]; 2]>,
 }
 
 #[derive(Debug, Clone)]
@@ -13,4 +25,12 @@ pub struct RenderScene {
     pub mesh: RenderMesh,
     pub base_color: [f32; 3],
     pub template_mesh: Option<RenderMesh>,
+    pub debug_channels: Vec<RenderDebugChannel>,
+}
+
+// What the viewport outlines for the current selection.
+#[derive(Debug, Clone)]
+pub enum RenderHighlight {
+    Display,
+    Mesh(RenderMesh),
 }
@@ -9,12 +9,23 @@ use egui::epaint::Rect;
 use egui_wgpu::wgpu::util::DeviceExt as _;
 use egui_wgpu::{CallbackResources, CallbackTrait};
 
-use super::mesh::{normals_vertices, point_cross_vertices};
-use super::pipeline::{apply_scene_to_pipeline, ensure_offscreen_targets, PipelineState, Uniforms};
-use super::{ViewportDebug, ViewportSceneState, ViewportShadingMode, ViewportStatsState};
-use crate::camera::{camera_position, camera_view_proj, CameraState};
+use super::mesh::{
+    normals_stride, normals_vertices, point_cross_vertices, scale_reference_vertices,
+    symmetry_plane_vertices, wireframe_vertices, DISPLAY_WIRE_COLOR, SYMMETRY_VERTEX_COUNT,
+};
+use super::pipeline::{
+    apply_highlight_to_pipeline, apply_scene_to_pipeline, ensure_offscreen_targets, reload_shaders,
+    set_surface_shader, PipelineState, Uniforms, TEMPLATE_MESH_ID,
+};
+use super::{
+    ViewportBackground, ViewportCullMode, ViewportDebug, ViewportHighlightState,
+    ViewportSceneState, ViewportShadingMode, ViewportStatsState, ViewportSurfaceShaderState,
+};
+use crate::camera::{aabb_in_frustum, camera_position, camera_relative_view_proj, CameraState};
 use glam::{Mat4, Vec3};
 
+const CONTACT_SHADOW_STRENGTH: f32 = 0.55;
+
 pub(super) struct ViewportCallback {
     pub(super) target_format: egui_wgpu::wgpu::TextureFormat,
     pub(super) rect: Rect,
@@ -22,6 +33,9 @@ pub(super) struct ViewportCallback {
     pub(super) debug: ViewportDebug,
     pub(super) stats: Arc<Mutex<ViewportStatsState>>,
     pub(super) scene: Arc<Mutex<ViewportSceneState>>,
+    pub(super) surface_shader: Arc<Mutex<ViewportSurfaceShaderState>>,
+    pub(super) highlight: Arc<Mutex<ViewportHighlightState>>,
+    pub(super) picked_triangle: Arc<Mutex<Option<u32>>>,
 }
 
 impl CallbackTrait for ViewportCallback {
@@ -37,7 +51,7 @@ impl CallbackTrait for ViewportCallback {
             callback_resources.insert(PipelineState::new(device, self.target_format));
         }
 
-        let view_proj = camera_view_proj(self.camera, self.rect, screen_descriptor);
+        let view_proj = camera_relative_view_proj(self.camera, self.rect, screen_descriptor);
         let camera_pos = camera_position(self.camera);
         let target = glam::Vec3::from(self.camera.target);
         let forward = (target - camera_pos).normalize_or_zero();
@@ -49,10 +63,23 @@ impl CallbackTrait for ViewportCallback {
         let key_dir = (-forward + right * 0.6 + up * 0.8).normalize_or_zero();
         let fill_dir = (-forward - right * 0.4 + up * 0.2).normalize_or_zero();
         let rim_dir = (forward + up * 0.6).normalize_or_zero();
+        // Flat shaded is lit shading with face normals; the wireframe keeps lit shading for
+        // anything that still draws a surface, such as the template.
         let shading_mode = match self.debug.shading_mode {
-            ViewportShadingMode::Lit => 0.0,
+            ViewportShadingMode::Lit
+            | ViewportShadingMode::FlatShaded
+            | ViewportShadingMode::Wireframe => 0.0,
             ViewportShadingMode::Normals => 1.0,
             ViewportShadingMode::Depth => 2.0,
+            ViewportShadingMode::Attribute => 3.0,
+        };
+        let flat_shading = self.debug.flat_shading
+            || matches!(self.debug.shading_mode, ViewportShadingMode::FlatShaded);
+        let wireframe = matches!(self.debug.shading_mode, ViewportShadingMode::Wireframe);
+        let cull_mode = match self.debug.cull_mode {
+            ViewportCullMode::None => 0.0,
+            ViewportCullMode::Back => 1.0,
+            ViewportCullMode::Front => 2.0,
         };
 
         if let Some(pipeline) = callback_resources.get_mut::<PipelineState>() {
@@ -63,6 +90,18 @@ impl CallbackTrait for ViewportCallback {
                 .round()
                 .max(1.0) as u32;
             ensure_offscreen_targets(device, pipeline, self.target_format, width, height);
+            reload_shaders(device, pipeline, self.target_format);
+            if let Ok(shader_state) = self.surface_shader.lock() {
+                if shader_state.version != pipeline.surface_shader_version {
+                    set_surface_shader(
+                        device,
+                        pipeline,
+                        self.target_format,
+                        shader_state.source.clone(),
+                    );
+                    pipeline.surface_shader_version = shader_state.version;
+                }
+            }
 
             if let Ok(scene_state) = self.scene.lock() {
                 match scene_state.scene.clone() {
@@ -80,13 +119,32 @@ impl CallbackTrait for ViewportCallback {
                             pipeline.mesh_bounds = ([0.0; 3], [0.0; 3]);
                             pipeline.base_color = [0.7, 0.72, 0.75];
                             pipeline.template_count = 0;
+                            pipeline.template_surface = None;
+                            pipeline.debug_channels.clear();
                             pipeline.scene_version = scene_state.version;
                         }
                     }
                 }
             }
 
-            let light_view_proj = light_view_projection(pipeline.mesh_bounds, key_dir);
+            if let Ok(highlight_state) = self.highlight.lock() {
+                if highlight_state.version != pipeline.highlight_version {
+                    apply_highlight_to_pipeline(
+                        device,
+                        pipeline,
+                        highlight_state.highlight.as_ref(),
+                    );
+                    pipeline.highlight_version = highlight_state.version;
+                }
+            }
+
+            let light_view_proj = light_view_projection(
+                (
+                    (Vec3::from(pipeline.mesh_bounds.0) - target).to_array(),
+                    (Vec3::from(pipeline.mesh_bounds.1) - target).to_array(),
+                ),
+                key_dir,
+            );
             let shadow_enabled = self.debug.key_shadows && pipeline.index_count > 0;
             let bounds_min = Vec3::from(pipeline.mesh_bounds.0);
             let bounds_max = Vec3::from(pipeline.mesh_bounds.1);
@@ -95,6 +153,10 @@ impl CallbackTrait for ViewportCallback {
             let normal_bias = 0.0;
             let shadow_texel = 1.0 / pipeline._shadow_size.max(1) as f32;
 
+            let (background_top, background_bottom) = match self.debug.background {
+                ViewportBackground::Solid(color) => (color, color),
+                ViewportBackground::Gradient { top, bottom } => (top, bottom),
+            };
             let uniforms = Uniforms {
                 view_proj: view_proj.to_cols_array_2d(),
                 light_view_proj: light_view_proj.to_cols_array_2d(),
@@ -104,7 +166,7 @@ impl CallbackTrait for ViewportCallback {
                 _pad1: 0.0,
                 rim_dir: rim_dir.to_array(),
                 _pad2: 0.0,
-                camera_pos: camera_pos.to_array(),
+                camera_pos: (camera_pos - target).to_array(),
                 _pad3: 0.0,
                 base_color: pipeline.base_color,
                 _pad4: 0.0,
@@ -121,23 +183,126 @@ impl CallbackTrait for ViewportCallback {
                     shadow_texel,
                     normal_bias,
                 ],
+                view_params: [
+                    cull_mode,
+                    if self.debug.double_sided { 1.0 } else { 0.0 },
+                    if flat_shading { 1.0 } else { 0.0 },
+                    0.0,
+                ],
+                clip_plane: self
+                    .debug
+                    .section_plane
+                    .map(|[x, y, z, offset]| [x, y, z, offset - Vec3::new(x, y, z).dot(target)])
+                    .unwrap_or([0.0; 4]),
+                clip_params: [
+                    if self.debug.section_plane.is_some() {
+                        1.0
+                    } else {
+                        0.0
+                    },
+                    self.debug.near_clip.max(0.0),
+                    if self.debug.section_outline { 1.0 } else { 0.0 },
+                    0.0,
+                ],
+                render_origin: target.to_array(),
+                _pad5: 0.0,
+                opacity: [
+                    pipeline.mesh_opacity,
+                    pipeline
+                        .template_surface
+                        .as_ref()
+                        .map_or(0.0, |surface| surface.opacity),
+                    0.0,
+                    0.0,
+                ],
+                background_top: [background_top[0], background_top[1], background_top[2], 1.0],
+                background_bottom: [
+                    background_bottom[0],
+                    background_bottom[1],
+                    background_bottom[2],
+                    1.0,
+                ],
+                contact_footprint: [bounds_min.x, bounds_min.z, bounds_max.x, bounds_max.z],
+                contact_params: [bounds_min.y, CONTACT_SHADOW_STRENGTH, 0.0, 0.0],
             };
 
             queue.write_buffer(&pipeline.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
 
-            if self.debug.show_normals
-                && (self.debug.normal_length - pipeline.normals_length).abs() > 0.0001
-            {
-                let normals_vertices =
-                    normals_vertices(&pipeline.mesh_vertices, self.debug.normal_length);
-                queue.write_buffer(
-                    &pipeline.normals_buffer,
-                    0,
-                    bytemuck::cast_slice(&normals_vertices),
-                );
-                pipeline.normals_length = self.debug.normal_length;
+            // Built lazily: scene uploads only invalidate the normals, so meshes viewed with
+            // the overlay off never pay for them.
+            if self.debug.show_normals {
+                let stride =
+                    normals_stride(pipeline.mesh_vertices.len(), self.debug.normal_density);
+                let stale = pipeline.normals_key.is_none_or(|(length, built_stride)| {
+                    (self.debug.normal_length - length).abs() > 0.0001 || built_stride != stride
+                });
+                if stale {
+                    let normals_vertices =
+                        normals_vertices(&pipeline.mesh_vertices, self.debug.normal_length, stride);
+                    if !normals_vertices.is_empty() {
+                        pipeline.normals_buffer = device.create_buffer_init(
+                            &egui_wgpu::wgpu::util::BufferInitDescriptor {
+                                label: Some("grapho_normals_vertices"),
+                                contents: bytemuck::cast_slice(&normals_vertices),
+                                usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
+                            },
+                        );
+                    }
+                    pipeline.normals_count = normals_vertices.len() as u32;
+                    pipeline.normals_key = Some((self.debug.normal_length, stride));
+                }
+            }
+
+            if wireframe && !pipeline.wire_built {
+                let positions: Vec<[f32; 3]> = pipeline
+                    .mesh_vertices
+                    .iter()
+                    .map(|vertex| vertex.position)
+                    .collect();
+                let lines =
+                    wireframe_vertices(&positions, &pipeline.mesh_indices, DISPLAY_WIRE_COLOR);
+                if !lines.is_empty() {
+                    pipeline.wire_buffer =
+                        device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
+                            label: Some("grapho_wire_vertices"),
+                            contents: bytemuck::cast_slice(&lines),
+                            usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
+                        });
+                }
+                pipeline.wire_count = lines.len() as u32;
+                pipeline.wire_built = true;
+            }
+
+            if let Some(size) = self.debug.scale_reference {
+                let key = (size, pipeline.mesh_bounds);
+                if pipeline.scale_reference_key != Some(key) {
+                    let lines = scale_reference_vertices(size, pipeline.mesh_bounds);
+                    queue.write_buffer(
+                        &pipeline.scale_reference_buffer,
+                        0,
+                        bytemuck::cast_slice(&lines),
+                    );
+                    pipeline.scale_reference_key = Some(key);
+                }
+            }
+
+            if let Some(plane) = self.debug.symmetry_plane {
+                let key = (plane, pipeline.mesh_bounds);
+                if pipeline.symmetry_key != Some(key) {
+                    let lines = symmetry_plane_vertices(plane, pipeline.mesh_bounds);
+                    queue.write_buffer(&pipeline.symmetry_buffer, 0, bytemuck::cast_slice(&lines));
+                    pipeline.symmetry_key = Some(key);
+                }
             }
 
+            // The view-projection is camera-relative, so the bounds are shifted the same way.
+            let (bounds_min, bounds_max) = pipeline.mesh_bounds;
+            let mesh_culled = !aabb_in_frustum(
+                view_proj,
+                Vec3::from(bounds_min) - target,
+                Vec3::from(bounds_max) - target,
+            );
+
             if let Ok(mut stats_state) = self.stats.lock() {
                 let now = Instant::now();
                 if let Some(last) = stats_state.last_frame {
@@ -159,6 +324,9 @@ impl CallbackTrait for ViewportCallback {
                 stats_state.last_frame = Some(now);
 
                 let cache_stats = pipeline.mesh_cache.stats_snapshot();
+                let submitted = u32::from(!pipeline.mesh_vertices.is_empty());
+                stats_state.stats.meshes_culled = u32::from(submitted > 0 && mesh_culled);
+                stats_state.stats.meshes_drawn = submitted - stats_state.stats.meshes_culled;
                 stats_state.stats.mesh_count = cache_stats.mesh_count;
                 stats_state.stats.cache_hits = cache_stats.hits;
                 stats_state.stats.cache_misses = cache_stats.misses;
@@ -167,7 +335,7 @@ impl CallbackTrait for ViewportCallback {
                 stats_state.stats.triangle_count = pipeline.index_count / 3;
             }
 
-            let mesh = if pipeline.mesh_vertices.is_empty() {
+            let mesh = if pipeline.mesh_vertices.is_empty() || mesh_culled {
                 None
             } else {
                 pipeline.mesh_cache.get(pipeline.mesh_id)
@@ -211,9 +379,9 @@ impl CallbackTrait for ViewportCallback {
                         depth_slice: None,
                         ops: egui_wgpu::wgpu::Operations {
                             load: egui_wgpu::wgpu::LoadOp::Clear(egui_wgpu::wgpu::Color {
-                                r: 28.0 / 255.0,
-                                g: 28.0 / 255.0,
-                                b: 28.0 / 255.0,
+                                r: background_bottom[0] as f64,
+                                g: background_bottom[1] as f64,
+                                b: background_bottom[2] as f64,
                                 a: 1.0,
                             }),
                             store: egui_wgpu::wgpu::StoreOp::Store,
@@ -234,8 +402,15 @@ impl CallbackTrait for ViewportCallback {
                 });
 
             render_pass.set_viewport(0.0, 0.0, width as f32, height as f32, 0.0, 1.0);
-            if let Some(mesh) = mesh {
-                if !self.debug.show_points && pipeline.index_count > 0 {
+            if matches!(self.debug.background, ViewportBackground::Gradient { .. }) {
+                render_pass.set_pipeline(&pipeline.background_pipeline);
+                render_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+                render_pass.draw(0..3, 0..1);
+            }
+            let draw_surface = !self.debug.show_points && !wireframe && pipeline.index_count > 0;
+            let mesh_transparent = pipeline.mesh_opacity < 1.0;
+            if let Some(mesh) = mesh.as_ref().filter(|_| !mesh_transparent) {
+                if draw_surface {
                     render_pass.set_pipeline(&pipeline.mesh_pipeline);
                     render_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
                     render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
@@ -247,6 +422,16 @@ impl CallbackTrait for ViewportCallback {
                 }
             }
 
+            // Meshes entirely below the floor have nothing to ground.
+            if self.debug.contact_shadow
+                && pipeline.index_count > 0
+                && pipeline.mesh_bounds.1[1] > 0.0
+            {
+                render_pass.set_pipeline(&pipeline.contact_pipeline);
+                render_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+                render_pass.draw(0..6, 0..1);
+            }
+
             render_pass.set_pipeline(&pipeline.line_pipeline);
             render_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
 
@@ -273,6 +458,11 @@ impl CallbackTrait for ViewportCallback {
                 }
             }
 
+            if wireframe && mesh.is_some() && pipeline.wire_count > 0 {
+                render_pass.set_vertex_buffer(0, pipeline.wire_buffer.slice(..));
+                render_pass.draw(0..pipeline.wire_count, 0..1);
+            }
+
             if pipeline.template_count > 0 {
                 render_pass.set_vertex_buffer(0, pipeline.template_buffer.slice(..));
                 render_pass.draw(0..pipeline.template_count, 0..1);
@@ -297,6 +487,182 @@ impl CallbackTrait for ViewportCallback {
                 render_pass.set_vertex_buffer(0, pipeline.bounds_buffer.slice(..));
                 render_pass.draw(0..pipeline.bounds_count, 0..1);
             }
+
+            if self.debug.scale_reference.is_some() {
+                render_pass.set_vertex_buffer(0, pipeline.scale_reference_buffer.slice(..));
+                render_pass.draw(0..pipeline.scale_reference_count, 0..1);
+            }
+
+            if self.debug.symmetry_plane.is_some() {
+                render_pass.set_vertex_buffer(0, pipeline.symmetry_buffer.slice(..));
+                render_pass.draw(0..SYMMETRY_VERTEX_COUNT as u32, 0..1);
+            }
+
+            for channel in &pipeline.debug_channels {
+                if channel.index < 64
+                    && self.debug.hidden_debug_channels & (1 << channel.index) != 0
+                {
+                    continue;
+                }
+                render_pass.set_vertex_buffer(0, channel.buffer.slice(..));
+                render_pass.draw(0..channel.count, 0..1);
+            }
+
+            // Blended meshes go last, farthest first, over everything opaque.
+            let mut transparent = Vec::new();
+            if mesh.is_some() && mesh_transparent && draw_surface {
+                transparent.push((
+                    pipeline.mesh_id,
+                    pipeline.mesh_bounds,
+                    &pipeline.transparent_pipelines,
+                ));
+            }
+            if let Some(surface) = &pipeline.template_surface {
+                transparent.push((TEMPLATE_MESH_ID, surface.bounds, &pipeline.ghost_pipelines));
+            }
+            let view_distance = |(min, max): ([f32; 3], [f32; 3])| {
+                ((Vec3::from(min) + Vec3::from(max)) * 0.5 - camera_pos).length_squared()
+            };
+            transparent.sort_by(|a, b| view_distance(b.1).total_cmp(&view_distance(a.1)));
+            for (mesh_id, _, stages) in transparent {
+                let Some(mesh) = pipeline.mesh_cache.get(mesh_id) else {
+                    continue;
+                };
+                render_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                render_pass.set_index_buffer(
+                    mesh.index_buffer.slice(..),
+                    egui_wgpu::wgpu::IndexFormat::Uint32,
+                );
+                for stage in stages {
+                    render_pass.set_pipeline(stage);
+                    render_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+                    render_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+                }
+            }
+            drop(render_pass);
+
+            // A cleared scene keeps its cache entry, so the display id alone is not enough.
+            let outline_mesh = pipeline
+                .highlight_mesh
+                .filter(|id| *id != pipeline.mesh_id || !pipeline.mesh_vertices.is_empty())
+                .and_then(|id| pipeline.mesh_cache.get(id));
+            if let Some(mesh) = outline_mesh {
+                let mut mask_pass =
+                    _egui_encoder.begin_render_pass(&egui_wgpu::wgpu::RenderPassDescriptor {
+                        label: Some("grapho_outline_mask_pass"),
+                        color_attachments: &[Some(egui_wgpu::wgpu::RenderPassColorAttachment {
+                            view: &pipeline.outline_mask_view,
+                            resolve_target: None,
+                            depth_slice: None,
+                            ops: egui_wgpu::wgpu::Operations {
+                                load: egui_wgpu::wgpu::LoadOp::Clear(
+                                    egui_wgpu::wgpu::Color::TRANSPARENT,
+                                ),
+                                store: egui_wgpu::wgpu::StoreOp::Store,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                        occlusion_query_set: None,
+                        timestamp_writes: None,
+                    });
+                mask_pass.set_pipeline(&pipeline.mask_pipeline);
+                mask_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+                mask_pass.set_vertex_buffer(0, mesh.vertex_buffer.slice(..));
+                mask_pass.set_index_buffer(
+                    mesh.index_buffer.slice(..),
+                    egui_wgpu::wgpu::IndexFormat::Uint32,
+                );
+                mask_pass.draw_indexed(0..mesh.index_count, 0, 0..1);
+                drop(mask_pass);
+
+                let mut outline_pass =
+                    _egui_encoder.begin_render_pass(&egui_wgpu::wgpu::RenderPassDescriptor {
+                        label: Some("grapho_outline_pass"),
+                        color_attachments: &[Some(egui_wgpu::wgpu::RenderPassColorAttachment {
+                            view: &pipeline.offscreen_view,
+                            resolve_target: None,
+                            depth_slice: None,
+                            ops: egui_wgpu::wgpu::Operations {
+                                load: egui_wgpu::wgpu::LoadOp::Load,
+                                store: egui_wgpu::wgpu::StoreOp::Store,
+                            },
+                        })],
+                        depth_stencil_attachment: None,
+                        occlusion_query_set: None,
+                        timestamp_writes: None,
+                    });
+                outline_pass.set_pipeline(&pipeline.outline_pipeline);
+                outline_pass.set_bind_group(0, &pipeline.outline_bind_group, &[]);
+                outline_pass.draw(0..3, 0..1);
+            }
+
+            if let Some(picked) = pipeline.id_picker.poll(device) {
+                if let Ok(mut triangle) = self.picked_triangle.lock() {
+                    *triangle = picked;
+                }
+            }
+            let pick_pixel = self
+                .debug
+                .pick_point
+                .filter(|_| draw_surface && mesh.is_some());
+            if let Some(point) = pick_pixel.filter(|_| pipeline.id_picker.ready()) {
+                pipeline.id_picker.prepare(
+                    device,
+                    [width, height],
+                    &pipeline.mesh_vertices,
+                    &pipeline.mesh_indices,
+                );
+                if let (Some(view), Some((buffer, count))) = (
+                    pipeline.id_picker.target_view(),
+                    pipeline.id_picker.vertex_buffer(),
+                ) {
+                    let mut id_pass =
+                        _egui_encoder.begin_render_pass(&egui_wgpu::wgpu::RenderPassDescriptor {
+                            label: Some("grapho_id_pass"),
+                            color_attachments: &[Some(
+                                egui_wgpu::wgpu::RenderPassColorAttachment {
+                                    view,
+                                    resolve_target: None,
+                                    depth_slice: None,
+                                    ops: egui_wgpu::wgpu::Operations {
+                                        load: egui_wgpu::wgpu::LoadOp::Clear(
+                                            egui_wgpu::wgpu::Color::TRANSPARENT,
+                                        ),
+                                        store: egui_wgpu::wgpu::StoreOp::Store,
+                                    },
+                                },
+                            )],
+                            depth_stencil_attachment: Some(
+                                egui_wgpu::wgpu::RenderPassDepthStencilAttachment {
+                                    view: &pipeline.depth_view,
+                                    depth_ops: Some(egui_wgpu::wgpu::Operations {
+                                        load: egui_wgpu::wgpu::LoadOp::Load,
+                                        store: egui_wgpu::wgpu::StoreOp::Store,
+                                    }),
+                                    stencil_ops: None,
+                                },
+                            ),
+                            occlusion_query_set: None,
+                            timestamp_writes: None,
+                        });
+                    id_pass.set_pipeline(&pipeline.id_pipeline);
+                    id_pass.set_bind_group(0, &pipeline.uniform_bind_group, &[]);
+                    id_pass.set_vertex_buffer(0, buffer.slice(..));
+                    id_pass.draw(0..count, 0..1);
+                    drop(id_pass);
+                    let pixel = [
+                        ((point[0] * screen_descriptor.pixels_per_point).max(0.0) as u32)
+                            .min(width - 1),
+                        ((point[1] * screen_descriptor.pixels_per_point).max(0.0) as u32)
+                            .min(height - 1),
+                    ];
+                    pipeline.id_picker.copy_texel(_egui_encoder, pixel);
+                }
+            } else if pick_pixel.is_none() {
+                if let Ok(mut triangle) = self.picked_triangle.lock() {
+                    *triangle = None;
+                }
+            }
         }
 
         Vec::new()
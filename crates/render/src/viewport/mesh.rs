@@ -1,4 +1,4 @@
-use crate::scene::RenderMesh;
+use crate::scene::{RenderDebugChannel, RenderMesh};
 use egui_wgpu::wgpu;
 
 #[repr(C)]
@@ -22,6 +22,16 @@ pub(crate) struct LineVertex {
 pub(crate) const LINE_ATTRIBUTES: [wgpu::VertexAttribute; 2] =
     wgpu::vertex_attr_array![0 => Float32x3, 1 => Float32x3];
 
+#[repr(C)]
+#[derive(Clone, Copy, bytemuck::Pod, bytemuck::Zeroable)]
+pub(crate) struct IdVertex {
+    pub(crate) position: [f32; 3],
+    pub(crate) id: u32,
+}
+
+pub(crate) const ID_ATTRIBUTES: [wgpu::VertexAttribute; 2] =
+    wgpu::vertex_attr_array![0 => Float32x3, 1 => Uint32];
+
 pub(crate) struct CubeMesh {
     pub(crate) vertices: Vec<Vertex>,
     pub(crate) indices: Vec<u32>,
@@ -171,10 +181,18 @@ pub(crate) fn build_vertices(mesh: &RenderMesh) -> (Vec<Vertex>, Vec<u32>) {
     (vertices, mesh.indices.clone())
 }
 
-pub(crate) fn normals_vertices(vertices: &[Vertex], length: f32) -> Vec<LineVertex> {
-    let mut lines = Vec::with_capacity(vertices.len() * 2);
+// Upper bound on drawn normals; denser meshes are thinned further so the overlay stays cheap.
+const MAX_NORMALS: usize = 250_000;
+
+pub(crate) fn normals_stride(vertex_count: usize, density: f32) -> usize {
+    let from_density = (1.0 / density.clamp(0.001, 1.0)).round() as usize;
+    from_density.max(vertex_count.div_ceil(MAX_NORMALS)).max(1)
+}
+
+pub(crate) fn normals_vertices(vertices: &[Vertex], length: f32, stride: usize) -> Vec<LineVertex> {
+    let mut lines = Vec::with_capacity(vertices.len().div_ceil(stride.max(1)) * 2);
     let color = [1.0, 0.85, 0.3];
-    for vertex in vertices {
+    for vertex in vertices.iter().step_by(stride.max(1)) {
         let start = vertex.position;
         let end = [
             vertex.position[0] + vertex.normal[0] * length,
@@ -229,12 +247,18 @@ pub(crate) fn point_cross_vertices(positions: &[[f32; 3]], size: f32) -> Vec<Lin
     lines
 }
 
-pub(crate) fn wireframe_vertices(positions: &[[f32; 3]], indices: &[u32]) -> Vec<LineVertex> {
+pub(crate) const TEMPLATE_WIRE_COLOR: [f32; 3] = [0.3, 0.75, 0.95];
+pub(crate) const DISPLAY_WIRE_COLOR: [f32; 3] = [0.82, 0.84, 0.88];
+
+pub(crate) fn wireframe_vertices(
+    positions: &[[f32; 3]],
+    indices: &[u32],
+    color: [f32; 3],
+) -> Vec<LineVertex> {
     if positions.is_empty() || indices.len() < 3 {
         return Vec::new();
     }
     let mut lines = Vec::with_capacity(indices.len() / 3 * 6);
-    let color = [0.3, 0.75, 0.95];
     for tri in indices.chunks_exact(3) {
         let [a, b, c] = [tri[0] as usize, tri[1] as usize, tri[2] as usize];
         let (pa, pb, pc) = match (positions.get(a), positions.get(b), positions.get(c)) {
@@ -270,7 +294,18 @@ pub(crate) fn wireframe_vertices(positions: &[[f32; 3]], indices: &[u32]) -> Vec
 }
 
 pub(crate) fn bounds_vertices(min: [f32; 3], max: [f32; 3]) -> Vec<LineVertex> {
-    let color = [0.85, 0.85, 0.9];
+    box_vertices(min, max, [0.85, 0.85, 0.9])
+}
+
+// Wire cube of the given edge length standing on the ground next to the mesh bounds.
+pub(crate) fn scale_reference_vertices(size: f32, bounds: ([f32; 3], [f32; 3])) -> Vec<LineVertex> {
+    let (min, max) = bounds;
+    let x = max[0] + size * 0.5;
+    let z = (min[2] + max[2]) * 0.5 - size * 0.5;
+    box_vertices([x, 0.0, z], [x + size, size, z + size], [0.4, 0.9, 0.5])
+}
+
+fn box_vertices(min: [f32; 3], max: [f32; 3], color: [f32; 3]) -> Vec<LineVertex> {
     let [min_x, min_y, min_z] = min;
     let [max_x, max_y, max_z] = max;
 
@@ -314,6 +349,67 @@ pub(crate) fn bounds_vertices(min: [f32; 3], max: [f32; 3]) -> Vec<LineVertex> {
     lines
 }
 
+// Lines as-is plus a small cross per point, all in the channel colour.
+pub(crate) fn debug_channel_vertices(channel: &RenderDebugChannel, size: f32) -> Vec<LineVertex> {
+    let color = channel.color;
+    let mut lines = Vec::with_capacity(channel.lines.len() * 2 + channel.points.len() * 6);
+    for segment in &channel.lines {
+        for position in segment {
+            lines.push(LineVertex {
+                position: *position,
+                color,
+            });
+        }
+    }
+    for mut vertex in point_cross_vertices(&channel.points, size) {
+        vertex.color = color;
+        lines.push(vertex);
+    }
+    lines
+}
+
+pub(crate) const SYMMETRY_VERTEX_COUNT: usize = 12;
+
+// Square outline of the symmetry plane plus a cross through it, centred on the mesh bounds.
+pub(crate) fn symmetry_plane_vertices(
+    plane: [f32; 4],
+    bounds: ([f32; 3], [f32; 3]),
+) -> [LineVertex; SYMMETRY_VERTEX_COUNT] {
+    let color = [0.85, 0.35, 0.8];
+    let normal = glam::Vec3::new(plane[0], plane[1], plane[2]).normalize_or(glam::Vec3::X);
+    let min = glam::Vec3::from(bounds.0);
+    let max = glam::Vec3::from(bounds.1);
+    let center = (min + max) * 0.5;
+    let center = center - normal * (normal.dot(center) - plane[3]);
+    let half = ((max - min).length() * 0.6).max(1.0);
+    let u = normal.any_orthonormal_vector() * half;
+    let v = normal.cross(u);
+
+    let corners = [
+        center - u - v,
+        center + u - v,
+        center + u + v,
+        center - u + v,
+    ];
+    let segments = [
+        (corners[0], corners[1]),
+        (corners[1], corners[2]),
+        (corners[2], corners[3]),
+        (corners[3], corners[0]),
+        (center - u, center + u),
+        (center - v, center + v),
+    ];
+    let mut lines = [LineVertex {
+        position: [0.0; 3],
+        color,
+    }; SYMMETRY_VERTEX_COUNT];
+    for (slot, (a, b)) in segments.into_iter().enumerate() {
+        lines[slot * 2].position = a.to_array();
+        lines[slot * 2 + 1].position = b.to_array();
+    }
+    lines
+}
+
 pub(crate) fn grid_and_axes() -> (Vec<LineVertex>, Vec<LineVertex>) {
     let grid_size = 10.0;
     let divisions = 20;
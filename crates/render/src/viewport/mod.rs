@@ -9,11 +9,13 @@ use egui::epaint::{PaintCallback, Rect};
 use egui_wgpu::Callback;
 
 use crate::camera::CameraState;
-use crate::scene::RenderScene;
+use crate::scene::{RenderHighlight, RenderScene};
 
 mod callback;
 mod mesh;
+mod picking;
 mod pipeline;
+mod shaders;
 
 use callback::ViewportCallback;
 
@@ -21,6 +23,9 @@ pub struct ViewportRenderer {
     target_format: egui_wgpu::wgpu::TextureFormat,
     stats: Arc<Mutex<ViewportStatsState>>,
     scene: Arc<Mutex<ViewportSceneState>>,
+    surface_shader: Arc<Mutex<ViewportSurfaceShaderState>>,
+    highlight: Arc<Mutex<ViewportHighlightState>>,
+    picked_triangle: Arc<Mutex<Option<u32>>>,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -28,6 +33,24 @@ pub enum ViewportShadingMode {
     Lit,
     Normals,
     Depth,
+    Attribute,
+    FlatShaded,
+    // Triangle edges only; the surface is not drawn.
+    Wireframe,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ViewportCullMode {
+    None,
+    Back,
+    Front,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub enum ViewportBackground {
+    Solid([f32; 3]),
+    // Vertical gradient across the viewport.
+    Gradient { top: [f32; 3], bottom: [f32; 3] },
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -37,12 +60,30 @@ pub struct ViewportDebug {
     pub show_normals: bool,
     pub show_bounds: bool,
     pub normal_length: f32,
+    // Fraction of vertices that get a normal line, e.g. 0.25 draws every 4th.
+    pub normal_density: f32,
     pub shading_mode: ViewportShadingMode,
     pub depth_near: f32,
     pub depth_far: f32,
     pub show_points: bool,
     pub point_size: f32,
     pub key_shadows: bool,
+    // Soft blob under the mesh footprint on the y = 0 floor.
+    pub contact_shadow: bool,
+    pub cull_mode: ViewportCullMode,
+    pub double_sided: bool,
+    pub flat_shading: bool,
+    pub section_plane: Option<[f32; 4]>,
+    pub section_outline: bool,
+    pub near_clip: f32,
+    pub symmetry_plane: Option<[f32; 4]>,
+    // Bit i hides the scene's i-th debug channel.
+    pub hidden_debug_channels: u64,
+    // Edge length of the reference cube in scene units, when shown.
+    pub scale_reference: Option<f32>,
+    // Cursor position in points relative to the viewport rect; enables the id pass.
+    pub pick_point: Option<[f32; 2]>,
+    pub background: ViewportBackground,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -52,6 +93,9 @@ pub struct ViewportStats {
     pub vertex_count: u32,
     pub triangle_count: u32,
     pub mesh_count: u32,
+    // Meshes submitted vs. skipped by frustum culling in the last frame.
+    pub meshes_drawn: u32,
+    pub meshes_culled: u32,
     pub cache_hits: u64,
     pub cache_misses: u64,
     pub cache_uploads: u64,
@@ -65,6 +109,8 @@ impl Default for ViewportStats {
             vertex_count: 0,
             triangle_count: 0,
             mesh_count: 0,
+            meshes_drawn: 0,
+            meshes_culled: 0,
             cache_hits: 0,
             cache_misses: 0,
             cache_uploads: 0,
@@ -82,6 +128,16 @@ pub(super) struct ViewportSceneState {
     scene: Option<RenderScene>,
 }
 
+pub(super) struct ViewportSurfaceShaderState {
+    version: u64,
+    source: Option<String>,
+}
+
+pub(super) struct ViewportHighlightState {
+    version: u64,
+    highlight: Option<RenderHighlight>,
+}
+
 impl ViewportRenderer {
     pub fn new(target_format: egui_wgpu::wgpu::TextureFormat) -> Self {
         Self {
@@ -94,6 +150,15 @@ impl ViewportRenderer {
                 version: 0,
                 scene: None,
             })),
+            surface_shader: Arc::new(Mutex::new(ViewportSurfaceShaderState {
+                version: 0,
+                source: None,
+            })),
+            highlight: Arc::new(Mutex::new(ViewportHighlightState {
+                version: 0,
+                highlight: None,
+            })),
+            picked_triangle: Arc::new(Mutex::new(None)),
         }
     }
 
@@ -112,6 +177,9 @@ impl ViewportRenderer {
                 debug,
                 stats: self.stats.clone(),
                 scene: self.scene.clone(),
+                surface_shader: self.surface_shader.clone(),
+                highlight: self.highlight.clone(),
+                picked_triangle: self.picked_triangle.clone(),
             },
         )
     }
@@ -123,6 +191,11 @@ impl ViewportRenderer {
             .unwrap_or_default()
     }
 
+    // Display mesh triangle under `ViewportDebug::pick_point`, a few frames behind the cursor.
+    pub fn picked_triangle(&self) -> Option<u32> {
+        self.picked_triangle.lock().ok().and_then(|picked| *picked)
+    }
+
     pub fn set_scene(&self, scene: RenderScene) {
         if let Ok(mut state) = self.scene.lock() {
             state.version = state.version.wrapping_add(1);
@@ -130,6 +203,26 @@ impl ViewportRenderer {
         }
     }
 
+    pub fn set_surface_shader(&self, source: Option<&str>) {
+        let source = source
+            .filter(|source| !source.trim().is_empty())
+            .map(str::to_string);
+        if let Ok(mut state) = self.surface_shader.lock() {
+            if state.source != source {
+                state.version = state.version.wrapping_add(1);
+                state.source = source;
+            }
+        }
+    }
+
+    // Kept apart from the scene so selection changes never re-upload the display mesh.
+    pub fn set_highlight(&self, highlight: Option<RenderHighlight>) {
+        if let Ok(mut state) = self.highlight.lock() {
+            state.version = state.version.wrapping_add(1);
+            state.highlight = highlight;
+        }
+    }
+
     pub fn clear_scene(&self) {
         if let Ok(mut state) = self.scene.lock() {
             state.version = state.version.wrapping_add(1);
@@ -0,0 +1,198 @@
+use std::sync::{Arc, Mutex};
+
+use egui_wgpu::wgpu;
+use egui_wgpu::wgpu::util::DeviceExt as _;
+
+use super::mesh::{IdVertex, Vertex};
+
+pub(super) const ID_FORMAT: wgpu::TextureFormat = wgpu::TextureFormat::R32Uint;
+// One texel is read back, but buffer copies need a full aligned row.
+const READBACK_SIZE: u64 = wgpu::COPY_BYTES_PER_ROW_ALIGNMENT as u64;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Readback {
+    Idle,
+    // The copy is recorded; mapping waits for the next frame, once it has been submitted.
+    Copied,
+    Mapping,
+    Mapped,
+    Failed,
+}
+
+// Triangle ids are rendered into an R32Uint target (0 = background, n = triangle n - 1), and
+// the texel under the cursor is read back a few frames later without stalling the GPU.
+pub(super) struct IdPicker {
+    target: Option<(wgpu::Texture, wgpu::TextureView, [u32; 2])>,
+    vertices: Option<(wgpu::Buffer, u32)>,
+    readback: wgpu::Buffer,
+    state: Arc<Mutex<Readback>>,
+}
+
+impl IdPicker {
+    pub(super) fn new(device: &wgpu::Device) -> Self {
+        Self {
+            target: None,
+            vertices: None,
+            readback: device.create_buffer(&wgpu::BufferDescriptor {
+                label: Some("grapho_id_readback"),
+                size: READBACK_SIZE,
+                usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+                mapped_at_creation: false,
+            }),
+            state: Arc::new(Mutex::new(Readback::Idle)),
+        }
+    }
+
+    pub(super) fn invalidate_mesh(&mut self) {
+        self.vertices = None;
+    }
+
+    // Advances the readback; returns the finished triangle id, if one arrived this frame.
+    pub(super) fn poll(&mut self, device: &wgpu::Device) -> Option<Option<u32>> {
+        let state = *self.state.lock().ok()?;
+        match state {
+            Readback::Copied => {
+                *self.state.lock().ok()? = Readback::Mapping;
+                let shared = self.state.clone();
+                self.readback
+                    .slice(..)
+                    .map_async(wgpu::MapMode::Read, move |result| {
+                        if let Ok(mut state) = shared.lock() {
+                            *state = if result.is_ok() {
+                                Readback::Mapped
+                            } else {
+                                Readback::Failed
+                            };
+                        }
+                    });
+                None
+            }
+            Readback::Mapping => {
+                let _ = device.poll(wgpu::PollType::Poll);
+                None
+            }
+            Readback::Mapped => {
+                let id = {
+                    let data = self.readback.slice(..4).get_mapped_range();
+                    u32::from_le_bytes([data[0], data[1], data[2], data[3]])
+                };
+                self.readback.unmap();
+                *self.state.lock().ok()? = Readback::Idle;
+                Some(id.checked_sub(1))
+            }
+            Readback::Failed => {
+                *self.state.lock().ok()? = Readback::Idle;
+                Some(None)
+            }
+            Readback::Idle => None,
+        }
+    }
+
+    pub(super) fn ready(&self) -> bool {
+        self.state
+            .lock()
+            .map(|state| *state == Readback::Idle)
+            .unwrap_or(false)
+    }
+
+    pub(super) fn prepare(
+        &mut self,
+        device: &wgpu::Device,
+        size: [u32; 2],
+        vertices: &[Vertex],
+        indices: &[u32],
+    ) {
+        if self
+            .target
+            .as_ref()
+            .is_none_or(|(_, _, built)| *built != size)
+        {
+            let texture = device.create_texture(&wgpu::TextureDescriptor {
+                label: Some("grapho_id_target"),
+                size: wgpu::Extent3d {
+                    width: size[0].max(1),
+                    height: size[1].max(1),
+                    depth_or_array_layers: 1,
+                },
+                mip_level_count: 1,
+                sample_count: 1,
+                dimension: wgpu::TextureDimension::D2,
+                format: ID_FORMAT,
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::COPY_SRC,
+                view_formats: &[],
+            });
+            let view = texture.create_view(&wgpu::TextureViewDescriptor::default());
+            self.target = Some((texture, view, size));
+        }
+        // Built on first use after each scene change: one unshared vertex per corner, each
+        // carrying its triangle id.
+        if self.vertices.is_none() {
+            let ids = id_vertices(vertices, indices);
+            let buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                label: Some("grapho_id_vertices"),
+                contents: bytemuck::cast_slice(&ids),
+                usage: wgpu::BufferUsages::VERTEX,
+            });
+            self.vertices = Some((buffer, ids.len() as u32));
+        }
+    }
+
+    pub(super) fn target_view(&self) -> Option<&wgpu::TextureView> {
+        self.target.as_ref().map(|(_, view, _)| view)
+    }
+
+    pub(super) fn vertex_buffer(&self) -> Option<(&wgpu::Buffer, u32)> {
+        self.vertices
+            .as_ref()
+            .map(|(buffer, count)| (buffer, *count))
+    }
+
+    pub(super) fn copy_texel(&mut self, encoder: &mut wgpu::CommandEncoder, pixel: [u32; 2]) {
+        let Some((texture, _, _)) = &self.target else {
+            return;
+        };
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d {
+                    x: pixel[0],
+                    y: pixel[1],
+                    z: 0,
+                },
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &self.readback,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(READBACK_SIZE as u32),
+                    rows_per_image: Some(1),
+                },
+            },
+            wgpu::Extent3d {
+                width: 1,
+                height: 1,
+                depth_or_array_layers: 1,
+            },
+        );
+        if let Ok(mut state) = self.state.lock() {
+            *state = Readback::Copied;
+        }
+    }
+}
+
+fn id_vertices(vertices: &[Vertex], indices: &[u32]) -> Vec<IdVertex> {
+    indices
+        .chunks_exact(3)
+        .enumerate()
+        .flat_map(|(triangle, corners)| {
+            corners.iter().map(move |corner| IdVertex {
+                position: vertices
+                    .get(*corner as usize)
+                    .map_or([0.0; 3], |vertex| vertex.position),
+                id: triangle as u32 + 1,
+            })
+        })
+        .collect()
+}
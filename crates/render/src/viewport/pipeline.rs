@@ -3,13 +3,16 @@ use std::borrow::Cow;
 use egui_wgpu::wgpu::util::DeviceExt as _;
 
 use crate::mesh_cache::GpuMeshCache;
-use crate::scene::RenderScene;
+use crate::scene::{RenderHighlight, RenderScene};
 
 use super::mesh::{
-    bounds_from_positions, bounds_vertices, build_vertices, cube_mesh, grid_and_axes,
-    normals_vertices, point_cross_vertices, wireframe_vertices, LineVertex, Vertex,
-    LINE_ATTRIBUTES, VERTEX_ATTRIBUTES,
+    bounds_from_positions, bounds_vertices, build_vertices, cube_mesh, debug_channel_vertices,
+    grid_and_axes, point_cross_vertices, scale_reference_vertices, symmetry_plane_vertices,
+    wireframe_vertices, IdVertex, LineVertex, Vertex, ID_ATTRIBUTES, LINE_ATTRIBUTES,
+    TEMPLATE_WIRE_COLOR, VERTEX_ATTRIBUTES,
 };
+use super::picking::{IdPicker, ID_FORMAT};
+use super::shaders::{ShaderSources, ShaderWatcher};
 
 pub(super) const DEPTH_FORMAT: egui_wgpu::wgpu::TextureFormat =
     egui_wgpu::wgpu::TextureFormat::Depth24Plus;
@@ -32,13 +35,36 @@ pub(super) struct Uniforms {
     pub(super) light_params: [f32; 4],
     pub(super) debug_params: [f32; 4],
     pub(super) shadow_params: [f32; 4],
+    pub(super) view_params: [f32; 4],
+    pub(super) clip_plane: [f32; 4],
+    pub(super) clip_params: [f32; 4],
+    pub(super) render_origin: [f32; 3],
+    pub(super) _pad5: f32,
+    pub(super) opacity: [f32; 4],
+    pub(super) background_top: [f32; 4],
+    pub(super) background_bottom: [f32; 4],
+    pub(super) contact_footprint: [f32; 4],
+    pub(super) contact_params: [f32; 4],
 }
 
 pub(super) struct PipelineState {
     pub(super) mesh_pipeline: egui_wgpu::wgpu::RenderPipeline,
     pub(super) shadow_pipeline: egui_wgpu::wgpu::RenderPipeline,
     pub(super) line_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    // Back faces first, then front faces; see `transparent_pipeline`.
+    pub(super) transparent_pipelines: [egui_wgpu::wgpu::RenderPipeline; 2],
+    pub(super) ghost_pipelines: [egui_wgpu::wgpu::RenderPipeline; 2],
+    pub(super) background_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    pub(super) contact_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    pub(super) mask_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    pub(super) outline_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    pub(super) id_pipeline: egui_wgpu::wgpu::RenderPipeline,
     pub(super) blit_pipeline: egui_wgpu::wgpu::RenderPipeline,
+    layouts: PipelineLayouts,
+    shader_sources: ShaderSources,
+    shader_watcher: Option<ShaderWatcher>,
+    surface_shader: Option<String>,
+    pub(super) surface_shader_version: u64,
     pub(super) blit_bind_group: egui_wgpu::wgpu::BindGroup,
     pub(super) blit_bind_group_layout: egui_wgpu::wgpu::BindGroupLayout,
     pub(super) blit_sampler: egui_wgpu::wgpu::Sampler,
@@ -51,14 +77,24 @@ pub(super) struct PipelineState {
     pub(super) depth_texture: egui_wgpu::wgpu::Texture,
     pub(super) depth_view: egui_wgpu::wgpu::TextureView,
     pub(super) offscreen_size: [u32; 2],
+    pub(super) _outline_mask_texture: egui_wgpu::wgpu::Texture,
+    pub(super) outline_mask_view: egui_wgpu::wgpu::TextureView,
+    pub(super) outline_bind_group: egui_wgpu::wgpu::BindGroup,
+    // Mesh cache id of the selection to outline: the display mesh or `HIGHLIGHT_MESH_ID`.
+    pub(super) highlight_mesh: Option<u64>,
+    pub(super) highlight_version: u64,
+    pub(super) id_picker: IdPicker,
     pub(super) uniform_buffer: egui_wgpu::wgpu::Buffer,
     pub(super) uniform_bind_group: egui_wgpu::wgpu::BindGroup,
     pub(super) shadow_bind_group: egui_wgpu::wgpu::BindGroup,
     pub(super) mesh_cache: GpuMeshCache,
     pub(super) mesh_id: u64,
     pub(super) mesh_vertices: Vec<Vertex>,
+    // Kept on the CPU only so the id picker can expand triangles on demand.
+    pub(super) mesh_indices: Vec<u32>,
     pub(super) point_positions: Vec<[f32; 3]>,
     pub(super) mesh_bounds: ([f32; 3], [f32; 3]),
+    pub(super) mesh_opacity: f32,
     pub(super) index_count: u32,
     pub(super) point_count: u32,
     pub(super) point_size: f32,
@@ -71,181 +107,54 @@ pub(super) struct PipelineState {
     pub(super) axes_count: u32,
     pub(super) normals_buffer: egui_wgpu::wgpu::Buffer,
     pub(super) normals_count: u32,
-    pub(super) normals_length: f32,
+    pub(super) normals_key: Option<NormalsKey>,
+    pub(super) wire_buffer: egui_wgpu::wgpu::Buffer,
+    pub(super) wire_count: u32,
+    pub(super) wire_built: bool,
     pub(super) bounds_buffer: egui_wgpu::wgpu::Buffer,
     pub(super) bounds_count: u32,
+    pub(super) symmetry_buffer: egui_wgpu::wgpu::Buffer,
+    pub(super) symmetry_key: Option<SymmetryKey>,
+    pub(super) scale_reference_buffer: egui_wgpu::wgpu::Buffer,
+    pub(super) scale_reference_count: u32,
+    pub(super) scale_reference_key: Option<ScaleReferenceKey>,
     pub(super) template_buffer: egui_wgpu::wgpu::Buffer,
     pub(super) template_count: u32,
+    // Ghost surface for the template mesh, drawn from `TEMPLATE_MESH_ID` in the mesh cache.
+    pub(super) template_surface: Option<TemplateSurface>,
+    pub(super) debug_channels: Vec<DebugChannelBuffer>,
 }
 
-impl PipelineState {
-    pub(super) fn new(
-        device: &egui_wgpu::wgpu::Device,
-        target_format: egui_wgpu::wgpu::TextureFormat,
-    ) -> Self {
-        let shader = device.create_shader_module(egui_wgpu::wgpu::ShaderModuleDescriptor {
-            label: Some("grapho_viewport_shader"),
-            source: egui_wgpu::wgpu::ShaderSource::Wgsl(Cow::Borrowed(
-                r#"
-struct Uniforms {
-    view_proj: mat4x4<f32>,
-    light_view_proj: mat4x4<f32>,
-    key_dir: vec3<f32>,
-    _pad0: f32,
-    fill_dir: vec3<f32>,
-    _pad1: f32,
-    rim_dir: vec3<f32>,
-    _pad2: f32,
-    camera_pos: vec3<f32>,
-    _pad3: f32,
-    base_color: vec3<f32>,
-    _pad4: f32,
-    light_params: vec4<f32>,
-    debug_params: vec4<f32>,
-    shadow_params: vec4<f32>,
-};
-
-@group(0) @binding(0)
-var<uniform> uniforms: Uniforms;
+pub(super) const TEMPLATE_MESH_ID: u64 = 2;
+pub(super) const HIGHLIGHT_MESH_ID: u64 = 3;
+const OUTLINE_MASK_FORMAT: egui_wgpu::wgpu::TextureFormat = egui_wgpu::wgpu::TextureFormat::R8Unorm;
 
-@group(0) @binding(1)
-var shadow_tex: texture_depth_2d;
-
-@group(0) @binding(2)
-var shadow_sampler: sampler_comparison;
-
-struct VertexInput {
-    @location(0) position: vec3<f32>,
-    @location(1) normal: vec3<f32>,
-    @location(2) color: vec3<f32>,
-};
-
-struct VertexOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) normal: vec3<f32>,
-    @location(1) world_pos: vec3<f32>,
-    @location(2) color: vec3<f32>,
-};
-
-@vertex
-fn vs_main(input: VertexInput) -> VertexOutput {
-    var out: VertexOutput;
-    out.world_pos = input.position;
-    out.normal = input.normal;
-    out.color = input.color;
-    out.position = uniforms.view_proj * vec4<f32>(input.position, 1.0);
-    return out;
+pub(super) struct TemplateSurface {
+    pub(super) opacity: f32,
+    pub(super) bounds: ([f32; 3], [f32; 3]),
 }
 
-fn shadow_factor(world_pos: vec3<f32>, normal: vec3<f32>) -> f32 {
-    let enabled = select(0.0, 1.0, uniforms.shadow_params.x >= 0.5);
-    let n = normalize(normal);
-    let light_dir = normalize(uniforms.key_dir);
-    let ndotl = max(dot(n, light_dir), 0.0);
-    let bias = uniforms.shadow_params.y + (1.0 - ndotl) * uniforms.shadow_params.y;
-    let offset_pos = world_pos + n * uniforms.shadow_params.w;
-    let light_clip = uniforms.light_view_proj * vec4<f32>(offset_pos, 1.0);
-    let ndc = light_clip.xyz / max(light_clip.w, 0.0001);
-    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
-    let in_bounds = uv.x >= 0.0 && uv.x <= 1.0 && uv.y >= 0.0 && uv.y <= 1.0 && ndc.z >= 0.0 && ndc.z <= 1.0;
-    let in_bounds_f = select(0.0, 1.0, in_bounds);
-    let uv_clamped = clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0));
-    let depth = ndc.z - bias;
-    let texel = max(uniforms.shadow_params.z, 0.000001);
-    var shadow = 0.0;
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, -texel), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(0.0, -texel), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, -texel), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, 0.0), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped, depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, 0.0), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, texel), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(0.0, texel), depth);
-    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, texel), depth);
-    let visibility = shadow / 9.0;
-    let lit = 1.0 - in_bounds_f + visibility * in_bounds_f;
-    return 1.0 - enabled + enabled * lit;
+pub(super) struct DebugChannelBuffer {
+    pub(super) index: usize,
+    pub(super) buffer: egui_wgpu::wgpu::Buffer,
+    pub(super) count: u32,
 }
 
-fn shade_surface(normal: vec3<f32>, world_pos: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
-    let n = normalize(normal);
-    let view_dir = normalize(uniforms.camera_pos - world_pos);
-    let key_dir = normalize(uniforms.key_dir);
-    let fill_dir = normalize(uniforms.fill_dir);
-    let rim_dir = normalize(uniforms.rim_dir);
-
-    let key_ndotl = max(dot(n, key_dir), 0.0);
-    let fill_ndotl = max(dot(n, fill_dir), 0.0);
-    let rim_ndotl = max(dot(n, rim_dir), 0.0);
+// Plane equation and the mesh bounds the indicator was sized for.
+pub(super) type SymmetryKey = ([f32; 4], ([f32; 3], [f32; 3]));
 
-    let half_dir = normalize(key_dir + view_dir);
-    let spec = pow(max(dot(n, half_dir), 0.0), 32.0);
+// Cube edge length and the mesh bounds it was placed against.
+// Normal length and vertex stride the normals buffer was built with.
+pub(super) type NormalsKey = (f32, usize);
 
-    let shadow = shadow_factor(world_pos, normal);
-    let key = key_ndotl * uniforms.light_params.x * shadow;
-    let fill = fill_ndotl * uniforms.light_params.y;
-    let rim = rim_ndotl * uniforms.light_params.z;
-    let ambient = uniforms.light_params.w;
+pub(super) type ScaleReferenceKey = (f32, ([f32; 3], [f32; 3]));
 
-    let base = color * uniforms.base_color;
-    return base * (ambient + key + fill + rim) + vec3<f32>(0.9) * spec * 0.2 * shadow;
-}
-
-@fragment
-fn fs_main(input: VertexOutput) -> @location(0) vec4<f32> {
-    let color = shade_surface(input.normal, input.world_pos, input.color);
-    let mode = i32(uniforms.debug_params.x + 0.5);
-    if mode == 1 {
-        let normal = normalize(input.normal);
-        return vec4<f32>(normal * 0.5 + vec3<f32>(0.5), 1.0);
-    }
-    if mode == 2 {
-        let near = uniforms.debug_params.y;
-        let far = uniforms.debug_params.z;
-        let denom = max(far - near, 0.0001);
-        let dist = distance(uniforms.camera_pos, input.world_pos);
-        let t = clamp((dist - near) / denom, 0.0, 1.0);
-        return vec4<f32>(vec3<f32>(1.0 - t), 1.0);
-    }
-    return vec4<f32>(color, 1.0);
-}
-
-struct ShadowOutput {
-    @builtin(position) position: vec4<f32>,
-};
-
-@vertex
-fn vs_shadow(input: VertexInput) -> ShadowOutput {
-    var out: ShadowOutput;
-    out.position = uniforms.light_view_proj * vec4<f32>(input.position, 1.0);
-    return out;
-}
-
-struct LineInput {
-    @location(0) position: vec3<f32>,
-    @location(1) color: vec3<f32>,
-};
-
-struct LineOutput {
-    @builtin(position) position: vec4<f32>,
-    @location(0) color: vec3<f32>,
-};
-
-@vertex
-fn vs_line(input: LineInput) -> LineOutput {
-    var out: LineOutput;
-    out.position = uniforms.view_proj * vec4<f32>(input.position, 1.0);
-    out.color = input.color;
-    return out;
-}
-
-@fragment
-fn fs_line(input: LineOutput) -> @location(0) vec4<f32> {
-    return vec4<f32>(input.color, 1.0);
-}
-"#,
-            )),
-        });
+impl PipelineState {
+    pub(super) fn new(
+        device: &egui_wgpu::wgpu::Device,
+        target_format: egui_wgpu::wgpu::TextureFormat,
+    ) -> Self {
+        let sources = ShaderSources::load();
 
         let uniform_buffer =
             device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
@@ -266,6 +175,16 @@ fn fs_line(input: LineOutput) -> @location(0) vec4<f32> {
                     light_params: [1.0, 0.4, 0.5, 0.15],
                     debug_params: [0.0, 0.5, 20.0, 4.0],
                     shadow_params: [0.0, 0.002, 0.0, 0.0],
+                    view_params: [0.0; 4],
+                    clip_plane: [0.0; 4],
+                    clip_params: [0.0; 4],
+                    render_origin: [0.0; 3],
+                    _pad5: 0.0,
+                    opacity: [1.0; 4],
+                    background_top: [0.0; 4],
+                    background_bottom: [0.0; 4],
+                    contact_footprint: [0.0; 4],
+                    contact_params: [0.0; 4],
                 }),
                 usage: egui_wgpu::wgpu::BufferUsages::UNIFORM
                     | egui_wgpu::wgpu::BufferUsages::COPY_DST,
@@ -374,166 +293,6 @@ fn fs_line(input: LineOutput) -> @location(0) vec4<f32> {
                 push_constant_ranges: &[],
             });
 
-        let mesh_pipeline =
-            device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
-                label: Some("grapho_viewport_pipeline"),
-                layout: Some(&pipeline_layout),
-                vertex: egui_wgpu::wgpu::VertexState {
-                    module: &shader,
-                    entry_point: Some("vs_main"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<Vertex>()
-                            as egui_wgpu::wgpu::BufferAddress,
-                        step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
-                        attributes: &VERTEX_ATTRIBUTES,
-                    }],
-                },
-                fragment: Some(egui_wgpu::wgpu::FragmentState {
-                    module: &shader,
-                    entry_point: Some("fs_main"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
-                        format: target_format,
-                        blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
-                        write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
-                    })],
-                }),
-                primitive: egui_wgpu::wgpu::PrimitiveState {
-                    topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
-                    ..Default::default()
-                },
-                depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
-                    format: DEPTH_FORMAT,
-                    depth_write_enabled: true,
-                    depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
-                    stencil: egui_wgpu::wgpu::StencilState::default(),
-                    bias: egui_wgpu::wgpu::DepthBiasState::default(),
-                }),
-                multisample: egui_wgpu::wgpu::MultisampleState::default(),
-                multiview: None,
-                cache: None,
-            });
-
-        let shadow_pipeline =
-            device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
-                label: Some("grapho_viewport_shadow"),
-                layout: Some(&shadow_pipeline_layout),
-                vertex: egui_wgpu::wgpu::VertexState {
-                    module: &shader,
-                    entry_point: Some("vs_shadow"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<Vertex>()
-                            as egui_wgpu::wgpu::BufferAddress,
-                        step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
-                        attributes: &VERTEX_ATTRIBUTES,
-                    }],
-                },
-                fragment: None,
-                primitive: egui_wgpu::wgpu::PrimitiveState {
-                    topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
-                    cull_mode: Some(egui_wgpu::wgpu::Face::Front),
-                    ..Default::default()
-                },
-                depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
-                    format: egui_wgpu::wgpu::TextureFormat::Depth32Float,
-                    depth_write_enabled: true,
-                    depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
-                    stencil: egui_wgpu::wgpu::StencilState::default(),
-                    bias: egui_wgpu::wgpu::DepthBiasState {
-                        constant: 1,
-                        slope_scale: 1.0,
-                        clamp: 0.0,
-                    },
-                }),
-                multisample: egui_wgpu::wgpu::MultisampleState::default(),
-                multiview: None,
-                cache: None,
-            });
-
-        let line_pipeline =
-            device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
-                label: Some("grapho_viewport_lines"),
-                layout: Some(&pipeline_layout),
-                vertex: egui_wgpu::wgpu::VertexState {
-                    module: &shader,
-                    entry_point: Some("vs_line"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
-                        array_stride: std::mem::size_of::<LineVertex>()
-                            as egui_wgpu::wgpu::BufferAddress,
-                        step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
-                        attributes: &LINE_ATTRIBUTES,
-                    }],
-                },
-                fragment: Some(egui_wgpu::wgpu::FragmentState {
-                    module: &shader,
-                    entry_point: Some("fs_line"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
-                        format: target_format,
-                        blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
-                        write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
-                    })],
-                }),
-                primitive: egui_wgpu::wgpu::PrimitiveState {
-                    topology: egui_wgpu::wgpu::PrimitiveTopology::LineList,
-                    ..Default::default()
-                },
-                depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
-                    format: DEPTH_FORMAT,
-                    depth_write_enabled: false,
-                    depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
-                    stencil: egui_wgpu::wgpu::StencilState::default(),
-                    bias: egui_wgpu::wgpu::DepthBiasState::default(),
-                }),
-                multisample: egui_wgpu::wgpu::MultisampleState::default(),
-                multiview: None,
-                cache: None,
-            });
-
-        let blit_shader = device.create_shader_module(egui_wgpu::wgpu::ShaderModuleDescriptor {
-            label: Some("grapho_viewport_blit"),
-            source: egui_wgpu::wgpu::ShaderSource::Wgsl(Cow::Borrowed(
-                r#"
-@group(0) @binding(0)
-var blit_tex: texture_2d<f32>;
-
-@group(0) @binding(1)
-var blit_sampler: sampler;
-
-struct BlitOut {
-    @builtin(position) position: vec4<f32>,
-    @location(0) uv: vec2<f32>,
-};
-
-@vertex
-fn vs_blit(@builtin(vertex_index) index: u32) -> BlitOut {
-    var positions = array<vec2<f32>, 3>(
-        vec2<f32>(-1.0, -1.0),
-        vec2<f32>(3.0, -1.0),
-        vec2<f32>(-1.0, 3.0),
-    );
-    var uvs = array<vec2<f32>, 3>(
-        vec2<f32>(0.0, 1.0),
-        vec2<f32>(2.0, 1.0),
-        vec2<f32>(0.0, -1.0),
-    );
-    var out: BlitOut;
-    out.position = vec4<f32>(positions[index], 0.0, 1.0);
-    out.uv = uvs[index];
-    return out;
-}
-
-@fragment
-fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
-    return textureSample(blit_tex, blit_sampler, input.uv);
-}
-"#,
-            )),
-        });
-
         let blit_bind_group_layout =
             device.create_bind_group_layout(&egui_wgpu::wgpu::BindGroupLayoutDescriptor {
                 label: Some("grapho_viewport_blit_layout"),
@@ -575,35 +334,28 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
                 push_constant_ranges: &[],
             });
 
-        let blit_pipeline =
-            device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
-                label: Some("grapho_viewport_blit_pipeline"),
-                layout: Some(&blit_pipeline_layout),
-                vertex: egui_wgpu::wgpu::VertexState {
-                    module: &blit_shader,
-                    entry_point: Some("vs_blit"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    buffers: &[],
-                },
-                fragment: Some(egui_wgpu::wgpu::FragmentState {
-                    module: &blit_shader,
-                    entry_point: Some("fs_blit"),
-                    compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
-                    targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
-                        format: target_format,
-                        blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
-                        write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
-                    })],
-                }),
-                primitive: egui_wgpu::wgpu::PrimitiveState::default(),
-                depth_stencil: None,
-                multisample: egui_wgpu::wgpu::MultisampleState::default(),
-                multiview: None,
-                cache: None,
-            });
+        let layouts = PipelineLayouts {
+            mesh: pipeline_layout,
+            shadow: shadow_pipeline_layout,
+            blit: blit_pipeline_layout,
+        };
+        let pipelines = create_pipelines(
+            device,
+            target_format,
+            &sources.viewport_module(None),
+            &sources.blit,
+            &layouts,
+        );
 
         let (offscreen_texture, offscreen_view, depth_texture, depth_view) =
             create_offscreen_targets(device, target_format, 1, 1);
+        let (outline_mask_texture, outline_mask_view) = create_outline_mask(device, 1, 1);
+        let outline_bind_group = outline_bind_group(
+            device,
+            &blit_bind_group_layout,
+            &outline_mask_view,
+            &blit_sampler,
+        );
         let blit_bind_group = device.create_bind_group(&egui_wgpu::wgpu::BindGroupDescriptor {
             label: Some("grapho_viewport_blit_group"),
             layout: &blit_bind_group_layout,
@@ -622,22 +374,32 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
         let mesh = cube_mesh();
         let mut mesh_cache = GpuMeshCache::new();
         let mesh_id = 1;
+        // The placeholder cube has no core mesh behind it, so no content hash either.
         mesh_cache.upload_or_update(
             device,
             mesh_id,
+            0,
             bytemuck::cast_slice(&mesh.vertices),
             &mesh.indices,
         );
         let index_count = mesh.indices.len() as u32;
-        let normals_length = 0.3;
-        let normals_vertices = normals_vertices(&mesh.vertices, normals_length);
         let normals_buffer =
             device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
                 label: Some("grapho_normals_vertices"),
-                contents: bytemuck::cast_slice(&normals_vertices),
-                usage: egui_wgpu::wgpu::BufferUsages::VERTEX
-                    | egui_wgpu::wgpu::BufferUsages::COPY_DST,
+                contents: bytemuck::cast_slice(&[LineVertex {
+                    position: [0.0, 0.0, 0.0],
+                    color: [0.0, 0.0, 0.0],
+                }]),
+                usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
             });
+        let wire_buffer = device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
+            label: Some("grapho_wire_vertices"),
+            contents: bytemuck::cast_slice(&[LineVertex {
+                position: [0.0, 0.0, 0.0],
+                color: [0.0, 0.0, 0.0],
+            }]),
+            usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
+        });
         let bounds_vertices = bounds_vertices(mesh.bounds_min, mesh.bounds_max);
         let bounds_buffer =
             device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
@@ -645,6 +407,25 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
                 contents: bytemuck::cast_slice(&bounds_vertices),
                 usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
             });
+        let symmetry_buffer =
+            device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
+                label: Some("grapho_symmetry_vertices"),
+                contents: bytemuck::cast_slice(&symmetry_plane_vertices(
+                    [1.0, 0.0, 0.0, 0.0],
+                    (mesh.bounds_min, mesh.bounds_max),
+                )),
+                usage: egui_wgpu::wgpu::BufferUsages::VERTEX
+                    | egui_wgpu::wgpu::BufferUsages::COPY_DST,
+            });
+        let scale_reference_lines =
+            scale_reference_vertices(1.0, (mesh.bounds_min, mesh.bounds_max));
+        let scale_reference_buffer =
+            device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
+                label: Some("grapho_scale_reference_vertices"),
+                contents: bytemuck::cast_slice(&scale_reference_lines),
+                usage: egui_wgpu::wgpu::BufferUsages::VERTEX
+                    | egui_wgpu::wgpu::BufferUsages::COPY_DST,
+            });
         let template_buffer =
             device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
                 label: Some("grapho_template_vertices"),
@@ -679,10 +460,22 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
         });
 
         Self {
-            mesh_pipeline,
-            shadow_pipeline,
-            line_pipeline,
-            blit_pipeline,
+            mesh_pipeline: pipelines.mesh,
+            shadow_pipeline: pipelines.shadow,
+            line_pipeline: pipelines.line,
+            transparent_pipelines: pipelines.transparent,
+            ghost_pipelines: pipelines.ghost,
+            background_pipeline: pipelines.background,
+            contact_pipeline: pipelines.contact,
+            mask_pipeline: pipelines.mask,
+            outline_pipeline: pipelines.outline,
+            id_pipeline: pipelines.id,
+            blit_pipeline: pipelines.blit,
+            layouts,
+            shader_sources: sources,
+            shader_watcher: ShaderWatcher::from_env(),
+            surface_shader: None,
+            surface_shader_version: 0,
             blit_bind_group,
             blit_bind_group_layout,
             blit_sampler,
@@ -695,14 +488,22 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
             depth_texture,
             depth_view,
             offscreen_size: [1, 1],
+            _outline_mask_texture: outline_mask_texture,
+            outline_mask_view,
+            outline_bind_group,
+            highlight_mesh: None,
+            highlight_version: 0,
+            id_picker: IdPicker::new(device),
             uniform_buffer,
             uniform_bind_group,
             shadow_bind_group,
             mesh_cache,
             mesh_id,
             mesh_vertices: mesh.vertices,
+            mesh_indices: mesh.indices,
             point_positions,
             mesh_bounds: (mesh.bounds_min, mesh.bounds_max),
+            mesh_opacity: 1.0,
             index_count,
             point_count,
             point_size,
@@ -714,16 +515,514 @@ fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
             axes_buffer,
             axes_count: axes_vertices.len() as u32,
             normals_buffer,
-            normals_count: normals_vertices.len() as u32,
-            normals_length,
+            normals_count: 0,
+            normals_key: None,
+            wire_buffer,
+            wire_count: 0,
+            wire_built: false,
             bounds_buffer,
             bounds_count: bounds_vertices.len() as u32,
+            symmetry_buffer,
+            symmetry_key: None,
+            scale_reference_buffer,
+            scale_reference_count: scale_reference_lines.len() as u32,
+            scale_reference_key: None,
             template_buffer,
             template_count: 0,
+            template_surface: None,
+            debug_channels: Vec::new(),
         }
     }
 }
 
+struct PipelineLayouts {
+    mesh: egui_wgpu::wgpu::PipelineLayout,
+    shadow: egui_wgpu::wgpu::PipelineLayout,
+    blit: egui_wgpu::wgpu::PipelineLayout,
+}
+
+struct ViewportPipelines {
+    mesh: egui_wgpu::wgpu::RenderPipeline,
+    shadow: egui_wgpu::wgpu::RenderPipeline,
+    line: egui_wgpu::wgpu::RenderPipeline,
+    transparent: [egui_wgpu::wgpu::RenderPipeline; 2],
+    ghost: [egui_wgpu::wgpu::RenderPipeline; 2],
+    background: egui_wgpu::wgpu::RenderPipeline,
+    contact: egui_wgpu::wgpu::RenderPipeline,
+    mask: egui_wgpu::wgpu::RenderPipeline,
+    outline: egui_wgpu::wgpu::RenderPipeline,
+    id: egui_wgpu::wgpu::RenderPipeline,
+    blit: egui_wgpu::wgpu::RenderPipeline,
+}
+
+fn create_pipelines(
+    device: &egui_wgpu::wgpu::Device,
+    target_format: egui_wgpu::wgpu::TextureFormat,
+    viewport_source: &str,
+    blit_source: &str,
+    layouts: &PipelineLayouts,
+) -> ViewportPipelines {
+    let shader = device.create_shader_module(egui_wgpu::wgpu::ShaderModuleDescriptor {
+        label: Some("grapho_viewport_shader"),
+        source: egui_wgpu::wgpu::ShaderSource::Wgsl(Cow::Borrowed(viewport_source)),
+    });
+
+    let mesh_pipeline = device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_pipeline"),
+        layout: Some(&layouts.mesh),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<Vertex>() as egui_wgpu::wgpu::BufferAddress,
+                step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                attributes: &VERTEX_ATTRIBUTES,
+            }],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_main"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: target_format,
+                blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState {
+            topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: true,
+            depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+            stencil: egui_wgpu::wgpu::StencilState::default(),
+            bias: egui_wgpu::wgpu::DepthBiasState::default(),
+        }),
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let shadow_pipeline =
+        device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+            label: Some("grapho_viewport_shadow"),
+            layout: Some(&layouts.shadow),
+            vertex: egui_wgpu::wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_shadow"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                    array_stride: std::mem::size_of::<Vertex>() as egui_wgpu::wgpu::BufferAddress,
+                    step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                    attributes: &VERTEX_ATTRIBUTES,
+                }],
+            },
+            fragment: None,
+            primitive: egui_wgpu::wgpu::PrimitiveState {
+                topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
+                cull_mode: Some(egui_wgpu::wgpu::Face::Front),
+                ..Default::default()
+            },
+            depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+                format: egui_wgpu::wgpu::TextureFormat::Depth32Float,
+                depth_write_enabled: true,
+                depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+                stencil: egui_wgpu::wgpu::StencilState::default(),
+                bias: egui_wgpu::wgpu::DepthBiasState {
+                    constant: 1,
+                    slope_scale: 1.0,
+                    clamp: 0.0,
+                },
+            }),
+            multisample: egui_wgpu::wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+    let line_pipeline = device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_lines"),
+        layout: Some(&layouts.mesh),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_line"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<LineVertex>() as egui_wgpu::wgpu::BufferAddress,
+                step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                attributes: &LINE_ATTRIBUTES,
+            }],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_line"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: target_format,
+                blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState {
+            topology: egui_wgpu::wgpu::PrimitiveTopology::LineList,
+            ..Default::default()
+        },
+        depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+            stencil: egui_wgpu::wgpu::StencilState::default(),
+            bias: egui_wgpu::wgpu::DepthBiasState::default(),
+        }),
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    // Gradient backgrounds are drawn first in the main pass; depth stays cleared at 1.0.
+    let background_pipeline =
+        device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+            label: Some("grapho_viewport_background"),
+            layout: Some(&layouts.mesh),
+            vertex: egui_wgpu::wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_background"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(egui_wgpu::wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_background"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                    format: target_format,
+                    blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                    write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: egui_wgpu::wgpu::PrimitiveState::default(),
+            depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: egui_wgpu::wgpu::CompareFunction::Always,
+                stencil: egui_wgpu::wgpu::StencilState::default(),
+                bias: egui_wgpu::wgpu::DepthBiasState::default(),
+            }),
+            multisample: egui_wgpu::wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+    // Contact shadow: a blended quad on the floor, hidden by the opaque mesh through depth.
+    let contact_pipeline =
+        device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+            label: Some("grapho_viewport_contact_shadow"),
+            layout: Some(&layouts.mesh),
+            vertex: egui_wgpu::wgpu::VertexState {
+                module: &shader,
+                entry_point: Some("vs_contact"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(egui_wgpu::wgpu::FragmentState {
+                module: &shader,
+                entry_point: Some("fs_contact"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                    format: target_format,
+                    blend: Some(egui_wgpu::wgpu::BlendState {
+                        color: egui_wgpu::wgpu::BlendComponent {
+                            src_factor: egui_wgpu::wgpu::BlendFactor::SrcAlpha,
+                            dst_factor: egui_wgpu::wgpu::BlendFactor::OneMinusSrcAlpha,
+                            operation: egui_wgpu::wgpu::BlendOperation::Add,
+                        },
+                        alpha: egui_wgpu::wgpu::BlendComponent::OVER,
+                    }),
+                    write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: egui_wgpu::wgpu::PrimitiveState::default(),
+            depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+                format: DEPTH_FORMAT,
+                depth_write_enabled: false,
+                depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+                stencil: egui_wgpu::wgpu::StencilState::default(),
+                bias: egui_wgpu::wgpu::DepthBiasState::default(),
+            }),
+            multisample: egui_wgpu::wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+    // Selection outline: the selected mesh is rasterized into a coverage mask, then a
+    // fullscreen pass over the offscreen target paints the mask's outer edge.
+    let mask_pipeline = device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_outline_mask"),
+        layout: Some(&layouts.mesh),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_main"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<Vertex>() as egui_wgpu::wgpu::BufferAddress,
+                step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                attributes: &VERTEX_ATTRIBUTES,
+            }],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_mask"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: OUTLINE_MASK_FORMAT,
+                blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState {
+            topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: None,
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    // Triangle ids for picking; tested against the depth the main pass left behind.
+    let id_pipeline = device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_ids"),
+        layout: Some(&layouts.mesh),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: &shader,
+            entry_point: Some("vs_id"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<IdVertex>() as egui_wgpu::wgpu::BufferAddress,
+                step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                attributes: &ID_ATTRIBUTES,
+            }],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: &shader,
+            entry_point: Some("fs_id"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: ID_FORMAT,
+                blend: None,
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState {
+            topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
+            ..Default::default()
+        },
+        depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+            stencil: egui_wgpu::wgpu::StencilState::default(),
+            bias: egui_wgpu::wgpu::DepthBiasState::default(),
+        }),
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let blit_shader = device.create_shader_module(egui_wgpu::wgpu::ShaderModuleDescriptor {
+        label: Some("grapho_viewport_blit"),
+        source: egui_wgpu::wgpu::ShaderSource::Wgsl(Cow::Borrowed(blit_source)),
+    });
+
+    let blit_pipeline = device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_blit_pipeline"),
+        layout: Some(&layouts.blit),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: &blit_shader,
+            entry_point: Some("vs_blit"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: &blit_shader,
+            entry_point: Some("fs_blit"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: target_format,
+                blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState::default(),
+        depth_stencil: None,
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    });
+
+    let outline_pipeline =
+        device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+            label: Some("grapho_viewport_outline"),
+            layout: Some(&layouts.blit),
+            vertex: egui_wgpu::wgpu::VertexState {
+                module: &blit_shader,
+                entry_point: Some("vs_blit"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                buffers: &[],
+            },
+            fragment: Some(egui_wgpu::wgpu::FragmentState {
+                module: &blit_shader,
+                entry_point: Some("fs_outline"),
+                compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+                targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                    format: target_format,
+                    blend: Some(egui_wgpu::wgpu::BlendState::REPLACE),
+                    write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: egui_wgpu::wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: egui_wgpu::wgpu::MultisampleState::default(),
+            multiview: None,
+            cache: None,
+        });
+
+    ViewportPipelines {
+        mesh: mesh_pipeline,
+        shadow: shadow_pipeline,
+        line: line_pipeline,
+        transparent: [egui_wgpu::wgpu::Face::Front, egui_wgpu::wgpu::Face::Back].map(|cull| {
+            transparent_pipeline(device, target_format, &shader, layouts, "fs_main", cull)
+        }),
+        ghost: [egui_wgpu::wgpu::Face::Front, egui_wgpu::wgpu::Face::Back].map(|cull| {
+            transparent_pipeline(device, target_format, &shader, layouts, "fs_ghost", cull)
+        }),
+        background: background_pipeline,
+        contact: contact_pipeline,
+        mask: mask_pipeline,
+        outline: outline_pipeline,
+        id: id_pipeline,
+        blit: blit_pipeline,
+    }
+}
+
+// Alpha-blended surfaces test against the opaque depth but never write it. Each mesh is
+// drawn twice, culling front faces and then back faces, so its far side always lands
+// before its near side; whole meshes are sorted back to front by the caller.
+fn transparent_pipeline(
+    device: &egui_wgpu::wgpu::Device,
+    target_format: egui_wgpu::wgpu::TextureFormat,
+    shader: &egui_wgpu::wgpu::ShaderModule,
+    layouts: &PipelineLayouts,
+    fragment_entry: &str,
+    cull: egui_wgpu::wgpu::Face,
+) -> egui_wgpu::wgpu::RenderPipeline {
+    device.create_render_pipeline(&egui_wgpu::wgpu::RenderPipelineDescriptor {
+        label: Some("grapho_viewport_transparent"),
+        layout: Some(&layouts.mesh),
+        vertex: egui_wgpu::wgpu::VertexState {
+            module: shader,
+            entry_point: Some("vs_main"),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            buffers: &[egui_wgpu::wgpu::VertexBufferLayout {
+                array_stride: std::mem::size_of::<Vertex>() as egui_wgpu::wgpu::BufferAddress,
+                step_mode: egui_wgpu::wgpu::VertexStepMode::Vertex,
+                attributes: &VERTEX_ATTRIBUTES,
+            }],
+        },
+        fragment: Some(egui_wgpu::wgpu::FragmentState {
+            module: shader,
+            entry_point: Some(fragment_entry),
+            compilation_options: egui_wgpu::wgpu::PipelineCompilationOptions::default(),
+            targets: &[Some(egui_wgpu::wgpu::ColorTargetState {
+                format: target_format,
+                // Alpha accumulates as "over" so the offscreen target stays opaque for the blit.
+                blend: Some(egui_wgpu::wgpu::BlendState {
+                    color: egui_wgpu::wgpu::BlendComponent {
+                        src_factor: egui_wgpu::wgpu::BlendFactor::SrcAlpha,
+                        dst_factor: egui_wgpu::wgpu::BlendFactor::OneMinusSrcAlpha,
+                        operation: egui_wgpu::wgpu::BlendOperation::Add,
+                    },
+                    alpha: egui_wgpu::wgpu::BlendComponent::OVER,
+                }),
+                write_mask: egui_wgpu::wgpu::ColorWrites::ALL,
+            })],
+        }),
+        primitive: egui_wgpu::wgpu::PrimitiveState {
+            topology: egui_wgpu::wgpu::PrimitiveTopology::TriangleList,
+            cull_mode: Some(cull),
+            ..Default::default()
+        },
+        depth_stencil: Some(egui_wgpu::wgpu::DepthStencilState {
+            format: DEPTH_FORMAT,
+            depth_write_enabled: false,
+            depth_compare: egui_wgpu::wgpu::CompareFunction::LessEqual,
+            stencil: egui_wgpu::wgpu::StencilState::default(),
+            bias: egui_wgpu::wgpu::DepthBiasState::default(),
+        }),
+        multisample: egui_wgpu::wgpu::MultisampleState::default(),
+        multiview: None,
+        cache: None,
+    })
+}
+
+pub(super) fn reload_shaders(
+    device: &egui_wgpu::wgpu::Device,
+    pipeline: &mut PipelineState,
+    target_format: egui_wgpu::wgpu::TextureFormat,
+) {
+    let Some(sources) = pipeline
+        .shader_watcher
+        .as_mut()
+        .and_then(ShaderWatcher::poll)
+    else {
+        return;
+    };
+    pipeline.shader_sources = sources;
+    rebuild_pipelines(device, pipeline, target_format);
+    tracing::info!("Viewport shaders reloaded");
+}
+
+pub(super) fn set_surface_shader(
+    device: &egui_wgpu::wgpu::Device,
+    pipeline: &mut PipelineState,
+    target_format: egui_wgpu::wgpu::TextureFormat,
+    surface_shader: Option<String>,
+) {
+    pipeline.surface_shader = surface_shader;
+    rebuild_pipelines(device, pipeline, target_format);
+}
+
+fn rebuild_pipelines(
+    device: &egui_wgpu::wgpu::Device,
+    pipeline: &mut PipelineState,
+    target_format: egui_wgpu::wgpu::TextureFormat,
+) {
+    let viewport_source = pipeline
+        .shader_sources
+        .viewport_module(pipeline.surface_shader.as_deref());
+    let pipelines = create_pipelines(
+        device,
+        target_format,
+        &viewport_source,
+        &pipeline.shader_sources.blit,
+        &pipeline.layouts,
+    );
+    pipeline.mesh_pipeline = pipelines.mesh;
+    pipeline.shadow_pipeline = pipelines.shadow;
+    pipeline.line_pipeline = pipelines.line;
+    pipeline.transparent_pipelines = pipelines.transparent;
+    pipeline.ghost_pipelines = pipelines.ghost;
+    pipeline.background_pipeline = pipelines.background;
+    pipeline.contact_pipeline = pipelines.contact;
+    pipeline.mask_pipeline = pipelines.mask;
+    pipeline.outline_pipeline = pipelines.outline;
+    pipeline.id_pipeline = pipelines.id;
+    pipeline.blit_pipeline = pipelines.blit;
+}
+
 pub(super) fn apply_scene_to_pipeline(
     device: &egui_wgpu::wgpu::Device,
     pipeline: &mut PipelineState,
@@ -733,25 +1032,24 @@ pub(super) fn apply_scene_to_pipeline(
     pipeline.mesh_cache.upload_or_update(
         device,
         pipeline.mesh_id,
+        scene.mesh.content_hash,
         bytemuck::cast_slice(&vertices),
         &indices,
     );
 
     pipeline.mesh_vertices = vertices;
     pipeline.index_count = indices.len() as u32;
+    pipeline.mesh_indices = indices;
+    pipeline.id_picker.invalidate_mesh();
     pipeline.point_count = pipeline.mesh_vertices.len() as u32;
     pipeline.point_positions = scene.mesh.positions.clone();
     pipeline.point_size = -1.0;
     pipeline.mesh_bounds = bounds_from_positions(&scene.mesh.positions);
+    pipeline.mesh_opacity = scene.mesh.opacity.clamp(0.0, 1.0);
 
-    let normals_vertices = normals_vertices(&pipeline.mesh_vertices, pipeline.normals_length);
-    pipeline.normals_buffer =
-        device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
-            label: Some("grapho_normals_vertices"),
-            contents: bytemuck::cast_slice(&normals_vertices),
-            usage: egui_wgpu::wgpu::BufferUsages::VERTEX | egui_wgpu::wgpu::BufferUsages::COPY_DST,
-        });
-    pipeline.normals_count = normals_vertices.len() as u32;
+    // Rebuilt on the next frame that shows normals or the wireframe.
+    pipeline.normals_key = None;
+    pipeline.wire_built = false;
 
     let bounds_vertices = bounds_vertices(pipeline.mesh_bounds.0, pipeline.mesh_bounds.1);
     pipeline.bounds_buffer =
@@ -763,7 +1061,7 @@ pub(super) fn apply_scene_to_pipeline(
     pipeline.bounds_count = bounds_vertices.len() as u32;
 
     let template_lines = if let Some(template) = &scene.template_mesh {
-        wireframe_vertices(&template.positions, &template.indices)
+        wireframe_vertices(&template.positions, &template.indices, TEMPLATE_WIRE_COLOR)
     } else {
         Vec::new()
     };
@@ -779,6 +1077,134 @@ pub(super) fn apply_scene_to_pipeline(
             });
         pipeline.template_count = template_lines.len() as u32;
     }
+
+    pipeline.template_surface = match &scene.template_mesh {
+        Some(template) if template.opacity > 0.0 && !template.indices.is_empty() => {
+            if !pipeline
+                .mesh_cache
+                .is_current(TEMPLATE_MESH_ID, template.content_hash)
+            {
+                let (vertices, indices) = build_vertices(template);
+                pipeline.mesh_cache.upload_or_update(
+                    device,
+                    TEMPLATE_MESH_ID,
+                    template.content_hash,
+                    bytemuck::cast_slice(&vertices),
+                    &indices,
+                );
+            }
+            Some(TemplateSurface {
+                opacity: template.opacity.min(1.0),
+                bounds: bounds_from_positions(&template.positions),
+            })
+        }
+        _ => {
+            pipeline.mesh_cache.remove(TEMPLATE_MESH_ID);
+            None
+        }
+    };
+
+    let (min, max) = pipeline.mesh_bounds;
+    let extent = glam::Vec3::from(max) - glam::Vec3::from(min);
+    let cross_size = (extent.length() * 0.005).max(0.005);
+    pipeline.debug_channels = scene
+        .debug_channels
+        .iter()
+        .enumerate()
+        .filter_map(|(index, channel)| {
+            let lines = debug_channel_vertices(channel, cross_size);
+            if lines.is_empty() {
+                return None;
+            }
+            let buffer = device.create_buffer_init(&egui_wgpu::wgpu::util::BufferInitDescriptor {
+                label: Some("grapho_debug_channel_vertices"),
+                contents: bytemuck::cast_slice(&lines),
+                usage: egui_wgpu::wgpu::BufferUsages::VERTEX,
+            });
+            Some(DebugChannelBuffer {
+                index,
+                buffer,
+                count: lines.len() as u32,
+            })
+        })
+        .collect();
+}
+
+pub(super) fn apply_highlight_to_pipeline(
+    device: &egui_wgpu::wgpu::Device,
+    pipeline: &mut PipelineState,
+    highlight: Option<&RenderHighlight>,
+) {
+    let mesh_id = match highlight {
+        Some(RenderHighlight::Display) => Some(pipeline.mesh_id),
+        Some(RenderHighlight::Mesh(mesh)) if !mesh.indices.is_empty() => {
+            if !pipeline
+                .mesh_cache
+                .is_current(HIGHLIGHT_MESH_ID, mesh.content_hash)
+            {
+                let (vertices, indices) = build_vertices(mesh);
+                pipeline.mesh_cache.upload_or_update(
+                    device,
+                    HIGHLIGHT_MESH_ID,
+                    mesh.content_hash,
+                    bytemuck::cast_slice(&vertices),
+                    &indices,
+                );
+            }
+            Some(HIGHLIGHT_MESH_ID)
+        }
+        _ => None,
+    };
+    if mesh_id != Some(HIGHLIGHT_MESH_ID) {
+        pipeline.mesh_cache.remove(HIGHLIGHT_MESH_ID);
+    }
+    pipeline.highlight_mesh = mesh_id;
+}
+
+fn create_outline_mask(
+    device: &egui_wgpu::wgpu::Device,
+    width: u32,
+    height: u32,
+) -> (egui_wgpu::wgpu::Texture, egui_wgpu::wgpu::TextureView) {
+    let texture = device.create_texture(&egui_wgpu::wgpu::TextureDescriptor {
+        label: Some("grapho_viewport_outline_mask"),
+        size: egui_wgpu::wgpu::Extent3d {
+            width: width.max(1),
+            height: height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count: 1,
+        dimension: egui_wgpu::wgpu::TextureDimension::D2,
+        format: OUTLINE_MASK_FORMAT,
+        usage: egui_wgpu::wgpu::TextureUsages::RENDER_ATTACHMENT
+            | egui_wgpu::wgpu::TextureUsages::TEXTURE_BINDING,
+        view_formats: &[],
+    });
+    let view = texture.create_view(&egui_wgpu::wgpu::TextureViewDescriptor::default());
+    (texture, view)
+}
+
+fn outline_bind_group(
+    device: &egui_wgpu::wgpu::Device,
+    layout: &egui_wgpu::wgpu::BindGroupLayout,
+    mask_view: &egui_wgpu::wgpu::TextureView,
+    sampler: &egui_wgpu::wgpu::Sampler,
+) -> egui_wgpu::wgpu::BindGroup {
+    device.create_bind_group(&egui_wgpu::wgpu::BindGroupDescriptor {
+        label: Some("grapho_viewport_outline_group"),
+        layout,
+        entries: &[
+            egui_wgpu::wgpu::BindGroupEntry {
+                binding: 0,
+                resource: egui_wgpu::wgpu::BindingResource::TextureView(mask_view),
+            },
+            egui_wgpu::wgpu::BindGroupEntry {
+                binding: 1,
+                resource: egui_wgpu::wgpu::BindingResource::Sampler(sampler),
+            },
+        ],
+    })
 }
 
 fn create_offscreen_targets(
@@ -870,6 +1296,15 @@ pub(super) fn ensure_offscreen_targets(
     pipeline.depth_texture = depth_texture;
     pipeline.depth_view = depth_view;
     pipeline.offscreen_size = [width, height];
+    let (outline_mask_texture, outline_mask_view) = create_outline_mask(device, width, height);
+    pipeline._outline_mask_texture = outline_mask_texture;
+    pipeline.outline_mask_view = outline_mask_view;
+    pipeline.outline_bind_group = outline_bind_group(
+        device,
+        &pipeline.blit_bind_group_layout,
+        &pipeline.outline_mask_view,
+        &pipeline.blit_sampler,
+    );
     pipeline.blit_bind_group = device.create_bind_group(&egui_wgpu::wgpu::BindGroupDescriptor {
         label: Some("grapho_viewport_blit_group"),
         layout: &pipeline.blit_bind_group_layout,
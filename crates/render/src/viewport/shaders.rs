@@ -0,0 +1,158 @@
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+use web_time::Instant;
+
+const VIEWPORT_WGSL: &str = include_str!("shaders/viewport.wgsl");
+const SURFACE_WGSL: &str = include_str!("shaders/surface.wgsl");
+const BLIT_WGSL: &str = include_str!("shaders/blit.wgsl");
+const SHADER_FILES: [&str; 3] = ["viewport.wgsl", "surface.wgsl", "blit.wgsl"];
+const SHADER_DIR_ENV: &str = "GRAPHO_SHADER_DIR";
+const HOT_RELOAD_ENV: &str = "GRAPHO_SHADER_HOT_RELOAD";
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+pub(super) struct ShaderSources {
+    viewport: String,
+    surface: String,
+    pub(super) blit: String,
+}
+
+impl ShaderSources {
+    fn embedded() -> Self {
+        Self {
+            viewport: VIEWPORT_WGSL.to_string(),
+            surface: SURFACE_WGSL.to_string(),
+            blit: BLIT_WGSL.to_string(),
+        }
+    }
+
+    pub(super) fn viewport_module(&self, surface_override: Option<&str>) -> String {
+        if let Some(snippet) = surface_override.filter(|snippet| !snippet.trim().is_empty()) {
+            let source = format!("{}\n{}", self.viewport, snippet);
+            match validate_wgsl(&source) {
+                Ok(()) => return source,
+                Err(err) => {
+                    tracing::error!("Custom surface shader failed to compile:\n{err}");
+                }
+            }
+        }
+        format!("{}\n{}", self.viewport, self.surface)
+    }
+
+    pub(super) fn load() -> Self {
+        if cfg!(target_arch = "wasm32") {
+            return Self::embedded();
+        }
+        match load_from_dir(&shader_dir()) {
+            Ok(sources) => sources,
+            Err(err) => {
+                tracing::error!("{err}; using built-in viewport shaders");
+                Self::embedded()
+            }
+        }
+    }
+}
+
+pub(super) struct ShaderWatcher {
+    dir: PathBuf,
+    modified: Vec<Option<SystemTime>>,
+    last_poll: Instant,
+}
+
+impl ShaderWatcher {
+    pub(super) fn from_env() -> Option<Self> {
+        if cfg!(target_arch = "wasm32") {
+            return None;
+        }
+        let enabled = std::env::var(HOT_RELOAD_ENV)
+            .map(|value| !value.is_empty() && value != "0")
+            .unwrap_or(false);
+        if !enabled {
+            return None;
+        }
+        let dir = shader_dir();
+        tracing::info!("Watching viewport shaders in {}", dir.display());
+        Some(Self {
+            modified: modified_times(&dir),
+            dir,
+            last_poll: Instant::now(),
+        })
+    }
+
+    pub(super) fn poll(&mut self) -> Option<ShaderSources> {
+        if self.last_poll.elapsed() < POLL_INTERVAL {
+            return None;
+        }
+        self.last_poll = Instant::now();
+        let modified = modified_times(&self.dir);
+        if modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        match load_from_dir(&self.dir) {
+            Ok(sources) => Some(sources),
+            Err(err) => {
+                tracing::error!("{err}; keeping previous viewport shaders");
+                None
+            }
+        }
+    }
+}
+
+fn shader_dir() -> PathBuf {
+    std::env::var_os(SHADER_DIR_ENV)
+        .map(PathBuf::from)
+        .unwrap_or_else(|| {
+            Path::new(env!("CARGO_MANIFEST_DIR"))
+                .join("src")
+                .join("viewport")
+                .join("shaders")
+        })
+}
+
+fn load_from_dir(dir: &Path) -> Result<ShaderSources, String> {
+    let sources = ShaderSources {
+        viewport: load_shader(dir, "viewport.wgsl", VIEWPORT_WGSL)?,
+        surface: load_shader(dir, "surface.wgsl", SURFACE_WGSL)?,
+        blit: load_shader(dir, "blit.wgsl", BLIT_WGSL)?,
+    };
+    validate_wgsl(&format!("{}\n{}", sources.viewport, sources.surface))
+        .map_err(|err| format!("Shader viewport.wgsl failed to compile:\n{err}"))?;
+    validate_wgsl(&sources.blit)
+        .map_err(|err| format!("Shader blit.wgsl failed to compile:\n{err}"))?;
+    Ok(sources)
+}
+
+fn load_shader(dir: &Path, name: &str, fallback: &str) -> Result<String, String> {
+    let path = dir.join(name);
+    match std::fs::read_to_string(&path) {
+        Ok(source) => Ok(source),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(fallback.to_string()),
+        Err(err) => Err(format!("Failed to read {}: {err}", path.display())),
+    }
+}
+
+fn validate_wgsl(source: &str) -> Result<(), String> {
+    let module = naga::front::wgsl::parse_str(source).map_err(|err| err.emit_to_string(source))?;
+    naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::default(),
+    )
+    .validate(&module)
+    .map_err(|err| err.emit_to_string(source))?;
+    Ok(())
+}
+
+fn modified_times(dir: &Path) -> Vec<Option<SystemTime>> {
+    SHADER_FILES
+        .iter()
+        .map(|name| {
+            std::fs::metadata(dir.join(name))
+                .and_then(|meta| meta.modified())
+                .ok()
+        })
+        .collect()
+}
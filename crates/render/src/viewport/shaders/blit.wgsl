@@ -0,0 +1,56 @@
+@group(0) @binding(0)
+var blit_tex: texture_2d<f32>;
+
+@group(0) @binding(1)
+var blit_sampler: sampler;
+
+struct BlitOut {
+    @builtin(position) position: vec4<f32>,
+    @location(0) uv: vec2<f32>,
+};
+
+@vertex
+fn vs_blit(@builtin(vertex_index) index: u32) -> BlitOut {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+    var uvs = array<vec2<f32>, 3>(
+        vec2<f32>(0.0, 1.0),
+        vec2<f32>(2.0, 1.0),
+        vec2<f32>(0.0, -1.0),
+    );
+    var out: BlitOut;
+    out.position = vec4<f32>(positions[index], 0.0, 1.0);
+    out.uv = uvs[index];
+    return out;
+}
+
+@fragment
+fn fs_blit(input: BlitOut) -> @location(0) vec4<f32> {
+    return textureSample(blit_tex, blit_sampler, input.uv);
+}
+
+const OUTLINE_RADIUS: i32 = 2;
+
+// blit_tex is the selection mask here; pixels just outside its coverage get the outline.
+@fragment
+fn fs_outline(input: BlitOut) -> @location(0) vec4<f32> {
+    let pixel = vec2<i32>(input.position.xy);
+    let last = vec2<i32>(textureDimensions(blit_tex)) - vec2<i32>(1);
+    if textureLoad(blit_tex, pixel, 0).r > 0.5 {
+        discard;
+    }
+    var coverage = 0.0;
+    for (var y = -OUTLINE_RADIUS; y <= OUTLINE_RADIUS; y++) {
+        for (var x = -OUTLINE_RADIUS; x <= OUTLINE_RADIUS; x++) {
+            let sample = clamp(pixel + vec2<i32>(x, y), vec2<i32>(0), last);
+            coverage = max(coverage, textureLoad(blit_tex, sample, 0).r);
+        }
+    }
+    if coverage < 0.5 {
+        discard;
+    }
+    return vec4<f32>(1.0, 0.6, 0.15, 1.0);
+}
@@ -0,0 +1,4 @@
+// world_pos is relative to the camera target; add uniforms.render_origin for world space.
+fn surface_color(normal: vec3<f32>, world_pos: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
+    return shade_surface(normal, world_pos, color);
+}
@@ -0,0 +1,333 @@
+struct Uniforms {
+    view_proj: mat4x4<f32>,
+    light_view_proj: mat4x4<f32>,
+    key_dir: vec3<f32>,
+    _pad0: f32,
+    fill_dir: vec3<f32>,
+    _pad1: f32,
+    rim_dir: vec3<f32>,
+    _pad2: f32,
+    camera_pos: vec3<f32>,
+    _pad3: f32,
+    base_color: vec3<f32>,
+    _pad4: f32,
+    light_params: vec4<f32>,
+    debug_params: vec4<f32>,
+    shadow_params: vec4<f32>,
+    view_params: vec4<f32>,
+    clip_plane: vec4<f32>,
+    clip_params: vec4<f32>,
+    render_origin: vec3<f32>,
+    _pad5: f32,
+    // x: display mesh, y: template ghost.
+    opacity: vec4<f32>,
+    background_top: vec4<f32>,
+    background_bottom: vec4<f32>,
+    // Mesh footprint on the floor: min x, min z, max x, max z.
+    contact_footprint: vec4<f32>,
+    // x: bottom of the mesh, y: strength.
+    contact_params: vec4<f32>,
+};
+
+@group(0) @binding(0)
+var<uniform> uniforms: Uniforms;
+
+@group(0) @binding(1)
+var shadow_tex: texture_depth_2d;
+
+@group(0) @binding(2)
+var shadow_sampler: sampler_comparison;
+
+struct VertexInput {
+    @location(0) position: vec3<f32>,
+    @location(1) normal: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+struct VertexOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) normal: vec3<f32>,
+    @location(1) world_pos: vec3<f32>,
+    @location(2) color: vec3<f32>,
+};
+
+@vertex
+fn vs_main(input: VertexInput) -> VertexOutput {
+    var out: VertexOutput;
+    // Positions are shifted by the camera target before projection; nearby values
+    // subtract exactly, so large world coordinates do not jitter.
+    let local_pos = input.position - uniforms.render_origin;
+    out.world_pos = local_pos;
+    out.normal = input.normal;
+    out.color = input.color;
+    out.position = uniforms.view_proj * vec4<f32>(local_pos, 1.0);
+    return out;
+}
+
+fn shadow_factor(world_pos: vec3<f32>, normal: vec3<f32>) -> f32 {
+    let enabled = select(0.0, 1.0, uniforms.shadow_params.x >= 0.5);
+    let n = normalize(normal);
+    let light_dir = normalize(uniforms.key_dir);
+    let ndotl = max(dot(n, light_dir), 0.0);
+    let bias = uniforms.shadow_params.y + (1.0 - ndotl) * uniforms.shadow_params.y;
+    let offset_pos = world_pos + n * uniforms.shadow_params.w;
+    let light_clip = uniforms.light_view_proj * vec4<f32>(offset_pos, 1.0);
+    let ndc = light_clip.xyz / max(light_clip.w, 0.0001);
+    let uv = vec2<f32>(ndc.x * 0.5 + 0.5, 0.5 - ndc.y * 0.5);
+    let in_bounds = uv.x >= 0.0 && uv.x <= 1.0 && uv.y >= 0.0 && uv.y <= 1.0 && ndc.z >= 0.0 && ndc.z <= 1.0;
+    let in_bounds_f = select(0.0, 1.0, in_bounds);
+    let uv_clamped = clamp(uv, vec2<f32>(0.0), vec2<f32>(1.0));
+    let depth = ndc.z - bias;
+    let texel = max(uniforms.shadow_params.z, 0.000001);
+    var shadow = 0.0;
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, -texel), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(0.0, -texel), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, -texel), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, 0.0), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped, depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, 0.0), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(-texel, texel), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(0.0, texel), depth);
+    shadow = shadow + textureSampleCompare(shadow_tex, shadow_sampler, uv_clamped + vec2<f32>(texel, texel), depth);
+    let visibility = shadow / 9.0;
+    let lit = 1.0 - in_bounds_f + visibility * in_bounds_f;
+    return 1.0 - enabled + enabled * lit;
+}
+
+fn shade_surface(normal: vec3<f32>, world_pos: vec3<f32>, color: vec3<f32>) -> vec3<f32> {
+    let n = normalize(normal);
+    let view_dir = normalize(uniforms.camera_pos - world_pos);
+    let key_dir = normalize(uniforms.key_dir);
+    let fill_dir = normalize(uniforms.fill_dir);
+    let rim_dir = normalize(uniforms.rim_dir);
+
+    let key_ndotl = max(dot(n, key_dir), 0.0);
+    let fill_ndotl = max(dot(n, fill_dir), 0.0);
+    let rim_ndotl = max(dot(n, rim_dir), 0.0);
+
+    let half_dir = normalize(key_dir + view_dir);
+    let spec = pow(max(dot(n, half_dir), 0.0), 32.0);
+
+    let shadow = shadow_factor(world_pos, normal);
+    let key = key_ndotl * uniforms.light_params.x * shadow;
+    let fill = fill_ndotl * uniforms.light_params.y;
+    let rim = rim_ndotl * uniforms.light_params.z;
+    let ambient = uniforms.light_params.w;
+
+    let base = color * uniforms.base_color;
+    return base * (ambient + key + fill + rim) + vec3<f32>(0.9) * spec * 0.2 * shadow;
+}
+
+@fragment
+fn fs_main(input: VertexOutput, @builtin(front_facing) front_facing: bool) -> @location(0) vec4<f32> {
+    let flat_normal = normalize(cross(dpdy(input.world_pos), dpdx(input.world_pos)));
+    let plane_dist = dot(uniforms.clip_plane.xyz, input.world_pos) - uniforms.clip_plane.w;
+    let plane_width = fwidth(plane_dist);
+    let cull = i32(uniforms.view_params.x + 0.5);
+    if (cull == 1 && !front_facing) || (cull == 2 && front_facing) {
+        discard;
+    }
+    let sectioned = uniforms.clip_params.x >= 0.5;
+    if sectioned && plane_dist > 0.0 {
+        discard;
+    }
+    if uniforms.clip_params.y > 0.0
+        && distance(uniforms.camera_pos, input.world_pos) < uniforms.clip_params.y {
+        discard;
+    }
+    // The cut edge is a screen-space band along the plane; back faces seen through
+    // the opening are flat-tinted so the inside of a closed mesh reads as a cap.
+    if sectioned && uniforms.clip_params.z >= 0.5 {
+        if plane_dist > -1.5 * plane_width {
+            return vec4<f32>(1.0, 0.55, 0.1, 1.0);
+        }
+        if !front_facing {
+            return vec4<f32>(0.55, 0.3, 0.22, 1.0);
+        }
+    }
+    var normal = normalize(input.normal);
+    if uniforms.view_params.z >= 0.5 {
+        normal = select(-flat_normal, flat_normal, front_facing);
+    }
+    if uniforms.view_params.y >= 0.5 && !front_facing {
+        normal = -normal;
+    }
+
+    let color = surface_color(normal, input.world_pos, input.color);
+    let mode = i32(uniforms.debug_params.x + 0.5);
+    let alpha = uniforms.opacity.x;
+    if mode == 1 {
+        return vec4<f32>(normal * 0.5 + vec3<f32>(0.5), alpha);
+    }
+    if mode == 2 {
+        let near = uniforms.debug_params.y;
+        let far = uniforms.debug_params.z;
+        let denom = max(far - near, 0.0001);
+        let dist = distance(uniforms.camera_pos, input.world_pos);
+        let t = clamp((dist - near) / denom, 0.0, 1.0);
+        return vec4<f32>(vec3<f32>(1.0 - t), alpha);
+    }
+    if mode == 3 {
+        return vec4<f32>(input.color, alpha);
+    }
+    return vec4<f32>(color, alpha);
+}
+
+// Template meshes: plain two-sided shading, no clipping or debug modes.
+@fragment
+fn fs_ghost(input: VertexOutput, @builtin(front_facing) front_facing: bool) -> @location(0) vec4<f32> {
+    let normal = select(-input.normal, input.normal, front_facing);
+    let color = shade_surface(normal, input.world_pos, input.color);
+    return vec4<f32>(color, uniforms.opacity.y);
+}
+
+@fragment
+fn fs_mask() -> @location(0) vec4<f32> {
+    return vec4<f32>(1.0);
+}
+
+struct BackgroundOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) height: f32,
+};
+
+// Fullscreen triangle at the far plane; height runs 0 at the bottom edge to 1 at the top.
+@vertex
+fn vs_background(@builtin(vertex_index) index: u32) -> BackgroundOutput {
+    var positions = array<vec2<f32>, 3>(
+        vec2<f32>(-1.0, -1.0),
+        vec2<f32>(3.0, -1.0),
+        vec2<f32>(-1.0, 3.0),
+    );
+    var out: BackgroundOutput;
+    out.position = vec4<f32>(positions[index], 1.0, 1.0);
+    out.height = positions[index].y * 0.5 + 0.5;
+    return out;
+}
+
+@fragment
+fn fs_background(input: BackgroundOutput) -> @location(0) vec4<f32> {
+    let color = mix(uniforms.background_bottom.rgb, uniforms.background_top.rgb, input.height);
+    return vec4<f32>(color, 1.0);
+}
+
+struct ContactOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) floor_pos: vec2<f32>,
+    @location(1) @interpolate(flat) softness: f32,
+};
+
+fn contact_softness() -> f32 {
+    let size = uniforms.contact_footprint.zw - uniforms.contact_footprint.xy;
+    let lift = max(uniforms.contact_params.x, 0.0);
+    return max(max(size.x, size.y) * 0.1, 0.05) + lift * 0.5;
+}
+
+// Quad on y = 0 around the footprint, grown enough to hold the blur.
+@vertex
+fn vs_contact(@builtin(vertex_index) index: u32) -> ContactOutput {
+    var corners = array<vec2<f32>, 6>(
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 0.0),
+        vec2<f32>(1.0, 1.0),
+        vec2<f32>(0.0, 1.0),
+    );
+    let softness = contact_softness();
+    let min_xz = uniforms.contact_footprint.xy - vec2<f32>(softness * 2.0);
+    let max_xz = uniforms.contact_footprint.zw + vec2<f32>(softness * 2.0);
+    let floor_pos = mix(min_xz, max_xz, corners[index]);
+    var out: ContactOutput;
+    let world = vec3<f32>(floor_pos.x, 0.0, floor_pos.y);
+    out.position = uniforms.view_proj * vec4<f32>(world - uniforms.render_origin, 1.0);
+    out.floor_pos = floor_pos;
+    out.softness = softness;
+    return out;
+}
+
+@fragment
+fn fs_contact(input: ContactOutput) -> @location(0) vec4<f32> {
+    let center = (uniforms.contact_footprint.xy + uniforms.contact_footprint.zw) * 0.5;
+    let half_size = (uniforms.contact_footprint.zw - uniforms.contact_footprint.xy) * 0.5;
+    let q = abs(input.floor_pos - center) - half_size;
+    let dist = length(max(q, vec2<f32>(0.0))) + min(max(q.x, q.y), 0.0);
+    let coverage = 1.0 - smoothstep(-input.softness, input.softness, dist);
+    // Objects lifted off the floor cast a fainter shadow.
+    let size = max(max(half_size.x, half_size.y) * 2.0, 0.001);
+    let fade = clamp(1.0 - max(uniforms.contact_params.x, 0.0) / size, 0.0, 1.0);
+    return vec4<f32>(0.0, 0.0, 0.0, coverage * fade * uniforms.contact_params.y);
+}
+
+struct IdInput {
+    @location(0) position: vec3<f32>,
+    @location(1) id: u32,
+};
+
+struct IdOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) world_pos: vec3<f32>,
+    @location(1) @interpolate(flat) id: u32,
+};
+
+@vertex
+fn vs_id(input: IdInput) -> IdOutput {
+    var out: IdOutput;
+    let local_pos = input.position - uniforms.render_origin;
+    out.world_pos = local_pos;
+    out.id = input.id;
+    out.position = uniforms.view_proj * vec4<f32>(local_pos, 1.0);
+    return out;
+}
+
+// Mirrors the discards in fs_main so ids match what is visible.
+@fragment
+fn fs_id(input: IdOutput, @builtin(front_facing) front_facing: bool) -> @location(0) u32 {
+    let cull = i32(uniforms.view_params.x + 0.5);
+    if (cull == 1 && !front_facing) || (cull == 2 && front_facing) {
+        discard;
+    }
+    let plane_dist = dot(uniforms.clip_plane.xyz, input.world_pos) - uniforms.clip_plane.w;
+    if uniforms.clip_params.x >= 0.5 && plane_dist > 0.0 {
+        discard;
+    }
+    if uniforms.clip_params.y > 0.0
+        && distance(uniforms.camera_pos, input.world_pos) < uniforms.clip_params.y {
+        discard;
+    }
+    return input.id;
+}
+
+struct ShadowOutput {
+    @builtin(position) position: vec4<f32>,
+};
+
+@vertex
+fn vs_shadow(input: VertexInput) -> ShadowOutput {
+    var out: ShadowOutput;
+    out.position = uniforms.light_view_proj * vec4<f32>(input.position - uniforms.render_origin, 1.0);
+    return out;
+}
+
+struct LineInput {
+    @location(0) position: vec3<f32>,
+    @location(1) color: vec3<f32>,
+};
+
+struct LineOutput {
+    @builtin(position) position: vec4<f32>,
+    @location(0) color: vec3<f32>,
+};
+
+@vertex
+fn vs_line(input: LineInput) -> LineOutput {
+    var out: LineOutput;
+    out.position = uniforms.view_proj * vec4<f32>(input.position - uniforms.render_origin, 1.0);
+    out.color = input.color;
+    return out;
+}
+
+@fragment
+fn fs_line(input: LineOutput) -> @location(0) vec4<f32> {
+    return vec4<f32>(input.color, 1.0);
+}